@@ -0,0 +1,110 @@
+//! Baseline benchmarks for parse_code and extract_enhanced_shape
+//!
+//! Each language is measured on generated 1 000- and 10 000-line files, with
+//! throughput reported in bytes.
+//!
+//! Run with: cargo bench --bench parse_benchmark
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use treesitter_mcp::analysis::shape::extract_enhanced_shape;
+use treesitter_mcp::parser::{parse_code, Language};
+
+const LINE_COUNTS: [usize; 2] = [1_000, 10_000];
+
+/// Ten-line declaration template per language; `{n}` keeps names unique.
+fn template(language: Language) -> &'static str {
+    match language {
+        Language::Rust => {
+            "/// Item {n}\npub struct Item{n} {\n    pub id: u64,\n}\n\nimpl Item{n} {\n    pub fn total(&self, x: u64) -> u64 {\n        self.id + x\n    }\n}\n"
+        }
+        Language::Python => {
+            "class Item{n}:\n    \"\"\"Item {n}\"\"\"\n\n    def __init__(self, id):\n        self.id = id\n\n    def total(self, x):\n        return self.id + x\n\n\n"
+        }
+        Language::JavaScript => {
+            "/** Item {n} */\nclass Item{n} {\n  constructor(id) {\n    this.id = id;\n  }\n\n  total(x) {\n    return this.id + x;\n  }\n}\n"
+        }
+        Language::TypeScript => {
+            "/** Item {n} */\nexport class Item{n} {\n  constructor(private id: number) {}\n\n  total(x: number): number {\n    return this.id + x;\n  }\n}\n\nexport type Id{n} = number;\n"
+        }
+        Language::Go => {
+            "// Item{n} is an item.\ntype Item{n} struct {\n\tID int\n}\n\n// Total adds x.\nfunc (i *Item{n}) Total(x int) int {\n\treturn i.ID + x\n}\n\n"
+        }
+        Language::Java => {
+            "/** Item {n} */\nclass Item{n} {\n    private int id;\n\n    int total(int x) {\n        return id + x;\n    }\n}\n\n\n"
+        }
+        Language::CSharp => {
+            "/// <summary>Item {n}</summary>\npublic class Item{n}\n{\n    public int Id { get; set; }\n\n    public int Total(int x)\n    {\n        return Id + x;\n    }\n}\n"
+        }
+        _ => unreachable!("no template for {language:?}"),
+    }
+}
+
+fn generate_source(language: Language, lines: usize) -> String {
+    let template = template(language);
+    let template_lines = template.lines().count();
+    let mut source = match language {
+        Language::Go => "package bench\n\n".to_string(),
+        _ => String::new(),
+    };
+    for n in 0..lines.div_ceil(template_lines) {
+        source.push_str(&template.replace("{n}", &n.to_string()));
+    }
+    source
+}
+
+const LANGUAGES: [Language; 7] = [
+    Language::Rust,
+    Language::Python,
+    Language::JavaScript,
+    Language::TypeScript,
+    Language::Go,
+    Language::Java,
+    Language::CSharp,
+];
+
+fn bench_parse_code(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_code");
+
+    for language in LANGUAGES {
+        for lines in LINE_COUNTS {
+            let source = generate_source(language, lines);
+            group.throughput(Throughput::Bytes(source.len() as u64));
+            group.bench_with_input(
+                BenchmarkId::new(language.name(), lines),
+                &source,
+                |b, source| {
+                    b.iter(|| parse_code(black_box(source), language).unwrap());
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_extract_enhanced_shape(c: &mut Criterion) {
+    let mut group = c.benchmark_group("extract_enhanced_shape");
+
+    for language in LANGUAGES {
+        for lines in LINE_COUNTS {
+            let source = generate_source(language, lines);
+            let tree = parse_code(&source, language).unwrap();
+            group.throughput(Throughput::Bytes(source.len() as u64));
+            group.bench_with_input(
+                BenchmarkId::new(language.name(), lines),
+                &source,
+                |b, source| {
+                    b.iter(|| {
+                        extract_enhanced_shape(&tree, black_box(source), language, None, true)
+                            .unwrap()
+                    });
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse_code, bench_extract_enhanced_shape);
+criterion_main!(benches);
@@ -0,0 +1,63 @@
+//! Fuzz tree-sitter query compilation for every supported language.
+//!
+//! `query_pattern` compiles user-supplied (often LLM-generated) queries, so
+//! `Query::new` must return `Err` for malformed input rather than panic.
+//!
+//! Run with: cargo fuzz run fuzz_query -- -max_total_time=60
+
+#![no_main]
+
+use std::panic::{self, AssertUnwindSafe};
+
+use libfuzzer_sys::fuzz_target;
+use tree_sitter::Query;
+use treesitter_mcp::parser::Language;
+
+const LANGUAGES: [Language; 10] = [
+    Language::Rust,
+    Language::Python,
+    Language::JavaScript,
+    Language::TypeScript,
+    Language::Html,
+    Language::Css,
+    Language::Swift,
+    Language::CSharp,
+    Language::Java,
+    Language::Go,
+];
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(query_src) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    for language in LANGUAGES {
+        let grammar = language.tree_sitter_language();
+        let compiled = panic::catch_unwind(AssertUnwindSafe(|| Query::new(&grammar, query_src)));
+
+        match compiled {
+            Err(_) => panic!(
+                "Query::new panicked for {} on {query_src:?}",
+                language.name()
+            ),
+            Ok(Ok(query)) => {
+                for pattern in 0..query.pattern_count() {
+                    let start = query.start_byte_for_pattern(pattern);
+                    assert!(
+                        start <= query_src.len(),
+                        "{} pattern {pattern} starts past the end of {query_src:?}",
+                        language.name()
+                    );
+                }
+            }
+            Ok(Err(error)) => {
+                assert!(
+                    error.offset <= query_src.len(),
+                    "{} reported error offset {} past the end of {query_src:?}",
+                    language.name(),
+                    error.offset
+                );
+            }
+        }
+    }
+});
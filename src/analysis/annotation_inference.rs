@@ -0,0 +1,434 @@
+//! Infer Types Tool
+//!
+//! Suggests return type annotations for unannotated Python and TypeScript
+//! functions by looking at what their `return` statements return.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "h": "file|line|fn|type|conf",
+//!   "hints": "app.py|3|is_ready|bool|high\n..."
+//! }
+//! ```
+
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{detect_language, parse_code, Language};
+
+const HINT_HEADER: &str = "file|line|fn|type|conf";
+
+/// A suggested return type for a function without one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InferenceHint {
+    pub file: String,
+    pub line: usize,
+    pub function_name: String,
+    pub inferred_return_type: String,
+    /// "high", "medium" or "low"
+    pub confidence: String,
+}
+
+pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    log::info!("Inferring return types in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let hints = infer_return_types(path)?;
+    let rows: Vec<String> = hints
+        .iter()
+        .map(|hint| {
+            let line = hint.line.to_string();
+            format::format_row(&[
+                &hint.file,
+                &line,
+                &hint.function_name,
+                &hint.inferred_return_type,
+                &hint.confidence,
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "h": HINT_HEADER,
+        "hints": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Infer return types for every unannotated Python/TypeScript function under `path`.
+pub fn infer_return_types(path: &Path) -> Result<Vec<InferenceHint>, io::Error> {
+    let files = if path.is_file() {
+        vec![path.to_path_buf()]
+    } else {
+        collect_project_files(path)?
+    };
+
+    let mut hints = Vec::new();
+    for file in files {
+        let language = match detect_language(&file) {
+            Ok(language @ (Language::Python | Language::TypeScript | Language::Tsx)) => language,
+            _ => continue,
+        };
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        let tree = parse_code(&source, language).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse {} code: {e}", language.name()),
+            )
+        })?;
+
+        let relative = path_utils::to_relative_path(&file.to_string_lossy());
+        let mut functions = Vec::new();
+        collect_functions(tree.root_node(), language, &mut functions);
+
+        for function in functions {
+            if let Some((inferred, confidence)) = infer_function(function, &source, language) {
+                hints.push(InferenceHint {
+                    file: relative.clone(),
+                    line: function.start_position().row + 1,
+                    function_name: function_name(function, &source),
+                    inferred_return_type: inferred,
+                    confidence: confidence.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(hints)
+}
+
+fn is_function_node(kind: &str, language: Language) -> bool {
+    match language {
+        Language::Python => kind == "function_definition",
+        _ => matches!(
+            kind,
+            "function_declaration"
+                | "generator_function_declaration"
+                | "method_definition"
+                | "arrow_function"
+                | "function_expression"
+        ),
+    }
+}
+
+/// Unannotated functions, including nested ones.
+fn collect_functions<'a>(node: Node<'a>, language: Language, out: &mut Vec<Node<'a>>) {
+    if is_function_node(node.kind(), language) && node.child_by_field_name("return_type").is_none()
+    {
+        out.push(node);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_functions(child, language, out);
+    }
+}
+
+fn function_name(function: Node, source: &str) -> String {
+    if let Some(name) = function.child_by_field_name("name") {
+        return name.utf8_text(source.as_bytes()).unwrap_or("").to_string();
+    }
+
+    // `const handler = () => ...` names the arrow function through its declarator.
+    function
+        .parent()
+        .filter(|parent| matches!(parent.kind(), "variable_declarator" | "pair"))
+        .and_then(|parent| {
+            parent
+                .child_by_field_name("name")
+                .or_else(|| parent.child_by_field_name("key"))
+        })
+        .and_then(|name| name.utf8_text(source.as_bytes()).ok())
+        .unwrap_or("<anonymous>")
+        .to_string()
+}
+
+/// Return statements belonging to `function`, skipping nested functions.
+fn collect_returns<'a>(node: Node<'a>, language: Language, out: &mut Vec<Node<'a>>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if is_function_node(child.kind(), language) || child.kind() == "lambda" {
+            continue;
+        }
+        if child.kind() == "return_statement" {
+            out.push(child);
+        }
+        collect_returns(child, language, out);
+    }
+}
+
+fn contains_yield(node: Node, language: Language) -> bool {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if is_function_node(child.kind(), language) {
+            continue;
+        }
+        if matches!(child.kind(), "yield" | "yield_expression") || contains_yield(child, language) {
+            return true;
+        }
+    }
+    false
+}
+
+fn infer_function(
+    function: Node,
+    source: &str,
+    language: Language,
+) -> Option<(String, &'static str)> {
+    // TypeScript rejects return annotations on these.
+    if is_constructor_or_setter(function, source) {
+        return None;
+    }
+
+    let body = function.child_by_field_name("body")?;
+
+    // Generators need `Iterator[...]`/`Generator<...>` annotations we cannot guess.
+    if contains_yield(body, language) {
+        return None;
+    }
+
+    let is_async = has_async_keyword(function);
+
+    // Concise arrow bodies return their expression directly.
+    if body.kind() != "statement_block" && body.kind() != "block" {
+        let (ty, confidence) = infer_expression(body, source, language)?;
+        return Some(wrap_async(ty, confidence, is_async, language));
+    }
+
+    let mut returns = Vec::new();
+    collect_returns(body, language, &mut returns);
+
+    let values: Vec<Node> = returns.iter().filter_map(|r| r.named_child(0)).collect();
+    if values.is_empty() {
+        // Stubs (`pass`, `...`, `raise NotImplementedError`) say nothing about the result.
+        if is_stub_body(body) {
+            return None;
+        }
+        let none = match language {
+            Language::Python => "None",
+            _ => "void",
+        };
+        return Some(wrap_async(none.to_string(), "high", is_async, language));
+    }
+
+    let mut types: Vec<String> = Vec::new();
+    let mut confidence = "high";
+    for value in values {
+        let (ty, value_confidence) = infer_expression(value, source, language)?;
+        confidence = weaker(confidence, value_confidence);
+        if !types.contains(&ty) {
+            types.push(ty);
+        }
+    }
+
+    // A bare `return` next to `return value` still yields None/undefined.
+    if returns.len() > types.len() && returns.iter().any(|r| r.named_child(0).is_none()) {
+        let empty = match language {
+            Language::Python => "None",
+            _ => "undefined",
+        };
+        if !types.iter().any(|ty| ty == empty) {
+            types.push(empty.to_string());
+        }
+    }
+
+    if types.len() > 1 {
+        // Optional results are a common, well-understood shape.
+        let nullable = types
+            .iter()
+            .filter(|ty| matches!(ty.as_str(), "None" | "null" | "undefined"))
+            .count();
+        confidence = if types.len() - nullable == 1 {
+            weaker(confidence, "medium")
+        } else {
+            "low"
+        };
+        // Keep null-like members last: `str | None`.
+        types.sort_by_key(|ty| matches!(ty.as_str(), "None" | "null" | "undefined"));
+    }
+
+    Some(wrap_async(
+        types.join(" | "),
+        confidence,
+        is_async,
+        language,
+    ))
+}
+
+fn is_stub_body(body: Node) -> bool {
+    let mut cursor = body.walk();
+    let statements: Vec<Node> = body.named_children(&mut cursor).collect();
+    statements.iter().all(|statement| match statement.kind() {
+        "pass_statement" | "raise_statement" | "throw_statement" | "comment" => true,
+        "expression_statement" => statement
+            .named_child(0)
+            .is_some_and(|expr| matches!(expr.kind(), "ellipsis" | "string")),
+        _ => false,
+    })
+}
+
+fn is_constructor_or_setter(function: Node, source: &str) -> bool {
+    if function.kind() != "method_definition" {
+        return false;
+    }
+    let is_constructor = function
+        .child_by_field_name("name")
+        .and_then(|name| name.utf8_text(source.as_bytes()).ok())
+        == Some("constructor");
+    let mut cursor = function.walk();
+    let is_setter = function
+        .children(&mut cursor)
+        .any(|child| child.kind() == "set");
+    is_constructor || is_setter
+}
+
+fn has_async_keyword(function: Node) -> bool {
+    let mut cursor = function.walk();
+    let found = function
+        .children(&mut cursor)
+        .any(|child| child.kind() == "async");
+    found
+}
+
+fn wrap_async(
+    ty: String,
+    confidence: &'static str,
+    is_async: bool,
+    language: Language,
+) -> (String, &'static str) {
+    // Python coroutines are annotated with their awaited type.
+    if is_async && matches!(language, Language::TypeScript | Language::Tsx) {
+        (format!("Promise<{ty}>"), confidence)
+    } else {
+        (ty, confidence)
+    }
+}
+
+fn weaker(a: &'static str, b: &'static str) -> &'static str {
+    let rank = |c: &str| match c {
+        "high" => 2,
+        "medium" => 1,
+        _ => 0,
+    };
+    if rank(a) <= rank(b) {
+        a
+    } else {
+        b
+    }
+}
+
+fn infer_expression(
+    node: Node,
+    source: &str,
+    language: Language,
+) -> Option<(String, &'static str)> {
+    match language {
+        Language::Python => infer_python_expression(node, source),
+        _ => infer_typescript_expression(node, source),
+    }
+}
+
+fn infer_python_expression(node: Node, source: &str) -> Option<(String, &'static str)> {
+    let simple = |ty: &str, confidence| Some((ty.to_string(), confidence));
+    match node.kind() {
+        "true" | "false" | "comparison_operator" | "not_operator" => simple("bool", "high"),
+        "string" | "concatenated_string" => simple("str", "high"),
+        "integer" => simple("int", "high"),
+        "float" => simple("float", "high"),
+        "none" => simple("None", "high"),
+        "list" | "list_comprehension" => simple("list", "medium"),
+        "dictionary" | "dictionary_comprehension" => simple("dict", "medium"),
+        "set" | "set_comprehension" => simple("set", "medium"),
+        "tuple" => simple("tuple", "medium"),
+        "parenthesized_expression" => infer_python_expression(node.named_child(0)?, source),
+        "call" => {
+            let callee = node.child_by_field_name("function")?;
+            let name = callee.utf8_text(source.as_bytes()).ok()?;
+            match name {
+                "str" | "int" | "float" | "bool" | "list" | "dict" | "set" | "tuple" | "bytes" => {
+                    simple(name, "high")
+                }
+                "len" => simple("int", "high"),
+                "sorted" => simple("list", "medium"),
+                _ if callee.kind() == "identifier"
+                    && name.chars().next().is_some_and(char::is_uppercase) =>
+                {
+                    // Calling a class constructs an instance of it.
+                    simple(name, "medium")
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn infer_typescript_expression(node: Node, source: &str) -> Option<(String, &'static str)> {
+    let simple = |ty: &str, confidence| Some((ty.to_string(), confidence));
+    match node.kind() {
+        "true" | "false" => simple("boolean", "high"),
+        "string" | "template_string" => simple("string", "high"),
+        "number" => simple("number", "high"),
+        "null" => simple("null", "high"),
+        "undefined" => simple("undefined", "high"),
+        "array" => simple("unknown[]", "low"),
+        "object" => simple("object", "low"),
+        "parenthesized_expression" => infer_typescript_expression(node.named_child(0)?, source),
+        "unary_expression" => {
+            let operator = node.child_by_field_name("operator")?.kind();
+            match operator {
+                "!" => simple("boolean", "high"),
+                "-" | "+" | "~" => simple("number", "medium"),
+                "typeof" => simple("string", "high"),
+                _ => None,
+            }
+        }
+        "binary_expression" => {
+            let operator = node.child_by_field_name("operator")?.kind();
+            match operator {
+                "==" | "===" | "!=" | "!==" | "<" | "<=" | ">" | ">=" | "instanceof" | "in" => {
+                    simple("boolean", "high")
+                }
+                "-" | "*" | "/" | "%" | "**" => simple("number", "medium"),
+                _ => None,
+            }
+        }
+        "new_expression" => {
+            let constructor = node.child_by_field_name("constructor")?;
+            let name = constructor.utf8_text(source.as_bytes()).ok()?;
+            simple(name, "high")
+        }
+        _ => None,
+    }
+}
@@ -1,17 +1,23 @@
 use eyre::{Result, WrapErr};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashSet;
 use std::io;
 use std::path::{Path, PathBuf};
 use tree_sitter::Node;
 
-use crate::analysis::path_utils;
+use crate::analysis::file_shape::{find_template_dependencies, resolve_template_dependencies};
+use crate::analysis::{liquid, path_utils};
 use crate::common::format;
 use crate::mcp_types::{CallToolResult, CallToolResultExt};
 use crate::parser::{parse_code, Language};
 
 const MAX_NESTED_DEPTH: u8 = 3;
 
+/// Maximum number of templates visited when following extends/include/import
+const MAX_TEMPLATE_HIERARCHY: usize = 50;
+
 /// MCP tool execute function for template_context
 ///
 /// Compact output schema:
@@ -20,6 +26,9 @@ const MAX_NESTED_DEPTH: u8 = 3;
 /// - `ctx`: newline-delimited rows (pipe-delimited fields)
 /// - `sh`: header for `s` rows
 /// - `s`: struct definition locations (struct|file|line)
+/// - `vh`: header for `vars` rows
+/// - `vars`: variables used by the template and the templates it extends,
+///   includes or imports (name|path|kind|tpl)
 pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
     let template_path_str = arguments["template_path"]
         .as_str()
@@ -33,17 +42,28 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
         template_path = cwd.join(template_path);
     }
 
+    // Liquid sites are rooted at the directory holding `_layouts/`/`_includes/`.
     // Askama templates typically live under `<project>/templates/...`.
     // Our fixture projects don't have a Cargo.toml, so infer the project root
     // from the templates directory when possible.
-    let project_root = find_templates_dir(template_path.parent().unwrap())
-        .and_then(|templates_dir| templates_dir.parent().map(|p| p.to_path_buf()))
+    let project_root = liquid::find_site_root(&template_path)
+        .or_else(|| {
+            find_templates_dir(template_path.parent().unwrap())
+                .and_then(|templates_dir| templates_dir.parent().map(|p| p.to_path_buf()))
+        })
+        .or_else(|| path_utils::find_workspace_root(&template_path))
         .or_else(|| path_utils::find_project_root(&template_path))
         .or_else(|| template_path.parent().map(|p| p.to_path_buf()))
         .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Cannot determine project root"))?;
 
-    let structs = find_askama_structs_for_template(&template_path, &project_root)
-        .map_err(|e| io::Error::other(format!("Failed to find template structs: {e}")))?;
+    let engine = detect_template_engine(&project_root);
+    let structs = match engine {
+        TemplateEngine::Askama => find_askama_structs_for_template(&template_path, &project_root),
+        TemplateEngine::Tera => find_tera_structs_for_template(&template_path, &project_root),
+        // Liquid data comes from front matter and site config, not Rust structs.
+        TemplateEngine::Liquid => Ok(Vec::new()),
+    }
+    .map_err(|e| io::Error::other(format!("Failed to find template structs: {e}")))?;
 
     let tpl_rel = path_utils::to_relative_path(template_path.to_string_lossy().as_ref());
 
@@ -53,12 +73,26 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
     let struct_header = "struct|file|line";
     let struct_rows = template_struct_locations_to_rows(&structs);
 
+    let vars_header = "name|path|kind|tpl";
+    let variables = match engine {
+        TemplateEngine::Liquid => liquid::site_template_variables(&template_path, &project_root)
+            .into_iter()
+            .map(|(template, var)| (template, var.into()))
+            .collect(),
+        TemplateEngine::Askama | TemplateEngine::Tera => {
+            template_hierarchy_variables(&template_path)
+        }
+    };
+    let vars_rows = template_variable_rows(&variables);
+
     let output = serde_json::json!({
         "tpl": tpl_rel,
         "h": ctx_header,
         "ctx": ctx_rows,
         "sh": struct_header,
-        "s": struct_rows
+        "s": struct_rows,
+        "vh": vars_header,
+        "vars": vars_rows
     });
 
     let json_string = serde_json::to_string(&output)
@@ -101,6 +135,206 @@ fn template_struct_locations_to_rows(structs: &[TemplateStructInfo]) -> String {
         .join("\n")
 }
 
+/// Template engine a project renders its templates with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateEngine {
+    /// Structs derive `Template` and name their file in `#[template(path = "...")]`
+    Askama,
+    /// Templates are rendered with `tera.render("name.html", &context)`
+    Tera,
+    /// Jekyll sites and Shopify themes (`_layouts/`, `_includes/`)
+    Liquid,
+}
+
+/// Detect the template engine from the project layout and `Cargo.toml`.
+///
+/// `_layouts/` or `_includes/` directories mean Liquid. Otherwise falls back
+/// to Askama when the manifest is missing or lists neither Rust engine.
+pub fn detect_template_engine(project_root: &Path) -> TemplateEngine {
+    if liquid::is_liquid_site(project_root) {
+        return TemplateEngine::Liquid;
+    }
+
+    let Ok(manifest) = path_utils::read_source_file(project_root.join("Cargo.toml")) else {
+        return TemplateEngine::Askama;
+    };
+    let Ok(document) = toml_edit::Document::parse(manifest) else {
+        return TemplateEngine::Askama;
+    };
+
+    let root = document.as_item();
+    let depends_on = |name: &str| {
+        ["dependencies", "dev-dependencies"]
+            .iter()
+            .filter_map(|table| root.get(table))
+            .chain(root.get("workspace").and_then(|w| w.get("dependencies")))
+            .any(|deps| deps.get(name).is_some())
+    };
+
+    if depends_on("tera") && !depends_on("askama") {
+        TemplateEngine::Tera
+    } else {
+        TemplateEngine::Askama
+    }
+}
+
+fn template_variable_rows(variables: &[(String, TemplateVariable)]) -> String {
+    variables
+        .iter()
+        .map(|(template, var)| {
+            format::format_row(&[
+                var.name.as_str(),
+                var.access_path.as_str(),
+                var.usage_kind.as_str(),
+                template.as_str(),
+            ])
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A variable a template reads from its context
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TemplateVariable {
+    /// Root context variable (e.g., "user")
+    pub name: String,
+
+    /// Full dotted access path (e.g., "user.profile.name")
+    pub access_path: String,
+
+    /// "scalar" (`{{ var }}`), "iterator" (`{% for x in var %}`) or
+    /// "conditional" (`{% if var %}`)
+    pub usage_kind: String,
+}
+
+impl From<liquid::LiquidVar> for TemplateVariable {
+    fn from(var: liquid::LiquidVar) -> Self {
+        Self {
+            name: var.name,
+            access_path: var.access_path,
+            usage_kind: var.usage_kind,
+        }
+    }
+}
+
+/// Extract the context variables a Jinja-style template uses.
+///
+/// Names bound inside the template (loop variables, `set`, macro
+/// parameters) are not context variables and are skipped. Each
+/// (path, kind) pair is reported once, in order of first use.
+pub fn extract_template_variables(source: &str) -> Vec<TemplateVariable> {
+    let path = r"([A-Za-z_]\w*(?:\.\w+)*)";
+    let output_re = Regex::new(&format!(r"\{{\{{-?\s*{path}\s*(::|\()?")).unwrap();
+    let for_re = Regex::new(&format!(r"\{{%-?\s*for\s+([\w\s,()]+?)\s+in\s+{path}")).unwrap();
+    let if_re = Regex::new(&format!(r"\{{%-?\s*(?:if|elif)\s+(?:not\s+)?{path}")).unwrap();
+    let set_re = Regex::new(r"\{%-?\s*set\s+(\w+)").unwrap();
+    let macro_re = Regex::new(r"\{%-?\s*macro\s+\w+\s*\(([^)]*)\)").unwrap();
+
+    let mut bound: Vec<String> = vec!["loop".into(), "self".into(), "super".into()];
+    for cap in for_re.captures_iter(source) {
+        bound.extend(
+            cap[1]
+                .split(|c: char| c == ',' || c == '(' || c == ')' || c.is_whitespace())
+                .filter(|name| !name.is_empty())
+                .map(str::to_string),
+        );
+    }
+    bound.extend(set_re.captures_iter(source).map(|cap| cap[1].to_string()));
+    for cap in macro_re.captures_iter(source) {
+        bound.extend(
+            cap[1]
+                .split(',')
+                .filter_map(|param| param.split('=').next())
+                .map(|param| param.trim().to_string())
+                .filter(|param| !param.is_empty()),
+        );
+    }
+
+    let mut usages: Vec<(usize, String, &str)> = Vec::new();
+    for cap in output_re.captures_iter(source) {
+        // `{{ macros::name(..) }}` and `{{ func(..) }}` are calls, not variables.
+        if cap.get(2).is_none() {
+            usages.push((cap.get(0).unwrap().start(), cap[1].to_string(), "scalar"));
+        }
+    }
+    for cap in for_re.captures_iter(source) {
+        usages.push((cap.get(0).unwrap().start(), cap[2].to_string(), "iterator"));
+    }
+    for cap in if_re.captures_iter(source) {
+        usages.push((
+            cap.get(0).unwrap().start(),
+            cap[1].to_string(),
+            "conditional",
+        ));
+    }
+    usages.sort_by_key(|(offset, _, _)| *offset);
+
+    let mut variables: Vec<TemplateVariable> = Vec::new();
+    for (_, access_path, usage_kind) in usages {
+        let name = access_path
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        if bound.contains(&name) || matches!(name.as_str(), "true" | "false" | "none" | "None") {
+            continue;
+        }
+        let variable = TemplateVariable {
+            name,
+            access_path,
+            usage_kind: usage_kind.to_string(),
+        };
+        if !variables.contains(&variable) {
+            variables.push(variable);
+        }
+    }
+
+    variables
+}
+
+/// Variables used by a template and every template it extends, includes or
+/// imports, tagged with the template (relative to the templates directory)
+/// that uses them.
+pub fn template_hierarchy_variables(template_path: &Path) -> Vec<(String, TemplateVariable)> {
+    let templates_dir = template_path.parent().and_then(find_templates_dir);
+    let display_name = |path: &Path| match &templates_dir {
+        Some(dir) => normalize_template_path(path, dir)
+            .unwrap_or_else(|_| path.to_string_lossy().to_string()),
+        None => path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    };
+
+    let mut queue = vec![template_path.to_path_buf()];
+    let mut visited: Vec<PathBuf> = Vec::new();
+    let mut result = Vec::new();
+
+    while !queue.is_empty() && visited.len() < MAX_TEMPLATE_HIERARCHY {
+        let path = queue.remove(0);
+        if visited.contains(&path) {
+            continue;
+        }
+        visited.push(path.clone());
+
+        let Ok(source) = path_utils::read_source_file(&path) else {
+            continue;
+        };
+        let name = display_name(&path);
+        for variable in extract_template_variables(&source) {
+            result.push((name.clone(), variable));
+        }
+
+        if let Some(dir) = &templates_dir {
+            if let Ok(dependencies) = find_template_dependencies(&source, dir) {
+                queue.extend(dependencies.iter().map(|dep| dir.join(&dep.path)));
+            }
+        }
+    }
+
+    result
+}
+
 /// Information about a struct that serves as a template context
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TemplateStructInfo {
@@ -169,6 +403,11 @@ pub fn find_askama_structs_for_template(
     // Calculate relative template path
     let relative_path = normalize_template_path(template_path, &templates_dir)?;
 
+    // Askama rejects templates that extend or include themselves; report the
+    // cycle instead of a context that could never compile
+    let mut visited = HashSet::new();
+    resolve_template_dependencies(template_path, &templates_dir, &mut visited, &mut Vec::new())?;
+
     // Search all Rust files in project
     let matches = search_rust_files_for_template(&relative_path, project_root)?;
 
@@ -204,11 +443,12 @@ fn search_rust_files_for_template(
 
     // Walk all Rust files in project
     for entry in walkdir::WalkDir::new(project_root)
+        .sort_by_file_name()
         .into_iter()
         .filter_map(|e| e.ok())
     {
         let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) == Some("rs") {
+        if is_rust_source(path) {
             if let Ok(mut file_matches) =
                 extract_template_structs_from_file(path, target_template_path, project_root)
             {
@@ -220,13 +460,26 @@ fn search_rust_files_for_template(
     Ok(matches)
 }
 
+/// `.rs` files that are actually text; build output can leave binaries with
+/// that extension around
+fn is_rust_source(path: &Path) -> bool {
+    if path.extension().and_then(|s| s.to_str()) != Some("rs") {
+        return false;
+    }
+    if path_utils::is_binary_file(path) {
+        log::debug!("Skipping binary file {}", path.display());
+        return false;
+    }
+    true
+}
+
 /// Extract template struct definitions from a single Rust file
 fn extract_template_structs_from_file(
     file_path: &Path,
     target_template_path: &str,
     project_root: &Path,
 ) -> Result<Vec<TemplateStructMatch>> {
-    let source_code = std::fs::read_to_string(file_path)?;
+    let source_code = path_utils::read_source_file(file_path)?;
     let tree = parse_code(&source_code, Language::Rust)?;
 
     let root = tree.root_node();
@@ -437,6 +690,244 @@ fn resolve_type_definition(
     }
 }
 
+/// Find the context structs rendered into a Tera template
+///
+/// Looks for `.render("template.html", &context)` calls and resolves the
+/// context expression to a struct through `let` bindings, function
+/// parameters, struct literals and `Context::from_serialize(..)`.
+pub fn find_tera_structs_for_template(
+    template_path: &Path,
+    project_root: &Path,
+) -> Result<Vec<TemplateStructInfo>> {
+    let templates_dir = find_templates_dir(template_path.parent().unwrap())
+        .ok_or_else(|| eyre::eyre!("Could not find templates directory"))?;
+    let relative_path = normalize_template_path(template_path, &templates_dir)?;
+
+    let mut type_names = Vec::new();
+    for entry in walkdir::WalkDir::new(project_root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !is_rust_source(path) {
+            continue;
+        }
+        let Ok(source_code) = path_utils::read_source_file(path) else {
+            continue;
+        };
+        let Ok(tree) = parse_code(&source_code, Language::Rust) else {
+            continue;
+        };
+        find_tera_render_types(
+            tree.root_node(),
+            &source_code,
+            &relative_path,
+            &mut type_names,
+        );
+    }
+
+    let mut results = Vec::new();
+    for type_name in type_names {
+        if let Some(info) = find_struct_info(&type_name, project_root)? {
+            results.push(info);
+        }
+    }
+
+    Ok(results)
+}
+
+/// Collect context type names from `render("<target>", ctx)` calls
+fn find_tera_render_types(
+    node: Node,
+    source_code: &str,
+    target_template_path: &str,
+    type_names: &mut Vec<String>,
+) {
+    if node.kind() == "call_expression" {
+        if let Some(context) = tera_render_context(node, source_code, target_template_path) {
+            if let Some(type_name) = resolve_context_type(context, source_code, node) {
+                if !type_names.contains(&type_name) {
+                    type_names.push(type_name);
+                }
+            }
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        find_tera_render_types(child, source_code, target_template_path, type_names);
+    }
+}
+
+/// The context argument of a `.render("<target>", ctx)` call
+fn tera_render_context<'a>(
+    call: Node<'a>,
+    source_code: &str,
+    target_template_path: &str,
+) -> Option<Node<'a>> {
+    let function = call.child_by_field_name("function")?;
+    if function.kind() != "field_expression" {
+        return None;
+    }
+    let method = function.child_by_field_name("field")?;
+    if method.utf8_text(source_code.as_bytes()).ok()? != "render" {
+        return None;
+    }
+
+    let arguments = call.child_by_field_name("arguments")?;
+    let mut cursor = arguments.walk();
+    let args: Vec<Node> = arguments.named_children(&mut cursor).collect();
+    let [name, context] = args.as_slice() else {
+        return None;
+    };
+    let name = name
+        .utf8_text(source_code.as_bytes())
+        .ok()?
+        .trim_matches('"');
+
+    (name == target_template_path).then_some(*context)
+}
+
+/// Resolve the struct type behind a context expression
+fn resolve_context_type(expr: Node, source_code: &str, call_site: Node) -> Option<String> {
+    let text = |node: Node| node.utf8_text(source_code.as_bytes()).ok();
+
+    match expr.kind() {
+        "reference_expression" | "try_expression" | "parenthesized_expression" => {
+            resolve_context_type(expr.named_child(0)?, source_code, call_site)
+        }
+        "struct_expression" => {
+            let name = text(expr.child_by_field_name("name")?)?;
+            Some(last_path_segment(name))
+        }
+        "call_expression" => {
+            let function = text(expr.child_by_field_name("function")?)?;
+            if function.ends_with("from_serialize") {
+                let arguments = expr.child_by_field_name("arguments")?;
+                return resolve_context_type(arguments.named_child(0)?, source_code, call_site);
+            }
+            // `Page::new(..)` builds a `Page`; a bare `tera::Context` has no struct.
+            let (owner, _) = function.rsplit_once("::")?;
+            let owner = last_path_segment(owner);
+            (owner != "Context").then_some(owner)
+        }
+        "identifier" => {
+            let name = text(expr)?;
+            binding_type(name, source_code, call_site)
+        }
+        _ => None,
+    }
+}
+
+/// Type of a local `let` binding or parameter visible from `call_site`
+fn binding_type(name: &str, source_code: &str, call_site: Node) -> Option<String> {
+    let text = |node: Node| node.utf8_text(source_code.as_bytes()).ok();
+
+    let mut scope = call_site.parent();
+    while let Some(node) = scope {
+        if node.kind() == "block" {
+            let mut cursor = node.walk();
+            let lets: Vec<Node> = node
+                .named_children(&mut cursor)
+                .filter(|child| {
+                    child.kind() == "let_declaration" && child.start_byte() < call_site.start_byte()
+                })
+                .collect();
+            // Later bindings shadow earlier ones.
+            for binding in lets.into_iter().rev() {
+                let pattern = binding.child_by_field_name("pattern")?;
+                if text(pattern)?.trim_start_matches("mut ") != name {
+                    continue;
+                }
+                if let Some(ty) = binding.child_by_field_name("type") {
+                    return Some(base_struct_name(text(ty)?));
+                }
+                let value = binding.child_by_field_name("value")?;
+                return resolve_context_type(value, source_code, binding);
+            }
+        }
+
+        if node.kind() == "function_item" {
+            let parameters = node.child_by_field_name("parameters")?;
+            let mut cursor = parameters.walk();
+            for parameter in parameters.named_children(&mut cursor) {
+                let Some(pattern) = parameter.child_by_field_name("pattern") else {
+                    continue;
+                };
+                if text(pattern)?.trim_start_matches("mut ") == name {
+                    return Some(base_struct_name(text(
+                        parameter.child_by_field_name("type")?,
+                    )?));
+                }
+            }
+            return None;
+        }
+
+        scope = node.parent();
+    }
+
+    None
+}
+
+fn last_path_segment(path: &str) -> String {
+    path.rsplit("::").next().unwrap_or(path).trim().to_string()
+}
+
+/// `&mut crate::models::Page<'a>` -> `Page`
+fn base_struct_name(type_text: &str) -> String {
+    let stripped = type_text
+        .trim_start_matches('&')
+        .trim_start()
+        .trim_start_matches("mut ")
+        .trim();
+    last_path_segment(&extract_base_type_name(stripped))
+}
+
+/// Locate a struct by name and describe it as a template context
+fn find_struct_info(type_name: &str, project_root: &Path) -> Result<Option<TemplateStructInfo>> {
+    for entry in walkdir::WalkDir::new(project_root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !is_rust_source(path) {
+            continue;
+        }
+        let source_code = path_utils::read_source_file(path)?;
+        let tree = parse_code(&source_code, Language::Rust)?;
+        if let Some(node) = find_struct_node(tree.root_node(), type_name, &source_code) {
+            let fields = extract_struct_fields(node, &source_code, path, project_root, 0)?;
+            return Ok(Some(TemplateStructInfo {
+                struct_name: type_name.to_string(),
+                fields,
+                file_path: path.to_path_buf(),
+                line: node.start_position().row + 1,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+fn find_struct_node<'a>(node: Node<'a>, type_name: &str, source_code: &str) -> Option<Node<'a>> {
+    if node.kind() == "struct_item"
+        && node
+            .child_by_field_name("name")
+            .and_then(|name| name.utf8_text(source_code.as_bytes()).ok())
+            == Some(type_name)
+    {
+        return Some(node);
+    }
+
+    let mut cursor = node.walk();
+    let found = node
+        .children(&mut cursor)
+        .find_map(|child| find_struct_node(child, type_name, source_code));
+    found
+}
+
 /// Find the templates directory by walking up the file system
 ///
 /// Searches for a directory named "templates" starting from the given path,
@@ -521,11 +1012,12 @@ fn find_type_definition(
 ) -> Result<NestedTypeDefinition> {
     // Walk all Rust files looking for struct/enum with this name
     for entry in walkdir::WalkDir::new(project_root)
+        .sort_by_file_name()
         .into_iter()
         .filter_map(|e| e.ok())
     {
         let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) == Some("rs") {
+        if is_rust_source(path) {
             if let Ok(Some(type_def)) =
                 find_type_in_file(path, type_name, project_root, current_depth)
             {
@@ -544,7 +1036,7 @@ fn find_type_in_file(
     project_root: &Path,
     current_depth: u8,
 ) -> Result<Option<NestedTypeDefinition>> {
-    let source_code = std::fs::read_to_string(file_path)?;
+    let source_code = path_utils::read_source_file(file_path)?;
     let tree = parse_code(&source_code, Language::Rust)?;
 
     let root = tree.root_node();
@@ -0,0 +1,552 @@
+//! Build Dependencies Tools
+//!
+//! Lists dependencies declared in Gradle (`build.gradle`, `build.gradle.kts`)
+//! and Maven (`pom.xml`) build files under a project root. When a
+//! `gradle.lockfile` sits next to a Gradle build file, declared versions are
+//! compared with the locked ones.
+//!
+//! Output schema (`list_build_dependencies`):
+//! ```json
+//! {
+//!   "h": "group|artifact|version|scope|src",
+//!   "deps": "com.google.guava|guava|31.1-jre|implementation|build.gradle\n...",
+//!   "dh": "group|artifact|declared|locked|src",
+//!   "drift": "com.google.guava|guava|31.1-jre|32.0.0-jre|gradle.lockfile"
+//! }
+//! ```
+//!
+//! `dh`/`drift` are present only when a `gradle.lockfile` exists. Versions
+//! that are unset, variables (`$kotlinVersion`) or ranges are not compared.
+//!
+//! `package.json` manifests are inspected separately.
+//!
+//! Output schema (`inspect_package_json`):
+//! ```json
+//! {
+//!   "name": "web",
+//!   "version": "1.0.0",
+//!   "h": "name|range|kind|latest",
+//!   "deps": "react|^18.2.0|dependencies|19.0.0\n...",
+//!   "sh": "name|command",
+//!   "scripts": "build|vite build\n..."
+//! }
+//! ```
+//!
+//! The `latest` column is present only with `check_outdated`, and is empty
+//! for packages `npm outdated` does not report.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::analysis::lockfile::LockedDep;
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+
+const DEPS_HEADER: &str = "group|artifact|version|scope|src";
+const DRIFT_HEADER: &str = "group|artifact|declared|locked|src";
+const PACKAGE_DEPS_HEADER: &str = "name|range|kind";
+const SCRIPTS_HEADER: &str = "name|command";
+
+/// `package.json` sections holding dependencies, in output order
+const PACKAGE_DEPENDENCY_KINDS: [&str; 3] = ["dependencies", "devDependencies", "peerDependencies"];
+
+/// Gradle configuration names (or suffixes, e.g. `testImplementation`)
+/// that declare dependencies
+const GRADLE_CONFIGURATIONS: [&str; 10] = [
+    "implementation",
+    "api",
+    "compileonly",
+    "runtimeonly",
+    "compile",
+    "runtime",
+    "annotationprocessor",
+    "kapt",
+    "ksp",
+    "classpath",
+];
+
+/// A dependency declared in a build file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildDep {
+    pub group: String,
+    pub artifact: String,
+    /// Declared version; `None` when managed elsewhere (BOM, parent POM)
+    pub version: Option<String>,
+    /// Gradle configuration or Maven scope (`compile` when omitted)
+    pub scope: String,
+}
+
+/// Dependencies and scripts of a `package.json`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageInfo {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    /// Dependencies in [`PACKAGE_DEPENDENCY_KINDS`] order, sorted by name
+    /// within each kind
+    pub dependencies: Vec<PackageDep>,
+    /// `npm run` targets as `(name, command)`, sorted by name
+    pub scripts: Vec<(String, String)>,
+}
+
+/// A `package.json` dependency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageDep {
+    pub name: String,
+    /// Version range as written, e.g. `^18.2.0` or `workspace:*`
+    pub range: String,
+    /// `dependencies`, `devDependencies` or `peerDependencies`
+    pub kind: String,
+}
+
+/// Versions reported by `npm outdated` for one package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutdatedPackage {
+    pub current: Option<String>,
+    pub wanted: Option<String>,
+    pub latest: Option<String>,
+}
+
+pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let root_str = arguments["project_root"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'project_root' argument",
+        )
+    })?;
+
+    log::info!("Listing build dependencies in: {root_str}");
+
+    let root = Path::new(root_str);
+    if !root.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Project root is not a directory: {root_str}"),
+        ));
+    }
+
+    let mut rows = Vec::new();
+    let mut drift_rows = Vec::new();
+    let mut has_lockfile = false;
+    for file in collect_project_files(root)? {
+        let file_name = file
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+        let deps = match file_name {
+            "build.gradle" | "build.gradle.kts" => parse_gradle(&file),
+            "pom.xml" => parse_maven_pom(&file),
+            _ => continue,
+        };
+        let src = path_utils::to_relative_path(&file.to_string_lossy());
+        for dep in &deps {
+            rows.push(format::format_row(&[
+                &dep.group,
+                &dep.artifact,
+                dep.version.as_deref().unwrap_or(""),
+                &dep.scope,
+                &src,
+            ]));
+        }
+
+        let lockfile = file.with_file_name("gradle.lockfile");
+        if file_name.starts_with("build.gradle") && lockfile.is_file() {
+            has_lockfile = true;
+            let lock_src = path_utils::to_relative_path(&lockfile.to_string_lossy());
+            for (dep, locked) in version_drift(&deps, &parse_gradle_lockfile(&lockfile)) {
+                drift_rows.push(format::format_row(&[
+                    &dep.group,
+                    &dep.artifact,
+                    dep.version.as_deref().unwrap_or(""),
+                    &locked.version,
+                    &lock_src,
+                ]));
+            }
+        }
+    }
+
+    let mut result = json!({
+        "h": DEPS_HEADER,
+        "deps": rows.join("\n"),
+    });
+    if has_lockfile {
+        result["dh"] = json!(DRIFT_HEADER);
+        result["drift"] = json!(drift_rows.join("\n"));
+    }
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Parse the dependency declarations of a Groovy or Kotlin DSL build script.
+///
+/// Recognizes string notation (`implementation 'g:a:v'`,
+/// `implementation("g:a:v")`, also inside `platform(...)`) and map notation
+/// (`implementation group: 'g', name: 'a', version: 'v'` or the Kotlin
+/// `group = "g", name = "a", version = "v"` form). Unreadable files yield an
+/// empty list.
+pub fn parse_gradle(path: &Path) -> Vec<BuildDep> {
+    let Ok(text) = path_utils::read_source_file(path) else {
+        return Vec::new();
+    };
+
+    let string_re = Regex::new(
+        r#"^\s*(\w+)\s*\(?\s*(?:(?:enforcedPlatform|platform)\s*\(\s*)?["']([^"':\s]+):([^"':\s]+)(?::([^"':\s]+))?(?::[^"'\s]*)?["']"#,
+    )
+    .unwrap();
+    let map_re = Regex::new(r#"^\s*(\w+)\s*\(?\s*group\s*[:=]\s*["']([^"']+)["']"#).unwrap();
+    let name_re = Regex::new(r#"\bname\s*[:=]\s*["']([^"']+)["']"#).unwrap();
+    let version_re = Regex::new(r#"\bversion\s*[:=]\s*["']([^"']+)["']"#).unwrap();
+
+    let mut deps = Vec::new();
+    for line in text.lines() {
+        if let Some(captures) = string_re.captures(line) {
+            if is_gradle_configuration(&captures[1]) {
+                deps.push(BuildDep {
+                    group: captures[2].to_string(),
+                    artifact: captures[3].to_string(),
+                    version: captures.get(4).map(|version| version.as_str().to_string()),
+                    scope: captures[1].to_string(),
+                });
+            }
+            continue;
+        }
+
+        let Some(captures) = map_re.captures(line) else {
+            continue;
+        };
+        let Some(artifact) = name_re.captures(line) else {
+            continue;
+        };
+        if is_gradle_configuration(&captures[1]) {
+            deps.push(BuildDep {
+                group: captures[2].to_string(),
+                artifact: artifact[1].to_string(),
+                version: version_re
+                    .captures(line)
+                    .map(|version| version[1].to_string()),
+                scope: captures[1].to_string(),
+            });
+        }
+    }
+    deps
+}
+
+/// Parse the `<dependencies>` of a Maven POM.
+///
+/// `${property}` versions are resolved from `<properties>`, and versions
+/// missing on a dependency are taken from `<dependencyManagement>` when
+/// declared there. Dependencies of build plugins are ignored. Unreadable
+/// files yield an empty list.
+pub fn parse_maven_pom(path: &Path) -> Vec<BuildDep> {
+    let Ok(text) = path_utils::read_source_file(path) else {
+        return Vec::new();
+    };
+
+    let comment_re = Regex::new(r"(?s)<!--.*?-->").unwrap();
+    let build_re = Regex::new(r"(?s)<build>.*?</build>").unwrap();
+    let management_re =
+        Regex::new(r"(?s)<dependencyManagement>(.*?)</dependencyManagement>").unwrap();
+    let text = comment_re.replace_all(&text, "");
+    let text = build_re.replace_all(&text, "");
+
+    let properties = maven_properties(&text);
+    let managed: HashMap<(String, String), String> = management_re
+        .captures(&text)
+        .map(|captures| maven_dependencies(&captures[1], &properties))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|dep| Some(((dep.group, dep.artifact), dep.version?)))
+        .collect();
+
+    let text = management_re.replace_all(&text, "");
+    let mut deps = maven_dependencies(&text, &properties);
+    for dep in &mut deps {
+        if dep.version.is_none() {
+            dep.version = managed
+                .get(&(dep.group.clone(), dep.artifact.clone()))
+                .cloned();
+        }
+    }
+    deps
+}
+
+/// Parse a Gradle `gradle.lockfile`.
+///
+/// Entries are `group:artifact:version=configurations`; dependencies are
+/// named `group:artifact`. Comments and the `empty=` line are skipped.
+pub fn parse_gradle_lockfile(path: &Path) -> Vec<LockedDep> {
+    let Ok(text) = path_utils::read_source_file(path) else {
+        return Vec::new();
+    };
+
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let coordinates = line.split('=').next()?;
+            let (name, version) = coordinates.rsplit_once(':')?;
+            name.contains(':').then(|| LockedDep {
+                name: name.to_string(),
+                version: version.to_string(),
+                checksum: None,
+            })
+        })
+        .collect()
+}
+
+/// Declared dependencies whose pinned version differs from the lockfile.
+///
+/// Lockfile entries are named `group:artifact`, as in `gradle.lockfile`.
+pub fn version_drift<'a>(
+    declared: &'a [BuildDep],
+    locked: &'a [LockedDep],
+) -> Vec<(&'a BuildDep, &'a LockedDep)> {
+    declared
+        .iter()
+        .filter(|dep| dep.version.as_deref().is_some_and(is_exact_version))
+        .filter_map(|dep| {
+            let name = format!("{}:{}", dep.group, dep.artifact);
+            let locked = locked.iter().find(|locked| locked.name == name)?;
+            (dep.version.as_deref() != Some(locked.version.as_str())).then_some((dep, locked))
+        })
+        .collect()
+}
+
+fn is_gradle_configuration(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    GRADLE_CONFIGURATIONS
+        .iter()
+        .any(|configuration| name.ends_with(configuration))
+}
+
+/// Versions such as `$kotlinVersion`, `1.+` or `[1.0,2.0)` are not exact.
+fn is_exact_version(version: &str) -> bool {
+    !version.is_empty() && !version.contains(['$', '+', '[', '(', ',', ' '])
+}
+
+fn maven_properties(text: &str) -> HashMap<String, String> {
+    let section_re = Regex::new(r"(?s)<properties>(.*?)</properties>").unwrap();
+    let property_re = Regex::new(r"<([\w.\-]+)>\s*([^<]*?)\s*</([\w.\-]+)>").unwrap();
+
+    let Some(section) = section_re.captures(text) else {
+        return HashMap::new();
+    };
+    property_re
+        .captures_iter(&section[1])
+        .filter(|captures| captures[1] == captures[3])
+        .map(|captures| (captures[1].to_string(), captures[2].to_string()))
+        .collect()
+}
+
+fn maven_dependencies(text: &str, properties: &HashMap<String, String>) -> Vec<BuildDep> {
+    let dependency_re = Regex::new(r"(?s)<dependency>(.*?)</dependency>").unwrap();
+    let exclusions_re = Regex::new(r"(?s)<exclusions>.*?</exclusions>").unwrap();
+    let property_ref_re = Regex::new(r"\$\{([^}]+)\}").unwrap();
+
+    let resolve = |value: &str| {
+        property_ref_re
+            .replace_all(value, |captures: &regex::Captures| {
+                properties
+                    .get(&captures[1])
+                    .cloned()
+                    .unwrap_or_else(|| captures[0].to_string())
+            })
+            .into_owned()
+    };
+
+    dependency_re
+        .captures_iter(text)
+        .filter_map(|captures| {
+            let block = exclusions_re.replace_all(&captures[1], "");
+            Some(BuildDep {
+                group: resolve(&xml_tag(&block, "groupId")?),
+                artifact: resolve(&xml_tag(&block, "artifactId")?),
+                version: xml_tag(&block, "version").map(|version| resolve(&version)),
+                scope: xml_tag(&block, "scope").unwrap_or_else(|| "compile".to_string()),
+            })
+        })
+        .collect()
+}
+
+fn xml_tag(block: &str, tag: &str) -> Option<String> {
+    let start = block.find(&format!("<{tag}>"))? + tag.len() + 2;
+    let end = start + block[start..].find(&format!("</{tag}>"))?;
+    Some(block[start..end].trim().to_string())
+}
+
+pub fn execute_inspect_package_json(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+    let check_outdated = arguments["check_outdated"].as_bool().unwrap_or(false);
+
+    log::info!("Inspecting package.json: {path_str} (check_outdated: {check_outdated})");
+
+    let path = Path::new(path_str);
+    let manifest: PathBuf = if path.is_dir() {
+        path.join("package.json")
+    } else {
+        path.to_path_buf()
+    };
+    if !manifest.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("package.json not found: {}", manifest.display()),
+        ));
+    }
+
+    let info = parse_package_json(&manifest);
+    let outdated = if check_outdated {
+        Some(npm_outdated(manifest.parent().unwrap_or(Path::new(".")))?)
+    } else {
+        None
+    };
+
+    let deps: Vec<String> = info
+        .dependencies
+        .iter()
+        .map(|dep| {
+            let mut fields = vec![dep.name.as_str(), dep.range.as_str(), dep.kind.as_str()];
+            if let Some(outdated) = &outdated {
+                fields.push(
+                    outdated
+                        .get(&dep.name)
+                        .and_then(|package| package.latest.as_deref())
+                        .unwrap_or(""),
+                );
+            }
+            format::format_row(&fields)
+        })
+        .collect();
+    let scripts: Vec<String> = info
+        .scripts
+        .iter()
+        .map(|(name, command)| format::format_row(&[name, command]))
+        .collect();
+
+    let header = if outdated.is_some() {
+        format!("{PACKAGE_DEPS_HEADER}|latest")
+    } else {
+        PACKAGE_DEPS_HEADER.to_string()
+    };
+    let mut result = json!({
+        "h": header,
+        "deps": deps.join("\n"),
+        "sh": SCRIPTS_HEADER,
+        "scripts": scripts.join("\n"),
+    });
+    if let Some(name) = &info.name {
+        result["name"] = json!(name);
+    }
+    if let Some(version) = &info.version {
+        result["version"] = json!(version);
+    }
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Parse the dependencies and scripts of a `package.json`.
+///
+/// Unreadable or malformed manifests yield an empty [`PackageInfo`].
+pub fn parse_package_json(path: &Path) -> PackageInfo {
+    let Ok(text) = path_utils::read_source_file(path) else {
+        return PackageInfo::default();
+    };
+    let Ok(manifest) = serde_json::from_str::<Value>(&text) else {
+        return PackageInfo::default();
+    };
+
+    let string_entries = |section: &str| -> Vec<(String, String)> {
+        manifest[section]
+            .as_object()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    PackageInfo {
+        name: manifest["name"].as_str().map(str::to_string),
+        version: manifest["version"].as_str().map(str::to_string),
+        dependencies: PACKAGE_DEPENDENCY_KINDS
+            .iter()
+            .flat_map(|kind| {
+                string_entries(kind)
+                    .into_iter()
+                    .map(|(name, range)| PackageDep {
+                        name,
+                        range,
+                        kind: kind.to_string(),
+                    })
+            })
+            .collect(),
+        scripts: string_entries("scripts"),
+    }
+}
+
+/// Parse the JSON printed by `npm outdated --json`.
+///
+/// Packages reported once per workspace (an array of entries) use the first
+/// entry. Empty or malformed output yields an empty map.
+pub fn parse_npm_outdated(output: &str) -> HashMap<String, OutdatedPackage> {
+    let Ok(Value::Object(packages)) = serde_json::from_str::<Value>(output) else {
+        return HashMap::new();
+    };
+
+    packages
+        .into_iter()
+        .filter_map(|(name, entry)| {
+            let entry = match entry {
+                Value::Array(entries) => entries.into_iter().next()?,
+                entry => entry,
+            };
+            let field = |key: &str| entry[key].as_str().map(str::to_string);
+            Some((
+                name,
+                OutdatedPackage {
+                    current: field("current"),
+                    wanted: field("wanted"),
+                    latest: field("latest"),
+                },
+            ))
+        })
+        .collect()
+}
+
+fn npm_outdated(dir: &Path) -> Result<HashMap<String, OutdatedPackage>, io::Error> {
+    // `npm outdated` exits with status 1 when anything is outdated, so only
+    // a failure to launch is an error.
+    let output = Command::new("npm")
+        .args(["outdated", "--json"])
+        .current_dir(dir)
+        .output()
+        .map_err(|e| io::Error::other(format!("Failed to execute npm: {e}")))?;
+
+    Ok(parse_npm_outdated(&String::from_utf8_lossy(&output.stdout)))
+}
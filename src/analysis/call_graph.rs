@@ -1,7 +1,6 @@
 //! Compact best-effort call graph extraction.
 
-use std::collections::{HashSet, VecDeque};
-use std::fs;
+use std::collections::{BTreeSet, HashSet, VecDeque};
 use std::io;
 use std::path::{Path, PathBuf};
 
@@ -13,10 +12,11 @@ use crate::analysis::path_utils;
 use crate::analysis::shape::{
     extract_enhanced_shape, EnhancedFileShape, EnhancedFunctionInfo, MethodInfo,
 };
+use crate::common::cancel::CancellationToken;
 use crate::common::format;
 use crate::common::project_files::collect_project_files;
 use crate::mcp_types::{CallToolResult, CallToolResultExt};
-use crate::parser::{detect_language, parse_code, Language};
+use crate::parser::{cache, detect_language, Language};
 
 const EDGE_HEADER: &str = "direction|symbol|file|line|scope|depth";
 const DEFAULT_MAX_TOKENS: usize = 2000;
@@ -48,8 +48,17 @@ struct Edge {
     depth: usize,
 }
 
-/// Return a compact caller/callee graph for one symbol.
+/// Compatibility wrapper for callers that never cancel.
+#[allow(dead_code)]
 pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    execute_with_cancel(arguments, &CancellationToken::default())
+}
+
+/// Return a compact caller/callee graph for one symbol.
+pub fn execute_with_cancel(
+    arguments: &Value,
+    cancel: &CancellationToken,
+) -> Result<CallToolResult, io::Error> {
     let file_path = arguments["file_path"].as_str().ok_or_else(|| {
         io::Error::new(
             io::ErrorKind::InvalidInput,
@@ -84,11 +93,12 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
         ));
     }
 
-    let root = path_utils::find_project_root(&target_path)
+    let root = path_utils::find_workspace_root(&target_path)
+        .or_else(|| path_utils::find_project_root(&target_path))
         .or_else(|| target_path.parent().map(Path::to_path_buf))
         .unwrap_or_else(|| PathBuf::from("."));
     let files = collect_supported_files(&root)?;
-    let definitions = collect_definitions(&files)?;
+    let definitions = collect_definitions(&files, cancel)?;
     let target = find_target_definition(&definitions, &target_path, symbol).ok_or_else(|| {
         io::Error::new(
             io::ErrorKind::NotFound,
@@ -101,7 +111,7 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
         collect_callee_edges(&target, &definitions, depth, &mut edges)?;
     }
     if matches!(direction, Direction::Callers | Direction::Both) {
-        collect_caller_edges(&target, &files, &definitions, depth, &mut edges)?;
+        collect_caller_edges(&target, &files, &definitions, depth, cancel, &mut edges)?;
     }
 
     edges.sort_by(|a, b| {
@@ -154,9 +164,13 @@ fn collect_supported_files(root: &Path) -> Result<Vec<PathBuf>, io::Error> {
         .collect())
 }
 
-fn collect_definitions(files: &[PathBuf]) -> Result<Vec<SymbolDef>, io::Error> {
+fn collect_definitions(
+    files: &[PathBuf],
+    cancel: &CancellationToken,
+) -> Result<Vec<SymbolDef>, io::Error> {
     let mut definitions = Vec::new();
-    for file in files {
+    for (index, file) in files.iter().enumerate() {
+        cancel.check(index)?;
         let Ok((shape, _tree, _source, _language)) = parse_shape(file) else {
             continue;
         };
@@ -166,7 +180,7 @@ fn collect_definitions(files: &[PathBuf]) -> Result<Vec<SymbolDef>, io::Error> {
 }
 
 fn parse_shape(path: &Path) -> Result<(EnhancedFileShape, Tree, String, Language), io::Error> {
-    let source = fs::read_to_string(path).map_err(|e| {
+    let source = path_utils::read_source_file(path).map_err(|e| {
         io::Error::new(
             io::ErrorKind::NotFound,
             format!("Failed to read file {}: {e}", path.display()),
@@ -178,12 +192,15 @@ fn parse_shape(path: &Path) -> Result<(EnhancedFileShape, Tree, String, Language
             format!("Cannot detect language for file {}: {e}", path.display()),
         )
     })?;
-    let tree = parse_code(&source, language).map_err(|e| {
-        io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("Failed to parse {} code: {e}", language.name()),
-        )
-    })?;
+    let parsed = cache::shared()
+        .parse(path, &source, language)
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse {} code: {e}", language.name()),
+            )
+        })?;
+    let tree = parsed.0.clone();
     let shape = extract_enhanced_shape(&tree, &source, language, path.to_str(), false)?;
     Ok((shape, tree, source, language))
 }
@@ -191,7 +208,7 @@ fn parse_shape(path: &Path) -> Result<(EnhancedFileShape, Tree, String, Language
 fn definitions_from_shape(file: &Path, shape: &EnhancedFileShape) -> Vec<SymbolDef> {
     let mut definitions = Vec::new();
 
-    for function in &shape.functions {
+    for function in shape.functions.iter().chain(&shape.components) {
         definitions.push(def_from_function(file, function, ""));
     }
 
@@ -297,6 +314,7 @@ fn collect_caller_edges(
     files: &[PathBuf],
     definitions: &[SymbolDef],
     max_depth: usize,
+    cancel: &CancellationToken,
     edges: &mut Vec<Edge>,
 ) -> Result<(), io::Error> {
     let mut queue = VecDeque::from([(target.clone(), 1usize)]);
@@ -307,7 +325,7 @@ fn collect_caller_edges(
             continue;
         }
 
-        for caller in callers_for_symbol(&current.name, files, definitions)? {
+        for caller in callers_for_symbol(&current.name, files, definitions, cancel)? {
             edges.push(edge("caller", &caller, depth));
             if depth < max_depth {
                 queue.push_back((caller, depth + 1));
@@ -318,7 +336,7 @@ fn collect_caller_edges(
     Ok(())
 }
 
-fn called_names_for_symbol(symbol: &SymbolDef) -> Result<HashSet<String>, io::Error> {
+fn called_names_for_symbol(symbol: &SymbolDef) -> Result<BTreeSet<String>, io::Error> {
     let (_shape, tree, source, language) = parse_shape(&symbol.file)?;
     Ok(collect_called_names(
         &tree,
@@ -333,10 +351,12 @@ fn callers_for_symbol(
     symbol_name: &str,
     files: &[PathBuf],
     definitions: &[SymbolDef],
+    cancel: &CancellationToken,
 ) -> Result<Vec<SymbolDef>, io::Error> {
     let mut callers = Vec::new();
 
-    for file in files {
+    for (index, file) in files.iter().enumerate() {
+        cancel.check(index)?;
         let Ok((_shape, tree, source, language)) = parse_shape(file) else {
             continue;
         };
@@ -404,8 +424,8 @@ fn collect_called_names(
     language: Language,
     start_line: usize,
     end_line: usize,
-) -> HashSet<String> {
-    let mut names = HashSet::new();
+) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
     collect_called_names_from_node(
         tree.root_node(),
         source,
@@ -423,7 +443,7 @@ fn collect_called_names_from_node(
     language: Language,
     start_row: usize,
     end_row: usize,
-    names: &mut HashSet<String>,
+    names: &mut BTreeSet<String>,
 ) {
     let node_start = node.start_position().row;
     let node_end = node.end_position().row;
@@ -473,17 +493,21 @@ fn collect_call_sites_from_node(
     }
 }
 
-fn is_call_node(kind: &str, language: Language) -> bool {
+pub(crate) fn is_call_node(kind: &str, language: Language) -> bool {
     match language {
         Language::Rust => matches!(kind, "call_expression" | "method_call_expression"),
         Language::Python => kind == "call",
-        Language::JavaScript | Language::TypeScript | Language::Go => kind == "call_expression",
+        Language::JavaScript
+        | Language::TypeScript
+        | Language::Jsx
+        | Language::Tsx
+        | Language::Go => kind == "call_expression",
         Language::Java | Language::CSharp | Language::Swift => kind.ends_with("invocation"),
-        Language::Html | Language::Css => false,
+        Language::Html | Language::Css | Language::Svelte => false,
     }
 }
 
-fn call_name(node: Node<'_>, source: &str) -> Option<String> {
+pub(crate) fn call_name(node: Node<'_>, source: &str) -> Option<String> {
     for field in ["function", "name", "method", "field"] {
         if let Some(child) = node.child_by_field_name(field) {
             if let Some(name) = last_identifier_text(child, source) {
@@ -7,13 +7,16 @@
 //! - Each file maps to an object with abbreviated keys:
 //!   - `h`: header string (pipe-delimited column names)
 //!   - `f`: functions (newline-delimited rows)
+//!   - `co`: React components of JSX/TSX files (same columns as `f`)
 //!   - `s`: structs (newline-delimited rows)
 //!   - `c`: classes (newline-delimited rows)
 //! - Optional meta is under `@` (e.g. `{ "t": true }` for truncated).
 //! - When `with_types=true`, also includes `types` key with type definitions.
+//! - When `workspace_mode=true`, the top level is keyed by Cargo workspace
+//!   member name, each mapping to the file map above (without `@`). Members
+//!   get a share of `max_tokens` proportional to their lines of code.
 
 use std::cmp::Reverse;
-use std::fs;
 use std::io;
 use std::path::Path;
 
@@ -25,13 +28,14 @@ use tiktoken_rs::cl100k_base;
 
 use crate::analysis::path_utils;
 use crate::analysis::shape::{EnhancedClassInfo, EnhancedFunctionInfo, EnhancedStructInfo};
+use crate::analysis::workspace::{list_workspace_members, WorkspaceMember};
 use crate::common::budget;
 use crate::common::budget::BudgetTracker;
 use crate::common::format;
 use crate::common::project_files::collect_project_files;
-use crate::extraction::types::{TypeDefinition, TypeKind};
+use crate::extraction::types::TypeDefinition;
 use crate::mcp_types::{CallToolResult, CallToolResultExt};
-use crate::parser::detect_language;
+use crate::parser::{cache, detect_language};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum DetailLevel {
@@ -50,6 +54,8 @@ impl DetailLevel {
         }
     }
 
+    /// Column names for symbol rows; fields escape `|` as `\|` (see
+    /// `format::escape_pipe`).
     fn header(self) -> &'static str {
         match self {
             DetailLevel::Minimal => "name|line",
@@ -63,6 +69,7 @@ impl DetailLevel {
 struct FileSymbols {
     path: String,
     functions: Vec<Value>,
+    components: Vec<Value>,
     structs: Vec<Value>,
     classes: Vec<Value>,
 }
@@ -94,6 +101,7 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
     let pattern = arguments["pattern"].as_str();
     let with_types = arguments["with_types"].as_bool().unwrap_or(false);
     let count_usages = arguments["count_usages"].as_bool().unwrap_or(false);
+    let workspace_mode = arguments["workspace_mode"].as_bool().unwrap_or(false);
 
     log::info!(
         "Generating compact code map for: {path_str} (max_tokens: {max_tokens}, detail: {detail_str}, with_types: {with_types})"
@@ -107,6 +115,19 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
         ));
     }
 
+    let members = if workspace_mode {
+        let members = list_workspace_members(path)?;
+        if members.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("No Cargo workspace members found in {path_str}"),
+            ));
+        }
+        members
+    } else {
+        Vec::new()
+    };
+
     let options = ExtractionOptions {
         detail_level,
         with_types,
@@ -152,13 +173,16 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
             .sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
     }
 
-    // Convert all file paths to relative paths
-    for entry in &mut result.files {
-        entry.path = path_utils::to_relative_path(&entry.path);
-    }
+    let result_map = if workspace_mode {
+        build_workspace_output(result, &members, detail_level, max_tokens, with_types)?
+    } else {
+        // Convert all file paths to relative paths
+        for entry in &mut result.files {
+            entry.path = path_utils::to_relative_path(&entry.path);
+        }
 
-    let (result_map, _truncated) =
-        build_compact_output_combined(&result, detail_level, max_tokens, with_types)?;
+        build_compact_output_combined(&result, detail_level, max_tokens, with_types)?.0
+    };
 
     let json_text = serde_json::to_string(&Value::Object(result_map)).map_err(|e| {
         io::Error::new(
@@ -169,6 +193,78 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
 
     Ok(CallToolResult::success(json_text))
 }
+
+/// Split files and types by workspace member and build one budgeted file
+/// map per member.
+///
+/// Files belong to the member with the longest matching directory; files
+/// outside every member are left out.
+fn build_workspace_output(
+    result: ExtractionResult,
+    members: &[WorkspaceMember],
+    detail_level: DetailLevel,
+    max_tokens: usize,
+    with_types: bool,
+) -> Result<Map<String, Value>, io::Error> {
+    let owner = |file: &Path| {
+        members
+            .iter()
+            .enumerate()
+            .filter(|(_, member)| file.starts_with(&member.path))
+            .max_by_key(|(_, member)| member.path.components().count())
+            .map(|(index, _)| index)
+    };
+
+    let mut per_member: Vec<(ExtractionResult, usize)> = members
+        .iter()
+        .map(|_| {
+            let empty = ExtractionResult {
+                files: Vec::new(),
+                types: Vec::new(),
+            };
+            (empty, 0)
+        })
+        .collect();
+
+    for mut entry in result.files {
+        let Some(index) = owner(Path::new(&entry.path)) else {
+            continue;
+        };
+        let lines = path_utils::read_source_file(&entry.path)
+            .map(|source| source.lines().count())
+            .unwrap_or(0);
+        entry.path = path_utils::to_relative_path(&entry.path);
+        per_member[index].0.files.push(entry);
+        per_member[index].1 += lines;
+    }
+    for ty in result.types {
+        if let Some(index) = owner(&ty.file) {
+            per_member[index].0.types.push(ty);
+        }
+    }
+
+    let total_lines: usize = per_member.iter().map(|(_, lines)| lines).sum::<usize>();
+    let mut output = Map::new();
+    let mut truncated = false;
+    for (member, (member_result, lines)) in members.iter().zip(per_member) {
+        if member_result.files.is_empty() {
+            continue;
+        }
+        let member_budget = (max_tokens * lines / total_lines.max(1)).max(1);
+        let (mut member_map, member_truncated) =
+            build_compact_output_combined(&member_result, detail_level, member_budget, with_types)?;
+        member_map.remove("@");
+        truncated |= member_truncated;
+        output.insert(member.name.clone(), Value::Object(member_map));
+    }
+
+    if truncated {
+        output.insert("@".to_string(), json!({"t": true}));
+    }
+
+    Ok(output)
+}
+
 fn build_compact_output_combined(
     result: &ExtractionResult,
     detail_level: DetailLevel,
@@ -287,7 +383,7 @@ fn build_types_output(types: &[TypeDefinition], _max_tokens: usize) -> Value {
         .iter()
         .map(|ty| {
             let file = path_utils::to_relative_path(ty.file.to_string_lossy().as_ref());
-            let kind = type_kind_str(ty.kind);
+            let kind = ty.kind.as_str();
             let fields = [
                 ty.name.as_str(),
                 kind,
@@ -326,21 +422,6 @@ fn shrink_types_output(types_val: &mut Value) -> bool {
     true
 }
 
-fn type_kind_str(kind: TypeKind) -> &'static str {
-    match kind {
-        TypeKind::Interface => "interface",
-        TypeKind::Class => "class",
-        TypeKind::Struct => "struct",
-        TypeKind::Enum => "enum",
-        TypeKind::Trait => "trait",
-        TypeKind::Protocol => "protocol",
-        TypeKind::TypeAlias => "type_alias",
-        TypeKind::Record => "record",
-        TypeKind::TypedDict => "typed_dict",
-        TypeKind::NamedTuple => "named_tuple",
-    }
-}
-
 fn build_compact_file(file: &FileSymbols, detail_level: DetailLevel) -> Value {
     let mut file_obj = Map::new();
     file_obj.insert("h".to_string(), json!(detail_level.header()));
@@ -356,6 +437,17 @@ fn build_compact_file(file: &FileSymbols, detail_level: DetailLevel) -> Value {
         );
     }
 
+    if !file.components.is_empty() {
+        file_obj.insert(
+            "co".to_string(),
+            json!(symbols_to_rows(
+                &file.components,
+                detail_level,
+                SymbolKind::Function,
+            )),
+        );
+    }
+
     if !file.structs.is_empty() {
         file_obj.insert(
             "s".to_string(),
@@ -392,7 +484,7 @@ fn shrink_single_file_to_fit(
 
     // Prefer removing rows from the largest table first.
     let mut candidates: Vec<(&str, usize)> = Vec::new();
-    for key in ["f", "s", "c"] {
+    for key in ["f", "co", "s", "c"] {
         if let Some(rows) = file_obj.get(key).and_then(Value::as_str) {
             let count = if rows.is_empty() {
                 0
@@ -434,8 +526,8 @@ fn shrink_single_file_to_fit(
     let snapshot = Value::Object(file_obj.clone());
     let tmp_json = serde_json::to_string(&json!({"_": snapshot})).unwrap_or_default();
     if bpe.encode_with_special_tokens(&tmp_json).len() > max_tokens {
-        // Prefer dropping `c`, then `s`, then `f`.
-        for drop_key in ["c", "s", "f"] {
+        // Prefer dropping `c`, then `s`, then `f`, then `co`.
+        for drop_key in ["c", "s", "f", "co"] {
             if file_obj.contains_key(drop_key) {
                 file_obj.remove(drop_key);
                 break;
@@ -506,7 +598,7 @@ fn symbols_to_rows(symbols: &[Value], detail_level: DetailLevel, kind: SymbolKin
 }
 
 fn symbol_count(entry: &FileSymbols) -> usize {
-    entry.functions.len() + entry.structs.len() + entry.classes.len()
+    entry.functions.len() + entry.components.len() + entry.structs.len() + entry.classes.len()
 }
 
 fn collect_files_combined(
@@ -537,7 +629,7 @@ fn process_file_combined(
     options: &ExtractionOptions,
     result: &mut ExtractionResult,
 ) -> Result<FileSymbols, io::Error> {
-    let source = fs::read_to_string(path).map_err(|e| {
+    let source = path_utils::read_source_file(path).map_err(|e| {
         io::Error::new(
             io::ErrorKind::NotFound,
             format!("Failed to read file {}: {e}", path.display()),
@@ -551,16 +643,19 @@ fn process_file_combined(
         )
     })?;
 
-    let tree = crate::parser::parse_code(&source, language).map_err(|e| {
-        io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("Failed to parse {} code: {e}", language.name()),
-        )
-    })?;
+    let parsed = cache::shared()
+        .parse(path, &source, language)
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse {} code: {e}", language.name()),
+            )
+        })?;
+    let tree = &parsed.0;
 
     let include_code = options.detail_level == DetailLevel::Full;
     let enhanced_shape = crate::analysis::shape::extract_enhanced_shape(
-        &tree,
+        tree,
         &source,
         language,
         Some(&path.to_string_lossy()),
@@ -585,6 +680,12 @@ fn process_file_combined(
             .collect()
     };
 
+    let components = enhanced_shape
+        .components
+        .iter()
+        .map(|f| filter_function_by_detail(f, options.detail_level))
+        .collect();
+
     let structs = if enhanced_shape.structs.is_empty() {
         Vec::new()
     } else {
@@ -608,6 +709,7 @@ fn process_file_combined(
     Ok(FileSymbols {
         path: path.to_string_lossy().to_string(),
         functions,
+        components,
         structs,
         classes,
     })
@@ -624,11 +726,11 @@ fn extract_types_from_source(
     let types = match language {
         Language::Rust => crate::extraction::types::extract_rust_types(source, path)
             .map_err(|e| io::Error::other(e.to_string()))?,
-        Language::TypeScript => {
+        Language::TypeScript | Language::Tsx => {
             crate::extraction::types::extract_typescript_types(source, path, true)
                 .map_err(|e| io::Error::other(e.to_string()))?
         }
-        Language::JavaScript => {
+        Language::JavaScript | Language::Jsx => {
             crate::extraction::types::extract_typescript_types(source, path, false)
                 .map_err(|e| io::Error::other(e.to_string()))?
         }
@@ -638,7 +740,7 @@ fn extract_types_from_source(
             // Type extraction for these languages uses different extractors
             Vec::new()
         }
-        Language::Html | Language::Css | Language::Swift => {
+        Language::Html | Language::Css | Language::Svelte | Language::Swift => {
             // These languages don't have type definitions
             Vec::new()
         }
@@ -0,0 +1,223 @@
+//! Comment Marker Tool
+//!
+//! Collects technical-debt markers (`TODO`, `FIXME`, `HACK`, `XXX`,
+//! `OPTIMIZE`, `BUG`) from comment nodes, so markers inside strings or
+//! identifiers are never reported.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "h": "kind|file|line|author|text",
+//!   "markers": "TODO|src/lib.rs|12|alice|handle empty input\nFIXME|app.py|3||off by one\n..."
+//! }
+//! ```
+//!
+//! `author` comes from `TODO(alice): ...` or a trailing single-word
+//! `... (alice)` separated from the text by a space. Every line of a block
+//! comment is scanned, so one comment can yield several markers; only the
+//! first marker of each line counts.
+
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::analysis::node_kind::{classify_node, NodeKind};
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{detect_language, parse_code, Language};
+
+const MARKERS_HEADER: &str = "kind|file|line|author|text";
+
+/// Marker words, matched case-sensitively as whole words
+const MARKERS: &[&str] = &["TODO", "FIXME", "HACK", "XXX", "OPTIMIZE", "BUG"];
+
+/// A marker found in a comment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentMarker {
+    /// Marker word, e.g. `TODO`
+    pub kind: String,
+    /// Comment text after the marker, without delimiters or author
+    pub text: String,
+    pub file: String,
+    pub line: usize,
+    pub author: Option<String>,
+}
+
+pub fn execute_find_comments(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    log::info!("Finding comment markers in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let rows: Vec<String> = find_comments(path)?
+        .iter()
+        .map(|marker| {
+            format::format_row(&[
+                &marker.kind,
+                &path_utils::to_relative_path(&marker.file),
+                &marker.line.to_string(),
+                marker.author.as_deref().unwrap_or(""),
+                &marker.text,
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "h": MARKERS_HEADER,
+        "markers": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Markers in a file or every supported file of a directory, sorted by file
+/// and line
+pub fn find_comments(path: &Path) -> Result<Vec<CommentMarker>, io::Error> {
+    let mut markers = Vec::new();
+    for file in collect_project_files(path)? {
+        let Ok(language) = detect_language(&file) else {
+            continue;
+        };
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        match extract_comment_markers(&source, language, &file.to_string_lossy()) {
+            Ok(file_markers) => markers.extend(file_markers),
+            Err(e) => log::debug!("Skipping {}: {e}", file.display()),
+        }
+    }
+
+    markers.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    Ok(markers)
+}
+
+/// Markers in the comments of one source file, in line order
+pub fn extract_comment_markers(
+    source: &str,
+    language: Language,
+    file: &str,
+) -> Result<Vec<CommentMarker>, io::Error> {
+    let tree = parse_code(source, language).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Failed to parse {} code from '{file}': {e}",
+                language.name()
+            ),
+        )
+    })?;
+
+    let mut comments = Vec::new();
+    collect_comments(tree.root_node(), language, &mut comments);
+
+    let mut markers = Vec::new();
+    for comment in comments {
+        let Ok(text) = comment.utf8_text(source.as_bytes()) else {
+            continue;
+        };
+        let first_line = comment.start_position().row + 1;
+        for (offset, line) in text.lines().enumerate() {
+            if let Some((kind, rest)) = find_marker(line) {
+                let (author, text) = split_author(rest);
+                markers.push(CommentMarker {
+                    kind: kind.to_string(),
+                    text,
+                    file: file.to_string(),
+                    line: first_line + offset,
+                    author,
+                });
+            }
+        }
+    }
+
+    Ok(markers)
+}
+
+/// Outermost comment nodes; Rust doc comments nest a `doc_comment` inside
+/// their `line_comment`, which must not be scanned twice
+fn collect_comments<'tree>(node: Node<'tree>, language: Language, out: &mut Vec<Node<'tree>>) {
+    if classify_node(node.kind(), language) == NodeKind::Comment {
+        out.push(node);
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_comments(child, language, out);
+    }
+}
+
+/// First whole-word marker in a comment line, with the text after it
+fn find_marker(line: &str) -> Option<(&'static str, &str)> {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    MARKERS
+        .iter()
+        .flat_map(|&marker| {
+            line.match_indices(marker).filter_map(move |(start, _)| {
+                let end = start + marker.len();
+                let before_ok = !line[..start].ends_with(is_word_char);
+                let after_ok = !line[end..].starts_with(is_word_char);
+                (before_ok && after_ok).then_some((start, marker, &line[end..]))
+            })
+        })
+        .min_by_key(|(start, _, _)| *start)
+        .map(|(_, marker, rest)| (marker, rest))
+}
+
+/// Split `(alice): text` or `: text (alice)` into author and cleaned text
+fn split_author(rest: &str) -> (Option<String>, String) {
+    let mut author = None;
+    let mut text = rest;
+
+    if let Some(inner) = text.trim_start().strip_prefix('(') {
+        if let Some((name, after)) = inner.split_once(')') {
+            author = Some(name.trim().to_string()).filter(|name| !name.is_empty());
+            text = after;
+        }
+    }
+
+    let mut text = text
+        .trim_start_matches([':', '-', '!', ' ', '\t'])
+        .trim_end();
+    for closer in ["*/", "-->"] {
+        text = text.strip_suffix(closer).unwrap_or(text).trim_end();
+    }
+
+    if author.is_none() {
+        if let Some((before, name)) = text
+            .strip_suffix(')')
+            .and_then(|stripped| stripped.rsplit_once('('))
+        {
+            // `call foo(bar)` is code, not an author
+            let separated = before.ends_with(char::is_whitespace);
+            if separated && !name.is_empty() && !name.contains(char::is_whitespace) {
+                author = Some(name.to_string());
+                text = before.trim_end();
+            }
+        }
+    }
+
+    (author, text.to_string())
+}
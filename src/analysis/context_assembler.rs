@@ -0,0 +1,388 @@
+//! Context Assembler Tool
+//!
+//! Packs the code around a few focal symbols into a token budget: their
+//! definitions first, then the functions calling them, then the definitions
+//! of types named in their signatures.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "h": "file|start|end|score|tokens|code",
+//!   "chunks": "src/pricing.rs|7|9|1|30|pub fn apply_discount(...) {...}\nsrc/checkout.rs|3|6|0.7|41|...",
+//!   "used": 71,
+//!   "budget": 2000
+//! }
+//! ```
+//!
+//! Chunks are added greedily in that order; one that does not fit in what is
+//! left of the budget, or that overlaps a chunk already taken, is skipped.
+//! A call outside of any function becomes a chunk of its own lines.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+use tree_sitter::{Node, Tree};
+
+use crate::analysis::call_graph::{call_name, is_call_node};
+use crate::analysis::get_symbol::{definition, SymbolKind};
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::common::tokenizer;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{cache, detect_language, Language};
+
+const CHUNK_HEADER: &str = "file|start|end|score|tokens|code";
+const DEFAULT_TOKEN_BUDGET: usize = 2000;
+
+const DEFINITION_SCORE: f64 = 1.0;
+const CALLER_SCORE: f64 = 0.7;
+const TYPE_SCORE: f64 = 0.5;
+
+/// A contiguous range of lines picked for the context
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextChunk {
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// 1.0 for focal definitions, 0.7 for callers, 0.5 for signature types
+    pub relevance_score: f64,
+    pub tokens: usize,
+    pub code: String,
+}
+
+pub fn execute_assemble_context(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+    let focal_symbols: Vec<String> = arguments["focal_symbols"]
+        .as_array()
+        .map(|symbols| {
+            symbols
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    if focal_symbols.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or empty 'focal_symbols' argument",
+        ));
+    }
+    let token_budget = arguments["token_budget"]
+        .as_u64()
+        .map(|value| value as usize)
+        .unwrap_or(DEFAULT_TOKEN_BUDGET);
+
+    log::info!("Assembling context for {focal_symbols:?} in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let chunks = assemble_context(&focal_symbols, path, token_budget)?;
+    let used: usize = chunks.iter().map(|chunk| chunk.tokens).sum();
+    let rows: Vec<String> = chunks
+        .iter()
+        .map(|chunk| {
+            format::format_row(&[
+                &path_utils::to_relative_path(&chunk.file),
+                &chunk.start_line.to_string(),
+                &chunk.end_line.to_string(),
+                &chunk.relevance_score.to_string(),
+                &chunk.tokens.to_string(),
+                &chunk.code,
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "h": CHUNK_HEADER,
+        "chunks": rows.join("\n"),
+        "used": used,
+        "budget": token_budget,
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Chunks for `focal_symbols` found in a file or directory, in the order
+/// they were taken, using at most `token_budget` tokens in total
+pub fn assemble_context(
+    focal_symbols: &[String],
+    path: &Path,
+    token_budget: usize,
+) -> Result<Vec<ContextChunk>, io::Error> {
+    let focal: HashSet<&str> = focal_symbols.iter().map(String::as_str).collect();
+
+    let mut files = Vec::new();
+    for file in collect_project_files(path)? {
+        let Ok(language) = detect_language(&file) else {
+            continue;
+        };
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        match cache::shared().parse(&file, &source, language) {
+            Ok(parsed) => files.push(ParsedFile {
+                path: file.to_string_lossy().into_owned(),
+                parsed,
+                language,
+            }),
+            Err(e) => log::debug!("Skipping {}: {e}", file.display()),
+        }
+    }
+
+    let mut index = SymbolIndex::default();
+    for (file, parsed) in files.iter().enumerate() {
+        let mut walker = Walker {
+            file,
+            source: parsed.source(),
+            language: parsed.language,
+            focal: &focal,
+            index: &mut index,
+        };
+        walker.visit(parsed.tree().root_node());
+    }
+
+    let mut candidates = index.focal_definitions.clone();
+    candidates.extend(index.callers());
+    candidates.extend(index.signature_type_definitions());
+
+    let counter = tokenizer::tokenizer(tokenizer::configured())?;
+    let mut chunks: Vec<ContextChunk> = Vec::new();
+    let mut used = 0;
+    for candidate in candidates {
+        let file = &files[candidate.file];
+        if chunks.iter().any(|chunk| {
+            chunk.file == file.path
+                && chunk.start_line <= candidate.end_line
+                && candidate.start_line <= chunk.end_line
+        }) {
+            continue;
+        }
+
+        let code = lines(file.source(), candidate.start_line, candidate.end_line);
+        let tokens = counter.count(&code);
+        if used + tokens > token_budget {
+            continue;
+        }
+        used += tokens;
+        chunks.push(ContextChunk {
+            file: file.path.clone(),
+            start_line: candidate.start_line,
+            end_line: candidate.end_line,
+            relevance_score: candidate.score,
+            tokens,
+            code,
+        });
+    }
+
+    Ok(chunks)
+}
+
+struct ParsedFile {
+    path: String,
+    parsed: Arc<(Tree, String)>,
+    language: Language,
+}
+
+impl ParsedFile {
+    fn tree(&self) -> &Tree {
+        &self.parsed.0
+    }
+
+    fn source(&self) -> &str {
+        &self.parsed.1
+    }
+}
+
+/// A line range of one of the parsed files
+#[derive(Debug, Clone)]
+struct Candidate {
+    file: usize,
+    start_line: usize,
+    end_line: usize,
+    score: f64,
+}
+
+#[derive(Default)]
+struct SymbolIndex {
+    focal_definitions: Vec<Candidate>,
+    /// Type names in focal signatures, first occurrence first
+    signature_types: Vec<String>,
+    /// Function definitions, to find the caller around a call
+    functions: Vec<Candidate>,
+    /// Struct, class, enum, trait, interface and type alias definitions
+    types: Vec<(String, Candidate)>,
+    /// Calls of a focal symbol
+    calls: Vec<Candidate>,
+}
+
+impl SymbolIndex {
+    /// The innermost function around each call, or the call itself, sorted
+    /// by file and line
+    fn callers(&self) -> Vec<Candidate> {
+        let mut callers: Vec<Candidate> = self
+            .calls
+            .iter()
+            .map(|call| {
+                self.functions
+                    .iter()
+                    .filter(|function| {
+                        function.file == call.file
+                            && function.start_line <= call.start_line
+                            && call.end_line <= function.end_line
+                    })
+                    .max_by_key(|function| function.start_line)
+                    .unwrap_or(call)
+                    .clone()
+            })
+            .map(|caller| Candidate {
+                score: CALLER_SCORE,
+                ..caller
+            })
+            .collect();
+        callers.sort_by_key(|caller| (caller.file, caller.start_line));
+        callers
+    }
+
+    fn signature_type_definitions(&self) -> Vec<Candidate> {
+        self.signature_types
+            .iter()
+            .flat_map(|wanted| {
+                self.types
+                    .iter()
+                    .filter(move |(name, _)| name == wanted)
+                    .map(|(_, definition)| Candidate {
+                        score: TYPE_SCORE,
+                        ..definition.clone()
+                    })
+            })
+            .collect()
+    }
+}
+
+struct Walker<'a> {
+    file: usize,
+    source: &'a str,
+    language: Language,
+    focal: &'a HashSet<&'a str>,
+    index: &'a mut SymbolIndex,
+}
+
+impl<'a> Walker<'a> {
+    fn visit(&mut self, node: Node<'a>) {
+        if let Some((name, kind, span)) = definition(node, self.source, self.language) {
+            let candidate = self.candidate(span, DEFINITION_SCORE);
+            if kind == SymbolKind::Function {
+                if self.focal.contains(name) {
+                    self.index.focal_definitions.push(candidate.clone());
+                    self.collect_signature_types(node, node.child_by_field_name("body"));
+                }
+                self.index.functions.push(candidate);
+            } else {
+                self.index.types.push((name.to_string(), candidate));
+            }
+        } else if let Some(name) = self.type_definition_name(node) {
+            let candidate = self.candidate(node, DEFINITION_SCORE);
+            self.index.types.push((name.to_string(), candidate));
+        }
+
+        if is_call_node(node.kind(), self.language)
+            && call_name(node, self.source).is_some_and(|name| self.focal.contains(name.as_str()))
+        {
+            let call = self.candidate(node, CALLER_SCORE);
+            self.index.calls.push(call);
+        }
+
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            self.visit(child);
+        }
+    }
+
+    fn candidate(&self, node: Node, score: f64) -> Candidate {
+        Candidate {
+            file: self.file,
+            start_line: node.start_position().row + 1,
+            end_line: node.end_position().row + 1,
+            score,
+        }
+    }
+
+    /// Enums, traits, interfaces and type aliases, which `get_symbol` does
+    /// not report
+    fn type_definition_name(&self, node: Node<'a>) -> Option<&'a str> {
+        match node.kind() {
+            "enum_item"
+            | "trait_item"
+            | "type_item"
+            | "enum_declaration"
+            | "interface_declaration"
+            | "type_alias_declaration" => node
+                .child_by_field_name("name")
+                .and_then(|name| name.utf8_text(self.source.as_bytes()).ok()),
+            _ => None,
+        }
+    }
+
+    /// Type names used in a function's parameters and return type
+    fn collect_signature_types(&mut self, node: Node<'a>, body: Option<Node<'a>>) {
+        if body.is_some_and(|body| body.id() == node.id()) {
+            return;
+        }
+
+        let is_type_name = match self.language {
+            // Python annotations are plain identifiers below a `type` node
+            Language::Python => {
+                node.kind() == "identifier"
+                    && node.parent().is_some_and(|parent| {
+                        parent.kind() == "type" || parent.kind() == "generic_type"
+                    })
+            }
+            _ => node.kind() == "type_identifier",
+        };
+        if is_type_name {
+            if let Ok(name) = node.utf8_text(self.source.as_bytes()) {
+                if !self.index.signature_types.iter().any(|known| known == name) {
+                    self.index.signature_types.push(name.to_string());
+                }
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            self.collect_signature_types(child, body);
+        }
+    }
+}
+
+/// Lines `start..=end` (1-based) of `source`
+fn lines(source: &str, start: usize, end: usize) -> String {
+    source
+        .lines()
+        .skip(start - 1)
+        .take(end + 1 - start)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,264 @@
+//! Test Coverage Map Tool
+//!
+//! Links test functions to the production symbols they probably exercise,
+//! based on naming alone: `test_add` tests `add`, `test_Calculator_reset`
+//! tests `Calculator::reset`, Go's `TestParseConfig` tests `ParseConfig`.
+//!
+//! A test function is any function named `test_*`, `test<Upper>*`,
+//! `Test<Upper>*` or `*_test`. Production symbols are the functions and
+//! types of the non-test files in the [`build_inventory`] of the path.
+//!
+//! Matching compares lower-cased words (split on `_` and camelCase), so
+//! `test_parse_http_request` and `testParseHttpRequest` both match
+//! `parse_http_request` and `parseHttpRequest`. A symbol matches when its
+//! words appear as a contiguous run in the test's words; symbols whose run
+//! lies inside a longer match are dropped, so `test_add_item` reports
+//! `add_item` but not `add`.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "h": "test_file|test_fn|line|targets",
+//!   "tests": "tests/calc_test.rs|test_add|3|add\ntests/calc_test.rs|test_Calculator_reset|8|Calculator::reset\n..."
+//! }
+//! ```
+//!
+//! Tests without a probable target keep an empty `targets` column.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::analysis::relevant_tests::is_test_file;
+use crate::analysis::symbol_inventory::{build_inventory, SymbolEntry};
+use crate::common::format;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+
+const TESTS_HEADER: &str = "test_file|test_fn|line|targets";
+
+/// A test function and the production symbols its name points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestMapping {
+    pub test_file: String,
+    pub test_function: String,
+    /// 1-based line of the test function
+    pub line: usize,
+    /// Symbol names, or `Type::method` when a type and a method declared in
+    /// the same file match back to back; longest matches first
+    pub probable_targets: Vec<String>,
+}
+
+pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    log::info!("Mapping tests to production code in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let rows: Vec<String> = map_tests_to_production(path)
+        .iter()
+        .map(|mapping| {
+            format::format_row(&[
+                &mapping.test_file,
+                &mapping.test_function,
+                &mapping.line.to_string(),
+                &mapping.probable_targets.join(","),
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "h": TESTS_HEADER,
+        "tests": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Test functions under `path` with their probable targets, ordered by file
+/// and line.
+pub fn map_tests_to_production(path: &Path) -> Vec<TestMapping> {
+    let inventory = build_inventory(path);
+
+    let is_production = |entry: &&SymbolEntry| !is_test_file(Path::new(&entry.file));
+    let functions: Vec<Candidate> = inventory
+        .functions
+        .iter()
+        .filter(is_production)
+        .filter(|entry| test_subject(&entry.name).is_none())
+        .map(Candidate::new)
+        .collect();
+    let types: Vec<Candidate> = inventory
+        .types
+        .iter()
+        .filter(is_production)
+        .map(Candidate::new)
+        .collect();
+
+    inventory
+        .functions
+        .iter()
+        .filter_map(|entry| {
+            let subject = test_subject(&entry.name)?;
+            Some(TestMapping {
+                test_file: entry.file.clone(),
+                test_function: entry.name.clone(),
+                line: entry.line,
+                probable_targets: probable_targets(&words(subject), &functions, &types),
+            })
+        })
+        .collect()
+}
+
+/// A production symbol and its lower-cased words
+struct Candidate<'a> {
+    entry: &'a SymbolEntry,
+    words: Vec<String>,
+}
+
+impl<'a> Candidate<'a> {
+    fn new(entry: &'a SymbolEntry) -> Self {
+        Self {
+            entry,
+            words: words(&entry.name),
+        }
+    }
+}
+
+/// A matched target covering `test_words[start..end]`
+struct Match {
+    target: String,
+    start: usize,
+    end: usize,
+}
+
+fn probable_targets(
+    test_words: &[String],
+    functions: &[Candidate],
+    types: &[Candidate],
+) -> Vec<String> {
+    let mut matches = Vec::new();
+    for ty in types {
+        for start in runs(test_words, &ty.words) {
+            let after_type = start + ty.words.len();
+            // `Type_method`: a method declared next to the type
+            let methods: Vec<&Candidate> = functions
+                .iter()
+                .filter(|function| function.entry.file == ty.entry.file)
+                .filter(|function| !function.words.is_empty())
+                .filter(|function| test_words[after_type..].starts_with(&function.words))
+                .collect();
+            for method in &methods {
+                matches.push(Match {
+                    target: format!("{}::{}", ty.entry.name, method.entry.name),
+                    start,
+                    end: after_type + method.words.len(),
+                });
+            }
+            if methods.is_empty() {
+                matches.push(Match {
+                    target: ty.entry.name.clone(),
+                    start,
+                    end: after_type,
+                });
+            }
+        }
+    }
+    for function in functions {
+        for start in runs(test_words, &function.words) {
+            matches.push(Match {
+                target: function.entry.name.clone(),
+                start,
+                end: start + function.words.len(),
+            });
+        }
+    }
+
+    // Longest first; drop matches inside a longer one.
+    matches.sort_by_key(|m| (std::cmp::Reverse(m.end - m.start), m.start));
+    let mut kept: Vec<&Match> = Vec::new();
+    let mut seen = HashSet::new();
+    for candidate in &matches {
+        let nested = kept.iter().any(|longer| {
+            longer.start <= candidate.start
+                && candidate.end <= longer.end
+                && longer.end - longer.start > candidate.end - candidate.start
+        });
+        if !nested && seen.insert(candidate.target.as_str()) {
+            kept.push(candidate);
+        }
+    }
+    kept.into_iter().map(|m| m.target.clone()).collect()
+}
+
+/// Start indexes where `needle` occurs as a contiguous run in `haystack`
+fn runs(haystack: &[String], needle: &[String]) -> Vec<usize> {
+    if needle.is_empty() || needle.len() > haystack.len() {
+        return Vec::new();
+    }
+    (0..=haystack.len() - needle.len())
+        .filter(|&start| haystack[start..start + needle.len()] == *needle)
+        .collect()
+}
+
+/// The tested part of a test function name, e.g. `Calculator_reset` for
+/// `test_Calculator_reset`; `None` when the name is not test-like.
+fn test_subject(name: &str) -> Option<&str> {
+    let subject = if let Some(rest) = name.strip_prefix("test_") {
+        rest
+    } else if let Some(rest) = name
+        .strip_prefix("test")
+        .or_else(|| name.strip_prefix("Test"))
+        .filter(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+    {
+        rest
+    } else {
+        name.strip_suffix("_test")?
+    };
+    (!subject.is_empty()).then_some(subject)
+}
+
+/// Lower-cased words of an identifier, split on `_` and camelCase humps
+/// (`parseHTTPRequest` -> `parse`, `http`, `request`).
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for part in name.split(|c: char| !c.is_alphanumeric()) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut current = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            let boundary = i > 0
+                && c.is_uppercase()
+                && (chars[i - 1].is_lowercase()
+                    || chars[i - 1].is_ascii_digit()
+                    || chars.get(i + 1).is_some_and(|next| next.is_lowercase()));
+            if boundary && !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            current.extend(c.to_lowercase());
+        }
+        if !current.is_empty() {
+            words.push(current);
+        }
+    }
+    words
+}
@@ -0,0 +1,260 @@
+//! CSS Custom Property Tool
+//!
+//! Cross-references CSS custom properties across the `.css` and `.html`
+//! files of a directory: every `--name: value` definition and every
+//! `var(--name)` usage.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "dh": "name|value|file|line|theme",
+//!   "defs": "--color-primary|#3b82f6|styles/theme.css|2|y\n...",
+//!   "uh": "name|file|line",
+//!   "uses": "--color-primary|components/button.css|3\n...",
+//!   "undefined": "--color-accent",
+//!   "dead": "--radius-lg"
+//! }
+//! ```
+//!
+//! `theme` is `y` for variables defined in a Tailwind `@theme` block.
+//! `undefined` lists variables used but never defined (a usage with a
+//! `var(--name, fallback)` fallback still counts), `dead` lists variables
+//! defined but never used. Both are sorted by name. Definitions and usages
+//! are found textually, so HTML `style` attributes and `<style>` blocks are
+//! covered alike; `/* ... */` comments are ignored.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+
+const DEFINITIONS_HEADER: &str = "name|value|file|line|theme";
+const USAGES_HEADER: &str = "name|file|line";
+
+/// Extensions of the files scanned for custom properties
+const SCANNED_EXTENSIONS: &[&str] = &["css", "html", "htm"];
+
+/// A `--name: value` declaration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CssVarDefinition {
+    pub name: String,
+    pub value: String,
+    pub file: String,
+    pub line: usize,
+    /// Declared inside a Tailwind `@theme` block
+    pub theme: bool,
+}
+
+/// A `var(--name)` reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CssVarUsage {
+    pub name: String,
+    pub file: String,
+    pub line: usize,
+}
+
+/// Definitions and usages of the custom properties of a directory
+#[derive(Debug, Clone, Default)]
+pub struct CssVarReport {
+    pub definitions: Vec<CssVarDefinition>,
+    pub usages: Vec<CssVarUsage>,
+    /// Names used but never defined
+    pub undefined: Vec<String>,
+    /// Names defined but never used
+    pub dead: Vec<String>,
+}
+
+pub fn execute_css_var_usages(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    log::info!("Cross-referencing CSS custom properties in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let report = css_var_usages(path)?;
+
+    let definitions: Vec<String> = report
+        .definitions
+        .iter()
+        .map(|definition| {
+            format::format_row(&[
+                &definition.name,
+                &definition.value,
+                &path_utils::to_relative_path(&definition.file),
+                &definition.line.to_string(),
+                if definition.theme { "y" } else { "" },
+            ])
+        })
+        .collect();
+    let usages: Vec<String> = report
+        .usages
+        .iter()
+        .map(|usage| {
+            format::format_row(&[
+                &usage.name,
+                &path_utils::to_relative_path(&usage.file),
+                &usage.line.to_string(),
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "dh": DEFINITIONS_HEADER,
+        "defs": definitions.join("\n"),
+        "uh": USAGES_HEADER,
+        "uses": usages.join("\n"),
+        "undefined": report.undefined.join("\n"),
+        "dead": report.dead.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Custom properties of a `.css`/`.html` file or of every such file in a
+/// directory, in file and line order
+pub fn css_var_usages(path: &Path) -> Result<CssVarReport, io::Error> {
+    let mut report = CssVarReport::default();
+    for file in collect_project_files(path)? {
+        let scanned = file
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                SCANNED_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+            });
+        if !scanned {
+            continue;
+        }
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        let (definitions, usages) = extract_css_vars(&source, &file.to_string_lossy())?;
+        report.definitions.extend(definitions);
+        report.usages.extend(usages);
+    }
+
+    let defined: HashSet<&str> = report
+        .definitions
+        .iter()
+        .map(|definition| definition.name.as_str())
+        .collect();
+    let used: HashSet<&str> = report
+        .usages
+        .iter()
+        .map(|usage| usage.name.as_str())
+        .collect();
+    let mut undefined: Vec<String> = used
+        .difference(&defined)
+        .map(|name| name.to_string())
+        .collect();
+    let mut dead: Vec<String> = defined
+        .difference(&used)
+        .map(|name| name.to_string())
+        .collect();
+    undefined.sort();
+    dead.sort();
+    report.undefined = undefined;
+    report.dead = dead;
+
+    Ok(report)
+}
+
+/// Definitions and usages of custom properties in one stylesheet or HTML
+/// document, each in line order
+pub fn extract_css_vars(
+    source: &str,
+    file: &str,
+) -> Result<(Vec<CssVarDefinition>, Vec<CssVarUsage>), io::Error> {
+    let source = blank_comments(source);
+
+    let theme_block_re = Regex::new(r"@theme\s*\{([\s\S]*?)\}")
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid regex: {e}")))?;
+    // A declaration starts a block, a declaration list or a style attribute,
+    // which keeps `var(--name)` arguments out
+    let definition_re = Regex::new(r#"(?:^|[{;\s"'])(--[\w-]+)\s*:\s*([^;}"']*)"#)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid regex: {e}")))?;
+    let usage_re = Regex::new(r"var\(\s*(--[\w-]+)")
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid regex: {e}")))?;
+
+    let theme_blocks: Vec<(usize, usize)> = theme_block_re
+        .captures_iter(&source)
+        .filter_map(|cap| cap.get(1))
+        .map(|body| (body.start(), body.end()))
+        .collect();
+
+    let definitions = definition_re
+        .captures_iter(&source)
+        .map(|cap| {
+            let name = cap.get(1).unwrap();
+            CssVarDefinition {
+                name: name.as_str().to_string(),
+                value: cap[2].trim().to_string(),
+                file: file.to_string(),
+                line: calculate_line(&source, name.start()),
+                theme: theme_blocks
+                    .iter()
+                    .any(|&(start, end)| (start..end).contains(&name.start())),
+            }
+        })
+        .collect();
+
+    let usages = usage_re
+        .captures_iter(&source)
+        .map(|cap| {
+            let name = cap.get(1).unwrap();
+            CssVarUsage {
+                name: name.as_str().to_string(),
+                file: file.to_string(),
+                line: calculate_line(&source, name.start()),
+            }
+        })
+        .collect();
+
+    Ok((definitions, usages))
+}
+
+/// `source` with the text of `/* ... */` comments replaced by spaces,
+/// keeping newlines so line numbers are unchanged
+fn blank_comments(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        result.push_str(&rest[..start]);
+        let comment_len = rest[start + 2..]
+            .find("*/")
+            .map_or(rest.len() - start, |end| end + 4);
+        let comment = &rest[start..start + comment_len];
+        result.extend(comment.chars().map(|c| if c == '\n' { '\n' } else { ' ' }));
+        rest = &rest[start + comment_len..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn calculate_line(source: &str, byte_offset: usize) -> usize {
+    source[..byte_offset].matches('\n').count() + 1
+}
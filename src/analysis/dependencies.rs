@@ -10,6 +10,7 @@ use std::path::{Path, PathBuf};
 use streaming_iterator::StreamingIterator;
 use tree_sitter::{Query, QueryCursor};
 
+use crate::analysis::path_utils;
 use crate::parser::Language;
 
 /// Resolve all file dependencies for a given source file
@@ -25,7 +26,7 @@ pub fn resolve_dependencies(
     match language {
         Language::Rust => find_rust_dependencies(source, file_path, project_root),
         Language::Python => find_python_dependencies(source, file_path, project_root),
-        Language::JavaScript | Language::TypeScript => {
+        Language::JavaScript | Language::TypeScript | Language::Jsx | Language::Tsx => {
             find_js_ts_dependencies(source, file_path, project_root)
         }
         Language::Go => find_go_dependencies(source, file_path, project_root),
@@ -282,17 +283,13 @@ pub fn find_js_ts_dependencies(
     let mut deps = Vec::new();
     let dir = file_path.parent().unwrap_or(project_root);
 
-    // Detect if this is TypeScript or JavaScript
-    let is_ts = file_path
-        .extension()
-        .and_then(|e| e.to_str())
-        .map(|e| e == "ts" || e == "tsx")
-        .unwrap_or(false);
+    // Detect if this is TypeScript, TSX or JavaScript
+    let extension = file_path.extension().and_then(|e| e.to_str());
 
-    let language = if is_ts {
-        tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()
-    } else {
-        tree_sitter_javascript::LANGUAGE.into()
+    let language = match extension {
+        Some("ts") => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+        Some("tsx") => tree_sitter_typescript::LANGUAGE_TSX.into(),
+        _ => tree_sitter_javascript::LANGUAGE.into(),
     };
 
     let mut parser = tree_sitter::Parser::new();
@@ -428,7 +425,7 @@ pub fn find_go_dependencies(source: &str, file_path: &Path, project_root: &Path)
 }
 
 fn read_go_module_path(project_root: &Path) -> Option<String> {
-    let content = fs::read_to_string(project_root.join("go.mod")).ok()?;
+    let content = path_utils::read_source_file(project_root.join("go.mod")).ok()?;
 
     content.lines().find_map(|line| {
         let line = line.trim();
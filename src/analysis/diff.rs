@@ -7,17 +7,20 @@ use crate::analysis::path_utils;
 use crate::common::format;
 use crate::mcp_types::{CallToolResult, CallToolResultExt};
 use crate::parser::{detect_language, parse_code, Language};
+use indexmap::IndexMap;
+use rayon::prelude::*;
 use regex::Regex;
 use serde::Serialize;
 use serde_json::json;
 use serde_json::Value;
-use std::collections::HashMap;
-use std::fs;
 use std::hash::{Hash, Hasher};
 use std::io;
 use std::path::Path;
 use std::process::Command;
 
+/// Lines of code context fetched around each affected usage.
+const USAGE_CONTEXT_LINES: u32 = 1;
+
 // ============================================================================
 // Data Structures
 // ============================================================================
@@ -41,6 +44,9 @@ pub struct DiffSummary {
     pub added: usize,
     pub removed: usize,
     pub modified: usize,
+    /// Struct fields added or removed; not part of `added`/`removed`
+    pub fields_added: usize,
+    pub fields_removed: usize,
 }
 
 /// A single structural change detected in the diff
@@ -92,6 +98,30 @@ pub struct ChangeDetail {
     pub to: Option<String>,
 }
 
+/// Diffs of several files against one revision
+#[derive(Debug, Serialize)]
+pub struct BatchDiff {
+    pub files: Vec<DiffAnalysis>,
+    pub summary: BatchDiffSummary,
+    /// Files that could not be analyzed; the rest of the batch still is
+    pub errors: Vec<BatchDiffError>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchDiffSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub modified: usize,
+    /// Any signature change, removed symbol or removed public field
+    pub has_breaking_changes: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchDiffError {
+    pub file_path: String,
+    pub error: String,
+}
+
 // ============================================================================
 // Affected Usages (internal)
 // ============================================================================
@@ -115,6 +145,9 @@ pub struct AffectedUsage {
     pub confidence: MatchConfidence,
     pub risk: RiskLevel,
     pub reason: String,
+    /// Concrete argument/parameter type conflict at a call site, when one can be spotted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_mismatch_hint: Option<String>,
 }
 
 #[derive(Debug, Serialize, Clone, PartialEq)]
@@ -138,7 +171,7 @@ pub enum MatchConfidence {
 // ============================================================================
 
 /// Validate git revision string to prevent command injection
-fn validate_git_revision(revision: &str) -> Result<(), io::Error> {
+pub(crate) fn validate_git_revision(revision: &str) -> Result<(), io::Error> {
     // Allow: branch names, commit SHAs, HEAD~N, tags, etc.
     // Pattern: alphanumeric, dash, underscore, slash, tilde, caret, at, colon, dot
     let valid_pattern = Regex::new(r"^[a-zA-Z0-9_\-/.~^@:]+$")
@@ -161,7 +194,7 @@ fn validate_git_revision(revision: &str) -> Result<(), io::Error> {
 }
 
 /// Get the old version of a file from git
-fn get_git_file_content(file_path: &Path, revision: &str) -> Result<String, io::Error> {
+pub(crate) fn get_git_file_content(file_path: &Path, revision: &str) -> Result<String, io::Error> {
     // Validate revision to prevent command injection
     validate_git_revision(revision)?;
 
@@ -251,6 +284,19 @@ struct ExtractedSymbol {
     line: usize,
     signature: Option<String>,
     body_hash: u64, // Hash of body for detecting body-only changes
+    /// Named fields, for structs only
+    fields: Vec<StructField>,
+}
+
+/// A named struct field
+#[derive(Debug, Clone)]
+struct StructField {
+    name: String,
+    /// Field declaration as written, e.g. `pub timeout: u64`
+    declaration: String,
+    line: usize,
+    /// Visible outside its module (`pub` in Rust, capitalized in Go)
+    public: bool,
 }
 
 /// Extract all symbols from a parsed tree
@@ -258,16 +304,21 @@ fn extract_symbols(
     tree: &tree_sitter::Tree,
     source: &str,
     language: Language,
-) -> Result<HashMap<String, ExtractedSymbol>, io::Error> {
-    let mut symbols = HashMap::new();
+) -> Result<IndexMap<String, ExtractedSymbol>, io::Error> {
+    let mut symbols = IndexMap::new();
 
     match language {
         Language::Rust => extract_rust_symbols(tree, source, &mut symbols)?,
         Language::Python => extract_python_symbols(tree, source, &mut symbols)?,
-        Language::JavaScript => extract_js_symbols(tree, source, &mut symbols)?,
-        Language::TypeScript => extract_ts_symbols(tree, source, &mut symbols)?,
+        Language::JavaScript | Language::Jsx => extract_js_symbols(tree, source, &mut symbols)?,
+        Language::TypeScript | Language::Tsx => extract_ts_symbols(tree, source, &mut symbols)?,
         Language::Go => extract_go_symbols(tree, source, &mut symbols)?,
-        Language::Html | Language::Css | Language::Swift | Language::CSharp | Language::Java => {
+        Language::Html
+        | Language::Css
+        | Language::Svelte
+        | Language::Swift
+        | Language::CSharp
+        | Language::Java => {
             // These languages don't have structural-diff extraction implemented yet.
             // Return empty - structural diff not applicable.
             log::debug!("Structural diff not applicable for {:?}", language);
@@ -280,7 +331,7 @@ fn extract_symbols(
 fn extract_rust_symbols(
     tree: &tree_sitter::Tree,
     source: &str,
-    symbols: &mut HashMap<String, ExtractedSymbol>,
+    symbols: &mut IndexMap<String, ExtractedSymbol>,
 ) -> Result<(), io::Error> {
     use streaming_iterator::StreamingIterator;
     use tree_sitter::{Query, QueryCursor};
@@ -318,6 +369,10 @@ fn extract_rust_symbols(
             if let (Ok(name), Some(full_node)) = (node.utf8_text(source.as_bytes()), full_node) {
                 let signature = extract_signature_from_node(&full_node, source);
                 let body_hash = hash_node_body(&full_node, source);
+                let fields = match symbol_type {
+                    SymbolType::Struct => rust_struct_fields(full_node, source),
+                    _ => Vec::new(),
+                };
 
                 symbols.insert(
                     format!("{:?}::{}", symbol_type, name),
@@ -327,6 +382,7 @@ fn extract_rust_symbols(
                         line: node.start_position().row + 1,
                         signature,
                         body_hash,
+                        fields,
                     },
                 );
             }
@@ -339,7 +395,7 @@ fn extract_rust_symbols(
 fn extract_python_symbols(
     tree: &tree_sitter::Tree,
     source: &str,
-    symbols: &mut HashMap<String, ExtractedSymbol>,
+    symbols: &mut IndexMap<String, ExtractedSymbol>,
 ) -> Result<(), io::Error> {
     use streaming_iterator::StreamingIterator;
     use tree_sitter::{Query, QueryCursor};
@@ -379,6 +435,7 @@ fn extract_python_symbols(
                         line: node.start_position().row + 1,
                         signature,
                         body_hash,
+                        fields: Vec::new(),
                     },
                 );
             }
@@ -391,7 +448,7 @@ fn extract_python_symbols(
 fn extract_js_symbols(
     tree: &tree_sitter::Tree,
     source: &str,
-    symbols: &mut HashMap<String, ExtractedSymbol>,
+    symbols: &mut IndexMap<String, ExtractedSymbol>,
 ) -> Result<(), io::Error> {
     use streaming_iterator::StreamingIterator;
     use tree_sitter::{Query, QueryCursor};
@@ -433,6 +490,7 @@ fn extract_js_symbols(
                         line: node.start_position().row + 1,
                         signature,
                         body_hash,
+                        fields: Vec::new(),
                     },
                 );
             }
@@ -445,13 +503,14 @@ fn extract_js_symbols(
 fn extract_ts_symbols(
     tree: &tree_sitter::Tree,
     source: &str,
-    symbols: &mut HashMap<String, ExtractedSymbol>,
+    symbols: &mut IndexMap<String, ExtractedSymbol>,
 ) -> Result<(), io::Error> {
     use streaming_iterator::StreamingIterator;
     use tree_sitter::{Query, QueryCursor};
 
+    // The TypeScript or TSX grammar, whichever parsed the tree
     let query = Query::new(
-        &tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+        &tree.language(),
         r#"
         (function_declaration name: (identifier) @func.name) @func
         (class_declaration name: (type_identifier) @class.name) @class
@@ -489,6 +548,7 @@ fn extract_ts_symbols(
                         line: node.start_position().row + 1,
                         signature,
                         body_hash,
+                        fields: Vec::new(),
                     },
                 );
             }
@@ -501,7 +561,7 @@ fn extract_ts_symbols(
 fn extract_go_symbols(
     tree: &tree_sitter::Tree,
     source: &str,
-    symbols: &mut HashMap<String, ExtractedSymbol>,
+    symbols: &mut IndexMap<String, ExtractedSymbol>,
 ) -> Result<(), io::Error> {
     use streaming_iterator::StreamingIterator;
     use tree_sitter::{Node, Query, QueryCursor};
@@ -557,6 +617,10 @@ fn extract_go_symbols(
             if let (Ok(name), Some(full_node)) = (node.utf8_text(source.as_bytes()), full_node) {
                 let signature = extract_signature_from_node(&full_node, source);
                 let body_hash = hash_node_body(&full_node, source);
+                let fields = match symbol_type {
+                    SymbolType::Struct => go_struct_fields(node.parent(), source),
+                    _ => Vec::new(),
+                };
 
                 symbols.insert(
                     format!("{:?}::{}", symbol_type, name),
@@ -566,6 +630,7 @@ fn extract_go_symbols(
                         line: node.start_position().row + 1,
                         signature,
                         body_hash,
+                        fields,
                     },
                 );
             }
@@ -575,6 +640,71 @@ fn extract_go_symbols(
     Ok(())
 }
 
+/// Named fields of a Rust `struct_item`; tuple structs have none.
+fn rust_struct_fields(node: tree_sitter::Node, source: &str) -> Vec<StructField> {
+    let Some(body) = node.child_by_field_name("body") else {
+        return Vec::new();
+    };
+
+    let mut cursor = body.walk();
+    body.named_children(&mut cursor)
+        .filter(|child| child.kind() == "field_declaration")
+        .filter_map(|field| {
+            let name = field.child_by_field_name("name")?;
+            let mut field_cursor = field.walk();
+            let public = field
+                .children(&mut field_cursor)
+                .any(|child| child.kind() == "visibility_modifier");
+            Some(StructField {
+                name: name.utf8_text(source.as_bytes()).ok()?.to_string(),
+                declaration: field.utf8_text(source.as_bytes()).ok()?.to_string(),
+                line: field.start_position().row + 1,
+                public,
+            })
+        })
+        .collect()
+}
+
+/// Named fields of a Go struct `type_spec`; embedded fields are skipped.
+fn go_struct_fields(type_spec: Option<tree_sitter::Node>, source: &str) -> Vec<StructField> {
+    let Some(list) = type_spec
+        .and_then(|spec| spec.child_by_field_name("type"))
+        .and_then(|struct_type| {
+            let mut cursor = struct_type.walk();
+            let list = struct_type
+                .named_children(&mut cursor)
+                .find(|child| child.kind() == "field_declaration_list");
+            list
+        })
+    else {
+        return Vec::new();
+    };
+
+    let mut fields = Vec::new();
+    let mut cursor = list.walk();
+    for declaration in list.named_children(&mut cursor) {
+        if declaration.kind() != "field_declaration" {
+            continue;
+        }
+        let Ok(text) = declaration.utf8_text(source.as_bytes()) else {
+            continue;
+        };
+        let mut name_cursor = declaration.walk();
+        for name in declaration.children_by_field_name("name", &mut name_cursor) {
+            let Ok(name) = name.utf8_text(source.as_bytes()) else {
+                continue;
+            };
+            fields.push(StructField {
+                name: name.to_string(),
+                declaration: text.to_string(),
+                line: declaration.start_position().row + 1,
+                public: name.starts_with(|c: char| c.is_uppercase()),
+            });
+        }
+    }
+    fields
+}
+
 /// Extract signature (first line or up to opening brace)
 fn extract_signature_from_node(node: &tree_sitter::Node, source: &str) -> Option<String> {
     let text = node.utf8_text(source.as_bytes()).ok()?;
@@ -622,8 +752,8 @@ fn hash_node_body(node: &tree_sitter::Node, source: &str) -> u64 {
 
 /// Compare old and new symbols to find structural changes
 fn compare_symbols(
-    old_symbols: &HashMap<String, ExtractedSymbol>,
-    new_symbols: &HashMap<String, ExtractedSymbol>,
+    old_symbols: &IndexMap<String, ExtractedSymbol>,
+    new_symbols: &IndexMap<String, ExtractedSymbol>,
     _old_source: &str,
     _new_source: &str,
 ) -> Result<Vec<StructuralChange>, io::Error> {
@@ -696,6 +826,10 @@ fn compare_symbols(
                     }],
                 });
             }
+
+            if old_sym.symbol_type == SymbolType::Struct {
+                changes.extend(field_changes(old_sym, new_sym));
+            }
         }
     }
 
@@ -705,6 +839,82 @@ fn compare_symbols(
     Ok(changes)
 }
 
+/// Fields added to or removed from a struct, one change per field, named
+/// `Struct.field`. Fields are paired by name, so reordering reports nothing.
+fn field_changes(old_sym: &ExtractedSymbol, new_sym: &ExtractedSymbol) -> Vec<StructuralChange> {
+    let field_change = |change_type: ChangeType, field: &StructField| {
+        let visibility = if field.public { "public" } else { "private" };
+        let (before, after, from, to) = match change_type {
+            ChangeType::Removed => (
+                Some(field.declaration.clone()),
+                None,
+                Some(visibility),
+                None,
+            ),
+            _ => (
+                None,
+                Some(field.declaration.clone()),
+                None,
+                Some(visibility),
+            ),
+        };
+        StructuralChange {
+            change_type,
+            symbol_type: SymbolType::Struct,
+            name: format!("{}.{}", new_sym.name, field.name),
+            line: field.line,
+            before,
+            after,
+            details: vec![
+                ChangeDetail {
+                    kind: "field_name".to_string(),
+                    name: Some(field.name.clone()),
+                    from: None,
+                    to: None,
+                },
+                ChangeDetail {
+                    kind: "visibility".to_string(),
+                    name: None,
+                    from: from.map(str::to_string),
+                    to: to.map(str::to_string),
+                },
+            ],
+        }
+    };
+    let has_field =
+        |fields: &[StructField], name: &str| fields.iter().any(|field| field.name == name);
+
+    let removed = old_sym
+        .fields
+        .iter()
+        .filter(|field| !has_field(&new_sym.fields, &field.name))
+        .map(|field| field_change(ChangeType::Removed, field));
+    let added = new_sym
+        .fields
+        .iter()
+        .filter(|field| !has_field(&old_sym.fields, &field.name))
+        .map(|field| field_change(ChangeType::Added, field));
+    removed.chain(added).collect()
+}
+
+/// The field a struct field change is about, if `change` is one
+fn changed_field_name(change: &StructuralChange) -> Option<&str> {
+    change
+        .details
+        .iter()
+        .find(|detail| detail.kind == "field_name")
+        .and_then(|detail| detail.name.as_deref())
+}
+
+fn is_public_field_removal(change: &StructuralChange) -> bool {
+    change.change_type == ChangeType::Removed
+        && changed_field_name(change).is_some()
+        && change
+            .details
+            .iter()
+            .any(|detail| detail.kind == "visibility" && detail.from.as_deref() == Some("public"))
+}
+
 /// Analyze what specifically changed in a signature
 fn analyze_signature_changes(old_sig: Option<&str>, new_sig: Option<&str>) -> Vec<ChangeDetail> {
     let mut details = Vec::new();
@@ -847,12 +1057,19 @@ pub fn execute_parse_diff(arguments: &Value) -> Result<CallToolResult, io::Error
         )
     })?;
 
-    let compare_to = arguments["compare_to"]
-        .as_str()
-        .unwrap_or("HEAD")
-        .to_string();
-
-    let analysis = analyze_diff(file_path_str, compare_to)?;
+    let compare_file = arguments["compare_file"].as_str();
+    let analysis = match (compare_file, arguments["compare_to"].as_str()) {
+        (Some(compare_file), None) => analyze_file_diff(file_path_str, compare_file)?,
+        (None, compare_to) => {
+            analyze_diff(file_path_str, compare_to.unwrap_or("HEAD").to_string())?
+        }
+        (Some(_), Some(_)) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Provide at most one of 'compare_to' or 'compare_file'",
+            ))
+        }
+    };
 
     let header = "type|name|line|change";
     let changes = analysis
@@ -884,6 +1101,128 @@ pub fn execute_parse_diff(arguments: &Value) -> Result<CallToolResult, io::Error
     Ok(CallToolResult::success(result_json))
 }
 
+// ============================================================================
+// batch_diff Implementation
+// ============================================================================
+
+pub fn execute_batch_diff(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let file_paths: Vec<String> = arguments["file_paths"]
+        .as_array()
+        .and_then(|paths| {
+            paths
+                .iter()
+                .map(|path| path.as_str().map(str::to_string))
+                .collect()
+        })
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Missing or invalid 'file_paths' argument",
+            )
+        })?;
+
+    let compare_to = arguments["compare_to"].as_str().unwrap_or("HEAD");
+
+    log::info!(
+        "Analyzing diff for {} files against {compare_to}",
+        file_paths.len()
+    );
+
+    let batch = batch_diff(&file_paths, compare_to);
+
+    let changes = batch
+        .files
+        .iter()
+        .flat_map(|analysis| {
+            analysis.structural_changes.iter().map(|c| {
+                let line = c.line.to_string();
+                let change = format_change(c);
+                format::format_row(&[
+                    &analysis.file_path,
+                    abbreviate_symbol_type(&c.symbol_type),
+                    &c.name,
+                    &line,
+                    &change,
+                ])
+            })
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut result = json!({
+        "cmp": compare_to,
+        "h": "file|type|name|line|change",
+        "changes": changes,
+        "summary": batch.summary,
+    });
+
+    if !batch.errors.is_empty() {
+        let errors = batch
+            .errors
+            .iter()
+            .map(|error| format::format_row(&[&error.file_path, &error.error]))
+            .collect::<Vec<_>>()
+            .join("\n");
+        result["eh"] = json!("file|error");
+        result["errors"] = json!(errors);
+    }
+
+    let result_json = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(result_json))
+}
+
+/// Diff every file against `compare_to` in parallel, keeping input order.
+pub fn batch_diff(file_paths: &[String], compare_to: &str) -> BatchDiff {
+    let results: Vec<Result<DiffAnalysis, io::Error>> = file_paths
+        .par_iter()
+        .map(|file_path| analyze_diff(file_path, compare_to.to_string()))
+        .collect();
+
+    let mut files = Vec::new();
+    let mut errors = Vec::new();
+    for (file_path, result) in file_paths.iter().zip(results) {
+        match result {
+            Ok(analysis) => files.push(analysis),
+            Err(e) => errors.push(BatchDiffError {
+                file_path: path_utils::to_relative_path(file_path),
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    let summary = BatchDiffSummary {
+        added: files.iter().map(|a| a.summary.added).sum(),
+        removed: files.iter().map(|a| a.summary.removed).sum(),
+        modified: files.iter().map(|a| a.summary.modified).sum(),
+        has_breaking_changes: files
+            .iter()
+            .flat_map(|a| &a.structural_changes)
+            .any(is_breaking_change),
+    };
+
+    BatchDiff {
+        files,
+        summary,
+        errors,
+    }
+}
+
+fn is_breaking_change(change: &StructuralChange) -> bool {
+    match change.change_type {
+        ChangeType::SignatureChanged => true,
+        ChangeType::Removed => {
+            changed_field_name(change).is_none() || is_public_field_removal(change)
+        }
+        ChangeType::Added | ChangeType::BodyChanged => false,
+    }
+}
+
 // ============================================================================
 // affected_by_diff Implementation
 // ============================================================================
@@ -933,11 +1272,12 @@ pub fn execute_affected_by_diff(arguments: &Value) -> Result<CallToolResult, io:
         collect_affected_changes_for(file_path, &search_path, &diff_analysis.structural_changes)?;
     let affected_rows = affected_rows(&affected_changes);
 
-    let result = json!({
+    let mut result = json!({
         "p": diff_analysis.file_path,
         "h": "symbol|change|file|line|risk",
         "affected": affected_rows,
     });
+    insert_mismatch_rows(&mut result, &affected_changes);
 
     let result_json = serde_json::to_string(&result).map_err(|e| {
         io::Error::new(
@@ -995,7 +1335,7 @@ pub fn execute_preview_impact(arguments: &Value) -> Result<CallToolResult, io::E
     };
 
     let details = analyze_signature_changes(Some(&before_signature), Some(new_signature));
-    let result = json!({
+    let mut result = json!({
         "p": path_utils::to_relative_path(file_path_str),
         "sym": current_symbol.name,
         "before": before_signature,
@@ -1005,6 +1345,7 @@ pub fn execute_preview_impact(arguments: &Value) -> Result<CallToolResult, io::E
         "h": "symbol|change|file|line|risk",
         "affected": affected_rows(&affected_changes),
     });
+    insert_mismatch_rows(&mut result, &affected_changes);
 
     let result_json = serde_json::to_string(&result).map_err(|e| {
         io::Error::new(
@@ -1022,6 +1363,59 @@ pub(crate) fn analyze_diff(
 ) -> Result<DiffAnalysis, io::Error> {
     log::info!("Analyzing diff for: {file_path_str} against {compare_to}");
 
+    let file_path = Path::new(file_path_str);
+    let language = diff_file_language(file_path_str)?;
+
+    let current_content = path_utils::read_source_file(file_path)?;
+    let old_content = get_git_file_content(file_path, &compare_to)?;
+    let compare_to_sha = resolve_git_sha(&compare_to, file_path).ok();
+
+    diff_contents(
+        file_path_str,
+        language,
+        &old_content,
+        &current_content,
+        compare_to,
+        compare_to_sha,
+    )
+}
+
+/// Analyze `file_path_str` against another file on disk instead of a git
+/// revision, so no repository is needed. Both files must be in the same
+/// language.
+pub(crate) fn analyze_file_diff(
+    file_path_str: &str,
+    compare_file: &str,
+) -> Result<DiffAnalysis, io::Error> {
+    log::info!("Analyzing diff for: {file_path_str} against file {compare_file}");
+
+    let language = diff_file_language(file_path_str)?;
+    let compare_language = diff_file_language(compare_file)?;
+    if compare_language != language {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "Cannot compare a {} file with a {} file",
+                language.name(),
+                compare_language.name()
+            ),
+        ));
+    }
+
+    let current_content = path_utils::read_source_file(file_path_str)?;
+    let old_content = path_utils::read_source_file(compare_file)?;
+
+    diff_contents(
+        file_path_str,
+        language,
+        &old_content,
+        &current_content,
+        path_utils::to_relative_path(compare_file),
+        None,
+    )
+}
+
+fn diff_file_language(file_path_str: &str) -> Result<Language, io::Error> {
     let file_path = Path::new(file_path_str);
 
     if !file_path.exists() {
@@ -1031,45 +1425,51 @@ pub(crate) fn analyze_diff(
         ));
     }
 
-    let language = detect_language(file_path).map_err(|e| {
+    detect_language(file_path).map_err(|e| {
         io::Error::new(
             io::ErrorKind::Unsupported,
             format!("Cannot detect language: {e}"),
         )
-    })?;
-
-    let current_content = std::fs::read_to_string(file_path)?;
-    let old_content = get_git_file_content(file_path, &compare_to)?;
+    })
+}
 
-    let old_tree = parse_code(&old_content, language).map_err(|e| {
+fn diff_contents(
+    file_path_str: &str,
+    language: Language,
+    old_content: &str,
+    current_content: &str,
+    compare_to: String,
+    compare_to_sha: Option<String>,
+) -> Result<DiffAnalysis, io::Error> {
+    let old_tree = parse_code(old_content, language).map_err(|e| {
         io::Error::new(
             io::ErrorKind::InvalidData,
             format!("Failed to parse old version: {e}"),
         )
     })?;
 
-    let new_tree = parse_code(&current_content, language).map_err(|e| {
+    let new_tree = parse_code(current_content, language).map_err(|e| {
         io::Error::new(
             io::ErrorKind::InvalidData,
             format!("Failed to parse current version: {e}"),
         )
     })?;
 
-    let old_symbols = extract_symbols(&old_tree, &old_content, language)?;
-    let new_symbols = extract_symbols(&new_tree, &current_content, language)?;
+    let old_symbols = extract_symbols(&old_tree, old_content, language)?;
+    let new_symbols = extract_symbols(&new_tree, current_content, language)?;
 
     let structural_changes =
-        compare_symbols(&old_symbols, &new_symbols, &old_content, &current_content)?;
+        compare_symbols(&old_symbols, &new_symbols, old_content, current_content)?;
 
-    let summary = DiffSummary {
-        added: structural_changes
-            .iter()
-            .filter(|c| c.change_type == ChangeType::Added)
-            .count(),
-        removed: structural_changes
+    let count = |change_type: ChangeType, field: bool| {
+        structural_changes
             .iter()
-            .filter(|c| c.change_type == ChangeType::Removed)
-            .count(),
+            .filter(|c| c.change_type == change_type && changed_field_name(c).is_some() == field)
+            .count()
+    };
+    let summary = DiffSummary {
+        added: count(ChangeType::Added, false),
+        removed: count(ChangeType::Removed, false),
         modified: structural_changes
             .iter()
             .filter(|c| {
@@ -1077,10 +1477,10 @@ pub(crate) fn analyze_diff(
                     || c.change_type == ChangeType::BodyChanged
             })
             .count(),
+        fields_added: count(ChangeType::Added, true),
+        fields_removed: count(ChangeType::Removed, true),
     };
 
-    let compare_to_sha = resolve_git_sha(&compare_to, file_path).ok();
-
     Ok(DiffAnalysis {
         file_path: path_utils::to_relative_path(file_path_str),
         compare_to,
@@ -1243,6 +1643,14 @@ fn assess_risk(
     usage_type: &str,
     confidence: MatchConfidence,
 ) -> (RiskLevel, String) {
+    // Field accesses are matched by name only, so confidence says little here.
+    if is_public_field_removal(change) {
+        return (
+            RiskLevel::High,
+            "Public field removed - field access and struct literals will break".to_string(),
+        );
+    }
+
     if confidence == MatchConfidence::Low {
         return (
             RiskLevel::Low,
@@ -1300,6 +1708,164 @@ fn assess_risk(
     }
 }
 
+/// Spell out a concrete type conflict for a call site of a changed signature.
+///
+/// Parameter types come from the `parameter_changed` details; the argument is
+/// read from the call in the usage's context code. Returns `None` when the
+/// argument already matches the new type or cannot be located.
+fn type_mismatch_hint(
+    change: &StructuralChange,
+    usage_type: &str,
+    line: usize,
+    code: &str,
+) -> Option<String> {
+    if change.change_type != ChangeType::SignatureChanged || usage_type != "call" {
+        return None;
+    }
+
+    let new_params = extract_parameters(change.after.as_deref()?);
+    // Receivers are not written inside the argument list of a method call.
+    let receiver_offset = usize::from(new_params.first().is_some_and(|p| is_receiver_param(p)));
+    // Context starts up to USAGE_CONTEXT_LINES before the usage; search from its own line.
+    let leading = line.saturating_sub(1).min(USAGE_CONTEXT_LINES as usize);
+    let call_code = code.lines().skip(leading).collect::<Vec<_>>().join("\n");
+    let args = call_arguments(&call_code, &change.name)?;
+
+    for detail in change
+        .details
+        .iter()
+        .filter(|d| d.kind == "parameter_changed")
+    {
+        let index: usize = detail
+            .name
+            .as_deref()?
+            .strip_prefix("param_")?
+            .parse()
+            .ok()?;
+        let Some(arg) = index.checked_sub(receiver_offset).and_then(|i| args.get(i)) else {
+            continue;
+        };
+
+        let old_type = parameter_type(detail.from.as_deref()?);
+        let new_type = parameter_type(detail.to.as_deref()?);
+        if old_type == new_type || new_type.is_empty() {
+            continue;
+        }
+
+        let hint = match infer_argument_type(arg) {
+            Some(arg_type) if arg_type == new_type => continue,
+            Some(arg_type) => format!(
+                "call at line {line} passes `{arg_type}` but signature now expects `{new_type}`"
+            ),
+            None => format!(
+                "call at line {line} passes `{arg}` (written for `{old_type}`) but signature now expects `{new_type}`"
+            ),
+        };
+        return Some(hint);
+    }
+
+    None
+}
+
+fn is_receiver_param(param: &str) -> bool {
+    matches!(
+        param.trim(),
+        "self" | "&self" | "&mut self" | "mut self" | "cls"
+    ) || param.trim().starts_with("self:")
+}
+
+/// Type part of a parameter: `name: Type = default` or Go-style `name Type`.
+fn parameter_type(param: &str) -> String {
+    let param = param.split('=').next().unwrap_or(param).trim();
+    match param.split_once(':') {
+        Some((_, ty)) => ty.trim().to_string(),
+        None => param
+            .split_once(char::is_whitespace)
+            .map(|(_, ty)| ty.trim().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Arguments of the first `name(...)` call in `code`.
+fn call_arguments(code: &str, name: &str) -> Option<Vec<String>> {
+    let needle = format!("{name}(");
+    let start = code
+        .match_indices(&needle)
+        .find(|(index, _)| {
+            code[..*index]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !(c.is_alphanumeric() || c == '_'))
+        })
+        .map(|(index, _)| index + name.len())?;
+
+    let mut depth = 0;
+    let mut end = None;
+    for (offset, c) in code[start..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    end = Some(start + offset);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(extract_parameters(&code[start..=end?]))
+}
+
+/// Best-effort static type of a call argument expression.
+fn infer_argument_type(arg: &str) -> Option<String> {
+    let arg = arg.trim();
+    let ty = if arg.ends_with(".to_string()")
+        || arg.ends_with(".to_owned()")
+        || arg.starts_with("String::")
+        || arg.starts_with("format!(")
+    {
+        "String"
+    } else if arg.starts_with('"') && arg.ends_with('"') {
+        "&str"
+    } else if arg.starts_with('\'') || arg.starts_with('`') {
+        "string"
+    } else if arg == "true" || arg == "false" {
+        "bool"
+    } else if arg.starts_with("vec![") {
+        "Vec<_>"
+    } else if !arg.is_empty() && arg.chars().all(|c| c.is_ascii_digit() || c == '_') {
+        "i32"
+    } else {
+        return None;
+    };
+    Some(ty.to_string())
+}
+
+/// Add `mh`/`mismatch` keys when any affected call has a type mismatch hint.
+fn insert_mismatch_rows(result: &mut Value, affected_changes: &[AffectedChange]) {
+    let rows = mismatch_rows(affected_changes);
+    if !rows.is_empty() {
+        result["mh"] = json!("file|line|hint");
+        result["mismatch"] = json!(rows);
+    }
+}
+
+/// Rows for usages that carry a type mismatch hint.
+fn mismatch_rows(affected_changes: &[AffectedChange]) -> String {
+    affected_changes
+        .iter()
+        .flat_map(|chg| chg.potentially_affected.iter())
+        .filter_map(|u| {
+            let hint = u.type_mismatch_hint.as_deref()?;
+            let line = u.line.to_string();
+            Some(format::format_row(&[&u.file, &line, hint]))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Helper to extract text from CallToolResult
 /// Uses JSON serialization approach consistent with tests/common/mod.rs
 fn get_result_text(result: &CallToolResult) -> String {
@@ -1320,7 +1886,8 @@ fn resolve_search_path(scope: Option<&str>, file_path: &Path) -> std::path::Path
     if let Some(scope) = scope {
         Path::new(scope).to_path_buf()
     } else {
-        find_project_root(file_path)
+        path_utils::find_workspace_root(file_path)
+            .or_else(|| find_project_root(file_path))
             .unwrap_or_else(|| file_path.parent().unwrap_or(Path::new(".")).to_path_buf())
     }
 }
@@ -1334,14 +1901,17 @@ fn collect_affected_changes_for(
     let rel_changed_file = path_utils::to_relative_path(&file_path.to_string_lossy());
 
     for change in changes {
-        if change.change_type == ChangeType::Removed {
+        // Removed symbols have no usages left to find, but code may still
+        // access a removed field.
+        let field_name = changed_field_name(change);
+        if change.change_type == ChangeType::Removed && field_name.is_none() {
             continue;
         }
 
         let usages_args = serde_json::json!({
-            "symbol": change.name,
+            "symbol": field_name.unwrap_or(&change.name),
             "path": search_path.to_str().unwrap_or("."),
-            "context_lines": 1
+            "context_lines": USAGE_CONTEXT_LINES
         });
 
         let usages_result = crate::analysis::find_usages::execute(&usages_args)?;
@@ -1394,6 +1964,7 @@ fn collect_affected_changes_for(
                 &rel_changed_file,
             );
             let (risk, reason) = assess_risk(change, usage_type, match_confidence);
+            let type_mismatch_hint = type_mismatch_hint(change, usage_type, usage_line, context);
 
             potentially_affected.push(AffectedUsage {
                 file: usage_file.to_string(),
@@ -1404,6 +1975,7 @@ fn collect_affected_changes_for(
                 confidence: match_confidence,
                 risk,
                 reason,
+                type_mismatch_hint,
             });
         }
 
@@ -1460,7 +2032,7 @@ fn find_symbol_in_current_file(
             format!("Cannot detect language: {e}"),
         )
     })?;
-    let current_content = fs::read_to_string(file_path)?;
+    let current_content = path_utils::read_source_file(file_path)?;
     let tree = parse_code(&current_content, language).map_err(|e| {
         io::Error::new(
             io::ErrorKind::InvalidData,
@@ -0,0 +1,245 @@
+//! Django Model Extraction
+//!
+//! Recognizes classes deriving from `models.Model` (or from another model
+//! declared earlier in the same file) and reads their field assignments,
+//! e.g. `author = models.ForeignKey(Author, on_delete=models.CASCADE)`.
+//! Python type extraction uses this to report model fields with their
+//! Django field type instead of `Any`.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use tree_sitter::Node;
+
+use crate::extraction::types::{Field, TypeDefinition};
+use crate::parser::{parse_code, Language};
+
+/// Field options worth surfacing, in display order
+const KEY_OPTIONS: [&str; 5] = ["to", "on_delete", "max_length", "related_name", "null"];
+
+/// Relation fields whose first positional argument is the target model
+const RELATION_FIELDS: [&str; 3] = ["ForeignKey", "OneToOneField", "ManyToManyField"];
+
+/// A class deriving from `django.db.models.Model`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DjangoModel {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub fields: Vec<DjangoField>,
+}
+
+/// A model field assignment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DjangoField {
+    pub name: String,
+    /// Django field class, e.g. `CharField` or `ForeignKey`
+    pub field_type: String,
+    /// Key options from [`KEY_OPTIONS`] in that order; string values are unquoted
+    pub options: Vec<(String, String)>,
+}
+
+impl DjangoField {
+    /// `ForeignKey(to=Author, on_delete=models.CASCADE)`
+    pub fn type_annotation(&self) -> String {
+        if self.options.is_empty() {
+            return self.field_type.clone();
+        }
+        let options = self
+            .options
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{}({options})", self.field_type)
+    }
+}
+
+/// Django models declared in a Python source file, in source order.
+pub fn extract_django_models(source: &str, file_path: &Path) -> Vec<DjangoModel> {
+    let Ok(tree) = parse_code(source, Language::Python) else {
+        return Vec::new();
+    };
+
+    let mut model_names = HashSet::new();
+    let mut models = Vec::new();
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        let class = match node.kind() {
+            "class_definition" => node,
+            "decorated_definition" => match node.child_by_field_name("definition") {
+                Some(definition) if definition.kind() == "class_definition" => definition,
+                _ => continue,
+            },
+            _ => continue,
+        };
+        let Some(name) = class.child_by_field_name("name").map(|n| text(n, source)) else {
+            continue;
+        };
+        if !derives_from_model(class, source, &model_names) {
+            continue;
+        }
+
+        model_names.insert(name.to_string());
+        models.push(DjangoModel {
+            name: name.to_string(),
+            file: file_path.to_path_buf(),
+            line: class.start_position().row + 1,
+            fields: model_fields(class, source),
+        });
+    }
+    models
+}
+
+/// Replace the generic class fields of Django models in `definitions` with
+/// their model fields and tag them with `framework: django`.
+pub(crate) fn annotate_django_models(
+    source: &str,
+    file_path: &Path,
+    definitions: &mut [TypeDefinition],
+) {
+    // Other ORMs also name their base `Model`; only files importing Django count.
+    if !source.contains("django") {
+        return;
+    }
+
+    for model in extract_django_models(source, file_path) {
+        let Some(definition) = definitions
+            .iter_mut()
+            .find(|definition| definition.name == model.name && definition.line == model.line)
+        else {
+            continue;
+        };
+
+        definition.fields = (!model.fields.is_empty()).then(|| {
+            model
+                .fields
+                .iter()
+                .map(|field| Field {
+                    name: field.name.clone(),
+                    type_annotation: field.type_annotation(),
+                    resolved_to: None,
+                })
+                .collect()
+        });
+        definition
+            .framework_metadata
+            .get_or_insert_with(BTreeMap::new)
+            .insert("framework".to_string(), "django".to_string());
+    }
+}
+
+fn derives_from_model(class: Node, source: &str, model_names: &HashSet<String>) -> bool {
+    let Some(superclasses) = class.child_by_field_name("superclasses") else {
+        return false;
+    };
+    let mut cursor = superclasses.walk();
+    let derives = superclasses
+        .named_children(&mut cursor)
+        .any(|base| match text(base, source) {
+            "models.Model" | "Model" => true,
+            name => model_names.contains(name),
+        });
+    derives
+}
+
+fn model_fields(class: Node, source: &str) -> Vec<DjangoField> {
+    let Some(body) = class.child_by_field_name("body") else {
+        return Vec::new();
+    };
+
+    let mut fields = Vec::new();
+    let mut cursor = body.walk();
+    for statement in body
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "expression_statement")
+    {
+        let Some(assignment) = statement
+            .named_child(0)
+            .filter(|node| node.kind() == "assignment")
+        else {
+            continue;
+        };
+        let Some(name) = assignment
+            .child_by_field_name("left")
+            .filter(|left| left.kind() == "identifier")
+            .map(|left| text(left, source))
+        else {
+            continue;
+        };
+        let Some(call) = assignment
+            .child_by_field_name("right")
+            .filter(|right| right.kind() == "call")
+        else {
+            continue;
+        };
+        if let Some(field) = model_field(name, call, source) {
+            fields.push(field);
+        }
+    }
+    fields
+}
+
+fn model_field(name: &str, call: Node, source: &str) -> Option<DjangoField> {
+    let function = call.child_by_field_name("function")?;
+    let field_type = match function.kind() {
+        "attribute" => text(function.child_by_field_name("attribute")?, source),
+        "identifier" => text(function, source),
+        _ => return None,
+    };
+    if !field_type.ends_with("Field") && !RELATION_FIELDS.contains(&field_type) {
+        return None;
+    }
+
+    let mut values = BTreeMap::new();
+    if let Some(arguments) = call.child_by_field_name("arguments") {
+        let mut cursor = arguments.walk();
+        let mut positional = 0;
+        for argument in arguments.named_children(&mut cursor) {
+            if argument.kind() == "keyword_argument" {
+                let key = argument
+                    .child_by_field_name("name")
+                    .map(|n| text(n, source));
+                let value = argument.child_by_field_name("value");
+                if let (Some(key), Some(value)) = (key, value) {
+                    values.insert(key, option_value(value, source));
+                }
+                continue;
+            }
+
+            // ForeignKey(Author, models.CASCADE)
+            if RELATION_FIELDS.contains(&field_type) {
+                match positional {
+                    0 => values.insert("to", option_value(argument, source)),
+                    1 => values.insert("on_delete", option_value(argument, source)),
+                    _ => None,
+                };
+            }
+            positional += 1;
+        }
+    }
+
+    let options = KEY_OPTIONS
+        .iter()
+        .filter_map(|key| Some((key.to_string(), values.remove(key)?)))
+        .collect();
+    Some(DjangoField {
+        name: name.to_string(),
+        field_type: field_type.to_string(),
+        options,
+    })
+}
+
+fn option_value(node: Node, source: &str) -> String {
+    let value = text(node, source);
+    if node.kind() == "string" {
+        value.trim_matches(['\'', '"']).to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn text<'a>(node: Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
@@ -0,0 +1,278 @@
+//! FastAPI Routes Tool
+//!
+//! Finds path operations registered with decorators such as
+//! `@app.get("/items/{item_id}")` or `@router.post("/users")` on `FastAPI`
+//! and `APIRouter` instances. The receiver name is not checked, so any
+//! `<name>.<method>(path)` decorator counts.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "h": "method|path|function|file|line|params",
+//!   "routes": "GET|/items/{item_id}|read_item|app/main.py|12|item_id: int, q: str | None\n..."
+//! }
+//! ```
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{parse_code, Language};
+
+const ROUTES_HEADER: &str = "method|path|function|file|line|params";
+
+/// Decorator attributes that register a path operation
+const ROUTE_METHODS: [&str; 9] = [
+    "get",
+    "post",
+    "put",
+    "patch",
+    "delete",
+    "head",
+    "options",
+    "trace",
+    "websocket",
+];
+
+/// A path operation registered with a FastAPI decorator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastApiRoute {
+    /// Upper-case HTTP method, or `WEBSOCKET`
+    pub method: String,
+    pub path: String,
+    /// Name of the decorated handler
+    pub function: String,
+    /// Receiver of the decorator, e.g. `app` or `router`
+    pub router: String,
+    pub file: PathBuf,
+    /// 1-based line of the decorator
+    pub line: usize,
+    pub parameters: Vec<RouteParameter>,
+}
+
+/// A handler parameter and its annotation, if any
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteParameter {
+    pub name: String,
+    pub type_annotation: Option<String>,
+}
+
+pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    log::info!("Listing FastAPI routes in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let mut rows = Vec::new();
+    for file in collect_project_files(path)? {
+        if file.extension().and_then(|ext| ext.to_str()) != Some("py") {
+            continue;
+        }
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        // Cheap pre-filter: every route needs a decorator
+        if !source.contains('@') {
+            continue;
+        }
+
+        let relative = path_utils::to_relative_path(&file.to_string_lossy());
+        for route in extract_fastapi_routes(&source, &file) {
+            rows.push(format::format_row(&[
+                &route.method,
+                &route.path,
+                &route.function,
+                &relative,
+                &route.line.to_string(),
+                &format_parameters(&route.parameters),
+            ]));
+        }
+    }
+
+    let result = json!({
+        "h": ROUTES_HEADER,
+        "routes": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Path operations declared in a Python source file, in source order.
+///
+/// A handler with several route decorators yields one route per decorator.
+pub fn extract_fastapi_routes(source: &str, file_path: &Path) -> Vec<FastApiRoute> {
+    let Ok(tree) = parse_code(source, Language::Python) else {
+        return Vec::new();
+    };
+
+    let mut routes = Vec::new();
+    collect_routes(tree.root_node(), source, file_path, &mut routes);
+    routes
+}
+
+fn collect_routes(node: Node, source: &str, file_path: &Path, routes: &mut Vec<FastApiRoute>) {
+    if node.kind() == "decorated_definition" {
+        if let Some(function) = node
+            .child_by_field_name("definition")
+            .filter(|definition| definition.kind() == "function_definition")
+        {
+            let name = function
+                .child_by_field_name("name")
+                .map(|name| text(name, source).to_string())
+                .unwrap_or_default();
+            let parameters = function
+                .child_by_field_name("parameters")
+                .map(|parameters| route_parameters(parameters, source))
+                .unwrap_or_default();
+
+            let mut cursor = node.walk();
+            for decorator in node
+                .named_children(&mut cursor)
+                .filter(|child| child.kind() == "decorator")
+            {
+                if let Some((router, method, path)) = route_decorator(decorator, source) {
+                    routes.push(FastApiRoute {
+                        method,
+                        path,
+                        function: name.clone(),
+                        router,
+                        file: file_path.to_path_buf(),
+                        line: decorator.start_position().row + 1,
+                        parameters: parameters.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_routes(child, source, file_path, routes);
+    }
+}
+
+/// `(router, METHOD, path)` for `@router.method("/path", ...)`
+fn route_decorator(decorator: Node, source: &str) -> Option<(String, String, String)> {
+    let call = decorator
+        .named_child(0)
+        .filter(|child| child.kind() == "call")?;
+    let function = call
+        .child_by_field_name("function")
+        .filter(|function| function.kind() == "attribute")?;
+
+    let method = text(function.child_by_field_name("attribute")?, source);
+    if !ROUTE_METHODS.contains(&method) {
+        return None;
+    }
+    let router = text(function.child_by_field_name("object")?, source);
+    let path = route_path(call.child_by_field_name("arguments")?, source)?;
+
+    Some((router.to_string(), method.to_uppercase(), path))
+}
+
+/// First positional string argument, or the `path=` keyword argument.
+fn route_path(arguments: Node, source: &str) -> Option<String> {
+    let mut cursor = arguments.walk();
+    let mut positional = None;
+    let mut keyword = None;
+    for argument in arguments.named_children(&mut cursor) {
+        match argument.kind() {
+            "string" if positional.is_none() => positional = string_value(argument, source),
+            "keyword_argument" => {
+                let name = argument
+                    .child_by_field_name("name")
+                    .map(|n| text(n, source));
+                if name == Some("path") {
+                    keyword = argument
+                        .child_by_field_name("value")
+                        .and_then(|value| string_value(value, source));
+                }
+            }
+            _ => {}
+        }
+    }
+    positional.or(keyword)
+}
+
+pub(crate) fn string_value(node: Node, source: &str) -> Option<String> {
+    if node.kind() != "string" {
+        return None;
+    }
+    let mut cursor = node.walk();
+    let content: String = node
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "string_content")
+        .map(|child| text(child, source))
+        .collect();
+    Some(content)
+}
+
+fn route_parameters(parameters: Node, source: &str) -> Vec<RouteParameter> {
+    let mut cursor = parameters.walk();
+    parameters
+        .named_children(&mut cursor)
+        .filter_map(|parameter| match parameter.kind() {
+            "identifier" => Some(RouteParameter {
+                name: text(parameter, source).to_string(),
+                type_annotation: None,
+            }),
+            "default_parameter" => Some(RouteParameter {
+                name: text(parameter.child_by_field_name("name")?, source).to_string(),
+                type_annotation: None,
+            }),
+            "typed_parameter" => Some(RouteParameter {
+                name: text(parameter.named_child(0)?, source).to_string(),
+                type_annotation: parameter
+                    .child_by_field_name("type")
+                    .map(|ty| text(ty, source).to_string()),
+            }),
+            "typed_default_parameter" => Some(RouteParameter {
+                name: text(parameter.child_by_field_name("name")?, source).to_string(),
+                type_annotation: parameter
+                    .child_by_field_name("type")
+                    .map(|ty| text(ty, source).to_string()),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn format_parameters(parameters: &[RouteParameter]) -> String {
+    parameters
+        .iter()
+        .map(|parameter| match &parameter.type_annotation {
+            Some(ty) => format!("{}: {ty}", parameter.name),
+            None => parameter.name.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn text<'a>(node: Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
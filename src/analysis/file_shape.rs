@@ -11,6 +11,7 @@
 use crate::analysis::dependencies::{
     find_js_ts_dependencies, find_python_dependencies, find_rust_dependencies,
 };
+use crate::analysis::path_utils;
 use crate::parser::{detect_language, parse_code, Language};
 use std::collections::HashSet;
 use std::fs;
@@ -19,7 +20,6 @@ use std::path::{Path, PathBuf};
 use streaming_iterator::StreamingIterator;
 use tree_sitter::{Query, QueryCursor, Tree};
 
-#[allow(dead_code)]
 const MAX_TEMPLATE_DEPTH: usize = 50;
 
 #[derive(Debug, serde::Serialize)]
@@ -371,7 +371,7 @@ fn build_shape_tree(
     let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
     if visited.contains(&canonical) {
         // Already processed – just return the flat shape
-        let source = fs::read_to_string(path).map_err(|e| {
+        let source = path_utils::read_source_file(path).map_err(|e| {
             io::Error::new(
                 io::ErrorKind::NotFound,
                 format!("Failed to read file {}: {e}", path.display()),
@@ -397,7 +397,7 @@ fn build_shape_tree(
     }
     visited.insert(canonical);
 
-    let source = fs::read_to_string(path).map_err(|e| {
+    let source = path_utils::read_source_file(path).map_err(|e| {
         io::Error::new(
             io::ErrorKind::NotFound,
             format!("Failed to read file {}: {e}", path.display()),
@@ -440,7 +440,7 @@ fn build_shape_tree(
                     deps.push(dep_shape);
                 }
             }
-            Language::JavaScript | Language::TypeScript => {
+            Language::JavaScript | Language::TypeScript | Language::Jsx | Language::Tsx => {
                 for dep_path in find_js_ts_dependencies(&source, path, project_root) {
                     let dep_shape =
                         build_shape_tree(&dep_path, project_root, include_deps, visited)?;
@@ -504,11 +504,25 @@ pub struct MergedTemplateShape {
     pub path: String,
     pub merged_content: String,
     pub dependencies: Vec<TemplateDependency>,
+    /// Whether following `dependencies` leads back to this template
+    pub has_cycle: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub template_structs: Option<Vec<crate::analysis::askama::TemplateStructInfo>>,
 }
 
-/// Find template dependencies (extends/includes) in a template file
+/// Directives that pull another template in. Askama and Tera share the
+/// Jinja-style syntax, including `{% import "macros.html" as m %}`.
+const TEMPLATE_DEPENDENCY_DIRECTIVES: [&str; 3] = ["extends", "include", "import"];
+
+/// Regex matching `{% <directive> "name.html" ... %}`, capturing the name
+fn template_directive_regex(directive: &str) -> Regex {
+    Regex::new(&format!(
+        r#"\{{%-?\s*{directive}\s+["']([^"']+)["'][^%]*-?%\}}"#
+    ))
+    .unwrap()
+}
+
+/// Find template dependencies (extends/includes/imports) in a template file
 ///
 /// Returns a list of template dependencies with their types and paths.
 #[allow(dead_code)]
@@ -518,40 +532,104 @@ pub fn find_template_dependencies(
 ) -> Result<Vec<TemplateDependency>, io::Error> {
     let mut dependencies = Vec::new();
 
-    // Regex for {% extends "base.html" %}
-    let extends_re = Regex::new(r#"\{%\s*extends\s+["']([^"']+)["']\s*%\}"#).unwrap();
-    // Regex for {% include "partial.html" %}
-    let include_re = Regex::new(r#"\{%\s*include\s+["']([^"']+)["']\s*%\}"#).unwrap();
-
-    // Find extends
-    for cap in extends_re.captures_iter(source) {
-        let template_name = &cap[1];
-        let template_path = templates_dir.join(template_name);
-        // Only include if the template file exists
-        if template_path.exists() {
-            dependencies.push(TemplateDependency {
-                path: template_name.to_string(),
-                dependency_type: "extends".to_string(),
-                name: template_name.to_string(),
-            });
+    for directive in TEMPLATE_DEPENDENCY_DIRECTIVES {
+        for cap in template_directive_regex(directive).captures_iter(source) {
+            let template_name = &cap[1];
+            let template_path = templates_dir.join(template_name);
+            // Only include if the template file exists
+            if template_path.exists() {
+                dependencies.push(TemplateDependency {
+                    path: template_name.to_string(),
+                    dependency_type: directive.to_string(),
+                    name: template_name.to_string(),
+                });
+            }
         }
     }
 
-    // Find includes
-    for cap in include_re.captures_iter(source) {
-        let template_name = &cap[1];
-        let template_path = templates_dir.join(template_name);
-        // Only include if the template file exists
-        if template_path.exists() {
-            dependencies.push(TemplateDependency {
-                path: template_name.to_string(),
-                dependency_type: "include".to_string(),
-                name: template_name.to_string(),
-            });
-        }
+    Ok(dependencies)
+}
+
+/// Follow the templates `template_path` extends, includes or imports,
+/// depth-first, adding each one reached to `visited`.
+///
+/// A template that pulls itself back in is an error naming the chain, e.g.
+/// `Circular template dependency: base.html -> layout.html -> base.html`.
+/// Chains deeper than [`MAX_TEMPLATE_DEPTH`] are not followed further, with
+/// a warning.
+pub fn resolve_template_dependencies(
+    template_path: &Path,
+    templates_dir: &Path,
+    visited: &mut HashSet<PathBuf>,
+    recursion_stack: &mut Vec<PathBuf>,
+) -> Result<(), io::Error> {
+    let template_path = template_path
+        .canonicalize()
+        .unwrap_or_else(|_| template_path.to_path_buf());
+
+    if let Some(start) = recursion_stack.iter().position(|p| *p == template_path) {
+        return Err(circular_dependency_error(
+            &recursion_stack[start..],
+            &template_path,
+            templates_dir,
+        ));
+    }
+    // Reached before through another branch, e.g. a partial included twice
+    if !visited.insert(template_path.clone()) {
+        return Ok(());
+    }
+    if recursion_stack.len() >= MAX_TEMPLATE_DEPTH {
+        log::warn!(
+            "Template chain deeper than {MAX_TEMPLATE_DEPTH} at {}; not following further",
+            template_path.display()
+        );
+        return Ok(());
     }
 
-    Ok(dependencies)
+    let Ok(source) = path_utils::read_source_file(&template_path) else {
+        return Ok(());
+    };
+
+    recursion_stack.push(template_path);
+    for dependency in find_template_dependencies(&source, templates_dir)? {
+        resolve_template_dependencies(
+            &templates_dir.join(&dependency.path),
+            templates_dir,
+            visited,
+            recursion_stack,
+        )?;
+    }
+    recursion_stack.pop();
+
+    Ok(())
+}
+
+/// `chain` ends with the template that pulls in `repeated` again
+fn circular_dependency_error(
+    chain: &[PathBuf],
+    repeated: &Path,
+    templates_dir: &Path,
+) -> io::Error {
+    let templates_dir = templates_dir
+        .canonicalize()
+        .unwrap_or_else(|_| templates_dir.to_path_buf());
+    let cycle = chain
+        .iter()
+        .map(PathBuf::as_path)
+        .chain([repeated])
+        .map(|path| {
+            path.strip_prefix(&templates_dir)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(" -> ");
+
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Circular template dependency: {cycle}"),
+    )
 }
 
 /// Recursively merge a template with its parent templates and includes
@@ -565,31 +643,30 @@ fn merge_template(
     recursion_stack: &mut Vec<PathBuf>,
 ) -> Result<String, io::Error> {
     // Check for circular dependencies
-    if recursion_stack.contains(&template_path.to_path_buf()) {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!(
-                "Circular template dependency detected: {}",
-                template_path.display()
-            ),
+    if let Some(start) = recursion_stack.iter().position(|p| p == template_path) {
+        return Err(circular_dependency_error(
+            &recursion_stack[start..],
+            template_path,
+            templates_dir,
         ));
     }
 
-    // Check recursion depth
+    let source = path_utils::read_source_file(template_path)?;
+
+    // Too deep to keep merging: leave the remaining directives in place
     if recursion_stack.len() >= MAX_TEMPLATE_DEPTH {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("Template recursion depth exceeded (max: {MAX_TEMPLATE_DEPTH})"),
-        ));
+        log::warn!(
+            "Template chain deeper than {MAX_TEMPLATE_DEPTH} at {}; not merging further",
+            template_path.display()
+        );
+        return Ok(source);
     }
 
     recursion_stack.push(template_path.to_path_buf());
     visited.insert(template_path.to_path_buf());
 
-    let source = fs::read_to_string(template_path)?;
-
     // Check for {% extends "parent.html" %}
-    let extends_re = Regex::new(r#"\{%\s*extends\s+["']([^"']+)["']\s*%\}"#).unwrap();
+    let extends_re = template_directive_regex("extends");
     if let Some(cap) = extends_re.captures(&source) {
         let parent_name = &cap[1];
         let parent_path = templates_dir.join(parent_name);
@@ -608,7 +685,7 @@ fn merge_template(
     }
 
     // Handle {% include "partial.html" %}
-    let include_re = Regex::new(r#"\{%\s*include\s+["']([^"']+)["']\s*%\}"#).unwrap();
+    let include_re = template_directive_regex("include");
     let mut result = source.clone();
 
     for cap in include_re.captures_iter(&source) {
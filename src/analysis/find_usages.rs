@@ -1,6 +1,8 @@
 //! Find Usages Tool
 //!
-//! Searches for all usages of a symbol across files.
+//! Searches for all usages of a symbol across files. CSS class names are
+//! matched against `.class` selectors (`css_definition`) and HTML `class`
+//! attributes (`html_usage`).
 //!
 //! Breaking schema change (v1):
 //! ```json
@@ -10,25 +12,54 @@
 //!   "u": "src/main.rs|42|10|call|let x = parse(input)|main|low|\n..."
 //! }
 //! ```
+//!
+//! With `pattern` instead of `symbol`, every identifier matching the regex is
+//! reported and rows gain a trailing `sym` column with the matched name. The
+//! pattern must match the whole name unless it starts with `^` or ends with
+//! `$` (inline flags such as `(?i)` may come first):
+//! ```json
+//! {
+//!   "sym": "get_.*",
+//!   "h": "file|line|col|type|context|scope|conf|owner|sym",
+//!   "u": "src/api.rs|3|8|definition|fn get_user() {}||high||get_user\n..."
+//! }
+//! ```
+//!
+//! `find_trait_implementations` lists the Rust `impl Trait for Type` blocks
+//! of a trait, matched by its last path segment (`fmt::Display` matches
+//! `Display`). `bounds` joins the impl's generic bounds and `where`
+//! predicates with `; `; `kind` is `blanket` for impls on a type parameter,
+//! `conditional` for other bounded impls and `plain` otherwise:
+//! ```json
+//! {
+//!   "trait": "Display",
+//!   "h": "impl_type|trait|file|line|bounds|kind",
+//!   "impls": "Point|fmt::Display|src/point.rs|12||plain\nWrapper<T>|Display|src/wrap.rs|4|T: Display|conditional"
+//! }
+//! ```
 
-use std::fs;
 use std::io;
 use std::path::Path;
 
+use regex::Regex;
 use serde_json::json;
 use serde_json::Value;
 use tiktoken_rs::cl100k_base;
-use tree_sitter::{Node, Tree};
+use tree_sitter::{Node, Point, Tree};
 
 use crate::analysis::path_utils;
 use crate::common::budget;
 use crate::common::budget::BudgetTracker;
 use crate::common::compact::CompactOutput;
+use crate::common::format;
 use crate::common::project_files::collect_project_files;
+use crate::extraction::types::{collapse_whitespace, rust_generics, rust_where_bounds};
 use crate::mcp_types::{CallToolResult, CallToolResultExt};
-use crate::parser::{detect_language, parse_code, Language};
+use crate::parser::{cache, detect_language, Language};
 
 pub(crate) const USAGE_HEADER: &str = "file|line|col|type|context|scope|conf|owner";
+const PATTERN_USAGE_HEADER: &str = "file|line|col|type|context|scope|conf|owner|sym";
+const TRAIT_IMPLS_HEADER: &str = "impl_type|trait|file|line|bounds|kind";
 
 #[derive(Debug, Clone)]
 pub(crate) struct UsageRow {
@@ -40,24 +71,53 @@ pub(crate) struct UsageRow {
     pub(crate) scope: String,
     pub(crate) confidence: String,
     pub(crate) owner_hint: Option<String>,
+    /// The identifier text that matched the search
+    pub(crate) matched_symbol: String,
+}
+
+/// How identifiers are compared against the search
+#[derive(Clone, Copy)]
+enum SymbolMatcher<'a> {
+    Exact(&'a str),
+    Pattern(&'a Regex),
+}
+
+impl SymbolMatcher<'_> {
+    fn matches(self, text: &str) -> bool {
+        match self {
+            SymbolMatcher::Exact(symbol) => text == symbol,
+            SymbolMatcher::Pattern(regex) => regex.is_match(text),
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
 struct SearchTarget<'a> {
     source: &'a str,
-    symbol: &'a str,
+    lines: &'a LineIndex,
+    symbol: SymbolMatcher<'a>,
     language: Language,
     path: &'a Path,
-    context_lines: u32,
+    /// Lines of code context around each usage; `None` skips context
+    context_lines: Option<u32>,
 }
 
 pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
-    let symbol = arguments["symbol"].as_str().ok_or_else(|| {
-        io::Error::new(
-            io::ErrorKind::InvalidInput,
-            "Missing or invalid 'symbol' argument",
-        )
-    })?;
+    let pattern = arguments["pattern"].as_str();
+    let symbol = match pattern {
+        Some(pattern) => pattern,
+        None => arguments["symbol"].as_str().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Missing or invalid 'symbol' argument",
+            )
+        })?,
+    };
+    let regex = pattern.map(compile_symbol_pattern).transpose()?;
+    let (matcher, header) = match &regex {
+        Some(regex) => (SymbolMatcher::Pattern(regex), PATTERN_USAGE_HEADER),
+        None => (SymbolMatcher::Exact(symbol), USAGE_HEADER),
+    };
 
     let path_str = arguments["path"].as_str().ok_or_else(|| {
         io::Error::new(
@@ -85,24 +145,13 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
     }
 
     let mut usages: Vec<UsageRow> = Vec::new();
-    let mut context_budget = ContextBudget::new(max_context_lines);
+    // With a zero budget no usage gets context, so skip building it.
+    let context = (max_context_lines != Some(0)).then_some(context_lines);
 
     if path.is_file() {
-        let _ = search_file(
-            path,
-            symbol,
-            context_lines,
-            &mut context_budget,
-            &mut usages,
-        )?;
+        search_file(path, matcher, context, &mut usages)?;
     } else if path.is_dir() {
-        let _ = search_directory(
-            path,
-            symbol,
-            context_lines,
-            &mut context_budget,
-            &mut usages,
-        )?;
+        search_directory(path, matcher, context, &mut usages)?;
     }
 
     assign_confidence(&mut usages);
@@ -114,6 +163,9 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
             .then_with(|| a.usage_type.cmp(&b.usage_type))
             .then_with(|| a.scope.cmp(&b.scope))
     });
+    if let Some(max_lines) = max_context_lines {
+        apply_context_budget(&mut usages, max_lines);
+    }
 
     // Convert all file paths to relative paths
     for usage in &mut usages {
@@ -123,14 +175,14 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
     let (rows, truncated_by_budget) = build_rows_with_budget(
         &usages,
         symbol,
-        USAGE_HEADER,
+        header,
         max_tokens.unwrap_or(usize::MAX),
         max_tokens.is_some(),
     )?;
 
     let mut result = json!({
         "sym": symbol,
-        "h": USAGE_HEADER,
+        "h": header,
         "u": rows,
     });
 
@@ -148,6 +200,213 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
     Ok(CallToolResult::success(json_text))
 }
 
+/// Compile a `pattern` argument. Unless the pattern anchors itself with a
+/// leading `^` or trailing `$`, it must match the whole identifier.
+fn compile_symbol_pattern(pattern: &str) -> Result<Regex, io::Error> {
+    // Skip leading inline flag groups such as `(?i)` before looking for `^`.
+    let mut body = pattern;
+    while let Some(rest) = body.strip_prefix("(?") {
+        let Some(end) = rest.find(')') else {
+            break;
+        };
+        let flags = &rest[..end];
+        if flags.is_empty() || !flags.chars().all(|c| c.is_ascii_alphabetic() || c == '-') {
+            break;
+        }
+        body = &rest[end + 1..];
+    }
+
+    let anchored = body.starts_with('^') || (body.ends_with('$') && !body.ends_with("\\$"));
+    let source = if anchored {
+        pattern.to_string()
+    } else {
+        format!("^(?:{pattern})$")
+    };
+
+    Regex::new(&source).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid 'pattern' argument: {e}"),
+        )
+    })
+}
+
+/// An `impl Trait for Type` block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraitImplementation {
+    /// Implementing type as written, e.g. `Vec<T>` or `T`
+    pub impl_type: String,
+    /// Trait as written, e.g. `fmt::Display` or `From<u32>`
+    pub trait_name: String,
+    pub file: String,
+    pub line: usize,
+    /// Generic bounds and `where` predicates, e.g. `T: Clone`
+    pub bounds: Vec<String>,
+    /// Implemented for one of the impl's own type parameters (`impl<T> Foo for T`)
+    pub blanket: bool,
+}
+
+pub fn execute_find_trait_implementations(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let trait_name = arguments["trait_name"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'trait_name' argument",
+        )
+    })?;
+
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    log::info!("Finding implementations of '{trait_name}' in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let rows: Vec<String> = find_trait_implementations(trait_name, path)?
+        .iter()
+        .map(|implementation| {
+            let kind = if implementation.blanket {
+                "blanket"
+            } else if implementation.bounds.is_empty() {
+                "plain"
+            } else {
+                "conditional"
+            };
+            format::format_row(&[
+                &implementation.impl_type,
+                &implementation.trait_name,
+                &path_utils::to_relative_path(&implementation.file),
+                &implementation.line.to_string(),
+                &implementation.bounds.join("; "),
+                kind,
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "trait": trait_name,
+        "h": TRAIT_IMPLS_HEADER,
+        "impls": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Rust `impl` blocks of `trait_name` in a file or directory, in file and
+/// line order. Only the trait's last path segment is compared, so `Display`,
+/// `fmt::Display` and `std::fmt::Display` all match.
+pub fn find_trait_implementations(
+    trait_name: &str,
+    path: &Path,
+) -> Result<Vec<TraitImplementation>, io::Error> {
+    let wanted = trait_last_segment(trait_name);
+    let mut implementations = Vec::new();
+
+    for file in collect_project_files(path)? {
+        if detect_language(&file).ok() != Some(Language::Rust) {
+            continue;
+        }
+        let source = path_utils::read_source_file(&file)?;
+        let Ok(parsed) = cache::shared().parse(&file, &source, Language::Rust) else {
+            continue;
+        };
+        collect_trait_impls(
+            parsed.0.root_node(),
+            &source,
+            wanted,
+            &file.to_string_lossy(),
+            &mut implementations,
+        );
+    }
+
+    Ok(implementations)
+}
+
+fn collect_trait_impls(
+    node: Node,
+    source: &str,
+    wanted: &str,
+    file: &str,
+    implementations: &mut Vec<TraitImplementation>,
+) {
+    if node.kind() == "impl_item" {
+        if let Some(implementation) = trait_impl(node, source, wanted, file) {
+            implementations.push(implementation);
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_trait_impls(child, source, wanted, file, implementations);
+    }
+}
+
+fn trait_impl(node: Node, source: &str, wanted: &str, file: &str) -> Option<TraitImplementation> {
+    let source_bytes = source.as_bytes();
+    let trait_node = node.child_by_field_name("trait")?;
+    let trait_text = trait_node.utf8_text(source_bytes).ok()?;
+    if trait_last_segment(trait_text) != wanted {
+        return None;
+    }
+    let type_node = node.child_by_field_name("type")?;
+
+    let generics = rust_generics(node, source_bytes).unwrap_or_default();
+    let parameters: Vec<&str> = generics
+        .iter()
+        .map(|generic| generic.split([':', '=']).next().unwrap_or("").trim())
+        .collect();
+    let mut self_type = type_node;
+    while self_type.kind() == "reference_type" {
+        self_type = self_type.child_by_field_name("type")?;
+    }
+    let blanket = self_type.kind() == "type_identifier"
+        && self_type
+            .utf8_text(source_bytes)
+            .is_ok_and(|name| parameters.contains(&name));
+
+    let bounds = generics
+        .iter()
+        .filter(|generic| generic.contains(':') && !generic.starts_with("const "))
+        .cloned()
+        .chain(rust_where_bounds(node, source_bytes).unwrap_or_default())
+        .collect();
+
+    Some(TraitImplementation {
+        impl_type: collapse_whitespace(type_node.utf8_text(source_bytes).ok()?),
+        trait_name: collapse_whitespace(trait_text),
+        file: file.to_string(),
+        line: node.start_position().row + 1,
+        bounds,
+        blanket,
+    })
+}
+
+/// `Display` for `std::fmt::Display`, `From` for `From<u32>`
+fn trait_last_segment(trait_name: &str) -> &str {
+    let without_generics = trait_name.split('<').next().unwrap_or(trait_name);
+    without_generics
+        .rsplit("::")
+        .next()
+        .unwrap_or(without_generics)
+        .trim()
+}
+
 pub(crate) fn build_rows_with_budget(
     usages: &[UsageRow],
     symbol: &str,
@@ -178,7 +437,8 @@ pub(crate) fn build_rows_with_budget(
             + usage.scope.len()
             + usage.confidence.len()
             + usage.owner_hint.as_deref().unwrap_or("").len()
-            + 7;
+            + usage.matched_symbol.len()
+            + 8;
 
         let estimated = budget::estimate_symbol_tokens(total_chars);
         if !tracker.add(estimated) {
@@ -222,13 +482,14 @@ pub(crate) fn build_rows_with_budget(
 
 fn usages_to_rows(usages: &[UsageRow], header: &str) -> String {
     let mut output = CompactOutput::new(header);
+    let with_symbol = header == PATTERN_USAGE_HEADER;
 
     for usage in usages {
         let line = usage.line.to_string();
         let column = usage.column.to_string();
 
-        output.add_row(&[
-            &usage.file,
+        let mut fields = vec![
+            usage.file.as_str(),
             &line,
             &column,
             &usage.usage_type,
@@ -236,172 +497,314 @@ fn usages_to_rows(usages: &[UsageRow], header: &str) -> String {
             &usage.scope,
             &usage.confidence,
             usage.owner_hint.as_deref().unwrap_or(""),
-        ]);
+        ];
+        if with_symbol {
+            fields.push(&usage.matched_symbol);
+        }
+        output.add_row(&fields);
     }
 
     output.rows_string()
 }
 
-struct ContextBudget {
-    max_total_lines: Option<u32>,
-    used_lines: u32,
-}
+/// Cap the total context lines at `max_lines`, keeping every usage.
+///
+/// Context is handed out greedily by relevance: definitions first, then
+/// calls, then everything else, higher confidence first and in file order
+/// within each group. Usages whose context no longer fits keep their
+/// location but lose their code.
+fn apply_context_budget(usages: &mut [UsageRow], max_lines: u32) {
+    let mut order: Vec<usize> = (0..usages.len()).collect();
+    order.sort_by_key(|&index| {
+        let usage = &usages[index];
+        (
+            usage_type_priority(&usage.usage_type),
+            confidence_priority(&usage.confidence),
+        )
+    });
 
-impl ContextBudget {
-    fn new(max_total_lines: Option<u32>) -> Self {
-        Self {
-            max_total_lines,
-            used_lines: 0,
+    let mut remaining = max_lines as usize;
+    for index in order {
+        let context = &mut usages[index].context;
+        let lines = context.lines().count();
+        if lines <= remaining {
+            remaining -= lines;
+        } else {
+            context.clear();
         }
     }
+}
 
-    fn can_add_lines(&self, additional: u32) -> bool {
-        match self.max_total_lines {
-            None => true,
-            Some(max) => self.used_lines + additional <= max,
-        }
-    }
-
-    fn add_lines(&mut self, additional: u32) -> bool {
-        if self.can_add_lines(additional) {
-            self.used_lines += additional;
-            true
-        } else {
-            false
-        }
+fn usage_type_priority(usage_type: &str) -> u8 {
+    match usage_type {
+        "definition" | "css_definition" => 0,
+        "call" => 1,
+        _ => 2,
     }
+}
 
-    fn max_is_zero(&self) -> bool {
-        matches!(self.max_total_lines, Some(0))
+fn confidence_priority(confidence: &str) -> u8 {
+    match confidence {
+        "high" => 0,
+        "medium" => 1,
+        _ => 2,
     }
 }
 
 fn search_directory(
     dir: &Path,
-    symbol: &str,
-    context_lines: u32,
-    budget: &mut ContextBudget,
+    symbol: SymbolMatcher<'_>,
+    context_lines: Option<u32>,
     usages: &mut Vec<UsageRow>,
-) -> Result<bool, io::Error> {
+) -> Result<(), io::Error> {
     for path in collect_project_files(dir)? {
-        if detect_language(&path).is_ok()
-            && !search_file(&path, symbol, context_lines, budget, usages)?
-        {
-            return Ok(false);
+        if search_language(&path).is_ok() {
+            search_file(&path, symbol, context_lines, usages)?;
         }
     }
 
-    Ok(true)
+    Ok(())
 }
 
 fn search_file(
     path: &Path,
-    symbol: &str,
-    context_lines: u32,
-    budget: &mut ContextBudget,
+    symbol: SymbolMatcher<'_>,
+    context_lines: Option<u32>,
     usages: &mut Vec<UsageRow>,
-) -> Result<bool, io::Error> {
-    let source = fs::read_to_string(path).map_err(|e| {
+) -> Result<(), io::Error> {
+    let source = path_utils::read_source_file(path).map_err(|e| {
         io::Error::new(
             io::ErrorKind::NotFound,
             format!("Failed to read file {}: {e}", path.display()),
         )
     })?;
 
-    let language = detect_language(path).map_err(|e| {
+    let language = search_language(path).map_err(|e| {
         io::Error::new(
             io::ErrorKind::Unsupported,
             format!("Cannot detect language for file {}: {e}", path.display()),
         )
     })?;
 
-    let tree = parse_code(&source, language).map_err(|e| {
-        io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("Failed to parse {} code: {e}", language.name()),
-        )
-    })?;
+    let parsed = cache::shared()
+        .parse(path, &source, language)
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse {} code: {e}", language.name()),
+            )
+        })?;
+    let tree = &parsed.0;
 
+    let lines = LineIndex::new(&source);
     let search = SearchTarget {
         source: &source,
+        lines: &lines,
         symbol,
         language,
         path,
         context_lines,
     };
 
-    Ok(find_identifiers(&tree, search, budget, usages))
+    find_identifiers(tree, search, usages);
+    Ok(())
+}
+
+/// Language `path` is searched as. SCSS has no grammar of its own, so it is
+/// searched as CSS for class names only, on a best-effort basis: Sass-only
+/// syntax such as `&` nesting or mixins can hide a selector.
+fn search_language(path: &Path) -> eyre::Result<Language> {
+    if is_scss(path) {
+        return Ok(Language::Css);
+    }
+    detect_language(path)
+}
+
+fn is_scss(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("scss"))
 }
 
-fn find_identifiers(
+/// Find every occurrence of `symbol` in an already-parsed file, without code
+/// context.
+pub(crate) fn usages_in_tree(
     tree: &Tree,
-    search: SearchTarget<'_>,
-    budget: &mut ContextBudget,
-    usages: &mut Vec<UsageRow>,
-) -> bool {
+    source: &str,
+    language: Language,
+    path: &Path,
+    symbol: &str,
+) -> Vec<UsageRow> {
+    let lines = LineIndex::new(source);
+    let search = SearchTarget {
+        source,
+        lines: &lines,
+        symbol: SymbolMatcher::Exact(symbol),
+        language,
+        path,
+        context_lines: None,
+    };
+    let mut usages = Vec::new();
+    find_identifiers(tree, search, &mut usages);
+    usages
+}
+
+fn find_identifiers(tree: &Tree, search: SearchTarget<'_>, usages: &mut Vec<UsageRow>) {
     let root = tree.root_node();
     let mut cursor = root.walk();
-    visit_node(&mut cursor, search, budget, usages)
+    visit_node(&mut cursor, search, usages);
 }
 
 fn visit_node(
     cursor: &mut tree_sitter::TreeCursor,
     search: SearchTarget<'_>,
-    budget: &mut ContextBudget,
     usages: &mut Vec<UsageRow>,
-) -> bool {
+) {
     let node = cursor.node();
 
-    if node.kind() == "identifier" || node.kind().ends_with("_identifier") {
-        if let Ok(text) = node.utf8_text(search.source.as_bytes()) {
-            if text == search.symbol {
-                let start_pos = node.start_position();
-                let usage_type = classify_usage_type(&node);
-
-                let context = if budget.max_is_zero() {
-                    String::new()
-                } else {
-                    extract_code_with_context(search.source, start_pos.row, search.context_lines)
-                };
-
-                let context_line_count = if budget.max_is_zero() {
-                    0
-                } else {
-                    context.lines().count() as u32
-                };
-
-                if !budget.add_lines(context_line_count) {
-                    return false;
+    match search.language {
+        // CSS class selectors: `.btn-primary { ... }`. The class name's inner
+        // identifier is not visited again so each selector is reported once.
+        Language::Css if node.kind() == "class_name" => {
+            if let Ok(text) = node.utf8_text(search.source.as_bytes()) {
+                if search.symbol.matches(text) {
+                    let position = node.start_position();
+                    let usage_type = "css_definition".to_string();
+                    record_usage(node, position, usage_type, text, search, usages);
+                }
+            }
+            return;
+        }
+        // HTML class attributes: `class="btn btn-primary"`.
+        Language::Html if node.kind() == "attribute" => {
+            for (position, token) in html_class_positions(node, search) {
+                record_usage(
+                    node,
+                    position,
+                    "html_usage".to_string(),
+                    token,
+                    search,
+                    usages,
+                );
+            }
+        }
+        _ => {
+            if node.kind() == "identifier" || node.kind().ends_with("_identifier") {
+                if let Ok(text) = node.utf8_text(search.source.as_bytes()) {
+                    if search.symbol.matches(text) {
+                        record_usage(
+                            node,
+                            node.start_position(),
+                            classify_usage_type(&node),
+                            text,
+                            search,
+                            usages,
+                        );
+                    }
                 }
-
-                usages.push(UsageRow {
-                    file: search.path.to_string_lossy().to_string(),
-                    line: start_pos.row + 1,
-                    column: start_pos.column + 1,
-                    usage_type,
-                    context,
-                    scope: scope_for_node(node, search.source, search.language),
-                    confidence: "low".to_string(),
-                    owner_hint: owner_hint(node, search.source),
-                });
             }
         }
     }
 
     if cursor.goto_first_child() {
         loop {
-            if !visit_node(cursor, search, budget, usages) {
-                cursor.goto_parent();
-                return false;
-            }
+            visit_node(cursor, search, usages);
             if !cursor.goto_next_sibling() {
                 break;
             }
         }
         cursor.goto_parent();
     }
+}
+
+fn record_usage(
+    node: Node,
+    position: Point,
+    usage_type: String,
+    matched_symbol: &str,
+    search: SearchTarget<'_>,
+    usages: &mut Vec<UsageRow>,
+) {
+    let context = search
+        .context_lines
+        .map_or_else(String::new, |context_lines| {
+            search
+                .lines
+                .context(search.source, position.row, context_lines)
+        });
 
-    true
+    usages.push(UsageRow {
+        file: search.path.to_string_lossy().to_string(),
+        line: position.row + 1,
+        column: position.column + 1,
+        usage_type,
+        context,
+        scope: scope_for_node(node, search.source, search.language),
+        confidence: "low".to_string(),
+        owner_hint: owner_hint(node, search.source),
+        matched_symbol: matched_symbol.to_string(),
+    });
+}
+
+/// Positions of every whitespace-separated token in a `class="..."` attribute
+/// that matches the search, with the token itself.
+fn html_class_positions<'a>(attribute: Node, search: SearchTarget<'a>) -> Vec<(Point, &'a str)> {
+    let mut cursor = attribute.walk();
+    let children: Vec<Node> = attribute.children(&mut cursor).collect();
+
+    let is_class_attribute = children.iter().any(|child| {
+        child.kind() == "attribute_name"
+            && child
+                .utf8_text(search.source.as_bytes())
+                .is_ok_and(|name| name.eq_ignore_ascii_case("class"))
+    });
+    if !is_class_attribute {
+        return Vec::new();
+    }
+
+    let value = children.iter().find_map(|child| match child.kind() {
+        "attribute_value" => Some(*child),
+        "quoted_attribute_value" => {
+            let mut value_cursor = child.walk();
+            let inner = child
+                .children(&mut value_cursor)
+                .find(|inner| inner.kind() == "attribute_value");
+            inner
+        }
+        _ => None,
+    });
+    let Some(value) = value else {
+        return Vec::new();
+    };
+
+    let base = value.start_byte();
+    let Ok(text) = value.utf8_text(search.source.as_bytes()) else {
+        return Vec::new();
+    };
+
+    let mut positions = Vec::new();
+    let mut offset = 0;
+    for token in text.split_ascii_whitespace() {
+        let Some(found) = text[offset..].find(token) else {
+            break;
+        };
+        let start = offset + found;
+        offset = start + token.len();
+        if search.symbol.matches(token) {
+            positions.push((point_for_byte(search.source, base + start), token));
+        }
+    }
+
+    positions
+}
+
+fn point_for_byte(source: &str, byte: usize) -> Point {
+    let before = &source[..byte];
+    let row = before.matches('\n').count();
+    let column = before
+        .rfind('\n')
+        .map_or(byte, |newline| byte - newline - 1);
+    Point { row, column }
 }
 
 pub(crate) fn classify_usage_type(node: &Node) -> String {
@@ -517,7 +920,7 @@ fn is_context_node(node_type: &str, language: Language) -> bool {
             "function_item" | "impl_item" | "trait_item" | "struct_item" | "enum_item" | "mod_item"
         ),
         Language::Python => matches!(node_type, "function_definition" | "class_definition"),
-        Language::JavaScript | Language::TypeScript => matches!(
+        Language::JavaScript | Language::TypeScript | Language::Jsx | Language::Tsx => matches!(
             node_type,
             "function_declaration"
                 | "method_definition"
@@ -547,7 +950,7 @@ fn is_context_node(node_type: &str, language: Language) -> bool {
                 | "interface_declaration"
                 | "enum_declaration"
         ),
-        Language::Html | Language::Css | Language::Swift => false,
+        Language::Html | Language::Css | Language::Svelte | Language::Swift => false,
     }
 }
 
@@ -675,7 +1078,13 @@ fn assign_confidence(usages: &mut [UsageRow]) {
         .collect();
 
     for usage in usages.iter_mut() {
-        usage.confidence = confidence_for_usage(usage, &definitions).to_string();
+        // A pattern search can match several names; only a definition of the
+        // same name says anything about a usage.
+        let same_symbol: Vec<&DefinitionSite> = definitions
+            .iter()
+            .filter(|definition| definition.symbol == usage.matched_symbol)
+            .collect();
+        usage.confidence = confidence_for_usage(usage, &same_symbol).to_string();
     }
 }
 
@@ -703,6 +1112,7 @@ impl std::fmt::Display for Confidence {
 }
 
 struct DefinitionSite {
+    symbol: String,
     file: String,
     scope: String,
     owner: Option<String>,
@@ -711,6 +1121,7 @@ struct DefinitionSite {
 impl DefinitionSite {
     fn from(usage: &UsageRow) -> Self {
         Self {
+            symbol: usage.matched_symbol.clone(),
             file: usage.file.clone(),
             scope: usage.scope.clone(),
             owner: definition_owner(usage),
@@ -718,8 +1129,11 @@ impl DefinitionSite {
     }
 }
 
-fn confidence_for_usage(usage: &UsageRow, definitions: &[DefinitionSite]) -> Confidence {
-    if usage.usage_type == "definition" {
+fn confidence_for_usage(usage: &UsageRow, definitions: &[&DefinitionSite]) -> Confidence {
+    if usage.usage_type == "css_definition" && is_scss(Path::new(&usage.file)) {
+        return Confidence::Medium;
+    }
+    if usage.usage_type == "definition" || usage.usage_type == "css_definition" {
         return Confidence::High;
     }
 
@@ -795,11 +1209,44 @@ fn definition_owner(usage: &UsageRow) -> Option<String> {
 }
 
 pub(crate) fn extract_code_with_context(source: &str, line: usize, context_lines: u32) -> String {
-    let lines: Vec<&str> = source.lines().collect();
-    let context_lines = context_lines as usize;
+    LineIndex::new(source).context(source, line, context_lines)
+}
 
-    let start_line = line.saturating_sub(context_lines);
-    let end_line = std::cmp::min(line + context_lines + 1, lines.len());
+/// Byte offsets of line starts, built once per file so each usage only
+/// touches the lines of its own context window.
+///
+/// Windows are sliced from the source already in memory for parsing rather
+/// than re-read from disk: the parse cache keeps that source alive, so
+/// seeking in the file would not lower peak memory, and offsets into the raw
+/// bytes would be wrong for UTF-16 and Windows-1252 files.
+struct LineIndex {
+    starts: Vec<usize>,
+    line_count: usize,
+}
 
-    lines[start_line..end_line].join("\n")
+impl LineIndex {
+    fn new(source: &str) -> Self {
+        let mut starts = vec![0];
+        starts.extend(source.match_indices('\n').map(|(index, _)| index + 1));
+        // Match `str::lines`, which yields no empty line after a final newline.
+        let line_count = if starts.last() == Some(&source.len()) {
+            starts.len() - 1
+        } else {
+            starts.len()
+        };
+        Self { starts, line_count }
+    }
+
+    fn context(&self, source: &str, line: usize, context_lines: u32) -> String {
+        let context_lines = context_lines as usize;
+        let start_line = line.saturating_sub(context_lines);
+        let end_line = std::cmp::min(line + context_lines + 1, self.line_count);
+        if start_line >= end_line {
+            return String::new();
+        }
+
+        let from = self.starts[start_line];
+        let to = self.starts.get(end_line).copied().unwrap_or(source.len());
+        source[from..to].lines().collect::<Vec<_>>().join("\n")
+    }
 }
@@ -260,7 +260,7 @@ fn parsed_file<'a>(
 ) -> Result<&'a ParsedFile, io::Error> {
     let key = fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
     if !cache.contains_key(&key) {
-        let source = fs::read_to_string(file).map_err(|e| {
+        let source = path_utils::read_source_file(file).map_err(|e| {
             io::Error::new(
                 io::ErrorKind::NotFound,
                 format!("Failed to read file {}: {e}", file.display()),
@@ -290,7 +290,7 @@ fn owner_for_line(shape: &EnhancedFileShape, line: usize) -> Option<String> {
     let mut owner = None;
     let mut owner_start = 0;
 
-    for function in &shape.functions {
+    for function in shape.functions.iter().chain(&shape.components) {
         if function.line <= line && line <= function.end_line && function.line >= owner_start {
             owner = Some(function.name.clone());
             owner_start = function.line;
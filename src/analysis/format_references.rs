@@ -90,6 +90,7 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
             scope,
             confidence: "high".to_string(),
             owner_hint: owner,
+            matched_symbol: symbol.to_string(),
         });
     }
 
@@ -231,7 +232,7 @@ fn parsed_file<'a>(
 ) -> Result<&'a ParsedFile, io::Error> {
     let key = fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
     if !cache.contains_key(&key) {
-        let source = fs::read_to_string(file).map_err(|e| {
+        let source = path_utils::read_source_file(file).map_err(|e| {
             io::Error::new(
                 io::ErrorKind::NotFound,
                 format!("Failed to read file {}: {e}", file.display()),
@@ -0,0 +1,218 @@
+//! Get Symbol Tool
+//!
+//! Returns the exact source text of a named function, struct or class,
+//! without the dependency resolution `view_code` does.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "symbols": [
+//!     {"name": "add", "kind": "function", "file": "src/calculator.rs",
+//!      "start_line": 13, "end_line": 15, "code": "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}"}
+//!   ]
+//! }
+//! ```
+//!
+//! Every definition with the name is returned (overloads, methods of
+//! several types), in source order; an unknown name gives an empty list.
+//! `code` does not include doc comments, attributes or decorators.
+
+use std::io;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::analysis::path_utils;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{cache, detect_language, Language};
+
+/// Definition kinds `get_symbol` can filter on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolKind {
+    Function,
+    Struct,
+    Class,
+}
+
+impl SymbolKind {
+    pub fn parse(value: &str) -> Result<Self, io::Error> {
+        match value {
+            "function" => Ok(SymbolKind::Function),
+            "struct" => Ok(SymbolKind::Struct),
+            "class" => Ok(SymbolKind::Class),
+            other => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Invalid 'kind' argument: {other}. Expected 'function', 'struct' or 'class'"
+                ),
+            )),
+        }
+    }
+}
+
+/// One definition of the requested symbol
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymbolSource {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Source text of the definition node
+    pub code: String,
+}
+
+pub fn execute_get_symbol(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let file_path = arguments["file_path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'file_path' argument",
+        )
+    })?;
+    let symbol_name = arguments["symbol_name"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'symbol_name' argument",
+        )
+    })?;
+    let kind = arguments["kind"]
+        .as_str()
+        .map(SymbolKind::parse)
+        .transpose()?;
+
+    log::info!("Getting symbol {symbol_name} (kind: {kind:?}) in: {file_path}");
+
+    let symbols: Vec<SymbolSource> = get_symbol(file_path, symbol_name, kind)?
+        .into_iter()
+        .map(|symbol| SymbolSource {
+            file: path_utils::to_relative_path(&symbol.file),
+            ..symbol
+        })
+        .collect();
+
+    let json_text = serde_json::to_string(&json!({ "symbols": symbols })).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Every definition named `symbol_name` in `file_path`, optionally limited
+/// to one `kind`
+pub fn get_symbol(
+    file_path: &str,
+    symbol_name: &str,
+    kind: Option<SymbolKind>,
+) -> Result<Vec<SymbolSource>, io::Error> {
+    let source = path_utils::read_source_file(file_path).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Failed to read file {file_path}: {e}"),
+        )
+    })?;
+
+    let language = detect_language(file_path).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("Cannot detect language for file {file_path}: {e}"),
+        )
+    })?;
+
+    let parsed = cache::shared()
+        .parse(Path::new(file_path), &source, language)
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse {} code: {e}", language.name()),
+            )
+        })?;
+    let tree = parsed.0.clone();
+
+    let mut symbols = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if let Some((name, found_kind, span)) = definition(node, &source, language) {
+            if name == symbol_name && kind.is_none_or(|kind| kind == found_kind) {
+                symbols.push(SymbolSource {
+                    name: name.to_string(),
+                    kind: found_kind,
+                    file: file_path.to_string(),
+                    start_line: span.start_position().row + 1,
+                    end_line: span.end_position().row + 1,
+                    code: span.utf8_text(source.as_bytes()).unwrap_or("").to_string(),
+                });
+            }
+        }
+
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+
+    Ok(symbols)
+}
+
+/// Name, kind and reported node of a definition, if `node` is one
+pub(crate) fn definition<'a>(
+    node: Node<'a>,
+    source: &'a str,
+    language: Language,
+) -> Option<(&'a str, SymbolKind, Node<'a>)> {
+    let name_of = |node: Node<'a>| {
+        node.child_by_field_name("name")
+            .and_then(|name| name.utf8_text(source.as_bytes()).ok())
+    };
+
+    let kind = match node.kind() {
+        "function_item"
+        | "function_signature_item"
+        | "function_definition"
+        | "function_declaration"
+        | "generator_function_declaration"
+        | "method_definition"
+        | "method_declaration"
+        | "constructor_declaration"
+        | "protocol_function_declaration" => SymbolKind::Function,
+        // `const add = (a, b) => a + b;` is reported as the whole declaration
+        "arrow_function" | "function_expression" | "function" => {
+            let declarator = node
+                .parent()
+                .filter(|p| p.kind() == "variable_declarator")?;
+            let declaration = declarator.parent()?;
+            return Some((name_of(declarator)?, SymbolKind::Function, declaration));
+        }
+        "struct_item" | "union_item" | "struct_declaration" | "record_declaration" => {
+            SymbolKind::Struct
+        }
+        // Go: `type Point struct { ... }`
+        "type_spec" => {
+            node.child_by_field_name("type")
+                .filter(|ty| ty.kind() == "struct_type")?;
+            let span = node
+                .parent()
+                .filter(|p| p.kind() == "type_declaration" && p.named_child_count() == 1)
+                .unwrap_or(node);
+            return Some((name_of(node)?, SymbolKind::Struct, span));
+        }
+        // Swift uses `class_declaration` for classes, structs, enums and actors
+        "class_declaration" if language == Language::Swift => {
+            match node.child_by_field_name("declaration_kind")?.kind() {
+                "class" | "actor" => SymbolKind::Class,
+                "struct" => SymbolKind::Struct,
+                _ => return None,
+            }
+        }
+        "class_definition" | "class_declaration" | "abstract_class_declaration" => {
+            SymbolKind::Class
+        }
+        _ => return None,
+    };
+
+    Some((name_of(node)?, kind, node))
+}
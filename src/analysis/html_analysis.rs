@@ -0,0 +1,206 @@
+//! HTML Form Tool
+//!
+//! Lists the `<form>` elements of an HTML file with the `input`, `select`
+//! and `textarea` controls inside each.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "file": "templates/login.html",
+//!   "fh": "form|action|method|id|line",
+//!   "forms": "1|/login|post|login|4\n2|/register|post|register|12",
+//!   "ih": "form|name|type|required|placeholder|line",
+//!   "inputs": "1|email|email|y|you@example.com|5\n1|csrf|hidden|||6\n..."
+//! }
+//! ```
+//!
+//! `form` in `inputs` is the 1-based index of the form in `forms`. `type`
+//! follows the DOM: an `input` without `type` is `text`, a `select` is
+//! `select-one` or `select-multiple`, a `textarea` is `textarea`. As in
+//! browsers, a `<form>` nested in another form is not a form of its own;
+//! its controls belong to the outer form.
+
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::analysis::path_utils;
+use crate::analysis::svelte::{attribute_value, has_attribute, text};
+use crate::common::format;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{cache, Language};
+
+const FORMS_HEADER: &str = "form|action|method|id|line";
+const INPUTS_HEADER: &str = "form|name|type|required|placeholder|line";
+
+/// A `<form>` element and its controls
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormInfo {
+    pub action: Option<String>,
+    pub method: Option<String>,
+    pub id: Option<String>,
+    pub line: usize,
+    pub inputs: Vec<InputInfo>,
+}
+
+/// An `input`, `select` or `textarea` of a form
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputInfo {
+    pub name: Option<String>,
+    /// DOM `type` of the control, e.g. `hidden` or `select-multiple`
+    pub type_attr: String,
+    pub required: bool,
+    pub placeholder: Option<String>,
+    pub line: usize,
+}
+
+pub fn execute_html_forms(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let file_path = arguments["file_path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'file_path' argument",
+        )
+    })?;
+
+    log::info!("Extracting HTML forms: {file_path}");
+
+    let source = path_utils::read_source_file(file_path).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Failed to read file {file_path}: {e}"),
+        )
+    })?;
+
+    let parsed = cache::shared()
+        .parse(Path::new(file_path), &source, Language::Html)
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse HTML: {e}"),
+            )
+        })?;
+    let forms = extract_html_forms(parsed.0.root_node(), &source);
+
+    let mut form_rows = Vec::new();
+    let mut input_rows = Vec::new();
+    for (index, form) in forms.iter().enumerate() {
+        let index = (index + 1).to_string();
+        form_rows.push(format::format_row(&[
+            &index,
+            form.action.as_deref().unwrap_or(""),
+            form.method.as_deref().unwrap_or(""),
+            form.id.as_deref().unwrap_or(""),
+            &form.line.to_string(),
+        ]));
+        for input in &form.inputs {
+            input_rows.push(format::format_row(&[
+                &index,
+                input.name.as_deref().unwrap_or(""),
+                &input.type_attr,
+                if input.required { "y" } else { "" },
+                input.placeholder.as_deref().unwrap_or(""),
+                &input.line.to_string(),
+            ]));
+        }
+    }
+
+    let result = json!({
+        "file": path_utils::to_relative_path(file_path),
+        "fh": FORMS_HEADER,
+        "forms": form_rows.join("\n"),
+        "ih": INPUTS_HEADER,
+        "inputs": input_rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Forms of a document parsed with the HTML grammar, in source order
+pub fn extract_html_forms(root: Node, source: &str) -> Vec<FormInfo> {
+    let mut forms = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if let Some(tag) =
+            element_tag(node).filter(|&tag| tag_name(tag, source).eq_ignore_ascii_case("form"))
+        {
+            let mut inputs = Vec::new();
+            collect_inputs(node, source, &mut inputs);
+            forms.push(FormInfo {
+                action: attribute_value(tag, source, "action"),
+                method: attribute_value(tag, source, "method")
+                    .map(|method| method.to_ascii_lowercase()),
+                id: attribute_value(tag, source, "id"),
+                line: node.start_position().row + 1,
+                inputs,
+            });
+            continue;
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    forms
+}
+
+/// Controls below `form`, including those of nested forms
+fn collect_inputs(form: Node, source: &str, inputs: &mut Vec<InputInfo>) {
+    let mut cursor = form.walk();
+    let mut stack: Vec<Node> = form.named_children(&mut cursor).collect();
+    stack.reverse();
+    while let Some(node) = stack.pop() {
+        if let Some(tag) = element_tag(node) {
+            let type_attr = match tag_name(tag, source).to_ascii_lowercase().as_str() {
+                "input" => Some(
+                    attribute_value(tag, source, "type")
+                        .map(|ty| ty.to_ascii_lowercase())
+                        .unwrap_or_else(|| "text".to_string()),
+                ),
+                "select" if has_attribute(tag, source, "multiple") => {
+                    Some("select-multiple".to_string())
+                }
+                "select" => Some("select-one".to_string()),
+                "textarea" => Some("textarea".to_string()),
+                _ => None,
+            };
+            if let Some(type_attr) = type_attr {
+                inputs.push(InputInfo {
+                    name: attribute_value(tag, source, "name"),
+                    type_attr,
+                    required: has_attribute(tag, source, "required"),
+                    placeholder: attribute_value(tag, source, "placeholder"),
+                    line: node.start_position().row + 1,
+                });
+            }
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+}
+
+/// The start tag of an `element`, or its tag when self-closing (`<input />`)
+fn element_tag(node: Node) -> Option<Node> {
+    if node.kind() != "element" {
+        return None;
+    }
+    let mut cursor = node.walk();
+    let tag = node
+        .named_children(&mut cursor)
+        .find(|child| matches!(child.kind(), "start_tag" | "self_closing_tag"));
+    tag
+}
+
+fn tag_name<'a>(tag: Node, source: &'a str) -> &'a str {
+    tag.named_child(0)
+        .filter(|name| name.kind() == "tag_name")
+        .map_or("", |name| text(name, source))
+}
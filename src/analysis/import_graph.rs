@@ -0,0 +1,363 @@
+//! Python Import Graph Tool
+//!
+//! Builds the module-level import graph of the `.py` files under a path and
+//! reports circular imports.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "graph": {
+//!     "shop.billing": ["shop.customers", "decimal"],
+//!     "shop.orders": ["shop.billing"]
+//!   },
+//!   "cycles": ["shop.billing -> shop.customers -> shop.orders -> shop.billing"]
+//! }
+//! ```
+//!
+//! A file's module name is its path below the outermost directory of its
+//! chain of `__init__.py` packages (`shop/orders.py` is `shop.orders`,
+//! `shop/__init__.py` is `shop`); a file outside any package is named by its
+//! stem. Relative imports are resolved against that name, and
+//! `from pkg import name` records `pkg.name` when that is a module of the
+//! project, `pkg` otherwise. Imports of third-party and standard library
+//! modules are kept in `graph`; `cycles` only ever contains project modules.
+//!
+//! Only imports executed when the module loads count: those inside function
+//! or class bodies and `if TYPE_CHECKING:` blocks are the usual way to break
+//! a cycle and are skipped, as is `from __future__ import ...`. Each cycle is
+//! one shortest loop through a group of mutually importing modules, starting
+//! at the group's first module by name.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::analysis::path_utils;
+use crate::common::project_files::collect_project_files;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{parse_code, Language};
+
+/// Import graph of a set of Python files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportGraph {
+    /// Imported modules of each project module, in import order
+    pub graph: BTreeMap<String, Vec<String>>,
+    /// Circular imports as module loops, first module repeated at the end
+    pub cycles: Vec<Vec<String>>,
+}
+
+pub fn execute_python_import_graph(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    log::info!("Building Python import graph for: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let import_graph = python_import_graph(path)?;
+    let cycles: Vec<String> = import_graph
+        .cycles
+        .iter()
+        .map(|cycle| cycle.join(" -> "))
+        .collect();
+
+    let result = json!({
+        "graph": import_graph.graph,
+        "cycles": cycles,
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Import graph of a `.py` file or of every `.py` file in a directory
+pub fn python_import_graph(path: &Path) -> Result<ImportGraph, io::Error> {
+    let mut modules: Vec<(String, bool, PathBuf)> = Vec::new();
+    for file in collect_project_files(path)? {
+        if file.extension().and_then(|extension| extension.to_str()) != Some("py") {
+            continue;
+        }
+        let (name, is_package) = module_name(&file);
+        modules.push((name, is_package, file));
+    }
+    let known: BTreeSet<&str> = modules.iter().map(|(name, ..)| name.as_str()).collect();
+
+    let mut graph = BTreeMap::new();
+    for (name, is_package, file) in &modules {
+        let Ok(source) = path_utils::read_source_file(file) else {
+            continue;
+        };
+        let tree = match parse_code(&source, Language::Python) {
+            Ok(tree) => tree,
+            Err(e) => {
+                log::debug!("Skipping {}: {e}", file.display());
+                continue;
+            }
+        };
+        let package = if *is_package {
+            name.as_str()
+        } else {
+            name.rsplit_once('.').map_or("", |(package, _)| package)
+        };
+
+        let mut imports: Vec<String> = Vec::new();
+        collect_imports(tree.root_node(), &source, package, &known, &mut imports);
+        graph.insert(name.clone(), imports);
+    }
+
+    let cycles = find_cycles(&graph);
+    Ok(ImportGraph { graph, cycles })
+}
+
+/// Dotted module name of `file` and whether it is a package `__init__.py`
+pub fn module_name(file: &Path) -> (String, bool) {
+    let stem = file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let is_package = stem == "__init__";
+
+    let mut parts = Vec::new();
+    if !is_package {
+        parts.push(stem);
+    }
+    let mut dir = file.parent();
+    while let Some(current) = dir.filter(|dir| dir.join("__init__.py").is_file()) {
+        let Some(name) = current.file_name() else {
+            break;
+        };
+        parts.push(name.to_string_lossy().into_owned());
+        dir = current.parent();
+    }
+    parts.reverse();
+    (parts.join("."), is_package)
+}
+
+/// Module-level imports below `node`, skipping function and class bodies and
+/// `if TYPE_CHECKING:` blocks
+fn collect_imports(
+    node: Node,
+    source: &str,
+    package: &str,
+    known: &BTreeSet<&str>,
+    imports: &mut Vec<String>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "import_statement" => {
+                let mut names_cursor = child.walk();
+                for name in child.children_by_field_name("name", &mut names_cursor) {
+                    if let Some(module) = imported_name(name, source) {
+                        push_unique(imports, module.to_string());
+                    }
+                }
+            }
+            "import_from_statement" => {
+                collect_from_import(child, source, package, known, imports);
+            }
+            "function_definition" | "class_definition" | "decorated_definition" => {}
+            "if_statement" if is_type_checking(child, source) => {}
+            _ => collect_imports(child, source, package, known, imports),
+        }
+    }
+}
+
+/// `from base import a, b` as `base.a`/`base.b` when those are project
+/// modules, else `base`
+fn collect_from_import(
+    statement: Node,
+    source: &str,
+    package: &str,
+    known: &BTreeSet<&str>,
+    imports: &mut Vec<String>,
+) {
+    let Some(module_name) = statement.child_by_field_name("module_name") else {
+        return;
+    };
+    let base = if module_name.kind() == "relative_import" {
+        let mut cursor = module_name.walk();
+        let children: Vec<Node> = module_name.named_children(&mut cursor).collect();
+        let level = children
+            .iter()
+            .find(|child| child.kind() == "import_prefix")
+            .map_or(0, |prefix| text(*prefix, source).matches('.').count());
+        let relative = children
+            .iter()
+            .find(|child| child.kind() == "dotted_name")
+            .map(|name| text(*name, source));
+        let Some(base) = resolve_relative(package, level, relative) else {
+            log::debug!(
+                "Relative import {} goes above package {package}",
+                text(module_name, source)
+            );
+            return;
+        };
+        base
+    } else {
+        text(module_name, source).to_string()
+    };
+
+    let mut cursor = statement.walk();
+    let names: Vec<&str> = statement
+        .children_by_field_name("name", &mut cursor)
+        .filter_map(|name| imported_name(name, source))
+        .collect();
+    // `from base import *` and names that are not modules import `base` itself
+    let mut imports_base = names.is_empty();
+    for name in names {
+        let module = join_module(&base, name);
+        if known.contains(module.as_str()) {
+            push_unique(imports, module);
+        } else {
+            imports_base = true;
+        }
+    }
+    if imports_base && !base.is_empty() {
+        push_unique(imports, base);
+    }
+}
+
+/// `package` moved up `level - 1` packages, then down into `relative`
+fn resolve_relative(package: &str, level: usize, relative: Option<&str>) -> Option<String> {
+    let mut parts: Vec<&str> = if package.is_empty() {
+        Vec::new()
+    } else {
+        package.split('.').collect()
+    };
+    for _ in 1..level {
+        parts.pop()?;
+    }
+    let base = parts.join(".");
+    Some(match relative {
+        Some(relative) => join_module(&base, relative),
+        None => base,
+    })
+}
+
+fn join_module(base: &str, name: &str) -> String {
+    if base.is_empty() {
+        name.to_string()
+    } else {
+        format!("{base}.{name}")
+    }
+}
+
+/// Module named by a `dotted_name` or the original name of an `aliased_import`
+fn imported_name<'a>(name: Node, source: &'a str) -> Option<&'a str> {
+    match name.kind() {
+        "dotted_name" => Some(text(name, source)),
+        "aliased_import" => name
+            .child_by_field_name("name")
+            .map(|name| text(name, source)),
+        _ => None,
+    }
+}
+
+/// `if TYPE_CHECKING:` or `if typing.TYPE_CHECKING:`
+fn is_type_checking(if_statement: Node, source: &str) -> bool {
+    if_statement
+        .child_by_field_name("condition")
+        .is_some_and(|condition| {
+            matches!(
+                text(condition, source),
+                "TYPE_CHECKING" | "typing.TYPE_CHECKING"
+            )
+        })
+}
+
+/// One shortest loop per strongly connected group of project modules, in
+/// order of the group's first module
+fn find_cycles(graph: &BTreeMap<String, Vec<String>>) -> Vec<Vec<String>> {
+    let mut cycles = Vec::new();
+    let mut covered: BTreeSet<&str> = BTreeSet::new();
+    for start in graph.keys() {
+        if covered.contains(start.as_str()) {
+            continue;
+        }
+        let Some(cycle) = shortest_loop(graph, start) else {
+            continue;
+        };
+        let reachable = reachable_from(graph, start);
+        for module in graph.keys() {
+            if reachable.contains(module.as_str())
+                && reachable_from(graph, module).contains(start.as_str())
+            {
+                covered.insert(module);
+            }
+        }
+        cycles.push(cycle);
+    }
+    cycles
+}
+
+/// Shortest import path from `start` back to itself
+fn shortest_loop(graph: &BTreeMap<String, Vec<String>>, start: &str) -> Option<Vec<String>> {
+    let mut previous: HashMap<&str, &str> = HashMap::new();
+    let mut queue: VecDeque<&str> = VecDeque::from([start]);
+    while let Some(module) = queue.pop_front() {
+        for next in graph.get(module).into_iter().flatten() {
+            if next == start {
+                let mut cycle = vec![start.to_string()];
+                let mut current = module;
+                while current != start {
+                    cycle.push(current.to_string());
+                    current = previous[current];
+                }
+                cycle[1..].reverse();
+                cycle.push(start.to_string());
+                return Some(cycle);
+            }
+            if graph.contains_key(next.as_str()) && !previous.contains_key(next.as_str()) {
+                previous.insert(next, module);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+fn reachable_from<'a>(
+    graph: &'a BTreeMap<String, Vec<String>>,
+    start: &'a str,
+) -> BTreeSet<&'a str> {
+    let mut seen = BTreeSet::new();
+    let mut stack = vec![start];
+    while let Some(module) = stack.pop() {
+        for next in graph.get(module).into_iter().flatten() {
+            if seen.insert(next.as_str()) {
+                stack.push(next);
+            }
+        }
+    }
+    seen
+}
+
+fn push_unique(imports: &mut Vec<String>, module: String) {
+    if !imports.contains(&module) {
+        imports.push(module);
+    }
+}
+
+fn text<'a>(node: Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
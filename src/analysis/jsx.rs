@@ -0,0 +1,272 @@
+//! JSX Component Tree Tool
+//!
+//! Outlines the element tree rendered by React components in `.tsx`, `.jsx`
+//! and `.js` files. Each top-level JSX expression becomes a [`JsxTree`]
+//! attributed to the outermost named function, class or variable declaring
+//! it, so `items.map(item => <li />)` helpers count toward their component.
+//! Elements nested in props (`icon={<Icon />}`) or child expressions
+//! (`{open && <Menu />}`) are children of the enclosing element.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "file": "src/App.tsx",
+//!   "h": "component|depth|tag|props|line",
+//!   "tree": "App|0|div|className|5\nApp|1|Button|onClick,disabled|6\n..."
+//! }
+//! ```
+//!
+//! Rows are in document order; `depth` 0 is the root element of a tree.
+//! Fragments are listed as `<>` and spread props as `...name`.
+
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use tree_sitter::{Node, Parser};
+
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+
+const TREE_HEADER: &str = "component|depth|tag|props|line";
+
+/// Tag used for `<>...</>` fragments
+const FRAGMENT_TAG: &str = "<>";
+
+/// A JSX expression rendered by a component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsxTree {
+    /// Outermost named declaration containing the expression; empty at module level
+    pub component: String,
+    /// Tag of the outermost element
+    pub root_element: String,
+    /// Attribute names of the outermost element
+    pub props: Vec<String>,
+    /// 1-based line of the outermost element
+    pub line: usize,
+    pub children: Vec<JsxNode>,
+}
+
+/// An element or fragment below the root of a [`JsxTree`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsxNode {
+    pub tag: String,
+    /// Attribute names only, in source order
+    pub props: Vec<String>,
+    /// 1-based line of the element
+    pub line: usize,
+    pub children: Vec<JsxNode>,
+}
+
+pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let file_path = arguments["file_path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'file_path' argument",
+        )
+    })?;
+    let component = arguments["component"].as_str();
+
+    log::info!("Extracting JSX tree from: {file_path}");
+
+    let path = Path::new(file_path);
+    let source = path_utils::read_source_file(path)?;
+    let is_tsx = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("tsx") => true,
+        Some("jsx" | "js" | "mjs" | "cjs") => false,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Not a JSX file (expected .tsx, .jsx or .js): {file_path}"),
+            ))
+        }
+    };
+
+    let mut trees = extract_jsx_trees(&source, is_tsx)?;
+    if let Some(component) = component {
+        trees.retain(|tree| tree.component == component);
+        if trees.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No JSX rendered by component '{component}'"),
+            ));
+        }
+    }
+
+    let mut rows = Vec::new();
+    for tree in &trees {
+        rows.push(format::format_row(&[
+            &tree.component,
+            "0",
+            &tree.root_element,
+            &tree.props.join(","),
+            &tree.line.to_string(),
+        ]));
+        push_rows(&tree.component, &tree.children, 1, &mut rows);
+    }
+
+    let result = json!({
+        "file": path_utils::to_relative_path(file_path),
+        "h": TREE_HEADER,
+        "tree": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Top-level JSX expressions of a source file, in document order.
+///
+/// `is_tsx` selects the TSX grammar; otherwise the JavaScript grammar (which
+/// includes JSX) is used.
+pub fn extract_jsx_trees(source: &str, is_tsx: bool) -> Result<Vec<JsxTree>, io::Error> {
+    let mut parser = Parser::new();
+    let language = if is_tsx {
+        tree_sitter_typescript::LANGUAGE_TSX.into()
+    } else {
+        tree_sitter_javascript::LANGUAGE.into()
+    };
+    parser
+        .set_language(&language)
+        .map_err(|e| io::Error::other(format!("Failed to configure JSX parser: {e}")))?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Failed to parse JSX source"))?;
+
+    Ok(collect_elements(tree.root_node(), source)
+        .into_iter()
+        .map(|(root, node)| JsxTree {
+            component: component_name(node, source),
+            root_element: root.tag,
+            props: root.props,
+            line: root.line,
+            children: root.children,
+        })
+        .collect())
+}
+
+fn push_rows(component: &str, nodes: &[JsxNode], depth: usize, rows: &mut Vec<String>) {
+    for node in nodes {
+        rows.push(format::format_row(&[
+            component,
+            &depth.to_string(),
+            &node.tag,
+            &node.props.join(","),
+            &node.line.to_string(),
+        ]));
+        push_rows(component, &node.children, depth + 1, rows);
+    }
+}
+
+/// Outermost JSX elements below `node`, each with its syntax node
+fn collect_elements<'a>(node: Node<'a>, source: &str) -> Vec<(JsxNode, Node<'a>)> {
+    let mut found = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "jsx_element" | "jsx_self_closing_element" => {
+                found.push((element_node(child, source), child))
+            }
+            _ => found.extend(collect_elements(child, source)),
+        }
+    }
+    found
+}
+
+fn element_node(element: Node, source: &str) -> JsxNode {
+    // `<Tag attrs>` of a paired element, or the self-closing element itself
+    let opening = if element.kind() == "jsx_element" {
+        element.child_by_field_name("open_tag").unwrap_or(element)
+    } else {
+        element
+    };
+
+    let tag = opening
+        .child_by_field_name("name")
+        .map(|name| text(name, source).to_string())
+        .unwrap_or_else(|| FRAGMENT_TAG.to_string());
+
+    let mut props = Vec::new();
+    let mut children = Vec::new();
+    let mut cursor = opening.walk();
+    for attribute in opening.children_by_field_name("attribute", &mut cursor) {
+        match attribute.kind() {
+            "jsx_attribute" => {
+                if let Some(name) = attribute.named_child(0) {
+                    props.push(text(name, source).to_string());
+                }
+            }
+            // `{...rest}`
+            "jsx_expression" => {
+                props.push(collapse(text(attribute, source).trim_matches(['{', '}'])));
+            }
+            _ => {}
+        }
+        children.extend(
+            collect_elements(attribute, source)
+                .into_iter()
+                .map(|(node, _)| node),
+        );
+    }
+
+    if element.kind() == "jsx_element" {
+        let mut cursor = element.walk();
+        for child in element.named_children(&mut cursor) {
+            match child.kind() {
+                "jsx_opening_element" | "jsx_closing_element" => {}
+                "jsx_element" | "jsx_self_closing_element" => {
+                    children.push(element_node(child, source))
+                }
+                _ => children.extend(
+                    collect_elements(child, source)
+                        .into_iter()
+                        .map(|(node, _)| node),
+                ),
+            }
+        }
+    }
+
+    JsxNode {
+        tag,
+        props,
+        line: element.start_position().row + 1,
+        children,
+    }
+}
+
+/// Name of the outermost named function, class or variable declaration
+/// containing `node`
+fn component_name(node: Node, source: &str) -> String {
+    let mut name = String::new();
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if matches!(
+            ancestor.kind(),
+            "function_declaration"
+                | "generator_function_declaration"
+                | "class_declaration"
+                | "variable_declarator"
+        ) {
+            if let Some(declared) = ancestor.child_by_field_name("name") {
+                name = text(declared, source).to_string();
+            }
+        }
+        current = ancestor.parent();
+    }
+    name
+}
+
+fn collapse(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn text<'a>(node: Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
@@ -0,0 +1,188 @@
+//! Liquid Template Support
+//!
+//! Variable extraction and layout/include resolution for Liquid templates
+//! (Jekyll sites, Shopify themes). Used by the `template_context` tool when
+//! the project root has `_layouts/` or `_includes/` directories.
+
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::path_utils;
+
+/// Maximum number of parent directories searched for the site root
+const MAX_ROOT_SEARCH_DEPTH: usize = 10;
+
+/// Maximum number of templates visited when following layouts and includes
+const MAX_TEMPLATE_HIERARCHY: usize = 50;
+
+/// A variable a Liquid template reads
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LiquidVar {
+    /// Root variable (e.g., "page")
+    pub name: String,
+
+    /// Full dotted access path (e.g., "page.title")
+    pub access_path: String,
+
+    /// "scalar" (`{{ var }}`, `{% assign x = var %}`), "iterator"
+    /// (`{% for x in var %}`) or "conditional" (`{% if var %}`,
+    /// `{% unless var %}`, `{% case var %}`)
+    pub usage_kind: String,
+}
+
+/// Whether a directory is the root of a Jekyll site or Liquid theme
+pub fn is_liquid_site(root: &Path) -> bool {
+    root.join("_layouts").is_dir() || root.join("_includes").is_dir()
+}
+
+/// Walk up from a template to the directory holding `_layouts/` or `_includes/`
+pub fn find_site_root(template_path: &Path) -> Option<PathBuf> {
+    let mut current = template_path.parent()?;
+    for _ in 0..MAX_ROOT_SEARCH_DEPTH {
+        if is_liquid_site(current) {
+            return Some(current.to_path_buf());
+        }
+        current = current.parent()?;
+    }
+    None
+}
+
+/// Extract the variables a Liquid template reads.
+///
+/// Names bound inside the template (`for`, `assign`, `capture`,
+/// `increment`) and the `forloop` object are skipped. `content` is the
+/// rendered child page in layouts, not data, and is skipped as well.
+pub fn extract_liquid_variables(source: &str) -> Vec<LiquidVar> {
+    let path = r"([A-Za-z_][\w-]*(?:\.[\w-]+)*)";
+    let output_re = Regex::new(&format!(r"\{{\{{-?\s*{path}")).unwrap();
+    let for_re = Regex::new(&format!(r"\{{%-?\s*(?:for|tablerow)\s+(\w+)\s+in\s+{path}")).unwrap();
+    let if_re = Regex::new(&format!(r"\{{%-?\s*(?:if|elsif|unless|case)\s+{path}")).unwrap();
+    let assign_re = Regex::new(&format!(r"\{{%-?\s*assign\s+\w+\s*=\s*{path}")).unwrap();
+    let bind_re = Regex::new(r"\{%-?\s*(?:assign|capture|increment|decrement)\s+(\w+)").unwrap();
+
+    let mut bound: Vec<String> = vec!["forloop".into(), "tablerowloop".into(), "content".into()];
+    bound.extend(for_re.captures_iter(source).map(|cap| cap[1].to_string()));
+    bound.extend(bind_re.captures_iter(source).map(|cap| cap[1].to_string()));
+
+    let mut usages: Vec<(usize, String, &str)> = Vec::new();
+    for cap in output_re.captures_iter(source) {
+        usages.push((cap.get(0).unwrap().start(), cap[1].to_string(), "scalar"));
+    }
+    for cap in assign_re.captures_iter(source) {
+        usages.push((cap.get(0).unwrap().start(), cap[1].to_string(), "scalar"));
+    }
+    for cap in for_re.captures_iter(source) {
+        usages.push((cap.get(0).unwrap().start(), cap[2].to_string(), "iterator"));
+    }
+    for cap in if_re.captures_iter(source) {
+        usages.push((
+            cap.get(0).unwrap().start(),
+            cap[1].to_string(),
+            "conditional",
+        ));
+    }
+    usages.sort_by_key(|(offset, _, _)| *offset);
+
+    let mut variables: Vec<LiquidVar> = Vec::new();
+    for (_, access_path, usage_kind) in usages {
+        let name = access_path
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        if bound.contains(&name) || matches!(name.as_str(), "true" | "false" | "nil" | "empty") {
+            continue;
+        }
+        let variable = LiquidVar {
+            name,
+            access_path,
+            usage_kind: usage_kind.to_string(),
+        };
+        if !variables.contains(&variable) {
+            variables.push(variable);
+        }
+    }
+
+    variables
+}
+
+/// Variables used by a template, its layout chain and its includes, tagged
+/// with the template (relative to the site root) that uses them.
+pub fn site_template_variables(template_path: &Path, site_root: &Path) -> Vec<(String, LiquidVar)> {
+    let mut queue = vec![template_path.to_path_buf()];
+    let mut visited: Vec<PathBuf> = Vec::new();
+    let mut result = Vec::new();
+
+    while !queue.is_empty() && visited.len() < MAX_TEMPLATE_HIERARCHY {
+        let path = queue.remove(0);
+        if visited.contains(&path) {
+            continue;
+        }
+        visited.push(path.clone());
+
+        let Ok(source) = path_utils::read_source_file(&path) else {
+            continue;
+        };
+        let name = path
+            .strip_prefix(site_root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .to_string();
+        for variable in extract_liquid_variables(&source) {
+            result.push((name.clone(), variable));
+        }
+
+        queue.extend(template_dependencies(&source, site_root));
+    }
+
+    result
+}
+
+/// Templates pulled in by front matter `layout:`, `{% include %}` and
+/// Shopify's `{% render %}`/`{% section %}`, limited to files that exist.
+fn template_dependencies(source: &str, site_root: &Path) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    if let Some(layout) = front_matter_layout(source) {
+        candidates.push(site_root.join("_layouts").join(format!("{layout}.html")));
+    }
+
+    let include_re =
+        Regex::new(r#"\{%-?\s*include\s+["']?([\w./-]+?)["']?(?:\s[^%]*)?-?%\}"#).unwrap();
+    for cap in include_re.captures_iter(source) {
+        candidates.push(site_root.join("_includes").join(&cap[1]));
+        candidates.push(
+            site_root
+                .join("snippets")
+                .join(format!("{}.liquid", &cap[1])),
+        );
+    }
+
+    let render_re = Regex::new(r#"\{%-?\s*(render|section)\s+["']([\w./-]+)["']"#).unwrap();
+    for cap in render_re.captures_iter(source) {
+        let dir = if &cap[1] == "render" {
+            "snippets"
+        } else {
+            "sections"
+        };
+        candidates.push(site_root.join(dir).join(format!("{}.liquid", &cap[2])));
+    }
+
+    candidates
+        .into_iter()
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// `layout: default` from a leading `---` front matter block
+fn front_matter_layout(source: &str) -> Option<String> {
+    let body = source.strip_prefix("---")?;
+    let (front_matter, _) = body.split_once("\n---")?;
+    front_matter.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("layout:")?.trim();
+        let value = value.trim_matches(|c| c == '"' || c == '\'');
+        (!value.is_empty() && value != "null" && value != "none").then(|| value.to_string())
+    })
+}
@@ -0,0 +1,235 @@
+//! Locked Dependencies Tool
+//!
+//! Lists the exact dependency versions pinned by `Cargo.lock` and `go.sum`,
+//! optionally cross-referenced against a vulnerability list.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "h": "name|version|checksum|src",
+//!   "deps": "serde|1.0.210|abc...|Cargo.lock\n...",
+//!   "vh": "name|version|id",
+//!   "vulns": "openssl|0.10.0|CVE-2023-0001"
+//! }
+//! ```
+//!
+//! The vulnerability list is a JSON array of
+//! `{"id": "...", "package": "...", "versions": ["..."]}` advisories.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+
+const DEPS_HEADER: &str = "name|version|checksum|src";
+const VULNS_HEADER: &str = "name|version|id";
+
+/// A dependency pinned to an exact version by a lockfile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedDep {
+    pub name: String,
+    pub version: String,
+    pub checksum: Option<String>,
+}
+
+/// The parts of a `Cargo.lock` this tool reads.
+#[derive(Debug, Deserialize)]
+struct CargoLock {
+    #[serde(default)]
+    package: Vec<CargoLockPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoLockPackage {
+    name: String,
+    version: String,
+    checksum: Option<String>,
+}
+
+/// One entry of a vulnerability list.
+#[derive(Debug, Clone, Deserialize)]
+struct Advisory {
+    id: String,
+    package: String,
+    versions: Vec<String>,
+}
+
+pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let root_str = arguments["project_root"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'project_root' argument",
+        )
+    })?;
+    let check_cve = arguments["check_cve"].as_bool().unwrap_or(false);
+
+    log::info!("Listing locked dependencies in: {root_str} (check_cve: {check_cve})");
+
+    let root = Path::new(root_str);
+    if !root.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Project root is not a directory: {root_str}"),
+        ));
+    }
+
+    let mut sources: Vec<(&str, Vec<LockedDep>)> = Vec::new();
+    let cargo_lock = root.join("Cargo.lock");
+    if cargo_lock.is_file() {
+        sources.push(("Cargo.lock", parse_cargo_lock(&cargo_lock)));
+    }
+    let go_sum = root.join("go.sum");
+    if go_sum.is_file() {
+        sources.push(("go.sum", parse_go_sum(&go_sum)));
+    }
+
+    let mut rows = Vec::new();
+    for (source, deps) in &sources {
+        for dep in deps {
+            rows.push(format::format_row(&[
+                &dep.name,
+                &dep.version,
+                dep.checksum.as_deref().unwrap_or(""),
+                source,
+            ]));
+        }
+    }
+
+    let mut result = json!({
+        "h": DEPS_HEADER,
+        "deps": rows.join("\n"),
+    });
+
+    if check_cve {
+        let url = arguments["cve_url"].as_str().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "'cve_url' is required when 'check_cve' is true",
+            )
+        })?;
+        let advisories = fetch_advisories(url)?;
+        let all_deps: Vec<&LockedDep> = sources.iter().flat_map(|(_, deps)| deps).collect();
+        let vulns: Vec<String> = match_advisories(&all_deps, &advisories)
+            .into_iter()
+            .map(|(dep, id)| format::format_row(&[&dep.name, &dep.version, id]))
+            .collect();
+
+        result["vh"] = json!(VULNS_HEADER);
+        result["vulns"] = json!(vulns.join("\n"));
+    }
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Parse the `[[package]]` entries of a `Cargo.lock`.
+///
+/// Unreadable or malformed lockfiles yield an empty list.
+pub fn parse_cargo_lock(path: &Path) -> Vec<LockedDep> {
+    let Ok(text) = path_utils::read_source_file(path) else {
+        return Vec::new();
+    };
+    let Ok(lock) = toml::from_str::<CargoLock>(&text) else {
+        return Vec::new();
+    };
+
+    lock.package
+        .into_iter()
+        .map(|package| LockedDep {
+            name: package.name,
+            version: package.version,
+            checksum: package.checksum,
+        })
+        .collect()
+}
+
+/// Parse a `go.sum` file.
+///
+/// Each module version appears twice (module zip and `/go.mod`); the module
+/// hash is preferred and `go.mod`-only entries are kept without duplicates.
+pub fn parse_go_sum(path: &Path) -> Vec<LockedDep> {
+    let Ok(text) = path_utils::read_source_file(path) else {
+        return Vec::new();
+    };
+
+    let mut deps: Vec<LockedDep> = Vec::new();
+    // Index into `deps` by (name, version), so each line is an O(1) lookup.
+    let mut index: HashMap<(String, String), usize> = HashMap::new();
+    for line in text.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(name), Some(version), Some(hash)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+
+        let (version, is_go_mod) = match version.strip_suffix("/go.mod") {
+            Some(version) => (version, true),
+            None => (version, false),
+        };
+
+        match index.entry((name.to_string(), version.to_string())) {
+            Entry::Occupied(entry) if !is_go_mod => {
+                deps[*entry.get()].checksum = Some(hash.to_string());
+            }
+            Entry::Occupied(_) => {}
+            Entry::Vacant(entry) => {
+                entry.insert(deps.len());
+                deps.push(LockedDep {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    checksum: (!is_go_mod).then(|| hash.to_string()),
+                });
+            }
+        }
+    }
+
+    deps
+}
+
+fn fetch_advisories(url: &str) -> Result<Vec<Advisory>, io::Error> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(Duration::from_secs(10)))
+        .build()
+        .into();
+    let body = agent
+        .get(url)
+        .call()
+        .and_then(|mut response| response.body_mut().read_to_string())
+        .map_err(|e| io::Error::other(format!("Failed to fetch CVE list: {e}")))?;
+
+    serde_json::from_str(&body).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to parse CVE list: {e}"),
+        )
+    })
+}
+
+fn match_advisories<'a>(
+    deps: &[&'a LockedDep],
+    advisories: &'a [Advisory],
+) -> Vec<(&'a LockedDep, &'a str)> {
+    let mut matches = Vec::new();
+    for dep in deps {
+        for advisory in advisories {
+            if advisory.package == dep.name && advisory.versions.contains(&dep.version) {
+                matches.push((*dep, advisory.id.as_str()));
+            }
+        }
+    }
+    matches
+}
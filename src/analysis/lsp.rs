@@ -0,0 +1,218 @@
+//! Folding Ranges Tool
+//!
+//! Lists the regions an editor can collapse: functions, classes and other
+//! type definitions (Rust structs, enums, traits and `impl` blocks), comment
+//! blocks and groups of imports. Python multiline strings (docstrings) fold
+//! as comments.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "h": "start_line|end_line|kind",
+//!   "folds": "3|6|import_group\n8|9|comment\n10|25|class\n..."
+//! }
+//! ```
+//!
+//! Lines are 1-based and inclusive; ranges are sorted by `start_line`, with
+//! enclosing ranges before the ones nested in them. An import group is a run
+//! of imports with no blank line between them, and a comment block is a run
+//! of line comments that each start their line. Single-line regions are not
+//! listed.
+
+use std::io;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::analysis::node_kind::{classify_node, NodeKind};
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{cache, detect_language, Language};
+
+const FOLDS_HEADER: &str = "start_line|end_line|kind";
+
+/// What a folding range collapses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FoldKind {
+    Function,
+    Class,
+    Comment,
+    ImportGroup,
+}
+
+impl FoldKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FoldKind::Function => "function",
+            FoldKind::Class => "class",
+            FoldKind::Comment => "comment",
+            FoldKind::ImportGroup => "import_group",
+        }
+    }
+}
+
+/// A collapsible region, 1-based and inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FoldingRange {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub kind: FoldKind,
+}
+
+pub fn execute_folding_ranges(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let file_path = arguments["file_path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'file_path' argument",
+        )
+    })?;
+
+    log::info!("Computing folding ranges for: {file_path}");
+
+    let source = path_utils::read_source_file(file_path).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Failed to read file {file_path}: {e}"),
+        )
+    })?;
+
+    let language = detect_language(file_path).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("Cannot detect language for file {file_path}: {e}"),
+        )
+    })?;
+
+    let parsed = cache::shared()
+        .parse(Path::new(file_path), &source, language)
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse {} code: {e}", language.name()),
+            )
+        })?;
+
+    let rows: Vec<String> = folding_ranges(parsed.0.root_node(), &source, language)
+        .iter()
+        .map(|fold| {
+            format::format_row(&[
+                &fold.start_line.to_string(),
+                &fold.end_line.to_string(),
+                fold.kind.as_str(),
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "h": FOLDS_HEADER,
+        "folds": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Folding ranges of the tree rooted at `root`, sorted by `start_line`
+pub fn folding_ranges(root: Node, source: &str, language: Language) -> Vec<FoldingRange> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut folds = Vec::new();
+    collect_folds(root, &lines, language, &mut folds);
+
+    folds.sort_by_key(|fold| (fold.start_line, usize::MAX - fold.end_line, fold.kind));
+    // Go reports both `type_declaration` and its single `type_spec`
+    folds.dedup();
+    folds
+}
+
+fn collect_folds(node: Node, lines: &[&str], language: Language, out: &mut Vec<FoldingRange>) {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.named_children(&mut cursor).collect();
+
+    let mut index = 0;
+    while index < children.len() {
+        let child = children[index];
+        let category = classify_node(child.kind(), language);
+
+        let group_kind = match category {
+            NodeKind::Import => Some(FoldKind::ImportGroup),
+            NodeKind::Comment if starts_line(child, lines) => Some(FoldKind::Comment),
+            _ => None,
+        };
+        if let Some(kind) = group_kind {
+            // Extend the run while the next sibling continues on the next line
+            let mut last = index;
+            while let Some(&next) = children.get(last + 1) {
+                if classify_node(next.kind(), language) != category
+                    || next.start_position().row != last_row(children[last]) + 1
+                    || (kind == FoldKind::Comment && !starts_line(next, lines))
+                {
+                    break;
+                }
+                last += 1;
+            }
+            push_fold(
+                out,
+                child.start_position().row,
+                last_row(children[last]),
+                kind,
+            );
+            index = last + 1;
+            continue;
+        }
+
+        let kind = match category {
+            NodeKind::FunctionDefinition => Some(FoldKind::Function),
+            NodeKind::TypeDefinition => Some(FoldKind::Class),
+            _ if language == Language::Python && child.kind() == "string" => {
+                Some(FoldKind::Comment)
+            }
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            push_fold(out, child.start_position().row, last_row(child), kind);
+        }
+
+        collect_folds(child, lines, language, out);
+        index += 1;
+    }
+}
+
+fn push_fold(out: &mut Vec<FoldingRange>, start_row: usize, end_row: usize, kind: FoldKind) {
+    if end_row > start_row {
+        out.push(FoldingRange {
+            start_line: start_row + 1,
+            end_line: end_row + 1,
+            kind,
+        });
+    }
+}
+
+/// Last row with content; Rust line comments end at column 0 of the next row
+fn last_row(node: Node) -> usize {
+    let start = node.start_position();
+    let end = node.end_position();
+    if end.column == 0 && end.row > start.row {
+        end.row - 1
+    } else {
+        end.row
+    }
+}
+
+/// Whether only whitespace precedes `node` on its first line
+fn starts_line(node: Node, lines: &[&str]) -> bool {
+    let position = node.start_position();
+    lines
+        .get(position.row)
+        .and_then(|line| line.get(..position.column))
+        .is_some_and(|prefix| prefix.trim().is_empty())
+}
@@ -0,0 +1,249 @@
+//! Rust Macro Tool
+//!
+//! Lists `macro_rules!` definitions and macro call sites, which shape
+//! extraction skips because their bodies are opaque token trees.
+//! `#[derive(...)]` attributes are reported as call sites of `derive`.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "dh": "name|file|line|arms",
+//!   "defs": "square|src/lib.rs|3|2\n...",
+//!   "ch": "name|file|line|args",
+//!   "calls": "println|src/main.rs|10|\"{}\", total\nderive|src/lib.rs|1|Debug, Clone\n..."
+//! }
+//! ```
+//!
+//! Invocations nested inside another macro's arguments (e.g. the `vec!` in
+//! `println!("{:?}", vec![1])`) stay unparsed token trees and are not listed.
+
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{Node, Query, QueryCursor, QueryMatch};
+
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::extraction::types::collapse_whitespace;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{detect_language, parse_code, Language};
+
+const DEFINITIONS_HEADER: &str = "name|file|line|arms";
+const CALLS_HEADER: &str = "name|file|line|args";
+
+/// Call arguments longer than this are cut off with `...`
+const MAX_ARGS_CHARS: usize = 120;
+
+const MACRO_QUERY: &str = r#"
+(macro_definition name: (identifier) @name) @definition
+(macro_invocation macro: (_) @name (token_tree) @args) @invocation
+(attribute_item
+  (attribute (identifier) @name arguments: (token_tree) @args)
+  (#eq? @name "derive")) @derive
+"#;
+
+/// A `macro_rules!` definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroDefinition {
+    pub name: String,
+    pub file: String,
+    pub line: usize,
+    /// Number of `(pattern) => { ... }` arms
+    pub pattern_count: usize,
+}
+
+/// A macro invocation such as `println!(...)`, or a `#[derive(...)]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroCallSite {
+    /// Macro path as written, without `!` (`println`, `serde_json::json`)
+    pub name: String,
+    pub file: String,
+    pub line: usize,
+    /// Arguments without the surrounding delimiters, whitespace collapsed
+    pub args_text: String,
+}
+
+/// Macro definitions and call sites, in file and line order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RustMacros {
+    pub definitions: Vec<MacroDefinition>,
+    pub call_sites: Vec<MacroCallSite>,
+}
+
+pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    log::info!("Finding macros in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let macros = find_macros(path)?;
+    let definitions: Vec<String> = macros
+        .definitions
+        .iter()
+        .map(|definition| {
+            format::format_row(&[
+                &definition.name,
+                &path_utils::to_relative_path(&definition.file),
+                &definition.line.to_string(),
+                &definition.pattern_count.to_string(),
+            ])
+        })
+        .collect();
+    let calls: Vec<String> = macros
+        .call_sites
+        .iter()
+        .map(|call| {
+            format::format_row(&[
+                &call.name,
+                &path_utils::to_relative_path(&call.file),
+                &call.line.to_string(),
+                &call.args_text,
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "dh": DEFINITIONS_HEADER,
+        "defs": definitions.join("\n"),
+        "ch": CALLS_HEADER,
+        "calls": calls.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Macros of a Rust file, or of every Rust file in a directory
+pub fn find_macros(path: &Path) -> Result<RustMacros, io::Error> {
+    if path.is_file() && detect_language(path).ok() != Some(Language::Rust) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("find_macros only supports Rust files: {}", path.display()),
+        ));
+    }
+
+    let mut macros = RustMacros::default();
+    for file in collect_project_files(path)? {
+        if detect_language(&file).ok() != Some(Language::Rust) {
+            continue;
+        }
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        let file_macros = extract_rust_macros(&source, &file.to_string_lossy())?;
+        macros.definitions.extend(file_macros.definitions);
+        macros.call_sites.extend(file_macros.call_sites);
+    }
+
+    Ok(macros)
+}
+
+/// Macro definitions and call sites of one Rust source file
+pub fn extract_rust_macros(source: &str, file: &str) -> Result<RustMacros, io::Error> {
+    let query = Query::new(&Language::Rust.tree_sitter_language(), MACRO_QUERY)
+        .map_err(|e| io::Error::other(format!("Failed to compile macro query: {e}")))?;
+    let tree = parse_code(source, Language::Rust).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to parse Rust code from '{file}': {e}"),
+        )
+    })?;
+    let source_bytes = source.as_bytes();
+    let mut macros = RustMacros::default();
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(&query, tree.root_node(), source_bytes);
+    while let Some(query_match) = matches.next() {
+        let Some(name) = captured(&query, query_match, "name")
+            .and_then(|node| node.utf8_text(source_bytes).ok())
+            .map(collapse_whitespace)
+        else {
+            continue;
+        };
+
+        if let Some(definition) = captured(&query, query_match, "definition") {
+            let mut walker = definition.walk();
+            let pattern_count = definition
+                .named_children(&mut walker)
+                .filter(|child| child.kind() == "macro_rule")
+                .count();
+            macros.definitions.push(MacroDefinition {
+                name,
+                file: file.to_string(),
+                line: definition.start_position().row + 1,
+                pattern_count,
+            });
+            continue;
+        }
+
+        let Some(call) = captured(&query, query_match, "invocation")
+            .or_else(|| captured(&query, query_match, "derive"))
+        else {
+            continue;
+        };
+        let args_text = captured(&query, query_match, "args")
+            .and_then(|node| node.utf8_text(source_bytes).ok())
+            .map(token_tree_contents)
+            .unwrap_or_default();
+        macros.call_sites.push(MacroCallSite {
+            name,
+            file: file.to_string(),
+            line: call.start_position().row + 1,
+            args_text,
+        });
+    }
+
+    Ok(macros)
+}
+
+pub(crate) fn captured<'tree>(
+    query: &Query,
+    query_match: &QueryMatch<'_, 'tree>,
+    name: &str,
+) -> Option<Node<'tree>> {
+    let index = query.capture_index_for_name(name)?;
+    query_match
+        .captures
+        .iter()
+        .find(|capture| capture.index == index)
+        .map(|capture| capture.node)
+}
+
+/// `"{}", x` for `("{}", x)`, shortened to [`MAX_ARGS_CHARS`]
+pub(crate) fn token_tree_contents(token_tree: &str) -> String {
+    let inner = token_tree
+        .get(1..token_tree.len().saturating_sub(1))
+        .unwrap_or("");
+    shorten(inner)
+}
+
+/// Collapse whitespace and cut off after [`MAX_ARGS_CHARS`] with `...`
+pub(crate) fn shorten(text: &str) -> String {
+    let collapsed = collapse_whitespace(text);
+    if collapsed.chars().count() <= MAX_ARGS_CHARS {
+        return collapsed;
+    }
+    let mut shortened: String = collapsed.chars().take(MAX_ARGS_CHARS).collect();
+    shortened.push_str("...");
+    shortened
+}
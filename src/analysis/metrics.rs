@@ -0,0 +1,257 @@
+//! Code Metrics
+//!
+//! Per-function cyclomatic complexity: one plus the number of decision
+//! points (branches, loops, extra match arms, short-circuit operators,
+//! catch clauses and Rust's `?`) in the function body.
+//!
+//! `complexity` tool output schema, most complex first; `over` is `1` for
+//! functions above `threshold` (default 10):
+//! ```json
+//! {
+//!   "threshold": 10,
+//!   "h": "symbol|file|line|complexity|over",
+//!   "fns": "parse_args|src/cli.rs|12|14|1\nmain|src/main.rs|3|2|\n..."
+//! }
+//! ```
+
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use tree_sitter::{Node, Tree};
+
+use crate::analysis::node_kind::{classify_node, NodeKind};
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{detect_language, parse_code, Language};
+
+const COMPLEXITY_HEADER: &str = "symbol|file|line|complexity|over";
+const DEFAULT_THRESHOLD: usize = 10;
+
+/// Complexity of one named function or method
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionComplexity {
+    pub name: String,
+    /// 1-based line of the declaration
+    pub line: usize,
+    pub complexity: usize,
+}
+
+/// Complexity of every named function in a parsed file, in source order.
+///
+/// Closures and lambdas count toward the function that contains them;
+/// named nested functions are reported on their own.
+pub fn function_complexities(
+    tree: &Tree,
+    source: &str,
+    language: Language,
+) -> Vec<FunctionComplexity> {
+    let mut functions = Vec::new();
+    collect_functions(tree.root_node(), source, language, &mut functions);
+    functions
+}
+
+/// Complexity of a function in a project file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFunctionComplexity {
+    pub symbol: String,
+    pub file: String,
+    pub line: usize,
+    pub complexity: usize,
+    /// Complexity is above the requested threshold
+    pub over_threshold: bool,
+}
+
+pub fn execute_complexity(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    let threshold = arguments["threshold"]
+        .as_u64()
+        .map(|v| v as usize)
+        .unwrap_or(DEFAULT_THRESHOLD);
+
+    log::info!("Measuring complexity in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let rows: Vec<String> = complexity_report(path, threshold)?
+        .iter()
+        .map(|function| {
+            format::format_row(&[
+                &function.symbol,
+                &function.file,
+                &function.line.to_string(),
+                &function.complexity.to_string(),
+                if function.over_threshold { "1" } else { "" },
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "threshold": threshold,
+        "h": COMPLEXITY_HEADER,
+        "fns": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Complexity of every function in a file or in every supported file of a
+/// directory, most complex first (ties in file and line order).
+pub fn complexity_report(
+    path: &Path,
+    threshold: usize,
+) -> Result<Vec<FileFunctionComplexity>, io::Error> {
+    let files = if path.is_file() {
+        vec![path.to_path_buf()]
+    } else {
+        collect_project_files(path)?
+    };
+
+    let mut report = Vec::new();
+    for file in files {
+        let Ok(language) = detect_language(&file) else {
+            continue;
+        };
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        let Ok(tree) = parse_code(&source, language) else {
+            continue;
+        };
+        let relative = path_utils::to_relative_path(&file.to_string_lossy());
+        report.extend(
+            function_complexities(&tree, &source, language)
+                .into_iter()
+                .map(|function| FileFunctionComplexity {
+                    over_threshold: function.complexity > threshold,
+                    symbol: function.name,
+                    file: relative.clone(),
+                    line: function.line,
+                    complexity: function.complexity,
+                }),
+        );
+    }
+
+    report.sort_by(|a, b| {
+        b.complexity
+            .cmp(&a.complexity)
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    Ok(report)
+}
+
+fn collect_functions(
+    node: Node,
+    source: &str,
+    language: Language,
+    out: &mut Vec<FunctionComplexity>,
+) {
+    if let Some(name) = function_name(node, source, language) {
+        out.push(FunctionComplexity {
+            name,
+            line: node.start_position().row + 1,
+            complexity: 1 + decision_points(node, source, language),
+        });
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_functions(child, source, language, out);
+    }
+}
+
+fn function_name(node: Node, source: &str, language: Language) -> Option<String> {
+    if classify_node(node.kind(), language) != NodeKind::FunctionDefinition {
+        return None;
+    }
+    node.child_by_field_name("name")
+        .and_then(|name| name.utf8_text(source.as_bytes()).ok())
+        .map(str::to_string)
+}
+
+/// Decision points under `node`, not descending into named nested functions.
+fn decision_points(node: Node, source: &str, language: Language) -> usize {
+    let mut count = 0;
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if function_name(child, source, language).is_some() {
+            continue;
+        }
+        count += node_decisions(child, language) + decision_points(child, source, language);
+    }
+    count
+}
+
+fn node_decisions(node: Node, language: Language) -> usize {
+    match node.kind() {
+        "if_expression"
+        | "if_statement"
+        | "elif_clause"
+        | "while_expression"
+        | "while_statement"
+        | "do_statement"
+        | "repeat_while_statement"
+        | "for_expression"
+        | "for_statement"
+        | "for_in_statement"
+        | "enhanced_for_statement"
+        | "foreach_statement"
+        | "conditional_expression"
+        | "ternary_expression"
+        | "catch_clause"
+        | "except_clause"
+        | "guard_statement"
+        | "case_clause"
+        | "switch_case"
+        | "expression_case"
+        | "type_case"
+        | "communication_case"
+        | "switch_section"
+        | "switch_block_statement_group"
+        | "switch_rule"
+        | "conjunction_expression"
+        | "disjunction_expression"
+        | "boolean_operator" => 1,
+        "try_expression" if language == Language::Rust => 1,
+        "match_expression" if language == Language::Rust => node
+            .child_by_field_name("body")
+            .map(|body| {
+                let mut cursor = body.walk();
+                let arms = body
+                    .named_children(&mut cursor)
+                    .filter(|arm| arm.kind() == "match_arm")
+                    .count();
+                arms.saturating_sub(1)
+            })
+            .unwrap_or(0),
+        "binary_expression" => {
+            let is_logical = node
+                .child_by_field_name("operator")
+                .is_some_and(|op| matches!(op.kind(), "&&" | "||" | "??"));
+            usize::from(is_logical)
+        }
+        _ => 0,
+    }
+}
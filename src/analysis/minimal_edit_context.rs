@@ -1,7 +1,6 @@
 //! Minimal edit context for one symbol.
 
 use std::collections::HashSet;
-use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 
@@ -17,7 +16,7 @@ use crate::analysis::shape::{
 };
 use crate::common::format;
 use crate::mcp_types::{CallToolResult, CallToolResultExt};
-use crate::parser::{detect_language, parse_code, Language};
+use crate::parser::{cache, detect_language, Language};
 
 const TARGET_HEADER: &str = "name|line|sig|code";
 const DEP_HEADER: &str = "kind|name|line|sig";
@@ -77,7 +76,7 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
         .map(|value| value as usize)
         .unwrap_or(2000);
 
-    let source = fs::read_to_string(file_path).map_err(|e| {
+    let source = path_utils::read_source_file(file_path).map_err(|e| {
         io::Error::new(
             io::ErrorKind::NotFound,
             format!("Failed to read file {file_path}: {e}"),
@@ -89,13 +88,16 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
             format!("Cannot detect language for file {file_path}: {e}"),
         )
     })?;
-    let tree = parse_code(&source, language).map_err(|e| {
-        io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("Failed to parse {} code: {e}", language.name()),
-        )
-    })?;
-    let shape = extract_enhanced_shape(&tree, &source, language, Some(file_path), true)?;
+    let parsed = cache::shared()
+        .parse(Path::new(file_path), &source, language)
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse {} code: {e}", language.name()),
+            )
+        })?;
+    let tree = &parsed.0;
+    let shape = extract_enhanced_shape(tree, &source, language, Some(file_path), true)?;
     let target = find_target_symbol(&shape, symbol).ok_or_else(|| {
         io::Error::new(
             io::ErrorKind::NotFound,
@@ -103,7 +105,7 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
         )
     })?;
 
-    let called_names = collect_called_names(&tree, &source, language, target.line, target.end_line);
+    let called_names = collect_called_names(tree, &source, language, target.line, target.end_line);
     let same_file_symbols = collect_same_file_signatures(&shape, None);
     let mut deps: Vec<SymbolSignature> = same_file_symbols
         .iter()
@@ -176,7 +178,7 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
 }
 
 fn find_target_symbol(shape: &EnhancedFileShape, symbol: &str) -> Option<TargetSymbol> {
-    for function in &shape.functions {
+    for function in shape.functions.iter().chain(&shape.components) {
         if function.name == symbol {
             return Some(target_from_function(function, ""));
         }
@@ -236,7 +238,7 @@ fn collect_same_file_signatures(
 ) -> Vec<SymbolSignature> {
     let mut signatures = Vec::new();
 
-    for function in &shape.functions {
+    for function in shape.functions.iter().chain(&shape.components) {
         signatures.push(SymbolSignature {
             kind: dependency_kind("fn", source_file),
             name: function.name.clone(),
@@ -291,24 +293,27 @@ fn collect_project_dependency_signatures(
         return Vec::new();
     }
 
-    let project_root = path_utils::find_project_root(file_path)
+    let project_root = path_utils::find_workspace_root(file_path)
+        .or_else(|| path_utils::find_project_root(file_path))
         .or_else(|| file_path.parent().map(Path::to_path_buf))
         .unwrap_or_else(|| PathBuf::from("."));
     let dependency_files = resolve_dependencies(language, source, file_path, &project_root);
     let mut signatures = Vec::new();
 
     for dependency_file in dependency_files {
-        let Ok(dependency_source) = fs::read_to_string(&dependency_file) else {
+        let Ok(dependency_source) = path_utils::read_source_file(&dependency_file) else {
             continue;
         };
         let Ok(dependency_language) = detect_language(&dependency_file) else {
             continue;
         };
-        let Ok(dependency_tree) = parse_code(&dependency_source, dependency_language) else {
+        let Ok(dependency_parsed) =
+            cache::shared().parse(&dependency_file, &dependency_source, dependency_language)
+        else {
             continue;
         };
         let Ok(dependency_shape) = extract_enhanced_shape(
-            &dependency_tree,
+            &dependency_parsed.0,
             &dependency_source,
             dependency_language,
             dependency_file.to_str(),
@@ -378,9 +383,13 @@ fn is_call_node(kind: &str, language: Language) -> bool {
     match language {
         Language::Rust => matches!(kind, "call_expression" | "method_call_expression"),
         Language::Python => kind == "call",
-        Language::JavaScript | Language::TypeScript | Language::Go => kind == "call_expression",
+        Language::JavaScript
+        | Language::TypeScript
+        | Language::Jsx
+        | Language::Tsx
+        | Language::Go => kind == "call_expression",
         Language::Java | Language::CSharp | Language::Swift => kind.ends_with("invocation"),
-        Language::Html | Language::Css => false,
+        Language::Html | Language::Css | Language::Svelte => false,
     }
 }
 
@@ -1,23 +1,60 @@
+pub mod annotation_inference;
 pub mod askama;
+pub mod build_files;
 pub mod call_graph;
 pub mod code_map;
+pub mod comments;
+pub mod context_assembler;
+pub mod coverage_map;
+pub mod css_analysis;
 pub mod dependencies;
 pub mod diff;
+pub mod django;
+pub mod fastapi;
 pub mod file_shape;
 pub mod find_usages;
 pub mod format_diagnostics;
 pub mod format_references;
+pub mod get_symbol;
+pub mod html_analysis;
+pub mod import_graph;
+pub mod jsx;
+pub mod liquid;
+pub mod lockfile;
+pub mod lsp;
+pub mod macros;
+pub mod metrics;
 pub mod minimal_edit_context;
+pub mod module_tree;
+pub mod nestjs;
+pub mod nextjs;
+pub mod node_kind;
 pub mod path_utils;
+pub mod pipeline;
+pub mod public_api;
 pub mod query_pattern;
+pub mod refactor;
 pub mod relevant_tests;
 pub mod review_context;
+pub mod routes;
+pub mod rust_edition;
+pub mod safety;
+pub mod semantic_tokens;
 pub mod shape;
+pub mod sqlalchemy;
+pub mod stale_imports;
+pub mod svelte;
 pub mod symbol_at_line;
+pub mod symbol_inventory;
+pub mod tests_finder;
+pub mod type_diff;
 pub mod type_map;
+pub mod unused;
 pub mod usage_counter;
 pub mod verify_edit;
 pub mod view_code;
+pub mod vue;
+pub mod workspace;
 
 #[cfg(test)]
 mod shape_tests;
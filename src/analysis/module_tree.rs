@@ -0,0 +1,346 @@
+//! Rust Module Tree Tool
+//!
+//! Reconstructs the module hierarchy of a Rust crate by following `mod`
+//! declarations from the crate root (`src/lib.rs` or `src/main.rs`).
+//!
+//! Output schema (nested, `path` omitted for inline modules and `children`
+//! omitted for leaves):
+//! ```json
+//! {
+//!   "name": "crate",
+//!   "path": "src/lib.rs",
+//!   "is_public": true,
+//!   "children": [
+//!     {"name": "api", "path": "src/api/mod.rs", "is_public": true, "children": [...]},
+//!     {"name": "tests", "is_public": false}
+//!   ]
+//! }
+//! ```
+//!
+//! `mod foo;` resolves to `foo.rs` or `foo/mod.rs` next to a `lib.rs`,
+//! `main.rs` or `mod.rs`, and below `bar/` for a declaration in `bar.rs`.
+//! Declarations whose file does not exist are left out. `is_public` is true
+//! for a plain `pub` only, not for `pub(crate)` and the like.
+//!
+//! With `max_depth`, modules at that depth (the crate root is depth 0) are
+//! listed without their children, as `"truncated": true` with a
+//! `child_count`. `module` (e.g. `api::handlers`) returns that module with
+//! its own children only, so an agent can expand a truncated node later.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+use tree_sitter::Node;
+
+use crate::analysis::path_utils;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{parse_code, Language};
+
+/// Nesting depth below which modules are not followed, guarding against
+/// symlink cycles
+const MAX_MODULE_DEPTH: usize = 20;
+
+/// Crate roots tried, in order, when given a directory
+const CRATE_ROOTS: &[&str] = &["src/lib.rs", "src/main.rs", "lib.rs", "main.rs"];
+
+/// A module and the modules it declares
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModuleNode {
+    pub name: String,
+    /// File of the module; `None` for inline `mod foo { ... }`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ModuleNode>,
+    pub is_public: bool,
+    /// Children left out at the depth limit
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+    /// Number of children left out, when `truncated`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub child_count: Option<usize>,
+}
+
+pub fn execute_module_tree(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+    let max_depth = arguments["max_depth"].as_u64().map(|depth| depth as usize);
+
+    log::info!("Building module tree for: {path_str}");
+
+    let tree = match arguments["module"].as_str() {
+        Some(module_path) => expand_module(module_path, Path::new(path_str))?,
+        None => build_module_tree(&crate_root(Path::new(path_str))?, max_depth)?,
+    };
+
+    let json_text = serde_json::to_string(&tree).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// The root file of the crate at `path`: `path` itself when it is a file,
+/// else the first of [`CRATE_ROOTS`] inside it
+pub fn crate_root(path: &Path) -> Result<PathBuf, io::Error> {
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+    if !path.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {}", path.display()),
+        ));
+    }
+    CRATE_ROOTS
+        .iter()
+        .map(|root| path.join(root))
+        .find(|root| root.is_file())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No src/lib.rs or src/main.rs in {}", path.display()),
+            )
+        })
+}
+
+/// Module tree rooted at the crate root file `root`
+#[allow(dead_code)]
+pub fn module_tree(root: &Path) -> Result<ModuleNode, io::Error> {
+    build_module_tree(root, None)
+}
+
+/// Module tree rooted at the crate root file `root`, with modules at
+/// `max_depth` truncated
+pub fn build_module_tree(root: &Path, max_depth: Option<usize>) -> Result<ModuleNode, io::Error> {
+    let module_dir = root.parent().unwrap_or(Path::new("."));
+    let max_depth = max_depth.unwrap_or(MAX_MODULE_DEPTH);
+    let mut node = ModuleNode {
+        name: "crate".to_string(),
+        path: Some(path_utils::to_relative_path(&root.to_string_lossy())),
+        children: file_modules(root, module_dir, 1, max_depth)?,
+        is_public: true,
+        truncated: false,
+        child_count: None,
+    };
+    if max_depth == 0 {
+        truncate(&mut node);
+    }
+    Ok(node)
+}
+
+/// The module at `module_path` (`crate::api::handlers` or `api::handlers`)
+/// in the crate at `project_root`, with its children truncated
+pub fn expand_module(module_path: &str, project_root: &Path) -> Result<ModuleNode, io::Error> {
+    let root = crate_root(project_root)?;
+    let segments: Vec<&str> = module_path
+        .split("::")
+        .filter(|segment| !segment.is_empty() && *segment != "crate")
+        .collect();
+    if segments.is_empty() {
+        return build_module_tree(&root, Some(1));
+    }
+    let not_found = || {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("No module {module_path} in {}", root.display()),
+        )
+    };
+
+    // Where the `mod` items of the current module are: a file, its module
+    // directory, and the inline modules leading to them inside that file
+    let mut file = root.clone();
+    let mut module_dir = root.parent().unwrap_or(Path::new(".")).to_path_buf();
+    let mut inline: Vec<&str> = Vec::new();
+    let mut is_public = true;
+    for segment in &segments {
+        let (source, tree) = parse_module_file(&file)?;
+        let container =
+            inline_container(tree.root_node(), &source, &inline).ok_or_else(not_found)?;
+        let item = mod_items(container)
+            .find(|item| mod_name(*item, &source) == Some(segment))
+            .ok_or_else(not_found)?;
+        is_public = is_pub(item, &source);
+        let child_dir = module_dir.join(segment);
+        if item.child_by_field_name("body").is_some() {
+            inline.push(segment);
+        } else {
+            file = module_file(&module_dir, segment).ok_or_else(not_found)?;
+            inline.clear();
+        }
+        module_dir = child_dir;
+    }
+
+    let (source, tree) = parse_module_file(&file)?;
+    let container = inline_container(tree.root_node(), &source, &inline).ok_or_else(not_found)?;
+    let depth = segments.len() + 1;
+    Ok(ModuleNode {
+        name: segments[segments.len() - 1].to_string(),
+        path: inline
+            .is_empty()
+            .then(|| path_utils::to_relative_path(&file.to_string_lossy())),
+        children: declared_modules(container, &source, &module_dir, depth, depth),
+        is_public,
+        truncated: false,
+        child_count: None,
+    })
+}
+
+/// Modules declared in `file`, whose `mod foo;` files live in `module_dir`
+fn file_modules(
+    file: &Path,
+    module_dir: &Path,
+    depth: usize,
+    max_depth: usize,
+) -> Result<Vec<ModuleNode>, io::Error> {
+    let (source, tree) = parse_module_file(file)?;
+    Ok(declared_modules(
+        tree.root_node(),
+        &source,
+        module_dir,
+        depth,
+        max_depth,
+    ))
+}
+
+fn parse_module_file(file: &Path) -> Result<(String, tree_sitter::Tree), io::Error> {
+    let source = path_utils::read_source_file(file).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Failed to read file {}: {e}", file.display()),
+        )
+    })?;
+    let tree = parse_code(&source, Language::Rust).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to parse {}: {e}", file.display()),
+        )
+    })?;
+    Ok((source, tree))
+}
+
+/// `mod` items directly inside `container` (a file or an inline module
+/// body). Modules deeper than `max_depth` are only listed, to be counted,
+/// and modules at `max_depth` are truncated.
+fn declared_modules(
+    container: Node,
+    source: &str,
+    module_dir: &Path,
+    depth: usize,
+    max_depth: usize,
+) -> Vec<ModuleNode> {
+    let mut modules = Vec::new();
+    for item in mod_items(container) {
+        let Some(name) = mod_name(item, source) else {
+            continue;
+        };
+        let child_dir = module_dir.join(name);
+
+        if depth >= MAX_MODULE_DEPTH {
+            log::warn!(
+                "Module tree deeper than {MAX_MODULE_DEPTH} at {}; not following further",
+                child_dir.display()
+            );
+            continue;
+        }
+
+        let (path, children) = if let Some(body) = item.child_by_field_name("body") {
+            let children = if depth > max_depth {
+                Vec::new()
+            } else {
+                declared_modules(body, source, &child_dir, depth + 1, max_depth)
+            };
+            (None, children)
+        } else {
+            let Some(file) = module_file(module_dir, name) else {
+                log::debug!("No file for module {name} in {}", module_dir.display());
+                continue;
+            };
+            let children = if depth > max_depth {
+                Vec::new()
+            } else {
+                file_modules(&file, &child_dir, depth + 1, max_depth).unwrap_or_else(|e| {
+                    log::debug!("Skipping modules of {}: {e}", file.display());
+                    Vec::new()
+                })
+            };
+            (
+                Some(path_utils::to_relative_path(&file.to_string_lossy())),
+                children,
+            )
+        };
+
+        let mut module = ModuleNode {
+            name: name.to_string(),
+            path,
+            children,
+            is_public: is_pub(item, source),
+            truncated: false,
+            child_count: None,
+        };
+        if depth == max_depth {
+            truncate(&mut module);
+        }
+        modules.push(module);
+    }
+    modules
+}
+
+/// Replace the children of `node` by their count
+fn truncate(node: &mut ModuleNode) {
+    if !node.children.is_empty() {
+        node.child_count = Some(node.children.len());
+        node.truncated = true;
+        node.children.clear();
+    }
+}
+
+fn mod_items(container: Node) -> impl Iterator<Item = Node> {
+    let mut cursor = container.walk();
+    container
+        .named_children(&mut cursor)
+        .filter(|item| item.kind() == "mod_item")
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+fn mod_name<'a>(item: Node, source: &'a str) -> Option<&'a str> {
+    item.child_by_field_name("name")
+        .and_then(|name| name.utf8_text(source.as_bytes()).ok())
+}
+
+fn is_pub(item: Node, source: &str) -> bool {
+    let mut cursor = item.walk();
+    let is_public = item.named_children(&mut cursor).any(|child| {
+        child.kind() == "visibility_modifier" && child.utf8_text(source.as_bytes()) == Ok("pub")
+    });
+    is_public
+}
+
+/// `name.rs` or `name/mod.rs` in `module_dir`
+fn module_file(module_dir: &Path, name: &str) -> Option<PathBuf> {
+    [
+        module_dir.join(format!("{name}.rs")),
+        module_dir.join(name).join("mod.rs"),
+    ]
+    .into_iter()
+    .find(|candidate| candidate.is_file())
+}
+
+/// Body of the inline module reached through `names` from `root`
+fn inline_container<'a>(root: Node<'a>, source: &str, names: &[&str]) -> Option<Node<'a>> {
+    names.iter().try_fold(root, |container, name| {
+        mod_items(container)
+            .find(|item| mod_name(*item, source) == Some(name))?
+            .child_by_field_name("body")
+    })
+}
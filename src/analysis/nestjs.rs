@@ -0,0 +1,377 @@
+//! NestJS Modules and Routes Tools
+//!
+//! Reads `@Module({ imports, controllers, providers, exports })` metadata
+//! and `@Controller('prefix')` classes with `@Get(':id')`-style handlers from
+//! TypeScript sources. Routes are linked to the module listing their
+//! controller in its `controllers` array.
+//!
+//! Output schema (`list_nest_modules`):
+//! ```json
+//! {
+//!   "h": "module|file|line|imports|controllers|providers|exports",
+//!   "modules": "UsersModule|src/users/users.module.ts|6|TypeOrmModule.forFeature([User])|UsersController|UsersService|UsersService\n..."
+//! }
+//! ```
+//!
+//! Output schema (`list_nest_routes`):
+//! ```json
+//! {
+//!   "h": "method|path|controller|handler|module|file|line",
+//!   "routes": "GET|/users/:id|UsersController|findOne|UsersModule|src/users/users.controller.ts|14\n..."
+//! }
+//! ```
+//!
+//! List columns are comma-separated; entries keep their source text, so a
+//! provider like `{ provide: TOKEN, useClass: Impl }` is listed as `TOKEN`.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::extraction::types::{
+    collapse_whitespace, ts_class_decorators, ts_decorator_call, ts_decorator_object,
+    ts_object_pairs,
+};
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{parse_code, Language};
+
+const MODULES_HEADER: &str = "module|file|line|imports|controllers|providers|exports";
+const ROUTES_HEADER: &str = "method|path|controller|handler|module|file|line";
+
+/// Handler decorators and the HTTP method they map to
+const METHOD_DECORATORS: [(&str, &str); 8] = [
+    ("Get", "GET"),
+    ("Post", "POST"),
+    ("Put", "PUT"),
+    ("Patch", "PATCH"),
+    ("Delete", "DELETE"),
+    ("Options", "OPTIONS"),
+    ("Head", "HEAD"),
+    ("All", "ALL"),
+];
+
+/// A class decorated with `@Module`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestModule {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub imports: Vec<String>,
+    pub controllers: Vec<String>,
+    pub providers: Vec<String>,
+    pub exports: Vec<String>,
+}
+
+/// A controller handler bound to an HTTP method
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestRoute {
+    /// Upper-case HTTP method, or `ALL`
+    pub method: String,
+    /// Controller prefix and handler path joined, e.g. `/users/:id`
+    pub path: String,
+    pub controller: String,
+    pub handler: String,
+    /// Module listing the controller, once linked
+    pub module: Option<String>,
+    pub file: PathBuf,
+    /// 1-based line of the handler
+    pub line: usize,
+}
+
+pub fn execute_list_nest_modules(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path = path_argument(arguments)?;
+    log::info!("Listing NestJS modules in: {}", path.display());
+
+    let (modules, _) = scan(&path)?;
+    let rows: Vec<String> = modules
+        .iter()
+        .map(|module| {
+            format::format_row(&[
+                &module.name,
+                &relative(&module.file),
+                &module.line.to_string(),
+                &module.imports.join(","),
+                &module.controllers.join(","),
+                &module.providers.join(","),
+                &module.exports.join(","),
+            ])
+        })
+        .collect();
+
+    to_result(json!({
+        "h": MODULES_HEADER,
+        "modules": rows.join("\n"),
+    }))
+}
+
+pub fn execute_list_nest_routes(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path = path_argument(arguments)?;
+    log::info!("Listing NestJS routes in: {}", path.display());
+
+    let (modules, mut routes) = scan(&path)?;
+    link_routes_to_modules(&mut routes, &modules);
+    let rows: Vec<String> = routes
+        .iter()
+        .map(|route| {
+            format::format_row(&[
+                &route.method,
+                &route.path,
+                &route.controller,
+                &route.handler,
+                route.module.as_deref().unwrap_or(""),
+                &relative(&route.file),
+                &route.line.to_string(),
+            ])
+        })
+        .collect();
+
+    to_result(json!({
+        "h": ROUTES_HEADER,
+        "routes": rows.join("\n"),
+    }))
+}
+
+/// `@Module` classes declared in a TypeScript source file.
+pub fn extract_nest_modules(source: &str, file_path: &Path) -> Vec<NestModule> {
+    let mut modules = Vec::new();
+    for_each_class(source, |class, name| {
+        let Some((_, call)) = ts_class_decorators(class, source.as_bytes())
+            .into_iter()
+            .find(|(decorator, _)| decorator == "Module")
+        else {
+            return;
+        };
+
+        let mut module = NestModule {
+            name: name.to_string(),
+            file: file_path.to_path_buf(),
+            line: class.start_position().row + 1,
+            imports: Vec::new(),
+            controllers: Vec::new(),
+            providers: Vec::new(),
+            exports: Vec::new(),
+        };
+        if let Some(object) = ts_decorator_object(call) {
+            for (key, value) in ts_object_pairs(object, source.as_bytes()) {
+                let entries = array_entries(value, source);
+                match key.as_str() {
+                    "imports" => module.imports = entries,
+                    "controllers" => module.controllers = entries,
+                    "providers" => module.providers = entries,
+                    "exports" => module.exports = entries,
+                    _ => {}
+                }
+            }
+        }
+        modules.push(module);
+    });
+    modules
+}
+
+/// Handlers of `@Controller` classes declared in a TypeScript source file.
+///
+/// Routes are not linked to modules; see [`link_routes_to_modules`].
+pub fn extract_nest_routes(source: &str, file_path: &Path) -> Vec<NestRoute> {
+    let source_bytes = source.as_bytes();
+    let mut routes = Vec::new();
+    for_each_class(source, |class, name| {
+        let Some((_, call)) = ts_class_decorators(class, source_bytes)
+            .into_iter()
+            .find(|(decorator, _)| decorator == "Controller")
+        else {
+            return;
+        };
+        let prefix = controller_prefix(call, source);
+        let Some(body) = class.child_by_field_name("body") else {
+            return;
+        };
+
+        // Method decorators are siblings preceding the method in the class body.
+        let mut pending = Vec::new();
+        let mut walker = body.walk();
+        for member in body.named_children(&mut walker) {
+            match member.kind() {
+                "decorator" => pending.extend(ts_decorator_call(member, source_bytes)),
+                "method_definition" => {
+                    let handler = member
+                        .child_by_field_name("name")
+                        .map(|name| text(name, source))
+                        .unwrap_or_default();
+                    for (decorator, call) in pending.drain(..) {
+                        let Some((_, method)) = METHOD_DECORATORS
+                            .iter()
+                            .find(|(candidate, _)| *candidate == decorator)
+                        else {
+                            continue;
+                        };
+                        routes.push(NestRoute {
+                            method: method.to_string(),
+                            path: join_route(&prefix, &first_string_argument(call, source)),
+                            controller: name.to_string(),
+                            handler: handler.to_string(),
+                            module: None,
+                            file: file_path.to_path_buf(),
+                            line: member.start_position().row + 1,
+                        });
+                    }
+                }
+                "comment" => {}
+                _ => pending.clear(),
+            }
+        }
+    });
+    routes
+}
+
+/// Set each route's module to the module listing its controller.
+pub fn link_routes_to_modules(routes: &mut [NestRoute], modules: &[NestModule]) {
+    for route in routes {
+        route.module = modules
+            .iter()
+            .find(|module| module.controllers.contains(&route.controller))
+            .map(|module| module.name.clone());
+    }
+}
+
+fn path_argument(arguments: &Value) -> Result<PathBuf, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+    let path = PathBuf::from(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+    Ok(path)
+}
+
+/// Modules and unlinked routes from every `.ts` file under `path`
+fn scan(path: &Path) -> Result<(Vec<NestModule>, Vec<NestRoute>), io::Error> {
+    let mut modules = Vec::new();
+    let mut routes = Vec::new();
+    for file in collect_project_files(path)? {
+        if file.extension().and_then(|ext| ext.to_str()) != Some("ts") {
+            continue;
+        }
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        if source.contains("@Module") {
+            modules.extend(extract_nest_modules(&source, &file));
+        }
+        if source.contains("@Controller") {
+            routes.extend(extract_nest_routes(&source, &file));
+        }
+    }
+    Ok((modules, routes))
+}
+
+fn for_each_class<'a>(source: &'a str, mut visit: impl FnMut(Node, &'a str)) {
+    let Ok(tree) = parse_code(source, Language::TypeScript) else {
+        return;
+    };
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "class_declaration" {
+            if let Some(name) = node.child_by_field_name("name") {
+                visit(node, text(name, source));
+            }
+            continue;
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+}
+
+/// `@Controller('users')` or `@Controller({ path: 'users' })`
+fn controller_prefix(call: Node, source: &str) -> String {
+    if let Some(object) = ts_decorator_object(call) {
+        return ts_object_pairs(object, source.as_bytes())
+            .into_iter()
+            .find(|(key, _)| key == "path")
+            .and_then(|(_, value)| string_literal(value, source))
+            .unwrap_or_default();
+    }
+    first_string_argument(call, source)
+}
+
+fn first_string_argument(call: Node, source: &str) -> String {
+    call.child_by_field_name("arguments")
+        .and_then(|arguments| arguments.named_child(0))
+        .and_then(|argument| string_literal(argument, source))
+        .unwrap_or_default()
+}
+
+fn string_literal(node: Node, source: &str) -> Option<String> {
+    matches!(node.kind(), "string" | "template_string").then(|| {
+        text(node, source)
+            .trim_matches(['\'', '"', '`'])
+            .to_string()
+    })
+}
+
+/// `/users/:id` from `users` and `:id`; an empty route is `/`.
+fn join_route(prefix: &str, path: &str) -> String {
+    let segments: Vec<&str> = [prefix, path]
+        .iter()
+        .map(|part| part.trim_matches('/'))
+        .filter(|part| !part.is_empty())
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+/// Array entries as text; `{ provide: X, ... }` providers are listed as `X`.
+fn array_entries(value: Node, source: &str) -> Vec<String> {
+    if value.kind() != "array" {
+        return vec![collapse_whitespace(text(value, source))];
+    }
+
+    let mut cursor = value.walk();
+    let entries = value
+        .named_children(&mut cursor)
+        .filter(|entry| entry.kind() != "comment")
+        .map(|entry| {
+            if entry.kind() == "object" {
+                if let Some((_, provide)) = ts_object_pairs(entry, source.as_bytes())
+                    .into_iter()
+                    .find(|(key, _)| key == "provide")
+                {
+                    return string_literal(provide, source)
+                        .unwrap_or_else(|| text(provide, source).to_string());
+                }
+            }
+            collapse_whitespace(text(entry, source))
+        })
+        .collect();
+    entries
+}
+
+fn relative(path: &Path) -> String {
+    path_utils::to_relative_path(&path.to_string_lossy())
+}
+
+fn to_result(result: Value) -> Result<CallToolResult, io::Error> {
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+fn text<'a>(node: Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
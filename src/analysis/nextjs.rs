@@ -0,0 +1,239 @@
+//! Next.js Routes Tool
+//!
+//! Derives App Router routes from the `app/` directory layout:
+//! `app/dashboard/[id]/page.tsx` serves `/dashboard/[id]`. Route groups
+//! (`(marketing)`) and parallel-route slots (`@modal`) do not appear in the
+//! URL; private folders (`_components`) are not routable. Both pages
+//! (`page.*`) and route handlers (`route.*`) are listed.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "app": "app",
+//!   "h": "route|file|loading|error|layouts|exports",
+//!   "routes": "/dashboard|app/dashboard/page.tsx|1|0|app/layout.tsx,app/dashboard/layout.tsx|default,generateMetadata\n..."
+//! }
+//! ```
+//!
+//! `loading`/`error` are `1` when a `loading.*`/`error.*` file in the route's
+//! folder or any parent folder covers it. `layouts` lists the layouts that
+//! wrap the route, outermost first.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+
+use crate::analysis::path_utils;
+use crate::analysis::shape::extract_enhanced_shape;
+use crate::analysis::symbol_inventory::{declaration_text, visibility_of};
+use crate::common::format;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{detect_language, parse_code};
+
+const ROUTES_HEADER: &str = "route|file|loading|error|layouts|exports";
+
+const SCRIPT_EXTENSIONS: [&str; 4] = ["tsx", "ts", "jsx", "js"];
+
+/// A routable page or route handler
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NextjsRoute {
+    /// URL path, e.g. `/blog/[slug]`
+    pub path: String,
+    /// The `page.*` or `route.*` file
+    pub file: PathBuf,
+    pub has_loading: bool,
+    pub has_error: bool,
+    /// Layouts wrapping the route, outermost first
+    pub layout_files: Vec<PathBuf>,
+    /// Exported functions such as `default`, `generateMetadata` or `GET`
+    pub exported_functions: Vec<String>,
+}
+
+pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    log::info!("Listing Next.js routes in: {path_str}");
+
+    let app_dir = find_app_dir(Path::new(path_str)).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("No Next.js app directory found in {path_str}"),
+        )
+    })?;
+
+    let relative = |path: &Path| path_utils::to_relative_path(&path.to_string_lossy());
+    let rows: Vec<String> = extract_nextjs_routes(&app_dir)
+        .iter()
+        .map(|route| {
+            let layouts = route
+                .layout_files
+                .iter()
+                .map(|layout| relative(layout))
+                .collect::<Vec<_>>()
+                .join(",");
+            format::format_row(&[
+                &route.path,
+                &relative(&route.file),
+                flag(route.has_loading),
+                flag(route.has_error),
+                &layouts,
+                &route.exported_functions.join(","),
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "app": relative(&app_dir),
+        "h": ROUTES_HEADER,
+        "routes": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Routes under an App Router `app` directory, sorted by URL path.
+pub fn extract_nextjs_routes(app_dir: &Path) -> Vec<NextjsRoute> {
+    let mut routes = Vec::new();
+    collect_routes(app_dir, "", &Wrappers::default(), &mut routes);
+    routes.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.file.cmp(&b.file)));
+    routes
+}
+
+/// `app/` or `src/app/` under a project root, or `path` itself when it is
+/// named `app`.
+fn find_app_dir(path: &Path) -> Option<PathBuf> {
+    if path.file_name().is_some_and(|name| name == "app") && path.is_dir() {
+        return Some(path.to_path_buf());
+    }
+    [path.join("app"), path.join("src").join("app")]
+        .into_iter()
+        .find(|candidate| candidate.is_dir())
+}
+
+/// Special files inherited from parent folders
+#[derive(Debug, Clone, Default)]
+struct Wrappers {
+    layouts: Vec<PathBuf>,
+    loading: bool,
+    error: bool,
+}
+
+fn collect_routes(dir: &Path, url: &str, inherited: &Wrappers, routes: &mut Vec<NextjsRoute>) {
+    let mut wrappers = inherited.clone();
+    if let Some(layout) = special_file(dir, "layout") {
+        wrappers.layouts.push(layout);
+    }
+    wrappers.loading |= special_file(dir, "loading").is_some();
+    wrappers.error |= special_file(dir, "error").is_some();
+
+    let route_path = if url.is_empty() {
+        "/".to_string()
+    } else {
+        url.to_string()
+    };
+    for kind in ["page", "route"] {
+        if let Some(file) = special_file(dir, kind) {
+            routes.push(NextjsRoute {
+                path: route_path.clone(),
+                exported_functions: exported_functions(&file),
+                file,
+                has_loading: wrappers.loading,
+                has_error: wrappers.error,
+                layout_files: wrappers.layouts.clone(),
+            });
+        }
+    }
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut children: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    children.sort();
+
+    for child in children {
+        let Some(segment) = child.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if segment.starts_with('_') {
+            continue;
+        }
+        let child_url = if is_group(segment) || segment.starts_with('@') {
+            url.to_string()
+        } else {
+            format!("{url}/{segment}")
+        };
+        collect_routes(&child, &child_url, &wrappers, routes);
+    }
+}
+
+/// `(group)` folders organize routes without adding a URL segment.
+fn is_group(segment: &str) -> bool {
+    segment.starts_with('(') && segment.ends_with(')') && !segment.contains('.')
+}
+
+fn special_file(dir: &Path, stem: &str) -> Option<PathBuf> {
+    SCRIPT_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{stem}.{ext}")))
+        .find(|path| path.is_file())
+}
+
+/// Exported function names; the default export is listed as `default`.
+fn exported_functions(file: &Path) -> Vec<String> {
+    let Ok(language) = detect_language(file) else {
+        return Vec::new();
+    };
+    let Ok(source) = path_utils::read_source_file(file) else {
+        return Vec::new();
+    };
+    let Ok(tree) = parse_code(&source, language) else {
+        return Vec::new();
+    };
+    let Ok(shape) = extract_enhanced_shape(&tree, &source, language, None, false) else {
+        return Vec::new();
+    };
+
+    let lines: Vec<&str> = source.lines().collect();
+    let mut exports = Vec::new();
+    for function in shape.functions.iter().chain(&shape.components) {
+        let declaration = declaration_text(&lines, function.line, &function.name);
+        if visibility_of(language, &function.name, &declaration, false) != "public" {
+            continue;
+        }
+        let name = if declaration.contains("export default") {
+            "default"
+        } else {
+            function.name.as_str()
+        };
+        if !exports.iter().any(|export| export == name) {
+            exports.push(name.to_string());
+        }
+    }
+    exports
+}
+
+fn flag(value: bool) -> &'static str {
+    if value {
+        "1"
+    } else {
+        "0"
+    }
+}
@@ -0,0 +1,292 @@
+//! Node Kind Taxonomy
+//!
+//! Maps language-specific tree-sitter node kinds (`function_item`,
+//! `call_expression`, `call`, ...) to a small set of semantic categories so
+//! callers can reason about nodes without knowing each grammar.
+
+use serde::{Deserialize, Serialize};
+
+use crate::parser::Language;
+
+/// Semantic category of a tree-sitter node
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    FunctionDefinition,
+    FunctionCall,
+    TypeDefinition,
+    Import,
+    Comment,
+    StringLiteral,
+    NumberLiteral,
+    BooleanLiteral,
+    BinaryExpression,
+    UnaryExpression,
+    Assignment,
+    VariableDeclaration,
+    Parameter,
+    FieldAccess,
+    Identifier,
+    TypeReference,
+    ControlFlow,
+    Return,
+    Block,
+    Decorator,
+    Module,
+    Other,
+}
+
+impl NodeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NodeKind::FunctionDefinition => "function_definition",
+            NodeKind::FunctionCall => "function_call",
+            NodeKind::TypeDefinition => "type_definition",
+            NodeKind::Import => "import",
+            NodeKind::Comment => "comment",
+            NodeKind::StringLiteral => "string_literal",
+            NodeKind::NumberLiteral => "number_literal",
+            NodeKind::BooleanLiteral => "boolean_literal",
+            NodeKind::BinaryExpression => "binary_expression",
+            NodeKind::UnaryExpression => "unary_expression",
+            NodeKind::Assignment => "assignment",
+            NodeKind::VariableDeclaration => "variable_declaration",
+            NodeKind::Parameter => "parameter",
+            NodeKind::FieldAccess => "field_access",
+            NodeKind::Identifier => "identifier",
+            NodeKind::TypeReference => "type_reference",
+            NodeKind::ControlFlow => "control_flow",
+            NodeKind::Return => "return",
+            NodeKind::Block => "block",
+            NodeKind::Decorator => "decorator",
+            NodeKind::Module => "module",
+            NodeKind::Other => "other",
+        }
+    }
+}
+
+/// Classify a raw tree-sitter node kind for `language`.
+///
+/// Unknown kinds map to [`NodeKind::Other`].
+pub fn classify_node(kind: &str, language: Language) -> NodeKind {
+    if let Some(node_kind) = classify_language_specific(kind, language) {
+        return node_kind;
+    }
+
+    match kind {
+        "function_item"
+        | "function_signature_item"
+        | "function_definition"
+        | "function_declaration"
+        | "generator_function_declaration"
+        | "function_expression"
+        | "arrow_function"
+        | "method_definition"
+        | "method_declaration"
+        | "constructor_declaration"
+        | "closure_expression"
+        | "lambda"
+        | "lambda_expression"
+        | "func_literal"
+        | "init_declaration" => NodeKind::FunctionDefinition,
+
+        "call_expression"
+        | "call"
+        | "macro_invocation"
+        | "method_invocation"
+        | "invocation_expression"
+        | "new_expression"
+        | "object_creation_expression" => NodeKind::FunctionCall,
+
+        "struct_item"
+        | "enum_item"
+        | "union_item"
+        | "trait_item"
+        | "type_item"
+        | "impl_item"
+        | "class_definition"
+        | "class_declaration"
+        | "abstract_class_declaration"
+        | "interface_declaration"
+        | "enum_declaration"
+        | "struct_declaration"
+        | "record_declaration"
+        | "protocol_declaration"
+        | "type_alias_declaration"
+        | "type_declaration"
+        | "type_spec" => NodeKind::TypeDefinition,
+
+        "use_declaration"
+        | "extern_crate_declaration"
+        | "import_statement"
+        | "import_from_statement"
+        | "future_import_statement"
+        | "import_declaration"
+        | "using_directive" => NodeKind::Import,
+
+        "comment" | "line_comment" | "block_comment" | "doc_comment" | "multiline_comment" => {
+            NodeKind::Comment
+        }
+
+        "string"
+        | "string_literal"
+        | "raw_string_literal"
+        | "char_literal"
+        | "character_literal"
+        | "template_string"
+        | "concatenated_string"
+        | "interpreted_string_literal"
+        | "line_string_literal"
+        | "verbatim_string_literal" => NodeKind::StringLiteral,
+
+        "integer"
+        | "float"
+        | "number"
+        | "integer_literal"
+        | "float_literal"
+        | "int_literal"
+        | "real_literal"
+        | "decimal_integer_literal"
+        | "decimal_floating_point_literal" => NodeKind::NumberLiteral,
+
+        "true" | "false" | "boolean_literal" => NodeKind::BooleanLiteral,
+
+        "binary_expression"
+        | "binary_operator"
+        | "boolean_operator"
+        | "comparison_operator"
+        | "range_expression" => NodeKind::BinaryExpression,
+
+        "unary_expression" | "not_operator" | "prefix_expression" | "negation" => {
+            NodeKind::UnaryExpression
+        }
+
+        "assignment_expression"
+        | "assignment"
+        | "augmented_assignment"
+        | "compound_assignment_expr"
+        | "augmented_assignment_expression"
+        | "assignment_statement" => NodeKind::Assignment,
+
+        "let_declaration"
+        | "const_item"
+        | "static_item"
+        | "variable_declaration"
+        | "lexical_declaration"
+        | "variable_declarator"
+        | "local_variable_declaration"
+        | "local_declaration_statement"
+        | "field_declaration"
+        | "short_var_declaration"
+        | "var_declaration"
+        | "const_declaration"
+        | "property_declaration" => NodeKind::VariableDeclaration,
+
+        "parameter"
+        | "parameters"
+        | "self_parameter"
+        | "formal_parameter"
+        | "formal_parameters"
+        | "required_parameter"
+        | "optional_parameter"
+        | "typed_parameter"
+        | "default_parameter"
+        | "typed_default_parameter"
+        | "parameter_declaration"
+        | "parameter_list" => NodeKind::Parameter,
+
+        "field_expression"
+        | "member_expression"
+        | "attribute"
+        | "selector_expression"
+        | "field_access"
+        | "member_access_expression"
+        | "navigation_expression"
+        | "scoped_identifier" => NodeKind::FieldAccess,
+
+        "identifier"
+        | "field_identifier"
+        | "property_identifier"
+        | "shorthand_property_identifier"
+        | "simple_identifier" => NodeKind::Identifier,
+
+        "type_identifier"
+        | "primitive_type"
+        | "generic_type"
+        | "reference_type"
+        | "scoped_type_identifier"
+        | "predefined_type"
+        | "type_annotation"
+        | "user_type"
+        | "qualified_type" => NodeKind::TypeReference,
+
+        "if_expression"
+        | "if_statement"
+        | "else_clause"
+        | "elif_clause"
+        | "match_expression"
+        | "switch_statement"
+        | "switch_expression"
+        | "expression_switch_statement"
+        | "for_expression"
+        | "for_statement"
+        | "for_in_statement"
+        | "foreach_statement"
+        | "enhanced_for_statement"
+        | "while_expression"
+        | "while_statement"
+        | "do_statement"
+        | "loop_expression"
+        | "try_statement"
+        | "try_expression"
+        | "guard_statement"
+        | "break_expression"
+        | "break_statement"
+        | "continue_expression"
+        | "continue_statement"
+        | "throw_statement"
+        | "raise_statement" => NodeKind::ControlFlow,
+
+        "return_expression" | "return_statement" => NodeKind::Return,
+
+        "block" | "statement_block" | "compound_statement" | "declaration_list" | "class_body" => {
+            NodeKind::Block
+        }
+
+        "attribute_item"
+        | "inner_attribute_item"
+        | "decorator"
+        | "annotation"
+        | "marker_annotation"
+        | "attribute_list" => NodeKind::Decorator,
+
+        "source_file"
+        | "module"
+        | "program"
+        | "compilation_unit"
+        | "mod_item"
+        | "namespace_declaration"
+        | "package_clause" => NodeKind::Module,
+
+        _ => NodeKind::Other,
+    }
+}
+
+/// Kinds whose meaning differs between grammars
+fn classify_language_specific(kind: &str, language: Language) -> Option<NodeKind> {
+    let node_kind = match (language, kind) {
+        // Rust's `attribute` is the inside of `#[...]`; Python's is `obj.attr`.
+        (Language::Rust, "attribute") => NodeKind::Decorator,
+        (Language::Html | Language::Svelte | Language::Css, "comment") => NodeKind::Comment,
+        (Language::Html | Language::Svelte, "element" | "script_element" | "style_element") => {
+            NodeKind::Block
+        }
+        (Language::Css, "rule_set" | "media_statement") => NodeKind::Block,
+        (Language::Css, "import_statement") => NodeKind::Import,
+        (Language::Css, "string_value") => NodeKind::StringLiteral,
+        (Language::Css, "integer_value" | "float_value") => NodeKind::NumberLiteral,
+        (Language::Html | Language::Svelte | Language::Css, _) => NodeKind::Other,
+        _ => return None,
+    };
+    Some(node_kind)
+}
@@ -1,6 +1,14 @@
+use serde::{Deserialize, Serialize};
 use std::env;
+use std::fs::{self, File};
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 
+use crate::analysis::workspace;
+
+/// Bytes sampled from the start of a file by [`is_binary_file`]
+const BINARY_SNIFF_BYTES: u64 = 8 * 1024;
+
 /// Convert an absolute path to a relative path from project/git root
 pub fn to_relative_path(path: &str) -> String {
     let path_buf = PathBuf::from(path);
@@ -81,6 +89,147 @@ fn find_git_root(path: &Path) -> Option<PathBuf> {
     }
 }
 
+/// Whether a file looks binary: its first 8 KB contain a null byte, or more
+/// than 1% of them are not valid UTF-8. Unreadable files are reported as text
+/// so the caller's own read surfaces the error.
+pub fn is_binary_file(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    let mut sample = Vec::new();
+    if file
+        .take(BINARY_SNIFF_BYTES)
+        .read_to_end(&mut sample)
+        .is_err()
+    {
+        return false;
+    }
+
+    if sample.starts_with(UTF16_LE_BOM) || sample.starts_with(UTF16_BE_BOM) {
+        return false;
+    }
+    if sample.contains(&0) {
+        return true;
+    }
+
+    let mut invalid = 0;
+    let mut rest = sample.as_slice();
+    while let Err(err) = std::str::from_utf8(rest) {
+        // `None` means a character cut off by the end of the sample
+        let Some(len) = err.error_len() else {
+            break;
+        };
+        invalid += len;
+        rest = &rest[err.valid_up_to() + len..];
+    }
+
+    invalid * 100 > sample.len()
+}
+
+const UTF16_LE_BOM: &[u8] = &[0xff, 0xfe];
+const UTF16_BE_BOM: &[u8] = &[0xfe, 0xff];
+
+/// Encoding a source file was decoded from by [`read_source_file`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SourceEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Windows1252,
+}
+
+/// Read a source file as text: UTF-8, then UTF-16 with a byte order mark,
+/// then Windows-1252 (a superset of Latin-1, so it decodes any byte string).
+pub fn read_source_file(path: impl AsRef<Path>) -> io::Result<String> {
+    read_source_file_with_encoding(path).map(|(source, _)| source)
+}
+
+/// [`read_source_file`], also reporting which encoding was used
+pub fn read_source_file_with_encoding(
+    path: impl AsRef<Path>,
+) -> io::Result<(String, SourceEncoding)> {
+    let path = path.as_ref();
+    let bytes = fs::read(path)?;
+    let bytes = match String::from_utf8(bytes) {
+        Ok(source) => return Ok((source, SourceEncoding::Utf8)),
+        Err(err) => err.into_bytes(),
+    };
+
+    if let Some(body) = bytes.strip_prefix(UTF16_LE_BOM) {
+        return decode_utf16(body, u16::from_le_bytes, path)
+            .map(|source| (source, SourceEncoding::Utf16Le));
+    }
+    if let Some(body) = bytes.strip_prefix(UTF16_BE_BOM) {
+        return decode_utf16(body, u16::from_be_bytes, path)
+            .map(|source| (source, SourceEncoding::Utf16Be));
+    }
+
+    Ok((decode_windows_1252(&bytes), SourceEncoding::Windows1252))
+}
+
+/// Encode `text` back into `encoding`, with the byte order mark for UTF-16;
+/// `None` when a character has no Windows-1252 byte.
+pub fn encode_source(text: &str, encoding: SourceEncoding) -> Option<Vec<u8>> {
+    let utf16 = |bom: &[u8], unit: fn(u16) -> [u8; 2]| {
+        let mut bytes = bom.to_vec();
+        bytes.extend(text.encode_utf16().flat_map(unit));
+        bytes
+    };
+    match encoding {
+        SourceEncoding::Utf8 => Some(text.as_bytes().to_vec()),
+        SourceEncoding::Utf16Le => Some(utf16(UTF16_LE_BOM, u16::to_le_bytes)),
+        SourceEncoding::Utf16Be => Some(utf16(UTF16_BE_BOM, u16::to_be_bytes)),
+        SourceEncoding::Windows1252 => text.chars().map(encode_windows_1252).collect(),
+    }
+}
+
+fn decode_utf16(body: &[u8], unit: fn([u8; 2]) -> u16, path: &Path) -> io::Result<String> {
+    let invalid = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Invalid UTF-16 in {}", path.display()),
+        )
+    };
+    if !body.len().is_multiple_of(2) {
+        return Err(invalid());
+    }
+    let units: Vec<u16> = body
+        .chunks_exact(2)
+        .map(|pair| unit([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16(&units).map_err(|_| invalid())
+}
+
+/// Characters of Windows-1252 bytes 0x80..=0x9F; the rest match Latin-1.
+/// Unassigned bytes map to the C1 control with the same value.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20ac}', '\u{81}', '\u{201a}', '\u{192}', '\u{201e}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2c6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8d}', '\u{17d}', '\u{8f}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201c}', '\u{201d}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2dc}', '\u{2122}', '\u{161}', '\u{203a}', '\u{153}', '\u{9d}', '\u{17e}', '\u{178}',
+];
+
+fn decode_windows_1252(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&byte| match byte {
+            0x80..=0x9f => WINDOWS_1252_HIGH[usize::from(byte - 0x80)],
+            _ => char::from(byte),
+        })
+        .collect()
+}
+
+fn encode_windows_1252(c: char) -> Option<u8> {
+    match u32::from(c) {
+        code @ (0..=0x7f | 0xa0..=0xff) => Some(code as u8),
+        _ => WINDOWS_1252_HIGH
+            .iter()
+            .position(|&high| high == c)
+            .map(|index| 0x80 + index as u8),
+    }
+}
+
 /// Find project root by looking for common project markers
 ///
 /// Searches upward from the given path for:
@@ -112,6 +261,57 @@ pub fn find_project_root(path: &Path) -> Option<PathBuf> {
     }
 }
 
+/// Find the root of the Cargo workspace containing `start`
+///
+/// Only applies when the nearest project root (see [`find_project_root`]) is
+/// a Cargo package: walks upward from it and returns the first directory
+/// whose `[workspace]` lists that package among its members, or the package
+/// itself when it declares the workspace. The walk stops at the first
+/// `Cargo.toml` that does not list the package and at the enclosing `.git`,
+/// so at most one manifest outside the workspace is read. Returns `None`
+/// otherwise; callers fall back to [`find_project_root`].
+pub fn find_workspace_root(start: &Path) -> Option<PathBuf> {
+    let package = find_project_root(start)?;
+    if !package.join("Cargo.toml").is_file() {
+        return None;
+    }
+    let package_dir = fs::canonicalize(&package).ok()?;
+
+    let mut current = package.as_path();
+    loop {
+        if current == package && declares_workspace(&current.join("Cargo.toml")) {
+            return Some(current.to_path_buf());
+        }
+        if current.join("Cargo.toml").is_file() {
+            let is_member = workspace::list_workspace_members(current)
+                .unwrap_or_default()
+                .iter()
+                .any(|member| fs::canonicalize(&member.path).is_ok_and(|dir| dir == package_dir));
+            if is_member {
+                return Some(current.to_path_buf());
+            }
+            if current != package {
+                return None;
+            }
+        }
+        if current.join(".git").exists() {
+            return None;
+        }
+        current = current.parent()?;
+    }
+}
+
+fn declares_workspace(manifest: &Path) -> bool {
+    let Ok(source) = read_source_file(manifest) else {
+        return false;
+    };
+    toml_edit::Document::parse(source).is_ok_and(|document| {
+        document
+            .get("workspace")
+            .is_some_and(toml_edit::Item::is_table)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,209 @@
+//! Analysis Pipeline
+//!
+//! Typed builder over `view_code` for library callers:
+//!
+//! ```no_run
+//! use treesitter_mcp::analysis::pipeline::AnalysisPipeline;
+//! use treesitter_mcp::parser::Language;
+//!
+//! let result = AnalysisPipeline::new("src/lib.rs")
+//!     .language(Language::Rust)
+//!     .max_tokens(4000)
+//!     .include_deps(true)
+//!     .focus("MyStruct")
+//!     .execute()?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! The file is read, parsed and shaped at most once per pipeline; options
+//! that do not change the shape (budget, focus, deps, ...) can be adjusted
+//! between `execute` calls without re-parsing.
+
+use std::cell::OnceCell;
+use std::io;
+use std::path::PathBuf;
+
+use tree_sitter::Tree;
+
+use crate::analysis::path_utils;
+use crate::analysis::shape::{extract_enhanced_shape, CommentMode, EnhancedFileShape};
+use crate::analysis::view_code::{self, DetailLevel, MinVisibility, ViewOptions};
+use crate::mcp_types::CallToolResult;
+use crate::parser::{detect_language, parse_code, Language};
+
+/// Builder for a single-file `view_code` analysis
+#[allow(dead_code)] // Library API; the server calls `view_code::execute` directly.
+#[derive(Debug)]
+pub struct AnalysisPipeline {
+    path: PathBuf,
+    language: Option<Language>,
+    options: ViewOptions,
+    parsed: OnceCell<ParsedFile>,
+    shape: OnceCell<EnhancedFileShape>,
+}
+
+#[derive(Debug)]
+struct ParsedFile {
+    source: String,
+    language: Language,
+    tree: Tree,
+}
+
+#[allow(dead_code)]
+impl AnalysisPipeline {
+    /// Pipeline for `path` with the `view_code` defaults: full detail,
+    /// dependencies included, 2000 token budget.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            language: None,
+            options: ViewOptions::default(),
+            parsed: OnceCell::new(),
+            shape: OnceCell::new(),
+        }
+    }
+
+    /// Parse as `language` instead of detecting it from the extension.
+    pub fn language(mut self, language: Language) -> Self {
+        if self.language != Some(language) {
+            self.language = Some(language);
+            self.parsed = OnceCell::new();
+            self.shape = OnceCell::new();
+        }
+        self
+    }
+
+    pub fn max_tokens(mut self, max_tokens: usize) -> Self {
+        self.options.max_tokens = max_tokens;
+        self
+    }
+
+    pub fn include_deps(mut self, include_deps: bool) -> Self {
+        self.options.include_deps = include_deps;
+        self
+    }
+
+    /// Keep code only for the symbol named `symbol`; everything else is
+    /// reduced to signatures.
+    pub fn focus(mut self, symbol: impl Into<String>) -> Self {
+        self.options.focus_symbol = Some(symbol.into());
+        self
+    }
+
+    /// `false` emits signatures only (`detail: "signatures"`).
+    pub fn include_code(mut self, include_code: bool) -> Self {
+        let detail = if include_code {
+            DetailLevel::Full
+        } else {
+            DetailLevel::Signatures
+        };
+        if self.options.detail != detail {
+            self.options.detail = detail;
+            self.shape = OnceCell::new();
+        }
+        self
+    }
+
+    pub fn comment_mode(mut self, comment_mode: CommentMode) -> Self {
+        self.options.comment_mode = comment_mode;
+        self
+    }
+
+    pub fn group_by_trait(mut self, group_by_trait: bool) -> Self {
+        self.options.group_by_trait = group_by_trait;
+        self
+    }
+
+    /// Drop symbols less visible than `min_visibility`.
+    pub fn min_visibility(mut self, min_visibility: MinVisibility) -> Self {
+        self.options.min_visibility = min_visibility;
+        self
+    }
+
+    /// Mark function rows changed since the git revision `revision`.
+    pub fn compare_to(mut self, revision: impl Into<String>) -> Self {
+        self.options.compare_to = Some(revision.into());
+        self
+    }
+
+    /// Language the file is (or will be) parsed as.
+    pub fn resolved_language(&self) -> Result<Language, io::Error> {
+        Ok(self.parsed()?.language)
+    }
+
+    /// Shape of the file before visibility, focus and comment options are applied.
+    pub fn shape(&self) -> Result<&EnhancedFileShape, io::Error> {
+        if let Some(shape) = self.shape.get() {
+            return Ok(shape);
+        }
+
+        let parsed = self.parsed()?;
+        let shape = extract_enhanced_shape(
+            &parsed.tree,
+            &parsed.source,
+            parsed.language,
+            Some(self.path_str()?),
+            self.options.detail.include_code(),
+        )?;
+        Ok(self.shape.get_or_init(|| shape))
+    }
+
+    /// Run `view_code` with the configured options.
+    pub fn execute(&self) -> Result<CallToolResult, io::Error> {
+        let shape = self.shape()?.clone();
+        let parsed = self.parsed()?;
+        view_code::render(
+            self.path_str()?,
+            &parsed.source,
+            parsed.language,
+            shape,
+            &self.options,
+        )
+    }
+
+    fn parsed(&self) -> Result<&ParsedFile, io::Error> {
+        if let Some(parsed) = self.parsed.get() {
+            return Ok(parsed);
+        }
+
+        let file_path = self.path_str()?;
+        let source = path_utils::read_source_file(&self.path).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Failed to read file {file_path}: {e}"),
+            )
+        })?;
+
+        let language = match self.language {
+            Some(language) => language,
+            None => detect_language(&self.path).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("Cannot detect language for file {file_path}: {e}"),
+                )
+            })?,
+        };
+
+        let tree = parse_code(&source, language).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse {} code: {e}", language.name()),
+            )
+        })?;
+
+        Ok(self.parsed.get_or_init(|| ParsedFile {
+            source,
+            language,
+            tree,
+        }))
+    }
+
+    fn path_str(&self) -> Result<&str, io::Error> {
+        self.path.to_str().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Path is not valid UTF-8: {}", self.path.display()),
+            )
+        })
+    }
+}
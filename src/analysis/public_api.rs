@@ -0,0 +1,435 @@
+//! Public API Tool
+//!
+//! Lists the public API surface of Rust and TypeScript code as one flat
+//! table, for reviewing a library for breaking changes.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "h": "kind|name|signature|file|line",
+//!   "api": "struct|Config|pub struct Config|src/lib.rs|3\nfield|Config::port|pub port: u16|src/lib.rs|4\n..."
+//! }
+//! ```
+//!
+//! Rust: `pub` functions (including inherent `impl` methods as
+//! `Type::method`), structs and their `pub` fields, enums and all their
+//! variants, traits, consts, statics and type aliases, also inside inline
+//! modules. Items marked `#[cfg(test)]` are skipped, as are the files of
+//! `#[cfg(test)] mod tests;` declarations. `pub(crate)`, `pub(super)` and
+//! `pub(in ...)` count only with `include_crate_pub`.
+//!
+//! TypeScript: exported functions (including `export const f = () => ...`),
+//! classes, interfaces and type aliases.
+//!
+//! Files below `tests`, `benches`, `examples` and `__tests__` directories are
+//! not part of the API. Rows are sorted by file and line; signatures stop
+//! before the body and are collapsed to one line.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::extraction::types::collapse_whitespace;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{detect_language, parse_code, Language};
+
+const API_HEADER: &str = "kind|name|signature|file|line";
+
+/// Directories whose files are never part of a library's API
+const NON_API_DIRS: &[&str] = &["tests", "benches", "examples", "__tests__"];
+
+/// One public item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiItem {
+    /// `fn`, `struct`, `field`, `enum`, `variant`, `trait`, `const`,
+    /// `static` or `type` for Rust; `function`, `class`, `interface` or
+    /// `type` for TypeScript
+    pub kind: String,
+    /// Item name; fields, variants and methods as `Owner::name`
+    pub name: String,
+    pub signature: String,
+    pub file: String,
+    pub line: usize,
+}
+
+pub fn execute_public_api(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+    let include_crate_pub = arguments["include_crate_pub"].as_bool().unwrap_or(false);
+
+    log::info!("Extracting public API: {path_str} (include_crate_pub: {include_crate_pub})");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let rows: Vec<String> = public_api(path, include_crate_pub)?
+        .iter()
+        .map(|item| {
+            format::format_row(&[
+                &item.kind,
+                &item.name,
+                &item.signature,
+                &path_utils::to_relative_path(&item.file),
+                &item.line.to_string(),
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "h": API_HEADER,
+        "api": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Public items of a file or of every Rust and TypeScript file in a
+/// directory, sorted by file and line
+pub fn public_api(path: &Path, include_crate_pub: bool) -> Result<Vec<ApiItem>, io::Error> {
+    let mut items = Vec::new();
+    let mut test_modules: HashSet<PathBuf> = HashSet::new();
+    for file in collect_project_files(path)? {
+        let in_non_api_dir = file.strip_prefix(path).is_ok_and(|relative| {
+            relative.components().any(|component| {
+                NON_API_DIRS.contains(&component.as_os_str().to_str().unwrap_or(""))
+            })
+        });
+        if in_non_api_dir {
+            continue;
+        }
+        let Ok(language) = detect_language(&file) else {
+            continue;
+        };
+        if !matches!(
+            language,
+            Language::Rust | Language::TypeScript | Language::Tsx
+        ) {
+            continue;
+        }
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        let tree = match parse_code(&source, language) {
+            Ok(tree) => tree,
+            Err(e) => {
+                log::debug!("Skipping {}: {e}", file.display());
+                continue;
+            }
+        };
+
+        let mut extractor = Extractor {
+            source: &source,
+            file: file.to_string_lossy().into_owned(),
+            include_crate_pub,
+            items: &mut items,
+        };
+        if language == Language::Rust {
+            extractor.rust_items(tree.root_node(), &rust_module_dir(&file), &mut test_modules);
+        } else {
+            extractor.typescript_items(tree.root_node());
+        }
+    }
+
+    items.retain(|item| !test_modules.contains(Path::new(&item.file)));
+    items.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    Ok(items)
+}
+
+struct Extractor<'a> {
+    source: &'a str,
+    file: String,
+    include_crate_pub: bool,
+    items: &'a mut Vec<ApiItem>,
+}
+
+impl<'a> Extractor<'a> {
+    /// Items of a Rust file or inline module body; files of `#[cfg(test)]`
+    /// `mod` declarations are added to `test_modules`
+    fn rust_items(
+        &mut self,
+        container: Node,
+        module_dir: &Path,
+        test_modules: &mut HashSet<PathBuf>,
+    ) {
+        let mut cursor = container.walk();
+        for item in container.named_children(&mut cursor) {
+            if is_cfg_test(item, self.source) {
+                if item.kind() == "mod_item" && item.child_by_field_name("body").is_none() {
+                    if let Some(name) = item.child_by_field_name("name") {
+                        let name = self.text(name);
+                        test_modules.insert(module_dir.join(format!("{name}.rs")));
+                        test_modules.insert(module_dir.join(name).join("mod.rs"));
+                    }
+                }
+                continue;
+            }
+
+            let kind = match item.kind() {
+                "function_item" => "fn",
+                "struct_item" => "struct",
+                "enum_item" => "enum",
+                "trait_item" => "trait",
+                "const_item" => "const",
+                "static_item" => "static",
+                "type_item" => "type",
+                "mod_item" => {
+                    if let (Some(name), Some(body)) = (
+                        item.child_by_field_name("name"),
+                        item.child_by_field_name("body"),
+                    ) {
+                        let dir = module_dir.join(self.text(name));
+                        self.rust_items(body, &dir, test_modules);
+                    }
+                    continue;
+                }
+                "impl_item" => {
+                    self.rust_impl_methods(item);
+                    continue;
+                }
+                _ => continue,
+            };
+            if !self.is_rust_public(item) {
+                continue;
+            }
+            let Some(name) = item.child_by_field_name("name") else {
+                continue;
+            };
+            let name = self.text(name);
+            self.push(kind, name.to_string(), item);
+
+            match kind {
+                "struct" => self.rust_fields(item, name),
+                "enum" => self.rust_variants(item, name),
+                _ => {}
+            }
+        }
+    }
+
+    /// `pub` methods of an inherent `impl Type { ... }`
+    fn rust_impl_methods(&mut self, impl_item: Node) {
+        if impl_item.child_by_field_name("trait").is_some() {
+            return;
+        }
+        let (Some(self_type), Some(body)) = (
+            impl_item.child_by_field_name("type"),
+            impl_item.child_by_field_name("body"),
+        ) else {
+            return;
+        };
+        let owner = self.text(self_type);
+        let mut cursor = body.walk();
+        for method in body.named_children(&mut cursor) {
+            if method.kind() != "function_item"
+                || !self.is_rust_public(method)
+                || is_cfg_test(method, self.source)
+            {
+                continue;
+            }
+            if let Some(name) = method.child_by_field_name("name") {
+                let name = format!("{owner}::{}", self.text(name));
+                self.push("fn", name, method);
+            }
+        }
+    }
+
+    fn rust_fields(&mut self, struct_item: Node, owner: &str) {
+        let Some(body) = struct_item
+            .child_by_field_name("body")
+            .filter(|body| body.kind() == "field_declaration_list")
+        else {
+            return;
+        };
+        let mut cursor = body.walk();
+        for field in body.named_children(&mut cursor) {
+            if field.kind() != "field_declaration" || !self.is_rust_public(field) {
+                continue;
+            }
+            if let Some(name) = field.child_by_field_name("name") {
+                let name = format!("{owner}::{}", self.text(name));
+                self.push("field", name, field);
+            }
+        }
+    }
+
+    fn rust_variants(&mut self, enum_item: Node, owner: &str) {
+        let Some(body) = enum_item.child_by_field_name("body") else {
+            return;
+        };
+        let mut cursor = body.walk();
+        for variant in body.named_children(&mut cursor) {
+            if variant.kind() != "enum_variant" {
+                continue;
+            }
+            if let Some(name) = variant.child_by_field_name("name") {
+                let name = format!("{owner}::{}", self.text(name));
+                self.push("variant", name, variant);
+            }
+        }
+    }
+
+    /// Plain `pub`, or any restricted `pub(...)` with `include_crate_pub`
+    fn is_rust_public(&self, item: Node) -> bool {
+        let mut cursor = item.walk();
+        let visibility = item
+            .named_children(&mut cursor)
+            .find(|child| child.kind() == "visibility_modifier");
+        visibility
+            .is_some_and(|visibility| self.include_crate_pub || self.text(visibility) == "pub")
+    }
+
+    /// Declarations of `export` statements
+    fn typescript_items(&mut self, root: Node) {
+        let mut cursor = root.walk();
+        for statement in root.named_children(&mut cursor) {
+            if statement.kind() != "export_statement" {
+                continue;
+            }
+            let Some(declaration) = statement.child_by_field_name("declaration") else {
+                continue;
+            };
+            let kind = match declaration.kind() {
+                "function_declaration" | "generator_function_declaration" => "function",
+                "class_declaration" | "abstract_class_declaration" => "class",
+                "interface_declaration" => "interface",
+                "type_alias_declaration" => "type",
+                "lexical_declaration" => {
+                    self.typescript_function_constants(declaration);
+                    continue;
+                }
+                _ => continue,
+            };
+            if let Some(name) = declaration.child_by_field_name("name") {
+                self.push(kind, self.text(name).to_string(), declaration);
+            }
+        }
+    }
+
+    /// `export const f = (...) => ...` and `export const f = function (...) {...}`
+    fn typescript_function_constants(&mut self, declaration: Node) {
+        let mut cursor = declaration.walk();
+        for declarator in declaration.named_children(&mut cursor) {
+            let (Some(name), Some(value)) = (
+                declarator.child_by_field_name("name"),
+                declarator.child_by_field_name("value"),
+            ) else {
+                continue;
+            };
+            if !matches!(value.kind(), "arrow_function" | "function_expression") {
+                continue;
+            }
+            let end = value
+                .child_by_field_name("body")
+                .map_or(value.end_byte(), |body| body.start_byte());
+            let signature = &self.source[declaration.start_byte()..end];
+            self.items.push(ApiItem {
+                kind: "function".to_string(),
+                name: self.text(name).to_string(),
+                signature: clean_signature(signature),
+                file: self.file.clone(),
+                line: declarator.start_position().row + 1,
+            });
+        }
+    }
+
+    fn push(&mut self, kind: &str, name: String, node: Node) {
+        self.items.push(ApiItem {
+            kind: kind.to_string(),
+            name,
+            signature: signature(node, self.source),
+            file: self.file.clone(),
+            line: node.start_position().row + 1,
+        });
+    }
+
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.source.as_bytes()).unwrap_or("")
+    }
+}
+
+/// Directory holding the files of `mod` declarations in `file`
+fn rust_module_dir(file: &Path) -> PathBuf {
+    let dir = file.parent().unwrap_or(Path::new("."));
+    match file.file_stem().and_then(|stem| stem.to_str()) {
+        Some("lib" | "main" | "mod") | None => dir.to_path_buf(),
+        Some(stem) => dir.join(stem),
+    }
+}
+
+/// Whether `#[cfg(test)]` is among the attributes right above `item`
+fn is_cfg_test(item: Node, source: &str) -> bool {
+    let mut previous = item.prev_named_sibling();
+    while let Some(node) = previous {
+        match node.kind() {
+            "attribute_item" => {
+                let attribute: String = node
+                    .utf8_text(source.as_bytes())
+                    .unwrap_or("")
+                    .split_whitespace()
+                    .collect();
+                if attribute == "#[cfg(test)]" {
+                    return true;
+                }
+            }
+            "line_comment" | "block_comment" => {}
+            _ => return false,
+        }
+        previous = node.prev_named_sibling();
+    }
+    false
+}
+
+/// Declaration text before its body, or before the value of a `const` or
+/// `static`
+fn signature(node: Node, source: &str) -> String {
+    let end = node
+        .child_by_field_name("body")
+        .filter(|body| {
+            matches!(
+                body.kind(),
+                "block"
+                    | "statement_block"
+                    | "field_declaration_list"
+                    | "enum_variant_list"
+                    | "declaration_list"
+                    | "class_body"
+                    | "interface_body"
+                    | "object_type"
+            )
+        })
+        .or_else(|| {
+            matches!(node.kind(), "const_item" | "static_item")
+                .then(|| node.child_by_field_name("value"))
+                .flatten()
+        })
+        .map_or(node.end_byte(), |end| end.start_byte());
+    clean_signature(&source[node.start_byte()..end])
+}
+
+fn clean_signature(text: &str) -> String {
+    collapse_whitespace(text)
+        .trim_end_matches(['{', '=', ';', ','])
+        .trim_end()
+        .to_string()
+}
@@ -1,6 +1,7 @@
 //! Query Pattern Tool
 //!
-//! Executes custom tree-sitter queries on source files.
+//! Executes custom tree-sitter queries on a source file or on every file of
+//! one language in a directory.
 //!
 //! Breaking schema change (v1):
 //! ```json
@@ -10,25 +11,38 @@
 //!   "m": "src/calculator.rs|10|5|add\n..."
 //! }
 //! ```
+//!
+//! The query is compiled before any file is read, so invalid syntax fails
+//! fast with the position of the error. Each match contributes the nodes of
+//! the `capture` argument, or its first capture when none is given. Output
+//! is capped at 1000 rows; `"@": {"t": true}` marks a truncated result.
 
 use crate::analysis::path_utils;
 use crate::common::compact::CompactOutput;
+use crate::common::project_files::collect_project_files;
 use crate::mcp_types::{CallToolResult, CallToolResultExt};
-use crate::parser::{detect_language, parse_code};
+use crate::parser::{detect_language, parse_code, Language};
 use serde_json::json;
 use serde_json::Value;
-use std::fs;
 use std::io;
+use std::path::{Path, PathBuf};
 use streaming_iterator::StreamingIterator;
 use tree_sitter::{Query, QueryCursor};
 
+const QUERY_HEADER: &str = "file|line|col|text";
+const MAX_MATCHES: usize = 1000;
+
 pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
-    let file_path = arguments["file_path"].as_str().ok_or_else(|| {
-        io::Error::new(
-            io::ErrorKind::InvalidInput,
-            "Missing or invalid 'file_path' argument",
-        )
-    })?;
+    // `file_path` is the name used before directories were supported.
+    let path_str = arguments["path"]
+        .as_str()
+        .or_else(|| arguments["file_path"].as_str())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Missing or invalid 'path' argument",
+            )
+        })?;
 
     let query_str = arguments["query"].as_str().ok_or_else(|| {
         io::Error::new(
@@ -37,80 +51,166 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
         )
     })?;
 
-    log::info!("Executing query on file: {file_path}");
+    log::info!("Executing query on: {path_str}");
 
-    let source = fs::read_to_string(file_path).map_err(|e| {
-        io::Error::new(
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
             io::ErrorKind::NotFound,
-            format!("Failed to read file '{file_path}': {e}"),
-        )
-    })?;
-
-    let language = detect_language(file_path).map_err(|e| {
-        io::Error::new(
-            io::ErrorKind::Unsupported,
-            format!("Cannot detect language for file '{file_path}': {e}"),
-        )
-    })?;
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
 
-    let tree = parse_code(&source, language).map_err(|e| {
-        io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!(
-                "Failed to parse {} code from file '{file_path}': {e}",
-                language.name()
-            ),
-        )
-    })?;
+    let language = query_language(path, path_str, arguments["language"].as_str())?;
 
-    let ts_language = language.tree_sitter_language();
-    let query = Query::new(&ts_language, query_str).map_err(|e| {
+    let query = Query::new(&language.tree_sitter_language(), query_str).map_err(|e| {
         io::Error::new(
             io::ErrorKind::InvalidInput,
             format!("Failed to parse query '{query_str}': {e}"),
         )
     })?;
-
-    let mut cursor = QueryCursor::new();
-    let mut matches = cursor.matches(&query, tree.root_node(), source.as_bytes());
-
-    let rel_path = path_utils::to_relative_path(file_path);
-    let header = "file|line|col|text";
-    let mut out = CompactOutput::new(header);
-
-    while let Some(query_match) = matches.next() {
-        let mut first_capture = None;
-
-        for capture in query_match.captures {
-            if first_capture.is_none() {
-                first_capture = Some(capture.node);
+    let capture_index = match arguments["capture"].as_str() {
+        Some(name) => Some(query.capture_index_for_name(name).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Capture '@{name}' not found in query (captures: {})",
+                    query.capture_names().join(", ")
+                ),
+            )
+        })?),
+        None => None,
+    };
+
+    let files: Vec<PathBuf> = if path.is_file() {
+        vec![path.to_path_buf()]
+    } else {
+        collect_project_files(path)?
+            .into_iter()
+            .filter(|file| detect_language(file).is_ok_and(|detected| detected == language))
+            .collect()
+    };
+
+    let mut out = CompactOutput::new(QUERY_HEADER);
+    let mut row_count = 0;
+    let mut truncated = false;
+
+    'files: for file in &files {
+        let file_str = file.to_string_lossy();
+        let source = match path_utils::read_source_file(file) {
+            Ok(source) => source,
+            Err(e) if path.is_file() => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("Failed to read file '{file_str}': {e}"),
+                ))
+            }
+            Err(_) => continue,
+        };
+        let tree = match parse_code(&source, language) {
+            Ok(tree) => tree,
+            Err(e) if path.is_file() => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "Failed to parse {} code from file '{file_str}': {e}",
+                        language.name()
+                    ),
+                ))
+            }
+            Err(_) => continue,
+        };
+
+        let rel_path = path_utils::to_relative_path(&file_str);
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&query, tree.root_node(), source.as_bytes());
+
+        while let Some(query_match) = matches.next() {
+            let nodes = query_match
+                .captures
+                .iter()
+                .filter(|capture| capture_index.is_none_or(|index| capture.index == index))
+                .take(if capture_index.is_some() {
+                    usize::MAX
+                } else {
+                    1
+                })
+                .map(|capture| capture.node);
+
+            for node in nodes {
+                if row_count == MAX_MATCHES {
+                    truncated = true;
+                    break 'files;
+                }
+                let start_pos = node.start_position();
+                let text = node.utf8_text(source.as_bytes()).unwrap_or("");
+                let line = (start_pos.row + 1).to_string();
+                let col = (start_pos.column + 1).to_string();
+
+                out.add_row(&[&rel_path, &line, &col, text]);
+                row_count += 1;
             }
-        }
-
-        if let Some(node) = first_capture {
-            let start_pos = node.start_position();
-            let text = node.utf8_text(source.as_bytes()).unwrap_or("");
-            let line = (start_pos.row + 1).to_string();
-            let col = (start_pos.column + 1).to_string();
-
-            out.add_row(&[&rel_path, &line, &col, text]);
         }
     }
 
-    let result = json!({
+    let mut result = json!({
         "q": query_str,
-        "h": header,
+        "h": QUERY_HEADER,
         "m": out.rows_string(),
     });
+    if truncated {
+        result["@"] = json!({"t": true});
+    }
 
     let result_json = serde_json::to_string(&result).map_err(|e| {
         io::Error::new(
             io::ErrorKind::InvalidData,
             format!(
-                "Failed to serialize query results for query '{query_str}' on file '{file_path}': {e}"
+                "Failed to serialize query results for query '{query_str}' on '{path_str}': {e}"
             ),
         )
     })?;
 
     Ok(CallToolResult::success(result_json))
 }
+
+/// The grammar to compile the query for: the `language` argument, or the
+/// language of a single file.
+fn query_language(path: &Path, path_str: &str, name: Option<&str>) -> Result<Language, io::Error> {
+    let Some(name) = name else {
+        if !path.is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Missing 'language' argument, required when 'path' is a directory",
+            ));
+        }
+        return detect_language(path).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("Cannot detect language for file '{path_str}': {e}"),
+            )
+        });
+    };
+
+    let language = Language::from_name(name).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Unsupported language '{name}'"),
+        )
+    })?;
+    if path.is_file() {
+        if let Ok(detected) = detect_language(path) {
+            if detected != language {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "File '{path_str}' is {}, not {}",
+                        detected.name(),
+                        language.name()
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(language)
+}
@@ -0,0 +1,581 @@
+//! Rename Symbol Tool
+//!
+//! Renames every identifier occurrence of a symbol in a file or directory.
+//! Occurrences come from the `find_usages` identifier search, so matches
+//! inside strings and comments are left alone, and each one is replaced at
+//! the byte offset of its tree-sitter node.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "from": "compute",
+//!   "to": "calculate",
+//!   "dry_run": false,
+//!   "files": 2,
+//!   "renamed": 5,
+//!   "skipped": "src/broken.rs: syntax errors\n...",
+//!   "diff": "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -3 +3 @@\n-...\n+...\n"
+//! }
+//! ```
+//!
+//! `diff` is present when `return_diff` is set. It is a unified diff without
+//! context lines (`git apply --unidiff-zero`). Files with syntax errors are
+//! skipped rather than edited. Files are written back in the encoding they
+//! were read in (UTF-16 keeps its byte order mark).
+//!
+//! `new_name` must be an identifier, and not a reserved word, in the
+//! language of every file it would be written to; otherwise nothing is
+//! written.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::analysis::find_usages::usages_in_tree;
+use crate::analysis::path_utils;
+use crate::common::project_files::collect_project_files;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{cache, detect_language, Language};
+
+/// Outcome of a rename, identical for dry runs and applied renames
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenameResult {
+    pub files_changed: usize,
+    pub occurrences_renamed: usize,
+    /// Files with occurrences that were left untouched, as `path: reason`
+    pub skipped: Vec<String>,
+    /// Unified diff of the rename, when requested
+    pub diff: Option<String>,
+}
+
+pub fn execute_rename_symbol(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let old_name = arguments["old_name"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'old_name' argument",
+        )
+    })?;
+    let new_name = arguments["new_name"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'new_name' argument",
+        )
+    })?;
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+    let dry_run = arguments["dry_run"].as_bool().unwrap_or(true);
+    let return_diff = arguments["return_diff"].as_bool().unwrap_or(false);
+
+    log::info!("Renaming '{old_name}' to '{new_name}' in: {path_str} (dry run: {dry_run})");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let result = rename_symbol(old_name, new_name, path, dry_run, return_diff)?;
+
+    let mut output = json!({
+        "from": old_name,
+        "to": new_name,
+        "dry_run": dry_run,
+        "files": result.files_changed,
+        "renamed": result.occurrences_renamed,
+        "skipped": result.skipped.join("\n"),
+    });
+    if let Some(diff) = result.diff {
+        output["diff"] = json!(diff);
+    }
+
+    let json_text = serde_json::to_string(&output).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Rename `old_name` to `new_name` in a file or every supported file of a
+/// directory. With `dry_run` nothing is written; the counts and diff describe
+/// what would change.
+pub fn rename_symbol(
+    old_name: &str,
+    new_name: &str,
+    path: &Path,
+    dry_run: bool,
+    return_diff: bool,
+) -> Result<RenameResult, io::Error> {
+    validate_new_name(old_name, new_name)?;
+
+    let mut result = RenameResult::default();
+    let mut diff = String::new();
+    let mut pending = Vec::new();
+
+    for file in collect_project_files(path)? {
+        let Ok(language) = detect_language(&file) else {
+            continue;
+        };
+        let rel_path = path_utils::to_relative_path(&file.to_string_lossy());
+        let Ok((source, encoding)) = path_utils::read_source_file_with_encoding(&file) else {
+            result.skipped.push(format!("{rel_path}: unreadable"));
+            continue;
+        };
+        if !source.contains(old_name) {
+            continue;
+        }
+        let Ok(parsed) = cache::shared().parse(&file, &source, language) else {
+            result.skipped.push(format!("{rel_path}: failed to parse"));
+            continue;
+        };
+        let tree = &parsed.0;
+
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        let mut offsets: Vec<usize> = usages_in_tree(tree, &source, language, &file, old_name)
+            .iter()
+            .filter_map(|usage| Some(line_starts.get(usage.line - 1)? + usage.column - 1))
+            .filter(|&offset| source.get(offset..offset + old_name.len()) == Some(old_name))
+            .collect();
+        offsets.sort_unstable();
+        offsets.dedup();
+        if offsets.is_empty() {
+            continue;
+        }
+        if tree.root_node().has_error() {
+            result.skipped.push(format!("{rel_path}: syntax errors"));
+            continue;
+        }
+
+        validate_name_for_language(new_name, language, &rel_path)?;
+
+        let mut renamed = source.clone();
+        for &offset in offsets.iter().rev() {
+            renamed.replace_range(offset..offset + old_name.len(), new_name);
+        }
+
+        if return_diff {
+            diff.push_str(&unified_diff(&rel_path, &source, &renamed));
+        }
+        let bytes = path_utils::encode_source(&renamed, encoding).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{new_name}' cannot be written to {rel_path}, which is not UTF-8"),
+            )
+        })?;
+        result.files_changed += 1;
+        result.occurrences_renamed += offsets.len();
+        pending.push((file, bytes));
+    }
+
+    // Written only once every file has been checked, so an invalid name
+    // leaves the project untouched.
+    if !dry_run {
+        for (file, bytes) in pending {
+            fs::write(&file, &bytes).map_err(|e| {
+                io::Error::new(e.kind(), format!("Failed to write {}: {e}", file.display()))
+            })?;
+        }
+    }
+
+    if return_diff {
+        result.diff = Some(diff);
+    }
+    Ok(result)
+}
+
+fn validate_new_name(old_name: &str, new_name: &str) -> Result<(), io::Error> {
+    let is_identifier = new_name.starts_with(|c: char| c.is_alphabetic() || matches!(c, '_' | '$'))
+        && new_name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '$'));
+    if !is_identifier {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("'{new_name}' is not a valid identifier"),
+        ));
+    }
+    if old_name == new_name {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "'old_name' and 'new_name' are the same",
+        ));
+    }
+    Ok(())
+}
+
+/// `$` is only part of identifiers in JavaScript and TypeScript, a lone `_`
+/// is not a Rust identifier, and reserved words are never identifiers.
+fn validate_name_for_language(
+    new_name: &str,
+    language: Language,
+    rel_path: &str,
+) -> Result<(), io::Error> {
+    let allows_dollar = matches!(
+        language,
+        Language::JavaScript
+            | Language::TypeScript
+            | Language::Jsx
+            | Language::Tsx
+            | Language::Svelte
+    );
+    let invalid = (new_name.contains('$') && !allows_dollar)
+        || (new_name == "_" && language == Language::Rust);
+    if invalid {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "'{new_name}' is not a valid {} identifier ({rel_path})",
+                language.name()
+            ),
+        ));
+    }
+    if reserved_words(language).contains(&new_name) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "'{new_name}' is a reserved word in {} ({rel_path})",
+                language.name()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Words that cannot name a variable, function or type in `language`
+fn reserved_words(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::Rust => &[
+            "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+            "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+            "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super",
+            "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become",
+            "box", "do", "final", "gen", "macro", "override", "priv", "try", "typeof", "unsized",
+            "virtual", "yield",
+        ],
+        Language::Python => &[
+            "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+            "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+            "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise",
+            "return", "try", "while", "with", "yield",
+        ],
+        Language::JavaScript
+        | Language::TypeScript
+        | Language::Jsx
+        | Language::Tsx
+        | Language::Svelte => &[
+            "await",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "continue",
+            "debugger",
+            "default",
+            "delete",
+            "do",
+            "else",
+            "enum",
+            "export",
+            "extends",
+            "false",
+            "finally",
+            "for",
+            "function",
+            "if",
+            "implements",
+            "import",
+            "in",
+            "instanceof",
+            "interface",
+            "let",
+            "new",
+            "null",
+            "package",
+            "private",
+            "protected",
+            "public",
+            "return",
+            "static",
+            "super",
+            "switch",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "typeof",
+            "var",
+            "void",
+            "while",
+            "with",
+            "yield",
+        ],
+        Language::Go => &[
+            "break",
+            "case",
+            "chan",
+            "const",
+            "continue",
+            "default",
+            "defer",
+            "else",
+            "fallthrough",
+            "for",
+            "func",
+            "go",
+            "goto",
+            "if",
+            "import",
+            "interface",
+            "map",
+            "package",
+            "range",
+            "return",
+            "select",
+            "struct",
+            "switch",
+            "type",
+            "var",
+        ],
+        Language::Java => &[
+            "_",
+            "abstract",
+            "assert",
+            "boolean",
+            "break",
+            "byte",
+            "case",
+            "catch",
+            "char",
+            "class",
+            "const",
+            "continue",
+            "default",
+            "do",
+            "double",
+            "else",
+            "enum",
+            "extends",
+            "false",
+            "final",
+            "finally",
+            "float",
+            "for",
+            "goto",
+            "if",
+            "implements",
+            "import",
+            "instanceof",
+            "int",
+            "interface",
+            "long",
+            "native",
+            "new",
+            "null",
+            "package",
+            "private",
+            "protected",
+            "public",
+            "return",
+            "short",
+            "static",
+            "strictfp",
+            "super",
+            "switch",
+            "synchronized",
+            "this",
+            "throw",
+            "throws",
+            "transient",
+            "true",
+            "try",
+            "void",
+            "volatile",
+            "while",
+        ],
+        Language::CSharp => &[
+            "abstract",
+            "as",
+            "base",
+            "bool",
+            "break",
+            "byte",
+            "case",
+            "catch",
+            "char",
+            "checked",
+            "class",
+            "const",
+            "continue",
+            "decimal",
+            "default",
+            "delegate",
+            "do",
+            "double",
+            "else",
+            "enum",
+            "event",
+            "explicit",
+            "extern",
+            "false",
+            "finally",
+            "fixed",
+            "float",
+            "for",
+            "foreach",
+            "goto",
+            "if",
+            "implicit",
+            "in",
+            "int",
+            "interface",
+            "internal",
+            "is",
+            "lock",
+            "long",
+            "namespace",
+            "new",
+            "null",
+            "object",
+            "operator",
+            "out",
+            "override",
+            "params",
+            "private",
+            "protected",
+            "public",
+            "readonly",
+            "ref",
+            "return",
+            "sbyte",
+            "sealed",
+            "short",
+            "sizeof",
+            "stackalloc",
+            "static",
+            "string",
+            "struct",
+            "switch",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "typeof",
+            "uint",
+            "ulong",
+            "unchecked",
+            "unsafe",
+            "ushort",
+            "using",
+            "virtual",
+            "void",
+            "volatile",
+            "while",
+        ],
+        Language::Swift => &[
+            "Any",
+            "as",
+            "associatedtype",
+            "await",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "continue",
+            "default",
+            "defer",
+            "deinit",
+            "do",
+            "else",
+            "enum",
+            "extension",
+            "fallthrough",
+            "false",
+            "fileprivate",
+            "for",
+            "func",
+            "guard",
+            "if",
+            "import",
+            "in",
+            "init",
+            "inout",
+            "internal",
+            "is",
+            "let",
+            "nil",
+            "open",
+            "operator",
+            "private",
+            "precedencegroup",
+            "protocol",
+            "public",
+            "repeat",
+            "rethrows",
+            "return",
+            "self",
+            "Self",
+            "static",
+            "struct",
+            "subscript",
+            "super",
+            "switch",
+            "throw",
+            "throws",
+            "true",
+            "try",
+            "typealias",
+            "var",
+            "where",
+            "while",
+        ],
+        Language::Html | Language::Css => &[],
+    }
+}
+
+/// Unified diff without context; renames never add or remove lines, so each
+/// run of changed lines is one hunk with the same line numbers on both sides.
+fn unified_diff(rel_path: &str, before: &str, after: &str) -> String {
+    let before: Vec<&str> = before.lines().collect();
+    let after: Vec<&str> = after.lines().collect();
+    let mut out = format!("--- a/{rel_path}\n+++ b/{rel_path}\n");
+
+    let mut line = 0;
+    while line < before.len() {
+        if before[line] == after[line] {
+            line += 1;
+            continue;
+        }
+        let start = line;
+        while line < before.len() && before[line] != after[line] {
+            line += 1;
+        }
+        let count = line - start;
+        let range = if count == 1 {
+            format!("{}", start + 1)
+        } else {
+            format!("{},{count}", start + 1)
+        };
+        out.push_str(&format!("@@ -{range} +{range} @@\n"));
+        for removed in &before[start..line] {
+            out.push_str(&format!("-{removed}\n"));
+        }
+        for added in &after[start..line] {
+            out.push_str(&format!("+{added}\n"));
+        }
+    }
+
+    out
+}
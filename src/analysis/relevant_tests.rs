@@ -42,7 +42,8 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
         })?;
 
     let target_path = Path::new(file_path);
-    let project_root = path_utils::find_project_root(target_path)
+    let project_root = path_utils::find_workspace_root(target_path)
+        .or_else(|| path_utils::find_project_root(target_path))
         .or_else(|| target_path.parent().map(Path::to_path_buf))
         .unwrap_or_else(|| PathBuf::from("."));
 
@@ -106,7 +107,7 @@ pub(crate) fn collect_relevant_tests(
 
         let mut matched = false;
         for row in usage_rows.lines() {
-            let fields = parse_compact_row(row);
+            let fields = format::parse_row(row);
             let usage_type = fields.get(3).map(String::as_str).unwrap_or("reference");
             if usage_type == "definition" {
                 continue;
@@ -181,7 +182,7 @@ fn relevance_rank(value: &str) -> usize {
     }
 }
 
-fn is_test_file(path: &Path) -> bool {
+pub(crate) fn is_test_file(path: &Path) -> bool {
     let lower = path.to_string_lossy().to_ascii_lowercase();
     lower.contains("/tests/")
         || lower.contains("\\tests\\")
@@ -238,47 +239,6 @@ fn looks_like_direct_call(context: &str, symbol_name: &str) -> bool {
     compact.contains(&format!("{symbol_name}("))
 }
 
-fn parse_compact_row(row: &str) -> Vec<String> {
-    let mut fields = Vec::new();
-    let mut current = String::new();
-    let mut escaped = false;
-
-    for ch in row.chars() {
-        if escaped {
-            match ch {
-                'n' => current.push('\n'),
-                'r' => current.push('\r'),
-                '|' => current.push('|'),
-                '\\' => current.push('\\'),
-                other => {
-                    current.push('\\');
-                    current.push(other);
-                }
-            }
-            escaped = false;
-            continue;
-        }
-
-        if ch == '\\' {
-            escaped = true;
-            continue;
-        }
-
-        if ch == '|' {
-            fields.push(std::mem::take(&mut current));
-            continue;
-        }
-
-        current.push(ch);
-    }
-
-    if escaped {
-        current.push('\\');
-    }
-    fields.push(current);
-    fields
-}
-
 fn get_result_text(result: &CallToolResult) -> String {
     if let Some(first_content) = result.content.first() {
         if let Ok(json_str) = serde_json::to_string(first_content) {
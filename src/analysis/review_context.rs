@@ -92,7 +92,7 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
             .and_then(Value::as_str)
             .unwrap_or("");
         for row in rows.lines() {
-            let fields = parse_compact_row(row);
+            let fields = format::parse_row(row);
             let prefixed = format::format_row(&[
                 &change.name,
                 fields.first().map(String::as_str).unwrap_or(""),
@@ -202,44 +202,3 @@ fn get_result_text(result: &CallToolResult) -> String {
     }
     String::new()
 }
-
-fn parse_compact_row(row: &str) -> Vec<String> {
-    let mut fields = Vec::new();
-    let mut current = String::new();
-    let mut escaped = false;
-
-    for ch in row.chars() {
-        if escaped {
-            match ch {
-                'n' => current.push('\n'),
-                'r' => current.push('\r'),
-                '|' => current.push('|'),
-                '\\' => current.push('\\'),
-                other => {
-                    current.push('\\');
-                    current.push(other);
-                }
-            }
-            escaped = false;
-            continue;
-        }
-
-        if ch == '\\' {
-            escaped = true;
-            continue;
-        }
-
-        if ch == '|' {
-            fields.push(std::mem::take(&mut current));
-            continue;
-        }
-
-        current.push(ch);
-    }
-
-    if escaped {
-        current.push('\\');
-    }
-    fields.push(current);
-    fields
-}
@@ -0,0 +1,346 @@
+//! Web Routes Tool
+//!
+//! Lists HTTP routes of Flask, FastAPI and Express services:
+//!
+//! - Python: `@<router>.route("/path", methods=[...])` decorators (Flask apps
+//!   and Blueprints, `GET` when `methods` is omitted) plus the
+//!   `@<router>.get("/path")` style found by [`fastapi`](crate::analysis::fastapi)
+//! - JavaScript/TypeScript: `<router>.get("/path", ...middleware, handler)`
+//!   calls and `<router>.route("/path").get(handler).post(handler)` chains
+//!
+//! Receiver names are not checked. Express calls only count when the path
+//! starts with `/` or `*` and a handler follows, which keeps `map.get("key")`
+//! out.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "h": "method|path|handler|file|line",
+//!   "routes": "GET|/items/{id}|read_item|app/main.py|12\nPOST|/users|createUser|src/app.js|8\n..."
+//! }
+//! ```
+//!
+//! The handler of an Express route is its last argument; inline functions are
+//! reported by name, or as `<anonymous>`.
+
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::analysis::fastapi::{extract_fastapi_routes, string_value};
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{detect_language, parse_code, Language};
+
+const ROUTES_HEADER: &str = "method|path|handler|file|line";
+
+/// Express router methods that register a route
+const EXPRESS_METHODS: [&str; 8] = [
+    "get", "post", "put", "patch", "delete", "head", "options", "all",
+];
+
+/// An HTTP route and the handler it dispatches to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteInfo {
+    /// Upper-case HTTP method (`ALL` for Express `.all`)
+    pub method: String,
+    pub path: String,
+    pub handler_name: String,
+    pub file: String,
+    /// 1-based line of the decorator, or of the method name of an Express call
+    pub line: usize,
+}
+
+pub fn execute_find_routes(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    log::info!("Finding web routes in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let rows: Vec<String> = find_routes(path)?
+        .iter()
+        .map(|route| {
+            format::format_row(&[
+                &route.method,
+                &route.path,
+                &route.handler_name,
+                &path_utils::to_relative_path(&route.file),
+                &route.line.to_string(),
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "h": ROUTES_HEADER,
+        "routes": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Routes in a file or every Python/JavaScript/TypeScript file of a directory
+pub fn find_routes(path: &Path) -> Result<Vec<RouteInfo>, io::Error> {
+    let mut routes = Vec::new();
+    for file in collect_project_files(path)? {
+        let Ok(language) = detect_language(&file) else {
+            continue;
+        };
+        if !matches!(
+            language,
+            Language::Python
+                | Language::JavaScript
+                | Language::TypeScript
+                | Language::Jsx
+                | Language::Tsx
+        ) {
+            continue;
+        }
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        routes.extend(extract_routes(&source, language, &file));
+    }
+    Ok(routes)
+}
+
+/// Routes declared in one source file, in source order
+pub fn extract_routes(source: &str, language: Language, file_path: &Path) -> Vec<RouteInfo> {
+    let Ok(tree) = parse_code(source, language) else {
+        return Vec::new();
+    };
+    let file = file_path.to_string_lossy().to_string();
+
+    let mut routes = Vec::new();
+    if language == Language::Python {
+        collect_flask_routes(tree.root_node(), source, &file, &mut routes);
+        routes.extend(
+            extract_fastapi_routes(source, file_path)
+                .into_iter()
+                .map(|route| RouteInfo {
+                    method: route.method,
+                    path: route.path,
+                    handler_name: route.function,
+                    file: file.clone(),
+                    line: route.line,
+                }),
+        );
+        routes.sort_by_key(|route| route.line);
+    } else {
+        collect_express_routes(tree.root_node(), source, &file, &mut routes);
+        // Chains are visited outermost (last) link first
+        routes.sort_by_key(|route| route.line);
+    }
+    routes
+}
+
+fn collect_flask_routes(node: Node, source: &str, file: &str, routes: &mut Vec<RouteInfo>) {
+    if node.kind() == "decorated_definition" {
+        let handler_name = node
+            .child_by_field_name("definition")
+            .filter(|definition| definition.kind() == "function_definition")
+            .and_then(|function| function.child_by_field_name("name"))
+            .map(|name| text(name, source).to_string());
+
+        if let Some(handler_name) = handler_name {
+            let mut cursor = node.walk();
+            for decorator in node
+                .named_children(&mut cursor)
+                .filter(|child| child.kind() == "decorator")
+            {
+                let Some((path, methods)) = flask_route(decorator, source) else {
+                    continue;
+                };
+                for method in methods {
+                    routes.push(RouteInfo {
+                        method,
+                        path: path.clone(),
+                        handler_name: handler_name.clone(),
+                        file: file.to_string(),
+                        line: decorator.start_position().row + 1,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_flask_routes(child, source, file, routes);
+    }
+}
+
+/// `(path, methods)` for `@router.route("/path", methods=["GET", "POST"])`
+fn flask_route(decorator: Node, source: &str) -> Option<(String, Vec<String>)> {
+    let call = decorator
+        .named_child(0)
+        .filter(|child| child.kind() == "call")?;
+    let function = call
+        .child_by_field_name("function")
+        .filter(|function| function.kind() == "attribute")?;
+    if text(function.child_by_field_name("attribute")?, source) != "route" {
+        return None;
+    }
+
+    let arguments = call.child_by_field_name("arguments")?;
+    let mut cursor = arguments.walk();
+    let mut path = None;
+    let mut methods = Vec::new();
+    for argument in arguments.named_children(&mut cursor) {
+        match argument.kind() {
+            "string" if path.is_none() => path = string_value(argument, source),
+            "keyword_argument" => {
+                let name = argument
+                    .child_by_field_name("name")
+                    .map(|n| text(n, source));
+                let Some(value) = argument.child_by_field_name("value") else {
+                    continue;
+                };
+                match name {
+                    Some("rule") => path = string_value(value, source),
+                    Some("methods") => {
+                        let mut cursor = value.walk();
+                        methods = value
+                            .named_children(&mut cursor)
+                            .filter_map(|method| string_value(method, source))
+                            .map(|method| method.to_uppercase())
+                            .collect();
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if methods.is_empty() {
+        methods.push("GET".to_string());
+    }
+    Some((path?, methods))
+}
+
+fn collect_express_routes(node: Node, source: &str, file: &str, routes: &mut Vec<RouteInfo>) {
+    if node.kind() == "call_expression" {
+        if let Some(route) = express_route(node, source, file) {
+            routes.push(route);
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_express_routes(child, source, file, routes);
+    }
+}
+
+/// Route for `router.get("/path", ...handlers)` or the `.get(handler)` link
+/// of a `router.route("/path")` chain
+fn express_route(call: Node, source: &str, file: &str) -> Option<RouteInfo> {
+    let callee = call
+        .child_by_field_name("function")
+        .filter(|callee| callee.kind() == "member_expression")?;
+    let property = callee.child_by_field_name("property")?;
+    let method = text(property, source);
+    if !EXPRESS_METHODS.contains(&method) {
+        return None;
+    }
+
+    let arguments = call.child_by_field_name("arguments")?;
+    let mut cursor = arguments.walk();
+    let arguments: Vec<Node> = arguments
+        .named_children(&mut cursor)
+        .filter(|argument| argument.kind() != "comment")
+        .collect();
+
+    let (path, handlers) = match arguments
+        .first()
+        .and_then(|first| js_string(*first, source))
+    {
+        Some(path) => (path, &arguments[1..]),
+        None => (
+            chained_route_path(callee.child_by_field_name("object")?, source)?,
+            &arguments[..],
+        ),
+    };
+    if !(path.starts_with('/') || path.starts_with('*')) {
+        return None;
+    }
+
+    Some(RouteInfo {
+        method: method.to_uppercase(),
+        path,
+        handler_name: handler_name(*handlers.last()?, source)?,
+        file: file.to_string(),
+        line: property.start_position().row + 1,
+    })
+}
+
+/// Path of `router.route("/path")`, possibly followed by other method links
+fn chained_route_path(object: Node, source: &str) -> Option<String> {
+    let callee = object.child_by_field_name("function").filter(|callee| {
+        object.kind() == "call_expression" && callee.kind() == "member_expression"
+    })?;
+    if text(callee.child_by_field_name("property")?, source) == "route" {
+        let arguments = object.child_by_field_name("arguments")?;
+        return js_string(arguments.named_child(0)?, source);
+    }
+    chained_route_path(callee.child_by_field_name("object")?, source)
+}
+
+fn handler_name(handler: Node, source: &str) -> Option<String> {
+    match handler.kind() {
+        "identifier" | "member_expression" => Some(text(handler, source).to_string()),
+        "function_expression" | "function" | "arrow_function" => Some(
+            handler
+                .child_by_field_name("name")
+                .map(|name| text(name, source))
+                .unwrap_or("<anonymous>")
+                .to_string(),
+        ),
+        _ => None,
+    }
+}
+
+/// Contents of a quoted string or a template literal without substitutions
+fn js_string(node: Node, source: &str) -> Option<String> {
+    match node.kind() {
+        "string" => {}
+        "template_string" => {
+            let mut cursor = node.walk();
+            if node
+                .named_children(&mut cursor)
+                .any(|child| child.kind() == "template_substitution")
+            {
+                return None;
+            }
+        }
+        _ => return None,
+    }
+    let literal = text(node, source);
+    Some(literal.get(1..literal.len().saturating_sub(1))?.to_string())
+}
+
+fn text<'a>(node: Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
@@ -0,0 +1,143 @@
+//! Rust Edition Detection
+//!
+//! Reads `[package] edition` from `Cargo.toml`, following
+//! `edition.workspace = true` to `[workspace.package]` in an ancestor
+//! manifest. Shape extraction uses the edition to decide which items count
+//! as imports (e.g. `extern crate` in 2015).
+//!
+//! Shape extraction asks once per Rust file, so the most recently used
+//! manifests are kept parsed and only re-parsed when their text changes.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use indexmap::IndexMap;
+use toml_edit::{Document, Item};
+
+use crate::analysis::path_utils;
+
+/// Rust language edition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum RustEdition {
+    E2015,
+    E2018,
+    /// Used for files outside any Cargo package
+    #[default]
+    E2021,
+    E2024,
+}
+
+impl RustEdition {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "2015" => Some(RustEdition::E2015),
+            "2018" => Some(RustEdition::E2018),
+            "2021" => Some(RustEdition::E2021),
+            "2024" => Some(RustEdition::E2024),
+            _ => None,
+        }
+    }
+}
+
+/// Edition declared by the manifest at `cargo_toml_path`.
+///
+/// A package without an `edition` field is 2015, as in Cargo. An
+/// unreadable manifest, or one without `[package]`, yields the default.
+pub fn detect_rust_edition(cargo_toml_path: &Path) -> RustEdition {
+    let Some(document) = read_manifest(cargo_toml_path) else {
+        return RustEdition::default();
+    };
+    let Some(package) = document.get("package") else {
+        return RustEdition::default();
+    };
+
+    match package.get("edition") {
+        None => RustEdition::E2015,
+        Some(edition) if inherits_from_workspace(edition) => {
+            workspace_edition(cargo_toml_path).unwrap_or_default()
+        }
+        Some(edition) => edition
+            .as_str()
+            .and_then(RustEdition::parse)
+            .unwrap_or_default(),
+    }
+}
+
+/// Edition of the package containing `file_path`, from the nearest
+/// ancestor `Cargo.toml`.
+pub fn rust_edition_for_file(file_path: &Path) -> RustEdition {
+    file_path
+        .ancestors()
+        .skip(1)
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|manifest| manifest.is_file())
+        .map(|manifest| detect_rust_edition(&manifest))
+        .unwrap_or_default()
+}
+
+/// Parsed manifests kept at once; the least recently used is evicted first
+pub const MANIFEST_CACHE_CAPACITY: usize = 64;
+
+/// Manifest text and its parse, keyed by path in least-recently-used order
+type ManifestCache = IndexMap<PathBuf, (String, Option<Arc<Document<String>>>)>;
+
+static MANIFESTS: OnceLock<Mutex<ManifestCache>> = OnceLock::new();
+
+/// The parsed manifest at `path`, from the cache while its text is unchanged
+fn read_manifest(path: &Path) -> Option<Arc<Document<String>>> {
+    let text = path_utils::read_source_file(path).ok()?;
+
+    let mut manifests = lock_manifests();
+    if let Some((cached_text, document)) = manifests.shift_remove(path) {
+        if cached_text == text {
+            manifests.insert(path.to_path_buf(), (cached_text, document.clone()));
+            return document;
+        }
+    }
+    drop(manifests);
+
+    let document = Document::parse(text.clone()).ok().map(Arc::new);
+
+    let mut manifests = lock_manifests();
+    while manifests.len() >= MANIFEST_CACHE_CAPACITY {
+        manifests.shift_remove_index(0);
+    }
+    manifests.insert(path.to_path_buf(), (text, document.clone()));
+    document
+}
+
+/// Number of manifests currently cached
+#[allow(dead_code)]
+pub fn cached_manifest_count() -> usize {
+    lock_manifests().len()
+}
+
+fn lock_manifests() -> std::sync::MutexGuard<'static, ManifestCache> {
+    // A panic while holding the lock cannot leave the map inconsistent.
+    MANIFESTS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn inherits_from_workspace(edition: &Item) -> bool {
+    edition
+        .get("workspace")
+        .and_then(Item::as_bool)
+        .unwrap_or(false)
+}
+
+/// `[workspace.package] edition` from the nearest manifest with a `[workspace]`
+/// table, starting at the member's own manifest.
+fn workspace_edition(cargo_toml_path: &Path) -> Option<RustEdition> {
+    cargo_toml_path
+        .parent()?
+        .ancestors()
+        .map(|dir| dir.join("Cargo.toml"))
+        .filter_map(|manifest| read_manifest(&manifest))
+        .find_map(|document| {
+            let workspace = document.get("workspace")?;
+            let edition = workspace.get("package")?.get("edition")?.as_str()?;
+            RustEdition::parse(edition)
+        })
+}
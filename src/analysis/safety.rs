@@ -0,0 +1,223 @@
+//! Panic Sites Tool
+//!
+//! Lists the places where Rust code can panic explicitly: `.unwrap()` and
+//! `.expect(..)` method calls and `panic!`, `todo!`, `unimplemented!` and
+//! `unreachable!` invocations.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "h": "kind|file|line|expression",
+//!   "panics": "unwrap|src/config.rs|14|fs::read_to_string(path)\ntodo|src/lib.rs|40|\"implement this\"\n..."
+//! }
+//! ```
+//!
+//! `expression` is the receiver for `unwrap`/`expect` and the macro arguments
+//! for the panicking macros, cut at 120 characters. Calls inside another
+//! macro's arguments (`println!("{}", x.unwrap())`) are unparsed token trees
+//! and are not listed.
+
+use std::io;
+use std::path::Path;
+
+use globset::{Glob, GlobMatcher};
+use serde_json::{json, Value};
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{Query, QueryCursor};
+
+use crate::analysis::macros::{captured, shorten, token_tree_contents};
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{detect_language, parse_code, Language};
+
+const PANICS_HEADER: &str = "kind|file|line|expression";
+
+const PANIC_MACROS: [&str; 4] = ["panic", "todo", "unimplemented", "unreachable"];
+
+const PANIC_QUERY: &str = r#"
+(call_expression
+  function: (field_expression
+    value: (_) @receiver
+    field: (field_identifier) @method)
+  (#any-of? @method "unwrap" "expect"))
+(macro_invocation macro: (_) @name (token_tree) @args) @macro
+"#;
+
+/// A call that panics on failure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicSite {
+    /// `unwrap`, `expect`, or the macro name (`panic`, `todo`, ...)
+    pub kind: String,
+    pub file: String,
+    pub line: usize,
+    /// Receiver of `unwrap`/`expect`, or the macro's arguments
+    pub expression: String,
+}
+
+pub fn execute_find_panics(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+    let whitelist_pattern = arguments["whitelist_pattern"].as_str();
+
+    log::info!("Finding panic sites in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let rows: Vec<String> = find_panics(path, whitelist_pattern)?
+        .iter()
+        .map(|site| {
+            format::format_row(&[
+                &site.kind,
+                &path_utils::to_relative_path(&site.file),
+                &site.line.to_string(),
+                &site.expression,
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "h": PANICS_HEADER,
+        "panics": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Panic sites of a Rust file or of every Rust file in a directory.
+///
+/// Files whose path relative to `path` matches the `whitelist_pattern` glob
+/// (e.g. `**/tests/**`) are skipped.
+pub fn find_panics(
+    path: &Path,
+    whitelist_pattern: Option<&str>,
+) -> Result<Vec<PanicSite>, io::Error> {
+    let whitelist = whitelist_pattern
+        .map(|pattern| {
+            Glob::new(pattern)
+                .map(|glob| glob.compile_matcher())
+                .map_err(|e| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("Invalid whitelist_pattern '{pattern}': {e}"),
+                    )
+                })
+        })
+        .transpose()?;
+
+    let mut sites = Vec::new();
+    for file in collect_project_files(path)? {
+        if detect_language(&file).ok() != Some(Language::Rust) {
+            continue;
+        }
+        if is_whitelisted(whitelist.as_ref(), path, &file) {
+            continue;
+        }
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        if !source.contains("unwrap")
+            && !source.contains("expect")
+            && !PANIC_MACROS.iter().any(|name| source.contains(name))
+        {
+            continue;
+        }
+        sites.extend(extract_panic_sites(&source, &file.to_string_lossy())?);
+    }
+
+    Ok(sites)
+}
+
+/// Panic sites of one Rust source file, in source order
+pub fn extract_panic_sites(source: &str, file: &str) -> Result<Vec<PanicSite>, io::Error> {
+    let query = Query::new(&Language::Rust.tree_sitter_language(), PANIC_QUERY)
+        .map_err(|e| io::Error::other(format!("Failed to compile panic query: {e}")))?;
+    let tree = parse_code(source, Language::Rust).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to parse Rust code from '{file}': {e}"),
+        )
+    })?;
+    let source_bytes = source.as_bytes();
+    let node_text = |node: tree_sitter::Node| node.utf8_text(source_bytes).unwrap_or("");
+
+    let mut sites = Vec::new();
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(&query, tree.root_node(), source_bytes);
+    while let Some(query_match) = matches.next() {
+        if let Some(method) = captured(&query, query_match, "method") {
+            let receiver = captured(&query, query_match, "receiver")
+                .map(|node| shorten(node_text(node)))
+                .unwrap_or_default();
+            // The method name, not the start of a multi-line chain
+            let position = method.start_position();
+            sites.push((
+                position,
+                PanicSite {
+                    kind: node_text(method).to_string(),
+                    file: file.to_string(),
+                    line: position.row + 1,
+                    expression: receiver,
+                },
+            ));
+            continue;
+        }
+
+        let (Some(name), Some(invocation)) = (
+            captured(&query, query_match, "name"),
+            captured(&query, query_match, "macro"),
+        ) else {
+            continue;
+        };
+        // `std::panic!` and `core::unreachable!` count too
+        let name = node_text(name).rsplit("::").next().unwrap_or("");
+        if !PANIC_MACROS.contains(&name) {
+            continue;
+        }
+        let expression = captured(&query, query_match, "args")
+            .map(|node| token_tree_contents(node_text(node)))
+            .unwrap_or_default();
+        let position = invocation.start_position();
+        sites.push((
+            position,
+            PanicSite {
+                kind: name.to_string(),
+                file: file.to_string(),
+                line: position.row + 1,
+                expression,
+            },
+        ));
+    }
+
+    sites.sort_by_key(|(position, _)| (position.row, position.column));
+    Ok(sites.into_iter().map(|(_, site)| site).collect())
+}
+
+fn is_whitelisted(whitelist: Option<&GlobMatcher>, root: &Path, file: &Path) -> bool {
+    let Some(whitelist) = whitelist else {
+        return false;
+    };
+    let relative = match file.strip_prefix(root) {
+        Ok(relative) if !relative.as_os_str().is_empty() => relative,
+        _ => Path::new(file.file_name().unwrap_or(file.as_os_str())),
+    };
+    whitelist.is_match(relative)
+}
@@ -0,0 +1,368 @@
+//! Semantic Tokens Tool
+//!
+//! Classifies the tokens of a file the way an editor colours them, using the
+//! LSP token types `function`, `type`, `variable`, `keyword`, `comment` and
+//! `string`. Classification is syntactic: an identifier is a `function` when
+//! it names a function or method declaration or is the callee of a call, a
+//! `type` when it names a type declaration or appears in a type position, and
+//! a `variable` otherwise. Comments and string literals are single tokens,
+//! including any interpolations inside them.
+//!
+//! Modifiers use the bit positions of the standard LSP legend; only
+//! `declaration` (the name of a declaration) and `documentation` (doc
+//! comments such as `///` and `/** */`) are set.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "file": "src/lib.rs",
+//!   "h": "line|col|len|type|mods|text",
+//!   "tokens": "1|1|2|keyword||fn\n1|4|4|function|declaration|main\n..."
+//! }
+//! ```
+//!
+//! `line` and `col` are 1-based, `len` is in bytes. `text` is empty for
+//! comments and strings to keep the output small.
+
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use tree_sitter::{Node, Tree};
+
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{detect_language, parse_code, Language};
+
+const TOKENS_HEADER: &str = "line|col|len|type|mods|text";
+
+/// LSP `declaration` modifier bit
+pub const MODIFIER_DECLARATION: u32 = 1 << 0;
+/// LSP `documentation` modifier bit
+pub const MODIFIER_DOCUMENTATION: u32 = 1 << 8;
+
+/// Subset of the LSP `SemanticTokenTypes`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SemanticTokenType {
+    Function,
+    Type,
+    Variable,
+    Keyword,
+    Comment,
+    String,
+}
+
+impl SemanticTokenType {
+    /// LSP name of the token type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SemanticTokenType::Function => "function",
+            SemanticTokenType::Type => "type",
+            SemanticTokenType::Variable => "variable",
+            SemanticTokenType::Keyword => "keyword",
+            SemanticTokenType::Comment => "comment",
+            SemanticTokenType::String => "string",
+        }
+    }
+}
+
+/// A classified span of source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub start_byte: usize,
+    /// Length in bytes
+    pub length: usize,
+    pub token_type: SemanticTokenType,
+    /// Bitset of `MODIFIER_*` values
+    pub modifiers: u32,
+}
+
+pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let file_path = arguments["file_path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'file_path' argument",
+        )
+    })?;
+
+    log::info!("Classifying semantic tokens in: {file_path}");
+
+    let path = Path::new(file_path);
+    let source = path_utils::read_source_file(path)?;
+    let language = detect_language(path).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("Cannot detect language for file {file_path}: {e}"),
+        )
+    })?;
+    let tree = parse_code(&source, language).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to parse {} code: {e}", language.name()),
+        )
+    })?;
+
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+
+    let rows: Vec<String> = classify_tokens(&tree, &source, language)
+        .iter()
+        .map(|token| {
+            let line = line_starts.partition_point(|&start| start <= token.start_byte);
+            let column = token.start_byte - line_starts[line - 1] + 1;
+            let text = match token.token_type {
+                SemanticTokenType::Comment | SemanticTokenType::String => "",
+                _ => &source[token.start_byte..token.start_byte + token.length],
+            };
+            format::format_row(&[
+                &line.to_string(),
+                &column.to_string(),
+                &token.length.to_string(),
+                token.token_type.as_str(),
+                &modifier_names(token.modifiers),
+                text,
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "file": path_utils::to_relative_path(file_path),
+        "h": TOKENS_HEADER,
+        "tokens": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Classified tokens of a parsed file, in document order.
+///
+/// Leaves that are neither keywords, identifiers, comments nor strings
+/// (punctuation, operators, numbers) are not reported.
+pub fn classify_tokens(tree: &Tree, source: &str, language: Language) -> Vec<SemanticToken> {
+    let mut tokens = Vec::new();
+    collect_tokens(tree.root_node(), source, language, &mut tokens);
+    tokens
+}
+
+fn collect_tokens(node: Node, source: &str, language: Language, tokens: &mut Vec<SemanticToken>) {
+    let whole = if is_comment(node) {
+        let text = node.utf8_text(source.as_bytes()).unwrap_or("");
+        let modifiers = if is_doc_comment(text) {
+            MODIFIER_DOCUMENTATION
+        } else {
+            0
+        };
+        Some((SemanticTokenType::Comment, modifiers))
+    } else if is_string(node) {
+        Some((SemanticTokenType::String, 0))
+    } else if node.child_count() == 0 {
+        classify_leaf(node, source, language)
+    } else {
+        None
+    };
+
+    if let Some((token_type, modifiers)) = whole {
+        if node.end_byte() > node.start_byte() {
+            tokens.push(SemanticToken {
+                start_byte: node.start_byte(),
+                length: node.end_byte() - node.start_byte(),
+                token_type,
+                modifiers,
+            });
+        }
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_tokens(child, source, language, tokens);
+    }
+}
+
+fn classify_leaf(node: Node, source: &str, language: Language) -> Option<(SemanticTokenType, u32)> {
+    let kind = node.kind();
+
+    if !node.is_named() {
+        // Anonymous leaves spelled like words are the grammar's keywords.
+        let keyword = kind.starts_with(|c: char| c.is_ascii_alphabetic())
+            && kind.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && node.utf8_text(source.as_bytes()) == Ok(kind);
+        return keyword.then_some((SemanticTokenType::Keyword, 0));
+    }
+
+    let modifiers = if is_declared_name(node) {
+        MODIFIER_DECLARATION
+    } else {
+        0
+    };
+
+    match (language, kind) {
+        (Language::Html | Language::Svelte | Language::Css, "tag_name") => {
+            Some((SemanticTokenType::Type, 0))
+        }
+        (Language::Css, "class_name" | "id_name") => Some((SemanticTokenType::Type, 0)),
+        (Language::Html | Language::Svelte, "attribute_name")
+        | (Language::Css, "property_name") => Some((SemanticTokenType::Variable, 0)),
+        (_, "self" | "this" | "super" | "true" | "false" | "null" | "nil" | "none") => {
+            Some((SemanticTokenType::Keyword, 0))
+        }
+        (
+            _,
+            "type_identifier"
+            | "primitive_type"
+            | "predefined_type"
+            | "builtin_type"
+            | "integral_type"
+            | "floating_point_type"
+            | "boolean_type"
+            | "void_type",
+        ) => Some((SemanticTokenType::Type, modifiers)),
+        (_, kind) if kind.ends_with("identifier") => {
+            let token_type = if is_function_name(node) {
+                SemanticTokenType::Function
+            } else if is_type_name(node) {
+                SemanticTokenType::Type
+            } else {
+                SemanticTokenType::Variable
+            };
+            Some((token_type, modifiers))
+        }
+        _ => None,
+    }
+}
+
+fn is_comment(node: Node) -> bool {
+    node.kind().contains("comment")
+}
+
+fn is_string(node: Node) -> bool {
+    let kind = node.kind();
+    node.is_named()
+        && (kind.contains("string") && !kind.contains("content") && !kind.contains("fragment")
+            || matches!(
+                kind,
+                "char_literal" | "character_literal" | "quoted_attribute_value"
+            ))
+}
+
+fn is_doc_comment(text: &str) -> bool {
+    (text.starts_with("///") && !text.starts_with("////"))
+        || text.starts_with("//!")
+        || (text.starts_with("/**") && text != "/**/")
+        || text.starts_with("/*!")
+}
+
+/// Whether `node` is the `field` child of its parent
+fn is_field(node: Node, field: &str) -> bool {
+    node.parent()
+        .and_then(|parent| parent.child_by_field_name(field))
+        .is_some_and(|child| child.id() == node.id())
+}
+
+/// Whether `node` is the name (or `let`/parameter pattern) of a declaration,
+/// as opposed to a reference such as `Type::name` or `Point { .. }`
+fn is_declared_name(node: Node) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    let parent_kind = parent.kind();
+    let declares = |markers: &[&str]| markers.iter().any(|marker| parent_kind.contains(marker));
+    (is_field(node, "name")
+        && declares(&[
+            "declaration",
+            "definition",
+            "declarator",
+            "_item",
+            "_spec",
+            "signature",
+            "parameter",
+        ]))
+        || (is_field(node, "pattern") && declares(&["declaration", "parameter"]))
+}
+
+fn is_function_name(node: Node) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    let parent_kind = parent.kind();
+    if is_field(node, "name")
+        && (parent_kind.contains("function")
+            || parent_kind.contains("method")
+            || parent_kind == "constructor_declaration")
+    {
+        return true;
+    }
+
+    // Callee of a call, possibly behind `obj.`, `Type::` or `pkg.` prefixes
+    let mut ancestor = Some(parent);
+    for _ in 0..3 {
+        let Some(candidate) = ancestor else {
+            break;
+        };
+        let callee = match candidate.kind() {
+            "call_expression" | "call" | "invocation_expression" => {
+                candidate.child_by_field_name("function")
+            }
+            "method_invocation" => candidate.child_by_field_name("name"),
+            "macro_invocation" => candidate.child_by_field_name("macro"),
+            _ => None,
+        };
+        if let Some(callee) = callee {
+            return rightmost_leaf(callee).id() == node.id();
+        }
+        ancestor = candidate.parent();
+    }
+    false
+}
+
+fn is_type_name(node: Node) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    let parent_kind = parent.kind();
+    is_field(node, "name")
+        && [
+            "class",
+            "struct",
+            "enum",
+            "interface",
+            "trait",
+            "type",
+            "record",
+            "protocol",
+        ]
+        .iter()
+        .any(|keyword| parent_kind.contains(keyword))
+}
+
+fn rightmost_leaf(node: Node) -> Node {
+    let mut current = node;
+    while let Some(last) = current
+        .named_child_count()
+        .checked_sub(1)
+        .and_then(|index| current.named_child(index as u32))
+    {
+        current = last;
+    }
+    current
+}
+
+fn modifier_names(modifiers: u32) -> String {
+    let mut names = Vec::new();
+    if modifiers & MODIFIER_DECLARATION != 0 {
+        names.push("declaration");
+    }
+    if modifiers & MODIFIER_DOCUMENTATION != 0 {
+        names.push("documentation");
+    }
+    names.join(",")
+}
@@ -3,10 +3,25 @@
 //! Extracts detailed file structure with signatures, doc comments, and full code blocks.
 //! Supports Rust, Python, JavaScript, TypeScript, Swift, C#, and Java.
 
-use crate::parser::Language;
+use crate::analysis::rust_edition::{rust_edition_for_file, RustEdition};
+use crate::common::tokenizer;
+use crate::extraction::types::collapse_whitespace;
+use crate::parser::{parse_code, parse_code_range, Language};
 use std::io;
+use std::path::Path;
 use streaming_iterator::StreamingIterator;
-use tree_sitter::{Node, Query, QueryCursor, Tree};
+use tree_sitter::{Node, Point, Query, QueryCursor, Range, Tree};
+
+/// Files above this size are extracted in chunks by
+/// [`extract_enhanced_shape_chunked`].
+const LARGE_FILE_THRESHOLD: usize = 1024 * 1024;
+
+/// Approximate size of each chunk of a large file.
+const CHUNK_TARGET_BYTES: usize = 256 * 1024;
+
+/// A chunk that still has syntax errors at this size is taken as it is
+/// rather than grown further.
+const MAX_CHUNK_BYTES: usize = 4 * CHUNK_TARGET_BYTES;
 
 /// Enhanced function information with signature and documentation
 #[derive(Debug, serde::Serialize, Clone)]
@@ -17,10 +32,57 @@ pub struct EnhancedFunctionInfo {
     pub end_line: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub doc: Option<String>,
+    /// Tags of a JS/TS `/** ... */` block; `doc` then holds its description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jsdoc: Option<JsDoc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub code: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub annotations: Vec<String>,
+    /// Arrow function bound to a variable (`const f = () => ...`)
+    pub is_arrow: bool,
+    /// Python decorators without the `@`, arguments kept (`app.route("/")`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub decorators: Vec<String>,
+    /// Receiver type of a Go method (`Server` for `func (s *Server) Start()`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receiver: Option<String>,
+    /// Tokens in `code`, set when code is extracted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_count: Option<usize>,
+}
+
+/// A parsed JSDoc block
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct JsDoc {
+    /// Text before the first tag
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<JsDocParam>,
+    /// `@returns` / `@return` text, type included (`{number} The sum`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub returns: Option<String>,
+    /// `@throws` / `@exception` texts, one per tag
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub throws: Vec<String>,
+    /// `@example` bodies with their line breaks kept
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+}
+
+/// One `@param {type} name - description` tag
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct JsDocParam {
+    /// Name without the brackets or default of an optional parameter
+    pub name: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_annotation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Written as `[name]` or `[name=default]`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
 }
 
 /// Enhanced struct information with documentation
@@ -33,6 +95,9 @@ pub struct EnhancedStructInfo {
     pub doc: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub code: Option<String>,
+    /// Tokens in `code`, set when code is extracted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_count: Option<usize>,
 }
 
 /// Enhanced class information with documentation
@@ -55,6 +120,9 @@ pub struct EnhancedClassInfo {
     pub fields: Vec<PropertyInfo>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub implements: Vec<String>,
+    /// Python decorators without the `@`, arguments kept (`dataclass(frozen=True)`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub decorators: Vec<String>,
 }
 
 /// Import information with text and line number
@@ -128,13 +196,16 @@ pub struct PropertyInfo {
 }
 
 /// Enhanced file shape with detailed information
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct EnhancedFileShape {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
     pub functions: Vec<EnhancedFunctionInfo>,
+    /// React components in JSX/TSX files; these are not repeated in `functions`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<EnhancedFunctionInfo>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub structs: Vec<EnhancedStructInfo>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -169,9 +240,147 @@ pub fn extract_enhanced_shape(
     language: Language,
     file_path: Option<&str>,
     include_code: bool,
+) -> Result<EnhancedFileShape, io::Error> {
+    let edition = edition_for(language, file_path);
+    let shape = extract_language_shape(tree, source, language, include_code, edition)?;
+    Ok(finish_shape(shape, language, file_path, include_code))
+}
+
+/// Extract enhanced shape from source that has not been parsed yet.
+///
+/// Sources over 1 MB are never parsed as a whole: they are parsed and
+/// extracted one chunk of top-level items at a time, and the results merged,
+/// so only one chunk's tree is held at once. Smaller sources are parsed
+/// whole.
+pub fn extract_enhanced_shape_chunked(
+    source: &str,
+    language: Language,
+    file_path: Option<&str>,
+    include_code: bool,
+) -> Result<EnhancedFileShape, io::Error> {
+    let parse_error = |e: eyre::Report| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to parse {} code: {e}", language.name()),
+        )
+    };
+    if source.len() <= LARGE_FILE_THRESHOLD {
+        let tree = parse_code(source, language).map_err(parse_error)?;
+        return extract_enhanced_shape(&tree, source, language, file_path, include_code);
+    }
+
+    let edition = edition_for(language, file_path);
+    let cuts = chunk_cuts(source);
+    let last_line = source.rsplit('\n').next().unwrap_or_default();
+    let eof = Point::new(source.matches('\n').count(), last_line.len());
+    log::debug!(
+        "Extracting shape of {} byte file in chunks ({} possible cuts)",
+        source.len(),
+        cuts.len()
+    );
+
+    let mut merged = empty_shape();
+    let mut start = Point::new(0, 0);
+    let mut start_byte = 0;
+    while start_byte < source.len() {
+        // Grow the chunk cut by cut until it parses cleanly: a cut inside a
+        // string or comment leaves an error at the chunk's edge.
+        let mut ends = cuts
+            .iter()
+            .filter(|(byte, _)| *byte >= start_byte + CHUNK_TARGET_BYTES)
+            .copied()
+            .chain(std::iter::once((source.len(), eof)));
+        let (chunk_tree, end_byte, end) = loop {
+            let Some((end_byte, end)) = ends.next() else {
+                unreachable!("the end of the file always closes a chunk");
+            };
+            let range = Range {
+                start_byte,
+                end_byte,
+                start_point: start,
+                end_point: end,
+            };
+            let chunk_tree = parse_code_range(source, language, range).map_err(parse_error)?;
+            if !chunk_tree.root_node().has_error()
+                || end_byte == source.len()
+                || end_byte - start_byte >= MAX_CHUNK_BYTES
+            {
+                break (chunk_tree, end_byte, end);
+            }
+        };
+
+        let chunk = extract_language_shape(&chunk_tree, source, language, include_code, edition)?;
+        merged.functions.extend(chunk.functions);
+        merged.components.extend(chunk.components);
+        merged.structs.extend(chunk.structs);
+        merged.classes.extend(chunk.classes);
+        merged.traits.extend(chunk.traits);
+        merged.interfaces.extend(chunk.interfaces);
+        merged.properties.extend(chunk.properties);
+        merged.imports.extend(chunk.imports);
+        merged.impl_blocks.extend(chunk.impl_blocks);
+        (start_byte, start) = (end_byte, end);
+    }
+
+    Ok(finish_shape(merged, language, file_path, include_code))
+}
+
+fn edition_for(language: Language, file_path: Option<&str>) -> RustEdition {
+    match (language, file_path) {
+        (Language::Rust, Some(path)) => rust_edition_for_file(Path::new(path)),
+        _ => RustEdition::default(),
+    }
+}
+
+fn finish_shape(
+    mut shape: EnhancedFileShape,
+    language: Language,
+    file_path: Option<&str>,
+    include_code: bool,
+) -> EnhancedFileShape {
+    if include_code {
+        fill_token_counts(&mut shape);
+    }
+    EnhancedFileShape {
+        path: file_path.map(|p| p.to_string()),
+        language: Some(language.name().to_string()),
+        ..shape
+    }
+}
+
+/// Set `token_count` on every function and struct that has code, counted
+/// with the configured tokenizer
+fn fill_token_counts(shape: &mut EnhancedFileShape) {
+    let Ok(counter) = tokenizer::tokenizer(tokenizer::configured()) else {
+        return;
+    };
+    let functions = shape
+        .functions
+        .iter_mut()
+        .chain(shape.components.iter_mut())
+        .chain(
+            shape
+                .classes
+                .iter_mut()
+                .flat_map(|class| class.methods.iter_mut()),
+        );
+    for function in functions {
+        function.token_count = function.code.as_deref().map(|code| counter.count(code));
+    }
+    for structure in &mut shape.structs {
+        structure.token_count = structure.code.as_deref().map(|code| counter.count(code));
+    }
+}
+
+fn extract_language_shape(
+    tree: &Tree,
+    source: &str,
+    language: Language,
+    include_code: bool,
+    edition: RustEdition,
 ) -> Result<EnhancedFileShape, io::Error> {
     let shape = match language {
-        Language::Rust => extract_rust_enhanced(tree, source, include_code)?,
+        Language::Rust => extract_rust_enhanced(tree, source, include_code, edition)?,
         Language::Python => extract_python_enhanced(tree, source, include_code)?,
         Language::JavaScript => {
             extract_js_enhanced(tree, source, Language::JavaScript, include_code)?
@@ -179,11 +388,13 @@ pub fn extract_enhanced_shape(
         Language::TypeScript => {
             extract_js_enhanced(tree, source, Language::TypeScript, include_code)?
         }
+        Language::Jsx => extract_js_enhanced(tree, source, Language::Jsx, include_code)?,
+        Language::Tsx => extract_js_enhanced(tree, source, Language::Tsx, include_code)?,
         Language::Swift => extract_swift_enhanced(tree, source, include_code)?,
         Language::CSharp => extract_csharp_enhanced(tree, source, include_code)?,
         Language::Java => extract_java_enhanced(tree, source, include_code)?,
         Language::Go => extract_go_enhanced(tree, source, include_code)?,
-        Language::Html | Language::Css => {
+        Language::Html | Language::Css | Language::Svelte => {
             // HTML and CSS are markup/styling languages and are not suitable for
             // structural shape analysis. They lack the function/class/module structure
             // that other programming languages have. Tools like view_code, code_map,
@@ -192,34 +403,60 @@ pub fn extract_enhanced_shape(
             //
             // For HTML/CSS analysis, consider using language-specific tools or parsers
             // designed for markup and styling languages.
-            EnhancedFileShape {
-                path: None,
-                language: None,
-                functions: vec![],
-                structs: vec![],
-                classes: vec![],
-                traits: vec![],
-                interfaces: vec![],
-                properties: vec![],
-                imports: vec![],
-                impl_blocks: vec![],
-                dependencies: vec![],
-            }
+            empty_shape()
         }
     };
 
-    Ok(EnhancedFileShape {
-        path: file_path.map(|p| p.to_string()),
-        language: Some(language.name().to_string()),
-        ..shape
-    })
+    Ok(shape)
+}
+
+/// Byte offsets and positions where a chunk of a large file may start: a
+/// line at column 0 after a blank line, so doc comments, attributes and
+/// decorators stay with their item. Lines closing a bracket are skipped.
+fn chunk_cuts(source: &str) -> Vec<(usize, Point)> {
+    let mut cuts = Vec::new();
+    let mut offset = 0;
+    let mut previous_blank = false;
+    for (row, line) in source.split_inclusive('\n').enumerate() {
+        let starts_item = line
+            .chars()
+            .next()
+            .is_some_and(|c| !c.is_whitespace() && !matches!(c, '}' | ')' | ']'));
+        if previous_blank && starts_item {
+            cuts.push((offset, Point::new(row, 0)));
+        }
+        previous_blank = line.trim().is_empty();
+        offset += line.len();
+    }
+    cuts
+}
+
+fn empty_shape() -> EnhancedFileShape {
+    EnhancedFileShape {
+        path: None,
+        language: None,
+        functions: vec![],
+        components: vec![],
+        structs: vec![],
+        classes: vec![],
+        traits: vec![],
+        interfaces: vec![],
+        properties: vec![],
+        imports: vec![],
+        impl_blocks: vec![],
+        dependencies: vec![],
+    }
 }
 
 /// Extract enhanced shape from Rust source code
+///
+/// In the 2015 edition `extern crate` declarations are imports too; from
+/// 2018 on crates are in scope without them.
 fn extract_rust_enhanced(
     tree: &Tree,
     source: &str,
     include_code: bool,
+    edition: RustEdition,
 ) -> Result<EnhancedFileShape, io::Error> {
     let mut functions = Vec::new();
     let mut structs = Vec::new();
@@ -227,8 +464,7 @@ fn extract_rust_enhanced(
     let mut impl_blocks = Vec::new();
     let mut traits = Vec::new();
 
-    let query = Query::new(
-        &tree_sitter_rust::LANGUAGE.into(),
+    let mut query_source = String::from(
         r#"
         (function_item name: (identifier) @func.name) @func
         (struct_item name: (type_identifier) @struct.name) @struct
@@ -236,8 +472,12 @@ fn extract_rust_enhanced(
         (impl_item) @impl
         (trait_item name: (type_identifier) @trait.name) @trait
         "#,
-    )
-    .map_err(|e| {
+    );
+    if edition == RustEdition::E2015 {
+        query_source.push_str("(extern_crate_declaration) @import\n");
+    }
+
+    let query = Query::new(&tree_sitter_rust::LANGUAGE.into(), &query_source).map_err(|e| {
         io::Error::new(
             io::ErrorKind::InvalidData,
             format!("Failed to create tree-sitter query: {e}"),
@@ -273,8 +513,13 @@ fn extract_rust_enhanced(
                                 line,
                                 end_line,
                                 doc,
+                                jsdoc: None,
                                 code,
                                 annotations: vec![],
+                                is_arrow: false,
+                                decorators: vec![],
+                                receiver: None,
+                                token_count: None,
                             });
                         }
                     }
@@ -297,6 +542,7 @@ fn extract_rust_enhanced(
                                 end_line,
                                 doc,
                                 code,
+                                token_count: None,
                             });
                         }
                     }
@@ -330,6 +576,7 @@ fn extract_rust_enhanced(
         path: None,
         language: None,
         functions,
+        components: vec![],
         structs,
         classes: vec![],
         imports,
@@ -401,8 +648,13 @@ fn extract_python_enhanced(
                                 line,
                                 end_line,
                                 doc,
+                                jsdoc: None,
                                 code,
                                 annotations: vec![],
+                                is_arrow: false,
+                                decorators: python_decorators(func_node, source),
+                                receiver: None,
+                                token_count: None,
                             });
                         }
                     }
@@ -442,6 +694,7 @@ fn extract_python_enhanced(
                                 implements: vec![],
                                 properties: vec![],
                                 fields: vec![],
+                                decorators: python_decorators(class_node, source),
                             });
                         }
                     }
@@ -463,6 +716,7 @@ fn extract_python_enhanced(
         path: None,
         language: None,
         functions,
+        components: vec![],
         structs: vec![],
         classes,
         imports,
@@ -475,6 +729,9 @@ fn extract_python_enhanced(
 }
 
 /// Extract enhanced shape from JavaScript/TypeScript source code
+///
+/// For JSX and TSX, React components are reported in `components` instead
+/// of `functions`.
 fn extract_js_enhanced(
     tree: &Tree,
     source: &str,
@@ -487,16 +744,16 @@ fn extract_js_enhanced(
     let mut interfaces = Vec::new();
 
     // Use the correct language for the query
-    let ts_language = match language {
-        Language::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
-        _ => tree_sitter_javascript::LANGUAGE.into(),
-    };
+    let ts_language = language.tree_sitter_language();
 
     // Different query patterns for TypeScript vs JavaScript
     let query_str = match language {
-        Language::TypeScript => {
+        Language::TypeScript | Language::Tsx => {
             r#"
         (function_declaration) @func
+        (variable_declarator
+            name: (identifier)
+            value: [(arrow_function) (function_expression)]) @func.variable
         (class_declaration) @class
         (interface_declaration name: (type_identifier) @interface.name) @interface
         (import_statement) @import
@@ -505,6 +762,9 @@ fn extract_js_enhanced(
         _ => {
             r#"
         (function_declaration name: (identifier) @func.name) @func
+        (variable_declarator
+            name: (identifier)
+            value: [(arrow_function) (function_expression)]) @func.variable
         (class_declaration name: (identifier) @class.name) @class
         (import_statement) @import
         "#
@@ -542,8 +802,8 @@ fn extract_js_enhanced(
                                 let line = func_node.start_position().row + 1;
                                 let end_line = func_node.end_position().row + 1;
                                 let signature = extract_signature(func_node, source)?;
-                                let doc =
-                                    extract_doc_comment(func_node, source, Language::JavaScript)?;
+                                let (doc, jsdoc) =
+                                    extract_js_docs(func_node, source, Language::JavaScript)?;
                                 let code = if include_code {
                                     extract_code(func_node, source)?
                                 } else {
@@ -556,8 +816,13 @@ fn extract_js_enhanced(
                                     line,
                                     end_line,
                                     doc,
+                                    jsdoc,
                                     code,
                                     annotations: vec![],
+                                    is_arrow: false,
+                                    decorators: vec![],
+                                    receiver: None,
+                                    token_count: None,
                                 });
                             }
                         }
@@ -574,7 +839,7 @@ fn extract_js_enhanced(
                                 let line = node.start_position().row + 1;
                                 let end_line = node.end_position().row + 1;
                                 let signature = extract_signature(node, source)?;
-                                let doc = extract_doc_comment(node, source, language)?;
+                                let (doc, jsdoc) = extract_js_docs(node, source, language)?;
                                 let code = if include_code {
                                     extract_code(node, source)?
                                 } else {
@@ -587,13 +852,26 @@ fn extract_js_enhanced(
                                     line,
                                     end_line,
                                     doc,
+                                    jsdoc,
                                     code,
                                     annotations: vec![],
+                                    is_arrow: false,
+                                    decorators: vec![],
+                                    receiver: None,
+                                    token_count: None,
                                 });
                             }
                         }
                     }
                 }
+                "func.variable" if processed_func_nodes.insert(node.id()) => {
+                    functions.extend(variable_function_info(
+                        node,
+                        source,
+                        language,
+                        include_code,
+                    )?);
+                }
                 "class.name" => {
                     // JavaScript: named capture for class name
                     if let Ok(class_node) = find_parent_by_type(node, "class_declaration") {
@@ -629,6 +907,7 @@ fn extract_js_enhanced(
                                     implements: vec![],
                                     properties: vec![],
                                     fields: vec![],
+                                    decorators: vec![],
                                 });
                             }
                         }
@@ -664,6 +943,7 @@ fn extract_js_enhanced(
                                     implements: vec![],
                                     properties: vec![],
                                     fields: vec![],
+                                    decorators: vec![],
                                 });
                             }
                         }
@@ -691,10 +971,22 @@ fn extract_js_enhanced(
         }
     }
 
+    let components = if matches!(language, Language::Jsx | Language::Tsx) {
+        extract_react_components(tree.root_node(), source, language, include_code)?
+    } else {
+        vec![]
+    };
+    functions.retain(|function| {
+        !components
+            .iter()
+            .any(|component| component.name == function.name)
+    });
+
     Ok(EnhancedFileShape {
         path: None,
         language: None,
         functions,
+        components,
         structs: vec![],
         classes,
         imports,
@@ -706,6 +998,202 @@ fn extract_js_enhanced(
     })
 }
 
+/// Top-level React components: capitalised functions, arrow functions and
+/// `memo()`/`forwardRef()` wrappers that render JSX, plus anonymous
+/// `export default` components (named `default`).
+fn extract_react_components(
+    root: Node,
+    source: &str,
+    language: Language,
+    include_code: bool,
+) -> Result<Vec<EnhancedFunctionInfo>, io::Error> {
+    let mut components = Vec::new();
+    let mut cursor = root.walk();
+
+    for statement in root.named_children(&mut cursor) {
+        let (declaration, default_value) = if statement.kind() == "export_statement" {
+            (
+                statement.child_by_field_name("declaration"),
+                statement.child_by_field_name("value"),
+            )
+        } else {
+            (Some(statement), None)
+        };
+
+        let mut found: Vec<(String, Node)> = Vec::new();
+        if let Some(declaration) = declaration {
+            match declaration.kind() {
+                "function_declaration" => {
+                    if let Some(name) = declaration.child_by_field_name("name") {
+                        let name = name.utf8_text(source.as_bytes()).unwrap_or_default();
+                        if is_component_name(name) && contains_jsx(declaration) {
+                            found.push((name.to_string(), declaration));
+                        }
+                    }
+                }
+                "lexical_declaration" | "variable_declaration" => {
+                    let mut declarators = declaration.walk();
+                    for declarator in declaration.named_children(&mut declarators) {
+                        let name = declarator
+                            .child_by_field_name("name")
+                            .filter(|name| name.kind() == "identifier")
+                            .and_then(|name| name.utf8_text(source.as_bytes()).ok())
+                            .unwrap_or_default();
+                        let renders = declarator
+                            .child_by_field_name("value")
+                            .and_then(|value| component_function(value, source));
+                        if is_component_name(name) && renders.is_some() {
+                            found.push((name.to_string(), declarator));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(function) = default_value.and_then(|value| component_function(value, source)) {
+            let name = function
+                .child_by_field_name("name")
+                .and_then(|name| name.utf8_text(source.as_bytes()).ok())
+                .unwrap_or("default");
+            found.push((name.to_string(), function));
+        }
+
+        for (name, node) in found {
+            let function = component_function(node, source).unwrap_or(node);
+            // Signature: from the statement start up to the function body
+            let body_start = function
+                .child_by_field_name("body")
+                .map_or(node.end_byte(), |body| body.start_byte());
+            let signature = source[statement.start_byte()..body_start]
+                .trim()
+                .to_string();
+            let code = if include_code {
+                extract_code(statement, source)?
+            } else {
+                None
+            };
+            let (doc, jsdoc) = extract_js_docs(statement, source, language)?;
+
+            components.push(EnhancedFunctionInfo {
+                name,
+                signature,
+                line: statement.start_position().row + 1,
+                end_line: statement.end_position().row + 1,
+                doc,
+                jsdoc,
+                code,
+                annotations: vec![],
+                is_arrow: function.kind() == "arrow_function",
+                decorators: vec![],
+                receiver: None,
+                token_count: None,
+            });
+        }
+    }
+
+    Ok(components)
+}
+
+/// Function bound by a module-level `const`/`let`/`var` declarator, possibly
+/// exported. Declarators inside function bodies are local helpers and skipped.
+fn variable_function_info(
+    declarator: Node,
+    source: &str,
+    language: Language,
+    include_code: bool,
+) -> Result<Option<EnhancedFunctionInfo>, io::Error> {
+    let Some(declaration) = declarator.parent().filter(|parent| {
+        matches!(
+            parent.kind(),
+            "lexical_declaration" | "variable_declaration"
+        )
+    }) else {
+        return Ok(None);
+    };
+    let top_level = declaration
+        .parent()
+        .is_some_and(|parent| matches!(parent.kind(), "program" | "export_statement"));
+    let (Some(name), Some(value)) = (
+        declarator.child_by_field_name("name"),
+        declarator.child_by_field_name("value"),
+    ) else {
+        return Ok(None);
+    };
+    if !top_level {
+        return Ok(None);
+    }
+
+    // Signature: from the declaration keyword up to the function body
+    let body_start = value
+        .child_by_field_name("body")
+        .map_or(value.end_byte(), |body| body.start_byte());
+    let signature = source[declaration.start_byte()..body_start]
+        .trim()
+        .to_string();
+    let code = if include_code {
+        extract_code(declaration, source)?
+    } else {
+        None
+    };
+    let (doc, jsdoc) = extract_js_docs(declaration, source, language)?;
+
+    Ok(Some(EnhancedFunctionInfo {
+        name: name
+            .utf8_text(source.as_bytes())
+            .unwrap_or_default()
+            .to_string(),
+        signature,
+        line: declaration.start_position().row + 1,
+        end_line: declaration.end_position().row + 1,
+        doc,
+        jsdoc,
+        code,
+        annotations: vec![],
+        is_arrow: value.kind() == "arrow_function",
+        decorators: vec![],
+        receiver: None,
+        token_count: None,
+    }))
+}
+
+fn is_component_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// The function rendering JSX behind `node`: the node itself, or the first
+/// argument of a `memo(...)`/`forwardRef(...)` call (also `React.`-qualified).
+fn component_function<'a>(node: Node<'a>, source: &str) -> Option<Node<'a>> {
+    match node.kind() {
+        "variable_declarator" => component_function(node.child_by_field_name("value")?, source),
+        "arrow_function" | "function_expression" | "function" | "function_declaration" => {
+            contains_jsx(node).then_some(node)
+        }
+        "call_expression" => {
+            let callee = node.child_by_field_name("function")?;
+            let callee = callee.utf8_text(source.as_bytes()).ok()?;
+            let wrapper = callee.strip_prefix("React.").unwrap_or(callee);
+            if !matches!(wrapper, "memo" | "forwardRef") {
+                return None;
+            }
+            let arguments = node.child_by_field_name("arguments")?;
+            component_function(arguments.named_child(0)?, source)
+        }
+        _ => None,
+    }
+}
+
+fn contains_jsx(node: Node) -> bool {
+    if matches!(
+        node.kind(),
+        "jsx_element" | "jsx_self_closing_element" | "jsx_fragment"
+    ) {
+        return true;
+    }
+    let mut cursor = node.walk();
+    let found = node.children(&mut cursor).any(contains_jsx);
+    found
+}
+
 /// Extract enhanced shape from Swift source code
 fn extract_swift_enhanced(
     tree: &Tree,
@@ -769,8 +1257,13 @@ fn extract_swift_enhanced(
                                 line,
                                 end_line,
                                 doc,
+                                jsdoc: None,
                                 code,
                                 annotations: vec![],
+                                is_arrow: false,
+                                decorators: vec![],
+                                receiver: None,
+                                token_count: None,
                             });
                         }
                     }
@@ -808,6 +1301,7 @@ fn extract_swift_enhanced(
                                     end_line,
                                     doc,
                                     code,
+                                    token_count: None,
                                 });
                             } else {
                                 // Extract methods from class body
@@ -828,6 +1322,7 @@ fn extract_swift_enhanced(
                                     implements: vec![],
                                     properties: vec![],
                                     fields: vec![],
+                                    decorators: vec![],
                                 });
                             }
                         }
@@ -891,6 +1386,7 @@ fn extract_swift_enhanced(
         path: None,
         language: None,
         functions,
+        components: vec![],
         structs,
         classes,
         imports,
@@ -971,8 +1467,13 @@ fn extract_csharp_enhanced(
                                     line,
                                     end_line,
                                     doc,
+                                    jsdoc: None,
                                     code,
                                     annotations: vec![],
+                                    is_arrow: false,
+                                    decorators: vec![],
+                                    receiver: None,
+                                    token_count: None,
                                 });
                             }
                         }
@@ -1012,6 +1513,7 @@ fn extract_csharp_enhanced(
                                     implements,
                                     properties: vec![],
                                     fields: vec![],
+                                    decorators: vec![],
                                 });
                             }
                         }
@@ -1090,6 +1592,7 @@ fn extract_csharp_enhanced(
         path: None,
         language: None,
         functions,
+        components: vec![],
         structs: vec![],
         classes,
         imports,
@@ -1148,8 +1651,13 @@ fn extract_csharp_method_info(
                 line,
                 end_line,
                 doc,
+                jsdoc: None,
                 code,
                 annotations: vec![],
+                is_arrow: false,
+                decorators: vec![],
+                receiver: None,
+                token_count: None,
             }));
         }
     }
@@ -1268,8 +1776,13 @@ fn extract_java_enhanced(
                                     line,
                                     end_line,
                                     doc,
+                                    jsdoc: None,
                                     code,
                                     annotations,
+                                    is_arrow: false,
+                                    decorators: vec![],
+                                    receiver: None,
+                                    token_count: None,
                                 });
                             }
                         }
@@ -1308,6 +1821,7 @@ fn extract_java_enhanced(
                                     implements,
                                     properties: vec![],
                                     fields: vec![],
+                                    decorators: vec![],
                                 });
                             }
                         }
@@ -1358,6 +1872,7 @@ fn extract_java_enhanced(
         path: None,
         language: None,
         functions,
+        components: vec![],
         structs: vec![],
         classes,
         imports,
@@ -1384,6 +1899,7 @@ fn extract_go_enhanced(
         &tree_sitter_go::LANGUAGE.into(),
         r#"
         (function_declaration name: (identifier) @func.name) @func
+        (method_declaration name: (field_identifier) @method.name) @method
         (type_spec name: (type_identifier) @struct.name type: (struct_type)) @struct
         (type_spec name: (type_identifier) @iface.name type: (interface_type)) @iface
         (import_spec path: (interpreted_string_literal) @import.path) @import
@@ -1408,46 +1924,15 @@ fn extract_go_enhanced(
             let capture_name = query.capture_names()[capture.index as usize];
 
             match capture_name {
-                "func.name" => {
-                    if let Ok(func_node) = find_parent_by_type(node, "function_declaration") {
-                        let node_id = func_node.id();
-                        if processed_function_nodes.contains(&node_id) {
-                            continue;
-                        }
-                        processed_function_nodes.insert(node_id);
-
-                        if let Ok(name) = node.utf8_text(source.as_bytes()) {
-                            let mut line = func_node.start_position().row + 1;
-                            let end_line = func_node.end_position().row + 1;
-                            let signature = extract_signature(func_node, source)?;
-                            let doc = extract_doc_comment(func_node, source, Language::Go)?;
-
-                            // Go tests expect the "start line" for functions to include the
-                            // immediately preceding doc comment.
-                            if let Some(prev) = func_node.prev_sibling() {
-                                if prev.kind() == "comment"
-                                    && prev.end_position().row + 1 == line.saturating_sub(1)
-                                {
-                                    line = prev.start_position().row + 1;
-                                }
-                            }
-
-                            let code = if include_code {
-                                extract_code(func_node, source)?
-                            } else {
-                                None
-                            };
-
-                            functions.push(EnhancedFunctionInfo {
-                                name: name.to_string(),
-                                signature,
-                                line,
-                                end_line,
-                                doc,
-                                code,
-                                annotations: vec![],
-                            });
-                        }
+                "func.name" | "method.name" => {
+                    let Some(func_node) = node.parent() else {
+                        continue;
+                    };
+                    if !processed_function_nodes.insert(func_node.id()) {
+                        continue;
+                    }
+                    if let Ok(name) = node.utf8_text(source.as_bytes()) {
+                        functions.push(go_function_info(func_node, name, source, include_code)?);
                     }
                 }
                 "struct.name" => {
@@ -1475,6 +1960,7 @@ fn extract_go_enhanced(
                                 end_line,
                                 doc,
                                 code,
+                                token_count: None,
                             });
                         }
                     }
@@ -1519,6 +2005,7 @@ fn extract_go_enhanced(
         path: None,
         language: None,
         functions,
+        components: vec![],
         structs,
         classes: vec![],
         imports,
@@ -1530,6 +2017,69 @@ fn extract_go_enhanced(
     })
 }
 
+/// Function or method info for a Go `function_declaration`/`method_declaration`
+fn go_function_info(
+    func_node: Node,
+    name: &str,
+    source: &str,
+    include_code: bool,
+) -> Result<EnhancedFunctionInfo, io::Error> {
+    let mut line = func_node.start_position().row + 1;
+    let end_line = func_node.end_position().row + 1;
+    let signature = extract_signature(func_node, source)?;
+    let doc = extract_doc_comment(func_node, source, Language::Go)?;
+
+    // Go tests expect the "start line" for functions to include the
+    // immediately preceding doc comment.
+    if let Some(prev) = func_node.prev_sibling() {
+        if prev.kind() == "comment" && prev.end_position().row + 1 == line.saturating_sub(1) {
+            line = prev.start_position().row + 1;
+        }
+    }
+
+    let code = if include_code {
+        extract_code(func_node, source)?
+    } else {
+        None
+    };
+
+    Ok(EnhancedFunctionInfo {
+        name: name.to_string(),
+        signature,
+        line,
+        end_line,
+        doc,
+        jsdoc: None,
+        code,
+        annotations: vec![],
+        is_arrow: false,
+        decorators: vec![],
+        receiver: go_receiver_type(func_node, source),
+        token_count: None,
+    })
+}
+
+/// Base type name of a Go method receiver: `Server` for `(s *Server)`,
+/// `List` for `(l *List[T])`
+fn go_receiver_type(method: Node, source: &str) -> Option<String> {
+    let receiver = method.child_by_field_name("receiver")?;
+    let mut cursor = receiver.walk();
+    let parameter = receiver
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "parameter_declaration")?;
+    let mut type_node = parameter.child_by_field_name("type")?;
+    loop {
+        type_node = match type_node.kind() {
+            "type_identifier" => {
+                return Some(type_node.utf8_text(source.as_bytes()).ok()?.to_string())
+            }
+            "pointer_type" | "parenthesized_type" => type_node.named_child(0)?,
+            "generic_type" => type_node.child_by_field_name("type")?,
+            _ => return None,
+        };
+    }
+}
+
 /// Helper function to extract methods from a Java class
 fn extract_java_class_methods(
     class_node: Node,
@@ -1579,8 +2129,13 @@ fn extract_java_method_info(
                 line,
                 end_line,
                 doc,
+                jsdoc: None,
                 code,
                 annotations,
+                is_arrow: false,
+                decorators: vec![],
+                receiver: None,
+                token_count: None,
             }));
         }
     }
@@ -1836,8 +2391,13 @@ fn extract_interface(
                             line: method_line,
                             end_line: method_end_line,
                             doc: method_doc,
+                            jsdoc: None,
                             code,
                             annotations: vec![],
+                            is_arrow: false,
+                            decorators: vec![],
+                            receiver: None,
+                            token_count: None,
                         });
                     }
                 }
@@ -1874,7 +2434,9 @@ fn extract_class_methods(
     // Find the class body
     let body = match language {
         Language::Python => class_node.child_by_field_name("body"),
-        Language::JavaScript | Language::TypeScript => class_node.child_by_field_name("body"),
+        Language::JavaScript | Language::TypeScript | Language::Jsx | Language::Tsx => {
+            class_node.child_by_field_name("body")
+        }
         Language::Swift => class_node.child_by_field_name("body"),
         _ => None,
     };
@@ -1882,6 +2444,14 @@ fn extract_class_methods(
     if let Some(body_node) = body {
         let mut cursor = body_node.walk();
         for child in body_node.children(&mut cursor) {
+            // Decorated Python methods are wrapped in a `decorated_definition`
+            let child = match child.kind() {
+                "decorated_definition" if language == Language::Python => {
+                    child.child_by_field_name("definition").unwrap_or(child)
+                }
+                _ => child,
+            };
+
             // Skip nested classes
             if child.kind() == "class_definition"
                 || child.kind() == "class_declaration"
@@ -1892,7 +2462,7 @@ fn extract_class_methods(
 
             let is_method = match language {
                 Language::Python => child.kind() == "function_definition",
-                Language::JavaScript | Language::TypeScript => {
+                Language::JavaScript | Language::TypeScript | Language::Jsx | Language::Tsx => {
                     child.kind() == "method_definition" || child.kind() == "function_declaration"
                 }
                 Language::Swift => child.kind() == "function_declaration",
@@ -1919,8 +2489,17 @@ fn extract_class_methods(
                             line,
                             end_line,
                             doc,
+                            jsdoc: None,
                             code,
                             annotations: vec![],
+                            is_arrow: false,
+                            decorators: if language == Language::Python {
+                                python_decorators(child, source)
+                            } else {
+                                vec![]
+                            },
+                            receiver: None,
+                            token_count: None,
                         });
                     }
                 }
@@ -1931,6 +2510,26 @@ fn extract_class_methods(
     Ok(methods)
 }
 
+/// Decorators of a Python function or class definition, in source order,
+/// without the `@` and with whitespace inside arguments collapsed
+fn python_decorators(definition: Node, source: &str) -> Vec<String> {
+    let Some(decorated) = definition
+        .parent()
+        .filter(|parent| parent.kind() == "decorated_definition")
+    else {
+        return vec![];
+    };
+
+    let mut cursor = decorated.walk();
+    let decorators = decorated
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "decorator")
+        .filter_map(|decorator| decorator.utf8_text(source.as_bytes()).ok())
+        .map(|text| collapse_whitespace(text.trim_start_matches('@')))
+        .collect();
+    decorators
+}
+
 /// Extract the signature line of a function or struct
 /// Uses tree-sitter to find the body node and extract signature efficiently
 fn extract_signature(node: Node, source: &str) -> Result<String, io::Error> {
@@ -2102,6 +2701,8 @@ fn is_line_comment_text(trimmed: &str, language: Language) -> bool {
         Language::Rust
         | Language::JavaScript
         | Language::TypeScript
+        | Language::Jsx
+        | Language::Tsx
         | Language::Swift
         | Language::CSharp
         | Language::Java
@@ -2116,6 +2717,8 @@ fn supports_block_comments(language: Language) -> bool {
         Language::Rust
             | Language::JavaScript
             | Language::TypeScript
+            | Language::Jsx
+            | Language::Tsx
             | Language::Swift
             | Language::CSharp
             | Language::Java
@@ -2173,6 +2776,145 @@ fn extract_doc_comment(
     Ok(None)
 }
 
+/// `doc` and `jsdoc` of a JS/TS definition: with a `/** ... */` block the
+/// doc is its description, otherwise whatever `extract_doc_comment` finds
+fn extract_js_docs(
+    node: Node,
+    source: &str,
+    language: Language,
+) -> Result<(Option<String>, Option<JsDoc>), io::Error> {
+    let doc = extract_doc_comment(node, source, language)?;
+    match leading_jsdoc(node, source) {
+        Some(jsdoc) => Ok((jsdoc.description.clone().or(doc), Some(jsdoc))),
+        None => Ok((doc, None)),
+    }
+}
+
+/// The `/** ... */` block directly above `node`, looking through `export`
+/// and `const f = ...` wrappers
+fn leading_jsdoc(node: Node, source: &str) -> Option<JsDoc> {
+    let mut current = node;
+    loop {
+        if let Some(prev) = current
+            .prev_sibling()
+            .filter(|prev| prev.kind() == "comment")
+        {
+            let text = prev.utf8_text(source.as_bytes()).ok()?;
+            return text.starts_with("/**").then(|| extract_jsdoc(text));
+        }
+        current = current.parent().filter(|parent| {
+            matches!(
+                parent.kind(),
+                "export_statement"
+                    | "lexical_declaration"
+                    | "variable_declaration"
+                    | "variable_declarator"
+            )
+        })?;
+    }
+}
+
+/// Parse a JSDoc block (`/** ... */`) into its description and
+/// `@param`, `@returns`, `@throws` and `@example` tags; other tags are
+/// ignored.
+pub fn extract_jsdoc(comment_text: &str) -> JsDoc {
+    let body = comment_text.trim();
+    let body = body.strip_prefix("/**").unwrap_or(body);
+    let body = body.strip_suffix("*/").unwrap_or(body);
+
+    // Leading `*` gutters are dropped; indentation after them is kept for
+    // examples.
+    let mut sections: Vec<(Option<&str>, Vec<&str>)> = vec![(None, Vec::new())];
+    for line in body.lines() {
+        let line = line.trim_start();
+        let line = line.strip_prefix('*').unwrap_or(line);
+        let line = line.strip_prefix(' ').unwrap_or(line);
+        match line.trim_start().strip_prefix('@') {
+            Some(tagged) => {
+                let (tag, rest) = tagged
+                    .split_once(char::is_whitespace)
+                    .unwrap_or((tagged, ""));
+                sections.push((Some(tag), vec![rest]));
+            }
+            None => sections.last_mut().expect("one section").1.push(line),
+        }
+    }
+
+    let mut jsdoc = JsDoc::default();
+    for (tag, lines) in sections {
+        let text = non_empty(
+            lines
+                .iter()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        match tag {
+            None => jsdoc.description = text,
+            Some("param" | "arg" | "argument") => {
+                if let Some(param) = text.as_deref().and_then(parse_jsdoc_param) {
+                    jsdoc.params.push(param);
+                }
+            }
+            Some("returns" | "return") => jsdoc.returns = text,
+            Some("throws" | "exception") => jsdoc.throws.extend(text),
+            Some("example") => {
+                let example = lines.join("\n");
+                jsdoc.examples.extend(non_empty(example.trim().to_string()));
+            }
+            Some(_) => {}
+        }
+    }
+    jsdoc
+}
+
+/// `{type} name - description`, `{type} [name=default] description` or
+/// just `name`
+fn parse_jsdoc_param(text: &str) -> Option<JsDocParam> {
+    let mut rest = text.trim();
+    let mut type_annotation = None;
+    if rest.starts_with('{') {
+        // Types can nest braces: `{{ id: number }}`
+        let mut depth = 0;
+        let end = rest.char_indices().find_map(|(i, c)| {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            (depth == 0).then_some(i)
+        })?;
+        type_annotation = non_empty(rest[1..end].trim().to_string());
+        rest = rest[end + 1..].trim_start();
+    }
+
+    let (name, optional, rest) = if let Some(bracketed) = rest.strip_prefix('[') {
+        let (inner, after) = bracketed.split_once(']')?;
+        let name = inner.split_once('=').map_or(inner, |(name, _)| name);
+        (name.trim(), true, after)
+    } else {
+        let (name, after) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        (name, false, after)
+    };
+    if name.is_empty() {
+        return None;
+    }
+
+    let description = rest.trim_start();
+    let description = description.strip_prefix('-').unwrap_or(description);
+    Some(JsDocParam {
+        name: name.to_string(),
+        type_annotation,
+        description: non_empty(description.trim().to_string()),
+        optional,
+    })
+}
+
+fn non_empty(text: String) -> Option<String> {
+    (!text.is_empty()).then_some(text)
+}
+
 /// Check if a node is a comment node
 fn is_comment_node(node: &Node, language: Language) -> bool {
     let kind = node.kind();
@@ -2180,6 +2922,8 @@ fn is_comment_node(node: &Node, language: Language) -> bool {
         Language::Rust
         | Language::JavaScript
         | Language::TypeScript
+        | Language::Jsx
+        | Language::Tsx
         | Language::Swift
         | Language::CSharp
         | Language::Java
@@ -2212,7 +2956,12 @@ fn extract_doc_from_comment(comment_text: &str, language: Language) -> String {
                 String::new()
             }
         }
-        Language::JavaScript | Language::TypeScript | Language::Java | Language::Go => {
+        Language::JavaScript
+        | Language::TypeScript
+        | Language::Jsx
+        | Language::Tsx
+        | Language::Java
+        | Language::Go => {
             // Handle /** */ and // comments
             if trimmed.starts_with("/**") && trimmed.ends_with("*/") {
                 trimmed
@@ -2871,7 +3620,8 @@ mod tests {
  }
  "#;
         let tree = parse_code(source, Language::Rust).expect("Failed to parse");
-        let shape = extract_rust_enhanced(&tree, source, true).expect("Failed to extract shape");
+        let shape = extract_rust_enhanced(&tree, source, true, RustEdition::default())
+            .expect("Failed to extract shape");
 
         assert_eq!(shape.functions.len(), 1);
         let func = &shape.functions[0];
@@ -2925,7 +3675,8 @@ def greet(name):
  fn main() {}
  "#;
         let tree = parse_code(source, Language::Rust).expect("Failed to parse");
-        let shape = extract_rust_enhanced(&tree, source, true).expect("Failed to extract shape");
+        let shape = extract_rust_enhanced(&tree, source, true, RustEdition::default())
+            .expect("Failed to extract shape");
 
         assert_eq!(shape.imports.len(), 2);
         assert_eq!(shape.imports[0].text, "use std::fmt;");
@@ -5,7 +5,7 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::analysis::shape::extract_enhanced_shape;
+    use crate::analysis::shape::{extract_enhanced_shape, extract_enhanced_shape_chunked};
     use crate::parser::{parse_code, Language};
 
     // ========================================================================
@@ -285,6 +285,75 @@ class Calculator:
         assert_eq!(shape.functions.len(), 0);
     }
 
+    #[test]
+    fn test_extract_python_decorators() {
+        // Given: Decorated functions, methods and classes
+        let source = r#"
+@app.route("/users/<int:id>", methods=["GET"])
+@login_required
+def get_user(id):
+    return id
+
+@dataclass(frozen=True)
+class Point:
+    x: int
+
+    @property
+    def norm(self) -> float:
+        return abs(self.x)
+
+    @staticmethod
+    @cache(
+        maxsize=32,
+    )
+    def origin():
+        return Point(0)
+
+    def plain(self):
+        pass
+
+def helper():
+    pass
+"#;
+
+        // When: Parse and extract shape
+        let tree = parse_code(source, Language::Python).unwrap();
+        let shape = extract_enhanced_shape(&tree, source, Language::Python, None, false).unwrap();
+
+        // Then: Decorators are listed in order without `@`, arguments kept
+        assert_eq!(shape.functions[0].name, "get_user");
+        assert_eq!(
+            shape.functions[0].decorators,
+            [
+                r#"app.route("/users/<int:id>", methods=["GET"])"#,
+                "login_required"
+            ]
+        );
+        assert_eq!(shape.functions[1].name, "helper");
+        assert!(shape.functions[1].decorators.is_empty());
+
+        let point = &shape.classes[0];
+        assert_eq!(point.decorators, ["dataclass(frozen=True)"]);
+        let methods: Vec<(&str, Vec<&str>)> = point
+            .methods
+            .iter()
+            .map(|m| {
+                (
+                    m.name.as_str(),
+                    m.decorators.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            methods,
+            [
+                ("norm", vec!["property"]),
+                ("origin", vec!["staticmethod", "cache(maxsize=32)"]),
+                ("plain", vec![]),
+            ]
+        );
+    }
+
     #[test]
     fn test_extract_python_nested_classes() {
         // Given: Nested classes
@@ -563,6 +632,179 @@ class Calculator:
         assert_eq!(shape.classes[0].methods[0].name, "method");
     }
 
+    #[test]
+    fn test_extract_js_variable_bound_functions() {
+        // Given: JavaScript with arrow and function-expression bindings
+        let source = r#"
+const add = (a, b) => a + b;
+
+const load = async function (url) {
+  return fetch(url);
+};
+
+function main() {
+  const local = () => 1;
+  return local();
+}
+
+const limit = 10;
+"#;
+
+        // When: Parse
+        let tree = parse_code(source, Language::JavaScript).unwrap();
+        let shape =
+            extract_enhanced_shape(&tree, source, Language::JavaScript, None, false).unwrap();
+
+        // Then: Module-level bindings are functions; locals and values are not
+        let functions: Vec<(&str, bool)> = shape
+            .functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.is_arrow))
+            .collect();
+        assert_eq!(functions, [("add", true), ("load", false), ("main", false)]);
+        assert_eq!(shape.functions[0].signature, "const add = (a, b) =>");
+        assert_eq!(
+            shape.functions[1].signature,
+            "const load = async function (url)"
+        );
+        assert_eq!(shape.functions[1].end_line, 6);
+    }
+
+    #[test]
+    fn test_extract_ts_exported_generic_and_destructured_arrows() {
+        // Given: TypeScript with exported, generic and destructuring arrows
+        let source = r#"
+interface Props { a: number; b: number }
+
+/** Sums the props */
+export const sum = ({ a, b }: Props) => a + b;
+
+export const identity = <T>(value: T): T => value;
+
+let handler = async (event: Event): Promise<void> => {
+  console.log(event);
+};
+"#;
+
+        // When: Parse
+        let tree = parse_code(source, Language::TypeScript).unwrap();
+        let shape =
+            extract_enhanced_shape(&tree, source, Language::TypeScript, None, false).unwrap();
+
+        // Then: Each binding is an arrow function named after its variable
+        let names: Vec<&str> = shape.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["sum", "identity", "handler"]);
+        assert!(shape.functions.iter().all(|f| f.is_arrow));
+        assert_eq!(
+            shape.functions[0].signature,
+            "const sum = ({ a, b }: Props) =>"
+        );
+        assert_eq!(shape.functions[0].doc.as_deref(), Some("Sums the props"));
+        assert_eq!(
+            shape.functions[1].signature,
+            "const identity = <T>(value: T): T =>"
+        );
+        assert_eq!(
+            shape.functions[2].signature,
+            "let handler = async (event: Event): Promise<void> =>"
+        );
+    }
+
+    // ========================================================================
+    // React Components (JSX/TSX)
+    // ========================================================================
+
+    #[test]
+    fn test_extract_jsx_components_separately_from_functions() {
+        // Given: JSX with a named function component, a memo()-wrapped arrow
+        // component, a utility function and a default export arrow component
+        let source = r#"
+import React, { memo } from 'react';
+
+export function Header({ title }) {
+  return <h1>{title}</h1>;
+}
+
+const Badge = memo(({ count }) => <span>{count}</span>);
+
+function formatCount(count) {
+  return `${count}`;
+}
+
+export default (props) => (
+  <main>
+    <Header title="Inbox" />
+  </main>
+);
+"#;
+
+        // When: Parse
+        let tree = parse_code(source, Language::Jsx).unwrap();
+        let shape = extract_enhanced_shape(&tree, source, Language::Jsx, None, false).unwrap();
+
+        // Then: Components and utility functions are listed apart
+        let components: Vec<(&str, usize)> = shape
+            .components
+            .iter()
+            .map(|c| (c.name.as_str(), c.line))
+            .collect();
+        assert_eq!(components, [("Header", 4), ("Badge", 8), ("default", 14)]);
+        assert_eq!(
+            shape.components[1].signature,
+            "const Badge = memo(({ count }) =>"
+        );
+
+        let functions: Vec<&str> = shape.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(functions, ["formatCount"]);
+        assert_eq!(shape.language.as_deref(), Some("JSX"));
+    }
+
+    #[test]
+    fn test_extract_tsx_components_with_typed_props() {
+        // Given: TSX with a typed arrow component and a React.memo-wrapped
+        // function expression
+        let source = r#"
+interface ButtonProps { label: string }
+
+export const Button = ({ label }: ButtonProps) => <button>{label}</button>;
+
+export const Icon = React.memo(function Icon(props: { name: string }) {
+  return <i className={props.name} />;
+});
+
+export function useToggle(initial: boolean): boolean {
+  return initial;
+}
+"#;
+
+        // When: Parse with the TSX grammar
+        let tree = parse_code(source, Language::Tsx).unwrap();
+        assert!(!tree.root_node().has_error());
+        let shape = extract_enhanced_shape(&tree, source, Language::Tsx, None, false).unwrap();
+
+        // Then: Both components are found, the hook stays a function
+        let components: Vec<&str> = shape.components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(components, ["Button", "Icon"]);
+        let functions: Vec<&str> = shape.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(functions, ["useToggle"]);
+        assert_eq!(shape.interfaces[0].name, "ButtonProps");
+    }
+
+    #[test]
+    fn test_plain_javascript_has_no_components() {
+        // Given: JSX syntax in a .js file
+        let source = "function App() { return <div />; }\n";
+
+        // When: Parse as plain JavaScript
+        let tree = parse_code(source, Language::JavaScript).unwrap();
+        let shape =
+            extract_enhanced_shape(&tree, source, Language::JavaScript, None, false).unwrap();
+
+        // Then: Only JSX/TSX files report components
+        assert!(shape.components.is_empty());
+        assert_eq!(shape.functions[0].name, "App");
+    }
+
     // ========================================================================
     // Module/JSON Format (from shape_module_test.rs)
     // ========================================================================
@@ -701,4 +943,67 @@ import { utils } from './utils.js';
         assert_eq!(cls.name, "Calculator");
         assert_eq!(cls.line, 6);
     }
+
+    // ========================================================================
+    // Large File Chunking
+    // ========================================================================
+
+    #[test]
+    fn test_chunked_shape_matches_whole_file_shape() {
+        let mut source = String::from("use std::fmt;\n\n");
+        for i in 0..12_000 {
+            source.push_str(&format!(
+                "/// Adds {i} to the input\n#[inline]\npub fn add_{i}(value: i32) -> i32 {{\n    value + {i}\n}}\n\n"
+            ));
+        }
+        source.push_str("pub struct Tail {\n    value: i32,\n}\n");
+        assert!(
+            source.len() > 1024 * 1024,
+            "fixture must exceed the chunk threshold"
+        );
+
+        let tree = parse_code(&source, Language::Rust).unwrap();
+        let whole = extract_enhanced_shape(&tree, &source, Language::Rust, None, false).unwrap();
+        let chunked = extract_enhanced_shape_chunked(&source, Language::Rust, None, false).unwrap();
+
+        assert_eq!(chunked.functions.len(), 12_000);
+        assert_eq!(chunked.imports.len(), 1);
+        assert_eq!(chunked.structs.len(), 1);
+        assert_eq!(chunked.structs[0].line, whole.structs[0].line);
+        for (a, b) in whole.functions.iter().zip(&chunked.functions) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.line, b.line);
+            assert_eq!(a.end_line, b.end_line);
+            assert_eq!(a.signature, b.signature);
+            assert_eq!(a.doc, b.doc);
+        }
+    }
+
+    #[test]
+    fn test_chunked_tsx_keeps_components() {
+        let mut source = String::from("import React from 'react';\n\n");
+        for i in 0..10_000 {
+            source.push_str(&format!(
+                "export function Card{i}({{ title }}: {{ title: string }}) {{\n  return <div className=\"card\">{{title}} {i}</div>;\n}}\n\n"
+            ));
+        }
+        source.push_str(
+            "export function formatTitle(title: string): string {\n  return title.trim();\n}\n",
+        );
+        assert!(
+            source.len() > 1024 * 1024,
+            "fixture must exceed the chunk threshold"
+        );
+
+        let tree = parse_code(&source, Language::Tsx).unwrap();
+        let whole = extract_enhanced_shape(&tree, &source, Language::Tsx, None, false).unwrap();
+        let chunked = extract_enhanced_shape_chunked(&source, Language::Tsx, None, false).unwrap();
+
+        assert_eq!(whole.components.len(), 10_000);
+        assert_eq!(chunked.components.len(), 10_000);
+        assert_eq!(chunked.components[9_999].name, "Card9999");
+        assert_eq!(chunked.components[9_999].line, whole.components[9_999].line);
+        assert_eq!(chunked.functions.len(), 1);
+        assert_eq!(chunked.functions[0].name, "formatTitle");
+    }
 }
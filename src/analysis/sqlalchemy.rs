@@ -0,0 +1,298 @@
+//! SQLAlchemy Model Extraction
+//!
+//! Recognizes declarative models in both API styles:
+//!
+//! - 1.x: `Base = declarative_base()`, `id = Column(Integer, primary_key=True)`
+//! - 2.x: `class Base(DeclarativeBase)`, `id: Mapped[int] = mapped_column(primary_key=True)`
+//!
+//! A model is a class deriving from `Base`, from a declarative base declared
+//! in the file, or from another model declared earlier in the file. Column
+//! fields are typed as `<type> [<constraints>]`, e.g.
+//! `String(100) [nullable=False, unique]`; `relationship()` attributes are
+//! not columns and are skipped.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use tree_sitter::Node;
+
+use crate::extraction::types::{signature_for, Field, TypeDefinition, TypeKind};
+use crate::parser::{parse_code, Language};
+
+/// Keyword arguments reported as column constraints, in display order
+const CONSTRAINT_KEYWORDS: [&str; 5] = ["primary_key", "nullable", "unique", "index", "default"];
+
+/// SQLAlchemy models declared in a Python source file, in source order.
+///
+/// Each model is a [`TypeKind::Class`] definition whose `framework_metadata`
+/// holds `framework: sqlalchemy` and, when declared, the `table` name.
+pub fn extract_sqlalchemy_models(source: &str, file_path: &Path) -> Vec<TypeDefinition> {
+    let Ok(tree) = parse_code(source, Language::Python) else {
+        return Vec::new();
+    };
+
+    let source_bytes = source.as_bytes();
+    let mut bases: HashSet<String> = HashSet::from(["Base".to_string()]);
+    let mut models = Vec::new();
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        if let Some(name) = declarative_base_assignment(node, source) {
+            bases.insert(name.to_string());
+            continue;
+        }
+
+        let class = match node.kind() {
+            "class_definition" => node,
+            "decorated_definition" => match node.child_by_field_name("definition") {
+                Some(definition) if definition.kind() == "class_definition" => definition,
+                _ => continue,
+            },
+            _ => continue,
+        };
+        let Some(name) = class.child_by_field_name("name").map(|n| text(n, source)) else {
+            continue;
+        };
+        let superclasses = superclass_names(class, source);
+
+        // `class Base(DeclarativeBase)` declares a base, not a model.
+        if superclasses
+            .iter()
+            .any(|base| base.rsplit('.').next() == Some("DeclarativeBase"))
+        {
+            bases.insert(name.to_string());
+            continue;
+        }
+        if !superclasses.iter().any(|base| bases.contains(*base)) {
+            continue;
+        }
+
+        // Models are bases for their own subclasses (joined/single table inheritance).
+        bases.insert(name.to_string());
+
+        let (fields, table) = model_columns(class, source);
+        let mut metadata = BTreeMap::from([("framework".to_string(), "sqlalchemy".to_string())]);
+        if let Some(table) = table {
+            metadata.insert("table".to_string(), table);
+        }
+        models.push(TypeDefinition {
+            name: name.to_string(),
+            kind: TypeKind::Class,
+            file: file_path.to_path_buf(),
+            line: class.start_position().row + 1,
+            column: class.start_position().column + 1,
+            end_line: class.end_position().row + 1,
+            end_column: class.end_position().column + 1,
+            signature: signature_for(class, source_bytes),
+            usage_count: 0,
+            fields: (!fields.is_empty()).then_some(fields),
+            variants: None,
+            members: None,
+            generics: None,
+            where_bounds: None,
+            framework_metadata: Some(metadata),
+        });
+    }
+    models
+}
+
+/// Replace the generic class fields of SQLAlchemy models in `definitions`
+/// with their columns and merge in the model's framework metadata.
+pub(crate) fn annotate_sqlalchemy_models(
+    source: &str,
+    file_path: &Path,
+    definitions: &mut [TypeDefinition],
+) {
+    if !source.contains("sqlalchemy") {
+        return;
+    }
+
+    for model in extract_sqlalchemy_models(source, file_path) {
+        let Some(definition) = definitions
+            .iter_mut()
+            .find(|definition| definition.name == model.name && definition.line == model.line)
+        else {
+            continue;
+        };
+
+        definition.fields = model.fields;
+        definition
+            .framework_metadata
+            .get_or_insert_with(BTreeMap::new)
+            .extend(model.framework_metadata.unwrap_or_default());
+    }
+}
+
+/// `Base = declarative_base()` (or `registry().generate_base()`)
+fn declarative_base_assignment<'a>(node: Node, source: &'a str) -> Option<&'a str> {
+    let assignment = node
+        .named_child(0)
+        .filter(|_| node.kind() == "expression_statement")
+        .filter(|child| child.kind() == "assignment")?;
+    let left = assignment
+        .child_by_field_name("left")
+        .filter(|left| left.kind() == "identifier")?;
+    let right = assignment
+        .child_by_field_name("right")
+        .filter(|right| right.kind() == "call")?;
+    let function = text(right.child_by_field_name("function")?, source);
+    (function.ends_with("declarative_base") || function.ends_with("generate_base"))
+        .then(|| text(left, source))
+}
+
+fn superclass_names<'a>(class: Node, source: &'a str) -> Vec<&'a str> {
+    let Some(superclasses) = class.child_by_field_name("superclasses") else {
+        return Vec::new();
+    };
+    let mut cursor = superclasses.walk();
+    let names = superclasses
+        .named_children(&mut cursor)
+        .filter(|base| matches!(base.kind(), "identifier" | "attribute"))
+        .map(|base| text(base, source))
+        .collect();
+    names
+}
+
+/// Column fields and the `__tablename__` of a model class
+fn model_columns(class: Node, source: &str) -> (Vec<Field>, Option<String>) {
+    let mut fields = Vec::new();
+    let mut table = None;
+    let Some(body) = class.child_by_field_name("body") else {
+        return (fields, table);
+    };
+
+    let mut cursor = body.walk();
+    for statement in body
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "expression_statement")
+    {
+        let Some(assignment) = statement
+            .named_child(0)
+            .filter(|node| node.kind() == "assignment")
+        else {
+            continue;
+        };
+        let Some(name) = assignment
+            .child_by_field_name("left")
+            .filter(|left| left.kind() == "identifier")
+            .map(|left| text(left, source))
+        else {
+            continue;
+        };
+        let annotation = assignment.child_by_field_name("type");
+        let right = assignment.child_by_field_name("right");
+
+        if name == "__tablename__" {
+            table = right.map(|value| unquote(text(value, source)).to_string());
+            continue;
+        }
+
+        let mapped_type = annotation.and_then(|annotation| mapped_inner_type(annotation, source));
+        let column = match right {
+            Some(right) if right.kind() == "call" => {
+                let function = right
+                    .child_by_field_name("function")
+                    .map(|function| text(function, source))
+                    .unwrap_or_default();
+                match function.rsplit('.').next() {
+                    Some("Column" | "mapped_column") => column_type(right, source, mapped_type),
+                    _ => None,
+                }
+            }
+            // `name: Mapped[str]` declares a column in 2.x
+            None => mapped_type.map(str::to_string),
+            _ => None,
+        };
+
+        if let Some(type_annotation) = column {
+            fields.push(Field {
+                name: name.to_string(),
+                type_annotation,
+                resolved_to: None,
+            });
+        }
+    }
+    (fields, table)
+}
+
+/// `str` for `Mapped[str]`
+fn mapped_inner_type<'a>(annotation: Node, source: &'a str) -> Option<&'a str> {
+    let annotation = text(annotation, source).trim();
+    let inner = annotation
+        .strip_prefix("Mapped[")
+        .or_else(|| annotation.strip_prefix("orm.Mapped["))?
+        .strip_suffix(']')?;
+    Some(inner.trim())
+}
+
+/// `<type> [<constraints>]` for a `Column(...)` or `mapped_column(...)` call.
+///
+/// The SQL type comes from the first positional argument that is not the
+/// column name or a `ForeignKey`; `mapped_column()` without one falls back
+/// to the `Mapped[...]` annotation.
+fn column_type(call: Node, source: &str, mapped_type: Option<&str>) -> Option<String> {
+    let mut sql_type = None;
+    let mut foreign_key = None;
+    let mut keywords = BTreeMap::new();
+
+    if let Some(arguments) = call.child_by_field_name("arguments") {
+        let mut cursor = arguments.walk();
+        for argument in arguments.named_children(&mut cursor) {
+            match argument.kind() {
+                "keyword_argument" => {
+                    let key = argument
+                        .child_by_field_name("name")
+                        .map(|n| text(n, source));
+                    let value = argument.child_by_field_name("value");
+                    if let (Some(key), Some(value)) = (key, value) {
+                        keywords.insert(key, text(value, source));
+                    }
+                }
+                // Explicit column name: Column("user_name", String)
+                "string" => {}
+                "call" if is_foreign_key(argument, source) => {
+                    foreign_key = argument
+                        .child_by_field_name("arguments")
+                        .and_then(|arguments| arguments.named_child(0))
+                        .map(|target| unquote(text(target, source)).to_string());
+                }
+                _ if sql_type.is_none() => sql_type = Some(text(argument, source)),
+                _ => {}
+            }
+        }
+    }
+
+    let mut constraints = Vec::new();
+    for key in CONSTRAINT_KEYWORDS {
+        match keywords.get(key) {
+            Some(&"True") if key != "default" => constraints.push(key.to_string()),
+            Some(value) => constraints.push(format!("{key}={value}")),
+            None => {}
+        }
+    }
+    if let Some(target) = foreign_key {
+        constraints.push(format!("fk={target}"));
+    }
+
+    let column_type = sql_type.or(mapped_type).unwrap_or("Any");
+    if constraints.is_empty() {
+        Some(column_type.to_string())
+    } else {
+        Some(format!("{column_type} [{}]", constraints.join(", ")))
+    }
+}
+
+fn is_foreign_key(call: Node, source: &str) -> bool {
+    call.child_by_field_name("function")
+        .map(|function| text(function, source))
+        .and_then(|function| function.rsplit('.').next())
+        == Some("ForeignKey")
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim_matches(['\'', '"'])
+}
+
+fn text<'a>(node: Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
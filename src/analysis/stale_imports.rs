@@ -0,0 +1,398 @@
+//! Stale Imports Tool
+//!
+//! Finds imports whose names are never referenced in the rest of the file.
+//! Imports come from shape extraction; references come from the same
+//! identifier search `find_usages` uses, ignoring hits inside the import.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "h": "file|line|import|unused",
+//!   "stale": "src/lib.rs|3|use std::fmt;|fmt\n..."
+//! }
+//! ```
+//!
+//! Language rules: Rust `pub use` re-exports and JS/TS side-effect imports
+//! are never stale, Python names listed in `__all__` count as used, and glob
+//! imports are skipped because their names cannot be resolved.
+
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::analysis::find_usages::usages_in_tree;
+use crate::analysis::path_utils;
+use crate::analysis::shape::{extract_enhanced_shape, ImportInfo};
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{detect_language, parse_code, Language};
+
+const STALE_HEADER: &str = "file|line|import|unused";
+
+/// An import with at least one name that is never used in its file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleImport {
+    pub file: String,
+    pub line: usize,
+    pub import_text: String,
+    /// Imported names without any reference outside the import.
+    pub unused: Vec<String>,
+}
+
+pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    log::info!("Finding stale imports in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let stale = find_stale_imports(path)?;
+    let rows: Vec<String> = stale
+        .iter()
+        .map(|import| {
+            let line = import.line.to_string();
+            let unused = import.unused.join(",");
+            format::format_row(&[&import.file, &line, &import.import_text, &unused])
+        })
+        .collect();
+
+    let result = json!({
+        "h": STALE_HEADER,
+        "stale": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Find stale imports in a file or in every supported file of a directory.
+pub fn find_stale_imports(path: &Path) -> Result<Vec<StaleImport>, io::Error> {
+    let files = if path.is_file() {
+        vec![path.to_path_buf()]
+    } else {
+        collect_project_files(path)?
+    };
+
+    let mut stale = Vec::new();
+    for file in files {
+        let Ok(language) = detect_language(&file) else {
+            continue;
+        };
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        stale.extend(stale_imports_in_file(&file, &source, language)?);
+    }
+
+    Ok(stale)
+}
+
+fn stale_imports_in_file(
+    path: &Path,
+    source: &str,
+    language: Language,
+) -> Result<Vec<StaleImport>, io::Error> {
+    let file = path_utils::to_relative_path(&path.to_string_lossy());
+
+    Ok(import_references(path, source, language)?
+        .into_iter()
+        .filter(|import| !import.unused.is_empty())
+        .map(|import| StaleImport {
+            file: file.clone(),
+            line: import.line,
+            import_text: import.import_text,
+            unused: import.unused,
+        })
+        .collect())
+}
+
+/// An import statement and which of its bound names are referenced.
+pub(crate) struct ImportReferences {
+    pub(crate) line: usize,
+    /// Trimmed import statement
+    pub(crate) import_text: String,
+    /// Checkable bound names; empty for re-exports, side-effect imports and
+    /// imports that cannot be resolved.
+    pub(crate) names: Vec<String>,
+    /// Names of `names` without any reference outside the import.
+    pub(crate) unused: Vec<String>,
+}
+
+/// Every import of a file with its reference check, in source order.
+pub(crate) fn import_references(
+    path: &Path,
+    source: &str,
+    language: Language,
+) -> Result<Vec<ImportReferences>, io::Error> {
+    let tree = parse_code(source, language).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to parse {} code: {e}", language.name()),
+        )
+    })?;
+    let shape = extract_enhanced_shape(&tree, source, language, None, false)?;
+    let lines: Vec<&str> = source.lines().collect();
+    let exported = python_all_names(source, language);
+
+    let mut imports = Vec::new();
+    for import in &shape.imports {
+        let import_text = import_statement_text(import, &lines, language);
+        let first_line = import.line;
+        let last_line = first_line + import_text.lines().count().saturating_sub(1);
+
+        let names: Vec<String> = imported_names(&import_text, language)
+            .into_iter()
+            .filter(|name| !exported.contains(name))
+            .collect();
+        let unused: Vec<String> = names
+            .iter()
+            .filter(|name| {
+                usages_in_tree(&tree, source, language, path, name)
+                    .iter()
+                    .all(|usage| (first_line..=last_line).contains(&usage.line))
+            })
+            .cloned()
+            .collect();
+
+        imports.push(ImportReferences {
+            line: import.line,
+            import_text: import_text.trim().to_string(),
+            names,
+            unused,
+        });
+    }
+
+    Ok(imports)
+}
+
+/// Go shape imports only carry the quoted path; read the full spec line so
+/// aliases are not lost.
+fn import_statement_text(import: &ImportInfo, lines: &[&str], language: Language) -> String {
+    match language {
+        Language::Go => lines
+            .get(import.line.saturating_sub(1))
+            .map(|line| line.trim().trim_start_matches("import").trim().to_string())
+            .unwrap_or_else(|| import.text.clone()),
+        _ => import.text.clone(),
+    }
+}
+
+/// Names an import statement binds in the file's namespace.
+///
+/// Returns an empty list for imports that can never be stale (re-exports,
+/// side-effect imports) or cannot be resolved (globs, C# namespaces).
+fn imported_names(text: &str, language: Language) -> Vec<String> {
+    let text = text.trim();
+    match language {
+        Language::Rust => {
+            if text.starts_with("pub") {
+                return Vec::new();
+            }
+            let body = text
+                .trim_start_matches("use")
+                .trim()
+                .trim_end_matches(';')
+                .trim();
+            let mut names = Vec::new();
+            rust_use_names(body, "", &mut names);
+            names
+        }
+        Language::Python => {
+            if let Some(rest) = text.strip_prefix("from ") {
+                let Some((_, items)) = rest.split_once(" import ") else {
+                    return Vec::new();
+                };
+                let items = items.trim().trim_start_matches('(').trim_end_matches(')');
+                split_items(items)
+                    .filter(|item| *item != "*")
+                    .map(|item| alias_or_last(item, '.'))
+                    .collect()
+            } else {
+                let items = text.trim_start_matches("import").trim();
+                split_items(items)
+                    .map(|item| match item.split_once(" as ") {
+                        Some((_, alias)) => alias.trim().to_string(),
+                        None => item.split('.').next().unwrap_or(item).trim().to_string(),
+                    })
+                    .collect()
+            }
+        }
+        Language::JavaScript | Language::TypeScript | Language::Jsx | Language::Tsx => {
+            let Some((clause, _)) = text.split_once(" from ") else {
+                // `import "./side-effect.css";`
+                return Vec::new();
+            };
+            let clause = clause.trim_start_matches("import").trim();
+            let clause = clause.strip_prefix("type ").unwrap_or(clause);
+            let mut names = Vec::new();
+            let (default_part, named_part) = match clause.split_once('{') {
+                Some((before, after)) => (before, after.split('}').next().unwrap_or("")),
+                None => (clause, ""),
+            };
+            for item in split_items(default_part) {
+                if let Some(namespace) = item.strip_prefix("* as ") {
+                    names.push(namespace.trim().to_string());
+                } else {
+                    names.push(item.to_string());
+                }
+            }
+            for item in split_items(named_part) {
+                let item = item.strip_prefix("type ").unwrap_or(item);
+                names.push(alias_or_last(item, '.'));
+            }
+            names
+        }
+        Language::Go => {
+            let (alias, path) = match text.split_once('"') {
+                Some((alias, path)) => (alias.trim(), path.trim_end_matches('"')),
+                None => return Vec::new(),
+            };
+            match alias {
+                "_" | "." => Vec::new(),
+                "" => path
+                    .rsplit('/')
+                    .next()
+                    .map(|name| vec![name.to_string()])
+                    .unwrap_or_default(),
+                alias => vec![alias.to_string()],
+            }
+        }
+        Language::Java => {
+            let body = text
+                .trim_start_matches("import")
+                .trim()
+                .trim_start_matches("static ")
+                .trim_end_matches(';')
+                .trim();
+            if body.ends_with('*') {
+                return Vec::new();
+            }
+            vec![alias_or_last(body, '.')]
+        }
+        Language::CSharp => {
+            // Only `using Alias = Namespace.Type;` binds a checkable name.
+            let body = text
+                .trim_start_matches("using")
+                .trim()
+                .trim_end_matches(';');
+            match body.split_once('=') {
+                Some((alias, _)) => vec![alias.trim().to_string()],
+                None => Vec::new(),
+            }
+        }
+        Language::Swift | Language::Html | Language::Css | Language::Svelte => Vec::new(),
+    }
+}
+
+/// Expand a Rust use tree (`a::{b, c::d as e, self}`) into bound names.
+fn rust_use_names(tree: &str, parent: &str, names: &mut Vec<String>) {
+    let tree = tree.trim();
+    if let Some(open) = tree.find('{') {
+        let prefix = tree[..open].trim_end_matches("::");
+        let inner = tree[open + 1..].trim_end_matches('}');
+        let prefix_last = prefix.rsplit("::").next().unwrap_or(prefix);
+        for item in split_top_level(inner) {
+            rust_use_names(item, prefix_last, names);
+        }
+        return;
+    }
+
+    if tree.is_empty() || tree.ends_with('*') {
+        return;
+    }
+
+    let name = match tree.split_once(" as ") {
+        Some((_, alias)) => alias.trim(),
+        None => tree.rsplit("::").next().unwrap_or(tree).trim(),
+    };
+    match name {
+        "_" => {}
+        "self" if !parent.is_empty() => names.push(parent.to_string()),
+        "self" => {}
+        name => names.push(name.to_string()),
+    }
+}
+
+/// Split on commas that are not nested inside braces.
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, ch) in text.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(text[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(text[start..].trim());
+    parts.into_iter().filter(|part| !part.is_empty()).collect()
+}
+
+fn split_items(text: &str) -> impl Iterator<Item = &str> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+fn alias_or_last(item: &str, separator: char) -> String {
+    match item.split_once(" as ") {
+        Some((_, alias)) => alias.trim().to_string(),
+        None => item
+            .rsplit(separator)
+            .next()
+            .unwrap_or(item)
+            .trim()
+            .to_string(),
+    }
+}
+
+/// Quoted names inside a Python `__all__` list count as used.
+fn python_all_names(source: &str, language: Language) -> Vec<String> {
+    if language != Language::Python {
+        return Vec::new();
+    }
+    let Some(start) = source.find("__all__") else {
+        return Vec::new();
+    };
+    let rest = &source[start..];
+    let Some(open) = rest.find(['[', '(']) else {
+        return Vec::new();
+    };
+    let Some(close) = rest[open..].find([']', ')']) else {
+        return Vec::new();
+    };
+
+    rest[open + 1..open + close]
+        .split(',')
+        .map(|item| {
+            item.trim()
+                .trim_matches(|c| c == '"' || c == '\'')
+                .to_string()
+        })
+        .filter(|item| !item.is_empty())
+        .collect()
+}
@@ -0,0 +1,472 @@
+//! Svelte Component Tool
+//!
+//! Summarizes a `.svelte` single-file component. The template is parsed with
+//! the HTML grammar; each `<script>` block is parsed as JavaScript, or as
+//! TypeScript with `lang="ts"`, and each `<style>` block as CSS.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "file": "src/Counter.svelte",
+//!   "ph": "name|type|default|line",
+//!   "props": "count|number|0|4\nlabel|string||5",
+//!   "reactive": "doubled\nquadrupled",
+//!   "events": "on:click={increment}\non:submit|preventDefault={save}",
+//!   "slots": "default\nfooter",
+//!   "ih": "line|text",
+//!   "im": "2|import Button from './Button.svelte';",
+//!   "classes": "counter\nlabel"
+//! }
+//! ```
+//!
+//! Props are `export let` declarations (or the fields destructured from
+//! `$props()`), reactive variables are the names assigned by `$:`
+//! statements, and events are `on:` directives as written. An unnamed
+//! `<slot>` is listed as `default`. Lines are lines of the `.svelte` file.
+
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::analysis::path_utils;
+use crate::analysis::shape::ImportInfo;
+use crate::common::format;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{cache, parse_code, Language};
+
+const PROPS_HEADER: &str = "name|type|default|line";
+const IMPORTS_HEADER: &str = "line|text";
+
+/// A component prop
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropInfo {
+    pub name: String,
+    /// TypeScript annotation without the colon
+    pub type_annotation: Option<String>,
+    /// Initializer source text
+    pub default: Option<String>,
+    pub line: usize,
+}
+
+/// What a Svelte component exposes and reacts to
+#[derive(Debug, Clone, Default)]
+pub struct SvelteShape {
+    pub props: Vec<PropInfo>,
+    pub reactive_vars: Vec<String>,
+    /// `on:` directives with their handler (`on:click={increment}`)
+    pub event_handlers: Vec<String>,
+    pub slots: Vec<String>,
+    pub imports: Vec<ImportInfo>,
+    /// Class selectors defined in `<style>` blocks
+    pub style_classes: Vec<String>,
+}
+
+pub fn execute_svelte_shape(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let file_path = arguments["file_path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'file_path' argument",
+        )
+    })?;
+
+    log::info!("Extracting Svelte component shape: {file_path}");
+
+    let source = path_utils::read_source_file(file_path).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Failed to read file {file_path}: {e}"),
+        )
+    })?;
+
+    let parsed = cache::shared()
+        .parse(Path::new(file_path), &source, Language::Svelte)
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse Svelte template: {e}"),
+            )
+        })?;
+    let shape = extract_svelte_shape(parsed.0.root_node(), &source)?;
+
+    let props: Vec<String> = shape
+        .props
+        .iter()
+        .map(|prop| {
+            format::format_row(&[
+                &prop.name,
+                prop.type_annotation.as_deref().unwrap_or(""),
+                prop.default.as_deref().unwrap_or(""),
+                &prop.line.to_string(),
+            ])
+        })
+        .collect();
+    let imports: Vec<String> = shape
+        .imports
+        .iter()
+        .map(|import| format::format_row(&[&import.line.to_string(), &import.text]))
+        .collect();
+
+    let result = json!({
+        "file": path_utils::to_relative_path(file_path),
+        "ph": PROPS_HEADER,
+        "props": props.join("\n"),
+        "reactive": shape.reactive_vars.join("\n"),
+        "events": shape.event_handlers.join("\n"),
+        "slots": shape.slots.join("\n"),
+        "ih": IMPORTS_HEADER,
+        "im": imports.join("\n"),
+        "classes": shape.style_classes.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Shape of a component whose template (parsed with the HTML grammar) is
+/// rooted at `root`
+pub fn extract_svelte_shape(root: Node, source: &str) -> Result<SvelteShape, io::Error> {
+    let mut shape = SvelteShape::default();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "script_element" => {
+                if let Some(raw) = raw_text(node) {
+                    let language = if is_typescript(node, source) {
+                        Language::TypeScript
+                    } else {
+                        Language::JavaScript
+                    };
+                    collect_script(raw, source, language, &mut shape)?;
+                }
+                continue;
+            }
+            "style_element" => {
+                if let Some(raw) = raw_text(node) {
+                    for name in css_class_names(text(raw, source))? {
+                        if !shape.style_classes.contains(&name) {
+                            shape.style_classes.push(name);
+                        }
+                    }
+                }
+                continue;
+            }
+            "start_tag" | "self_closing_tag" => collect_tag(node, source, &mut shape),
+            _ => {}
+        }
+
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    Ok(shape)
+}
+
+/// Contents of a `<script>` or `<style>` element
+pub(crate) fn raw_text(element: Node) -> Option<Node> {
+    let mut cursor = element.walk();
+    let raw = element
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "raw_text");
+    raw
+}
+
+/// `<script lang="ts">` or `lang="typescript"`
+pub(crate) fn is_typescript(script: Node, source: &str) -> bool {
+    start_tag(script).is_some_and(|tag| {
+        matches!(
+            attribute_value(tag, source, "lang").as_deref(),
+            Some("ts" | "typescript")
+        )
+    })
+}
+
+pub(crate) fn start_tag(element: Node) -> Option<Node> {
+    let mut cursor = element.walk();
+    let tag = element
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "start_tag");
+    tag
+}
+
+/// The `attribute` node named `name` of a start tag
+fn attribute<'a>(tag: Node<'a>, source: &str, name: &str) -> Option<Node<'a>> {
+    let mut cursor = tag.walk();
+    let attributes: Vec<Node> = tag
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "attribute")
+        .collect();
+    attributes.into_iter().find(|attribute| {
+        attribute
+            .named_child(0)
+            .is_some_and(|attribute_name| text(attribute_name, source) == name)
+    })
+}
+
+/// Value of a plain `name="value"` attribute of a start tag
+pub(crate) fn attribute_value(tag: Node, source: &str, name: &str) -> Option<String> {
+    let value = attribute(tag, source, name)?.named_child(1)?;
+    Some(
+        text(value, source)
+            .trim_matches(|c| c == '"' || c == '\'')
+            .to_string(),
+    )
+}
+
+/// Whether a start tag carries attribute `name`, with or without a value
+/// (`<script setup>`, `<style scoped>`)
+pub(crate) fn has_attribute(tag: Node, source: &str, name: &str) -> bool {
+    attribute(tag, source, name).is_some()
+}
+
+fn collect_tag(tag: Node, source: &str, shape: &mut SvelteShape) {
+    let mut cursor = tag.walk();
+    for child in tag.named_children(&mut cursor) {
+        match child.kind() {
+            "tag_name" if text(child, source) == "slot" => {
+                let name = attribute_value(tag, source, "name");
+                shape
+                    .slots
+                    .push(name.unwrap_or_else(|| "default".to_string()));
+            }
+            "attribute" => {
+                let Some(name) = child.named_child(0) else {
+                    continue;
+                };
+                if text(name, source).starts_with("on:") {
+                    shape
+                        .event_handlers
+                        .push(directive_text(name, source).to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `on:click={...}` as written. The HTML grammar does not know Svelte's
+/// `{...}` values and can split `{() => n++}` at the `>`, so the value is
+/// read from the source with balanced braces.
+fn directive_text<'a>(name: Node, source: &'a str) -> &'a str {
+    let start = name.start_byte();
+    let rest = &source[name.end_byte()..];
+    let Some(value) = rest.trim_start().strip_prefix('=') else {
+        return text(name, source);
+    };
+    let value = value.trim_start();
+    let value_start = source.len() - value.len();
+
+    let value_len = match value.chars().next() {
+        Some('{') => {
+            let mut depth = 0;
+            value.char_indices().find_map(|(i, c)| {
+                match c {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+                (depth == 0).then_some(i + 1)
+            })
+        }
+        Some(quote @ ('"' | '\'')) => value[1..].find(quote).map(|end| end + 2),
+        _ => value.find(|c: char| c.is_whitespace() || c == '>' || c == '/'),
+    }
+    .unwrap_or(value.len());
+    &source[start..value_start + value_len]
+}
+
+fn collect_script(
+    raw: Node,
+    source: &str,
+    language: Language,
+    shape: &mut SvelteShape,
+) -> Result<(), io::Error> {
+    let script = text(raw, source);
+    let line_offset = raw.start_position().row;
+    let tree = parse_code(script, language).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to parse <script> block as {}: {e}", language.name()),
+        )
+    })?;
+
+    let mut cursor = tree.root_node().walk();
+    for statement in tree.root_node().named_children(&mut cursor) {
+        let line = line_offset + statement.start_position().row + 1;
+        match statement.kind() {
+            "import_statement" => shape.imports.push(ImportInfo {
+                text: text(statement, script).to_string(),
+                line,
+            }),
+            "export_statement" => {
+                if let Some(declaration) = statement
+                    .child_by_field_name("declaration")
+                    .filter(|d| d.kind() == "lexical_declaration" && is_let(*d, script))
+                {
+                    collect_declared_props(declaration, script, line_offset, shape);
+                }
+            }
+            "lexical_declaration" => collect_rune_props(statement, script, line_offset, shape),
+            "labeled_statement" => {
+                let is_reactive = statement
+                    .child_by_field_name("label")
+                    .is_some_and(|label| text(label, script) == "$");
+                if let Some(name) = is_reactive
+                    .then(|| reactive_assignment(statement, script))
+                    .flatten()
+                {
+                    if !shape.reactive_vars.contains(&name) {
+                        shape.reactive_vars.push(name);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn is_let(declaration: Node, script: &str) -> bool {
+    declaration
+        .child(0)
+        .is_some_and(|keyword| text(keyword, script) == "let")
+}
+
+/// `export let count: number = 0, label;`
+fn collect_declared_props(
+    declaration: Node,
+    script: &str,
+    line_offset: usize,
+    shape: &mut SvelteShape,
+) {
+    let mut cursor = declaration.walk();
+    for declarator in declaration.named_children(&mut cursor) {
+        if declarator.kind() != "variable_declarator" {
+            continue;
+        }
+        let Some(name) = declarator
+            .child_by_field_name("name")
+            .filter(|name| name.kind() == "identifier")
+        else {
+            continue;
+        };
+        shape.props.push(PropInfo {
+            name: text(name, script).to_string(),
+            type_annotation: declarator
+                .child_by_field_name("type")
+                .map(|ty| type_text(ty, script)),
+            default: declarator
+                .child_by_field_name("value")
+                .map(|value| text(value, script).to_string()),
+            line: line_offset + declarator.start_position().row + 1,
+        });
+    }
+}
+
+/// Svelte 5: `let { count = 0, label } = $props();`
+fn collect_rune_props(
+    declaration: Node,
+    script: &str,
+    line_offset: usize,
+    shape: &mut SvelteShape,
+) {
+    let mut cursor = declaration.walk();
+    for declarator in declaration.named_children(&mut cursor) {
+        let is_props_call = declarator
+            .child_by_field_name("value")
+            .filter(|value| value.kind() == "call_expression")
+            .and_then(|call| call.child_by_field_name("function"))
+            .is_some_and(|function| text(function, script) == "$props");
+        let Some(pattern) = declarator
+            .child_by_field_name("name")
+            .filter(|name| is_props_call && name.kind() == "object_pattern")
+        else {
+            continue;
+        };
+
+        let mut pattern_cursor = pattern.walk();
+        for field in pattern.named_children(&mut pattern_cursor) {
+            let (name, default) = match field.kind() {
+                "shorthand_property_identifier_pattern" => (Some(field), None),
+                "object_assignment_pattern" => (
+                    field.child_by_field_name("left"),
+                    field.child_by_field_name("right"),
+                ),
+                "pair_pattern" => (field.child_by_field_name("key"), None),
+                _ => continue,
+            };
+            let Some(name) = name else {
+                continue;
+            };
+            shape.props.push(PropInfo {
+                name: text(name, script).to_string(),
+                type_annotation: None,
+                default: default.map(|value| text(value, script).to_string()),
+                line: line_offset + field.start_position().row + 1,
+            });
+        }
+    }
+}
+
+/// Name assigned by `$: doubled = count * 2;`
+fn reactive_assignment(statement: Node, script: &str) -> Option<String> {
+    let body = statement.child_by_field_name("body")?;
+    let expression = if body.kind() == "expression_statement" {
+        body.named_child(0)?
+    } else {
+        body
+    };
+    let left = expression
+        .child_by_field_name("left")
+        .filter(|_| expression.kind() == "assignment_expression")?;
+    (left.kind() == "identifier").then(|| text(left, script).to_string())
+}
+
+/// Class selectors of a stylesheet, each once, in source order
+pub(crate) fn css_class_names(css: &str) -> Result<Vec<String>, io::Error> {
+    let tree = parse_code(css, Language::Css).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to parse <style> block: {e}"),
+        )
+    })?;
+
+    let mut names: Vec<String> = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "class_selector" {
+            if let Some(name) = node
+                .named_children(&mut node.walk())
+                .find(|child| child.kind() == "class_name")
+            {
+                let name = text(name, css).to_string();
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    Ok(names)
+}
+
+/// `: number` type annotation without the colon
+pub(crate) fn type_text(type_annotation: Node, script: &str) -> String {
+    text(type_annotation, script)
+        .trim_start_matches(':')
+        .trim()
+        .to_string()
+}
+
+pub(crate) fn text<'a>(node: Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
@@ -3,12 +3,14 @@
 //! Find what symbol (function/class) is at a specific line with signature and scope chain.
 //! Merges functionality from get_context and get_node_at_position.
 
+use crate::analysis::node_kind::classify_node;
+use crate::analysis::path_utils;
 use crate::mcp_types::{CallToolResult, CallToolResultExt};
-use crate::parser::{detect_language, parse_code, Language};
+use crate::parser::{cache, detect_language, Language};
 use serde_json::json;
 use serde_json::Value;
-use std::fs;
 use std::io;
+use std::path::Path;
 use tree_sitter::Node;
 
 #[derive(Debug)]
@@ -18,6 +20,33 @@ struct ScopeInfo {
     kind: String,
 }
 
+/// Which node the symbol lookup reports on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    /// Innermost named scope around the position
+    Leaf,
+    /// Nearest enclosing function or method, returned with its full text
+    EnclosingFunction,
+    /// Nearest enclosing class-like type, returned with its full text
+    EnclosingClass,
+}
+
+impl Scope {
+    fn parse(value: Option<&str>) -> Result<Self, io::Error> {
+        match value {
+            None | Some("leaf") => Ok(Scope::Leaf),
+            Some("enclosing_function") => Ok(Scope::EnclosingFunction),
+            Some("enclosing_class") => Ok(Scope::EnclosingClass),
+            Some(other) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Invalid 'scope' argument: {other}. Expected 'leaf', 'enclosing_function' or 'enclosing_class'"
+                ),
+            )),
+        }
+    }
+}
+
 /// Execute the symbol_at_line tool
 ///
 /// # Arguments
@@ -25,6 +54,8 @@ struct ScopeInfo {
 ///   - `file_path`: String - Path to the source file
 ///   - `line`: u32 - 1-indexed line number
 ///   - `column`: Option<u32> - 1-indexed column number (default: 1)
+///   - `scope`: Option<String> - "leaf" (default), "enclosing_function" or "enclosing_class"
+///   - `max_lines`: Option<usize> - Cap on the lines of code returned for enclosing scopes
 ///
 /// # Returns
 /// Returns a `CallToolResult` with JSON containing symbol info and scope chain
@@ -44,10 +75,12 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
     })? as u32;
 
     let column = arguments["column"].as_u64().map(|c| c as u32).unwrap_or(1);
+    let scope_mode = Scope::parse(arguments["scope"].as_str())?;
+    let max_lines = arguments["max_lines"].as_u64().map(|n| n as usize);
 
-    log::info!("Getting symbol at {file_path}:{line}:{column}");
+    log::info!("Getting symbol at {file_path}:{line}:{column} (scope: {scope_mode:?})");
 
-    let source = fs::read_to_string(file_path).map_err(|e| {
+    let source = path_utils::read_source_file(file_path).map_err(|e| {
         io::Error::new(
             io::ErrorKind::NotFound,
             format!("Failed to read file {file_path}: {e}"),
@@ -61,12 +94,15 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
         )
     })?;
 
-    let tree = parse_code(&source, language).map_err(|e| {
-        io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("Failed to parse {} code: {e}", language.name()),
-        )
-    })?;
+    let parsed = cache::shared()
+        .parse(Path::new(file_path), &source, language)
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse {} code: {e}", language.name()),
+            )
+        })?;
+    let tree = parsed.0.clone();
 
     // Convert 1-indexed line/column to 0-indexed for tree-sitter
     let ts_line = if line > 0 { (line - 1) as usize } else { 0 };
@@ -80,6 +116,22 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
         )
     })?;
 
+    let node = match scope_mode {
+        Scope::Leaf => node,
+        Scope::EnclosingFunction => find_enclosing(node, is_function_node).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No enclosing function found at line {line}, column {column}"),
+            )
+        })?,
+        Scope::EnclosingClass => find_enclosing(node, is_class_node).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No enclosing class found at line {line}, column {column}"),
+            )
+        })?,
+    };
+
     // Build scope chain - this will walk up from the node to find context nodes
     let scope_chain = collect_scope_chain(node, &source, language);
 
@@ -100,14 +152,30 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
         .collect::<Vec<_>>()
         .join("::");
 
-    let output = json!({
+    let mut output = json!({
         "sym": innermost.name,
         "kind": abbreviate_kind(&innermost.kind),
         "sig": innermost.signature,
         "l": line,
         "scope": scope,
+        "node": node.kind(),
+        "nk": classify_node(node.kind(), language).as_str(),
     });
 
+    if scope_mode != Scope::Leaf {
+        let text = node.utf8_text(source.as_bytes()).unwrap_or("");
+        let (code, truncated) = cap_lines(text, max_lines);
+        output["code"] = json!(code);
+        output["range"] = json!(format!(
+            "{}-{}",
+            node.start_position().row + 1,
+            node.end_position().row + 1
+        ));
+        if truncated {
+            output["@"] = json!({"t": true});
+        }
+    }
+
     let output_json = serde_json::to_string(&output).map_err(|e| {
         io::Error::new(
             io::ErrorKind::InvalidData,
@@ -141,6 +209,56 @@ fn find_node_at_position(tree: &tree_sitter::Tree, line: usize, column: usize) -
     Some(node)
 }
 
+/// Walk up from `node` (inclusive) to the first ancestor matching `predicate`
+fn find_enclosing<'a>(mut node: Node<'a>, predicate: fn(&str) -> bool) -> Option<Node<'a>> {
+    loop {
+        if predicate(node.kind()) {
+            return Some(node);
+        }
+        node = node.parent()?;
+    }
+}
+
+/// Function and method nodes across supported grammars
+fn is_function_node(node_type: &str) -> bool {
+    matches!(
+        node_type,
+        "function_item"
+            | "function_definition"
+            | "function_declaration"
+            | "method_definition"
+            | "method_declaration"
+            | "constructor_declaration"
+    )
+}
+
+/// Class-like type nodes across supported grammars
+fn is_class_node(node_type: &str) -> bool {
+    matches!(
+        node_type,
+        "class_definition"
+            | "class_declaration"
+            | "impl_item"
+            | "trait_item"
+            | "struct_item"
+            | "enum_item"
+            | "struct_declaration"
+            | "interface_declaration"
+            | "enum_declaration"
+            | "protocol_declaration"
+    )
+}
+
+/// Keep at most `max_lines` lines of `text`, reporting whether any were dropped
+fn cap_lines(text: &str, max_lines: Option<usize>) -> (String, bool) {
+    match max_lines {
+        Some(max) if text.lines().count() > max => {
+            (text.lines().take(max).collect::<Vec<_>>().join("\n"), true)
+        }
+        _ => (text.to_string(), false),
+    }
+}
+
 /// Check if a position is within a range
 fn is_position_in_range(
     line: usize,
@@ -194,7 +312,7 @@ fn is_context_node(node_type: &str, language: Language) -> bool {
             node_type,
             "function_definition" | "class_definition" | "module"
         ),
-        Language::JavaScript | Language::TypeScript => matches!(
+        Language::JavaScript | Language::TypeScript | Language::Jsx | Language::Tsx => matches!(
             node_type,
             "function_declaration"
                 | "method_definition"
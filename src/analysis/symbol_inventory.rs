@@ -0,0 +1,652 @@
+//! Project Inventory Tool
+//!
+//! Lists every named symbol in a project as four flat tables: functions,
+//! types, constants and imports. Each entry carries its file, line, language
+//! and visibility so agents get a bird's-eye view without reading code.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "by": "file",
+//!   "h": "kind|name|line|lang|vis",
+//!   "g": { "src/lib.rs": "fn|run|12|Rust|public\n..." }
+//! }
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Map, Value};
+use tiktoken_rs::cl100k_base;
+use tree_sitter::Node;
+
+use crate::analysis::path_utils;
+use crate::analysis::shape::extract_enhanced_shape;
+use crate::common::budget;
+use crate::common::budget::BudgetTracker;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::extraction::types::extract_types_for_language;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{detect_language, parse_code, Language};
+
+/// A single named symbol in the inventory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolEntry {
+    pub name: String,
+    pub file: String,
+    pub line: usize,
+    pub language: String,
+    pub visibility: String,
+}
+
+/// All symbols of a project, split by kind.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolInventory {
+    pub functions: Vec<SymbolEntry>,
+    pub types: Vec<SymbolEntry>,
+    pub constants: Vec<SymbolEntry>,
+    pub imports: Vec<SymbolEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GroupBy {
+    File,
+    Kind,
+    Language,
+}
+
+impl GroupBy {
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "file" => Some(GroupBy::File),
+            "kind" => Some(GroupBy::Kind),
+            "language" => Some(GroupBy::Language),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            GroupBy::File => "file",
+            GroupBy::Kind => "kind",
+            GroupBy::Language => "language",
+        }
+    }
+
+    fn header(self) -> &'static str {
+        match self {
+            GroupBy::File => "kind|name|line|lang|vis",
+            GroupBy::Kind => "name|file|line|lang|vis",
+            GroupBy::Language => "kind|name|file|line|vis",
+        }
+    }
+}
+
+pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    let group_by_str = arguments["group_by"].as_str().unwrap_or("file");
+    let group_by = GroupBy::from_str(group_by_str).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "Invalid 'group_by' argument: {group_by_str} (expected file, kind or language)"
+            ),
+        )
+    })?;
+    let max_tokens = arguments["max_tokens"].as_i64().unwrap_or(2000) as usize;
+
+    log::info!("Building symbol inventory for: {path_str} (group_by: {group_by_str})");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let inventory = build_inventory(path);
+    let rows = grouped_rows(&inventory, group_by);
+    let (groups, truncated) = fit_to_budget(&rows, group_by, max_tokens)?;
+
+    let mut result = json!({
+        "by": group_by.as_str(),
+        "h": group_by.header(),
+        "g": groups,
+    });
+    if truncated {
+        result["@"] = json!({"t": true});
+    }
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize inventory to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Collect functions, types, constants and imports for a file or directory.
+///
+/// Files that cannot be read or parsed are skipped.
+pub fn build_inventory(path: &Path) -> SymbolInventory {
+    let mut inventory = SymbolInventory::default();
+
+    let files = if path.is_file() {
+        vec![path.to_path_buf()]
+    } else {
+        collect_project_files(path).unwrap_or_default()
+    };
+
+    for file in files {
+        let Ok(language) = detect_language(&file) else {
+            continue;
+        };
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        add_file_symbols(&mut inventory, &file, &source, language);
+    }
+
+    for table in [
+        &mut inventory.functions,
+        &mut inventory.types,
+        &mut inventory.constants,
+        &mut inventory.imports,
+    ] {
+        table.sort_by(|a, b| {
+            a.file
+                .cmp(&b.file)
+                .then_with(|| a.line.cmp(&b.line))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+    }
+
+    inventory
+}
+
+fn add_file_symbols(
+    inventory: &mut SymbolInventory,
+    path: &Path,
+    source: &str,
+    language: Language,
+) {
+    let Ok(tree) = parse_code(source, language) else {
+        return;
+    };
+    let file = path_utils::to_relative_path(&path.to_string_lossy());
+    let lines: Vec<&str> = source.lines().collect();
+
+    let entry = |name: &str, line: usize, visibility: &str| SymbolEntry {
+        name: name.to_string(),
+        file: file.clone(),
+        line,
+        language: language.name().to_string(),
+        visibility: visibility.to_string(),
+    };
+
+    if let Ok(shape) = extract_enhanced_shape(&tree, source, language, None, false) {
+        let mut seen: HashSet<(String, usize)> = HashSet::new();
+        let mut push_function = |name: &str, line: usize, is_member: bool, forced: Option<&str>| {
+            if !seen.insert((name.to_string(), line)) {
+                return;
+            }
+            let visibility = forced.unwrap_or_else(|| {
+                visibility_of(
+                    language,
+                    name,
+                    &declaration_text(&lines, line, name),
+                    is_member,
+                )
+            });
+            inventory.functions.push(entry(name, line, visibility));
+        };
+
+        for function in shape.functions.iter().chain(&shape.components) {
+            push_function(&function.name, function.line, false, None);
+        }
+        for class in &shape.classes {
+            for method in &class.methods {
+                push_function(&method.name, method.line, true, None);
+            }
+        }
+        for block in &shape.impl_blocks {
+            // Trait impl methods inherit the trait's visibility.
+            let forced = block.trait_name.as_ref().map(|_| "public");
+            for method in &block.methods {
+                push_function(&method.name, method.line, true, forced);
+            }
+        }
+
+        for import in &shape.imports {
+            let visibility = import_visibility(language, &import.text);
+            inventory
+                .imports
+                .push(entry(import.text.trim(), import.line, visibility));
+        }
+
+        if language == Language::Swift {
+            let declared = shape
+                .structs
+                .iter()
+                .map(|s| (&s.name, s.line))
+                .chain(shape.classes.iter().map(|c| (&c.name, c.line)));
+            for (name, line) in declared {
+                let visibility =
+                    visibility_of(language, name, &declaration_text(&lines, line, name), false);
+                inventory.types.push(entry(name, line, visibility));
+            }
+        }
+    }
+
+    if let Ok(types) = extract_types_for_language(source, path, language) {
+        for ty in types {
+            let visibility = visibility_of(
+                language,
+                &ty.name,
+                &declaration_text(&lines, ty.line, &ty.name),
+                false,
+            );
+            inventory.types.push(entry(&ty.name, ty.line, visibility));
+        }
+    }
+
+    let mut constants = Vec::new();
+    collect_constants(tree.root_node(), source, language, true, &mut constants);
+    for (name, line) in constants {
+        let visibility = visibility_of(
+            language,
+            &name,
+            &declaration_text(&lines, line, &name),
+            false,
+        );
+        inventory.constants.push(entry(&name, line, visibility));
+    }
+}
+
+/// Walk the tree for constant declarations.
+///
+/// Rust, Go, Python and JS/TS constants are module-level; Java and C#
+/// constants live in class bodies, so those are searched recursively.
+fn collect_constants(
+    node: Node,
+    source: &str,
+    language: Language,
+    top_level: bool,
+    out: &mut Vec<(String, usize)>,
+) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        let line = child.start_position().row + 1;
+        match (language, child.kind()) {
+            (Language::Rust, "const_item" | "static_item") => {
+                if let Some(name) = field_text(child, "name", source) {
+                    out.push((name, line));
+                }
+            }
+            (Language::Go, "const_declaration") => {
+                let mut spec_cursor = child.walk();
+                for spec in child.children(&mut spec_cursor) {
+                    if spec.kind() != "const_spec" {
+                        continue;
+                    }
+                    let mut name_cursor = spec.walk();
+                    for name in spec.children_by_field_name("name", &mut name_cursor) {
+                        if let Ok(text) = name.utf8_text(source.as_bytes()) {
+                            out.push((text.to_string(), name.start_position().row + 1));
+                        }
+                    }
+                }
+            }
+            (Language::Python, "expression_statement") if top_level => {
+                if let Some(assignment) = child.named_child(0) {
+                    if assignment.kind() == "assignment" {
+                        if let Some(name) = field_text(assignment, "left", source) {
+                            if is_constant_case(&name) {
+                                out.push((name, line));
+                            }
+                        }
+                    }
+                }
+            }
+            (
+                Language::JavaScript | Language::TypeScript | Language::Jsx | Language::Tsx,
+                "export_statement",
+            ) if top_level => {
+                collect_constants(child, source, language, true, out);
+            }
+            (
+                Language::JavaScript | Language::TypeScript | Language::Jsx | Language::Tsx,
+                "lexical_declaration",
+            ) if top_level => {
+                let is_const = child
+                    .child(0)
+                    .is_some_and(|keyword| keyword.kind() == "const");
+                if !is_const {
+                    continue;
+                }
+                let mut decl_cursor = child.walk();
+                for declarator in child.named_children(&mut decl_cursor) {
+                    if let Some(name) = field_text(declarator, "name", source) {
+                        if is_constant_case(&name) {
+                            out.push((name, declarator.start_position().row + 1));
+                        }
+                    }
+                }
+            }
+            (Language::Java | Language::CSharp, "field_declaration") => {
+                let text = child.utf8_text(source.as_bytes()).unwrap_or("");
+                let head = text.split('=').next().unwrap_or(text);
+                let is_constant = match language {
+                    Language::Java => has_keyword(head, "static") && has_keyword(head, "final"),
+                    _ => has_keyword(head, "const"),
+                };
+                if is_constant {
+                    collect_declarator_names(child, source, out);
+                }
+            }
+            (Language::Java | Language::CSharp, _) => {
+                collect_constants(child, source, language, false, out);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn collect_declarator_names(node: Node, source: &str, out: &mut Vec<(String, usize)>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if child.kind() == "variable_declarator" {
+            let name = child
+                .child_by_field_name("name")
+                .or_else(|| {
+                    let mut inner = child.walk();
+                    let found = child
+                        .named_children(&mut inner)
+                        .find(|n| n.kind() == "identifier");
+                    found
+                })
+                .and_then(|n| n.utf8_text(source.as_bytes()).ok());
+            if let Some(name) = name {
+                out.push((name.to_string(), child.start_position().row + 1));
+            }
+        } else {
+            collect_declarator_names(child, source, out);
+        }
+    }
+}
+
+fn field_text(node: Node, field: &str, source: &str) -> Option<String> {
+    let child = node.child_by_field_name(field)?;
+    if child.kind() != "identifier" {
+        return None;
+    }
+    child
+        .utf8_text(source.as_bytes())
+        .ok()
+        .map(ToString::to_string)
+}
+
+fn is_constant_case(name: &str) -> bool {
+    name.chars().any(|c| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Source text from the declaration line up to the line naming the symbol.
+///
+/// Annotations and decorators can push the modifiers onto a later line, so a
+/// few lines are joined.
+pub(crate) fn declaration_text(lines: &[&str], line: usize, name: &str) -> String {
+    let start = line.saturating_sub(1);
+    let mut text = String::new();
+    for current in lines.iter().skip(start).take(5) {
+        text.push_str(current);
+        text.push(' ');
+        if current.contains(name) {
+            break;
+        }
+    }
+    text
+}
+
+fn has_keyword(text: &str, keyword: &str) -> bool {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .any(|token| token == keyword)
+}
+
+pub(crate) fn visibility_of(
+    language: Language,
+    name: &str,
+    declaration: &str,
+    is_member: bool,
+) -> &'static str {
+    match language {
+        Language::Rust => {
+            let trimmed = declaration.trim_start();
+            if trimmed.contains("pub(crate)")
+                || trimmed.contains("pub(super)")
+                || trimmed.contains("pub(in ")
+            {
+                "crate"
+            } else if has_keyword(declaration, "pub") {
+                "public"
+            } else {
+                "private"
+            }
+        }
+        Language::Python => {
+            if name.starts_with('_') && !(name.starts_with("__") && name.ends_with("__")) {
+                "private"
+            } else {
+                "public"
+            }
+        }
+        Language::Go => {
+            if name.chars().next().is_some_and(char::is_uppercase) {
+                "public"
+            } else {
+                "private"
+            }
+        }
+        Language::Java => {
+            if has_keyword(declaration, "public") {
+                "public"
+            } else if has_keyword(declaration, "protected") {
+                "protected"
+            } else if has_keyword(declaration, "private") {
+                "private"
+            } else {
+                "package"
+            }
+        }
+        Language::CSharp => {
+            if has_keyword(declaration, "public") {
+                "public"
+            } else if has_keyword(declaration, "protected") {
+                "protected"
+            } else if has_keyword(declaration, "private") {
+                "private"
+            } else if has_keyword(declaration, "internal") || !is_member {
+                "internal"
+            } else {
+                "private"
+            }
+        }
+        Language::JavaScript | Language::TypeScript | Language::Jsx | Language::Tsx => {
+            if is_member {
+                if name.starts_with('#') || has_keyword(declaration, "private") {
+                    "private"
+                } else if has_keyword(declaration, "protected") {
+                    "protected"
+                } else {
+                    "public"
+                }
+            } else if has_keyword(declaration, "export") {
+                "public"
+            } else {
+                "private"
+            }
+        }
+        Language::Swift => {
+            if has_keyword(declaration, "public") || has_keyword(declaration, "open") {
+                "public"
+            } else if has_keyword(declaration, "private") || has_keyword(declaration, "fileprivate")
+            {
+                "private"
+            } else {
+                "internal"
+            }
+        }
+        Language::Html | Language::Css | Language::Svelte => "public",
+    }
+}
+
+fn import_visibility(language: Language, text: &str) -> &'static str {
+    let trimmed = text.trim_start();
+    match language {
+        Language::Rust if trimmed.starts_with("pub") => "public",
+        Language::JavaScript | Language::TypeScript | Language::Jsx | Language::Tsx
+            if trimmed.starts_with("export") =>
+        {
+            "public"
+        }
+        _ => "private",
+    }
+}
+
+/// Rows in output order, keyed by their group.
+fn grouped_rows(inventory: &SymbolInventory, group_by: GroupBy) -> Vec<(String, String)> {
+    let tables = [
+        ("fn", "functions", &inventory.functions),
+        ("type", "types", &inventory.types),
+        ("const", "constants", &inventory.constants),
+        ("import", "imports", &inventory.imports),
+    ];
+
+    match group_by {
+        GroupBy::Kind => tables
+            .iter()
+            .flat_map(|(_, group, entries)| {
+                entries.iter().map(move |e| {
+                    let line = e.line.to_string();
+                    let row =
+                        format::format_row(&[&e.name, &e.file, &line, &e.language, &e.visibility]);
+                    (group.to_string(), row)
+                })
+            })
+            .collect(),
+        GroupBy::File | GroupBy::Language => {
+            let mut grouped: BTreeMap<String, Vec<(&str, &SymbolEntry)>> = BTreeMap::new();
+            for (kind, _, entries) in &tables {
+                for e in entries.iter() {
+                    let key = match group_by {
+                        GroupBy::File => e.file.clone(),
+                        _ => e.language.clone(),
+                    };
+                    grouped.entry(key).or_default().push((kind, e));
+                }
+            }
+
+            let mut rows = Vec::new();
+            for (key, mut entries) in grouped {
+                entries.sort_by(|(_, a), (_, b)| {
+                    a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line))
+                });
+                for (kind, e) in entries {
+                    let line = e.line.to_string();
+                    let row = if group_by == GroupBy::File {
+                        format::format_row(&[kind, &e.name, &line, &e.language, &e.visibility])
+                    } else {
+                        format::format_row(&[kind, &e.name, &e.file, &line, &e.visibility])
+                    };
+                    rows.push((key.clone(), row));
+                }
+            }
+            rows
+        }
+    }
+}
+
+fn rows_to_groups(rows: &[(String, String)]) -> Map<String, Value> {
+    let mut groups: Map<String, Value> = Map::new();
+    let mut current: Option<(&str, Vec<&str>)> = None;
+
+    for (group, row) in rows {
+        match current.as_mut() {
+            Some((name, group_rows)) if *name == group.as_str() => group_rows.push(row),
+            _ => {
+                if let Some((name, group_rows)) = current.take() {
+                    groups.insert(name.to_string(), json!(group_rows.join("\n")));
+                }
+                current = Some((group.as_str(), vec![row.as_str()]));
+            }
+        }
+    }
+    if let Some((name, group_rows)) = current {
+        groups.insert(name.to_string(), json!(group_rows.join("\n")));
+    }
+
+    groups
+}
+
+/// Keep as many leading rows as fit in `max_tokens`, mirroring `code_map`:
+/// a cheap estimate first, then hard enforcement with real token counts.
+fn fit_to_budget(
+    rows: &[(String, String)],
+    group_by: GroupBy,
+    max_tokens: usize,
+) -> Result<(Map<String, Value>, bool), io::Error> {
+    let bpe = cl100k_base()
+        .map_err(|e| io::Error::other(format!("Failed to initialize tiktoken tokenizer: {e}")))?;
+
+    // 10% buffer for conservative estimate.
+    let mut tracker = BudgetTracker::new((max_tokens * 9) / 10);
+    let mut kept = 0;
+    for (group, row) in rows {
+        let estimated = budget::estimate_symbol_tokens(group.len() + row.len());
+        if !tracker.add(estimated) {
+            break;
+        }
+        kept += 1;
+    }
+
+    let mut truncated = kept < rows.len();
+
+    loop {
+        let groups = rows_to_groups(&rows[..kept]);
+        let mut candidate = json!({
+            "by": group_by.as_str(),
+            "h": group_by.header(),
+            "g": groups,
+        });
+        if truncated {
+            candidate["@"] = json!({"t": true});
+        }
+
+        let candidate_json = serde_json::to_string(&candidate).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to serialize inventory to JSON: {e}"),
+            )
+        })?;
+
+        if kept == 0 || bpe.encode_with_special_tokens(&candidate_json).len() <= max_tokens {
+            return Ok((rows_to_groups(&rows[..kept]), truncated));
+        }
+
+        kept -= 1;
+        truncated = true;
+    }
+}
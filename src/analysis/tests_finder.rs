@@ -0,0 +1,461 @@
+//! Test Finder Tool
+//!
+//! Lists test functions of a file or directory and tells unit tests,
+//! integration tests and benchmarks apart.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "h": "name|kind|framework|file|line",
+//!   "tests": "test_add|unit|libtest|src/lib.rs|42\nCalculator > adds numbers|unit|jest|calculator.test.js|3\n..."
+//! }
+//! ```
+//!
+//! Recognized tests:
+//! - Rust: `#[test]` (`libtest`), `#[tokio::test]` and other `#[<runtime>::test]`
+//!   (framework `<runtime>`), `#[bench]` and functions taking a `Criterion`
+//!   (`criterion`)
+//! - Python: `test_*` methods of `unittest.TestCase` subclasses (`unittest`),
+//!   `test_*` functions and methods of `Test*` classes (`pytest`)
+//! - JavaScript/TypeScript: `it`/`test`/`specify` calls, named after their
+//!   enclosing `describe`/`context`/`suite` titles joined with ` > `;
+//!   framework `vitest`, `node:test` or `mocha` when the file mentions it,
+//!   `jest` otherwise
+//! - Java: methods annotated `@Test`, `@ParameterizedTest`, `@RepeatedTest`
+//!   or `@TestFactory` (`junit5` with `org.junit.jupiter` imports, `testng`,
+//!   else `junit4`)
+//! - Go: `TestXxx(t *testing.T)` and `BenchmarkXxx(b *testing.B)` (`go-test`)
+//!
+//! Methods are named `Class.method`. `kind` is `benchmark` for benchmarks,
+//! `integration` when the file's path below the searched directory contains
+//! `integration` or an `it/` directory, `unit` otherwise.
+
+use std::io;
+use std::path::{Component, Path};
+
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::analysis::path_utils;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{detect_language, parse_code, Language};
+
+const TESTS_HEADER: &str = "name|kind|framework|file|line";
+
+/// Java annotations that mark a test method
+const JAVA_TEST_ANNOTATIONS: &[&str] =
+    &["Test", "ParameterizedTest", "RepeatedTest", "TestFactory"];
+
+/// A test function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestInfo {
+    pub name: String,
+    /// `unit`, `integration` or `benchmark`
+    pub kind: String,
+    pub file: String,
+    pub line: usize,
+    /// e.g. `libtest`, `tokio`, `pytest`, `jest`, `junit5`
+    pub test_framework: String,
+}
+
+pub fn execute_find_tests(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    log::info!("Finding tests in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let rows: Vec<String> = find_tests(path)?
+        .iter()
+        .map(|test| {
+            format::format_row(&[
+                &test.name,
+                &test.kind,
+                &test.test_framework,
+                &path_utils::to_relative_path(&test.file),
+                &test.line.to_string(),
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "h": TESTS_HEADER,
+        "tests": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Tests of a file or every supported file of a directory, sorted by file
+/// and line
+pub fn find_tests(path: &Path) -> Result<Vec<TestInfo>, io::Error> {
+    let mut tests = Vec::new();
+    for file in collect_project_files(path)? {
+        let Ok(language) = detect_language(&file) else {
+            continue;
+        };
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        let integration = is_integration_path(file.strip_prefix(path).unwrap_or(&file));
+        match extract_tests(&source, language, &file.to_string_lossy()) {
+            Ok(file_tests) => tests.extend(file_tests.into_iter().map(|mut test| {
+                if integration && test.kind == "unit" {
+                    test.kind = "integration".to_string();
+                }
+                test
+            })),
+            Err(e) => log::debug!("Skipping {}: {e}", file.display()),
+        }
+    }
+
+    tests.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    Ok(tests)
+}
+
+/// Tests of one source file in line order, each `unit` or `benchmark`;
+/// [`find_tests`] tells integration tests apart by their path
+pub fn extract_tests(
+    source: &str,
+    language: Language,
+    file: &str,
+) -> Result<Vec<TestInfo>, io::Error> {
+    let tree = parse_code(source, language).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Failed to parse {} code from '{file}': {e}",
+                language.name()
+            ),
+        )
+    })?;
+
+    let mut finder = Finder {
+        source,
+        file,
+        tests: Vec::new(),
+    };
+    let root = tree.root_node();
+    match language {
+        Language::Rust => finder.rust_tests(root),
+        Language::Python => finder.python_tests(root),
+        Language::JavaScript | Language::TypeScript | Language::Jsx | Language::Tsx => {
+            let framework = js_framework(source);
+            finder.js_tests(root, framework, &mut Vec::new());
+        }
+        Language::Java => finder.java_tests(root, java_framework(source)),
+        Language::Go => finder.go_tests(root),
+        _ => {}
+    }
+
+    finder.tests.sort_by_key(|test| test.line);
+    Ok(finder.tests)
+}
+
+/// Path below the searched directory names `integration` or an `it/`
+/// directory
+fn is_integration_path(relative: &Path) -> bool {
+    relative.to_string_lossy().contains("integration")
+        || relative.parent().is_some_and(|dir| {
+            dir.components()
+                .any(|c| c == Component::Normal("it".as_ref()))
+        })
+}
+
+struct Finder<'a> {
+    source: &'a str,
+    file: &'a str,
+    tests: Vec<TestInfo>,
+}
+
+impl<'a> Finder<'a> {
+    fn push(&mut self, name: String, benchmark: bool, framework: &str, node: Node) {
+        self.tests.push(TestInfo {
+            name,
+            kind: if benchmark { "benchmark" } else { "unit" }.to_string(),
+            file: self.file.to_string(),
+            line: node.start_position().row + 1,
+            test_framework: framework.to_string(),
+        });
+    }
+
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.source.as_bytes()).unwrap_or("")
+    }
+
+    fn rust_tests(&mut self, node: Node) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            if child.kind() != "function_item" {
+                self.rust_tests(child);
+                continue;
+            }
+            let Some(name) = child.child_by_field_name("name") else {
+                continue;
+            };
+            let name = self.text(name).to_string();
+            if let Some((framework, benchmark)) = self.rust_test_attribute(child) {
+                self.push(name, benchmark, &framework, child);
+            } else if child
+                .child_by_field_name("parameters")
+                .is_some_and(|parameters| self.text(parameters).contains("Criterion"))
+            {
+                self.push(name, true, "criterion", child);
+            }
+        }
+    }
+
+    /// Framework and whether it is a benchmark, from the attributes above a
+    /// function
+    fn rust_test_attribute(&self, function: Node) -> Option<(String, bool)> {
+        let mut previous = function.prev_named_sibling();
+        while let Some(node) = previous {
+            match node.kind() {
+                "attribute_item" => {
+                    let attribute: String = self.text(node).split_whitespace().collect();
+                    let path = attribute
+                        .trim_start_matches("#[")
+                        .trim_end_matches(']')
+                        .split('(')
+                        .next()
+                        .unwrap_or("");
+                    match path {
+                        "test" => return Some(("libtest".to_string(), false)),
+                        "bench" => return Some(("libtest".to_string(), true)),
+                        _ => {
+                            if let Some(runtime) = path.strip_suffix("::test") {
+                                return Some((runtime.to_string(), false));
+                            }
+                        }
+                    }
+                }
+                "line_comment" | "block_comment" => {}
+                _ => return None,
+            }
+            previous = node.prev_named_sibling();
+        }
+        None
+    }
+
+    fn python_tests(&mut self, root: Node) {
+        let mut cursor = root.walk();
+        for statement in root.named_children(&mut cursor) {
+            let definition = unwrap_decorated(statement);
+            match definition.kind() {
+                "function_definition" => {
+                    if let Some(name) = self.python_test_name(definition) {
+                        self.push(name.to_string(), false, "pytest", definition);
+                    }
+                }
+                "class_definition" => self.python_class_tests(definition),
+                _ => {}
+            }
+        }
+    }
+
+    fn python_class_tests(&mut self, class: Node) {
+        let (Some(name), Some(body)) = (
+            class.child_by_field_name("name"),
+            class.child_by_field_name("body"),
+        ) else {
+            return;
+        };
+        let class_name = self.text(name);
+        let is_test_case = class
+            .child_by_field_name("superclasses")
+            .is_some_and(|bases| self.text(bases).contains("TestCase"));
+        let framework = if is_test_case {
+            "unittest"
+        } else if class_name.starts_with("Test") {
+            "pytest"
+        } else {
+            return;
+        };
+
+        let mut cursor = body.walk();
+        for statement in body.named_children(&mut cursor) {
+            let method = unwrap_decorated(statement);
+            if method.kind() != "function_definition" {
+                continue;
+            }
+            if let Some(name) = self.python_test_name(method) {
+                self.push(format!("{class_name}.{name}"), false, framework, method);
+            }
+        }
+    }
+
+    fn python_test_name(&self, function: Node) -> Option<&'a str> {
+        let name = self.text(function.child_by_field_name("name")?);
+        name.starts_with("test").then_some(name)
+    }
+
+    /// `it`/`test` calls below `node`, inside the `describe` titles of `suites`
+    fn js_tests(&mut self, node: Node, framework: &str, suites: &mut Vec<String>) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            if child.kind() != "call_expression" {
+                self.js_tests(child, framework, suites);
+                continue;
+            }
+            let callee = child
+                .child_by_field_name("function")
+                .map(|function| js_callee_base(function, self.source));
+            let arguments = child.child_by_field_name("arguments");
+            let title = arguments
+                .and_then(|arguments| arguments.named_child(0))
+                .filter(|title| matches!(title.kind(), "string" | "template_string"))
+                .map(|title| {
+                    self.text(title)
+                        .trim_matches(|c| c == '"' || c == '\'' || c == '`')
+                        .to_string()
+                });
+            match (callee, title) {
+                (Some("describe" | "context" | "suite"), Some(title)) => {
+                    suites.push(title);
+                    if let Some(arguments) = arguments {
+                        self.js_tests(arguments, framework, suites);
+                    }
+                    suites.pop();
+                }
+                (Some("it" | "test" | "specify"), Some(title)) => {
+                    let name = suites
+                        .iter()
+                        .map(String::as_str)
+                        .chain([title.as_str()])
+                        .collect::<Vec<_>>()
+                        .join(" > ");
+                    self.push(name, false, framework, child);
+                }
+                _ => self.js_tests(child, framework, suites),
+            }
+        }
+    }
+
+    fn java_tests(&mut self, node: Node, framework: &str) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            if child.kind() != "method_declaration" {
+                self.java_tests(child, framework);
+                continue;
+            }
+            if !self.has_java_test_annotation(child) {
+                continue;
+            }
+            let Some(method_name) = child.child_by_field_name("name") else {
+                continue;
+            };
+            let class_name = std::iter::successors(child.parent(), |node| node.parent())
+                .find(|node| node.kind() == "class_declaration")
+                .and_then(|class| class.child_by_field_name("name"))
+                .map(|name| self.text(name));
+            let name = match class_name {
+                Some(class_name) => format!("{class_name}.{}", self.text(method_name)),
+                None => self.text(method_name).to_string(),
+            };
+            // The declaration starts at its annotations; report the name's line
+            self.push(name, false, framework, method_name);
+        }
+    }
+
+    fn has_java_test_annotation(&self, method: Node) -> bool {
+        let mut cursor = method.walk();
+        let Some(modifiers) = method
+            .named_children(&mut cursor)
+            .find(|child| child.kind() == "modifiers")
+        else {
+            return false;
+        };
+        let mut cursor = modifiers.walk();
+        let annotated = modifiers.named_children(&mut cursor).any(|annotation| {
+            matches!(annotation.kind(), "marker_annotation" | "annotation")
+                && annotation.child_by_field_name("name").is_some_and(|name| {
+                    let name = self.text(name);
+                    let simple = name.rsplit('.').next().unwrap_or(name);
+                    JAVA_TEST_ANNOTATIONS.contains(&simple)
+                })
+        });
+        annotated
+    }
+
+    fn go_tests(&mut self, root: Node) {
+        let mut cursor = root.walk();
+        for function in root.named_children(&mut cursor) {
+            if function.kind() != "function_declaration" {
+                continue;
+            }
+            let (Some(name), Some(parameters)) = (
+                function.child_by_field_name("name"),
+                function.child_by_field_name("parameters"),
+            ) else {
+                continue;
+            };
+            let name = self.text(name);
+            let parameters = self.text(parameters);
+            if name.starts_with("Test") && parameters.contains("*testing.T") {
+                self.push(name.to_string(), false, "go-test", function);
+            } else if name.starts_with("Benchmark") && parameters.contains("*testing.B") {
+                self.push(name.to_string(), true, "go-test", function);
+            }
+        }
+    }
+}
+
+fn unwrap_decorated(node: Node) -> Node {
+    if node.kind() == "decorated_definition" {
+        node.child_by_field_name("definition").unwrap_or(node)
+    } else {
+        node
+    }
+}
+
+/// `it` for `it(...)`, `it.only(...)` and `it.skip(...)`
+fn js_callee_base<'a>(function: Node, source: &'a str) -> &'a str {
+    let base = if function.kind() == "member_expression" {
+        function.child_by_field_name("object").unwrap_or(function)
+    } else {
+        function
+    };
+    base.utf8_text(source.as_bytes()).unwrap_or("")
+}
+
+fn js_framework(source: &str) -> &'static str {
+    if source.contains("vitest") {
+        "vitest"
+    } else if source.contains("node:test") {
+        "node:test"
+    } else if source.contains("mocha") || source.contains("chai") {
+        "mocha"
+    } else {
+        "jest"
+    }
+}
+
+fn java_framework(source: &str) -> &'static str {
+    if source.contains("org.junit.jupiter") {
+        "junit5"
+    } else if source.contains("org.testng") {
+        "testng"
+    } else {
+        "junit4"
+    }
+}
@@ -0,0 +1,233 @@
+//! Type Diff Tool
+//!
+//! Compares the types extracted from two snapshots of a file or directory:
+//! either two paths on disk (`path` vs `other_path`) or the working tree
+//! against a git revision (`path` vs `compare_to`). Types are paired by
+//! name; see [`diff_type_results`].
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "before": "HEAD",
+//!   "after": "src/models",
+//!   "h": "change|kind|name|file|line",
+//!   "types": "+|struct|Invoice|billing.rs|12\n-|enum|LegacyStatus|billing.rs|40",
+//!   "fh": "type|change|field|before|after",
+//!   "fields": "User|+|email||String\nUser|~|id|u32|u64\nUser|-|nickname|String|"
+//! }
+//! ```
+//!
+//! `change` is `+` (added), `-` (removed) or `~` (type changed).
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde_json::{json, Value};
+
+use crate::analysis::diff::{get_git_file_content, validate_git_revision};
+use crate::common::format;
+use crate::extraction::types::{
+    diff_type_results, extract_types_for_language, extract_types_with_options, TypeDefinition,
+    TypeExtractionResult,
+};
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::detect_language;
+
+const TYPES_HEADER: &str = "change|kind|name|file|line";
+const FIELDS_HEADER: &str = "type|change|field|before|after";
+
+pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+    let other_path = arguments["other_path"].as_str();
+    let compare_to = arguments["compare_to"].as_str();
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let (before, before_label) = match (other_path, compare_to) {
+        (Some(other), None) => {
+            log::info!("Diffing types of {other} against {path_str}");
+            (extract(Path::new(other))?, other.to_string())
+        }
+        (None, Some(revision)) => {
+            log::info!("Diffing types of {path_str} against revision {revision}");
+            (extract_at_revision(path, revision)?, revision.to_string())
+        }
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Provide exactly one of 'other_path' or 'compare_to'",
+            ))
+        }
+    };
+    let after = extract(path)?;
+    let diff = diff_type_results(&before, &after);
+
+    let type_row = |change: &str, ty: &TypeDefinition| {
+        format::format_row(&[
+            change,
+            ty.kind.as_str(),
+            &ty.name,
+            &ty.file.to_string_lossy(),
+            &ty.line.to_string(),
+        ])
+    };
+    let type_rows: Vec<String> = diff
+        .added
+        .iter()
+        .map(|ty| type_row("+", ty))
+        .chain(diff.removed.iter().map(|ty| type_row("-", ty)))
+        .collect();
+
+    let mut field_rows = Vec::new();
+    for modification in &diff.modified {
+        let name = modification.name.as_str();
+        for field in &modification.added_fields {
+            field_rows.push(format::format_row(&[
+                name,
+                "+",
+                &field.name,
+                "",
+                &field.type_annotation,
+            ]));
+        }
+        for change in &modification.changed_fields {
+            field_rows.push(format::format_row(&[
+                name,
+                "~",
+                &change.name,
+                &change.before,
+                &change.after,
+            ]));
+        }
+        for field in &modification.removed_fields {
+            field_rows.push(format::format_row(&[
+                name,
+                "-",
+                &field.name,
+                &field.type_annotation,
+                "",
+            ]));
+        }
+    }
+
+    let result = json!({
+        "before": before_label,
+        "after": path_str,
+        "h": TYPES_HEADER,
+        "types": type_rows.join("\n"),
+        "fh": FIELDS_HEADER,
+        "fields": field_rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+fn extract(path: &Path) -> Result<TypeExtractionResult, io::Error> {
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {}", path.display()),
+        ));
+    }
+    extract_types_with_options(path, None, 0, false, false)
+        .map_err(|e| io::Error::other(format!("Failed to extract types: {e}")))
+}
+
+/// Types of `path` as committed at `revision`, with files named relative to
+/// `path` (or by file name for a single file) to match [`extract`].
+fn extract_at_revision(path: &Path, revision: &str) -> Result<TypeExtractionResult, io::Error> {
+    let mut sources: Vec<(PathBuf, String)> = Vec::new();
+    if path.is_file() {
+        let name = PathBuf::from(path.file_name().unwrap_or(path.as_os_str()));
+        sources.push((name, get_git_file_content(path, revision)?));
+    } else {
+        for relative in tracked_files(path, revision)? {
+            if detect_language(&relative).is_ok() {
+                let source = git_show(path, revision, &relative)?;
+                sources.push((relative, source));
+            }
+        }
+    }
+
+    let mut types = Vec::new();
+    for (relative, source) in sources {
+        let Ok(language) = detect_language(&relative) else {
+            continue;
+        };
+        let extracted = extract_types_for_language(&source, &relative, language)
+            .map_err(|e| io::Error::other(format!("Failed to extract types: {e}")))?;
+        types.extend(extracted);
+    }
+
+    Ok(TypeExtractionResult {
+        total_types: types.len(),
+        types_included: types.len(),
+        types,
+        limit_hit: None,
+        truncated: false,
+        encoding_detected: Vec::new(),
+        function_signatures: Vec::new(),
+    })
+}
+
+/// Files under `dir` at `revision`, relative to `dir`
+fn tracked_files(dir: &Path, revision: &str) -> Result<Vec<PathBuf>, io::Error> {
+    validate_git_revision(revision)?;
+    let output = Command::new("git")
+        .args(["ls-tree", "-r", "--name-only", revision, "--", "."])
+        .current_dir(dir)
+        .output()
+        .map_err(|e| io::Error::other(format!("Failed to execute git: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Git ls-tree failed: {stderr}"),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// Content of `relative` (under `dir`) at `revision`; works for deleted files.
+fn git_show(dir: &Path, revision: &str, relative: &Path) -> Result<String, io::Error> {
+    let output = Command::new("git")
+        .args(["show", &format!("{revision}:./{}", relative.display())])
+        .current_dir(dir)
+        .output()
+        .map_err(|e| io::Error::other(format!("Failed to execute git: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Git show failed: {stderr}"),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
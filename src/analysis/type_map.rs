@@ -6,11 +6,22 @@ use tiktoken_rs::cl100k_base;
 
 use crate::analysis::path_utils;
 use crate::common::budget::BudgetTracker;
+use crate::common::cancel::CancellationToken;
 use crate::common::{budget, format};
-use crate::extraction::types::{extract_types_with_options, TypeDefinition, TypeKind};
+use crate::extraction::types::{TypeDefinition, TypeExtractionOptions, TypeExtractor};
 use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::output::{csv, mermaid};
 
+/// Compatibility wrapper for callers that never cancel.
+#[allow(dead_code)]
 pub fn execute(arguments: &Value) -> Result<CallToolResult> {
+    execute_with_cancel(arguments, &CancellationToken::default())
+}
+
+pub fn execute_with_cancel(
+    arguments: &Value,
+    cancel: &CancellationToken,
+) -> Result<CallToolResult> {
     // Backward-compatible input handling:
     // - legacy: `file_path` for single file
     // - current: `path` for file or directory
@@ -25,6 +36,12 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult> {
     let count_usages = arguments["count_usages"].as_bool().unwrap_or(true);
 
     let pattern = arguments["pattern"].as_str();
+    let output_format = arguments["output_format"].as_str().unwrap_or("compact");
+    if !matches!(output_format, "compact" | "mermaid" | "csv" | "tsv") {
+        eyre::bail!(
+            "Invalid 'output_format' argument: {output_format} (expected compact, mermaid, csv or tsv)"
+        );
+    }
 
     let path = Path::new(path_str);
     if !path.exists() {
@@ -43,7 +60,14 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult> {
     };
 
     // 1) Extract types with optional usage counting in a single pass
-    let mut extraction_result = extract_types_with_options(path, file_glob, 1000, count_usages)?;
+    let options = TypeExtractionOptions {
+        pattern: file_glob.map(str::to_string),
+        max_types: 1000,
+        count_usages,
+        resolve_field_types: false,
+        cancel: cancel.clone(),
+    };
+    let mut extraction_result = TypeExtractor::new(path, options).collect_result()?;
 
     // 2) Sort by usage_count DESC (if counted), then name ASC
     extraction_result.types.sort_by(|a, b| {
@@ -77,6 +101,20 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult> {
         filtered.truncate(limit);
     }
 
+    if output_format == "mermaid" {
+        return Ok(mermaid_output(
+            &filtered,
+            max_tokens,
+            extraction_result.limit_hit.is_some(),
+        ));
+    }
+
+    match output_format {
+        "csv" => return Ok(table_output(&filtered, csv::Delimiter::Comma, max_tokens)),
+        "tsv" => return Ok(table_output(&filtered, csv::Delimiter::Tab, max_tokens)),
+        _ => {}
+    }
+
     // 6) Build compact output
     // `BudgetTracker` uses a conservative estimate; final enforcement below uses BPE.
     let mut budget_tracker = BudgetTracker::new((max_tokens * 9) / 10);
@@ -95,7 +133,10 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult> {
     }
 
     let mut out = Map::new();
-    out.insert("h".to_string(), json!("name|kind|file|line|usage_count"));
+    out.insert(
+        "h".to_string(),
+        json!("name|kind|file|line|usage_count|col|end_line|end_col"),
+    );
     out.insert("types".to_string(), json!(rows.join("\n")));
 
     // Hard enforcement: drop rows until within token budget.
@@ -130,11 +171,111 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult> {
     ))
 }
 
+/// Hand each type to `emit` as soon as its file has been parsed, in walk
+/// order.
+///
+/// Takes the same `path` and `pattern` arguments as [`execute_with_cancel`];
+/// usage counting, sorting, pagination and the token budget need every type
+/// first and are skipped.
+pub fn stream_types(
+    arguments: &Value,
+    cancel: &CancellationToken,
+    mut emit: impl FnMut(&TypeDefinition) -> std::io::Result<()>,
+) -> Result<()> {
+    let path_str = arguments["path"]
+        .as_str()
+        .or_else(|| arguments["file_path"].as_str())
+        .ok_or_else(|| eyre::eyre!("Missing or invalid 'path' argument"))?;
+    let (file_glob, name_filter) = match arguments["pattern"].as_str() {
+        Some(pat) if looks_like_glob(pat) => (Some(pat), None),
+        Some(pat) => (None, Some(pat)),
+        None => (None, None),
+    };
+
+    let options = TypeExtractionOptions {
+        pattern: file_glob.map(str::to_string),
+        max_types: 1000,
+        count_usages: false,
+        resolve_field_types: false,
+        cancel: cancel.clone(),
+    };
+    for ty in TypeExtractor::new(path_str, options) {
+        let ty = ty?;
+        if name_filter.is_none_or(|filter| ty.name.contains(filter)) {
+            emit(&ty)?;
+        }
+    }
+    Ok(())
+}
+
+/// Render a Mermaid class diagram, dropping trailing types until it fits.
+fn mermaid_output(types: &[TypeDefinition], max_tokens: usize, limit_hit: bool) -> CallToolResult {
+    let bpe = cl100k_base().unwrap();
+    let mut kept = types.len();
+    let mut truncated = limit_hit;
+
+    loop {
+        let mut out = Map::new();
+        out.insert(
+            "mermaid".to_string(),
+            json!(mermaid::render_class_diagram(&types[..kept])),
+        );
+        if truncated {
+            out.insert("@".to_string(), json!({"t": true}));
+        }
+
+        let text = serde_json::to_string(&Value::Object(out)).unwrap_or_default();
+        if kept == 0 || bpe.encode_with_special_tokens(&text).len() <= max_tokens {
+            return CallToolResult::success(text);
+        }
+
+        kept -= 1;
+        truncated = true;
+    }
+}
+
+/// Render CSV/TSV, dropping trailing types until it fits.
+///
+/// Tables have no room for a truncation marker, so callers paging through
+/// large projects should narrow `pattern` instead.
+fn table_output(
+    types: &[TypeDefinition],
+    delimiter: csv::Delimiter,
+    max_tokens: usize,
+) -> CallToolResult {
+    let bpe = cl100k_base().unwrap();
+    let fits = |kept: usize| {
+        let text = csv::render_types(&types[..kept], delimiter);
+        bpe.encode_with_special_tokens(&text).len() <= max_tokens
+    };
+
+    // Binary search for the longest prefix of types that fits the budget.
+    let (mut low, mut high) = (0, types.len());
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        if fits(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    let text = csv::render_types(&types[..low], delimiter);
+
+    let (uri, mime_type) = match delimiter {
+        csv::Delimiter::Comma => ("treesitter-mcp://type_map.csv", "text/csv"),
+        csv::Delimiter::Tab => ("treesitter-mcp://type_map.tsv", "text/tab-separated-values"),
+    };
+    CallToolResult::success_with_mime_type(text, uri, mime_type)
+}
+
 fn type_to_row(ty: &TypeDefinition) -> String {
     let file = path_utils::to_relative_path(ty.file.to_string_lossy().as_ref());
-    let kind = type_kind_str(ty.kind);
+    let kind = ty.kind.as_str();
     let line = ty.line.to_string();
     let usage = ty.usage_count.to_string();
+    let column = ty.column.to_string();
+    let end_line = ty.end_line.to_string();
+    let end_column = ty.end_column.to_string();
 
     let owned = [
         ty.name.as_str(),
@@ -142,25 +283,13 @@ fn type_to_row(ty: &TypeDefinition) -> String {
         file.as_str(),
         line.as_str(),
         usage.as_str(),
+        column.as_str(),
+        end_line.as_str(),
+        end_column.as_str(),
     ];
     format::format_row(&owned)
 }
 
-fn type_kind_str(kind: TypeKind) -> &'static str {
-    match kind {
-        TypeKind::Interface => "interface",
-        TypeKind::Class => "class",
-        TypeKind::Struct => "struct",
-        TypeKind::Enum => "enum",
-        TypeKind::Trait => "trait",
-        TypeKind::Protocol => "protocol",
-        TypeKind::TypeAlias => "type_alias",
-        TypeKind::Record => "record",
-        TypeKind::TypedDict => "typed_dict",
-        TypeKind::NamedTuple => "named_tuple",
-    }
-}
-
 fn looks_like_glob(pattern: &str) -> bool {
     pattern.contains('*')
         || pattern.contains('?')
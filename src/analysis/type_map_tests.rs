@@ -83,7 +83,10 @@ mod tests {
 
         let json: Value = serde_json::from_str(text)?;
 
-        assert_eq!(json["h"], "name|kind|file|line|usage_count");
+        assert_eq!(
+            json["h"],
+            "name|kind|file|line|usage_count|col|end_line|end_col"
+        );
 
         let rows_str = json["types"].as_str().unwrap_or("");
         let rows = parse_compact_rows(rows_str);
@@ -94,7 +97,7 @@ mod tests {
                 .unwrap_or_else(|| panic!("Missing type row for '{name}'"))
         };
 
-        // Row columns: name|kind|file|line|usage_count
+        // Row columns: name|kind|file|line|usage_count|col|end_line|end_col
         let config = find_row("Config");
         assert_eq!(config.get(1).map(|s| s.as_str()), Some("struct"));
         assert_eq!(config.get(4).and_then(|s| s.parse::<u64>().ok()), Some(1));
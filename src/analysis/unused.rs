@@ -0,0 +1,174 @@
+//! Unused Imports Tool
+//!
+//! Reports every import with whether its bound names are referenced in the
+//! rest of the file. Names come from the same import parsing `stale_imports`
+//! uses (aliases, `{a, b}` groups); references are syntax-aware identifier
+//! matches outside the import itself.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "h": "file|line|import|status|unused",
+//!   "imports": "src/lib.rs|1|use std::fmt;|unused|fmt\nsrc/lib.rs|2|use std::io::*;|unknown|\n..."
+//! }
+//! ```
+//!
+//! `status` is `used` when any bound name is referenced, `unused` when none
+//! is, and `unknown` for imports whose names cannot be known without type
+//! information: globs (`use a::*`, `from a import *`, `import a.*`, Go dot
+//! imports), C# namespace `using`s and Swift module imports. Re-exports
+//! (Rust `pub use`, Python names in `__all__`) and side-effect imports are
+//! always `used`. `unused` lists the unreferenced names of `used` imports too.
+
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::analysis::path_utils;
+use crate::analysis::stale_imports::import_references;
+use crate::common::cancel::CancellationToken;
+use crate::common::format;
+use crate::common::project_files::collect_project_files;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{detect_language, Language};
+
+const UNUSED_HEADER: &str = "file|line|import|status|unused";
+
+/// An import and whether it looks unused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportUsage {
+    pub file: String,
+    pub line: usize,
+    pub import_text: String,
+    /// `None` when the import's names cannot be resolved (globs).
+    pub is_probably_unused: Option<bool>,
+    /// Bound names without any reference outside the import.
+    pub unused_names: Vec<String>,
+}
+
+impl ImportUsage {
+    fn status(&self) -> &'static str {
+        match self.is_probably_unused {
+            Some(true) => "unused",
+            Some(false) => "used",
+            None => "unknown",
+        }
+    }
+}
+
+/// Compatibility wrapper for callers that never cancel.
+#[allow(dead_code)]
+pub fn execute_find_unused_imports(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    execute_find_unused_imports_with_cancel(arguments, &CancellationToken::default())
+}
+
+pub fn execute_find_unused_imports_with_cancel(
+    arguments: &Value,
+    cancel: &CancellationToken,
+) -> Result<CallToolResult, io::Error> {
+    let path_str = arguments["path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'path' argument",
+        )
+    })?;
+
+    log::info!("Finding unused imports in: {path_str}");
+
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Path does not exist: {path_str}"),
+        ));
+    }
+
+    let rows: Vec<String> = find_unused_imports(path, cancel)?
+        .iter()
+        .map(|import| {
+            format::format_row(&[
+                &import.file,
+                &import.line.to_string(),
+                &import.import_text,
+                import.status(),
+                &import.unused_names.join(","),
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "h": UNUSED_HEADER,
+        "imports": rows.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Classify every import in a file or in every supported file of a directory.
+pub fn find_unused_imports(
+    path: &Path,
+    cancel: &CancellationToken,
+) -> Result<Vec<ImportUsage>, io::Error> {
+    let files = if path.is_file() {
+        vec![path.to_path_buf()]
+    } else {
+        collect_project_files(path)?
+    };
+
+    let mut imports = Vec::new();
+    for (index, file) in files.into_iter().enumerate() {
+        cancel.check(index)?;
+        let Ok(language) = detect_language(&file) else {
+            continue;
+        };
+        let Ok(source) = path_utils::read_source_file(&file) else {
+            continue;
+        };
+        let relative = path_utils::to_relative_path(&file.to_string_lossy());
+
+        for import in import_references(&file, &source, language)? {
+            let is_probably_unused = if is_unresolvable(&import.import_text, language) {
+                None
+            } else {
+                Some(!import.names.is_empty() && import.unused.len() == import.names.len())
+            };
+            imports.push(ImportUsage {
+                file: relative.clone(),
+                line: import.line,
+                import_text: import.import_text,
+                is_probably_unused,
+                unused_names: import.unused,
+            });
+        }
+    }
+
+    Ok(imports)
+}
+
+/// Imports whose bound names depend on the imported module's contents.
+fn is_unresolvable(text: &str, language: Language) -> bool {
+    let body = text.trim().trim_end_matches(';').trim_end();
+    match language {
+        // `pub use a::*` is a re-export, not a lookup.
+        Language::Rust => !body.starts_with("pub") && body.contains('*'),
+        Language::Python | Language::Java => body.ends_with('*'),
+        Language::Go => body.starts_with('.'),
+        Language::CSharp => !body.contains('='),
+        Language::Swift => true,
+        Language::JavaScript
+        | Language::TypeScript
+        | Language::Jsx
+        | Language::Tsx
+        | Language::Html
+        | Language::Css
+        | Language::Svelte => false,
+    }
+}
@@ -5,6 +5,7 @@ use std::path::{Path, PathBuf};
 use eyre::Result;
 
 use crate::analysis::dependencies::resolve_dependencies;
+use crate::analysis::path_utils;
 use crate::common::project_files::collect_project_files;
 use crate::extraction::types::TypeDefinition;
 use crate::parser::detect_language;
@@ -39,7 +40,7 @@ pub fn count_all_usages(types: &mut [TypeDefinition], project_path: &Path) -> Re
     let mut usage_counts = vec![0usize; types.len()];
 
     for file_path in collect_project_files(scan_root)? {
-        let content = match fs::read_to_string(&file_path) {
+        let content = match path_utils::read_source_file(&file_path) {
             Ok(c) => c,
             Err(_) => continue,
         };
@@ -146,7 +147,10 @@ fn canonicalize_or_identity(path: &Path) -> PathBuf {
 
 #[derive(Debug, Clone, Copy)]
 pub enum CountLanguage {
+    /// C-family syntax; `"""` text blocks as in Java, Kotlin, Swift and C# 11
     CLike,
+    /// C-like with backtick raw strings
+    Go,
     JavaScript,
     Rust,
     Python,
@@ -161,8 +165,11 @@ pub fn language_for_path(path: &Path) -> CountLanguage {
     match ext.to_ascii_lowercase().as_str() {
         "rs" => CountLanguage::Rust,
         "py" => CountLanguage::Python,
-        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => CountLanguage::JavaScript,
-        "go" | "java" | "cs" | "c" | "h" | "cpp" | "hpp" | "cc" => CountLanguage::CLike,
+        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => CountLanguage::JavaScript,
+        "go" => CountLanguage::Go,
+        "java" | "cs" | "kt" | "kts" | "swift" | "c" | "h" | "cpp" | "hpp" | "cc" => {
+            CountLanguage::CLike
+        }
         _ => CountLanguage::Plain,
     }
 }
@@ -195,6 +202,7 @@ pub fn strip_comments_and_strings(content: &str, language: CountLanguage) -> Str
                 allow_double_quote: true,
                 allow_triple_quote: true,
                 allow_backtick: false,
+                raw_backticks: false,
                 allow_rust_raw_strings: false,
             },
         ),
@@ -207,6 +215,7 @@ pub fn strip_comments_and_strings(content: &str, language: CountLanguage) -> Str
                 allow_double_quote: true,
                 allow_triple_quote: false,
                 allow_backtick: true,
+                raw_backticks: false,
                 allow_rust_raw_strings: false,
             },
         ),
@@ -219,6 +228,7 @@ pub fn strip_comments_and_strings(content: &str, language: CountLanguage) -> Str
                 allow_double_quote: true,
                 allow_triple_quote: false,
                 allow_backtick: false,
+                raw_backticks: false,
                 allow_rust_raw_strings: true,
             },
         ),
@@ -229,8 +239,22 @@ pub fn strip_comments_and_strings(content: &str, language: CountLanguage) -> Str
                 c_like_comments: true,
                 allow_single_quote: true,
                 allow_double_quote: true,
-                allow_triple_quote: false,
+                allow_triple_quote: true,
                 allow_backtick: false,
+                raw_backticks: false,
+                allow_rust_raw_strings: false,
+            },
+        ),
+        CountLanguage::Go => strip_with_config(
+            content,
+            StripConfig {
+                line_comment: Some('/'),
+                c_like_comments: true,
+                allow_single_quote: true,
+                allow_double_quote: true,
+                allow_triple_quote: false,
+                allow_backtick: true,
+                raw_backticks: true,
                 allow_rust_raw_strings: false,
             },
         ),
@@ -245,6 +269,8 @@ struct StripConfig {
     allow_double_quote: bool,
     allow_triple_quote: bool,
     allow_backtick: bool,
+    /// Backslashes inside backticks are literal (Go raw strings)
+    raw_backticks: bool,
     allow_rust_raw_strings: bool,
 }
 
@@ -389,7 +415,7 @@ fn strip_with_config(content: &str, config: StripConfig) -> String {
                     continue;
                 }
 
-                if b == b'\\' {
+                if b == b'\\' && !config.raw_backticks {
                     out.push(' ');
                     if i + 1 < bytes.len() {
                         out.push(' ');
@@ -6,6 +6,7 @@
 //! - `p`: relative path of the main file
 //! - `h`: header string (pipe-delimited column names)
 //! - `f`/`s`/`c`: newline-delimited row strings for functions/structs/classes
+//! - `co`: React components of JSX/TSX files, in the same columns as `f`
 //! - `deps`: map of dependency file path -> newline-delimited type rows
 //! - Optional meta is under `@` (e.g. `{ "t": true }` for truncated)
 
@@ -17,33 +18,67 @@ use std::path::{Path, PathBuf};
 use serde_json::json;
 use serde_json::Map;
 use serde_json::Value;
-use tiktoken_rs::cl100k_base;
 
 use crate::analysis::dependencies::resolve_dependencies;
+use crate::analysis::diff::{self, ChangeType, SymbolType};
 use crate::analysis::path_utils;
 use crate::analysis::shape::{
     extract_enhanced_shape, prepend_leading_comments_to_code, CommentMode, EnhancedClassInfo,
     EnhancedFileShape, EnhancedFunctionInfo, EnhancedStructInfo, ImplBlockInfo, ImportInfo,
     InterfaceInfo, MethodInfo, PropertyInfo, TraitInfo,
 };
+use crate::analysis::symbol_inventory::{declaration_text, visibility_of};
 use crate::common::budget;
 use crate::common::budget::BudgetTracker;
 use crate::common::format;
+use crate::common::tokenizer::{self, Tokenizer, TokenizerKind};
 use crate::extraction::types::{
     extract_go_types, extract_python_types, extract_rust_types, extract_typescript_types,
     TypeDefinition,
 };
 use crate::mcp_types::{CallToolResult, CallToolResultExt};
-use crate::parser::{detect_language, parse_code};
+use crate::parser::{cache, detect_language_from_content, parse_code, Language};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum DetailLevel {
+pub(crate) enum DetailLevel {
     Signatures,
     Full,
 }
 
+/// Least visible symbol kept in the view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MinVisibility {
+    /// Every symbol (default)
+    #[default]
+    Private,
+    /// Drop private symbols; crate, package, internal and protected stay
+    PubCrate,
+    /// Public API only
+    Pub,
+}
+
+impl MinVisibility {
+    pub fn from_option(value: Option<&str>) -> Self {
+        match value {
+            Some("pub") => MinVisibility::Pub,
+            Some("pub_crate") => MinVisibility::PubCrate,
+            _ => MinVisibility::Private,
+        }
+    }
+
+    /// Whether a `symbol_inventory` visibility label passes this threshold.
+    fn allows(self, visibility: &str) -> bool {
+        let rank = match visibility {
+            "private" => MinVisibility::Private,
+            "public" => MinVisibility::Pub,
+            _ => MinVisibility::PubCrate,
+        };
+        rank as u8 >= self as u8
+    }
+}
+
 #[derive(Debug, Clone)]
-struct DefinitionLocation {
+pub(crate) struct DefinitionLocation {
     file: PathBuf,
     line: usize,
     column: usize,
@@ -70,6 +105,9 @@ impl DetailLevel {
         DetailLevel::Full
     }
 
+    /// Column names for `f`/`s`/`c` rows. Values are written with
+    /// `format::format_row`, so a `|` inside a field (e.g. `u8 | u16` in a
+    /// signature) arrives as `\|`; split rows with `format::parse_row`.
     fn header(self) -> &'static str {
         match self {
             DetailLevel::Signatures => "name|line|sig",
@@ -77,7 +115,7 @@ impl DetailLevel {
         }
     }
 
-    fn include_code(self) -> bool {
+    pub(crate) fn include_code(self) -> bool {
         matches!(self, DetailLevel::Full)
     }
 
@@ -118,14 +156,14 @@ fn parse_comment_mode(arguments: &Value) -> CommentMode {
 fn apply_comment_mode(
     shape: &mut EnhancedFileShape,
     source: &str,
-    language: crate::parser::Language,
+    language: Language,
     comment_mode: CommentMode,
 ) {
     if comment_mode == CommentMode::None {
         return;
     }
 
-    for func in &mut shape.functions {
+    for func in shape.functions.iter_mut().chain(&mut shape.components) {
         func.code = prepend_leading_comments_to_code(
             source,
             func.line,
@@ -208,6 +246,118 @@ fn apply_comment_mode(
     }
 }
 
+/// Settings for one view, parsed from tool arguments or set by
+/// [`crate::analysis::pipeline::AnalysisPipeline`]
+#[derive(Debug, Clone)]
+pub(crate) struct ViewOptions {
+    pub(crate) detail: DetailLevel,
+    pub(crate) focus_symbol: Option<String>,
+    pub(crate) comment_mode: CommentMode,
+    pub(crate) group_by_trait: bool,
+    pub(crate) include_deps: bool,
+    pub(crate) max_tokens: usize,
+    pub(crate) definition_location: Option<DefinitionLocation>,
+    pub(crate) min_visibility: MinVisibility,
+    pub(crate) compare_to: Option<String>,
+    /// Encoding `max_tokens` is counted in
+    pub(crate) tokenizer: TokenizerKind,
+    /// Inclusive 1-based lines; only symbols overlapping them are shown
+    pub(crate) line_range: Option<(usize, usize)>,
+}
+
+impl Default for ViewOptions {
+    fn default() -> Self {
+        Self {
+            detail: DetailLevel::Full,
+            focus_symbol: None,
+            comment_mode: CommentMode::None,
+            group_by_trait: false,
+            include_deps: true,
+            max_tokens: 2000,
+            definition_location: None,
+            min_visibility: MinVisibility::Private,
+            compare_to: None,
+            tokenizer: tokenizer::configured(),
+            line_range: None,
+        }
+    }
+}
+
+impl ViewOptions {
+    fn from_args(arguments: &Value) -> Result<Self, io::Error> {
+        let defaults = Self::default();
+
+        // Back-compat: tests pass include_deps without tool schema.
+        let include_deps = arguments
+            .get("include_deps")
+            .and_then(Value::as_bool)
+            .unwrap_or(defaults.include_deps);
+
+        let definition_location = arguments
+            .get("definition_location")
+            .filter(|value| !value.is_null())
+            .map(parse_definition_location)
+            .transpose()?;
+
+        let tokenizer = match arguments.get("tokenizer").and_then(Value::as_str) {
+            Some(name) => TokenizerKind::from_name(name).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Unknown tokenizer '{name}' (expected cl100k, p50k, r50k or o200k)"),
+                )
+            })?,
+            None => defaults.tokenizer,
+        };
+
+        Ok(Self {
+            line_range: parse_line_range(arguments)?,
+            detail: DetailLevel::from_args(arguments),
+            focus_symbol: arguments
+                .get("focus_symbol")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            comment_mode: parse_comment_mode(arguments),
+            group_by_trait: arguments
+                .get("group_by_trait")
+                .and_then(Value::as_bool)
+                .unwrap_or(defaults.group_by_trait),
+            include_deps,
+            max_tokens: arguments
+                .get("max_tokens")
+                .and_then(Value::as_u64)
+                .map_or(defaults.max_tokens, |n| n as usize),
+            definition_location,
+            min_visibility: MinVisibility::from_option(
+                arguments.get("min_visibility").and_then(Value::as_str),
+            ),
+            compare_to: arguments
+                .get("compare_to")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            tokenizer,
+        })
+    }
+}
+
+/// `start_line`/`end_line` as an inclusive range; a missing bound is open
+fn parse_line_range(arguments: &Value) -> Result<Option<(usize, usize)>, io::Error> {
+    let start = arguments.get("start_line").and_then(Value::as_u64);
+    let end = arguments.get("end_line").and_then(Value::as_u64);
+    if start.is_none() && end.is_none() {
+        return Ok(None);
+    }
+
+    let start = start.map_or(1, |n| n as usize);
+    let end = end.map_or(usize::MAX, |n| n as usize);
+    if start == 0 || start > end {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid line range {start}..={end}: lines are 1-based and start_line must not exceed end_line"),
+        ));
+    }
+    Ok(Some((start, end)))
+}
+
 pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
     let file_path = arguments["file_path"].as_str().ok_or_else(|| {
         io::Error::new(
@@ -216,73 +366,86 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
         )
     })?;
 
-    let detail = DetailLevel::from_args(arguments);
-    let focus_symbol = arguments.get("focus_symbol").and_then(Value::as_str);
-    let comment_mode = parse_comment_mode(arguments);
-
-    // Back-compat: tests pass include_deps without tool schema.
-    let include_deps = arguments
-        .get("include_deps")
-        .and_then(Value::as_bool)
-        .unwrap_or(true);
-
-    let max_tokens = arguments
-        .get("max_tokens")
-        .and_then(Value::as_u64)
-        .unwrap_or(2000) as usize;
-
-    let definition_location = arguments
-        .get("definition_location")
-        .filter(|value| !value.is_null())
-        .map(parse_definition_location)
-        .transpose()?;
-
-    log::info!(
-        "Viewing code: {file_path} (detail: {:?}, focus_symbol: {:?}, include_deps: {include_deps}, max_tokens: {max_tokens})",
-        detail,
-        focus_symbol
-    );
+    let options = ViewOptions::from_args(arguments)?;
 
     // Parse main file
-    let source = fs::read_to_string(file_path).map_err(|e| {
+    let source = path_utils::read_source_file(file_path).map_err(|e| {
         io::Error::new(
             io::ErrorKind::NotFound,
             format!("Failed to read file {file_path}: {e}"),
         )
     })?;
 
-    let language = detect_language(file_path).map_err(|e| {
+    let language = detect_language_from_content(file_path, &source).map_err(|e| {
         io::Error::new(
             io::ErrorKind::Unsupported,
             format!("Cannot detect language for file {file_path}: {e}"),
         )
     })?;
 
-    let tree = parse_code(&source, language).map_err(|e| {
-        io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("Failed to parse {} code: {e}", language.name()),
-        )
-    })?;
+    let parsed = cache::shared()
+        .parse(Path::new(file_path), &source, language)
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse {} code: {e}", language.name()),
+            )
+        })?;
+    let tree = parsed.0.clone();
 
-    let mut main_shape = extract_enhanced_shape(
+    let main_shape = extract_enhanced_shape(
         &tree,
         &source,
         language,
         Some(file_path),
-        detail.include_code(),
+        options.detail.include_code(),
     )?;
 
+    render(file_path, &source, language, main_shape, &options)
+}
+
+/// Build the view for an already parsed file.
+///
+/// `main_shape` must have been extracted with code when
+/// `options.detail` is [`DetailLevel::Full`].
+pub(crate) fn render(
+    file_path: &str,
+    source: &str,
+    language: Language,
+    mut main_shape: EnhancedFileShape,
+    options: &ViewOptions,
+) -> Result<CallToolResult, io::Error> {
+    let ViewOptions {
+        detail,
+        comment_mode,
+        group_by_trait,
+        include_deps,
+        max_tokens,
+        min_visibility,
+        ..
+    } = *options;
+    let focus_symbol = options.focus_symbol.as_deref();
+    let definition_location = options.definition_location.as_ref();
+
+    log::info!(
+        "Viewing code: {file_path} (detail: {:?}, focus_symbol: {:?}, include_deps: {include_deps}, max_tokens: {max_tokens})",
+        detail,
+        focus_symbol
+    );
+
+    apply_min_visibility(&mut main_shape, source, language, min_visibility);
+    if let Some(range) = options.line_range {
+        apply_line_range(&mut main_shape, range);
+    }
     if let Some(symbol) = focus_symbol {
         apply_focus(&mut main_shape, symbol);
     }
-    apply_comment_mode(&mut main_shape, &source, language, comment_mode);
+    apply_comment_mode(&mut main_shape, source, language, comment_mode);
 
     // Convert main file path to relative
     let main_path = path_utils::to_relative_path(file_path);
 
-    let bpe = cl100k_base()
-        .map_err(|e| io::Error::other(format!("Failed to initialize tiktoken tokenizer: {e}")))?;
+    let counter = tokenizer::tokenizer(options.tokenizer)?;
 
     // Build output map (compact)
     let mut out = Map::new();
@@ -290,15 +453,19 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
     out.insert("h".to_string(), json!(detail.header()));
 
     insert_symbol_tables(&mut out, &main_shape, detail);
+    if let Some(compare_to) = options.compare_to.as_deref() {
+        insert_function_changes(&mut out, &main_shape, file_path, compare_to, detail)?;
+    }
     insert_imports_and_traits(&mut out, &main_shape, detail);
     insert_interfaces_and_properties(&mut out, &main_shape, detail);
     insert_class_methods(&mut out, &main_shape, detail);
-    insert_impl_methods(&mut out, &main_shape, detail);
+    insert_impl_methods(&mut out, &main_shape, detail, group_by_trait);
 
     let mut truncated = false;
 
     if include_deps {
-        let project_root = path_utils::find_project_root(Path::new(file_path))
+        let project_root = path_utils::find_workspace_root(Path::new(file_path))
+            .or_else(|| path_utils::find_project_root(Path::new(file_path)))
             .or_else(|| Path::new(file_path).parent().map(|p| p.to_path_buf()))
             .ok_or_else(|| {
                 io::Error::new(
@@ -308,21 +475,22 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
             })?;
 
         let mut dep_paths =
-            resolve_dependencies(language, &source, Path::new(file_path), &project_root);
-        if let Some(location) = definition_location.as_ref() {
+            resolve_dependencies(language, source, Path::new(file_path), &project_root);
+        if let Some(location) = definition_location {
             push_unique_path(&mut dep_paths, location.file.clone());
         }
         let project_deps = filter_project_dependencies(dep_paths, &project_root);
 
-        let referenced = if let Some(location) = definition_location.as_ref() {
+        let referenced = if let Some(location) = definition_location {
             referenced_type_from_definition_location(location)?
         } else {
             extract_referenced_type_names(
                 language,
-                &source,
+                source,
                 &main_shape,
                 Path::new(file_path),
                 focus_symbol,
+                options.line_range,
             )
         };
 
@@ -333,6 +501,7 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
             detail,
             max_tokens,
             comment_mode,
+            counter,
         )?;
 
         if !deps_obj.is_empty() {
@@ -340,6 +509,16 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
         }
     }
 
+    // Token costs of the symbols behind each row of these tables
+    let mut row_costs = HashMap::from([
+        ("f", token_counts(&main_shape.functions)),
+        ("co", token_counts(&main_shape.components)),
+        (
+            "s",
+            main_shape.structs.iter().map(|s| s.token_count).collect(),
+        ),
+    ]);
+
     // Early estimate-based budget: only used to stop adding deps.
     // Hard enforcement below uses actual token count.
     loop {
@@ -350,7 +529,7 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
             )
         })?;
 
-        if bpe.encode_with_special_tokens(&json_text).len() <= max_tokens {
+        if counter.count(&json_text) <= max_tokens {
             break;
         }
 
@@ -359,7 +538,7 @@ pub fn execute(arguments: &Value) -> Result<CallToolResult, io::Error> {
             continue;
         }
 
-        if !shrink_symbol_tables(&mut out) {
+        if !shrink_symbol_tables(&mut out, &mut row_costs) {
             truncated = true;
             break;
         }
@@ -486,6 +665,11 @@ fn insert_symbol_tables(
         out.insert("f".to_string(), json!(functions));
     }
 
+    let components = functions_to_rows(&shape.components, detail);
+    if !components.is_empty() {
+        out.insert("co".to_string(), json!(components));
+    }
+
     let structs = structs_to_rows(&shape.structs, detail);
     if !structs.is_empty() {
         out.insert("s".to_string(), json!(structs));
@@ -557,8 +741,13 @@ fn insert_impl_methods(
     out: &mut Map<String, Value>,
     shape: &EnhancedFileShape,
     detail: DetailLevel,
+    group_by_trait: bool,
 ) {
-    let rows = impl_methods_to_rows(&shape.impl_blocks, detail);
+    let rows = if group_by_trait {
+        grouped_impl_methods_to_rows(&shape.impl_blocks, detail)
+    } else {
+        impl_methods_to_rows(&shape.impl_blocks, detail)
+    };
     if rows.is_empty() {
         return;
     }
@@ -571,23 +760,93 @@ fn functions_to_rows(functions: &[EnhancedFunctionInfo], detail: DetailLevel) ->
     functions
         .iter()
         .map(|func| {
-            let line = func.line.to_string();
+            let fields = function_fields(func, detail);
+            let refs: Vec<&str> = fields.iter().map(|s| s.as_str()).collect();
+            format::format_row(&refs)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
 
-            let mut fields: Vec<String> = Vec::new();
-            fields.push(func.name.clone());
-            fields.push(line);
-            fields.push(func.signature.clone());
+fn function_fields(func: &EnhancedFunctionInfo, detail: DetailLevel) -> Vec<String> {
+    let mut fields: Vec<String> = Vec::new();
+    fields.push(func.name.clone());
+    fields.push(func.line.to_string());
+    fields.push(func.signature.clone());
 
-            if detail == DetailLevel::Full {
-                fields.push(func.doc.clone().unwrap_or_default());
-                fields.push(func.code.clone().unwrap_or_default());
+    if detail == DetailLevel::Full {
+        fields.push(func.doc.clone().unwrap_or_default());
+        fields.push(func.code.clone().unwrap_or_default());
+    }
+
+    fields
+}
+
+/// Replace `f` with rows carrying a trailing change marker against
+/// `compare_to`: `+` added, `-` removed, `~` signature changed, `!` body
+/// changed, empty when unchanged. Removed functions are appended with their
+/// old line and signature. The rows get their own `fh` header.
+fn insert_function_changes(
+    out: &mut Map<String, Value>,
+    shape: &EnhancedFileShape,
+    file_path: &str,
+    compare_to: &str,
+    detail: DetailLevel,
+) -> Result<(), io::Error> {
+    let analysis = diff::analyze_diff(file_path, compare_to.to_string())?;
+
+    let mut markers: HashMap<&str, &str> = HashMap::new();
+    let mut removed = Vec::new();
+    for change in &analysis.structural_changes {
+        if change.symbol_type != SymbolType::Function {
+            continue;
+        }
+        let marker = match change.change_type {
+            ChangeType::Added => "+",
+            ChangeType::Removed => {
+                removed.push(change);
+                continue;
             }
+            ChangeType::SignatureChanged => "~",
+            ChangeType::BodyChanged => "!",
+        };
+        markers.insert(&change.name, marker);
+    }
 
+    let mut rows: Vec<String> = shape
+        .functions
+        .iter()
+        .map(|func| {
+            let mut fields = function_fields(func, detail);
+            fields.push(
+                markers
+                    .get(func.name.as_str())
+                    .copied()
+                    .unwrap_or_default()
+                    .to_string(),
+            );
             let refs: Vec<&str> = fields.iter().map(|s| s.as_str()).collect();
             format::format_row(&refs)
         })
-        .collect::<Vec<_>>()
-        .join("\n")
+        .collect();
+
+    for change in removed {
+        let line = change.line.to_string();
+        let signature = change.before.as_deref().unwrap_or("");
+        let row = match detail {
+            DetailLevel::Signatures => format::format_row(&[&change.name, &line, signature, "-"]),
+            DetailLevel::Full => format::format_row(&[&change.name, &line, signature, "", "", "-"]),
+        };
+        rows.push(row);
+    }
+
+    out.insert("cmp".to_string(), json!(compare_to));
+    if rows.is_empty() {
+        return Ok(());
+    }
+    out.insert("fh".to_string(), json!(format!("{}|chg", detail.header())));
+    out.insert("f".to_string(), json!(rows.join("\n")));
+    Ok(())
 }
 
 fn structs_to_rows(structs: &[EnhancedStructInfo], detail: DetailLevel) -> String {
@@ -774,6 +1033,41 @@ fn impl_methods_to_rows(impl_blocks: &[ImplBlockInfo], detail: DetailLevel) -> S
     rows.join("\n")
 }
 
+/// Group impl method rows by trait, preceding each group with a
+/// `--- trait: Name ---` separator row. Inherent impls form the `-` group.
+/// Groups keep the order in which their trait first appears in the file.
+fn grouped_impl_methods_to_rows(impl_blocks: &[ImplBlockInfo], detail: DetailLevel) -> String {
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+
+    for block in impl_blocks {
+        let trait_name = block.trait_name.as_deref().unwrap_or("-");
+        let index = match groups.iter().position(|(name, _)| name == trait_name) {
+            Some(index) => index,
+            None => {
+                groups.push((trait_name.to_string(), Vec::new()));
+                groups.len() - 1
+            }
+        };
+
+        for method in &block.methods {
+            groups[index]
+                .1
+                .push(impl_method_to_row(block, method, detail));
+        }
+    }
+
+    let mut rows = Vec::new();
+    for (trait_name, group_rows) in groups {
+        if group_rows.is_empty() {
+            continue;
+        }
+        rows.push(format!("--- trait: {trait_name} ---"));
+        rows.extend(group_rows);
+    }
+
+    rows.join("\n")
+}
+
 fn impl_method_to_row(block: &ImplBlockInfo, method: &MethodInfo, detail: DetailLevel) -> String {
     let line = method.line.to_string();
     let trait_name = block.trait_name.clone().unwrap_or_default();
@@ -846,14 +1140,21 @@ fn extract_referenced_type_names(
     main_shape: &EnhancedFileShape,
     file_path: &Path,
     focus_symbol: Option<&str>,
+    line_range: Option<(usize, usize)>,
 ) -> HashSet<String> {
     match language {
         crate::parser::Language::Rust
         | crate::parser::Language::TypeScript
+        | crate::parser::Language::Tsx
         | crate::parser::Language::Python
-        | crate::parser::Language::Go => {
-            extract_ast_position_type_names(language, source, main_shape, file_path, focus_symbol)
-        }
+        | crate::parser::Language::Go => extract_ast_position_type_names(
+            language,
+            source,
+            main_shape,
+            file_path,
+            focus_symbol,
+            line_range,
+        ),
         _ => extract_referenced_type_names_fallback(source, main_shape),
     }
 }
@@ -889,13 +1190,21 @@ fn extract_ast_position_type_names(
     main_shape: &EnhancedFileShape,
     file_path: &Path,
     focus_symbol: Option<&str>,
+    line_range: Option<(usize, usize)>,
 ) -> HashSet<String> {
     let mut names = HashSet::new();
     collect_shape_type_references(main_shape, focus_symbol, &mut names);
 
     let local_types = extract_local_type_definitions(language, source, file_path);
     if let Some(type_defs) = local_types.as_deref() {
-        collect_type_definition_references(type_defs, focus_symbol, &mut names);
+        // Types defined outside the range are not shown, so neither are
+        // the types they reference.
+        let in_range: Vec<TypeDefinition> = type_defs
+            .iter()
+            .filter(|ty| line_range.is_none_or(|range| overlaps(range, ty.line, ty.end_line)))
+            .cloned()
+            .collect();
+        collect_type_definition_references(&in_range, focus_symbol, &mut names);
         remove_local_type_names(main_shape, Some(type_defs), &mut names);
     } else {
         remove_local_type_names(main_shape, None, &mut names);
@@ -911,7 +1220,7 @@ fn extract_local_type_definitions(
 ) -> Option<Vec<TypeDefinition>> {
     match language {
         crate::parser::Language::Rust => extract_rust_types(source, file_path).ok(),
-        crate::parser::Language::TypeScript => {
+        crate::parser::Language::TypeScript | crate::parser::Language::Tsx => {
             extract_typescript_types(source, file_path, true).ok()
         }
         crate::parser::Language::Python => extract_python_types(source, file_path).ok(),
@@ -931,7 +1240,7 @@ fn collect_shape_type_references(
         }
     }
 
-    for func in &shape.functions {
+    for func in shape.functions.iter().chain(&shape.components) {
         if matches_focus(&func.name, focus_symbol) {
             collect_type_like_tokens(&func.signature, out);
         }
@@ -1083,7 +1392,7 @@ fn extract_referenced_type_names_fallback(
 ) -> HashSet<String> {
     let mut names = HashSet::new();
 
-    for func in &main_shape.functions {
+    for func in main_shape.functions.iter().chain(&main_shape.components) {
         collect_type_like_tokens(&func.signature, &mut names);
     }
 
@@ -1135,14 +1444,12 @@ fn build_dependency_rows(
     detail: DetailLevel,
     max_tokens: usize,
     comment_mode: CommentMode,
+    counter: &dyn Tokenizer,
 ) -> Result<Map<String, Value>, io::Error> {
     if dep_paths.is_empty() {
         return Ok(Map::new());
     }
 
-    let bpe = cl100k_base()
-        .map_err(|e| io::Error::other(format!("Failed to initialize tiktoken tokenizer: {e}")))?;
-
     // 10% buffer: estimates should err on the safe side.
     let mut budget_tracker = BudgetTracker::new((max_tokens * 9) / 10);
 
@@ -1182,6 +1489,7 @@ fn build_dependency_rows(
     }
 
     // Only return explicitly referenced dependency types.
+    let mut selected_deps: Vec<(&String, Vec<&TypeRow>)> = Vec::new();
     for (dep_path, rows) in &dep_type_candidates {
         let selected: Vec<&TypeRow> = rows.iter().filter(|row| row.referenced).collect();
         if selected.is_empty() {
@@ -1199,24 +1507,46 @@ fn build_dependency_rows(
         }
 
         deps.insert(dep_path.clone(), json!(rows_str));
+        selected_deps.push((dep_path, selected));
     }
 
-    // Hard enforcement: drop deps until within token budget.
+    // Hard enforcement: drop the lowest-priority rows (latest first on ties)
+    // until within token budget.
     loop {
         if deps.is_empty() {
             break;
         }
 
         let snapshot = serde_json::to_string(&json!({"deps": deps.clone()})).unwrap_or_default();
-        if bpe.encode_with_special_tokens(&snapshot).len() <= max_tokens {
+        if counter.count(&snapshot) <= max_tokens {
             break;
         }
 
-        // Remove last inserted key (Map iteration order is insertion order).
-        let Some(last_key) = deps.keys().next_back().cloned() else {
+        let Some((dep_idx, row_idx)) = selected_deps
+            .iter()
+            .enumerate()
+            .flat_map(|(dep_idx, (_, rows))| {
+                rows.iter()
+                    .enumerate()
+                    .map(move |(row_idx, row)| (dep_idx, row_idx, priority_score(row, "deps")))
+            })
+            .min_by(|a, b| a.2.cmp(&b.2).then_with(|| (b.0, b.1).cmp(&(a.0, a.1))))
+            .map(|(dep_idx, row_idx, _)| (dep_idx, row_idx))
+        else {
             break;
         };
-        deps.remove(&last_key);
+
+        let (dep_path, rows) = &mut selected_deps[dep_idx];
+        rows.remove(row_idx);
+        if rows.is_empty() {
+            deps.remove(dep_path.as_str());
+            selected_deps.remove(dep_idx);
+        } else {
+            deps.insert(
+                dep_path.to_string(),
+                json!(type_rows_to_string(rows, detail)),
+            );
+        }
     }
 
     Ok(deps)
@@ -1237,8 +1567,8 @@ fn extract_dependency_types(
     detail: DetailLevel,
     comment_mode: CommentMode,
 ) -> Result<Vec<TypeRow>, io::Error> {
-    let source = fs::read_to_string(file_path)?;
-    let language = detect_language(file_path).map_err(|e| {
+    let source = path_utils::read_source_file(file_path)?;
+    let language = detect_language_from_content(file_path, &source).map_err(|e| {
         io::Error::new(
             io::ErrorKind::Unsupported,
             format!("Cannot detect language: {e}"),
@@ -1394,11 +1724,103 @@ fn push_unique_path(paths: &mut Vec<PathBuf>, path: PathBuf) {
     paths.push(path);
 }
 
+/// Drop symbols less visible than `min_visibility`.
+///
+/// Visibility follows `symbol_inventory`; JS/TS names starting with `_` are
+/// also treated as private. Trait impl methods inherit the trait's
+/// visibility and are kept.
+fn apply_min_visibility(
+    shape: &mut EnhancedFileShape,
+    source: &str,
+    language: Language,
+    min_visibility: MinVisibility,
+) {
+    if min_visibility == MinVisibility::Private {
+        return;
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+    let keep = |name: &str, line: usize, is_member: bool| {
+        let declaration = declaration_text(&lines, line, name);
+        let visibility = if matches!(
+            language,
+            Language::JavaScript | Language::TypeScript | Language::Jsx | Language::Tsx
+        ) && name.starts_with('_')
+        {
+            "private"
+        } else {
+            visibility_of(language, name, &declaration, is_member)
+        };
+        min_visibility.allows(visibility)
+    };
+
+    // Rust impl methods are also listed as functions.
+    let trait_impl_lines: HashSet<usize> = shape
+        .impl_blocks
+        .iter()
+        .filter(|block| block.trait_name.is_some())
+        .flat_map(|block| block.methods.iter().map(|m| m.line))
+        .collect();
+    shape
+        .functions
+        .retain(|f| trait_impl_lines.contains(&f.line) || keep(&f.name, f.line, false));
+    shape.components.retain(|c| keep(&c.name, c.line, false));
+    shape.structs.retain(|s| keep(&s.name, s.line, false));
+    shape.classes.retain(|c| keep(&c.name, c.line, false));
+    for class in &mut shape.classes {
+        class.methods.retain(|m| keep(&m.name, m.line, true));
+    }
+    shape.traits.retain(|t| keep(&t.name, t.line, false));
+    shape.interfaces.retain(|i| keep(&i.name, i.line, false));
+    shape.properties.retain(|p| keep(&p.name, p.line, true));
+    for block in &mut shape.impl_blocks {
+        if block.trait_name.is_none() {
+            block.methods.retain(|m| keep(&m.name, m.line, true));
+        }
+    }
+    shape.impl_blocks.retain(|block| !block.methods.is_empty());
+}
+
+/// Keep only symbols whose lines overlap the inclusive `range`; imports
+/// outside it are dropped too, so deps follow the remaining symbols.
+fn apply_line_range(shape: &mut EnhancedFileShape, range: (usize, usize)) {
+    shape
+        .functions
+        .retain(|f| overlaps(range, f.line, f.end_line));
+    shape
+        .components
+        .retain(|c| overlaps(range, c.line, c.end_line));
+    shape
+        .structs
+        .retain(|s| overlaps(range, s.line, s.end_line));
+    shape
+        .classes
+        .retain(|c| overlaps(range, c.line, c.end_line));
+    shape.traits.retain(|t| overlaps(range, t.line, t.end_line));
+    shape
+        .interfaces
+        .retain(|i| overlaps(range, i.line, i.end_line));
+    shape
+        .properties
+        .retain(|p| overlaps(range, p.line, p.end_line));
+    for block in &mut shape.impl_blocks {
+        block
+            .methods
+            .retain(|m| overlaps(range, m.line, m.end_line));
+    }
+    shape.impl_blocks.retain(|block| !block.methods.is_empty());
+    shape.imports.retain(|i| overlaps(range, i.line, i.line));
+}
+
+fn overlaps((start, end): (usize, usize), line: usize, end_line: usize) -> bool {
+    line <= end && end_line.max(line) >= start
+}
+
 /// Apply focus to show full code only for the specified symbol
 fn apply_focus(shape: &mut EnhancedFileShape, focus_symbol: &str) {
     let mut found = false;
 
-    for func in &mut shape.functions {
+    for func in shape.functions.iter_mut().chain(&mut shape.components) {
         if func.name == focus_symbol {
             found = true;
         } else {
@@ -1473,10 +1895,39 @@ fn remove_last_dep_entry(out: &mut Map<String, Value>) -> bool {
     true
 }
 
-fn shrink_symbol_tables(out: &mut Map<String, Value>) -> bool {
-    // Prefer removing rows from the largest table first.
+/// Trim priority of an output section; lower priorities are trimmed first.
+///
+/// Imports outrank free functions, which outrank local types and their
+/// members and methods; dependency types rank lowest.
+fn section_priority(shape_section: &str) -> u32 {
+    match shape_section {
+        "im" => 4,
+        "f" | "co" => 3,
+        "s" | "c" | "i" | "pr" | "ci" | "tm" | "cm" | "bm" => 2,
+        _ => 1,
+    }
+}
+
+/// Trim priority of a type row in `shape_section`; rows referenced from the
+/// main file rank above unreferenced rows of the same section.
+fn priority_score(symbol: &TypeRow, shape_section: &str) -> u32 {
+    section_priority(shape_section) * 2 + u32::from(symbol.referenced)
+}
+
+fn token_counts(functions: &[EnhancedFunctionInfo]) -> Vec<Option<usize>> {
+    functions.iter().map(|func| func.token_count).collect()
+}
+
+/// Drop one row from the lowest-priority table. Rows with a known token cost
+/// in `row_costs` go most expensive first, so the budget keeps as many
+/// symbols as possible; other tables lose their last row.
+fn shrink_symbol_tables(
+    out: &mut Map<String, Value>,
+    row_costs: &mut HashMap<&str, Vec<Option<usize>>>,
+) -> bool {
+    // Trim the lowest-priority table first, the largest one on ties.
     let mut candidates: Vec<(&str, usize)> = Vec::new();
-    for key in ["f", "s", "c", "im", "tm", "i", "pr", "ci", "cm", "bm"] {
+    for key in ["f", "co", "s", "c", "im", "tm", "i", "pr", "ci", "cm", "bm"] {
         if let Some(rows) = out.get(key).and_then(Value::as_str) {
             let count = if rows.is_empty() {
                 0
@@ -1487,7 +1938,7 @@ fn shrink_symbol_tables(out: &mut Map<String, Value>) -> bool {
         }
     }
 
-    candidates.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    candidates.sort_by_key(|(key, count)| (section_priority(key), std::cmp::Reverse(*count)));
     let Some((key, _)) = candidates.first().copied() else {
         return false;
     };
@@ -1502,10 +1953,24 @@ fn shrink_symbol_tables(out: &mut Map<String, Value>) -> bool {
     }
 
     let mut lines: Vec<&str> = rows.lines().collect();
-    if lines.pop().is_none() {
+    let Some(last) = lines.len().checked_sub(1) else {
         remove_table_and_header(out, key);
         return true;
-    }
+    };
+    let index = match row_costs.get_mut(key) {
+        Some(costs) if costs.len() == lines.len() => {
+            let index = costs
+                .iter()
+                .enumerate()
+                .filter_map(|(index, cost)| cost.map(|cost| (cost, index)))
+                .max()
+                .map_or(last, |(_, index)| index);
+            costs.remove(index);
+            index
+        }
+        _ => last,
+    };
+    lines.remove(index);
 
     let new_rows = lines.join("\n");
     if new_rows.is_empty() {
@@ -1521,6 +1986,7 @@ fn remove_table_and_header(out: &mut Map<String, Value>, key: &str) {
     out.remove(key);
 
     let header_key = match key {
+        "f" => Some("fh"),
         "im" => Some("ih"),
         "tm" => Some("th"),
         "i" => Some("ah"),
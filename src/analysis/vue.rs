@@ -0,0 +1,573 @@
+//! Vue Component Tool
+//!
+//! Summarizes a `.vue` single-file component. The file is parsed with the
+//! HTML grammar to find its `<template>`, `<script>` and `<style>` sections;
+//! scripts are parsed as JavaScript, or as TypeScript with `lang="ts"`.
+//!
+//! Output schema:
+//! ```json
+//! {
+//!   "file": "src/components/Counter.vue",
+//!   "ph": "name|type|default|line",
+//!   "props": "title|string||9\ncount|number|0|10",
+//!   "emits": "change\nreset",
+//!   "exposed": "reset",
+//!   "composables": "total: ref\ndoubled: computed\n{ x, y }: useMouse",
+//!   "data": "",
+//!   "methods": "",
+//!   "computed": "",
+//!   "classes": "counter\nlabel"
+//! }
+//! ```
+//!
+//! The Composition API is read from `<script setup>`: `defineProps`
+//! (runtime object or array, TypeScript type argument or named interface,
+//! defaults from `withDefaults`), `defineEmits`, `defineExpose`, and
+//! top-level variables initialized by `ref`, `reactive`, `computed` or a
+//! `use*` composable, listed as `name: function`. The Options API is read
+//! from the `export default` object of a plain `<script>` (optionally
+//! wrapped in `defineComponent`): `props`, `emits`, the keys returned by
+//! `data()`, `methods` and `computed`. Classes are the class selectors of
+//! `<style scoped>` blocks. Lines are lines of the `.vue` file.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::analysis::path_utils;
+use crate::analysis::svelte::{
+    css_class_names, has_attribute, is_typescript, raw_text, start_tag, text, type_text, PropInfo,
+};
+use crate::common::format;
+use crate::mcp_types::{CallToolResult, CallToolResultExt};
+use crate::parser::{cache, parse_code, Language};
+
+const PROPS_HEADER: &str = "name|type|default|line";
+
+/// Vue reactivity functions whose results are listed as composables,
+/// alongside any `use*` call
+const REACTIVITY_FUNCTIONS: &[&str] = &[
+    "ref",
+    "shallowRef",
+    "reactive",
+    "shallowReactive",
+    "computed",
+    "readonly",
+    "toRef",
+    "toRefs",
+];
+
+/// What a Vue component accepts, emits and keeps as state
+#[derive(Debug, Clone, Default)]
+pub struct VueShape {
+    pub props: Vec<PropInfo>,
+    pub emits: Vec<String>,
+    /// Keys passed to `defineExpose`
+    pub exposed: Vec<String>,
+    /// `<script setup>` variables as `name: function`
+    pub composables: Vec<String>,
+    /// Options API `data()` keys
+    pub data: Vec<String>,
+    /// Options API `methods` keys
+    pub methods: Vec<String>,
+    /// Options API `computed` keys
+    pub computed: Vec<String>,
+    /// Class selectors defined in `<style scoped>` blocks
+    pub scoped_classes: Vec<String>,
+}
+
+pub fn execute_vue_shape(arguments: &Value) -> Result<CallToolResult, io::Error> {
+    let file_path = arguments["file_path"].as_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Missing or invalid 'file_path' argument",
+        )
+    })?;
+
+    log::info!("Extracting Vue component shape: {file_path}");
+
+    let source = path_utils::read_source_file(file_path).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Failed to read file {file_path}: {e}"),
+        )
+    })?;
+
+    let parsed = cache::shared()
+        .parse(Path::new(file_path), &source, Language::Html)
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse Vue component: {e}"),
+            )
+        })?;
+    let shape = extract_vue_shape(parsed.0.root_node(), &source)?;
+
+    let props: Vec<String> = shape
+        .props
+        .iter()
+        .map(|prop| {
+            format::format_row(&[
+                &prop.name,
+                prop.type_annotation.as_deref().unwrap_or(""),
+                prop.default.as_deref().unwrap_or(""),
+                &prop.line.to_string(),
+            ])
+        })
+        .collect();
+
+    let result = json!({
+        "file": path_utils::to_relative_path(file_path),
+        "ph": PROPS_HEADER,
+        "props": props.join("\n"),
+        "emits": shape.emits.join("\n"),
+        "exposed": shape.exposed.join("\n"),
+        "composables": shape.composables.join("\n"),
+        "data": shape.data.join("\n"),
+        "methods": shape.methods.join("\n"),
+        "computed": shape.computed.join("\n"),
+        "classes": shape.scoped_classes.join("\n"),
+    });
+
+    let json_text = serde_json::to_string(&result).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to serialize result to JSON: {e}"),
+        )
+    })?;
+
+    Ok(CallToolResult::success(json_text))
+}
+
+/// Shape of a component whose file (parsed with the HTML grammar) is rooted
+/// at `root`
+pub fn extract_vue_shape(root: Node, source: &str) -> Result<VueShape, io::Error> {
+    let mut shape = VueShape::default();
+    let mut cursor = root.walk();
+    for section in root.named_children(&mut cursor) {
+        let Some(raw) = raw_text(section) else {
+            continue;
+        };
+        let has = |name| start_tag(section).is_some_and(|tag| has_attribute(tag, source, name));
+        match section.kind() {
+            "script_element" => {
+                let language = if is_typescript(section, source) {
+                    Language::TypeScript
+                } else {
+                    Language::JavaScript
+                };
+                let script = Script::parse(raw, source, language)?;
+                if has("setup") {
+                    script.collect_composition(&mut shape);
+                } else {
+                    script.collect_options(&mut shape);
+                }
+            }
+            "style_element" if has("scoped") => {
+                for name in css_class_names(text(raw, source))? {
+                    if !shape.scoped_classes.contains(&name) {
+                        shape.scoped_classes.push(name);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(shape)
+}
+
+/// A parsed `<script>` block
+struct Script<'a> {
+    text: &'a str,
+    tree: tree_sitter::Tree,
+    /// Row of the block's first line in the `.vue` file
+    line_offset: usize,
+}
+
+impl<'a> Script<'a> {
+    fn parse(raw: Node, source: &'a str, language: Language) -> Result<Self, io::Error> {
+        let script = text(raw, source);
+        let tree = parse_code(script, language).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse <script> block as {}: {e}", language.name()),
+            )
+        })?;
+        Ok(Script {
+            text: script,
+            tree,
+            line_offset: raw.start_position().row,
+        })
+    }
+
+    fn text(&self, node: Node) -> &'a str {
+        text(node, self.text)
+    }
+
+    fn line(&self, node: Node) -> usize {
+        self.line_offset + node.start_position().row + 1
+    }
+
+    /// `defineProps`, `defineEmits`, `defineExpose` and reactive variables
+    fn collect_composition(&self, shape: &mut VueShape) {
+        let root = self.tree.root_node();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if node.kind() == "call_expression" {
+                let function = node
+                    .child_by_field_name("function")
+                    .map(|function| self.text(function));
+                match function {
+                    Some("defineProps") => self.collect_define_props(node, &mut shape.props),
+                    Some("defineEmits") => {
+                        if let Some(object_type) = type_argument(node) {
+                            self.collect_typed_emits(object_type, &mut shape.emits);
+                        } else if let Some(emits) = first_argument(node) {
+                            self.collect_runtime_names(emits, &mut shape.emits);
+                        }
+                    }
+                    Some("defineExpose") => {
+                        if let Some(object) = first_argument(node) {
+                            self.collect_runtime_names(object, &mut shape.exposed);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let mut cursor = node.walk();
+            let children: Vec<Node> = node.named_children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+
+        let mut cursor = root.walk();
+        for statement in root.named_children(&mut cursor) {
+            if matches!(
+                statement.kind(),
+                "lexical_declaration" | "variable_declaration"
+            ) {
+                self.collect_composables(statement, &mut shape.composables);
+            }
+        }
+    }
+
+    /// The `export default {...}` or `export default defineComponent({...})`
+    /// options object
+    fn collect_options(&self, shape: &mut VueShape) {
+        let root = self.tree.root_node();
+        let mut cursor = root.walk();
+        let Some(options) = root
+            .named_children(&mut cursor)
+            .filter(|statement| statement.kind() == "export_statement")
+            .find_map(|statement| statement.child_by_field_name("value"))
+            .and_then(|value| match value.kind() {
+                "object" => Some(value),
+                "call_expression" => first_argument(value),
+                _ => None,
+            })
+            .filter(|options| options.kind() == "object")
+        else {
+            return;
+        };
+
+        let mut cursor = options.walk();
+        for option in options.named_children(&mut cursor) {
+            let Some(key) = option.child_by_field_name(match option.kind() {
+                "pair" => "key",
+                "method_definition" => "name",
+                _ => continue,
+            }) else {
+                continue;
+            };
+            let value = option.child_by_field_name("value");
+            match (unquote(self.text(key)), value) {
+                ("props", Some(props)) => self.collect_runtime_props(props, None, &mut shape.props),
+                ("emits", Some(emits)) => self.collect_runtime_names(emits, &mut shape.emits),
+                ("methods", Some(methods)) => {
+                    self.collect_runtime_names(methods, &mut shape.methods)
+                }
+                ("computed", Some(computed)) => {
+                    self.collect_runtime_names(computed, &mut shape.computed)
+                }
+                ("data", _) => {
+                    if let Some(state) = returned_object(value.unwrap_or(option)) {
+                        self.collect_runtime_names(state, &mut shape.data);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn collect_define_props(&self, call: Node, props: &mut Vec<PropInfo>) {
+        let defaults = self.with_defaults(call);
+        if let Some(type_argument) = type_argument(call) {
+            let members = match type_argument.kind() {
+                "object_type" => Some(type_argument),
+                "type_identifier" => self.type_members(self.text(type_argument)),
+                _ => None,
+            };
+            if let Some(members) = members {
+                self.collect_typed_props(members, &defaults, props);
+            }
+        } else if let Some(runtime) = first_argument(call) {
+            self.collect_runtime_props(runtime, Some(&defaults), props);
+        }
+    }
+
+    /// Defaults from `withDefaults(defineProps<...>(), { count: 0 })`
+    fn with_defaults(&self, define_props: Node) -> HashMap<&'a str, &'a str> {
+        let mut defaults = HashMap::new();
+        let Some(object) = define_props
+            .parent()
+            .and_then(|arguments| arguments.parent())
+            .filter(|call| {
+                call.child_by_field_name("function")
+                    .is_some_and(|function| self.text(function) == "withDefaults")
+            })
+            .and_then(|call| call.child_by_field_name("arguments"))
+            .and_then(|arguments| arguments.named_child(1))
+            .filter(|object| object.kind() == "object")
+        else {
+            return defaults;
+        };
+
+        let mut cursor = object.walk();
+        for pair in object.named_children(&mut cursor) {
+            if let (Some(key), Some(value)) = (
+                pair.child_by_field_name("key"),
+                pair.child_by_field_name("value"),
+            ) {
+                defaults.insert(unquote(self.text(key)), self.text(value));
+            }
+        }
+        defaults
+    }
+
+    /// Members of the interface or object type alias named `name`
+    fn type_members(&self, name: &str) -> Option<Node<'_>> {
+        let root = self.tree.root_node();
+        let mut cursor = root.walk();
+        let statements: Vec<Node> = root.named_children(&mut cursor).collect();
+        statements.into_iter().find_map(|statement| {
+            let declaration = if statement.kind() == "export_statement" {
+                statement.child_by_field_name("declaration")?
+            } else {
+                statement
+            };
+            let members = match declaration.kind() {
+                "interface_declaration" => declaration.child_by_field_name("body")?,
+                "type_alias_declaration" => declaration
+                    .child_by_field_name("value")
+                    .filter(|value| value.kind() == "object_type")?,
+                _ => return None,
+            };
+            let declared = declaration.child_by_field_name("name")?;
+            (self.text(declared) == name).then_some(members)
+        })
+    }
+
+    /// `{ title: string; count?: number }`
+    fn collect_typed_props(
+        &self,
+        members: Node,
+        defaults: &HashMap<&str, &str>,
+        props: &mut Vec<PropInfo>,
+    ) {
+        let mut cursor = members.walk();
+        for member in members.named_children(&mut cursor) {
+            if member.kind() != "property_signature" {
+                continue;
+            }
+            let Some(name) = member.child_by_field_name("name") else {
+                continue;
+            };
+            let name = unquote(self.text(name));
+            let optional = member
+                .children(&mut member.walk())
+                .any(|child| child.kind() == "?");
+            let type_annotation = member
+                .child_by_field_name("type")
+                .map(|ty| type_text(ty, self.text))
+                .map(|ty| if optional { format!("{ty}?") } else { ty });
+            props.push(PropInfo {
+                name: name.to_string(),
+                type_annotation,
+                default: defaults.get(name).map(|value| value.to_string()),
+                line: self.line(member),
+            });
+        }
+    }
+
+    /// `['title', 'count']` or `{ title: String, count: { type: Number, default: 0 } }`
+    fn collect_runtime_props(
+        &self,
+        runtime: Node,
+        defaults: Option<&HashMap<&str, &str>>,
+        props: &mut Vec<PropInfo>,
+    ) {
+        let mut cursor = runtime.walk();
+        for entry in runtime.named_children(&mut cursor) {
+            let (name, type_annotation, default) = match (runtime.kind(), entry.kind()) {
+                ("array", "string") => (unquote(self.text(entry)), None, None),
+                ("object", "pair") => {
+                    let Some(key) = entry.child_by_field_name("key") else {
+                        continue;
+                    };
+                    let value = entry.child_by_field_name("value");
+                    let (type_annotation, default) = match value {
+                        Some(options) if options.kind() == "object" => (
+                            self.option_value(options, "type"),
+                            self.option_value(options, "default"),
+                        ),
+                        Some(ty) => (Some(self.text(ty)), None),
+                        None => (None, None),
+                    };
+                    (unquote(self.text(key)), type_annotation, default)
+                }
+                ("object", "shorthand_property_identifier") => (self.text(entry), None, None),
+                _ => continue,
+            };
+            let default =
+                default.or_else(|| defaults.and_then(|defaults| defaults.get(name).copied()));
+            props.push(PropInfo {
+                name: name.to_string(),
+                type_annotation: type_annotation.map(str::to_string),
+                default: default.map(str::to_string),
+                line: self.line(entry),
+            });
+        }
+    }
+
+    /// Value of `key` in a runtime prop options object
+    fn option_value(&self, options: Node, key: &str) -> Option<&'a str> {
+        let mut cursor = options.walk();
+        let pairs: Vec<Node> = options
+            .named_children(&mut cursor)
+            .filter(|pair| pair.kind() == "pair")
+            .collect();
+        pairs.into_iter().find_map(|pair| {
+            let name = pair.child_by_field_name("key")?;
+            (unquote(self.text(name)) == key)
+                .then(|| pair.child_by_field_name("value"))
+                .flatten()
+                .map(|value| self.text(value))
+        })
+    }
+
+    /// `{ (e: 'change', id: number): void }` or `{ change: [id: number] }`
+    fn collect_typed_emits(&self, object_type: Node, emits: &mut Vec<String>) {
+        let mut cursor = object_type.walk();
+        for member in object_type.named_children(&mut cursor) {
+            let name = match member.kind() {
+                "property_signature" => member.child_by_field_name("name"),
+                "call_signature" => member
+                    .child_by_field_name("parameters")
+                    .and_then(|parameters| parameters.named_child(0))
+                    .and_then(|event| event.child_by_field_name("type"))
+                    .and_then(|annotation| annotation.named_child(0))
+                    .filter(|ty| ty.kind() == "literal_type"),
+                _ => None,
+            };
+            if let Some(name) = name {
+                push_unique(emits, unquote(self.text(name)));
+            }
+        }
+    }
+
+    /// Strings of an array or keys of an object
+    fn collect_runtime_names(&self, node: Node, names: &mut Vec<String>) {
+        let mut cursor = node.walk();
+        for entry in node.named_children(&mut cursor) {
+            let name = match (node.kind(), entry.kind()) {
+                ("array", "string") => Some(entry),
+                ("object", "pair") => entry.child_by_field_name("key"),
+                ("object", "method_definition") => entry.child_by_field_name("name"),
+                ("object", "shorthand_property_identifier") => Some(entry),
+                _ => None,
+            };
+            if let Some(name) = name {
+                push_unique(names, unquote(self.text(name)));
+            }
+        }
+    }
+
+    /// `const count = ref(0)`, `const { x, y } = useMouse()`
+    fn collect_composables(&self, declaration: Node, composables: &mut Vec<String>) {
+        let mut cursor = declaration.walk();
+        for declarator in declaration.named_children(&mut cursor) {
+            let (Some(name), Some(mut value)) = (
+                declarator.child_by_field_name("name"),
+                declarator.child_by_field_name("value"),
+            ) else {
+                continue;
+            };
+            if value.kind() == "await_expression" {
+                match value.named_child(0) {
+                    Some(awaited) => value = awaited,
+                    None => continue,
+                }
+            }
+            let Some(function) = value
+                .child_by_field_name("function")
+                .filter(|_| value.kind() == "call_expression")
+                .map(|function| self.text(function))
+            else {
+                continue;
+            };
+            if REACTIVITY_FUNCTIONS.contains(&function) || is_composable(function) {
+                composables.push(format!("{}: {function}", self.text(name)));
+            }
+        }
+    }
+}
+
+/// `useMouse`, not `user`
+fn is_composable(function: &str) -> bool {
+    function
+        .strip_prefix("use")
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c.is_ascii_uppercase())
+}
+
+/// The type argument of `defineProps<...>()`
+fn type_argument(call: Node) -> Option<Node> {
+    call.child_by_field_name("type_arguments")?.named_child(0)
+}
+
+fn first_argument(call: Node) -> Option<Node> {
+    call.child_by_field_name("arguments")?.named_child(0)
+}
+
+/// The object literal returned by `data() { return {...} }` or
+/// `data: () => ({...})`
+fn returned_object(function: Node) -> Option<Node> {
+    let body = function.child_by_field_name("body")?;
+    let returned = if body.kind() == "statement_block" {
+        let mut cursor = body.walk();
+        let statement = body
+            .named_children(&mut cursor)
+            .find(|statement| statement.kind() == "return_statement")?;
+        statement.named_child(0)?
+    } else {
+        body
+    };
+    let returned = if returned.kind() == "parenthesized_expression" {
+        returned.named_child(0)?
+    } else {
+        returned
+    };
+    (returned.kind() == "object").then_some(returned)
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim_matches(|c| c == '"' || c == '\'' || c == '`')
+}
+
+fn push_unique(names: &mut Vec<String>, name: &str) {
+    if !names.iter().any(|existing| existing == name) {
+        names.push(name.to_string());
+    }
+}
@@ -0,0 +1,122 @@
+//! Cargo Workspace Detection
+//!
+//! Reads `[workspace] members` (with `*` globs) and `exclude` from a root
+//! `Cargo.toml`. A root manifest that is also a `[package]` is listed as a
+//! member at the root itself.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use globset::Glob;
+use toml_edit::{Document, Item};
+
+use crate::analysis::path_utils;
+
+/// A crate belonging to a Cargo workspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    /// `package.name`, or the directory name when the manifest has none
+    pub name: String,
+    /// Member directory (joined onto the workspace root)
+    pub path: PathBuf,
+}
+
+/// Members of the Cargo workspace rooted at `root`, sorted by path.
+///
+/// Returns an empty list when `root/Cargo.toml` has no `[workspace]` table.
+pub fn list_workspace_members(root: &Path) -> Result<Vec<WorkspaceMember>, io::Error> {
+    let manifest = path_utils::read_source_file(root.join("Cargo.toml"))?;
+    let document = parse_manifest(&manifest, &root.join("Cargo.toml"))?;
+    let Some(workspace) = document.get("workspace") else {
+        return Ok(Vec::new());
+    };
+
+    let excluded: Vec<PathBuf> = string_array(workspace.get("exclude"))
+        .iter()
+        .map(|path| root.join(path))
+        .collect();
+
+    let mut dirs: Vec<PathBuf> = string_array(workspace.get("members"))
+        .iter()
+        .flat_map(|pattern| expand_member_pattern(root, pattern))
+        .filter(|dir| dir.join("Cargo.toml").is_file())
+        .filter(|dir| !excluded.iter().any(|excluded| dir.starts_with(excluded)))
+        .collect();
+    if document.get("package").is_some() {
+        dirs.push(root.to_path_buf());
+    }
+    dirs.sort();
+    dirs.dedup();
+
+    Ok(dirs
+        .into_iter()
+        .map(|path| WorkspaceMember {
+            name: package_name(&path).unwrap_or_else(|| directory_name(&path)),
+            path,
+        })
+        .collect())
+}
+
+fn parse_manifest(manifest: &str, path: &Path) -> Result<Document<String>, io::Error> {
+    Document::parse(manifest.to_string()).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to parse {}: {e}", path.display()),
+        )
+    })
+}
+
+fn string_array(item: Option<&Item>) -> Vec<String> {
+    item.and_then(Item::as_array)
+        .map(|array| {
+            array
+                .iter()
+                .filter_map(|value| value.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Directories matching a member entry such as `crates/*` or `tools/cli`.
+fn expand_member_pattern(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+    for component in pattern.split('/').filter(|c| !c.is_empty() && *c != ".") {
+        if !component.contains(['*', '?', '[']) {
+            dirs = dirs.into_iter().map(|dir| dir.join(component)).collect();
+            continue;
+        }
+
+        let Ok(glob) = Glob::new(component) else {
+            log::warn!("Invalid workspace member pattern '{pattern}'");
+            return Vec::new();
+        };
+        let matcher = glob.compile_matcher();
+        dirs = dirs
+            .iter()
+            .filter_map(|dir| fs::read_dir(dir).ok())
+            .flatten()
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .filter(|path| path.file_name().is_some_and(|name| matcher.is_match(name)))
+            .collect();
+    }
+    dirs
+}
+
+fn package_name(dir: &Path) -> Option<String> {
+    let manifest = path_utils::read_source_file(dir.join("Cargo.toml")).ok()?;
+    let document = Document::parse(manifest).ok()?;
+    document
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn directory_name(dir: &Path) -> String {
+    dir.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| dir.display().to_string())
+}
@@ -0,0 +1,238 @@
+//! Pre-commit check
+//!
+//! Checks staged files (or the files given as arguments) for public
+//! functions without doc comments and functions above a cyclomatic
+//! complexity threshold.
+//!
+//! Exit codes: 0 = pass, 1 = violations found, 2 = internal error.
+//! With `GITHUB_ACTIONS=true`, violations are printed as workflow
+//! annotations (`::error file=...,line=...::message`).
+//!
+//! Files are read from the working tree, so partially staged files are
+//! checked as they currently stand on disk.
+
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+use tree_sitter::Tree;
+use treesitter_mcp::analysis::metrics::function_complexities;
+use treesitter_mcp::analysis::path_utils;
+use treesitter_mcp::analysis::shape::extract_enhanced_shape;
+use treesitter_mcp::analysis::symbol_inventory::build_inventory;
+use treesitter_mcp::parser::{detect_language, parse_code, Language};
+
+const DEFAULT_MAX_COMPLEXITY: usize = 10;
+
+const USAGE: &str = "Usage: treesitter_check [--max-complexity N] [--no-doc-check] [FILE...]
+
+Checks staged files (git diff --cached) unless FILEs are given.
+Exit codes: 0 = pass, 1 = violations found, 2 = internal error.";
+
+#[derive(Debug)]
+struct Options {
+    max_complexity: usize,
+    require_docs: bool,
+    files: Vec<String>,
+}
+
+#[derive(Debug)]
+struct Violation {
+    file: String,
+    line: usize,
+    message: String,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("treesitter_check: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let violations = match run(&options) {
+        Ok(violations) => violations,
+        Err(e) => {
+            eprintln!("treesitter_check: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let github = env::var("GITHUB_ACTIONS").is_ok_and(|value| value == "true");
+    for violation in &violations {
+        if github {
+            println!(
+                "::error file={},line={}::{}",
+                violation.file, violation.line, violation.message
+            );
+        } else {
+            println!(
+                "{}:{}: {}",
+                violation.file, violation.line, violation.message
+            );
+        }
+    }
+
+    if violations.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("treesitter_check: {} violation(s)", violations.len());
+        ExitCode::from(1)
+    }
+}
+
+/// `Ok(None)` when help was requested.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        max_complexity: DEFAULT_MAX_COMPLEXITY,
+        require_docs: true,
+        files: Vec::new(),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--no-doc-check" => options.require_docs = false,
+            "--max-complexity" => {
+                let value = args.next().ok_or("--max-complexity requires a value")?;
+                options.max_complexity = value
+                    .parse()
+                    .map_err(|_| format!("Invalid --max-complexity value: {value}"))?;
+            }
+            other if other.starts_with("--") => return Err(format!("Unknown option: {other}")),
+            _ => options.files.push(arg),
+        }
+    }
+
+    Ok(Some(options))
+}
+
+fn run(options: &Options) -> Result<Vec<Violation>, String> {
+    let files: Vec<(String, PathBuf)> = if options.files.is_empty() {
+        staged_files()?
+    } else {
+        options
+            .files
+            .iter()
+            .map(|file| (file.clone(), PathBuf::from(file)))
+            .collect()
+    };
+
+    let mut violations = Vec::new();
+    for (display, path) in files {
+        // Unsupported languages (Markdown, TOML, ...) are not checked.
+        let Ok(language) = detect_language(&path) else {
+            continue;
+        };
+        let source = path_utils::read_source_file(&path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let tree = parse_code(&source, language)
+            .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+
+        if options.require_docs {
+            for (name, line) in undocumented_public_functions(&path, &tree, &source, language)? {
+                violations.push(Violation {
+                    file: display.clone(),
+                    line,
+                    message: format!("Public function `{name}` has no doc comment"),
+                });
+            }
+        }
+
+        for function in function_complexities(&tree, &source, language) {
+            if function.complexity > options.max_complexity {
+                violations.push(Violation {
+                    file: display.clone(),
+                    line: function.line,
+                    message: format!(
+                        "Function `{}` has cyclomatic complexity {} (max {})",
+                        function.name, function.complexity, options.max_complexity
+                    ),
+                });
+            }
+        }
+    }
+
+    violations.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    Ok(violations)
+}
+
+/// Paths of added, copied, modified and renamed files in the index.
+fn staged_files() -> Result<Vec<(String, PathBuf)>, String> {
+    let root = git(&["rev-parse", "--show-toplevel"])?;
+    let root = Path::new(root.trim());
+    let names = git(&["diff", "--name-only", "--cached", "--diff-filter=ACMR"])?;
+
+    Ok(names
+        .lines()
+        .filter(|name| !name.is_empty())
+        .map(|name| (name.to_string(), root.join(name)))
+        .collect())
+}
+
+fn git(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Public functions and methods without a doc comment.
+///
+/// Trait impl methods are skipped: their documentation lives on the trait.
+fn undocumented_public_functions(
+    path: &Path,
+    tree: &Tree,
+    source: &str,
+    language: Language,
+) -> Result<Vec<(String, usize)>, String> {
+    let shape = extract_enhanced_shape(tree, source, language, None, false)
+        .map_err(|e| format!("Failed to extract {}: {e}", path.display()))?;
+
+    let mut documented: HashMap<usize, bool> = HashMap::new();
+    let mut note = |line: usize, has_doc: bool| {
+        *documented.entry(line).or_default() |= has_doc;
+    };
+    for function in shape.functions.iter().chain(&shape.components) {
+        note(function.line, function.doc.is_some());
+    }
+    for class in &shape.classes {
+        for method in &class.methods {
+            note(method.line, method.doc.is_some());
+        }
+    }
+    let mut trait_impl_lines = Vec::new();
+    for block in &shape.impl_blocks {
+        for method in &block.methods {
+            note(method.line, method.doc.is_some());
+            if block.trait_name.is_some() {
+                trait_impl_lines.push(method.line);
+            }
+        }
+    }
+
+    let inventory = build_inventory(path);
+    Ok(inventory
+        .functions
+        .into_iter()
+        .filter(|entry| entry.visibility == "public")
+        .filter(|entry| !trait_impl_lines.contains(&entry.line))
+        .filter(|entry| documented.get(&entry.line) == Some(&false))
+        .map(|entry| (entry.name, entry.line))
+        .collect())
+}
@@ -0,0 +1,198 @@
+//! Command-line front end
+//!
+//! `treesitter-mcp --cli <subcommand>` runs one analysis and prints its JSON
+//! result to stdout, for use without an MCP client:
+//!
+//! - `shape <file>` → `view_code` with signatures only
+//! - `types <path>` → `type_map`
+//! - `usages <symbol> <path>` → `find_usages`
+//! - `diff <file> [--compare REV]` → `parse_diff`
+//! - `complexity <path> [--threshold N]` → `complexity`
+//!
+//! Arguments are turned into a tool call and routed through the MCP handler,
+//! so the output is exactly what the tool returns. `--pretty` indents it.
+//! `types --stream` prints one JSON object per type as it is found (NDJSON),
+//! ending with a `{"total_count": N}` line.
+//! Exit codes: 0 = success, 1 = the tool failed, 2 = invalid arguments.
+
+use std::io;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use rust_mcp_sdk::schema::{CallToolRequestParams, CallToolResult};
+use serde_json::{json, Map, Value};
+
+use crate::common::cancel::CancellationToken;
+use crate::handler::TreesitterServerHandler;
+use crate::mcp::streaming::{self, StreamingWriter};
+
+fn command() -> Command {
+    Command::new("treesitter-mcp --cli")
+        .about("Run one tree-sitter analysis and print its JSON result")
+        .subcommand_required(true)
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .global(true)
+                .value_name("PATH")
+                .help("Config file (default: treesitter-mcp.toml in the current directory)"),
+        )
+        .arg(
+            Arg::new("pretty")
+                .long("pretty")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Pretty-print the JSON output"),
+        )
+        .subcommand(
+            Command::new("shape")
+                .about("Signatures of the functions and types in a file")
+                .arg(Arg::new("file").required(true)),
+        )
+        .subcommand(
+            Command::new("types")
+                .about("Type definitions in a file or directory")
+                .arg(Arg::new("path").required(true))
+                .arg(
+                    Arg::new("stream")
+                        .long("stream")
+                        .action(ArgAction::SetTrue)
+                        .help("Print each type as soon as it is found, one JSON object per line"),
+                ),
+        )
+        .subcommand(
+            Command::new("usages")
+                .about("Usages of a symbol in a file or directory")
+                .arg(Arg::new("symbol").required(true))
+                .arg(Arg::new("path").required(true)),
+        )
+        .subcommand(
+            Command::new("diff")
+                .about("Structural changes of a file against a git revision")
+                .arg(Arg::new("file").required(true))
+                .arg(
+                    Arg::new("compare")
+                        .long("compare")
+                        .default_value("HEAD")
+                        .help("Git revision to compare against"),
+                ),
+        )
+        .subcommand(
+            Command::new("complexity")
+                .about("Cyclomatic complexity of every function")
+                .arg(Arg::new("path").required(true))
+                .arg(
+                    Arg::new("threshold")
+                        .long("threshold")
+                        .value_parser(clap::value_parser!(u32))
+                        .help("Flag functions above this complexity (default: 10)"),
+                ),
+        )
+}
+
+/// Run the subcommand in `args` (the command line without `--cli`) and
+/// return the process exit code.
+///
+/// `--config` was already applied to `handler`; it is accepted here so it
+/// can appear anywhere on the command line.
+pub fn run(args: &[String], handler: &TreesitterServerHandler) -> i32 {
+    let matches = match command().try_get_matches_from(
+        std::iter::once("treesitter-mcp --cli".to_string()).chain(args.iter().cloned()),
+    ) {
+        Ok(matches) => matches,
+        Err(e) => {
+            let _ = e.print();
+            return if e.use_stderr() { 2 } else { 0 };
+        }
+    };
+
+    let Some((subcommand, sub_matches)) = matches.subcommand() else {
+        return 2;
+    };
+    let (tool, arguments) = tool_call(subcommand, sub_matches);
+    let request = CallToolRequestParams {
+        name: tool.to_string(),
+        arguments: Some(arguments),
+        meta: None,
+        task: None,
+    };
+
+    if streaming::wants_stream(&request) {
+        let mut writer = StreamingWriter::new(io::stdout().lock());
+        return match streaming::stream_tool_call(
+            &request,
+            &CancellationToken::default(),
+            &mut writer,
+        ) {
+            Ok(_) => 0,
+            Err(e) => {
+                eprintln!("{e}");
+                1
+            }
+        };
+    }
+
+    let result = match handler.call_tool(request, &CancellationToken::default()) {
+        Ok(result) if result.is_error != Some(true) => result,
+        Ok(result) => {
+            eprintln!("{}", result_text(&result).unwrap_or_default());
+            return 1;
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            return 1;
+        }
+    };
+
+    let text = result_text(&result).unwrap_or_default();
+    let output = match serde_json::from_str::<Value>(&text) {
+        Ok(value) if matches.get_flag("pretty") => {
+            serde_json::to_string_pretty(&value).unwrap_or(text)
+        }
+        Ok(value) => value.to_string(),
+        // Not every tool answers with JSON; print other text unchanged.
+        Err(_) => text,
+    };
+    println!("{output}");
+    0
+}
+
+/// Tool name and arguments for a subcommand
+fn tool_call(subcommand: &str, matches: &ArgMatches) -> (&'static str, Map<String, Value>) {
+    let arg = |name: &str| matches.get_one::<String>(name).cloned();
+    let (tool, arguments) = match subcommand {
+        "shape" => (
+            "view_code",
+            json!({"file_path": arg("file"), "detail": "signatures"}),
+        ),
+        "types" => (
+            "type_map",
+            json!({"path": arg("path"), "stream": matches.get_flag("stream")}),
+        ),
+        "usages" => (
+            "find_usages",
+            json!({"symbol": arg("symbol"), "path": arg("path")}),
+        ),
+        "diff" => (
+            "parse_diff",
+            json!({"file_path": arg("file"), "compare_to": arg("compare")}),
+        ),
+        _ => (
+            "complexity",
+            json!({
+                "path": arg("path"),
+                "threshold": matches.get_one::<u32>("threshold"),
+            }),
+        ),
+    };
+    (tool, arguments.as_object().cloned().unwrap_or_default())
+}
+
+/// Text of the first content block, plain or embedded as a resource
+fn result_text(result: &CallToolResult) -> Option<String> {
+    let value = serde_json::to_value(result).ok()?;
+    let content = &value["content"][0];
+    content["text"]
+        .as_str()
+        .or_else(|| content["resource"]["text"].as_str())
+        .map(str::to_string)
+}
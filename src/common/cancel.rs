@@ -0,0 +1,111 @@
+//! Cooperative cancellation for long-running analyses
+//!
+//! Directory walks check a [`CancellationToken`] every few files and stop
+//! with a [`CANCELLED`] error once the client has abandoned the request.
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Error message of a cancelled operation
+pub const CANCELLED: &str = "cancelled";
+/// Files processed between two checks of the token
+pub const DEFAULT_CHECK_INTERVAL: usize = 100;
+
+/// Shared flag set when the request running an analysis is cancelled.
+///
+/// Clones share the flag, so the transport keeps one clone and the analysis
+/// another.
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    check_interval: usize,
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::with_check_interval(DEFAULT_CHECK_INTERVAL)
+    }
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A token checked every `check_interval` files (at least every file).
+    pub fn with_check_interval(check_interval: usize) -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            check_interval: check_interval.max(1),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Fail with [`cancelled_error`] if cancelled and `files_seen` is a
+    /// multiple of the check interval.
+    pub fn check(&self, files_seen: usize) -> Result<(), io::Error> {
+        if files_seen.is_multiple_of(self.check_interval) && self.is_cancelled() {
+            return Err(cancelled_error());
+        }
+        Ok(())
+    }
+}
+
+/// The error returned by cancelled operations
+pub fn cancelled_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Interrupted, CANCELLED)
+}
+
+/// Tokens of the in-flight requests of one client, keyed by JSON-RPC id.
+#[derive(Debug, Default)]
+pub struct CancellationRegistry {
+    tokens: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl CancellationRegistry {
+    /// Register a fresh token for the request `id`.
+    pub fn register(&self, id: &serde_json::Value) -> CancellationToken {
+        let token = CancellationToken::new();
+        self.lock().insert(id.to_string(), token.clone());
+        token
+    }
+
+    /// Cancel the request `id`; `false` if it is not in flight.
+    pub fn cancel(&self, id: &serde_json::Value) -> bool {
+        match self.lock().get(&id.to_string()) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancel every request in flight, e.g. when the client disconnects.
+    pub fn cancel_all(&self) {
+        for token in self.lock().values() {
+            token.cancel();
+        }
+    }
+
+    /// Forget the request `id` once it has been answered.
+    pub fn finish(&self, id: &serde_json::Value) {
+        self.lock().remove(&id.to_string());
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CancellationToken>> {
+        // Every update is a single map call, so a poisoned map is still valid.
+        self.tokens
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
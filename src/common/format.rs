@@ -1,18 +1,84 @@
-fn escape_field(s: &str) -> String {
-    // Keep row format parseable:
-    // - rows split on "\n"
-    // - columns split on "|"
-    // So we must escape backslashes first, then delimiters/newlines.
-    s.replace('\\', "\\\\")
-        .replace('\n', "\\n")
-        .replace('\r', "\\r")
-        .replace('|', "\\|")
+use std::borrow::Cow;
+
+/// Escape a field for the compact row format.
+///
+/// Rows split on "\n" and columns split on "|", so backslashes are escaped
+/// first, then delimiters and newlines. Fields without any of these are
+/// returned as-is.
+pub fn escape_pipe(s: &str) -> Cow<'_, str> {
+    if !s.contains(['\\', '\n', '\r', '|']) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut escaped = String::with_capacity(s.len() + 8);
+    for ch in s.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '|' => escaped.push_str("\\|"),
+            other => escaped.push(other),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Reverse [`escape_pipe`] for a single field.
+///
+/// Unknown escape sequences and a trailing lone backslash are kept verbatim.
+pub fn unescape_pipe(s: &str) -> Cow<'_, str> {
+    if !s.contains('\\') {
+        return Cow::Borrowed(s);
+    }
+
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            unescaped.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('|') => unescaped.push('|'),
+            Some('\\') => unescaped.push('\\'),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    Cow::Owned(unescaped)
 }
 
 pub fn format_row(fields: &[&str]) -> String {
     fields
         .iter()
-        .map(|field| escape_field(field))
+        .map(|field| escape_pipe(field))
         .collect::<Vec<_>>()
         .join("|")
 }
+
+/// Split a row produced by [`format_row`] back into unescaped fields.
+pub fn parse_row(row: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (index, ch) in row.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '|' => {
+                fields.push(unescape_pipe(&row[start..index]).into_owned());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    fields.push(unescape_pipe(&row[start..]).into_owned());
+    fields
+}
@@ -1,7 +1,9 @@
 pub mod budget;
+pub mod cancel;
 pub mod compact;
 pub mod format;
 pub mod project_files;
+pub mod tokenizer;
 
 #[cfg(test)]
 mod tests;
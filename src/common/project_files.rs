@@ -2,11 +2,42 @@ use std::collections::HashSet;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::RwLock;
 
 use walkdir::{DirEntry, WalkDir};
 
+use crate::analysis::path_utils::is_binary_file;
+
 const LEGACY_IGNORED_DIR_NAMES: &[&str] = &["target", "node_modules", "vendor", "build", "dist"];
 
+/// Size limit applied to walked files unless configured otherwise
+pub const DEFAULT_MAX_FILE_BYTES: usize = 1024 * 1024;
+
+/// Walk settings from the server configuration
+#[derive(Debug)]
+struct WalkSettings {
+    extra_ignored_dirs: Vec<String>,
+    max_file_bytes: usize,
+}
+
+static WALK_SETTINGS: RwLock<WalkSettings> = RwLock::new(WalkSettings {
+    extra_ignored_dirs: Vec::new(),
+    max_file_bytes: DEFAULT_MAX_FILE_BYTES,
+});
+
+/// Skip directories named in `extra_ignored_dirs` (in addition to `target`,
+/// `node_modules`, ...) and files over `max_file_bytes` (0 = no limit) in
+/// every later walk.
+pub fn configure(extra_ignored_dirs: Vec<String>, max_file_bytes: usize) {
+    let mut settings = WALK_SETTINGS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *settings = WalkSettings {
+        extra_ignored_dirs,
+        max_file_bytes,
+    };
+}
+
 #[derive(Debug, Default)]
 struct IgnoredPaths {
     exact: HashSet<PathBuf>,
@@ -98,11 +129,15 @@ pub fn collect_project_files(root: &Path) -> Result<Vec<PathBuf>, io::Error> {
     }
 
     let ignored = IgnoredPaths::for_root(root);
+    let settings = WALK_SETTINGS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
     let mut files = Vec::new();
 
     let walker = WalkDir::new(root)
+        .sort_by_file_name()
         .into_iter()
-        .filter_entry(|entry| should_descend(entry, &ignored));
+        .filter_entry(|entry| should_descend(entry, &ignored, &settings.extra_ignored_dirs));
 
     for entry in walker {
         let entry = entry
@@ -116,6 +151,25 @@ pub fn collect_project_files(root: &Path) -> Result<Vec<PathBuf>, io::Error> {
             continue;
         }
 
+        if settings.max_file_bytes > 0 {
+            if let Ok(metadata) = entry.metadata() {
+                if metadata.len() > settings.max_file_bytes as u64 {
+                    log::warn!(
+                        "Skipping {} ({} bytes, limit {})",
+                        entry.path().display(),
+                        metadata.len(),
+                        settings.max_file_bytes
+                    );
+                    continue;
+                }
+            }
+        }
+
+        if is_binary_file(entry.path()) {
+            log::debug!("Skipping binary file {}", entry.path().display());
+            continue;
+        }
+
         files.push(entry.into_path());
     }
 
@@ -123,7 +177,7 @@ pub fn collect_project_files(root: &Path) -> Result<Vec<PathBuf>, io::Error> {
     Ok(files)
 }
 
-fn should_descend(entry: &DirEntry, ignored: &IgnoredPaths) -> bool {
+fn should_descend(entry: &DirEntry, ignored: &IgnoredPaths, extra_ignored_dirs: &[String]) -> bool {
     if entry.depth() == 0 {
         return true;
     }
@@ -136,12 +190,12 @@ fn should_descend(entry: &DirEntry, ignored: &IgnoredPaths) -> bool {
         return true;
     }
 
-    !LEGACY_IGNORED_DIR_NAMES.iter().any(|name| {
-        entry
-            .file_name()
-            .to_string_lossy()
-            .eq_ignore_ascii_case(name)
-    })
+    let name = entry.file_name().to_string_lossy();
+    !LEGACY_IGNORED_DIR_NAMES
+        .iter()
+        .copied()
+        .chain(extra_ignored_dirs.iter().map(String::as_str))
+        .any(|ignored_name| name.eq_ignore_ascii_case(ignored_name))
 }
 
 fn is_hidden_name(name: &std::ffi::OsStr) -> bool {
@@ -1,5 +1,7 @@
+use std::borrow::Cow;
+
 use super::budget::BudgetTracker;
-use super::format::format_row;
+use super::format::{escape_pipe, format_row, parse_row, unescape_pipe};
 
 #[test]
 fn format_row_escapes_delimiters() {
@@ -9,6 +11,25 @@ fn format_row_escapes_delimiters() {
     assert!(row.contains("line1\\nline2"));
 }
 
+#[test]
+fn escape_pipe_borrows_plain_fields() {
+    assert!(matches!(escape_pipe("fn foo(x: u8)"), Cow::Borrowed(_)));
+    assert_eq!(escape_pipe("fn foo(x: u8 | u16)"), "fn foo(x: u8 \\| u16)");
+    assert_eq!(
+        unescape_pipe("fn foo(x: u8 \\| u16)"),
+        "fn foo(x: u8 | u16)"
+    );
+    assert_eq!(unescape_pipe("trailing\\"), "trailing\\");
+}
+
+#[test]
+fn parse_row_round_trips_format_row() {
+    let fields = ["f", "12", "fn f(x: u8 | u16)", "a\\|b\nc"];
+    let row = format_row(&fields);
+    assert_eq!(row.matches('|').count(), 5);
+    assert_eq!(parse_row(&row), fields);
+}
+
 #[test]
 fn budget_tracker_enforces_limit() {
     let mut tracker = BudgetTracker::new(10);
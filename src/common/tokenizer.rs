@@ -0,0 +1,110 @@
+//! Token counting for output budgets
+//!
+//! Budgets are enforced with a tiktoken encoding chosen by the server config
+//! (`tokenizer = "o200k"`) or per call. Each encoding is built once per
+//! process and shared.
+
+use std::io;
+use std::sync::{OnceLock, RwLock};
+
+use serde::Deserialize;
+use tiktoken_rs::{cl100k_base, o200k_base, p50k_base, r50k_base, CoreBPE};
+
+/// Counts the tokens a model would see for a piece of text
+pub trait Tokenizer: Send + Sync {
+    fn count(&self, text: &str) -> usize;
+}
+
+impl Tokenizer for CoreBPE {
+    fn count(&self, text: &str) -> usize {
+        self.encode_with_special_tokens(text).len()
+    }
+}
+
+/// Supported tiktoken encodings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenizerKind {
+    /// GPT-3.5 / GPT-4
+    #[default]
+    Cl100k,
+    /// Codex and text-davinci-002/003
+    P50k,
+    /// GPT-3 (davinci)
+    R50k,
+    /// GPT-4o
+    O200k,
+}
+
+impl TokenizerKind {
+    pub const ALL: [TokenizerKind; 4] = [
+        TokenizerKind::Cl100k,
+        TokenizerKind::P50k,
+        TokenizerKind::R50k,
+        TokenizerKind::O200k,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TokenizerKind::Cl100k => "cl100k",
+            TokenizerKind::P50k => "p50k",
+            TokenizerKind::R50k => "r50k",
+            TokenizerKind::O200k => "o200k",
+        }
+    }
+
+    /// Parse `cl100k`, `p50k`, `r50k` or `o200k`, with or without `_base`
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        let name = name.strip_suffix("_base").unwrap_or(&name);
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    fn load(self) -> Result<CoreBPE, io::Error> {
+        let bpe = match self {
+            TokenizerKind::Cl100k => cl100k_base(),
+            TokenizerKind::P50k => p50k_base(),
+            TokenizerKind::R50k => r50k_base(),
+            TokenizerKind::O200k => o200k_base(),
+        };
+        bpe.map_err(|e| {
+            io::Error::other(format!(
+                "Failed to initialize {} tokenizer: {e}",
+                self.name()
+            ))
+        })
+    }
+}
+
+static CONFIGURED: RwLock<TokenizerKind> = RwLock::new(TokenizerKind::Cl100k);
+
+static ENCODINGS: [OnceLock<CoreBPE>; 4] = [
+    OnceLock::new(),
+    OnceLock::new(),
+    OnceLock::new(),
+    OnceLock::new(),
+];
+
+/// Use `kind` wherever a call does not pick a tokenizer itself
+pub fn configure(kind: TokenizerKind) {
+    *CONFIGURED
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = kind;
+}
+
+/// The tokenizer set by [`configure`] (cl100k by default)
+pub fn configured() -> TokenizerKind {
+    *CONFIGURED
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The shared tokenizer for `kind`, built on first use
+pub fn tokenizer(kind: TokenizerKind) -> Result<&'static dyn Tokenizer, io::Error> {
+    let slot = &ENCODINGS[kind as usize];
+    if let Some(bpe) = slot.get() {
+        return Ok(bpe);
+    }
+    let bpe = kind.load()?;
+    Ok(slot.get_or_init(|| bpe))
+}
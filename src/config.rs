@@ -0,0 +1,153 @@
+//! Server configuration
+//!
+//! Settings are read from `treesitter-mcp.toml` in the current directory, or
+//! from the file given with `--config`. Every key is optional:
+//!
+//! ```toml
+//! cache_size = 128                       # parse trees kept in memory
+//! extra_ignored_dirs = ["generated"]     # skipped by every directory walk
+//! max_file_bytes = 1048576               # larger files are skipped (default 1 MB); 0 = no limit
+//! transport = "ws"                       # "stdio" (default) or "ws"
+//! port = 9229                            # WebSocket port
+//! allowed_origins = ["chrome-extension://abc"]  # browser origins allowed over WebSocket
+//! log_level = "info"                     # used when RUST_LOG is unset
+//! tokenizer = "o200k"                    # budget tokenizer: cl100k (default), p50k, r50k, o200k
+//! ```
+//!
+//! Command-line flags (`--transport`, `--port`) and `RUST_LOG` take
+//! precedence over the file.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use toml_edit::{Document, Item, Table};
+
+use crate::common::project_files;
+use crate::common::tokenizer::TokenizerKind;
+use crate::mcp::ws_transport;
+use crate::parser::cache;
+
+/// Configuration file looked up in the current directory
+pub const CONFIG_FILE_NAME: &str = "treesitter-mcp.toml";
+
+/// How clients connect to the server
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportKind {
+    #[default]
+    Stdio,
+    Ws,
+}
+
+/// Settings for one server process
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// Parse trees kept by the shared cache (default: `TREESITTER_CACHE_SIZE` or 64)
+    pub cache_size: usize,
+    /// Directory names skipped in addition to `target`, `node_modules`, ...
+    pub extra_ignored_dirs: Vec<String>,
+    /// Files larger than this are skipped by directory walks (default 1 MB); 0 means no limit
+    pub max_file_bytes: usize,
+    pub transport: TransportKind,
+    /// Port of the WebSocket transport
+    pub port: u16,
+    /// Browser `Origin`s allowed to open a WebSocket connection (default: none)
+    pub allowed_origins: Vec<String>,
+    /// Log filter used when `RUST_LOG` is unset
+    pub log_level: String,
+    /// Encoding used to count tokens for `max_tokens` budgets
+    pub tokenizer: TokenizerKind,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            cache_size: cache::default_capacity(),
+            extra_ignored_dirs: Vec::new(),
+            max_file_bytes: project_files::DEFAULT_MAX_FILE_BYTES,
+            transport: TransportKind::Stdio,
+            port: ws_transport::DEFAULT_PORT,
+            allowed_origins: Vec::new(),
+            log_level: "error".to_string(),
+            tokenizer: TokenizerKind::default(),
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Load `path`, or `treesitter-mcp.toml` in the current directory when no
+    /// path is given. Without either file the defaults are used.
+    pub fn load(path: Option<&Path>) -> Result<Self, io::Error> {
+        match path {
+            Some(path) => Self::from_file(path),
+            None => {
+                let default_path = PathBuf::from(CONFIG_FILE_NAME);
+                if default_path.is_file() {
+                    Self::from_file(&default_path)
+                } else {
+                    Ok(Self::default())
+                }
+            }
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, io::Error> {
+        let text = fs::read_to_string(path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("Failed to read config {}: {e}", path.display()),
+            )
+        })?;
+        Self::from_toml_str(&text).map_err(|e| {
+            io::Error::new(e.kind(), format!("Invalid config {}: {e}", path.display()))
+        })
+    }
+
+    pub fn from_toml_str(text: &str) -> Result<Self, io::Error> {
+        let document = Document::parse(text)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        serde_json::from_value(table_to_json(document.as_table()))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+    }
+}
+
+// toml_edit is built without its serde support, so documents are converted
+// to JSON values and deserialized from there.
+fn table_to_json(table: &Table) -> Value {
+    Value::Object(
+        table
+            .iter()
+            .map(|(key, item)| (key.to_string(), item_to_json(item)))
+            .collect(),
+    )
+}
+
+fn item_to_json(item: &Item) -> Value {
+    match item {
+        Item::None => Value::Null,
+        Item::Value(value) => value_to_json(value),
+        Item::Table(table) => table_to_json(table),
+        Item::ArrayOfTables(tables) => Value::Array(tables.iter().map(table_to_json).collect()),
+    }
+}
+
+fn value_to_json(value: &toml_edit::Value) -> Value {
+    match value {
+        toml_edit::Value::String(s) => Value::from(s.value().as_str()),
+        toml_edit::Value::Integer(i) => Value::from(*i.value()),
+        toml_edit::Value::Float(f) => Value::from(*f.value()),
+        toml_edit::Value::Boolean(b) => Value::from(*b.value()),
+        toml_edit::Value::Datetime(d) => Value::from(d.value().to_string()),
+        toml_edit::Value::Array(array) => Value::Array(array.iter().map(value_to_json).collect()),
+        toml_edit::Value::InlineTable(table) => Value::Object(
+            table
+                .iter()
+                .map(|(key, value)| (key.to_string(), value_to_json(value)))
+                .collect::<Map<_, _>>(),
+        ),
+    }
+}
@@ -69,6 +69,82 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_typescript_union_and_intersection_aliases() -> Result<()> {
+        let source = r#"
+type Shape =
+    | { kind: "circle"; radius: number }
+    | { kind: 'square'; size: number }
+    | Triangle;
+type Digit = 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | 10 | 11;
+type Tree = Leaf | { children: Tree[] };
+type Entity = Base & Timestamps & { id: string; save(): void };
+type Id = string;
+"#;
+        let result = extract_typescript_types(source, Path::new("shapes.ts"), true)?;
+        let find = |name: &str| result.iter().find(|t| t.name == name).unwrap();
+
+        let shape = find("Shape");
+        assert_eq!(shape.kind, TypeKind::TypeAlias);
+        let variants: Vec<(&str, Option<&str>)> = shape
+            .variants
+            .iter()
+            .flatten()
+            .map(|v| (v.name.as_str(), v.type_annotation.as_deref()))
+            .collect();
+        assert_eq!(
+            variants,
+            [
+                ("circle", Some(r#"{ kind: "circle"; radius: number }"#)),
+                ("square", Some("{ kind: 'square'; size: number }")),
+                ("Triangle", None),
+            ]
+        );
+
+        let digits: Vec<&str> = find("Digit")
+            .variants
+            .iter()
+            .flatten()
+            .map(|v| v.name.as_str())
+            .collect();
+        assert_eq!(digits.len(), 12);
+        assert_eq!(digits.first(), Some(&"0"));
+        assert_eq!(digits.last(), Some(&"11"));
+
+        let tree = find("Tree");
+        let names: Vec<&str> = tree
+            .variants
+            .iter()
+            .flatten()
+            .map(|v| v.name.as_str())
+            .collect();
+        assert_eq!(names, ["Leaf", "{ children: Tree[] }"]);
+
+        let entity = find("Entity");
+        assert_eq!(entity.kind, TypeKind::TypeAlias);
+        let members: Vec<(&str, &str)> = entity
+            .members
+            .iter()
+            .flatten()
+            .map(|m| (m.name.as_str(), m.type_annotation.as_str()))
+            .collect();
+        assert_eq!(
+            members,
+            [
+                ("Base", ""),
+                ("Timestamps", ""),
+                ("id", "string"),
+                ("save", "save(): void"),
+            ]
+        );
+
+        let id = find("Id");
+        assert!(id.variants.is_none());
+        assert!(id.members.is_none());
+
+        Ok(())
+    }
+
     #[test]
     fn test_python_extraction() -> Result<()> {
         let source = r#"
@@ -117,4 +193,72 @@ Point = NamedTuple("Point", [("x", int), ("y", int)])
 
         Ok(())
     }
+
+    #[test]
+    fn test_python_dataclass_extraction() -> Result<()> {
+        let source = r#"
+from dataclasses import InitVar, dataclass, field
+import dataclasses
+from typing import ClassVar
+
+@dataclass
+class Order:
+    id: int
+    tags: list[str] = field(default_factory=list)
+    registry: ClassVar[dict] = {}
+    secret: InitVar[str] = ""
+    untyped = 5
+
+    def __init__(self, id: int):
+        self.extra = id
+
+    def __post_init__(self, secret: str):
+        self.digest = hash(secret)
+
+@dataclasses.dataclass(frozen=True)
+class Point:
+    x: float
+    y: float = 0.0
+    origin: typing.ClassVar["Point"]
+
+class Plain:
+    z: int
+"#;
+
+        let result = extract_python_types(source, Path::new("models.py"))?;
+
+        let order = result.iter().find(|t| t.name == "Order").unwrap();
+        assert_eq!(order.kind, TypeKind::Dataclass);
+        let fields: Vec<(&str, &str)> = order
+            .fields
+            .iter()
+            .flatten()
+            .map(|f| (f.name.as_str(), f.type_annotation.as_str()))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("id", "int"),
+                ("tags", "list[str]"),
+                ("secret", "InitVar[str]")
+            ]
+        );
+        let members = order.members.as_ref().unwrap();
+        assert!(members.iter().any(|m| m.name == "__post_init__"));
+
+        let point = result.iter().find(|t| t.name == "Point").unwrap();
+        assert_eq!(point.kind, TypeKind::Dataclass);
+        let names: Vec<&str> = point
+            .fields
+            .iter()
+            .flatten()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(names, ["x", "y"]);
+
+        let plain = result.iter().find(|t| t.name == "Plain").unwrap();
+        assert_eq!(plain.kind, TypeKind::Class);
+
+        Ok(())
+    }
 }
@@ -1,14 +1,17 @@
-use std::collections::HashMap;
-use std::fs;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use eyre::{bail, Result, WrapErr};
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use log::debug;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use streaming_iterator::StreamingIterator;
 use tree_sitter::{Node, Parser, Query, QueryCursor};
 
+use crate::analysis::path_utils::{self, SourceEncoding};
+use crate::common::cancel::{self, CancellationToken};
 use crate::common::project_files::collect_project_files;
 
 const HARD_TYPE_LIMIT: usize = 1000;
@@ -20,6 +23,19 @@ pub struct TypeExtractionResult {
     pub types_included: usize,
     pub limit_hit: Option<LimitHit>,
     pub truncated: bool,
+    /// Files that were not UTF-8 and were decoded from another encoding
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub encoding_detected: Vec<DecodedFile>,
+    /// Annotated Python function signatures; not subject to the type limit
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub function_signatures: Vec<FunctionSignature>,
+}
+
+/// A source file decoded from an encoding other than UTF-8
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DecodedFile {
+    pub file: PathBuf,
+    pub encoding: SourceEncoding,
 }
 
 impl TypeExtractionResult {
@@ -30,6 +46,8 @@ impl TypeExtractionResult {
             types_included: 0,
             limit_hit: None,
             truncated: false,
+            encoding_detected: Vec::new(),
+            function_signatures: Vec::new(),
         }
     }
 
@@ -59,6 +77,38 @@ pub enum TypeKind {
     Record,
     TypedDict,
     NamedTuple,
+    /// Python class decorated with `@dataclass`
+    Dataclass,
+    /// Class decorated with Angular's `@Component`
+    AngularComponent,
+    /// Class decorated with Angular's `@Injectable`
+    AngularInjectable,
+    /// Class decorated with Angular's `@Directive`
+    AngularDirective,
+    /// Class decorated with Angular's `@Pipe`
+    AngularPipe,
+}
+
+impl TypeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TypeKind::Interface => "interface",
+            TypeKind::Class => "class",
+            TypeKind::Struct => "struct",
+            TypeKind::Enum => "enum",
+            TypeKind::Trait => "trait",
+            TypeKind::Protocol => "protocol",
+            TypeKind::TypeAlias => "type_alias",
+            TypeKind::Record => "record",
+            TypeKind::TypedDict => "typed_dict",
+            TypeKind::NamedTuple => "named_tuple",
+            TypeKind::Dataclass => "dataclass",
+            TypeKind::AngularComponent => "angular_component",
+            TypeKind::AngularInjectable => "angular_injectable",
+            TypeKind::AngularDirective => "angular_directive",
+            TypeKind::AngularPipe => "angular_pipe",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -66,6 +116,9 @@ pub struct Field {
     pub name: String,
     #[serde(rename = "type")]
     pub type_annotation: String,
+    /// File defining the type referenced by `type_annotation`, when resolved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_to: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -82,12 +135,46 @@ pub struct Member {
     pub type_annotation: String,
 }
 
+/// A function parameter, with its annotation and default as written
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TypedParam {
+    /// Name including any `*`/`**` prefix, e.g. `*args`
+    pub name: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_annotation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+/// A function whose parameters or return value carry type annotations
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FunctionSignature {
+    /// Function name, qualified by enclosing classes (`Client.fetch`)
+    pub name: String,
+    pub params: Vec<TypedParam>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_type: Option<String>,
+    #[serde(default)]
+    pub is_async: bool,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct TypeDefinition {
     pub name: String,
     pub kind: TypeKind,
     pub file: PathBuf,
     pub line: usize,
+    /// 1-based column of the first character of the definition
+    #[serde(default)]
+    pub column: usize,
+    /// 1-based line where the definition ends
+    #[serde(default)]
+    pub end_line: usize,
+    /// 1-based column just past the last character of the definition
+    #[serde(default)]
+    pub end_column: usize,
     pub signature: String,
     pub usage_count: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -96,6 +183,15 @@ pub struct TypeDefinition {
     pub variants: Option<Vec<Variant>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub members: Option<Vec<Member>>,
+    /// Generic parameters as written, e.g. `T: Clone`, `'a`, `const N: usize`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generics: Option<Vec<String>>,
+    /// `where` clause predicates, e.g. `T: Debug + Send`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub where_bounds: Option<Vec<String>>,
+    /// Framework details, e.g. Angular `selector` or `framework: django`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framework_metadata: Option<BTreeMap<String, String>>,
 }
 
 /// Compatibility wrapper for callers that do not need usage counting.
@@ -105,150 +201,513 @@ pub fn extract_types(
     pattern: Option<&str>,
     max_types: usize,
 ) -> Result<TypeExtractionResult> {
-    extract_types_with_options(path, pattern, max_types, false)
+    extract_types_with_options(path, pattern, max_types, false, false)
 }
 
 /// Extract types from a path (file or directory).
 ///
 /// Set `count_usages` to true to also count usages during extraction (single-pass).
+/// Set `resolve_field_types` to true to point each field at the file defining its type.
 pub fn extract_types_with_options(
     path: impl AsRef<Path>,
     pattern: Option<&str>,
     max_types: usize,
     count_usages: bool,
+    resolve_field_types: bool,
 ) -> Result<TypeExtractionResult> {
-    let path = path.as_ref();
-    if !path.exists() {
-        bail!("Path does not exist: {}", path.display());
-    }
-
-    let matcher = match pattern {
-        Some(pat) => Some(build_globset(pat)?),
-        None => None,
+    let options = TypeExtractionOptions {
+        pattern: pattern.map(str::to_string),
+        max_types,
+        count_usages,
+        resolve_field_types,
+        ..Default::default()
     };
+    TypeExtractor::new(path, options).collect_result()
+}
 
-    let root_dir = if path.is_file() {
-        path.parent()
-            .map(PathBuf::from)
-            .unwrap_or_else(PathBuf::new)
-    } else {
-        path.to_path_buf()
-    };
+/// Options for [`TypeExtractor`]
+#[derive(Debug, Clone, Default)]
+pub struct TypeExtractionOptions {
+    /// Glob matched against paths relative to the extraction root
+    pub pattern: Option<String>,
+    /// Maximum number of types to yield; 0 means the hard limit (1000)
+    pub max_types: usize,
+    /// Count usages across the root (applied by [`TypeExtractor::collect_result`])
+    pub count_usages: bool,
+    /// Resolve field annotations (applied by [`TypeExtractor::collect_result`])
+    pub resolve_field_types: bool,
+    /// Stops the scan with a [`cancel::CANCELLED`] error once cancelled
+    pub cancel: CancellationToken,
+}
 
-    let effective_limit = if max_types == 0 {
-        HARD_TYPE_LIMIT
-    } else {
-        max_types.min(HARD_TYPE_LIMIT)
-    };
+/// What one file contributes to a [`TypeExtractionResult`]
+#[derive(Debug, Default)]
+struct FileTypes {
+    types: Vec<TypeDefinition>,
+    function_signatures: Vec<FunctionSignature>,
+    /// How the file was decoded when it was not UTF-8
+    decoded: Option<DecodedFile>,
+}
 
-    let mut result = TypeExtractionResult::new();
+/// Streaming type extraction over a file or directory.
+///
+/// Files are read and parsed lazily, so the first types are available
+/// before the scan finishes. Usage counting and field resolution need the
+/// full set of types and only happen in [`TypeExtractor::collect_result`].
+///
+/// In a directory scan, unreadable or unparsable files are skipped; for a
+/// single file (and for an invalid path or pattern) the error is yielded
+/// and iteration ends.
+#[derive(Debug)]
+pub struct TypeExtractor {
+    options: TypeExtractionOptions,
+    root_dir: PathBuf,
+    matcher: Option<GlobSet>,
+    limit: usize,
+    single_file: bool,
+    files: std::vec::IntoIter<PathBuf>,
+    pending: std::vec::IntoIter<TypeDefinition>,
+    files_seen: usize,
+    yielded: usize,
+    limit_hit: Option<LimitHit>,
+    decoded: Vec<DecodedFile>,
+    function_signatures: Vec<FunctionSignature>,
+    error: Option<eyre::Report>,
+}
 
-    if path.is_file() {
-        process_single_file(path, &root_dir, &matcher, effective_limit, &mut result)?;
-    } else {
-        let files = collect_project_files(path)
-            .map_err(|err| eyre::eyre!("Failed to walk {}: {err}", path.display()))?;
-        for file_path in files {
-            let rel_path = relative_path(&root_dir, &file_path);
-            if let Some(matcher) = matcher.as_ref() {
-                if !matcher.is_match(normalize_path(&rel_path)) {
-                    continue;
-                }
-            }
+impl TypeExtractor {
+    pub fn new(path: impl AsRef<Path>, options: TypeExtractionOptions) -> Self {
+        let path = path.as_ref();
+        let root_dir = if path.is_file() {
+            path.parent()
+                .map(PathBuf::from)
+                .unwrap_or_else(PathBuf::new)
+        } else {
+            path.to_path_buf()
+        };
+        let limit = if options.max_types == 0 {
+            HARD_TYPE_LIMIT
+        } else {
+            options.max_types.min(HARD_TYPE_LIMIT)
+        };
 
-            // Read file once for both word counting and type extraction
-            let content = match fs::read_to_string(&file_path) {
-                Ok(c) => c,
-                Err(err) => {
-                    debug!("Skipping file {}: {err}", file_path.display());
-                    continue;
+        let mut extractor = Self {
+            options,
+            root_dir,
+            matcher: None,
+            limit,
+            single_file: path.is_file(),
+            files: Vec::new().into_iter(),
+            pending: Vec::new().into_iter(),
+            files_seen: 0,
+            yielded: 0,
+            limit_hit: None,
+            decoded: Vec::new(),
+            function_signatures: Vec::new(),
+            error: None,
+        };
+
+        let setup = || -> Result<(Option<GlobSet>, Vec<PathBuf>)> {
+            if !path.exists() {
+                bail!("Path does not exist: {}", path.display());
+            }
+            let matcher = match extractor.options.pattern.as_deref() {
+                Some(pat) => Some(build_globset(pat)?),
+                None => None,
+            };
+            let files = if path.is_file() {
+                if is_hidden(path) {
+                    Vec::new()
+                } else {
+                    vec![path.to_path_buf()]
                 }
+            } else {
+                collect_project_files(path)
+                    .map_err(|err| eyre::eyre!("Failed to walk {}: {err}", path.display()))?
             };
+            Ok((matcher, files))
+        };
 
-            // Extract types from supported languages
-            if let Some(language) = detect_language(&file_path) {
-                if let Err(err) = process_file_with_source(
-                    &content,
-                    &rel_path,
-                    language,
-                    effective_limit,
-                    &mut result,
-                ) {
-                    debug!("Skipping file {}: {err}", file_path.display());
-                }
+        match setup() {
+            Ok((matcher, files)) => {
+                extractor.matcher = matcher;
+                extractor.files = files.into_iter();
             }
+            Err(err) => extractor.error = Some(err),
+        }
+        extractor
+    }
 
-            if result.limit_hit.is_some() {
+    /// Set once more types were found than `max_types` allows.
+    #[allow(dead_code)]
+    pub fn limit_hit(&self) -> Option<LimitHit> {
+        self.limit_hit
+    }
+
+    /// Drain the iterator into a [`TypeExtractionResult`], applying usage
+    /// counting and field resolution when requested.
+    ///
+    /// The remaining files are extracted in parallel; the types come out in
+    /// the same order, with the same limit, as iterating would give.
+    pub fn collect_result(mut self) -> Result<TypeExtractionResult> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+
+        let mut result = TypeExtractionResult::new();
+        result.types = self.extract_remaining()?;
+
+        result.limit_hit = self.limit_hit;
+        result.encoding_detected = std::mem::take(&mut self.decoded);
+        result.function_signatures = std::mem::take(&mut self.function_signatures);
+        // The type that hit the limit was seen but not included.
+        result.total_types = result.types.len() + usize::from(self.limit_hit.is_some());
+        result.finalize();
+
+        if self.options.resolve_field_types {
+            resolve_fields(&mut result.types);
+        }
+
+        if self.options.count_usages {
+            crate::analysis::usage_counter::count_all_usages(&mut result.types, &self.root_dir)?;
+        }
+
+        Ok(result)
+    }
+
+    fn extract_remaining(&mut self) -> Result<Vec<TypeDefinition>> {
+        if self.limit_hit.is_some() {
+            return Ok(Vec::new());
+        }
+
+        let files: Vec<PathBuf> = self.files.by_ref().collect();
+        let mut types: Vec<TypeDefinition> = self.pending.by_ref().collect();
+        let remaining = self.limit - self.yielded;
+
+        // Workers stop picking up files once one type past the limit has
+        // been found somewhere; files skipped that way are extracted during
+        // the merge if earlier files turn out to fall short of the limit.
+        // Once cancelled, no worker picks up another file.
+        let found = AtomicUsize::new(types.len());
+        let extractor = &*self;
+        let cancel = &self.options.cancel;
+        let per_file: Vec<Option<Result<FileTypes>>> = files
+            .par_iter()
+            .map(|file_path| {
+                if found.load(Ordering::Relaxed) > remaining || cancel.is_cancelled() {
+                    return None;
+                }
+                let file_types = extractor.file_types(file_path);
+                if let Ok(file_types) = &file_types {
+                    found.fetch_add(file_types.types.len(), Ordering::Relaxed);
+                }
+                Some(file_types)
+            })
+            .collect();
+
+        for (file_path, file_types) in files.iter().zip(per_file) {
+            if types.len() > remaining {
                 break;
             }
+            let file_types = match file_types {
+                Some(file_types) => file_types,
+                None if self.options.cancel.is_cancelled() => {
+                    return Err(cancel::cancelled_error().into())
+                }
+                None => self.file_types(file_path),
+            };
+            match file_types {
+                Ok(file_types) => {
+                    types.extend(file_types.types);
+                    self.function_signatures
+                        .extend(file_types.function_signatures);
+                    self.decoded.extend(file_types.decoded);
+                }
+                Err(err) if self.single_file => return Err(err),
+                Err(err) => debug!("Skipping file {}: {err}", file_path.display()),
+            }
         }
+
+        if types.len() > remaining {
+            types.truncate(remaining);
+            self.limit_hit = Some(LimitHit::TypeLimit);
+        }
+        self.yielded += types.len();
+        Ok(types)
     }
 
-    result.finalize();
+    fn file_types(&self, file_path: &Path) -> Result<FileTypes> {
+        let rel_path = relative_path(&self.root_dir, file_path);
+        if let Some(matcher) = self.matcher.as_ref() {
+            if !matcher.is_match(normalize_path(&rel_path)) {
+                return Ok(FileTypes::default());
+            }
+        }
 
-    if count_usages {
-        crate::analysis::usage_counter::count_all_usages(&mut result.types, &root_dir)?;
-    }
+        let Some(language) = detect_language(file_path) else {
+            return Ok(FileTypes::default());
+        };
 
-    Ok(result)
-}
+        let (source, encoding) = path_utils::read_source_file_with_encoding(file_path)
+            .wrap_err_with(|| format!("Failed to read {}", file_path.display()))?;
+        let decoded = (encoding != SourceEncoding::Utf8).then(|| DecodedFile {
+            file: rel_path.clone(),
+            encoding,
+        });
 
-fn process_single_file(
-    path: &Path,
-    root_dir: &Path,
-    matcher: &Option<GlobSet>,
-    limit: usize,
-    result: &mut TypeExtractionResult,
-) -> Result<()> {
-    if is_hidden(path) {
-        return Ok(());
+        let types = match language {
+            SupportedLanguage::Rust => extract_rust_types(&source, &rel_path),
+            SupportedLanguage::TypeScript => extract_typescript_types(&source, &rel_path, true),
+            SupportedLanguage::JavaScript => extract_typescript_types(&source, &rel_path, false),
+            SupportedLanguage::Python => extract_python_types(&source, &rel_path),
+            SupportedLanguage::Java => extract_java_types(&source, &rel_path),
+            SupportedLanguage::CSharp => extract_csharp_types(&source, &rel_path),
+            SupportedLanguage::Go => extract_go_types(&source, &rel_path),
+            SupportedLanguage::Swift => extract_swift_types(&source, &rel_path),
+        }?;
+        let function_signatures = if matches!(language, SupportedLanguage::Python) {
+            extract_python_function_annotations(&source, &rel_path)?
+        } else {
+            Vec::new()
+        };
+        Ok(FileTypes {
+            types,
+            function_signatures,
+            decoded,
+        })
     }
+}
+
+impl Iterator for TypeExtractor {
+    type Item = Result<TypeDefinition>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(err) = self.error.take() {
+            return Some(Err(err));
+        }
+        if self.limit_hit.is_some() {
+            return None;
+        }
+
+        loop {
+            if let Some(ty) = self.pending.next() {
+                if self.yielded < self.limit {
+                    self.yielded += 1;
+                    return Some(Ok(ty));
+                }
+                self.limit_hit = Some(LimitHit::TypeLimit);
+                return None;
+            }
 
-    let rel_path = relative_path(root_dir, path);
-    if let Some(matcher) = matcher.as_ref() {
-        if !matcher.is_match(normalize_path(&rel_path)) {
-            return Ok(());
+            let file_path = self.files.next()?;
+            if let Err(err) = self.options.cancel.check(self.files_seen) {
+                self.files = Vec::new().into_iter();
+                return Some(Err(err.into()));
+            }
+            self.files_seen += 1;
+            match self.file_types(&file_path) {
+                Ok(file_types) => {
+                    self.pending = file_types.types.into_iter();
+                    self.function_signatures
+                        .extend(file_types.function_signatures);
+                    self.decoded.extend(file_types.decoded);
+                }
+                Err(err) if self.single_file => return Some(Err(err)),
+                Err(err) => debug!("Skipping file {}: {err}", file_path.display()),
+            }
         }
     }
+}
 
-    // Read file once for both extraction and counting
-    let source =
-        fs::read_to_string(path).wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+/// Link field annotations to the extracted types they mention.
+///
+/// Only direct field annotations are resolved (depth 1); the fields of a
+/// resolved type are never followed. When a name is defined more than once,
+/// a definition in the field's own file wins.
+fn resolve_fields(types: &mut [TypeDefinition]) {
+    let mut known: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for ty in types.iter() {
+        known
+            .entry(ty.name.clone())
+            .or_default()
+            .push(ty.file.clone());
+    }
 
-    if let Some(language) = detect_language(path) {
-        process_file_with_source(&source, &rel_path, language, limit, result)?;
+    for ty in types.iter_mut() {
+        let Some(fields) = ty.fields.as_mut() else {
+            continue;
+        };
+        for field in fields {
+            field.resolved_to = annotation_identifiers(&field.type_annotation)
+                .find_map(|name| known.get(name))
+                .map(|files| {
+                    files
+                        .iter()
+                        .find(|file| **file == ty.file)
+                        .unwrap_or(&files[0])
+                        .clone()
+                });
+        }
     }
-    Ok(())
 }
 
-fn process_file_with_source(
+/// Identifier-like tokens of a type annotation, e.g. `Vec`, `OrderItem` in `Vec<OrderItem>`.
+fn annotation_identifiers(annotation: &str) -> impl Iterator<Item = &str> {
+    annotation
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|token| token.chars().next().is_some_and(|c| !c.is_ascii_digit()))
+}
+
+/// Extract types from already-read source using the parser's language detection.
+///
+/// Languages without a type extractor (HTML, CSS) yield an empty list.
+pub(crate) fn extract_types_for_language(
     source: &str,
     relative_path: &Path,
-    language: SupportedLanguage,
-    limit: usize,
-    result: &mut TypeExtractionResult,
-) -> Result<()> {
-    let file_types = match language {
-        SupportedLanguage::Rust => extract_rust_types(source, relative_path)?,
-        SupportedLanguage::TypeScript => extract_typescript_types(source, relative_path, true)?,
-        SupportedLanguage::JavaScript => extract_typescript_types(source, relative_path, false)?,
-        SupportedLanguage::Python => extract_python_types(source, relative_path)?,
-        SupportedLanguage::Java => extract_java_types(source, relative_path)?,
-        SupportedLanguage::CSharp => extract_csharp_types(source, relative_path)?,
-        SupportedLanguage::Go => extract_go_types(source, relative_path)?,
-    };
+    language: crate::parser::Language,
+) -> Result<Vec<TypeDefinition>> {
+    use crate::parser::Language;
 
-    for ty in file_types {
-        result.total_types += 1;
-        if result.types.len() < limit {
-            result.types.push(ty);
-        } else {
-            result.limit_hit = Some(LimitHit::TypeLimit);
-            break;
+    match language {
+        Language::Rust => extract_rust_types(source, relative_path),
+        Language::TypeScript | Language::Tsx => {
+            extract_typescript_types(source, relative_path, true)
         }
+        Language::JavaScript | Language::Jsx => {
+            extract_typescript_types(source, relative_path, false)
+        }
+        Language::Python => extract_python_types(source, relative_path),
+        Language::Java => extract_java_types(source, relative_path),
+        Language::CSharp => extract_csharp_types(source, relative_path),
+        Language::Go => extract_go_types(source, relative_path),
+        Language::Swift => extract_swift_types(source, relative_path),
+        Language::Html | Language::Css | Language::Svelte => Ok(Vec::new()),
     }
+}
+
+/// Types added, removed and modified between two extraction snapshots.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TypeDiff {
+    pub added: Vec<TypeDefinition>,
+    pub removed: Vec<TypeDefinition>,
+    pub modified: Vec<TypeModification>,
+}
 
-    Ok(())
+/// Field-level changes of a type present in both snapshots.
+///
+/// Fields, members and enum variants are compared alike by name; a variant
+/// without a payload has an empty type.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TypeModification {
+    pub name: String,
+    pub added_fields: Vec<Field>,
+    pub removed_fields: Vec<Field>,
+    pub changed_fields: Vec<FieldChange>,
+}
+
+/// A field whose type annotation differs between snapshots
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FieldChange {
+    pub name: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Compare two snapshots by type name.
+///
+/// Types sharing a name are paired in snapshot order, so a name declared
+/// twice before and once after reports its second declaration as removed.
+pub fn diff_type_results(before: &TypeExtractionResult, after: &TypeExtractionResult) -> TypeDiff {
+    let mut unmatched: Vec<Option<&TypeDefinition>> = before.types.iter().map(Some).collect();
+    let mut diff = TypeDiff::default();
+
+    for new in &after.types {
+        let Some(old) = unmatched
+            .iter_mut()
+            .find(|slot| slot.is_some_and(|old| old.name == new.name))
+            .and_then(Option::take)
+        else {
+            diff.added.push(new.clone());
+            continue;
+        };
+
+        let old_fields = comparable_fields(old);
+        let new_fields = comparable_fields(new);
+        let mut modification = TypeModification {
+            name: new.name.clone(),
+            added_fields: Vec::new(),
+            removed_fields: Vec::new(),
+            changed_fields: Vec::new(),
+        };
+        for (name, after_type) in &new_fields {
+            match old_fields.iter().find(|(old_name, _)| old_name == name) {
+                None => modification
+                    .added_fields
+                    .push(plain_field(name, after_type)),
+                Some((_, before_type)) if before_type != after_type => {
+                    modification.changed_fields.push(FieldChange {
+                        name: name.clone(),
+                        before: before_type.clone(),
+                        after: after_type.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        for (name, before_type) in &old_fields {
+            if !new_fields.iter().any(|(new_name, _)| new_name == name) {
+                modification
+                    .removed_fields
+                    .push(plain_field(name, before_type));
+            }
+        }
+
+        if !(modification.added_fields.is_empty()
+            && modification.removed_fields.is_empty()
+            && modification.changed_fields.is_empty())
+        {
+            diff.modified.push(modification);
+        }
+    }
+
+    diff.removed = unmatched.into_iter().flatten().cloned().collect();
+    diff
+}
+
+/// `(name, type)` of every field, member and variant, first declaration wins.
+fn comparable_fields(ty: &TypeDefinition) -> Vec<(String, String)> {
+    let fields = ty
+        .fields
+        .iter()
+        .flatten()
+        .map(|f| (f.name.clone(), f.type_annotation.clone()));
+    let members = ty
+        .members
+        .iter()
+        .flatten()
+        .map(|m| (m.name.clone(), m.type_annotation.clone()));
+    let variants = ty.variants.iter().flatten().map(|v| {
+        (
+            v.name.clone(),
+            v.type_annotation.clone().unwrap_or_default(),
+        )
+    });
+
+    let mut entries: Vec<(String, String)> = Vec::new();
+    for (name, type_annotation) in fields.chain(members).chain(variants) {
+        if !entries.iter().any(|(seen, _)| *seen == name) {
+            entries.push((name, type_annotation));
+        }
+    }
+    entries
+}
+
+fn plain_field(name: &str, type_annotation: &str) -> Field {
+    Field {
+        name: name.to_string(),
+        type_annotation: type_annotation.to_string(),
+        resolved_to: None,
+    }
 }
 
 fn build_globset(pattern: &str) -> Result<GlobSet> {
@@ -295,6 +754,7 @@ enum SupportedLanguage {
     Java,
     CSharp,
     Go,
+    Swift,
 }
 
 fn detect_language(path: &Path) -> Option<SupportedLanguage> {
@@ -307,6 +767,7 @@ fn detect_language(path: &Path) -> Option<SupportedLanguage> {
         "java" => Some(SupportedLanguage::Java),
         "cs" => Some(SupportedLanguage::CSharp),
         "go" => Some(SupportedLanguage::Go),
+        "swift" => Some(SupportedLanguage::Swift),
         _ => None,
     }
 }
@@ -329,6 +790,7 @@ pub(crate) fn extract_rust_types(
         (trait_item name: (type_identifier) @name) @trait
         (type_item name: (type_identifier) @name) @alias
         (impl_item type: (type_identifier) @impl_name) @impl
+        (impl_item type: (generic_type type: (type_identifier) @impl_name)) @impl
     "#;
 
     let query = Query::new(&tree_sitter_rust::LANGUAGE.into(), query_src)
@@ -397,12 +859,20 @@ pub(crate) fn extract_rust_types(
             kind,
             file: file_path.clone(),
             line: node.start_position().row + 1,
+            column: node.start_position().column + 1,
+            end_line: node.end_position().row + 1,
+            end_column: node.end_position().column + 1,
             signature: signature_for(node, source_bytes),
             usage_count: 0,
             fields: None,
             variants: None,
             members: None,
+            generics: None,
+            where_bounds: None,
+            framework_metadata: None,
         };
+        def.generics = rust_generics(node, source_bytes);
+        def.where_bounds = rust_where_bounds(node, source_bytes);
 
         match kind {
             TypeKind::Struct => {
@@ -427,6 +897,7 @@ pub(crate) fn extract_rust_types(
                             struct_fields.push(Field {
                                 name,
                                 type_annotation,
+                                resolved_to: None,
                             });
                         }
                     }
@@ -595,13 +1066,47 @@ pub(crate) fn extract_rust_types(
     Ok(definitions)
 }
 
+/// Generic parameters of a Rust item (`T: Clone`, `'a`, `const N: usize`)
+pub(crate) fn rust_generics(node: Node, source: &[u8]) -> Option<Vec<String>> {
+    let parameters = node.child_by_field_name("type_parameters")?;
+    let mut walker = parameters.walk();
+    let generics: Vec<String> = parameters
+        .named_children(&mut walker)
+        .filter(|child| child.kind() != "attribute_item")
+        .filter_map(|child| child.utf8_text(source).ok())
+        .map(collapse_whitespace)
+        .collect();
+    (!generics.is_empty()).then_some(generics)
+}
+
+/// Predicates of a Rust item's `where` clause (`T: Debug + Send`)
+pub(crate) fn rust_where_bounds(node: Node, source: &[u8]) -> Option<Vec<String>> {
+    let mut walker = node.walk();
+    let clause = node
+        .children(&mut walker)
+        .find(|child| child.kind() == "where_clause")?;
+    let mut walker = clause.walk();
+    let bounds: Vec<String> = clause
+        .named_children(&mut walker)
+        .filter(|child| child.kind() == "where_predicate")
+        .filter_map(|child| child.utf8_text(source).ok())
+        .map(collapse_whitespace)
+        .collect();
+    (!bounds.is_empty()).then_some(bounds)
+}
+
 pub(crate) fn extract_typescript_types(
     source: &str,
     relative_path: &Path,
     is_typescript: bool,
 ) -> Result<Vec<TypeDefinition>> {
     let mut parser = Parser::new();
-    let language = if is_typescript {
+    let is_tsx = relative_path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("tsx"));
+    let language = if is_typescript && is_tsx {
+        tree_sitter_typescript::LANGUAGE_TSX.into()
+    } else if is_typescript {
         tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()
     } else {
         tree_sitter_javascript::LANGUAGE.into()
@@ -675,10 +1180,15 @@ pub(crate) fn extract_typescript_types(
         let mut fields = None;
         let mut members = None;
         let mut variants = None;
+        let mut framework_metadata = None;
 
         match kind {
             TypeKind::Class => {
                 fields = collect_ts_fields(def_node, source_bytes);
+                if let Some((angular_kind, metadata)) = angular_decorator(def_node, source_bytes) {
+                    kind = angular_kind;
+                    framework_metadata = Some(metadata);
+                }
             }
             TypeKind::Interface => {
                 members = collect_ts_members(def_node, source_bytes);
@@ -686,6 +1196,17 @@ pub(crate) fn extract_typescript_types(
             TypeKind::Enum => {
                 variants = collect_ts_variants(def_node, source_bytes);
             }
+            TypeKind::TypeAlias => {
+                if let Some(value) = def_node.child_by_field_name("value") {
+                    match value.kind() {
+                        "union_type" => variants = collect_ts_union_variants(value, source_bytes),
+                        "intersection_type" => {
+                            members = collect_ts_intersection_members(value, source_bytes)
+                        }
+                        _ => {}
+                    }
+                }
+            }
             _ => {}
         }
 
@@ -694,17 +1215,131 @@ pub(crate) fn extract_typescript_types(
             kind,
             file: file_path.clone(),
             line: def_node.start_position().row + 1,
+            column: def_node.start_position().column + 1,
+            end_line: def_node.end_position().row + 1,
+            end_column: def_node.end_position().column + 1,
             signature: signature_for(def_node, source_bytes),
             usage_count: 0,
             fields,
             variants,
             members,
+            generics: None,
+            where_bounds: None,
+            framework_metadata,
         });
     }
 
     Ok(definitions)
 }
 
+/// Angular kind and decorator arguments for a class decorated with
+/// `@Component`, `@Injectable`, `@Directive` or `@Pipe`.
+fn angular_decorator(
+    class_node: Node,
+    source: &[u8],
+) -> Option<(TypeKind, BTreeMap<String, String>)> {
+    ts_class_decorators(class_node, source)
+        .into_iter()
+        .find_map(|(name, call)| {
+            let kind = match name.as_str() {
+                "Component" => TypeKind::AngularComponent,
+                "Injectable" => TypeKind::AngularInjectable,
+                "Directive" => TypeKind::AngularDirective,
+                "Pipe" => TypeKind::AngularPipe,
+                _ => return None,
+            };
+            let metadata = ts_decorator_object(call)
+                .map(|object| ts_object_metadata(object, source))
+                .unwrap_or_default();
+            Some((kind, metadata))
+        })
+}
+
+/// Called decorators on a TypeScript class as `(name, call_expression)`.
+pub(crate) fn ts_class_decorators<'tree>(
+    class_node: Node<'tree>,
+    source: &[u8],
+) -> Vec<(String, Node<'tree>)> {
+    // `@Component(...) export class X` attaches the decorator to the export statement.
+    let owners = std::iter::once(class_node).chain(
+        class_node
+            .parent()
+            .filter(|parent| parent.kind() == "export_statement"),
+    );
+
+    let mut decorators = Vec::new();
+    for owner in owners {
+        let mut walker = owner.walk();
+        decorators.extend(
+            owner
+                .children(&mut walker)
+                .filter(|child| child.kind() == "decorator")
+                .filter_map(|decorator| ts_decorator_call(decorator, source)),
+        );
+    }
+    decorators
+}
+
+/// `(name, call_expression)` for a decorator such as `@Get(':id')`.
+///
+/// Bare decorators (`@Input`) are not calls and yield `None`.
+pub(crate) fn ts_decorator_call<'tree>(
+    decorator: Node<'tree>,
+    source: &[u8],
+) -> Option<(String, Node<'tree>)> {
+    let call = decorator
+        .named_child(0)
+        .filter(|node| node.kind() == "call_expression")?;
+    let name = call
+        .child_by_field_name("function")?
+        .utf8_text(source)
+        .ok()?;
+    Some((name.to_string(), call))
+}
+
+/// The object literal passed as a decorator's first argument.
+pub(crate) fn ts_decorator_object(call: Node) -> Option<Node> {
+    call.child_by_field_name("arguments")
+        .and_then(|arguments| arguments.named_child(0))
+        .filter(|argument| argument.kind() == "object")
+}
+
+/// Object literal entries as `(key, value)` nodes, with quotes stripped from keys.
+pub(crate) fn ts_object_pairs<'tree>(
+    object: Node<'tree>,
+    source: &[u8],
+) -> Vec<(String, Node<'tree>)> {
+    let mut pairs = object.walk();
+    let entries = object
+        .named_children(&mut pairs)
+        .filter(|child| child.kind() == "pair")
+        .filter_map(|pair| {
+            let key = pair.child_by_field_name("key")?.utf8_text(source).ok()?;
+            let value = pair.child_by_field_name("value")?;
+            Some((key.trim_matches(['\'', '"']).to_string(), value))
+        })
+        .collect();
+    entries
+}
+
+/// Object literal entries as text.
+///
+/// String values are unquoted; other values (arrays, inline templates,
+/// references) keep their source text with whitespace collapsed.
+fn ts_object_metadata(object: Node, source: &[u8]) -> BTreeMap<String, String> {
+    ts_object_pairs(object, source)
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let text = value.utf8_text(source).ok()?;
+            let value = match value.kind() {
+                "string" | "template_string" => text.trim_matches(['\'', '"', '`']).to_string(),
+                _ => collapse_whitespace(text),
+            };
+            Some((key, value))
+        })
+        .collect()
+}
+
 fn collect_ts_fields(node: Node, source: &[u8]) -> Option<Vec<Field>> {
     let body = node.child_by_field_name("body")?;
     let mut fields = Vec::new();
@@ -732,6 +1367,7 @@ fn collect_ts_fields(node: Node, source: &[u8]) -> Option<Vec<Field>> {
             fields.push(Field {
                 name,
                 type_annotation,
+                resolved_to: None,
             });
         }
     }
@@ -744,7 +1380,16 @@ fn collect_ts_fields(node: Node, source: &[u8]) -> Option<Vec<Field>> {
 }
 
 fn collect_ts_members(node: Node, source: &[u8]) -> Option<Vec<Member>> {
-    let body = node.child_by_field_name("body")?;
+    let members = ts_signature_members(node.child_by_field_name("body")?, source);
+    if members.is_empty() {
+        None
+    } else {
+        Some(members)
+    }
+}
+
+/// Property and method signatures of an interface body or object type
+fn ts_signature_members(body: Node, source: &[u8]) -> Vec<Member> {
     let mut members = Vec::new();
     let mut walker = body.walk();
     for child in body.children(&mut walker) {
@@ -774,12 +1419,81 @@ fn collect_ts_members(node: Node, source: &[u8]) -> Option<Vec<Member>> {
             });
         }
     }
+    members
+}
 
-    if members.is_empty() {
-        None
-    } else {
-        Some(members)
+/// Operands of a nested `A | B | C` or `A & B & C` type, left to right.
+///
+/// Only the alias's own syntax is walked; named operands are never resolved,
+/// so recursive aliases terminate.
+fn ts_type_operands<'a>(node: Node<'a>, operator_kind: &str) -> Vec<Node<'a>> {
+    let mut operands = Vec::new();
+    let mut stack = vec![node];
+    while let Some(current) = stack.pop() {
+        if current.kind() == operator_kind {
+            let mut walker = current.walk();
+            let children: Vec<Node> = current.named_children(&mut walker).collect();
+            stack.extend(children.into_iter().rev());
+        } else {
+            operands.push(current);
+        }
+    }
+    operands
+}
+
+/// Members of a union alias. Object members tagged with a literal property
+/// (`{ kind: "circle"; ... }`) are named after the tag and keep their shape as
+/// the variant type; other members are named by their type text.
+fn collect_ts_union_variants(union: Node, source: &[u8]) -> Option<Vec<Variant>> {
+    let variants: Vec<Variant> = ts_type_operands(union, "union_type")
+        .into_iter()
+        .filter_map(|operand| {
+            let text = collapse_whitespace(operand.utf8_text(source).ok()?);
+            if operand.kind() != "object_type" {
+                return Some(Variant {
+                    name: text,
+                    type_annotation: None,
+                });
+            }
+            Some(Variant {
+                name: ts_discriminant(operand, source).unwrap_or_else(|| text.clone()),
+                type_annotation: Some(text),
+            })
+        })
+        .collect();
+    (!variants.is_empty()).then_some(variants)
+}
+
+/// Value of the first literal-typed property of an object type
+fn ts_discriminant(object: Node, source: &[u8]) -> Option<String> {
+    let mut walker = object.walk();
+    let tag = object
+        .named_children(&mut walker)
+        .filter(|child| child.kind() == "property_signature")
+        .filter_map(|child| child.child_by_field_name("type")?.named_child(0))
+        .find(|type_node| type_node.kind() == "literal_type")?;
+    let text = tag.utf8_text(source).ok()?;
+    Some(
+        text.trim_matches(|c| c == '"' || c == '\'' || c == '`')
+            .to_string(),
+    )
+}
+
+/// Members of an intersection alias: object operands contribute their
+/// signatures, named operands appear as untyped members.
+fn collect_ts_intersection_members(intersection: Node, source: &[u8]) -> Option<Vec<Member>> {
+    let mut members = Vec::new();
+    for operand in ts_type_operands(intersection, "intersection_type") {
+        if operand.kind() == "object_type" {
+            members.extend(ts_signature_members(operand, source));
+        } else if let Ok(text) = operand.utf8_text(source) {
+            members.push(Member {
+                name: collapse_whitespace(text),
+                type_annotation: String::new(),
+            });
+        }
     }
+    (!members.is_empty()).then_some(members)
 }
 
 fn collect_ts_variants(node: Node, source: &[u8]) -> Option<Vec<Variant>> {
@@ -787,11 +1501,17 @@ fn collect_ts_variants(node: Node, source: &[u8]) -> Option<Vec<Variant>> {
     let mut variants = Vec::new();
     let mut walker = body.walk();
     for child in body.children(&mut walker) {
-        if child.kind() == "enum_member" {
-            let name = child
-                .child_by_field_name("name")
-                .and_then(|n| n.utf8_text(source).ok())
+        // Plain members (`Red,`) are bare names; `Red = 1` is an `enum_assignment`.
+        let name_node = match child.kind() {
+            "enum_assignment" => child.child_by_field_name("name"),
+            "property_identifier" | "string" | "number" => Some(child),
+            _ => None,
+        };
+        if let Some(name_node) = name_node {
+            let name = name_node
+                .utf8_text(source)
                 .unwrap_or_default()
+                .trim_matches(|c| c == '"' || c == '\'')
                 .to_string();
             if name.is_empty() {
                 continue;
@@ -902,11 +1622,17 @@ pub(crate) fn extract_python_types(
                 kind,
                 file: file_path.clone(),
                 line: def_node.start_position().row + 1,
+                column: def_node.start_position().column + 1,
+                end_line: def_node.end_position().row + 1,
+                end_column: def_node.end_position().column + 1,
                 signature: signature_for(def_node, source_bytes),
                 usage_count: 0,
                 fields,
                 variants: None,
                 members: None,
+                generics: None,
+                where_bounds: None,
+                framework_metadata: None,
             });
             continue;
         }
@@ -925,6 +1651,9 @@ pub(crate) fn extract_python_types(
                 kind = TypeKind::Protocol;
             }
         }
+        if is_python_dataclass(def_node, source_bytes) {
+            kind = TypeKind::Dataclass;
+        }
 
         if let Some(body) = def_node.child_by_field_name("body") {
             let mut walker = body.walk();
@@ -935,7 +1664,7 @@ pub(crate) fn extract_python_types(
                             .child_by_field_name("name")
                             .and_then(|n| n.utf8_text(source_bytes).ok())
                             .unwrap_or_default();
-                        if fname == "__init__" {
+                        if fname == "__init__" && kind != TypeKind::Dataclass {
                             // Extract fields from self assignments
                             if let Some(fbody) = child.child_by_field_name("body") {
                                 let mut fwalker = fbody.walk();
@@ -968,6 +1697,7 @@ pub(crate) fn extract_python_types(
                                                                         name: field_name,
                                                                         type_annotation: "Any"
                                                                             .to_string(),
+                                                                        resolved_to: None,
                                                                     });
                                                                 }
                                                             }
@@ -1057,6 +1787,12 @@ pub(crate) fn extract_python_types(
                                     continue;
                                 }
 
+                                // Dataclass fields are the annotated class attributes
+                                if kind == TypeKind::Dataclass {
+                                    fields.extend(python_dataclass_field(statement, source_bytes));
+                                    continue;
+                                }
+
                                 // Class attributes / fields
                                 if let Some(left) = statement.child_by_field_name("left") {
                                     if left.kind() == "identifier" {
@@ -1073,6 +1809,7 @@ pub(crate) fn extract_python_types(
                                             fields.push(Field {
                                                 name,
                                                 type_annotation,
+                                                resolved_to: None,
                                             });
                                         }
                                     }
@@ -1090,6 +1827,9 @@ pub(crate) fn extract_python_types(
             kind,
             file: file_path.clone(),
             line: def_node.start_position().row + 1,
+            column: def_node.start_position().column + 1,
+            end_line: def_node.end_position().row + 1,
+            end_column: def_node.end_position().column + 1,
             signature: signature_for(def_node, source_bytes),
             usage_count: 0,
             fields: if fields.is_empty() {
@@ -1099,12 +1839,192 @@ pub(crate) fn extract_python_types(
             },
             variants,
             members,
+            generics: None,
+            where_bounds: None,
+            framework_metadata: None,
         });
     }
 
+    crate::analysis::django::annotate_django_models(source, relative_path, &mut definitions);
+    crate::analysis::sqlalchemy::annotate_sqlalchemy_models(
+        source,
+        relative_path,
+        &mut definitions,
+    );
+
     Ok(definitions)
 }
 
+/// Whether a class carries `@dataclass` or `@dataclasses.dataclass`, with or
+/// without arguments
+/// Signatures of Python functions and methods with at least one PEP 484
+/// annotation (on a parameter or the return value), in source order.
+pub fn extract_python_function_annotations(
+    source: &str,
+    relative_path: &Path,
+) -> Result<Vec<FunctionSignature>> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&tree_sitter_python::LANGUAGE.into())
+        .wrap_err("Failed to configure Python parser")?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| eyre::eyre!("Failed to parse Python source"))?;
+
+    let query = Query::new(
+        &tree_sitter_python::LANGUAGE.into(),
+        "(function_definition name: (identifier) @name parameters: (parameters) @params) @function",
+    )
+    .wrap_err("Failed to compile Python function query")?;
+
+    let source_bytes = source.as_bytes();
+    let mut signatures = Vec::new();
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(&query, tree.root_node(), source_bytes);
+
+    while let Some(match_) = matches.next() {
+        let mut name = None;
+        let mut params_node = None;
+        let mut function_node = None;
+        for capture in match_.captures {
+            match query.capture_names()[capture.index as usize] {
+                "name" => name = capture.node.utf8_text(source_bytes).ok(),
+                "params" => params_node = Some(capture.node),
+                "function" => function_node = Some(capture.node),
+                _ => {}
+            }
+        }
+        let (Some(name), Some(params_node), Some(function_node)) =
+            (name, params_node, function_node)
+        else {
+            continue;
+        };
+
+        let text = |node: Node| collapse_whitespace(node.utf8_text(source_bytes).unwrap_or(""));
+        let mut walker = params_node.walk();
+        let params: Vec<TypedParam> = params_node
+            .named_children(&mut walker)
+            .filter_map(|param| python_param(param, source_bytes))
+            .collect();
+        let return_type = function_node.child_by_field_name("return_type").map(text);
+
+        if return_type.is_none() && params.iter().all(|p| p.type_annotation.is_none()) {
+            continue;
+        }
+
+        signatures.push(FunctionSignature {
+            name: python_qualified_name(function_node, name, source_bytes),
+            params,
+            return_type,
+            is_async: function_node
+                .child(0)
+                .is_some_and(|first| first.kind() == "async"),
+            file: relative_path.to_path_buf(),
+            line: function_node.start_position().row + 1,
+        });
+    }
+
+    Ok(signatures)
+}
+
+/// One entry of a Python `parameters` list; `None` for the bare `*` and `/`
+/// separators
+fn python_param(param: Node, source: &[u8]) -> Option<TypedParam> {
+    let text = |node: Node| collapse_whitespace(node.utf8_text(source).unwrap_or(""));
+    let field = |name: &str| param.child_by_field_name(name).map(text);
+
+    let (name, type_annotation, default) = match param.kind() {
+        "identifier" | "list_splat_pattern" | "dictionary_splat_pattern" => {
+            (text(param), None, None)
+        }
+        "default_parameter" => (field("name")?, None, field("value")),
+        "typed_default_parameter" => (field("name")?, field("type"), field("value")),
+        // The name is the unnamed child: `x`, `*args` or `**kwargs`
+        "typed_parameter" => (text(param.named_child(0)?), field("type"), None),
+        _ => return None,
+    };
+
+    Some(TypedParam {
+        name,
+        type_annotation,
+        default,
+    })
+}
+
+/// `Outer.Inner.method` for a method nested in classes
+fn python_qualified_name(function: Node, name: &str, source: &[u8]) -> String {
+    let mut parts = vec![name.to_string()];
+    let mut current = function.parent();
+    while let Some(node) = current {
+        if node.kind() == "class_definition" {
+            if let Some(class_name) = node
+                .child_by_field_name("name")
+                .and_then(|n| n.utf8_text(source).ok())
+            {
+                parts.push(class_name.to_string());
+            }
+        }
+        current = node.parent();
+    }
+    parts.reverse();
+    parts.join(".")
+}
+
+fn is_python_dataclass(class_node: Node, source: &[u8]) -> bool {
+    let Some(decorated) = class_node
+        .parent()
+        .filter(|parent| parent.kind() == "decorated_definition")
+    else {
+        return false;
+    };
+
+    let mut walker = decorated.walk();
+    let found = decorated
+        .children(&mut walker)
+        .filter(|child| child.kind() == "decorator")
+        .filter_map(|decorator| decorator.named_child(0))
+        .map(|expression| match expression.kind() {
+            "call" => expression
+                .child_by_field_name("function")
+                .unwrap_or(expression),
+            _ => expression,
+        })
+        .any(|target| {
+            matches!(
+                target.utf8_text(source),
+                Ok("dataclass" | "dataclasses.dataclass")
+            )
+        });
+    found
+}
+
+/// Field declared by an annotated dataclass attribute (`name: Type = default`).
+///
+/// Unannotated attributes and `ClassVar`s are not fields; `InitVar`s are kept
+/// with their `InitVar[...]` type since they are `__init__` parameters.
+fn python_dataclass_field(assignment: Node, source: &[u8]) -> Option<Field> {
+    let left = assignment.child_by_field_name("left")?;
+    if left.kind() != "identifier" {
+        return None;
+    }
+    let type_annotation = assignment
+        .child_by_field_name("type")?
+        .utf8_text(source)
+        .ok()?;
+    let unqualified = type_annotation
+        .strip_prefix("typing.")
+        .unwrap_or(type_annotation);
+    if unqualified == "ClassVar" || unqualified.starts_with("ClassVar[") {
+        return None;
+    }
+
+    Some(Field {
+        name: left.utf8_text(source).ok()?.to_string(),
+        type_annotation: type_annotation.to_string(),
+        resolved_to: None,
+    })
+}
+
 fn parse_python_typed_dict_fields(args: Node, source: &[u8]) -> Option<Vec<Field>> {
     let mut walker = args.walk();
     let dict_node = args
@@ -1134,6 +2054,7 @@ fn parse_python_typed_dict_fields(args: Node, source: &[u8]) -> Option<Vec<Field
         fields.push(Field {
             name,
             type_annotation,
+            resolved_to: None,
         });
     }
 
@@ -1177,6 +2098,7 @@ fn parse_python_named_tuple_fields(args: Node, source: &[u8]) -> Option<Vec<Fiel
         fields.push(Field {
             name,
             type_annotation,
+            resolved_to: None,
         });
     }
 
@@ -1289,6 +2211,7 @@ fn extract_java_types(source: &str, relative_path: &Path) -> Result<Vec<TypeDefi
                                                 .unwrap_or_default()
                                                 .to_string(),
                                             type_annotation: type_str.to_string(),
+                                            resolved_to: None,
                                         });
                                     }
                                 }
@@ -1330,17 +2253,251 @@ fn extract_java_types(source: &str, relative_path: &Path) -> Result<Vec<TypeDefi
             kind,
             file: file_path.clone(),
             line: def_node.start_position().row + 1,
+            column: def_node.start_position().column + 1,
+            end_line: def_node.end_position().row + 1,
+            end_column: def_node.end_position().column + 1,
+            signature: signature_for(def_node, source_bytes),
+            usage_count: 0,
+            fields,
+            variants,
+            members,
+            generics: None,
+            where_bounds: None,
+            framework_metadata: None,
+        });
+    }
+
+    Ok(definitions)
+}
+
+fn extract_swift_types(source: &str, relative_path: &Path) -> Result<Vec<TypeDefinition>> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&tree_sitter_swift::LANGUAGE.into())
+        .wrap_err("Failed to configure Swift parser")?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| eyre::eyre!("Failed to parse Swift source"))?;
+
+    // `class_declaration` covers class, struct, actor, enum and extension;
+    // extensions name a `user_type` and are not matched.
+    let query_src = r#"
+        (class_declaration name: (type_identifier) @name) @class
+        (protocol_declaration name: (type_identifier) @name) @protocol
+        (typealias_declaration name: (type_identifier) @name) @alias
+    "#;
+
+    let query = Query::new(&tree_sitter_swift::LANGUAGE.into(), query_src)
+        .wrap_err("Failed to compile Swift query")?;
+
+    let source_bytes = source.as_bytes();
+    let file_path = relative_path.to_path_buf();
+    let mut definitions = Vec::new();
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(&query, tree.root_node(), source_bytes);
+
+    while let Some(match_) = matches.next() {
+        let mut name_node = None;
+        let mut def_node = None;
+        let mut kind = None;
+
+        for capture in match_.captures {
+            let capture_name = query.capture_names()[capture.index as usize];
+            match capture_name {
+                "name" => name_node = Some(capture.node),
+                "class" => {
+                    def_node = Some(capture.node);
+                    kind = swift_declaration_kind(capture.node);
+                }
+                "protocol" => {
+                    def_node = Some(capture.node);
+                    kind = Some(TypeKind::Protocol);
+                }
+                "alias" => {
+                    def_node = Some(capture.node);
+                    kind = Some(TypeKind::TypeAlias);
+                }
+                _ => {}
+            }
+        }
+
+        let Some(name_node) = name_node else {
+            continue;
+        };
+        let Some(def_node) = def_node else {
+            continue;
+        };
+        let Some(kind) = kind else {
+            continue;
+        };
+        let Ok(name) = name_node.utf8_text(source_bytes) else {
+            continue;
+        };
+
+        let mut fields = None;
+        let mut members = None;
+        let mut variants = None;
+
+        if let Some(body) = def_node.child_by_field_name("body") {
+            let mut f = Vec::new();
+            let mut m = Vec::new();
+            let mut v = Vec::new();
+            let mut walker = body.walk();
+
+            for child in body.named_children(&mut walker) {
+                match child.kind() {
+                    "property_declaration" => {
+                        let type_annotation = swift_type_annotation(child, source_bytes);
+                        for name in swift_bound_names(child, source_bytes) {
+                            f.push(Field {
+                                name,
+                                type_annotation: type_annotation.clone(),
+                                resolved_to: None,
+                            });
+                        }
+                    }
+                    "protocol_property_declaration" => {
+                        for name in swift_bound_names(child, source_bytes) {
+                            m.push(Member {
+                                name,
+                                type_annotation: collapse_whitespace(
+                                    child.utf8_text(source_bytes).unwrap_or_default(),
+                                ),
+                            });
+                        }
+                    }
+                    "protocol_function_declaration" => {
+                        if let Some(n) = child.child_by_field_name("name") {
+                            m.push(Member {
+                                name: n.utf8_text(source_bytes).unwrap_or_default().to_string(),
+                                type_annotation: signature_for(child, source_bytes),
+                            });
+                        }
+                    }
+                    "enum_entry" => v.extend(swift_enum_variants(child, source_bytes)),
+                    _ => {}
+                }
+            }
+            if !f.is_empty() {
+                fields = Some(f);
+            }
+            if !m.is_empty() {
+                members = Some(m);
+            }
+            if !v.is_empty() {
+                variants = Some(v);
+            }
+        }
+
+        definitions.push(TypeDefinition {
+            name: name.to_string(),
+            kind,
+            file: file_path.clone(),
+            line: def_node.start_position().row + 1,
+            column: def_node.start_position().column + 1,
+            end_line: def_node.end_position().row + 1,
+            end_column: def_node.end_position().column + 1,
             signature: signature_for(def_node, source_bytes),
             usage_count: 0,
             fields,
             variants,
             members,
+            generics: swift_generics(def_node, source_bytes),
+            where_bounds: swift_where_bounds(def_node, source_bytes),
+            framework_metadata: None,
         });
     }
 
     Ok(definitions)
 }
 
+/// Kind of a Swift `class_declaration` from its leading keyword; actors are
+/// reported as classes and extensions are skipped.
+fn swift_declaration_kind(node: Node) -> Option<TypeKind> {
+    let mut walker = node.walk();
+    let keyword = node.children(&mut walker).find(|child| !child.is_named())?;
+    match keyword.kind() {
+        "struct" => Some(TypeKind::Struct),
+        "enum" => Some(TypeKind::Enum),
+        "class" | "actor" => Some(TypeKind::Class),
+        _ => None,
+    }
+}
+
+/// Names bound by a Swift property declaration (`let x`, `var a, b`)
+fn swift_bound_names(node: Node, source: &[u8]) -> Vec<String> {
+    let mut walker = node.walk();
+    node.children_by_field_name("name", &mut walker)
+        .filter_map(|pattern| {
+            pattern
+                .child_by_field_name("bound_identifier")
+                .unwrap_or(pattern)
+                .utf8_text(source)
+                .ok()
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Declared type of a Swift property, empty when it is inferred
+fn swift_type_annotation(node: Node, source: &[u8]) -> String {
+    let mut walker = node.walk();
+    let annotation = node
+        .named_children(&mut walker)
+        .find(|child| child.kind() == "type_annotation");
+    annotation
+        .and_then(|annotation| annotation.utf8_text(source).ok())
+        .map(clean_type_annotation)
+        .unwrap_or_default()
+}
+
+/// Cases of a Swift `case` entry; associated values such as
+/// `(value: Int, error: String?)` become the variant's type.
+fn swift_enum_variants(node: Node, source: &[u8]) -> Vec<Variant> {
+    let mut variants: Vec<Variant> = Vec::new();
+    let mut walker = node.walk();
+    for (index, child) in node.children(&mut walker).enumerate() {
+        match node.field_name_for_child(index as u32) {
+            Some("name") => variants.push(Variant {
+                name: child.utf8_text(source).unwrap_or_default().to_string(),
+                type_annotation: None,
+            }),
+            Some("data_contents") => {
+                if let Some(variant) = variants.last_mut() {
+                    variant.type_annotation = child.utf8_text(source).ok().map(collapse_whitespace);
+                }
+            }
+            _ => {}
+        }
+    }
+    variants
+}
+
+/// Generic parameters of a Swift type (`T: Equatable`)
+fn swift_generics(node: Node, source: &[u8]) -> Option<Vec<String>> {
+    swift_clause_items(node, "type_parameters", "type_parameter", source)
+}
+
+/// Constraints of a Swift type's `where` clause (`T: Hashable`)
+fn swift_where_bounds(node: Node, source: &[u8]) -> Option<Vec<String>> {
+    swift_clause_items(node, "type_constraints", "type_constraint", source)
+}
+
+fn swift_clause_items(node: Node, clause: &str, item: &str, source: &[u8]) -> Option<Vec<String>> {
+    let mut walker = node.walk();
+    let clause = node
+        .named_children(&mut walker)
+        .find(|child| child.kind() == clause)?;
+    let mut walker = clause.walk();
+    let items: Vec<String> = clause
+        .named_children(&mut walker)
+        .filter(|child| child.kind() == item)
+        .filter_map(|child| child.utf8_text(source).ok())
+        .map(collapse_whitespace)
+        .collect();
+    (!items.is_empty()).then_some(items)
+}
+
 pub(crate) fn extract_go_types(source: &str, relative_path: &Path) -> Result<Vec<TypeDefinition>> {
     let mut parser = Parser::new();
     parser
@@ -1353,6 +2510,8 @@ pub(crate) fn extract_go_types(source: &str, relative_path: &Path) -> Result<Vec
     let query_src = r#"
         (type_spec name: (type_identifier) @name type: (struct_type) @struct) @struct_spec
         (type_spec name: (type_identifier) @name type: (interface_type) @iface) @iface_spec
+        (type_spec name: (type_identifier) @name type: (_) @defined) @defined_spec
+        (type_alias name: (type_identifier) @name) @alias
     "#;
 
     let query = Query::new(&tree_sitter_go::LANGUAGE.into(), query_src)
@@ -1370,6 +2529,7 @@ pub(crate) fn extract_go_types(source: &str, relative_path: &Path) -> Result<Vec
         let mut kind = TypeKind::Struct;
         let mut struct_node = None;
         let mut iface_node = None;
+        let mut defined_node = None;
 
         for capture in match_.captures {
             let capture_name = query.capture_names()[capture.index as usize];
@@ -1383,12 +2543,22 @@ pub(crate) fn extract_go_types(source: &str, relative_path: &Path) -> Result<Vec
                     def_node = Some(capture.node);
                     kind = TypeKind::Interface;
                 }
+                "defined_spec" | "alias" => {
+                    def_node = Some(capture.node);
+                    kind = TypeKind::TypeAlias;
+                }
                 "struct" => struct_node = Some(capture.node),
                 "iface" => iface_node = Some(capture.node),
+                "defined" => defined_node = Some(capture.node),
                 _ => {}
             }
         }
 
+        // Struct and interface specs are handled by their own patterns.
+        if defined_node.is_some_and(|n| matches!(n.kind(), "struct_type" | "interface_type")) {
+            continue;
+        }
+
         let Some(name_node) = name_node else {
             continue;
         };
@@ -1404,13 +2574,26 @@ pub(crate) fn extract_go_types(source: &str, relative_path: &Path) -> Result<Vec
             kind,
             file: file_path.clone(),
             line: def_node.start_position().row + 1,
+            column: def_node.start_position().column + 1,
+            end_line: def_node.end_position().row + 1,
+            end_column: def_node.end_position().column + 1,
             signature: signature_for(def_node, source_bytes),
             usage_count: 0,
             fields: None,
             variants: None,
             members: None,
+            generics: None,
+            where_bounds: None,
+            framework_metadata: None,
         };
 
+        if kind == TypeKind::TypeAlias {
+            // No body to cut at: keep the whole spec, so defined types read
+            // `Celsius float64` and true aliases keep their `=` (`Temp = Celsius`).
+            def.signature =
+                collapse_whitespace(def_node.utf8_text(source_bytes).unwrap_or_default());
+        }
+
         match kind {
             TypeKind::Struct => {
                 let Some(struct_node) = struct_node else {
@@ -1461,6 +2644,7 @@ pub(crate) fn extract_go_types(source: &str, relative_path: &Path) -> Result<Vec
                             fields.push(Field {
                                 name,
                                 type_annotation: type_annotation.clone(),
+                                resolved_to: None,
                             });
                         }
                     }
@@ -1605,11 +2789,17 @@ fn extract_csharp_types(source: &str, relative_path: &Path) -> Result<Vec<TypeDe
                 kind,
                 file: file_path.clone(),
                 line: def_node.start_position().row + 1,
+                column: def_node.start_position().column + 1,
+                end_line: def_node.end_position().row + 1,
+                end_column: def_node.end_position().column + 1,
                 signature: signature_for(def_node, source_bytes),
                 usage_count: 0,
                 fields: None,
                 variants: None,
                 members: None,
+                generics: None,
+                where_bounds: None,
+                framework_metadata: None,
             });
             continue;
         }
@@ -1640,6 +2830,7 @@ fn extract_csharp_types(source: &str, relative_path: &Path) -> Result<Vec<TypeDe
                                                     .unwrap_or_default()
                                                     .to_string(),
                                                 type_annotation: type_str.to_string(),
+                                                resolved_to: None,
                                             });
                                         }
                                     }
@@ -1656,6 +2847,7 @@ fn extract_csharp_types(source: &str, relative_path: &Path) -> Result<Vec<TypeDe
                             f.push(Field {
                                 name: n.utf8_text(source_bytes).unwrap_or_default().to_string(),
                                 type_annotation: type_ann.to_string(),
+                                resolved_to: None,
                             });
                         }
                     }
@@ -1694,23 +2886,87 @@ fn extract_csharp_types(source: &str, relative_path: &Path) -> Result<Vec<TypeDe
             kind,
             file: file_path.clone(),
             line: def_node.start_position().row + 1,
+            column: def_node.start_position().column + 1,
+            end_line: def_node.end_position().row + 1,
+            end_column: def_node.end_position().column + 1,
             signature: signature_for(def_node, source_bytes),
             usage_count: 0,
             fields,
             variants,
             members,
+            generics: None,
+            where_bounds: None,
+            framework_metadata: None,
         });
     }
 
     Ok(definitions)
 }
 
-fn signature_for(node: Node, source: &[u8]) -> String {
-    if let Ok(text) = node.utf8_text(source) {
-        text.lines().next().unwrap_or("").trim().to_string()
-    } else {
-        String::new()
+/// Declaration text up to the body, with multi-line parameter lists and
+/// where clauses collapsed onto one line.
+pub(crate) fn signature_for(node: Node, source: &[u8]) -> String {
+    let Ok(text) = node.utf8_text(source) else {
+        return String::new();
+    };
+
+    let head = match node.child_by_field_name("body") {
+        Some(body) if body.start_byte() > node.start_byte() => {
+            &text[..body.start_byte() - node.start_byte()]
+        }
+        _ => match body_brace_offset(text) {
+            Some(offset) => &text[..offset],
+            None => text.lines().next().unwrap_or(""),
+        },
+    };
+
+    collapse_whitespace(head)
+}
+
+/// Offset of the first `{` outside strings and `()`, `[]`, `<>` nesting.
+fn body_brace_offset(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut previous = ' ';
+
+    for (index, ch) in text.char_indices() {
+        if let Some(open) = quote {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == open {
+                quote = None;
+            }
+            continue;
+        }
+
+        match ch {
+            '"' | '`' => quote = Some(ch),
+            '(' | '[' | '<' => depth += 1,
+            // `->` and `=>` are arrows, not closing generics.
+            '>' if previous == '-' || previous == '=' => {}
+            ')' | ']' | '>' => depth = depth.saturating_sub(1),
+            '{' if depth == 0 => return Some(index),
+            _ => {}
+        }
+        previous = ch;
     }
+
+    None
+}
+
+pub(crate) fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(", )", ")")
+        .replace(", >", ">")
+        .replace("( ", "(")
+        .replace("< ", "<")
+        .replace(" )", ")")
+        .replace(" >", ">")
 }
 
 fn clean_type_annotation(text: &str) -> String {
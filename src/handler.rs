@@ -5,64 +5,85 @@
 
 use async_trait::async_trait;
 use rust_mcp_sdk::schema::{
-    schema_utils::CallToolError, CallToolRequestParams, CallToolResult, ListToolsResult,
-    PaginatedRequestParams, RpcError,
+    schema_utils::CallToolError, CallToolRequestParams, CallToolResult, Implementation,
+    InitializeResult, ListToolsResult, PaginatedRequestParams, ProtocolVersion, RpcError,
+    ServerCapabilities, ServerCapabilitiesTools,
 };
 use rust_mcp_sdk::{mcp_server::ServerHandler, McpServer};
+use std::io;
 use std::sync::Arc;
 
+use crate::common::cancel::CancellationToken;
+use crate::common::project_files;
+use crate::common::tokenizer;
+use crate::config::ServerConfig;
+use crate::mcp::streaming;
+use crate::parser::cache;
 use crate::tools::TreesitterTools;
 
 /// Custom handler for tree-sitter MCP server
+///
+/// The handler holds no state of its own: tools parse through the
+/// process-wide [`cache::shared`] parse cache, so every handler and
+/// transport of the process reuses the same trees.
+#[derive(Default)]
 pub struct TreesitterServerHandler;
 
-impl Default for TreesitterServerHandler {
-    fn default() -> Self {
-        Self
-    }
-}
-
 impl TreesitterServerHandler {
+    /// A handler with the default configuration.
+    #[allow(dead_code)]
     pub fn new() -> Self {
         Self
     }
-}
 
-#[async_trait]
-impl ServerHandler for TreesitterServerHandler {
-    async fn handle_list_tools_request(
-        &self,
-        _request: Option<PaginatedRequestParams>,
-        _runtime: Arc<dyn McpServer>,
-    ) -> Result<ListToolsResult, RpcError> {
-        Ok(ListToolsResult {
-            tools: TreesitterTools::tools(),
-            meta: None,
-            next_cursor: None,
-        })
+    /// A handler whose directory walks and parse cache follow `config`.
+    ///
+    /// The walk settings, tokenizer and cache size are process-wide, so the
+    /// first configured handler decides the cache size.
+    pub fn with_config(config: &ServerConfig) -> Self {
+        project_files::configure(config.extra_ignored_dirs.clone(), config.max_file_bytes);
+        tokenizer::configure(config.tokenizer);
+        cache::init_shared(config.cache_size);
+        Self
     }
 
-    async fn handle_call_tool_request(
+    /// Route a tool call to its implementation; directory scans stop early
+    /// once `cancel` is cancelled.
+    ///
+    /// Results are returned in one piece, so a call asking for a stream is
+    /// rejected; transports that can stream handle such calls themselves.
+    pub fn call_tool(
         &self,
         request: CallToolRequestParams,
-        _runtime: Arc<dyn McpServer>,
+        cancel: &CancellationToken,
     ) -> Result<CallToolResult, CallToolError> {
         log::info!("Calling tool: {}", request.name);
 
+        if streaming::wants_stream(&request) {
+            return Err(CallToolError::new(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Tool {} cannot stream on this transport; use the WebSocket transport or omit 'stream'",
+                    request.name
+                ),
+            )));
+        }
+
         // Convert request params into the TreesitterTools enum
         let tool: TreesitterTools = TreesitterTools::try_from(request)?;
 
         // Match the tool variant and execute its corresponding logic
-        match tool {
+        let result = match tool {
             TreesitterTools::ViewCode(t) => t.call_tool(),
             TreesitterTools::CodeMap(t) => t.call_tool(),
             TreesitterTools::FindUsages(t) => t.call_tool(),
             TreesitterTools::FormatReferences(t) => t.call_tool(),
             TreesitterTools::FormatDiagnostics(t) => t.call_tool(),
             TreesitterTools::MinimalEditContext(t) => t.call_tool(),
-            TreesitterTools::CallGraph(t) => t.call_tool(),
+            TreesitterTools::CallGraph(t) => t.call_tool(cancel),
             TreesitterTools::SymbolAtLine(t) => t.call_tool(),
             TreesitterTools::ParseDiff(t) => t.call_tool(),
+            TreesitterTools::BatchDiff(t) => t.call_tool(),
             TreesitterTools::AffectedByDiff(t) => t.call_tool(),
             TreesitterTools::PreviewImpact(t) => t.call_tool(),
             TreesitterTools::QueryPattern(t) => t.call_tool(),
@@ -70,7 +91,94 @@ impl ServerHandler for TreesitterServerHandler {
             TreesitterTools::VerifyEdit(t) => t.call_tool(),
             TreesitterTools::ReviewContext(t) => t.call_tool(),
             TreesitterTools::TemplateContext(t) => t.call_tool(),
-            TreesitterTools::TypeMap(t) => t.call_tool(),
-        }
+            TreesitterTools::TypeMap(t) => t.call_tool(cancel),
+            TreesitterTools::ProjectInventory(t) => t.call_tool(),
+            TreesitterTools::StaleImports(t) => t.call_tool(),
+            TreesitterTools::Complexity(t) => t.call_tool(),
+            TreesitterTools::FindUnusedImports(t) => t.call_tool(cancel),
+            TreesitterTools::InferTypes(t) => t.call_tool(),
+            TreesitterTools::ListLockedDependencies(t) => t.call_tool(),
+            TreesitterTools::ListRoutes(t) => t.call_tool(),
+            TreesitterTools::ListApiRoutes(t) => t.call_tool(),
+            TreesitterTools::ListNestModules(t) => t.call_tool(),
+            TreesitterTools::ListNestRoutes(t) => t.call_tool(),
+            TreesitterTools::ListBuildDependencies(t) => t.call_tool(),
+            TreesitterTools::InspectPackageJson(t) => t.call_tool(),
+            TreesitterTools::DiffTypes(t) => t.call_tool(),
+            TreesitterTools::ExtractJsxTree(t) => t.call_tool(),
+            TreesitterTools::MapTestCoverage(t) => t.call_tool(),
+            TreesitterTools::SemanticTokens(t) => t.call_tool(),
+            TreesitterTools::FindTraitImplementations(t) => t.call_tool(),
+            TreesitterTools::FindMacros(t) => t.call_tool(),
+            TreesitterTools::RenameSymbol(t) => t.call_tool(),
+            TreesitterTools::FindComments(t) => t.call_tool(),
+            TreesitterTools::FindRoutes(t) => t.call_tool(),
+            TreesitterTools::FindPanics(t) => t.call_tool(),
+            TreesitterTools::GetSymbol(t) => t.call_tool(),
+            TreesitterTools::FoldingRanges(t) => t.call_tool(),
+            TreesitterTools::SvelteShape(t) => t.call_tool(),
+            TreesitterTools::VueShape(t) => t.call_tool(),
+            TreesitterTools::CssVarUsages(t) => t.call_tool(),
+            TreesitterTools::HtmlForms(t) => t.call_tool(),
+            TreesitterTools::ModuleTree(t) => t.call_tool(),
+            TreesitterTools::PythonImportGraph(t) => t.call_tool(),
+            TreesitterTools::PublicApi(t) => t.call_tool(),
+            TreesitterTools::FindTests(t) => t.call_tool(),
+            TreesitterTools::AssembleContext(t) => t.call_tool(),
+        };
+
+        log::debug!("Parse cache holds {} trees", cache::shared().tree_count());
+        result
+    }
+}
+
+/// Server details and capabilities sent in response to `initialize`
+pub fn server_details() -> InitializeResult {
+    InitializeResult {
+        server_info: Implementation {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            title: Some("Tree-sitter MCP Server".to_string()),
+            description: Some(
+                "A high-performance MCP server for tree-sitter code analysis operations."
+                    .to_string(),
+            ),
+            icons: vec![],
+            website_url: None,
+        },
+        capabilities: ServerCapabilities {
+            tools: Some(ServerCapabilitiesTools { list_changed: None }),
+            ..Default::default()
+        },
+        meta: None,
+        instructions: Some(
+            "A high-performance MCP server for tree-sitter code analysis operations.".to_string(),
+        ),
+        protocol_version: ProtocolVersion::V2025_11_25.into(),
+    }
+}
+
+#[async_trait]
+impl ServerHandler for TreesitterServerHandler {
+    async fn handle_list_tools_request(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _runtime: Arc<dyn McpServer>,
+    ) -> Result<ListToolsResult, RpcError> {
+        Ok(ListToolsResult {
+            tools: TreesitterTools::tools(),
+            meta: None,
+            next_cursor: None,
+        })
+    }
+
+    async fn handle_call_tool_request(
+        &self,
+        request: CallToolRequestParams,
+        _runtime: Arc<dyn McpServer>,
+    ) -> Result<CallToolResult, CallToolError> {
+        // The SDK runtime does not pass request ids to handlers, so
+        // `notifications/cancelled` cannot be matched to a call here.
+        self.call_tool(request, &CancellationToken::default())
     }
 }
@@ -1,7 +1,11 @@
 pub mod analysis;
 pub mod common;
+pub mod config;
 pub mod extraction;
 pub mod handler;
+pub mod lsp;
+pub mod mcp;
 pub mod mcp_types;
+pub mod output;
 pub mod parser;
 pub mod tools;
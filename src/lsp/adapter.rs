@@ -0,0 +1,439 @@
+//! LSP Adapter
+//!
+//! Serves a read-only subset of the Language Server Protocol over TCP,
+//! backed by the same analysis code as the MCP tools:
+//!
+//! - `textDocument/documentSymbol` → file shape (functions, types, methods)
+//! - `workspace/symbol` → project inventory, filtered by the query
+//! - `textDocument/hover` → symbol at line (signature and scope chain)
+//! - `textDocument/foldingRange` → functions, types, comments and imports
+//!
+//! Documents are read from disk; `didOpen`/`didChange` notifications are
+//! accepted but ignored, so hover and symbols reflect the saved file.
+//! Write operations (rename, code actions, formatting) are out of scope.
+
+use std::collections::HashSet;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::analysis::lsp::{self, FoldKind};
+use crate::analysis::path_utils;
+use crate::analysis::shape::{extract_enhanced_shape_chunked, EnhancedFileShape};
+use crate::analysis::symbol_at_line;
+use crate::analysis::symbol_inventory::{build_inventory, SymbolEntry};
+use crate::parser::{detect_language, parse_code};
+
+/// Address used when `--lsp` is given without one
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:9257";
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+// LSP `SymbolKind` values
+const KIND_CLASS: u32 = 5;
+const KIND_METHOD: u32 = 6;
+const KIND_PROPERTY: u32 = 7;
+const KIND_INTERFACE: u32 = 11;
+const KIND_FUNCTION: u32 = 12;
+const KIND_CONSTANT: u32 = 14;
+const KIND_OBJECT: u32 = 19;
+const KIND_STRUCT: u32 = 23;
+
+/// Bind `address` and serve LSP clients until the listener fails.
+pub fn serve(address: &str) -> io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    log::info!("LSP adapter listening on {}", listener.local_addr()?);
+    serve_listener(listener)
+}
+
+/// Serve every connection accepted by `listener` on its own thread.
+pub fn serve_listener(listener: TcpListener) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream) {
+                log::warn!("LSP connection closed with error: {e}");
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    let mut session = Session::default();
+
+    while let Some(message) = read_message(&mut reader)? {
+        if message["method"] == "exit" {
+            break;
+        }
+        if let Some(response) = session.handle(&message) {
+            write_message(&mut writer, &response)?;
+        }
+    }
+    Ok(())
+}
+
+/// Read one `Content-Length` framed JSON-RPC message; `None` at end of stream.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let length = content_length.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "Missing Content-Length header")
+    })?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Write one `Content-Length` framed JSON-RPC message.
+pub fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = serde_json::to_string(message)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    write!(writer, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    writer.flush()
+}
+
+/// State of one client connection
+#[derive(Debug, Default)]
+pub struct Session {
+    root: Option<PathBuf>,
+}
+
+impl Session {
+    /// Handle a request or notification, returning the response for requests.
+    pub fn handle(&mut self, message: &Value) -> Option<Value> {
+        let id = message.get("id")?.clone();
+        let method = message["method"].as_str().unwrap_or("");
+        let params = &message["params"];
+
+        let result = match method {
+            "initialize" => Ok(self.initialize(params)),
+            "shutdown" => Ok(Value::Null),
+            "textDocument/documentSymbol" => {
+                document_path(params).and_then(|path| document_symbols(&path))
+            }
+            "workspace/symbol" => self.workspace_symbols(params),
+            "textDocument/hover" => document_path(params).and_then(|path| hover(&path, params)),
+            "textDocument/foldingRange" => {
+                document_path(params).and_then(|path| folding_ranges(&path))
+            }
+            _ => Err((METHOD_NOT_FOUND, format!("Method not found: {method}"))),
+        };
+
+        Some(match result {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err((code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {"code": code, "message": message},
+            }),
+        })
+    }
+
+    fn initialize(&mut self, params: &Value) -> Value {
+        self.root = params["rootUri"]
+            .as_str()
+            .or_else(|| params["workspaceFolders"][0]["uri"].as_str())
+            .and_then(uri_to_path)
+            .or_else(|| params["rootPath"].as_str().map(PathBuf::from));
+
+        json!({
+            "capabilities": {
+                "textDocumentSync": 0,
+                "documentSymbolProvider": true,
+                "workspaceSymbolProvider": true,
+                "hoverProvider": true,
+                "foldingRangeProvider": true,
+            },
+            "serverInfo": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+        })
+    }
+
+    fn workspace_symbols(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let root = self.root.as_ref().ok_or_else(|| {
+            (
+                INVALID_PARAMS,
+                "workspace/symbol requires a rootUri in initialize".to_string(),
+            )
+        })?;
+        let query = params["query"].as_str().unwrap_or("").to_lowercase();
+
+        let inventory = build_inventory(root);
+        let tables: [(&[SymbolEntry], u32); 3] = [
+            (&inventory.functions, KIND_FUNCTION),
+            (&inventory.types, KIND_CLASS),
+            (&inventory.constants, KIND_CONSTANT),
+        ];
+
+        let mut symbols = Vec::new();
+        for (entries, kind) in tables {
+            for entry in entries {
+                if !entry.name.to_lowercase().contains(&query) {
+                    continue;
+                }
+                let Some(uri) = resolve_entry_path(root, &entry.file).and_then(path_to_uri) else {
+                    continue;
+                };
+                let position = json!({"line": entry.line.saturating_sub(1), "character": 0});
+                symbols.push(json!({
+                    "name": entry.name,
+                    "kind": kind,
+                    "location": {
+                        "uri": uri,
+                        "range": {"start": position, "end": position},
+                    },
+                }));
+            }
+        }
+        Ok(Value::Array(symbols))
+    }
+}
+
+fn document_symbols(path: &Path) -> Result<Value, (i64, String)> {
+    let source = path_utils::read_source_file(path).map_err(internal_error)?;
+    let language = detect_language(path).map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+    let shape =
+        extract_enhanced_shape_chunked(&source, language, None, false).map_err(internal_error)?;
+
+    let lines: Vec<&str> = source.lines().collect();
+    Ok(Value::Array(shape_symbols(&shape, &lines)))
+}
+
+/// Convert a file shape into nested `DocumentSymbol`s.
+fn shape_symbols(shape: &EnhancedFileShape, lines: &[&str]) -> Vec<Value> {
+    let symbol = |name: &str, detail: &str, kind: u32, line: usize, end_line: usize| {
+        json!({
+            "name": name,
+            "detail": detail,
+            "kind": kind,
+            "range": line_range(lines, line, end_line),
+            "selectionRange": line_range(lines, line, line),
+            "children": [],
+        })
+    };
+
+    // Rust shapes also list impl methods as functions; keep them nested only.
+    let method_lines: HashSet<usize> = shape
+        .impl_blocks
+        .iter()
+        .flat_map(|block| &block.methods)
+        .map(|method| method.line)
+        .collect();
+
+    let mut symbols = Vec::new();
+    for function in shape.functions.iter().chain(&shape.components) {
+        if method_lines.contains(&function.line) {
+            continue;
+        }
+        symbols.push(symbol(
+            &function.name,
+            &function.signature,
+            KIND_FUNCTION,
+            function.line,
+            function.end_line,
+        ));
+    }
+    for structure in &shape.structs {
+        symbols.push(symbol(
+            &structure.name,
+            "",
+            KIND_STRUCT,
+            structure.line,
+            structure.end_line,
+        ));
+    }
+    for class in &shape.classes {
+        let mut class_symbol = symbol(&class.name, "", KIND_CLASS, class.line, class.end_line);
+        let mut children: Vec<Value> = class
+            .methods
+            .iter()
+            .map(|m| symbol(&m.name, &m.signature, KIND_METHOD, m.line, m.end_line))
+            .collect();
+        children.extend(class.properties.iter().chain(&class.fields).map(|p| {
+            let detail = p.property_type.as_deref().unwrap_or("");
+            symbol(&p.name, detail, KIND_PROPERTY, p.line, p.end_line)
+        }));
+        class_symbol["children"] = json!(children);
+        symbols.push(class_symbol);
+    }
+    for interface in &shape.interfaces {
+        let mut interface_symbol = symbol(
+            &interface.name,
+            "",
+            KIND_INTERFACE,
+            interface.line,
+            interface.end_line,
+        );
+        let children: Vec<Value> = interface
+            .methods
+            .iter()
+            .map(|m| symbol(&m.name, &m.signature, KIND_METHOD, m.line, m.end_line))
+            .collect();
+        interface_symbol["children"] = json!(children);
+        symbols.push(interface_symbol);
+    }
+    for trait_info in &shape.traits {
+        let mut trait_symbol = symbol(
+            &trait_info.name,
+            "",
+            KIND_INTERFACE,
+            trait_info.line,
+            trait_info.end_line,
+        );
+        let children: Vec<Value> = trait_info
+            .methods
+            .iter()
+            .map(|m| symbol(&m.name, &m.signature, KIND_METHOD, m.line, m.end_line))
+            .collect();
+        trait_symbol["children"] = json!(children);
+        symbols.push(trait_symbol);
+    }
+    for block in &shape.impl_blocks {
+        let name = match &block.trait_name {
+            Some(trait_name) => format!("impl {trait_name} for {}", block.type_name),
+            None => format!("impl {}", block.type_name),
+        };
+        let mut impl_symbol = symbol(&name, "", KIND_OBJECT, block.line, block.end_line);
+        let children: Vec<Value> = block
+            .methods
+            .iter()
+            .map(|m| symbol(&m.name, &m.signature, KIND_METHOD, m.line, m.end_line))
+            .collect();
+        impl_symbol["children"] = json!(children);
+        symbols.push(impl_symbol);
+    }
+
+    symbols.sort_by_key(|symbol| symbol["range"]["start"]["line"].as_u64());
+    symbols
+}
+
+fn folding_ranges(path: &Path) -> Result<Value, (i64, String)> {
+    let source = path_utils::read_source_file(path).map_err(internal_error)?;
+    let language = detect_language(path).map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+    let tree = parse_code(&source, language).map_err(|e| (INTERNAL_ERROR, e.to_string()))?;
+
+    let ranges = lsp::folding_ranges(tree.root_node(), &source, language)
+        .into_iter()
+        .map(|fold| {
+            // LSP only knows comment, imports and region
+            let kind = match fold.kind {
+                FoldKind::Comment => "comment",
+                FoldKind::ImportGroup => "imports",
+                FoldKind::Function | FoldKind::Class => "region",
+            };
+            json!({
+                "startLine": fold.start_line - 1,
+                "endLine": fold.end_line - 1,
+                "kind": kind,
+            })
+        })
+        .collect();
+    Ok(Value::Array(ranges))
+}
+
+fn hover(path: &Path, params: &Value) -> Result<Value, (i64, String)> {
+    let position = &params["position"];
+    let (Some(line), Some(character)) = (position["line"].as_u64(), position["character"].as_u64())
+    else {
+        return Err((INVALID_PARAMS, "Missing or invalid 'position'".to_string()));
+    };
+
+    // No symbol at the position is an empty hover, not an error.
+    let Ok(result) = symbol_at_line::execute(&json!({
+        "file_path": path.to_string_lossy(),
+        "line": line + 1,
+        "column": character + 1,
+    })) else {
+        return Ok(Value::Null);
+    };
+    let content = serde_json::to_value(result.content.first()).map_err(internal_error)?;
+    let output: Value = content["text"]
+        .as_str()
+        .and_then(|text| serde_json::from_str(text).ok())
+        .unwrap_or(Value::Null);
+    let Some(signature) = output["sig"].as_str() else {
+        return Ok(Value::Null);
+    };
+
+    let kind = output["kind"].as_str().unwrap_or("");
+    let scope = output["scope"].as_str().unwrap_or("");
+    Ok(json!({
+        "contents": {
+            "kind": "markdown",
+            "value": format!("```\n{signature}\n```\n\n{kind} `{scope}`"),
+        },
+    }))
+}
+
+fn document_path(params: &Value) -> Result<PathBuf, (i64, String)> {
+    params["textDocument"]["uri"]
+        .as_str()
+        .and_then(uri_to_path)
+        .ok_or_else(|| {
+            (
+                INVALID_PARAMS,
+                "Missing or invalid 'textDocument.uri'".to_string(),
+            )
+        })
+}
+
+/// Zero-based LSP range covering 1-based lines `start..=end`.
+fn line_range(lines: &[&str], start: usize, end: usize) -> Value {
+    let end = end.max(start);
+    let end_character = lines
+        .get(end.saturating_sub(1))
+        .map_or(0, |line| line.encode_utf16().count());
+    json!({
+        "start": {"line": start.saturating_sub(1), "character": 0},
+        "end": {"line": end.saturating_sub(1), "character": end_character},
+    })
+}
+
+/// Inventory paths are relative to the git or project root, which may sit
+/// above the workspace root.
+fn resolve_entry_path(root: &Path, file: &str) -> Option<PathBuf> {
+    root.ancestors()
+        .map(|ancestor| ancestor.join(file))
+        .find(|candidate| candidate.is_file())
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    Url::parse(uri).ok()?.to_file_path().ok()
+}
+
+fn path_to_uri(path: PathBuf) -> Option<String> {
+    let path = path.canonicalize().unwrap_or(path);
+    Url::from_file_path(path).ok().map(String::from)
+}
+
+fn internal_error(e: impl ToString) -> (i64, String) {
+    (INTERNAL_ERROR, e.to_string())
+}
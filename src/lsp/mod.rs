@@ -0,0 +1,3 @@
+//! Language Server Protocol front end for the analysis tools.
+
+pub mod adapter;
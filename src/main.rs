@@ -1,59 +1,122 @@
 mod analysis;
+mod cli;
 mod common;
+mod config;
 mod extraction;
 mod handler;
+mod lsp;
+mod mcp;
 mod mcp_types;
+mod output;
 mod parser;
 mod tools;
 
+use config::{ServerConfig, TransportKind};
 use handler::TreesitterServerHandler;
-use rust_mcp_sdk::schema::{
-    Implementation, InitializeResult, ProtocolVersion, ServerCapabilities, ServerCapabilitiesTools,
-};
 use rust_mcp_sdk::{
     error::SdkResult,
     mcp_server::{server_runtime, McpServerOptions, ServerRuntime},
     McpServer, StdioTransport, ToMcpServerHandler, TransportOptions,
 };
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::Path;
 use std::sync::Arc;
 
+/// Exit code for an invalid config file or command line, as in `--cli`
+const EXIT_USAGE: i32 = 2;
+
 #[tokio::main]
 async fn main() -> SdkResult<()> {
     color_eyre::install().ok();
-    env_logger::init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // `--config PATH`, else `treesitter-mcp.toml` in the current directory.
+    let config = match ServerConfig::load(flag_value(&args, "--config").map(Path::new)) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(EXIT_USAGE);
+        }
+    };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(&config.log_level))
+        .init();
+
+    // Applies the configured walk settings and cache size for every front end.
+    let handler = TreesitterServerHandler::with_config(&config);
+
+    // `--cli <subcommand> ...` runs one analysis and prints its JSON result.
+    // `--config PATH` may come before or after it.
+    if let Some(position) = args.iter().position(|arg| arg == "--cli") {
+        let mut cli_args = args.clone();
+        cli_args.remove(position);
+        let code = cli::run(&cli_args, &handler);
+        std::process::exit(code);
+    }
+
+    // `--lsp [ADDR]` serves the read-only LSP adapter over TCP instead of MCP on stdio.
+    if args.first().map(String::as_str) == Some("--lsp") {
+        let address = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| lsp::adapter::DEFAULT_ADDRESS.to_string());
+        log::info!("Tree-sitter LSP adapter starting");
+        match tokio::task::spawn_blocking(move || lsp::adapter::serve(&address)).await {
+            Ok(Err(e)) => eprintln!("LSP adapter failed: {e}"),
+            Err(e) => eprintln!("LSP adapter panicked: {e}"),
+            Ok(Ok(())) => {}
+        }
+        return Ok(());
+    }
+
+    // `--transport ws [--port PORT]` serves MCP over WebSocket on localhost.
+    let transport = match flag_value(&args, "--transport") {
+        None => config.transport,
+        Some("stdio") => TransportKind::Stdio,
+        Some("ws") => TransportKind::Ws,
+        Some(other) => {
+            eprintln!("Unknown transport: {other} (expected stdio or ws)");
+            std::process::exit(EXIT_USAGE);
+        }
+    };
+    if transport == TransportKind::Ws {
+        let port = match flag_value(&args, "--port") {
+            Some(port) => match port.parse::<u16>() {
+                Ok(port) => port,
+                Err(_) => {
+                    eprintln!("Invalid --port value: {port}");
+                    std::process::exit(EXIT_USAGE);
+                }
+            },
+            None => config.port,
+        };
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+        log::info!("Tree-sitter MCP Server starting on WebSocket");
+        let handler = Arc::new(handler);
+        let options = mcp::ws_transport::WebSocketOptions {
+            allowed_origins: config.allowed_origins.clone(),
+            ..Default::default()
+        };
+        match tokio::task::spawn_blocking(move || {
+            mcp::ws_transport::run_websocket_server(address, handler, options)
+        })
+        .await
+        {
+            Ok(Err(e)) => eprintln!("WebSocket transport failed: {e}"),
+            Err(e) => eprintln!("WebSocket transport panicked: {e}"),
+            Ok(Ok(())) => {}
+        }
+        return Ok(());
+    }
 
     log::info!("Tree-sitter MCP Server starting");
 
     // Define server details and capabilities
-    let server_details = InitializeResult {
-        server_info: Implementation {
-            name: env!("CARGO_PKG_NAME").to_string(),
-            version: env!("CARGO_PKG_VERSION").to_string(),
-            title: Some("Tree-sitter MCP Server".to_string()),
-            description: Some(
-                "A high-performance MCP server for tree-sitter code analysis operations."
-                    .to_string(),
-            ),
-            icons: vec![],
-            website_url: None,
-        },
-        capabilities: ServerCapabilities {
-            tools: Some(ServerCapabilitiesTools { list_changed: None }),
-            ..Default::default()
-        },
-        meta: None,
-        instructions: Some(
-            "A high-performance MCP server for tree-sitter code analysis operations.".to_string(),
-        ),
-        protocol_version: ProtocolVersion::V2025_11_25.into(),
-    };
+    let server_details = handler::server_details();
 
     // Create stdio transport
     let transport = StdioTransport::new(TransportOptions::default())?;
 
-    // Create handler
-    let handler = TreesitterServerHandler::new();
-
     // Create and start MCP server
     let server: Arc<ServerRuntime> = server_runtime::create_server(McpServerOptions {
         server_details,
@@ -76,3 +139,11 @@ async fn main() -> SdkResult<()> {
 
     Ok(())
 }
+
+/// Value following `flag` on the command line
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|index| args.get(index + 1))
+        .map(String::as_str)
+}
@@ -0,0 +1,4 @@
+//! Additional transports for the MCP server.
+
+pub mod streaming;
+pub mod ws_transport;
@@ -0,0 +1,111 @@
+//! Streaming tool output
+//!
+//! A `tools/call` with `"stream": true` among its arguments is answered
+//! with NDJSON instead of one buffered result: one JSON object per line,
+//! written and flushed as soon as it is found, then a `{"total_count": N}`
+//! sentinel line.
+//!
+//! Only `type_map` streams so far, one object per type in walk order. Usage
+//! counting, sorting and the token budget need every type first, so they
+//! do not apply to a streamed call. Other tools ignore the flag.
+//!
+//! The CLI writes the lines to stdout and the WebSocket transport wraps each
+//! one in a progress notification. Stdio MCP calls go through
+//! [`TreesitterServerHandler::call_tool`](crate::handler::TreesitterServerHandler::call_tool),
+//! which rejects them.
+
+use std::io::{self, Write};
+
+use rust_mcp_sdk::schema::CallToolRequestParams;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::analysis::type_map;
+use crate::common::cancel::CancellationToken;
+
+/// Tools that can stream their results
+const STREAMING_TOOLS: &[&str] = &["type_map"];
+
+/// Writes NDJSON lines to `W`, flushing after every line
+#[derive(Debug)]
+pub struct StreamingWriter<W: Write> {
+    writer: W,
+    count: usize,
+}
+
+impl<W: Write> StreamingWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer, count: 0 }
+    }
+
+    /// Write `value` as one line and flush it
+    pub fn write_ndjson_line(&mut self, value: &impl Serialize) -> io::Result<()> {
+        self.write_line(value)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Lines written so far, not counting the sentinel
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The wrapped writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Write the `{"total_count": N}` sentinel that ends the stream
+    pub fn finish(&mut self) -> io::Result<()> {
+        self.write_line(&json!({ "total_count": self.count }))
+    }
+
+    fn write_line(&mut self, value: &impl Serialize) -> io::Result<()> {
+        let mut line = serde_json::to_vec(value).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to serialize stream line: {e}"),
+            )
+        })?;
+        line.push(b'\n');
+        // One `write_all` per line, so writers that frame each write (like
+        // the WebSocket transport) see whole lines.
+        self.writer.write_all(&line)?;
+        self.writer.flush()
+    }
+}
+
+/// Whether `request` asks for a stream from a tool that can stream
+pub fn wants_stream(request: &CallToolRequestParams) -> bool {
+    STREAMING_TOOLS.contains(&request.name.as_str())
+        && request
+            .arguments
+            .as_ref()
+            .and_then(|arguments| arguments.get("stream"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+}
+
+/// Run a streaming tool call, writing each result to `writer` as it is
+/// found, then the sentinel. Returns the number of results.
+pub fn stream_tool_call<W: Write>(
+    request: &CallToolRequestParams,
+    cancel: &CancellationToken,
+    writer: &mut StreamingWriter<W>,
+) -> io::Result<usize> {
+    let arguments = Value::Object(request.arguments.clone().unwrap_or_default());
+
+    match request.name.as_str() {
+        "type_map" => type_map::stream_types(&arguments, cancel, |ty| writer.write_ndjson_line(ty))
+            .map_err(|e| io::Error::other(e.to_string()))?,
+        other => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("Tool {other} does not support streaming"),
+            ))
+        }
+    }
+
+    writer.finish()?;
+    Ok(writer.count())
+}
@@ -0,0 +1,633 @@
+//! WebSocket Transport
+//!
+//! Serves MCP over WebSocket (RFC 6455) for clients that cannot spawn the
+//! server on stdio, such as browser extensions and remote agents. Each text
+//! frame carries one JSON-RPC message; every connection runs its own message
+//! loop on its own thread and shares one [`TreesitterServerHandler`].
+//!
+//! Handled methods: `initialize`, `ping`, `tools/list` and `tools/call`.
+//! Tool calls run on their own thread, so a `notifications/cancelled` for a
+//! call still in flight stops its directory scan and the call answers with a
+//! `cancelled` tool error. Closing the connection cancels all of its calls.
+//! Other notifications are accepted and ignored. Binary frames and
+//! extensions are not supported.
+//!
+//! Any web page can open a WebSocket to localhost, so an upgrade request
+//! carrying an `Origin` header is refused unless that origin is listed in
+//! [`WebSocketOptions::allowed_origins`]. Non-browser clients send no
+//! `Origin` and are always accepted. The number of connections and of tool
+//! calls running per connection are capped; a call over the cap is answered
+//! with a "server busy" error instead of being started.
+//!
+//! A streamed tool call (see [`streaming`](super::streaming)) that carries a
+//! `_meta.progressToken` sends each NDJSON line, sentinel included, as the
+//! `message` of a `notifications/progress` with that token before the
+//! JSON-RPC response, whose result text repeats the sentinel. MCP only allows
+//! progress notifications for tokens the client supplied, so without one no
+//! notifications are sent and the result text holds all the NDJSON lines.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rust_mcp_sdk::schema::schema_utils::CallToolError;
+use rust_mcp_sdk::schema::{CallToolRequestParams, CallToolResult, ListToolsResult};
+use serde_json::{json, Value};
+
+use crate::common::cancel::{CancellationRegistry, CancellationToken};
+use crate::handler::{self, TreesitterServerHandler};
+use crate::mcp::streaming::{self, StreamingWriter};
+use crate::mcp_types::CallToolResultExt;
+use crate::tools::TreesitterTools;
+
+/// Port used when `--transport ws` is given without `--port`
+pub const DEFAULT_PORT: u16 = 9229;
+
+/// GUID appended to the client key when computing `Sec-WebSocket-Accept`
+const HANDSHAKE_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+/// Largest message accepted from a client
+const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+/// Most header lines, request line included, read from an upgrade request
+const MAX_HEADER_LINES: usize = 100;
+/// Most bytes read from an upgrade request before giving up on it
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+/// Implementation-defined JSON-RPC server error for calls over the cap
+const SERVER_BUSY: i64 = -32000;
+
+/// Who may connect, and how much work clients may start at once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebSocketOptions {
+    /// `Origin` header values accepted in an upgrade request (default: none)
+    pub allowed_origins: Vec<String>,
+    /// Connections served at the same time; more are refused with 503
+    pub max_connections: usize,
+    /// Tool calls running at the same time on one connection
+    pub max_calls_per_connection: usize,
+}
+
+impl Default for WebSocketOptions {
+    fn default() -> Self {
+        Self {
+            allowed_origins: Vec::new(),
+            max_connections: 16,
+            max_calls_per_connection: 8,
+        }
+    }
+}
+
+/// Bind `addr` and serve WebSocket clients until the listener fails.
+pub fn run_websocket_server(
+    addr: SocketAddr,
+    server: Arc<TreesitterServerHandler>,
+    options: WebSocketOptions,
+) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    log::info!(
+        "WebSocket transport listening on {}",
+        listener.local_addr()?
+    );
+    serve_listener(listener, server, options)
+}
+
+/// Serve every connection accepted by `listener` on its own thread, up to
+/// `options.max_connections` at once.
+pub fn serve_listener(
+    listener: TcpListener,
+    server: Arc<TreesitterServerHandler>,
+    options: WebSocketOptions,
+) -> io::Result<()> {
+    let options = Arc::new(options);
+    let connections = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        let Some(slot) = Slot::acquire(&connections, options.max_connections) else {
+            log::warn!("Refusing WebSocket connection: too many connections");
+            if let Err(e) = reject(&mut stream, "503 Service Unavailable") {
+                log::debug!("Failed to refuse WebSocket connection: {e}");
+            }
+            continue;
+        };
+        let (server, options) = (Arc::clone(&server), Arc::clone(&options));
+        thread::spawn(move || {
+            let _slot = slot;
+            if let Err(e) = handle_connection(stream, &server, &options) {
+                log::warn!("WebSocket connection closed with error: {e}");
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    server: &Arc<TreesitterServerHandler>,
+    options: &WebSocketOptions,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    if !handshake(&mut reader, &mut writer, &options.allowed_origins)? {
+        return Ok(());
+    }
+
+    let writer = Arc::new(Mutex::new(writer));
+    let in_flight = Arc::new(CancellationRegistry::default());
+    let result = message_loop(
+        &mut reader,
+        &writer,
+        server,
+        &in_flight,
+        options.max_calls_per_connection,
+    );
+    in_flight.cancel_all();
+    result
+}
+
+fn message_loop(
+    reader: &mut impl Read,
+    writer: &Arc<Mutex<TcpStream>>,
+    server: &Arc<TreesitterServerHandler>,
+    in_flight: &Arc<CancellationRegistry>,
+    max_calls: usize,
+) -> io::Result<()> {
+    let running_calls = Arc::new(AtomicUsize::new(0));
+    while let Some(text) = read_message(reader, writer)? {
+        let message = match serde_json::from_str::<Value>(&text) {
+            Ok(message) => message,
+            Err(e) => {
+                let response =
+                    error_response(Value::Null, PARSE_ERROR, format!("Parse error: {e}"));
+                send(writer, OPCODE_TEXT, response.to_string().as_bytes())?;
+                continue;
+            }
+        };
+
+        match (message["method"].as_str(), message.get("id")) {
+            (Some("notifications/cancelled"), _) => {
+                let id = &message["params"]["requestId"];
+                if !in_flight.cancel(id) {
+                    log::debug!("No tool call in flight for cancelled request {id}");
+                }
+            }
+            (Some("tools/call"), Some(id)) => {
+                let id = id.clone();
+                let Some(slot) = Slot::acquire(&running_calls, max_calls) else {
+                    let response = error_response(
+                        id,
+                        SERVER_BUSY,
+                        format!("Server busy: {max_calls} tool calls already running"),
+                    );
+                    send(writer, OPCODE_TEXT, response.to_string().as_bytes())?;
+                    continue;
+                };
+                let cancel = in_flight.register(&id);
+                let (server, writer, in_flight) = (
+                    Arc::clone(server),
+                    Arc::clone(writer),
+                    Arc::clone(in_flight),
+                );
+                thread::spawn(move || {
+                    let _slot = slot;
+                    let response =
+                        process_message(&server, &message, &cancel, &mut notifier(&writer));
+                    in_flight.finish(&id);
+                    if let Some(response) = response {
+                        if let Err(e) = send(&writer, OPCODE_TEXT, response.to_string().as_bytes())
+                        {
+                            log::warn!("Failed to send tool call response: {e}");
+                        }
+                    }
+                });
+            }
+            _ => {
+                if let Some(response) = process_message(
+                    server,
+                    &message,
+                    &CancellationToken::default(),
+                    &mut notifier(writer),
+                ) {
+                    send(writer, OPCODE_TEXT, response.to_string().as_bytes())?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Handle one JSON-RPC message, returning the response for requests.
+///
+/// `cancel` is passed to tool calls so they can be stopped from another
+/// thread. A tool call asking for a stream with a progress token hands a
+/// progress notification per result to `notify` while it runs; the response
+/// then only carries the sentinel. Without a token the response carries
+/// every line.
+pub fn process_message(
+    server: &TreesitterServerHandler,
+    message: &Value,
+    cancel: &CancellationToken,
+    notify: &mut impl FnMut(Value) -> io::Result<()>,
+) -> Option<Value> {
+    let id = message.get("id")?.clone();
+    let method = message["method"].as_str().unwrap_or("");
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    let progress_token = params.pointer("/_meta/progressToken").cloned();
+
+    let result = match method {
+        "initialize" => to_value(handler::server_details()),
+        "ping" => Ok(json!({})),
+        "tools/list" => to_value(ListToolsResult {
+            tools: TreesitterTools::tools(),
+            meta: None,
+            next_cursor: None,
+        }),
+        "tools/call" => serde_json::from_value::<CallToolRequestParams>(params)
+            .map_err(|e| (INVALID_PARAMS, format!("Invalid tools/call params: {e}")))
+            .and_then(|request| {
+                let result = if streaming::wants_stream(&request) {
+                    stream_tool_call(&request, cancel, progress_token, notify)
+                } else {
+                    server.call_tool(request, cancel)
+                };
+                to_value(result.unwrap_or_else(CallToolResult::from))
+            }),
+        _ => Err((METHOD_NOT_FOUND, format!("Method not found: {method}"))),
+    };
+
+    Some(match result {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err((code, message)) => error_response(id, code, message),
+    })
+}
+
+/// Stream a tool call as progress notifications under `progress_token`, or
+/// collect its NDJSON lines into the result when there is no token.
+fn stream_tool_call(
+    request: &CallToolRequestParams,
+    cancel: &CancellationToken,
+    progress_token: Option<Value>,
+    notify: &mut impl FnMut(Value) -> io::Result<()>,
+) -> Result<CallToolResult, CallToolError> {
+    let Some(progress_token) = progress_token else {
+        let mut stream = StreamingWriter::new(Vec::new());
+        streaming::stream_tool_call(request, cancel, &mut stream).map_err(CallToolError::new)?;
+        let lines = String::from_utf8(stream.into_inner()).map_err(CallToolError::new)?;
+        return Ok(CallToolResult::success(lines));
+    };
+
+    let mut stream = StreamingWriter::new(ProgressNotifications {
+        progress_token,
+        progress: 0,
+        notify,
+    });
+    let total =
+        streaming::stream_tool_call(request, cancel, &mut stream).map_err(CallToolError::new)?;
+    Ok(CallToolResult::success(
+        json!({ "total_count": total }).to_string(),
+    ))
+}
+
+fn to_value(value: impl serde::Serialize) -> Result<Value, (i64, String)> {
+    serde_json::to_value(value).map_err(|e| (INTERNAL_ERROR, e.to_string()))
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {"code": code, "message": message},
+    })
+}
+
+/// Answer the HTTP upgrade request; `false` if it was refused.
+///
+/// Only a `GET` with `Upgrade: websocket`, `Connection: Upgrade` and a key
+/// is accepted, and only from an allowed `Origin` when one is sent. The
+/// request may not exceed [`MAX_HEADER_LINES`] lines or [`MAX_HEADER_BYTES`].
+fn handshake(
+    reader: &mut impl BufRead,
+    writer: &mut impl Write,
+    allowed_origins: &[String],
+) -> io::Result<bool> {
+    let mut request_line = None;
+    let (mut key, mut origin) = (None, None);
+    let (mut upgrade, mut connection_upgrade) = (false, false);
+    let mut remaining = MAX_HEADER_BYTES;
+    let mut lines = 0;
+    loop {
+        if lines == MAX_HEADER_LINES {
+            return reject(writer, "431 Request Header Fields Too Large");
+        }
+        lines += 1;
+
+        let mut line = String::new();
+        let read = reader
+            .by_ref()
+            .take(remaining as u64)
+            .read_line(&mut line)?;
+        remaining -= read;
+        if !line.ends_with('\n') {
+            if remaining == 0 {
+                return reject(writer, "431 Request Header Fields Too Large");
+            }
+            // The client went away mid-request.
+            return Ok(false);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if request_line.is_none() {
+            request_line = Some(line.to_string());
+            continue;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            match name.trim().to_ascii_lowercase().as_str() {
+                "sec-websocket-key" => key = Some(value.to_string()),
+                "origin" => origin = Some(value.to_string()),
+                "upgrade" => upgrade = value.eq_ignore_ascii_case("websocket"),
+                "connection" => {
+                    connection_upgrade = value
+                        .split(',')
+                        .any(|token| token.trim().eq_ignore_ascii_case("upgrade"))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if !request_line.is_some_and(|line| line.starts_with("GET ")) {
+        return reject(writer, "405 Method Not Allowed");
+    }
+    if let Some(origin) = origin {
+        if !allowed_origins
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(&origin))
+        {
+            log::warn!("Refusing WebSocket upgrade from origin {origin}");
+            return reject(writer, "403 Forbidden");
+        }
+    }
+    let Some(key) = key.filter(|_| upgrade && connection_upgrade) else {
+        return reject(writer, "400 Bad Request");
+    };
+
+    write!(
+        writer,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(&key)
+    )?;
+    writer.flush()?;
+    Ok(true)
+}
+
+/// Answer with an empty HTTP error response; always `false`, for
+/// [`handshake`] to return
+fn reject(writer: &mut impl Write, status: &str) -> io::Result<bool> {
+    write!(
+        writer,
+        "HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+    )?;
+    writer.flush()?;
+    Ok(false)
+}
+
+/// Holds one of a limited number of places (a connection or a running
+/// call) and gives it back when dropped
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    fn acquire(count: &Arc<AtomicUsize>, limit: usize) -> Option<Self> {
+        count
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |taken| {
+                (taken < limit).then_some(taken + 1)
+            })
+            .ok()?;
+        Some(Slot(Arc::clone(count)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// `Sec-WebSocket-Accept` value for a client's `Sec-WebSocket-Key`
+pub fn accept_key(key: &str) -> String {
+    BASE64.encode(sha1(format!("{key}{HANDSHAKE_GUID}").as_bytes()))
+}
+
+/// Read the next text message, answering pings along the way; `None` once
+/// the client closes the connection.
+fn read_message(reader: &mut impl Read, writer: &Mutex<impl Write>) -> io::Result<Option<String>> {
+    let mut message = Vec::new();
+    loop {
+        let Some((fin, opcode, payload)) = read_frame(reader)? else {
+            return Ok(None);
+        };
+        match opcode {
+            OPCODE_TEXT | OPCODE_CONTINUATION => {
+                if message.len() + payload.len() > MAX_MESSAGE_SIZE {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "WebSocket message too large",
+                    ));
+                }
+                message.extend_from_slice(&payload);
+                if fin {
+                    return String::from_utf8(message)
+                        .map(Some)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+                }
+            }
+            OPCODE_PING => send(writer, OPCODE_PONG, &payload)?,
+            OPCODE_PONG => {}
+            OPCODE_CLOSE => {
+                send(writer, OPCODE_CLOSE, &payload)?;
+                return Ok(None);
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Unsupported WebSocket opcode: {opcode:#x}"),
+                ))
+            }
+        }
+    }
+}
+
+/// Read one frame as `(fin, opcode, unmasked payload)`; `None` at end of stream.
+fn read_frame(reader: &mut impl Read) -> io::Result<Option<(bool, u8, Vec<u8>)>> {
+    let mut header = [0u8; 2];
+    match reader.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+
+    let fin = header[0] & 0x80 != 0;
+    let opcode = header[0] & 0x0F;
+    let masked = header[1] & 0x80 != 0;
+    let length = match header[1] & 0x7F {
+        126 => {
+            let mut bytes = [0u8; 2];
+            reader.read_exact(&mut bytes)?;
+            u64::from(u16::from_be_bytes(bytes))
+        }
+        127 => {
+            let mut bytes = [0u8; 8];
+            reader.read_exact(&mut bytes)?;
+            u64::from_be_bytes(bytes)
+        }
+        length => u64::from(length),
+    };
+    let length = usize::try_from(length)
+        .ok()
+        .filter(|length| *length <= MAX_MESSAGE_SIZE)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "WebSocket frame too large"))?;
+
+    let mut mask = [0u8; 4];
+    if masked {
+        reader.read_exact(&mut mask)?;
+    }
+    let mut payload = vec![0; length];
+    reader.read_exact(&mut payload)?;
+    if masked {
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+    }
+    Ok(Some((fin, opcode, payload)))
+}
+
+/// Sends each notification as a text frame of its own
+fn notifier(writer: &Mutex<impl Write>) -> impl FnMut(Value) -> io::Result<()> + '_ {
+    move |notification| send(writer, OPCODE_TEXT, notification.to_string().as_bytes())
+}
+
+/// Turns every [`StreamingWriter`] line into a `notifications/progress`
+/// carrying the line as its `message`
+struct ProgressNotifications<'a, N> {
+    progress_token: Value,
+    progress: u64,
+    notify: &'a mut N,
+}
+
+impl<N: FnMut(Value) -> io::Result<()>> Write for ProgressNotifications<'_, N> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let line = std::str::from_utf8(buf.strip_suffix(b"\n").unwrap_or(buf))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.progress += 1;
+        (self.notify)(json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/progress",
+            "params": {
+                "progressToken": self.progress_token,
+                "progress": self.progress,
+                "message": line,
+            },
+        }))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Write one frame on a writer shared with the tool call threads.
+fn send(writer: &Mutex<impl Write>, opcode: u8, payload: &[u8]) -> io::Result<()> {
+    // Frames are written with a single `write_all`, so a poisoned lock
+    // still guards a usable stream.
+    let mut writer = writer
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    write_frame(&mut *writer, opcode, payload)
+}
+
+/// Write one unmasked, unfragmented frame.
+fn write_frame(writer: &mut impl Write, opcode: u8, payload: &[u8]) -> io::Result<()> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        length @ 0..=125 => frame.push(length as u8),
+        length @ 126..=0xFFFF => {
+            frame.push(126);
+            frame.extend_from_slice(&(length as u16).to_be_bytes());
+        }
+        length => {
+            frame.push(127);
+            frame.extend_from_slice(&(length as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    writer.write_all(&frame)?;
+    writer.flush()
+}
+
+/// SHA-1 digest, needed only for the opening handshake.
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks_exact(64) {
+        let mut words = [0u32; 80];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            words[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (i, word) in words.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (value, add) in state.iter_mut().zip([a, b, c, d, e]) {
+            *value = value.wrapping_add(add);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (chunk, value) in digest.chunks_exact_mut(4).zip(state) {
+        chunk.copy_from_slice(&value.to_be_bytes());
+    }
+    digest
+}
@@ -2,15 +2,30 @@
 //!
 //! This module re-exports MCP types from rust-mcp-sdk for use in analysis modules.
 
-pub use rust_mcp_sdk::schema::{CallToolResult, TextContent};
+pub use rust_mcp_sdk::schema::{
+    CallToolResult, EmbeddedResource, TextContent, TextResourceContents,
+};
 
 // Helper extension trait for CallToolResult
 pub trait CallToolResultExt {
     fn success(text: String) -> Self;
+
+    /// Text result carried as an embedded resource so it can declare a MIME type
+    fn success_with_mime_type(text: String, uri: &str, mime_type: &str) -> Self;
 }
 
 impl CallToolResultExt for CallToolResult {
     fn success(text: String) -> Self {
         CallToolResult::text_content(vec![TextContent::from(text)])
     }
+
+    fn success_with_mime_type(text: String, uri: &str, mime_type: &str) -> Self {
+        let contents = TextResourceContents {
+            meta: None,
+            mime_type: Some(mime_type.to_string()),
+            text,
+            uri: uri.to_string(),
+        };
+        CallToolResult::embedded_resource(vec![EmbeddedResource::new(contents.into(), None, None)])
+    }
 }
@@ -0,0 +1,177 @@
+//! CSV/TSV tables for extracted types.
+//!
+//! The first section lists types; fields, variants and members follow as
+//! separate sections, each with its own header and separated by a blank
+//! line. Sections without rows are omitted.
+
+use crate::analysis::path_utils;
+use crate::extraction::types::TypeDefinition;
+
+/// Field separator for tabular output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// RFC 4180: fields containing `,`, `"` or line breaks are quoted
+    Comma,
+    /// Tab-separated: tabs, line breaks and backslashes are escaped as `\t`, `\n`, `\r`, `\\`
+    Tab,
+}
+
+/// Render types as CSV or TSV sections.
+pub fn render_types(types: &[TypeDefinition], delimiter: Delimiter) -> String {
+    let mut type_rows = Vec::new();
+    let mut field_rows = Vec::new();
+    let mut variant_rows = Vec::new();
+    let mut member_rows = Vec::new();
+
+    for ty in types {
+        let file = path_utils::to_relative_path(ty.file.to_string_lossy().as_ref());
+        let line = ty.line.to_string();
+        let usage = ty.usage_count.to_string();
+        type_rows.push(record(
+            &[&ty.name, ty.kind.as_str(), &file, &line, &usage],
+            delimiter,
+        ));
+
+        for field in ty.fields.iter().flatten() {
+            field_rows.push(record(
+                &[&ty.name, &field.name, &field.type_annotation],
+                delimiter,
+            ));
+        }
+        for variant in ty.variants.iter().flatten() {
+            variant_rows.push(record(
+                &[
+                    &ty.name,
+                    &variant.name,
+                    variant.type_annotation.as_deref().unwrap_or(""),
+                ],
+                delimiter,
+            ));
+        }
+        for member in ty.members.iter().flatten() {
+            member_rows.push(record(
+                &[&ty.name, &member.name, &member.type_annotation],
+                delimiter,
+            ));
+        }
+    }
+
+    let mut sections = vec![section(
+        &["name", "kind", "file", "line", "usage_count"],
+        type_rows,
+        delimiter,
+    )];
+    for (header, rows) in [
+        (["type", "field", "field_type"], field_rows),
+        (["type", "variant", "variant_type"], variant_rows),
+        (["type", "member", "member_type"], member_rows),
+    ] {
+        if !rows.is_empty() {
+            sections.push(section(&header, rows, delimiter));
+        }
+    }
+
+    sections.join("\n\n")
+}
+
+fn section(header: &[&str], rows: Vec<String>, delimiter: Delimiter) -> String {
+    let mut lines = vec![record(header, delimiter)];
+    lines.extend(rows);
+    lines.join("\n")
+}
+
+fn record(fields: &[&str], delimiter: Delimiter) -> String {
+    match delimiter {
+        Delimiter::Comma => fields
+            .iter()
+            .map(|field| quote_csv(field))
+            .collect::<Vec<_>>()
+            .join(","),
+        Delimiter::Tab => fields
+            .iter()
+            .map(|field| escape_tsv(field))
+            .collect::<Vec<_>>()
+            .join("\t"),
+    }
+}
+
+fn quote_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn escape_tsv(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::extraction::types::{Field, TypeKind, Variant};
+
+    fn definition(name: &str, kind: TypeKind) -> TypeDefinition {
+        TypeDefinition {
+            name: name.to_string(),
+            kind,
+            file: PathBuf::from("lib.rs"),
+            line: 3,
+            column: 1,
+            end_line: 3,
+            end_column: 2,
+            signature: String::new(),
+            usage_count: 2,
+            fields: None,
+            variants: None,
+            members: None,
+            generics: None,
+            where_bounds: None,
+            framework_metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_csv_quotes_fields_with_delimiters() {
+        let mut config = definition("Config", TypeKind::Struct);
+        config.fields = Some(vec![Field {
+            name: "pairs".to_string(),
+            type_annotation: "HashMap<String, \"quoted\">".to_string(),
+            resolved_to: None,
+        }]);
+
+        assert_eq!(
+            render_types(&[config], Delimiter::Comma),
+            "name,kind,file,line,usage_count\n\
+             Config,struct,lib.rs,3,2\n\
+             \n\
+             type,field,field_type\n\
+             Config,pairs,\"HashMap<String, \"\"quoted\"\">\""
+        );
+    }
+
+    #[test]
+    fn test_tsv_escapes_tabs_and_skips_empty_sections() {
+        let mut mode = definition("Mode", TypeKind::Enum);
+        mode.variants = Some(vec![Variant {
+            name: "Custom".to_string(),
+            type_annotation: Some("(u8,\tu8)".to_string()),
+        }]);
+
+        assert_eq!(
+            render_types(&[mode], Delimiter::Tab),
+            "name\tkind\tfile\tline\tusage_count\n\
+             Mode\tenum\tlib.rs\t3\t2\n\
+             \n\
+             type\tvariant\tvariant_type\n\
+             Mode\tCustom\t(u8,\\tu8)"
+        );
+    }
+}
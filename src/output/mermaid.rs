@@ -0,0 +1,268 @@
+//! Mermaid class diagrams for extracted types.
+//!
+//! Output is kept terse for token budgets: no visibility markers, methods
+//! without parameters, and generics rendered with Mermaid's `~T~` syntax.
+
+use crate::extraction::types::{TypeDefinition, TypeKind};
+
+/// Render types as a Mermaid `classDiagram` block.
+///
+/// Fields come from `fields`, enum variants from `variants`, and members
+/// from `members` (entries whose type is a call signature render as methods).
+/// Inheritance arrows are read from each type's declaration line.
+pub fn render_class_diagram(types: &[TypeDefinition]) -> String {
+    let mut lines = vec!["classDiagram".to_string()];
+    let mut arrows = Vec::new();
+
+    for ty in types {
+        let name = class_id(&ty.name);
+        let mut body = Vec::new();
+
+        match ty.kind {
+            TypeKind::Enum => body.push("<<enumeration>>".to_string()),
+            TypeKind::Trait | TypeKind::Interface | TypeKind::Protocol => {
+                body.push("<<interface>>".to_string())
+            }
+            _ => {}
+        }
+
+        for field in ty.fields.iter().flatten() {
+            body.push(member_line(&field.name, &field.type_annotation));
+        }
+        for variant in ty.variants.iter().flatten() {
+            body.push(mermaid_name(&variant.name));
+        }
+        for member in ty.members.iter().flatten() {
+            if member.type_annotation.contains('(') {
+                body.push(format!("{}()", mermaid_name(&member.name)));
+            } else {
+                body.push(member_line(&member.name, &member.type_annotation));
+            }
+        }
+
+        if body.is_empty() {
+            lines.push(format!("class {name}"));
+        } else {
+            lines.push(format!("class {name} {{"));
+            lines.extend(body.into_iter().map(|line| format!("  {line}")));
+            lines.push("}".to_string());
+        }
+
+        for parent in parent_types(&ty.signature) {
+            arrows.push(format!("{} <|-- {name}", class_id(&parent)));
+        }
+    }
+
+    lines.extend(arrows);
+    lines.join("\n")
+}
+
+fn member_line(name: &str, type_annotation: &str) -> String {
+    if type_annotation.is_empty() {
+        mermaid_name(name)
+    } else {
+        format!("{}: {}", mermaid_name(name), mermaid_name(type_annotation))
+    }
+}
+
+/// Mermaid reserves `<>` and `{}`; generics use `~` instead.
+fn mermaid_name(text: &str) -> String {
+    text.replace(['<', '>'], "~")
+        .replace(['{', '}'], "")
+        .replace('\n', " ")
+}
+
+/// Class identifiers only allow word characters, so path separators
+/// (`fmt::Debug`, `abc.ABC`) become `_`.
+fn class_id(text: &str) -> String {
+    mermaid_name(text).replace("::", "_").replace('.', "_")
+}
+
+/// Base types named in a declaration line.
+///
+/// Handles `extends`/`implements` (TS, Java), `class A(B):` (Python),
+/// `class A : B` (C#) and `trait A: B + C` (Rust supertraits).
+fn parent_types(signature: &str) -> Vec<String> {
+    let stripped = strip_generics(signature);
+    let head = stripped
+        .split('{')
+        .next()
+        .unwrap_or("")
+        .split(" where ")
+        .next()
+        .unwrap_or("")
+        .trim();
+
+    let list = if let Some((_, rest)) = head.split_once(" extends ") {
+        rest.replace(" implements ", ",")
+    } else if let Some((_, rest)) = head.split_once(" implements ") {
+        rest.to_string()
+    } else if head.starts_with("class ") && head.contains('(') {
+        let inner = head
+            .split_once('(')
+            .map(|(_, rest)| rest.rsplit_once(')').map_or(rest, |(args, _)| args))
+            .unwrap_or("");
+        inner.to_string()
+    } else if let Some((_, rest)) = head.split_once(':') {
+        rest.replace('+', ",")
+    } else {
+        return Vec::new();
+    };
+
+    split_top_level(&list)
+        .into_iter()
+        .map(|parent| parent.trim().to_string())
+        .filter(|parent| {
+            !parent.is_empty()
+                && !parent.contains('=')
+                && parent != "object"
+                && parent
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '.' || c == ':')
+        })
+        .collect()
+}
+
+/// Drop everything between the outermost `<...>` / `[...]` pairs.
+///
+/// The `>` of a `->` arrow and unmatched closers are not brackets.
+fn strip_generics(text: &str) -> String {
+    let mut depth = 0;
+    let mut out = String::new();
+    let mut previous = None;
+    for ch in text.chars() {
+        match ch {
+            '<' | '[' => depth += 1,
+            '>' | ']' if depth > 0 && !(ch == '>' && previous == Some('-')) => depth -= 1,
+            _ if depth == 0 => out.push(ch),
+            _ => {}
+        }
+        previous = Some(ch);
+    }
+    out
+}
+
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, ch) in text.char_indices() {
+        match ch {
+            '<' | '[' | '(' => depth += 1,
+            '>' | ']' | ')' if depth > 0 => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&text[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::extraction::types::{Field, Variant};
+
+    fn definition(name: &str, kind: TypeKind, signature: &str) -> TypeDefinition {
+        TypeDefinition {
+            name: name.to_string(),
+            kind,
+            file: PathBuf::from("lib.rs"),
+            line: 1,
+            column: 1,
+            end_line: 1,
+            end_column: 2,
+            signature: signature.to_string(),
+            usage_count: 0,
+            fields: None,
+            variants: None,
+            members: None,
+            generics: None,
+            where_bounds: None,
+            framework_metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_parent_types_by_language() {
+        assert_eq!(
+            parent_types("export class Admin extends User implements Auditable, Named {"),
+            vec!["User", "Auditable", "Named"]
+        );
+        assert_eq!(
+            parent_types("class Dog(Animal, metaclass=ABCMeta):"),
+            vec!["Animal"]
+        );
+        assert_eq!(
+            parent_types("public class Repo : Base<T>, IRepo {"),
+            vec!["Base", "IRepo"]
+        );
+        assert_eq!(
+            parent_types("pub trait Shape: Debug + Clone {"),
+            vec!["Debug", "Clone"]
+        );
+        assert!(parent_types("pub struct Point<T: Copy> {").is_empty());
+        assert_eq!(
+            parent_types("interface Box<T extends Item> extends Container {"),
+            vec!["Container"]
+        );
+        assert_eq!(
+            parent_types("pub trait Handler: Fn(u32) -> bool + Send {"),
+            vec!["Send"]
+        );
+        assert_eq!(
+            parent_types("pub trait Visitor<F: Fn() -> u8>: fmt::Debug {"),
+            vec!["fmt::Debug"]
+        );
+    }
+
+    #[test]
+    fn test_strip_generics_keeps_arrows() {
+        assert_eq!(strip_generics("fn f() -> Vec<u8>"), "fn f() -> Vec");
+        assert_eq!(strip_generics("Box<dyn Fn() -> T> + Send"), "Box + Send");
+        assert_eq!(strip_generics("a > b"), "a > b");
+    }
+
+    #[test]
+    fn test_render_class_diagram_sanitizes_paths_in_class_ids() {
+        let handler = definition(
+            "Handler",
+            TypeKind::Trait,
+            "pub trait Handler: fmt::Debug + serde::Serialize {",
+        );
+
+        let diagram = render_class_diagram(&[handler]);
+
+        assert_eq!(
+            diagram,
+            "classDiagram\nclass Handler {\n  <<interface>>\n}\nfmt_Debug <|-- Handler\nserde_Serialize <|-- Handler"
+        );
+    }
+
+    #[test]
+    fn test_render_class_diagram_enum_and_generics() {
+        let mut point = definition("Point", TypeKind::Struct, "pub struct Point {");
+        point.fields = Some(vec![Field {
+            name: "tags".to_string(),
+            type_annotation: "Vec<String>".to_string(),
+            resolved_to: None,
+        }]);
+        let mut color = definition("Color", TypeKind::Enum, "enum Color {");
+        color.variants = Some(vec![Variant {
+            name: "Red".to_string(),
+            type_annotation: None,
+        }]);
+
+        let diagram = render_class_diagram(&[point, color]);
+
+        assert_eq!(
+            diagram,
+            "classDiagram\nclass Point {\n  tags: Vec~String~\n}\nclass Color {\n  <<enumeration>>\n  Red\n}"
+        );
+    }
+}
@@ -0,0 +1,4 @@
+//! Alternative renderings of extraction results.
+
+pub mod csv;
+pub mod mermaid;
@@ -0,0 +1,129 @@
+//! Parse-tree cache
+//!
+//! Agents often ask for the shape, usages and code of the same file in quick
+//! succession. [`CachedParser`] keeps the most recently used trees keyed by
+//! path and modification time so unchanged files are parsed once.
+//!
+//! There is one cache per process, reached through [`shared`]. Analysis code
+//! calls it directly instead of having a parser threaded through from the
+//! server handler, so the CLI, LSP adapter and every MCP transport share the
+//! same trees.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::UNIX_EPOCH;
+
+use eyre::Result;
+use indexmap::IndexMap;
+use tree_sitter::Tree;
+
+use super::{parse_code, Language};
+
+/// Environment variable overriding the number of cached trees
+pub const CACHE_SIZE_VAR: &str = "TREESITTER_CACHE_SIZE";
+const DEFAULT_CACHE_SIZE: usize = 64;
+
+type CacheKey = (PathBuf, u64);
+
+/// Least-recently-used cache of parse trees and the source they were parsed
+/// from.
+///
+/// A hit requires the same path, modification time, source text and
+/// grammar, so a file rewritten within the timestamp resolution is still
+/// re-parsed.
+pub struct CachedParser {
+    capacity: usize,
+    /// Entries in use order, least recently used first
+    entries: Mutex<IndexMap<CacheKey, Arc<(Tree, String)>>>,
+}
+
+impl CachedParser {
+    /// A cache holding at most `capacity` trees; 0 disables caching.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(IndexMap::new()),
+        }
+    }
+
+    /// A cache sized by `TREESITTER_CACHE_SIZE` (default 64).
+    pub fn from_env() -> Self {
+        Self::new(default_capacity())
+    }
+
+    /// Parse `source`, the current contents of `path`, reusing the cached
+    /// tree when the file has not changed since it was last parsed.
+    pub fn parse(
+        &self,
+        path: &Path,
+        source: &str,
+        language: Language,
+    ) -> Result<Arc<(Tree, String)>> {
+        let Some(key) = cache_key(path).filter(|_| self.capacity > 0) else {
+            return Ok(Arc::new((
+                parse_code(source, language)?,
+                source.to_string(),
+            )));
+        };
+
+        let mut entries = self.lock();
+        if let Some(cached) = entries.shift_remove(&key) {
+            if cached.1 == source && *cached.0.language() == language.tree_sitter_language() {
+                entries.insert(key, Arc::clone(&cached));
+                return Ok(cached);
+            }
+        }
+        drop(entries);
+
+        let parsed = Arc::new((parse_code(source, language)?, source.to_string()));
+
+        let mut entries = self.lock();
+        // Older versions of the file can never be hit again.
+        entries.retain(|(cached_path, _), _| cached_path != &key.0);
+        while entries.len() >= self.capacity {
+            entries.shift_remove_index(0);
+        }
+        entries.insert(key, Arc::clone(&parsed));
+        Ok(parsed)
+    }
+
+    /// Number of cached trees
+    pub fn tree_count(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, IndexMap<CacheKey, Arc<(Tree, String)>>> {
+        // A panic while holding the lock cannot leave the map inconsistent.
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+static SHARED: OnceLock<Arc<CachedParser>> = OnceLock::new();
+
+/// The process-wide cache used by the analysis tools.
+pub fn shared() -> Arc<CachedParser> {
+    Arc::clone(SHARED.get_or_init(|| Arc::new(CachedParser::from_env())))
+}
+
+/// The process-wide cache, created with `capacity` unless it already exists.
+pub fn init_shared(capacity: usize) -> Arc<CachedParser> {
+    Arc::clone(SHARED.get_or_init(|| Arc::new(CachedParser::new(capacity))))
+}
+
+/// `TREESITTER_CACHE_SIZE`, or 64 when unset or invalid
+pub fn default_capacity() -> usize {
+    env::var(CACHE_SIZE_VAR)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(DEFAULT_CACHE_SIZE)
+}
+
+fn cache_key(path: &Path) -> Option<CacheKey> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    let nanos = modified.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+    Some((path.to_path_buf(), nanos as u64))
+}
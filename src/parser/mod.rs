@@ -1,6 +1,10 @@
+pub mod cache;
+
 use eyre::{bail, Result};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
-use tree_sitter::{Parser, Tree};
+use tree_sitter::{Parser, Range, Tree};
 
 /// Supported programming languages for tree-sitter parsing
 ///
@@ -14,8 +18,12 @@ pub enum Language {
     Python,
     /// JavaScript (.js, .mjs, .cjs)
     JavaScript,
-    /// TypeScript (.ts, .tsx)
+    /// TypeScript (.ts)
     TypeScript,
+    /// JavaScript with JSX (.jsx), parsed with the JavaScript grammar
+    Jsx,
+    /// TypeScript with JSX (.tsx), parsed with the TSX grammar
+    Tsx,
     /// HTML markup (.html, .htm)
     Html,
     /// CSS stylesheets (.css)
@@ -28,6 +36,9 @@ pub enum Language {
     Java,
     /// Go programming language (.go)
     Go,
+    /// Svelte components (.svelte); the template is parsed with the HTML
+    /// grammar, `<script>` and `<style>` blocks separately
+    Svelte,
 }
 
 impl Language {
@@ -38,13 +49,41 @@ impl Language {
             Language::Python => "Python",
             Language::JavaScript => "JavaScript",
             Language::TypeScript => "TypeScript",
+            Language::Jsx => "JSX",
+            Language::Tsx => "TSX",
             Language::Html => "HTML",
             Language::Css => "CSS",
             Language::Swift => "Swift",
             Language::CSharp => "C#",
             Language::Java => "Java",
             Language::Go => "Go",
+            Language::Svelte => "Svelte",
+        }
+    }
+
+    /// Look up a language by name, case-insensitively ("rust", "C#" or "csharp", "tsx", ...)
+    pub fn from_name(name: &str) -> Option<Language> {
+        const ALL: [Language; 13] = [
+            Language::Rust,
+            Language::Python,
+            Language::JavaScript,
+            Language::TypeScript,
+            Language::Jsx,
+            Language::Tsx,
+            Language::Html,
+            Language::Css,
+            Language::Swift,
+            Language::CSharp,
+            Language::Java,
+            Language::Go,
+            Language::Svelte,
+        ];
+        let name = name.trim();
+        if name.eq_ignore_ascii_case("csharp") {
+            return Some(Language::CSharp);
         }
+        ALL.into_iter()
+            .find(|language| language.name().eq_ignore_ascii_case(name))
     }
 
     /// Get the tree-sitter language grammar for this language
@@ -54,12 +93,15 @@ impl Language {
             Language::Python => tree_sitter_python::LANGUAGE.into(),
             Language::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
             Language::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Language::Jsx => tree_sitter_javascript::LANGUAGE.into(),
+            Language::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
             Language::Html => tree_sitter_html::LANGUAGE.into(),
             Language::Css => tree_sitter_css::LANGUAGE.into(),
             Language::Swift => tree_sitter_swift::LANGUAGE.into(),
             Language::CSharp => tree_sitter_c_sharp::LANGUAGE.into(),
             Language::Java => tree_sitter_java::LANGUAGE.into(),
             Language::Go => tree_sitter_go::LANGUAGE.into(),
+            Language::Svelte => tree_sitter_html::LANGUAGE.into(),
         }
     }
 }
@@ -73,20 +115,26 @@ impl Language {
 /// - `.rs` → Rust
 /// - `.py` → Python
 /// - `.js`, `.mjs`, `.cjs` → JavaScript
-/// - `.ts`, `.tsx` → TypeScript
+/// - `.ts` → TypeScript
+/// - `.jsx` → JSX
+/// - `.tsx` → TSX
 /// - `.html`, `.htm` → HTML
 /// - `.css` → CSS
 /// - `.swift` → Swift
 /// - `.cs` → C#
 /// - `.java` → Java
 /// - `.go` → Go
+/// - `.svelte` → Svelte
+///
+/// Files without an extension fall back to their shebang line (see
+/// [`detect_language_from_content`]), read from disk when the file exists.
 ///
 /// # Arguments
 /// * `path` - File path (can be absolute, relative, or just a filename)
 ///
 /// # Errors
 /// Returns an error if:
-/// - The file has no extension
+/// - The file has no extension and no recognised shebang
 /// - The extension is not supported
 ///
 /// # Examples
@@ -119,19 +167,76 @@ pub fn detect_language(path: impl AsRef<Path>) -> Result<Language> {
         Some("rs") => Ok(Language::Rust),
         Some("py") => Ok(Language::Python),
         Some("js") | Some("mjs") | Some("cjs") => Ok(Language::JavaScript),
-        Some("ts") | Some("tsx") => Ok(Language::TypeScript),
+        Some("ts") => Ok(Language::TypeScript),
+        Some("jsx") => Ok(Language::Jsx),
+        Some("tsx") => Ok(Language::Tsx),
         Some("html") | Some("htm") => Ok(Language::Html),
         Some("css") => Ok(Language::Css),
         Some("swift") => Ok(Language::Swift),
         Some("cs") => Ok(Language::CSharp),
         Some("java") => Ok(Language::Java),
         Some("go") => Ok(Language::Go),
+        Some("svelte") => Ok(Language::Svelte),
         Some(ext) => {
             bail!("Unsupported file extension: .{}", ext)
         }
-        None => {
-            bail!("No file extension found in path: {}", path.display())
-        }
+        None => match read_first_line(path)
+            .as_deref()
+            .and_then(language_from_shebang)
+        {
+            Some(language) => Ok(language),
+            None => bail!("No file extension found in path: {}", path.display()),
+        },
+    }
+}
+
+/// Detect language from the path, falling back to the shebang line of
+/// `source` when the extension is missing or unsupported
+///
+/// Recognised interpreters: `python`/`python3` → Python, `node`/`nodejs`/
+/// `bun`/`deno` → JavaScript, `ts-node`/`tsx` → TypeScript, `swift` → Swift.
+/// `#!/usr/bin/env` is followed to the interpreter it names.
+///
+/// # Examples
+/// ```
+/// use treesitter_mcp::parser::{detect_language_from_content, Language};
+///
+/// let lang = detect_language_from_content("bin/deploy", "#!/usr/bin/env python3\n").unwrap();
+/// assert_eq!(lang, Language::Python);
+/// ```
+pub fn detect_language_from_content(path: impl AsRef<Path>, source: &str) -> Result<Language> {
+    let path = path.as_ref();
+    detect_language(path).or_else(|err| {
+        source
+            .lines()
+            .next()
+            .and_then(language_from_shebang)
+            .ok_or(err)
+    })
+}
+
+/// First line of the file at `path`, if it can be read
+fn read_first_line(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    let mut line = String::new();
+    BufReader::new(file).take(256).read_line(&mut line).ok()?;
+    Some(line)
+}
+
+fn language_from_shebang(line: &str) -> Option<Language> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = words.find(|word| !word.starts_with('-') && !word.contains('='))?;
+    }
+
+    let interpreter = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    match interpreter {
+        "python" => Some(Language::Python),
+        "node" | "nodejs" | "bun" | "deno" => Some(Language::JavaScript),
+        "ts-node" | "tsx" => Some(Language::TypeScript),
+        "swift" => Some(Language::Swift),
+        _ => None,
     }
 }
 
@@ -185,3 +290,17 @@ pub fn parse_code(source: &str, language: Language) -> Result<Tree> {
 
     Ok(tree)
 }
+
+/// Parse only `range` of `source`.
+///
+/// Node positions in the returned tree are relative to the whole source, so
+/// lines and byte offsets need no adjustment.
+pub fn parse_code_range(source: &str, language: Language, range: Range) -> Result<Tree> {
+    let mut parser = Parser::new();
+    parser.set_language(&language.tree_sitter_language())?;
+    parser.set_included_ranges(&[range])?;
+
+    parser
+        .parse(source, None)
+        .ok_or_else(|| eyre::eyre!("Failed to parse {} code", language.name()))
+}
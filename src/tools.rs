@@ -8,10 +8,14 @@ use rust_mcp_sdk::schema::{schema_utils::CallToolError, CallToolResult};
 use rust_mcp_sdk::tool_box;
 
 use crate::analysis::{
-    call_graph, code_map, diff, find_usages, format_diagnostics, format_references,
-    minimal_edit_context, query_pattern, relevant_tests, review_context, symbol_at_line,
-    verify_edit, view_code,
+    annotation_inference, build_files, call_graph, code_map, comments, context_assembler,
+    coverage_map, css_analysis, diff, fastapi, find_usages, format_diagnostics, format_references,
+    get_symbol, html_analysis, import_graph, jsx, lockfile, lsp, macros, metrics,
+    minimal_edit_context, module_tree, nestjs, nextjs, public_api, query_pattern, refactor,
+    relevant_tests, review_context, routes, safety, semantic_tokens, stale_imports, svelte,
+    symbol_at_line, symbol_inventory, tests_finder, type_diff, unused, verify_edit, view_code, vue,
 };
+use crate::common::cancel::CancellationToken;
 
 // Helper function for serde default
 fn default_full() -> String {
@@ -22,10 +26,14 @@ fn default_one() -> Option<u32> {
     Some(1)
 }
 
+fn default_true() -> bool {
+    true
+}
+
 /// View a source file with flexible detail levels and automatic type inclusion
 #[mcp_tool(
     name = "view_code",
-    description = "View file in compact schema (BREAKING). Output keys: `p` (relative path), `h` (header for f/s/c rows), `f` (functions rows), `s` (structs rows), `c` (classes rows), optional deps `deps` (map dep_path -> type rows), plus optional tables: imports `ih`+`im`, trait methods `th`+`tm`, interfaces `ah`+`i`, properties `ph`+`pr`, class implements `ch`+`ci`, class methods `mh`+`cm`, Rust impl methods `bh`+`bm`. Rows are newline-delimited; fields are pipe-delimited and escaped: `\\` -> `\\\\`, `\n` -> `\\n`, `\r` -> `\\r`, `|` -> `\\|`. Meta: `@.t=true` when truncated. DETAIL: 'signatures' (name/line/sig), 'full' (adds doc/code). COMMENTS: `comment_mode=\"leading\"` prepends the contiguous leading comment block to returned code fields. FOCUS: set focus_symbol to keep code only for that symbol. LSP: pass definition_location from textDocument/definition to include the exact dependency type."
+    description = "View file in compact schema (BREAKING). Output keys: `p` (relative path), `h` (header for f/co/s/c rows), `f` (functions rows), `co` (React component rows in .jsx/.tsx files), `s` (structs rows), `c` (classes rows), optional deps `deps` (map dep_path -> type rows), plus optional tables: imports `ih`+`im`, trait methods `th`+`tm`, interfaces `ah`+`i`, properties `ph`+`pr`, class implements `ch`+`ci`, class methods `mh`+`cm`, Rust impl methods `bh`+`bm` (set group_by_trait=true to group `bm` rows under `--- trait: Name ---` separator rows). Rows are newline-delimited; fields are pipe-delimited and escaped: `\\` -> `\\\\`, `\n` -> `\\n`, `\r` -> `\\r`, `|` -> `\\|`. Meta: `@.t=true` when truncated. DETAIL: 'signatures' (name/line/sig), 'full' (adds doc/code). COMMENTS: `comment_mode=\"leading\"` prepends the contiguous leading comment block to returned code fields. FOCUS: set focus_symbol to keep code only for that symbol. VISIBILITY: `min_visibility=\"pub\"` keeps only the public API (`\"pub_crate\"` also keeps crate/package/internal/protected symbols); Python and JS/TS names starting with `_` count as private. DIFF: set compare_to to a git revision to get `f` rows with a trailing `chg` column (header `fh`): `+` added, `-` removed, `~` signature changed, `!` body changed, empty when unchanged. LSP: pass definition_location from textDocument/definition to include the exact dependency type. RANGE: set start_line/end_line to keep only symbols overlapping those lines; deps follow the kept symbols."
 )]
 #[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
 pub struct ViewCode {
@@ -52,12 +60,40 @@ pub struct ViewCode {
     /// - "leading": prepend the contiguous leading comment block above returned symbols
     #[serde(default)]
     pub comment_mode: Option<String>,
+
+    /// Group Rust impl method rows (`bm`) by trait, with a `--- trait: Name ---`
+    /// separator row before each group (default: false)
+    #[serde(default)]
+    pub group_by_trait: Option<bool>,
+
+    /// Least visible symbols to keep: "private" (default, everything),
+    /// "pub_crate" (drop private symbols) or "pub" (public API only)
+    #[serde(default)]
+    pub min_visibility: Option<String>,
+
+    /// Optional git revision (e.g. "HEAD~1") to mark changed functions against
+    #[serde(default)]
+    pub compare_to: Option<String>,
+
+    /// Tokenizer the output budget is counted with: "cl100k", "p50k", "r50k"
+    /// or "o200k" (default: the server's `tokenizer` setting, else cl100k)
+    #[serde(default)]
+    pub tokenizer: Option<String>,
+
+    /// First line (1-based) of the range to view; symbols overlapping
+    /// `start_line..=end_line` are kept (default: start of file)
+    #[serde(default)]
+    pub start_line: Option<u32>,
+
+    /// Last line (inclusive) of the range to view (default: end of file)
+    #[serde(default)]
+    pub end_line: Option<u32>,
 }
 
 /// Generate a high-level code map of a directory with token budget awareness and detail levels
 #[mcp_tool(
     name = "code_map",
-    description = "Generate hierarchical map of a DIRECTORY (not single file). Returns structure overview of multiple files with functions/classes/types. Detail levels: 'minimal' (names only), 'signatures' (DEFAULT, names + signatures), 'full' (includes code). USE WHEN: ✅ First time exploring unfamiliar codebase ✅ Finding where functionality lives across files ✅ Getting project structure overview ✅ Don't know which file to examine. DON'T USE: ❌ Know specific file → use view_code ❌ Need implementation details → use view_code after identifying files. TOKEN COST: MEDIUM (scales with project size). OPTIMIZATION: Start with detail='minimal' for large projects, use pattern to filter. WORKFLOW: code_map → view_code. COMBINED MODE: Set with_types=true to also extract type definitions (structs, enums, interfaces, etc.) in the same pass - more efficient than calling type_map separately."
+    description = "Generate hierarchical map of a DIRECTORY (not single file). Returns structure overview of multiple files with functions/classes/types. Detail levels: 'minimal' (names only), 'signatures' (DEFAULT, names + signatures), 'full' (includes code). USE WHEN: ✅ First time exploring unfamiliar codebase ✅ Finding where functionality lives across files ✅ Getting project structure overview ✅ Don't know which file to examine. DON'T USE: ❌ Know specific file → use view_code ❌ Need implementation details → use view_code after identifying files. TOKEN COST: MEDIUM (scales with project size). OPTIMIZATION: Start with detail='minimal' for large projects, use pattern to filter. WORKFLOW: code_map → view_code. COMBINED MODE: Set with_types=true to also extract type definitions (structs, enums, interfaces, etc.) in the same pass - more efficient than calling type_map separately. WORKSPACES: set workspace_mode=true on a Cargo workspace root to key the map by member name (`member -> file -> symbols`), with the token budget split across members by lines of code."
 )]
 #[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
 pub struct CodeMap {
@@ -79,24 +115,35 @@ pub struct CodeMap {
     /// When with_types=true, also count usages for each type (default: false for performance).
     #[serde(default)]
     pub count_usages: Option<bool>,
+    /// Group the map by Cargo workspace member (`member -> file -> symbols`),
+    /// splitting max_tokens across members by lines of code (default: false)
+    #[serde(default)]
+    pub workspace_mode: Option<bool>,
 }
 
 /// Find all usages of a symbol with context and usage type classification
 #[mcp_tool(
     name = "find_usages",
-    description = "Find ALL usages of a symbol (function, variable, class, type) across files. Syntax-aware search, not text search. Returns file locations, code context, usage type (definition, call, type_reference, import, reference). USE WHEN: ✅ Refactoring: see all places that call a function ✅ Impact analysis: checking what breaks if you change signature ✅ Tracing data flow ✅ Before renaming/modifying shared code. DON'T USE: ❌ Need structural changes only → use parse_diff ❌ Want risk assessment → use affected_by_diff ❌ Symbol used >50 places → use affected_by_diff or set max_context_lines=50. TOKEN COST: MEDIUM-HIGH (scales with usage count × context_lines). OPTIMIZATION: Set max_context_lines=50 for frequent symbols, context_lines=1 for locations only. WORKFLOW: find_usages (before changes) → make changes → affected_by_diff (verify)"
+    description = "Find ALL usages of a symbol (function, variable, class, type) across files. Syntax-aware search, not text search. Returns file locations, code context, usage type (definition, call, type_reference, import, reference; css_definition/html_usage for CSS class names in .css/.scss and HTML class attributes; .scss is read as plain CSS on a best-effort basis, so its css_definition rows have medium confidence). USE WHEN: ✅ Refactoring: see all places that call a function ✅ Impact analysis: checking what breaks if you change signature ✅ Tracing data flow ✅ Before renaming/modifying shared code. DON'T USE: ❌ Need structural changes only → use parse_diff ❌ Want risk assessment → use affected_by_diff ❌ Symbol used >50 places → use affected_by_diff or set max_context_lines=50. TOKEN COST: MEDIUM-HIGH (scales with usage count × context_lines). OPTIMIZATION: Set max_context_lines=50 for frequent symbols, context_lines=1 for locations only. Use pattern (regex, e.g. \"get_.*\") instead of symbol to search a family of names. WORKFLOW: find_usages (before changes) → make changes → affected_by_diff (verify)"
 )]
 #[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
 pub struct FindUsages {
-    /// Symbol name to search for
-    pub symbol: String,
+    /// Symbol name to search for. Required unless `pattern` is given.
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Regex matched against identifier names instead of `symbol`. Matches
+    /// the whole name unless it starts with `^` or ends with `$`; `(?i)`
+    /// makes it case-insensitive. Rows gain a trailing `sym` column.
+    #[serde(default)]
+    pub pattern: Option<String>,
     /// File or directory path to search in
     pub path: String,
     /// Number of context lines around each usage (default: 3)
     #[serde(default)]
     pub context_lines: Option<u32>,
-    /// Maximum total context lines across ALL usages (prevents token explosion)
-    /// When set, limits the total number of context lines returned
+    /// Maximum total context lines across ALL usages (prevents token explosion).
+    /// Every usage is still listed; definitions, then calls, get context first
+    /// and the rest keep only their location. 0 returns locations only.
     #[serde(default)]
     pub max_context_lines: Option<u32>,
     /// Maximum tokens for output (tiktoken counted). When set, output is
@@ -259,7 +306,7 @@ pub struct CallGraph {
 /// Get symbol information at a specific line with signature and scope chain
 #[mcp_tool(
     name = "symbol_at_line",
-    description = "Get symbol (function/class/method) at specific line with signature and scope chain. Returns symbol name, signature, kind, and enclosing scopes from innermost to outermost. USE WHEN: ✅ Have line number from error/stack trace ✅ Need to know 'what function is this line in?' ✅ Want function signature at a location ✅ Understanding scope hierarchy. DON'T USE: ❌ Need full code → use view_code with focus_symbol ❌ Know symbol name already → use view_code directly. TOKEN COST: LOW. WORKFLOW: symbol_at_line (find symbol) → view_code (see code)"
+    description = "Get symbol (function/class/method) at specific line with signature and scope chain. Returns symbol name, signature, kind, and enclosing scopes from innermost to outermost, plus the tree-sitter kind of the node at the position (`node`) and its language-agnostic category (`nk`: function_definition, function_call, type_definition, import, comment, string_literal, binary_expression, identifier, ...). USE WHEN: ✅ Have line number from error/stack trace ✅ Need to know 'what function is this line in?' ✅ Want function signature at a location ✅ Understanding scope hierarchy ✅ Need the whole function/class around a line → scope='enclosing_function' or 'enclosing_class' (add max_lines to cap). DON'T USE: ❌ Need full code → use view_code with focus_symbol ❌ Know symbol name already → use view_code directly. TOKEN COST: LOW. WORKFLOW: symbol_at_line (find symbol) → view_code (see code)"
 )]
 #[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
 pub struct SymbolAtLine {
@@ -272,12 +319,21 @@ pub struct SymbolAtLine {
     /// Column number (1-indexed, default: 1)
     #[serde(default = "default_one")]
     pub column: Option<u32>,
+
+    /// Which scope to report: "leaf" (default, innermost symbol), "enclosing_function" or
+    /// "enclosing_class". Enclosing scopes also return their full code and line range.
+    #[serde(default)]
+    pub scope: Option<String>,
+
+    /// Maximum lines of code returned for enclosing scopes (default: unlimited)
+    #[serde(default)]
+    pub max_lines: Option<u32>,
 }
 
 /// Analyze structural changes in a file compared to a git revision
 #[mcp_tool(
     name = "parse_diff",
-    description = "Analyze structural changes vs git revision. Returns symbol-level diff (functions/classes added/removed/modified), not line-level. USE WHEN: ✅ Verifying what you changed at structural level ✅ Checking if changes are cosmetic (formatting) or substantive ✅ Understanding changes without re-reading entire file ✅ Generating change summaries. DON'T USE: ❌ Need to see what might break → use affected_by_diff ❌ Haven't made changes yet → use view_code ❌ Need line-by-line diff → use git diff. TOKEN COST: LOW-MEDIUM (much smaller than re-reading file). WORKFLOW: After changes: parse_diff (verify) → affected_by_diff (check impact)"
+    description = "Analyze structural changes vs git revision. Returns symbol-level diff (functions/classes added/removed/modified), not line-level. USE WHEN: ✅ Verifying what you changed at structural level ✅ Checking if changes are cosmetic (formatting) or substantive ✅ Understanding changes without re-reading entire file ✅ Generating change summaries. Struct fields added or removed get their own `Struct.field` rows. Set compare_file to diff two files directly without git. DON'T USE: ❌ Need to see what might break → use affected_by_diff ❌ Haven't made changes yet → use view_code ❌ Need line-by-line diff → use git diff. TOKEN COST: LOW-MEDIUM (much smaller than re-reading file). WORKFLOW: After changes: parse_diff (verify) → affected_by_diff (check impact)"
 )]
 #[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
 pub struct ParseDiff {
@@ -287,12 +343,30 @@ pub struct ParseDiff {
     /// Examples: "HEAD", "HEAD~1", "main", "abc123"
     #[serde(default)]
     pub compare_to: Option<String>,
+    /// Another file to compare against instead of a git revision (no git
+    /// needed). Treated as the old version; must be in the same language.
+    #[serde(default)]
+    pub compare_file: Option<String>,
+}
+
+/// Analyze structural changes in several files at once
+#[mcp_tool(
+    name = "batch_diff",
+    description = "Run parse_diff on many files against one git revision in a single call, in parallel. Output keys: `cmp`, `h`, `changes` (rows: file|type|name|line|change), `summary` (added/removed/modified totals and `has_breaking_changes`: any signature change, removed symbol or removed public field), plus `eh`/`errors` (rows: file|error) for files that could not be diffed; the other files are still reported. USE WHEN: ✅ Reviewing a commit or branch that touched several files ✅ Quick check whether a change set breaks any API. DON'T USE: ❌ Single file → use parse_diff ❌ Need call sites that break → use affected_by_diff. TOKEN COST: LOW-MEDIUM (one row per changed symbol)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct BatchDiff {
+    /// Source files to analyze
+    pub file_paths: Vec<String>,
+    /// Git revision to compare against (default: "HEAD")
+    #[serde(default)]
+    pub compare_to: Option<String>,
 }
 
 /// Find usages that might be affected by changes in a file
 #[mcp_tool(
     name = "affected_by_diff",
-    description = "Find usages AFFECTED by your changes. Combines parse_diff + find_usages to show blast radius with risk levels (HIGH/MEDIUM/LOW) based on change type. USE WHEN: ✅ After modifying function signatures - what might break? ✅ Before running tests - anticipate failures ✅ During refactoring - understand impact radius ✅ Risk assessment for code changes. DON'T USE: ❌ Haven't made changes yet → use find_usages first ❌ Just want to see what changed → use parse_diff ❌ Changes are purely internal (no signature changes) → parse_diff is enough. TOKEN COST: MEDIUM-HIGH (combines parse_diff + find_usages). OPTIMIZATION: Use scope parameter to limit search area. WORKFLOW: parse_diff (see changes) → affected_by_diff (assess impact) → fix issues"
+    description = "Find usages AFFECTED by your changes. Combines parse_diff + find_usages to show blast radius with risk levels (HIGH/MEDIUM/LOW) based on change type. USE WHEN: ✅ After modifying function signatures - what might break? ✅ Before running tests - anticipate failures ✅ During refactoring - understand impact radius ✅ Risk assessment for code changes. DON'T USE: ❌ Haven't made changes yet → use find_usages first ❌ Just want to see what changed → use parse_diff ❌ Changes are purely internal (no signature changes) → parse_diff is enough. Output keys: `p`, `h`, `affected` (rows: symbol|change|file|line|risk), plus `mh`/`mismatch` (rows: file|line|hint) when a call passes an argument that no longer matches a changed parameter type. TOKEN COST: MEDIUM-HIGH (combines parse_diff + find_usages). OPTIMIZATION: Use scope parameter to limit search area. WORKFLOW: parse_diff (see changes) → affected_by_diff (assess impact) → fix issues"
 )]
 #[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
 pub struct AffectedByDiff {
@@ -309,7 +383,7 @@ pub struct AffectedByDiff {
 /// Preview downstream impact from a planned signature change
 #[mcp_tool(
     name = "preview_impact",
-    description = "Preview downstream blast radius for a planned signature change before editing the file. Input accepts `file_path`, `symbol_name`, and `new_signature`; optional `scope` limits the search area. Output keys: `p`, `sym`, `before`, `after`, `dh`, `d`, `h`, `affected`; detail rows are `kind|name|from|to` and affected rows reuse `symbol|change|file|line|risk`. USE WHEN: ✅ You want to estimate call-site fallout before changing a function signature ✅ You are comparing alternative signatures and want the least disruptive option."
+    description = "Preview downstream blast radius for a planned signature change before editing the file. Input accepts `file_path`, `symbol_name`, and `new_signature`; optional `scope` limits the search area. Output keys: `p`, `sym`, `before`, `after`, `dh`, `d`, `h`, `affected`; detail rows are `kind|name|from|to` and affected rows reuse `symbol|change|file|line|risk`. When a call site's argument no longer fits a changed parameter type, `mh`/`mismatch` rows (`file|line|hint`) spell out the conflict. USE WHEN: ✅ You want to estimate call-site fallout before changing a function signature ✅ You are comparing alternative signatures and want the least disruptive option."
 )]
 #[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
 pub struct PreviewImpact {
@@ -324,17 +398,24 @@ pub struct PreviewImpact {
     pub scope: Option<String>,
 }
 
-/// Execute a custom tree-sitter query pattern on a source file with code context
+/// Execute a custom tree-sitter query pattern on a source file or directory
 #[mcp_tool(
     name = "query_pattern",
-    description = "Execute custom tree-sitter S-expression query for advanced AST pattern matching. Returns matches with code context for complex structural patterns. USE WHEN: ✅ Finding all instances of specific syntax pattern (e.g., all if statements) ✅ Complex structural queries (e.g., all async functions with try-catch) ✅ Language-specific patterns find_usages can't handle ✅ You know tree-sitter query syntax. DON'T USE: ❌ Finding function/variable usages → use find_usages (simpler, cross-language) ❌ Don't know tree-sitter syntax → use find_usages or view_code ❌ Simple symbol search → use find_usages. TOKEN COST: MEDIUM (depends on matches). COMPLEXITY: HIGH - requires tree-sitter query knowledge. RECOMMENDATION: Prefer find_usages for 90% of use cases."
+    description = "Execute custom tree-sitter S-expression query for advanced AST pattern matching. Runs on one file, or on every file of `language` in a directory. Rows are `file|line|col|text` for the first capture of each match (or every node of `capture`), capped at 1000. USE WHEN: ✅ Finding all instances of specific syntax pattern (e.g., all if statements) ✅ Complex structural queries (e.g., all async functions with try-catch) ✅ Language-specific patterns find_usages can't handle ✅ You know tree-sitter query syntax. DON'T USE: ❌ Finding function/variable usages → use find_usages (simpler, cross-language) ❌ Don't know tree-sitter syntax → use find_usages or view_code ❌ Simple symbol search → use find_usages. TOKEN COST: MEDIUM (depends on matches). COMPLEXITY: HIGH - requires tree-sitter query knowledge. RECOMMENDATION: Prefer find_usages for 90% of use cases."
 )]
 #[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
 pub struct QueryPattern {
-    /// Path to the source file
-    pub file_path: String,
+    /// Path to a source file or directory
+    #[serde(alias = "file_path")]
+    pub path: String,
     /// Tree-sitter query pattern in S-expression format
     pub query: String,
+    /// Language the query is written for (required for directories; e.g. "rust", "python")
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Capture name to report (without '@'; default: first capture of each match)
+    #[serde(default)]
+    pub capture: Option<String>,
     /// Number of context lines around each match (default: 2)
     #[serde(default)]
     pub context_lines: Option<u32>,
@@ -397,7 +478,13 @@ impl ViewCode {
             "file_path": self.file_path,
             "detail": self.detail,
             "focus_symbol": self.focus_symbol,
-            "definition_location": self.definition_location
+            "definition_location": self.definition_location,
+            "group_by_trait": self.group_by_trait.unwrap_or(false),
+            "min_visibility": self.min_visibility,
+            "compare_to": self.compare_to,
+            "tokenizer": self.tokenizer,
+            "start_line": self.start_line,
+            "end_line": self.end_line
         });
 
         view_code::execute(&args).map_err(CallToolError::new)
@@ -412,7 +499,8 @@ impl CodeMap {
             "detail": self.detail,
             "pattern": self.pattern,
             "with_types": self.with_types.unwrap_or(false),
-            "count_usages": self.count_usages.unwrap_or(false)
+            "count_usages": self.count_usages.unwrap_or(false),
+            "workspace_mode": self.workspace_mode.unwrap_or(false)
         });
 
         code_map::execute(&args).map_err(CallToolError::new)
@@ -423,6 +511,7 @@ impl FindUsages {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
             "symbol": self.symbol,
+            "pattern": self.pattern,
             "path": self.path,
             "context_lines": self.context_lines,
             "max_context_lines": self.max_context_lines,
@@ -433,222 +522,1025 @@ impl FindUsages {
     }
 }
 
-impl FormatReferences {
+/// Find the Rust types implementing a trait
+#[mcp_tool(
+    name = "find_trait_implementations",
+    description = "Find every Rust `impl Trait for Type` block of a trait in a file or directory. The trait is matched by its last path segment (`Display` matches `fmt::Display`). Output keys: `trait`, `h` (header), `impls` (rows: impl_type|trait|file|line|bounds|kind). `bounds` lists generic bounds and where predicates; `kind` is `blanket` (impl on a type parameter, e.g. `impl<T: Display> ToString for T`), `conditional` (other bounded impls) or `plain`. USE WHEN: ✅ Refactoring a trait: see every implementor ✅ Checking which types get a blanket impl. DON'T USE: ❌ Need call sites of trait methods → use find_usages. TOKEN COST: LOW (one row per impl)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct FindTraitImplementations {
+    /// Trait name, optionally path-qualified (e.g. "Display" or "std::fmt::Display")
+    pub trait_name: String,
+    /// Rust file or directory to search in
+    pub path: String,
+}
+
+impl FindTraitImplementations {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
-            "symbol": self.symbol,
-            "references": self.references,
-            "context_lines": self.context_lines,
-            "max_tokens": self.max_tokens
+            "trait_name": self.trait_name,
+            "path": self.path
         });
 
-        format_references::execute(&args).map_err(CallToolError::new)
+        find_usages::execute_find_trait_implementations(&args).map_err(CallToolError::new)
     }
 }
 
-impl FormatDiagnostics {
+/// List Rust macro definitions and call sites
+#[mcp_tool(
+    name = "find_macros",
+    description = "List Rust `macro_rules!` definitions and macro call sites in a file or directory. Output keys: `dh`/`defs` (rows: name|file|line|arms, arms = number of macro_rules patterns) and `ch`/`calls` (rows: name|file|line|args, args without delimiters, cut at 120 chars). `#[derive(...)]` attributes are listed as `derive` calls. USE WHEN: ✅ Reviewing code that defines items through macros ✅ Finding where a macro is used before changing its arms. LIMITATION: invocations nested inside another macro's arguments are not listed. TOKEN COST: LOW-MEDIUM (one row per call)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct FindMacros {
+    /// Rust file or directory to search in
+    pub path: String,
+}
+
+impl FindMacros {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
-            "diagnostics": self.diagnostics,
-            "max_tokens": self.max_tokens
+            "path": self.path
         });
 
-        format_diagnostics::execute(&args).map_err(CallToolError::new)
+        macros::execute(&args).map_err(CallToolError::new)
     }
 }
 
-impl MinimalEditContext {
+/// Rename a symbol across a file or directory
+#[mcp_tool(
+    name = "rename_symbol",
+    description = "Rename every identifier occurrence of a symbol in a file or directory, using the same tree-sitter search as find_usages so strings and comments are untouched. Defaults to a dry run; set `dry_run: false` to write the files. Output: `files` (files changed), `renamed` (occurrences), `skipped` (newline-separated `path: reason`, e.g. files with syntax errors) and, with `return_diff: true`, `diff` (unified diff without context lines). USE WHEN: ✅ Renaming a function, type or variable across a project ✅ Previewing the blast radius of a rename. LIMITATION: name-based, not scope-aware; unrelated symbols with the same name are renamed too. TOKEN COST: LOW (HIGH with diff)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct RenameSymbol {
+    /// Current name of the symbol
+    pub old_name: String,
+    /// New name for the symbol
+    pub new_name: String,
+    /// File or directory to rename in
+    pub path: String,
+    /// Only report what would change (default: true)
+    #[serde(default = "default_true")]
+    pub dry_run: bool,
+    /// Include a unified diff of the changes (default: false)
+    #[serde(default)]
+    pub return_diff: bool,
+}
+
+impl RenameSymbol {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
-            "file_path": self.file_path,
-            "symbol_name": self.symbol_name,
-            "max_tokens": self.max_tokens
+            "old_name": self.old_name,
+            "new_name": self.new_name,
+            "path": self.path,
+            "dry_run": self.dry_run,
+            "return_diff": self.return_diff
         });
 
-        minimal_edit_context::execute(&args).map_err(CallToolError::new)
+        refactor::execute_rename_symbol(&args).map_err(CallToolError::new)
     }
 }
 
-impl CallGraph {
+/// List TODO/FIXME-style markers in comments
+#[mcp_tool(
+    name = "find_comments",
+    description = "List technical-debt markers (TODO, FIXME, HACK, XXX, OPTIMIZE, BUG) found in comments of a file or directory, sorted by file and line. Output: `h` header and `markers` rows: kind|file|line|author|text. `author` is filled from `TODO(alice): ...` or a trailing ` (alice)`. Markers in strings and identifiers are ignored. USE WHEN: ✅ Summarizing known debt before a review ✅ Checking whether a change resolves an open TODO. TOKEN COST: LOW (one row per marker)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct FindComments {
+    /// File or directory to search in
+    pub path: String,
+}
+
+impl FindComments {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
-            "file_path": self.file_path,
-            "symbol_name": self.symbol_name,
-            "direction": self.direction,
-            "depth": self.depth,
-            "max_tokens": self.max_tokens
+            "path": self.path
         });
 
-        call_graph::execute(&args).map_err(CallToolError::new)
+        comments::execute_find_comments(&args).map_err(CallToolError::new)
     }
 }
 
-impl SymbolAtLine {
+/// Cross-reference CSS custom properties
+#[mcp_tool(
+    name = "css_var_usages",
+    description = "Cross-reference CSS custom properties in the .css and .html files of a file or directory: every `--name: value` definition and every `var(--name)` usage. Output: `dh` header and `defs` rows: name|value|file|line|theme (`theme` is `y` inside a Tailwind `@theme` block), `uh` header and `uses` rows: name|file|line, plus newline-separated `undefined` (used, never defined) and `dead` (defined, never used) names. USE WHEN: ✅ Renaming or removing a design token ✅ Finding typos in `var(--...)` references ✅ Cleaning up unused theme variables. TOKEN COST: LOW (one row per definition and usage)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct CssVarUsages {
+    /// File or directory to search in
+    pub path: String,
+}
+
+impl CssVarUsages {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
-            "file_path": self.file_path,
-            "line": self.line,
-            "column": self.column
+            "path": self.path
         });
 
-        symbol_at_line::execute(&args).map_err(CallToolError::new)
+        css_analysis::execute_css_var_usages(&args).map_err(CallToolError::new)
     }
 }
 
-impl ParseDiff {
+/// List HTTP routes of Flask, FastAPI and Express services
+#[mcp_tool(
+    name = "find_routes",
+    description = "List HTTP routes of Python (Flask `@app.route`/Blueprint routes, `@router.get`-style FastAPI decorators) and JavaScript/TypeScript Express services (`app.get('/path', ...middleware, handler)`, `router.route('/path').get(h).post(h)`) in a file or directory. Output: `h` header and `routes` rows: method|path|handler|file|line. Flask routes without `methods=` are GET; a route with several methods yields one row each. The Express handler is the last argument (`<anonymous>` for unnamed inline functions). USE WHEN: ✅ Getting the API surface of a mixed Python/JS service ✅ Finding the handler behind an endpoint. Use list_api_routes for FastAPI handler parameters. TOKEN COST: LOW (one row per route)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct FindRoutes {
+    /// File or directory to scan
+    pub path: String,
+}
+
+impl FindRoutes {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
-            "file_path": self.file_path,
-            "compare_to": self.compare_to
+            "path": self.path
         });
 
-        diff::execute_parse_diff(&args).map_err(CallToolError::new)
+        routes::execute_find_routes(&args).map_err(CallToolError::new)
     }
 }
 
-impl AffectedByDiff {
+/// List Rust panic sites for safety review
+#[mcp_tool(
+    name = "find_panics",
+    description = "List every explicit panic site in Rust code: `.unwrap()`, `.expect(..)`, and `panic!`/`todo!`/`unimplemented!`/`unreachable!` invocations, in a file or directory. Output: `h` header and `panics` rows: kind|file|line|expression, where expression is the receiver of unwrap/expect or the macro arguments (cut at 120 chars). Set `whitelist_pattern` to a glob (relative to `path`, e.g. `**/tests/**`) to skip files. USE WHEN: ✅ Auditing error handling before a release ✅ Reviewing a crate for production readiness. LIMITATION: calls inside other macros' arguments are not listed. TOKEN COST: LOW-MEDIUM (one row per site)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct FindPanics {
+    /// Rust file or directory to search in
+    pub path: String,
+    /// Glob of files to skip, relative to `path` (e.g. "**/tests/**")
+    #[serde(default)]
+    pub whitelist_pattern: Option<String>,
+}
+
+impl FindPanics {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
-            "file_path": self.file_path,
-            "compare_to": self.compare_to,
-            "scope": self.scope
+            "path": self.path,
+            "whitelist_pattern": self.whitelist_pattern
         });
 
-        diff::execute_affected_by_diff(&args).map_err(CallToolError::new)
+        safety::execute_find_panics(&args).map_err(CallToolError::new)
     }
 }
 
-impl PreviewImpact {
+/// Return the exact source of one named symbol
+#[mcp_tool(
+    name = "get_symbol",
+    description = "Get the exact source text of a named function, struct or class in one file, without view_code's dependency resolution. Output: `symbols` array of {name, kind, file, start_line, end_line, code}; every definition with the name is returned (overloads, same-named methods), and an unknown name gives an empty array. Set `kind` to \"function\", \"struct\" or \"class\" to disambiguate. Doc comments, attributes and decorators are not included in `code`. USE WHEN: ✅ Need the full body of one known symbol ✅ Copying a definition before editing it. DON'T USE: ❌ Need referenced types too → use view_code with focus_symbol. TOKEN COST: LOW (only the symbol's text)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct GetSymbol {
+    /// Path to the source file
+    pub file_path: String,
+    /// Name of the function, struct or class
+    pub symbol_name: String,
+    /// Optional: "function", "struct" or "class"
+    #[serde(default)]
+    pub kind: Option<String>,
+}
+
+impl GetSymbol {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
             "file_path": self.file_path,
             "symbol_name": self.symbol_name,
-            "new_signature": self.new_signature,
-            "scope": self.scope
+            "kind": self.kind
         });
 
-        diff::execute_preview_impact(&args).map_err(CallToolError::new)
+        get_symbol::execute_get_symbol(&args).map_err(CallToolError::new)
     }
 }
 
-impl QueryPattern {
+/// List the collapsible regions of a file
+#[mcp_tool(
+    name = "folding_ranges",
+    description = "List code folding ranges of one file: functions, classes and type definitions (Rust structs, enums, traits, impl blocks), comment blocks, import groups (imports with no blank line between them) and Python multiline strings. Output: `h` header and `folds` rows: start_line|end_line|kind, kind one of function, class, comment, import_group; lines are 1-based and inclusive, sorted by start_line with enclosing ranges first. Single-line regions are omitted. USE WHEN: ✅ Feeding an editor or LSP client ✅ Finding the line span of every block in a file. TOKEN COST: LOW (one short row per region)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct FoldingRanges {
+    /// Path to the source file
+    pub file_path: String,
+}
+
+impl FoldingRanges {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
-            "file_path": self.file_path,
-            "query": self.query,
-            "context_lines": self.context_lines
+            "file_path": self.file_path
         });
 
-        query_pattern::execute(&args).map_err(CallToolError::new)
+        lsp::execute_folding_ranges(&args).map_err(CallToolError::new)
     }
 }
 
-impl RelevantTests {
+/// Summarize a Svelte single-file component
+#[mcp_tool(
+    name = "svelte_shape",
+    description = "Summarize a Svelte `.svelte` component: props (`export let` declarations or fields destructured from `$props()`), reactive variables assigned by `$:` statements, `on:` event directives in the template, slots, script imports and class selectors of the `<style>` block. `<script lang=\"ts\">` is parsed as TypeScript. Output: `file`, props table `ph`+`props` (name|type|default|line), newline-separated `reactive`, `events` (as written, e.g. `on:click={increment}`), `slots` (unnamed slot = `default`) and `classes`, imports `ih`+`im` (line|text). Lines are lines of the .svelte file. USE WHEN: ✅ Learning a component's API before using or editing it ✅ Checking which events and slots a component wires up. TOKEN COST: LOW."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct SvelteShape {
+    /// Path to the .svelte file
+    pub file_path: String,
+}
+
+impl SvelteShape {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
-            "file_path": self.file_path,
-            "symbol_name": self.symbol_name
+            "file_path": self.file_path
         });
 
-        relevant_tests::execute(&args).map_err(CallToolError::new)
+        svelte::execute_svelte_shape(&args).map_err(CallToolError::new)
     }
 }
 
-impl VerifyEdit {
+/// List HTML forms and their controls
+#[mcp_tool(
+    name = "html_forms",
+    description = "List the `<form>` elements of an HTML file with their `input`, `select` and `textarea` controls. Output: `file`, `fh` header and `forms` rows: form|action|method|id|line, `ih` header and `inputs` rows: form|name|type|required|placeholder|line, where `form` is the 1-based form index. `type` is the DOM type (`text` when missing, `select-one`/`select-multiple`, `textarea`); `required` is `y` when set. Controls of a form nested in another form belong to the outer form. USE WHEN: ✅ Building a handler for a submitted form ✅ Reviewing field names, types and validation. TOKEN COST: LOW (one row per form and control)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct HtmlForms {
+    /// Path to the HTML file
+    pub file_path: String,
+}
+
+impl HtmlForms {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
-            "file_path": self.file_path,
-            "compare_to": self.compare_to,
-            "target_symbol": self.target_symbol
+            "file_path": self.file_path
         });
 
-        verify_edit::execute(&args).map_err(CallToolError::new)
+        html_analysis::execute_html_forms(&args).map_err(CallToolError::new)
     }
 }
 
-impl ReviewContext {
+/// Show the module hierarchy of a Rust crate
+#[mcp_tool(
+    name = "module_tree",
+    description = "Reconstruct the module tree of a Rust crate by following `mod` declarations from `src/lib.rs` or `src/main.rs` (or a given root file). `mod foo;` resolves to `foo.rs` or `foo/mod.rs`; inline `mod foo { ... }` modules appear without a `path`. Output: nested JSON nodes with `name`, `path` (relative), `is_public` (plain `pub` only) and `children`. Depth is capped at 20. LARGE CRATES: set max_depth to list modules at that depth (crate root = 0) as `truncated: true` with a `child_count` instead of their children, then pass `module` (e.g. `api::handlers`) to fetch one module with its direct children. USE WHEN: ✅ Getting oriented in an unfamiliar or large crate ✅ Finding which file implements a module path. TOKEN COST: LOW (one node per module)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct ModuleTree {
+    /// Crate directory or crate root file (e.g. `src/lib.rs`)
+    pub path: String,
+    /// Depth at which modules are listed without their children (crate root = 0)
+    #[serde(default)]
+    pub max_depth: Option<u64>,
+    /// Module path (e.g. `api::handlers`) to return with only its direct children
+    #[serde(default)]
+    pub module: Option<String>,
+}
+
+impl ModuleTree {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
-            "file_path": self.file_path,
-            "compare_to": self.compare_to,
-            "scope": self.scope,
-            "max_tokens": self.max_tokens
+            "path": self.path,
+            "max_depth": self.max_depth,
+            "module": self.module
         });
 
-        review_context::execute(&args).map_err(CallToolError::new)
+        module_tree::execute_module_tree(&args).map_err(CallToolError::new)
     }
 }
 
-/// Find Rust structs that provide context for an Askama template.
-///
-/// USE WHEN:
-/// ✅ Editing Askama HTML templates and need to know available variables
-/// ✅ Understanding what data is passed to a template
-/// ✅ Debugging template rendering issues
-///
-/// DON'T USE:
-/// ❌ Not using Askama templates
-/// ❌ Working with non-template files
-///
-/// RETURNS:
-/// - Struct names associated with the template
-/// - All fields with their types (resolved up to 3 levels deep)
-/// - Nested struct field expansions
-///
-/// TOKEN COST: LOW-MEDIUM
-/// WORKFLOW: template_context → edit template with known variables
+/// Build the import graph of a Python project
 #[mcp_tool(
-    name = "template_context",
-    description = "Find Askama template context in compact schema (BREAKING). Output keys: `tpl` (relative template path), `h` (header), `ctx` (rows: struct|field|type), `sh` (header), `s` (rows: struct|file|line). Rows are newline-delimited; fields are pipe-delimited and escaped: `\\` -> `\\\\`, `\n` -> `\\n`, `\r` -> `\\r`, `|` -> `\\|`."
+    name = "python_import_graph",
+    description = "Build the module-level import graph of the .py files in a file or directory and detect circular imports. Module names follow the `__init__.py` package layout; relative imports are resolved, and `from pkg import mod` records `pkg.mod` when that is a project module. Imports inside functions, classes and `if TYPE_CHECKING:` blocks and `from __future__` imports are ignored. Output: `graph` (module -> imported modules, including third-party ones) and `cycles` (`a -> b -> a`, one shortest loop per group of mutually importing project modules). USE WHEN: ✅ Moving code between Python modules ✅ Debugging an ImportError caused by a circular import. TOKEN COST: MEDIUM (one entry per module)."
 )]
 #[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
-pub struct TemplateContext {
-    /// Path to the template file (relative or absolute)
-    pub template_path: String,
+pub struct PythonImportGraph {
+    /// File or directory to analyze
+    pub path: String,
 }
 
-impl TemplateContext {
+impl PythonImportGraph {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
-            "template_path": self.template_path
+            "path": self.path
         });
 
-        crate::analysis::askama::execute(&args).map_err(CallToolError::new)
+        import_graph::execute_python_import_graph(&args).map_err(CallToolError::new)
     }
 }
 
-/// Generate a usage-sorted map of all project types. Returns structs, classes, enums, interfaces, traits, protocols, and type aliases prioritized by usage frequency.
+/// List the public API surface of Rust and TypeScript code
 #[mcp_tool(
-    name = "type_map",
-    description = "Generate a usage-sorted map of project types in compact schema (BREAKING). Output keys: `h` (header) and `types` (rows: name|kind|file|line|usage_count). Optional meta under `@` (e.g. `@.t=true` when truncated). Rows are newline-delimited; fields are pipe-delimited and escaped: `\\` -> `\\\\`, `\n` -> `\\n`, `\r` -> `\\r`, `|` -> `\\|`. PERFORMANCE: Set count_usages=false to skip usage counting for faster results when you only need type locations."
+    name = "public_api",
+    description = "List the public API surface of a file or directory in one flat table. Rust: `pub` functions and inherent methods (`Type::method`), structs with their `pub` fields, enums with their variants, traits, consts, statics and type aliases, including inline modules; `#[cfg(test)]` items and test module files are skipped, and `pub(crate)`/`pub(super)` items are only included with include_crate_pub=true. TypeScript: exported functions (also `export const f = () => ...`), classes, interfaces and type aliases. Files under tests/benches/examples are ignored. Output: `h` header and `api` rows: kind|name|signature|file|line, sorted by file and line. USE WHEN: ✅ Reviewing a library change for breaking API changes ✅ Documenting what a crate or package exports. TOKEN COST: MEDIUM (one row per public item)."
 )]
 #[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
-pub struct TypeMap {
-    /// Directory path to scan for types
+pub struct PublicApi {
+    /// File or directory to analyze
     pub path: String,
-    /// Maximum tokens in output (counted via tiktoken, default: 2000)
-    #[serde(default)]
-    pub max_tokens: Option<u32>,
-    /// Optional glob pattern to filter files (e.g., '*.rs', 'src/**/*.ts')
+    /// Also list Rust `pub(crate)`, `pub(super)` and `pub(in ...)` items (default: false)
     #[serde(default)]
-    pub pattern: Option<String>,
-    /// Whether to count usages across the project (default: true).
-    /// Set to false for faster results when you only need type locations.
+    pub include_crate_pub: Option<bool>,
+}
+
+impl PublicApi {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path,
+            "include_crate_pub": self.include_crate_pub.unwrap_or(false)
+        });
+
+        public_api::execute_public_api(&args).map_err(CallToolError::new)
+    }
+}
+
+/// List test functions by kind and framework
+#[mcp_tool(
+    name = "find_tests",
+    description = "List the test functions of a file or directory. Rust `#[test]`/`#[tokio::test]`/`#[bench]` and Criterion benchmarks, Python pytest functions and `unittest.TestCase` methods, JavaScript/TypeScript `it`/`test` calls (named `describe > it`), Java `@Test` methods and Go `TestXxx`/`BenchmarkXxx`. Output: `h` header and `tests` rows: name|kind|framework|file|line, sorted by file and line. `kind` is `benchmark`, `integration` (path contains `integration` or an `it/` directory) or `unit`. USE WHEN: ✅ Finding the tests of a module before changing it ✅ Deciding which test suite to run. DON'T USE: ❌ Need tests ranked for one symbol → use relevant_tests. TOKEN COST: LOW (one row per test)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct FindTests {
+    /// File or directory to search in
+    pub path: String,
+}
+
+impl FindTests {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path
+        });
+
+        tests_finder::execute_find_tests(&args).map_err(CallToolError::new)
+    }
+}
+
+/// Pack the code around focal symbols into a token budget
+#[mcp_tool(
+    name = "assemble_context",
+    description = "Assemble the code needed to work on a few symbols across a file or directory, within a token budget. Chunks are taken greedily in this order: definitions of the focal symbols (score 1), the functions calling them (score 0.7; a call outside any function is its own chunk), then definitions of types named in the focal signatures (score 0.5). A chunk that does not fit in the remaining budget or overlaps one already taken is skipped. Output: `h` header and `chunks` rows: file|start|end|score|tokens|code, plus `used` and `budget` token totals. USE WHEN: ✅ Gathering everything relevant to a change in one call ✅ Filling a fixed context window with the most relevant code first. DON'T USE: ❌ Editing one symbol in one file → use minimal_edit_context. TOKEN COST: bounded by token_budget."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct AssembleContext {
+    /// Function or method names to build the context around
+    pub focal_symbols: Vec<String>,
+    /// File or directory to search in
+    pub path: String,
+    /// Maximum tokens for all chunks together (default: 2000)
     #[serde(default)]
-    pub count_usages: Option<bool>,
+    pub token_budget: Option<u32>,
 }
 
-impl TypeMap {
+impl AssembleContext {
     pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
         let args = serde_json::json!({
+            "focal_symbols": self.focal_symbols,
             "path": self.path,
-            "max_tokens": self.max_tokens.unwrap_or(2000),
-            "pattern": self.pattern,
-            "count_usages": self.count_usages.unwrap_or(true)
+            "token_budget": self.token_budget
         });
 
-        crate::analysis::type_map::execute(&args)
-            .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))
+        context_assembler::execute_assemble_context(&args).map_err(CallToolError::new)
+    }
+}
+
+/// Summarize a Vue single-file component
+#[mcp_tool(
+    name = "vue_shape",
+    description = "Summarize a Vue `.vue` single-file component. Composition API (`<script setup>`): props from `defineProps` (runtime object/array, TypeScript type argument or named interface, defaults from `withDefaults`), events from `defineEmits`, keys of `defineExpose`, and top-level variables initialized by `ref`/`reactive`/`computed` or a `use*` composable. Options API (`export default {...}` or `defineComponent({...})` in a plain `<script>`): `props`, `emits`, `data()` keys, `methods` and `computed`. Also class selectors of `<style scoped>` blocks. `lang=\"ts\"` scripts are parsed as TypeScript. Output: `file`, props table `ph`+`props` (name|type|default|line; optional typed props end in `?`), newline-separated `emits`, `exposed`, `composables` (`name: function`), `data`, `methods`, `computed` and `classes`. Lines are lines of the .vue file. USE WHEN: ✅ Learning a component's API before using or editing it ✅ Checking what state and events a component defines. TOKEN COST: LOW."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct VueShape {
+    /// Path to the .vue file
+    pub file_path: String,
+}
+
+impl VueShape {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "file_path": self.file_path
+        });
+
+        vue::execute_vue_shape(&args).map_err(CallToolError::new)
+    }
+}
+
+impl FormatReferences {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "symbol": self.symbol,
+            "references": self.references,
+            "context_lines": self.context_lines,
+            "max_tokens": self.max_tokens
+        });
+
+        format_references::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+impl FormatDiagnostics {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "diagnostics": self.diagnostics,
+            "max_tokens": self.max_tokens
+        });
+
+        format_diagnostics::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+impl MinimalEditContext {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "file_path": self.file_path,
+            "symbol_name": self.symbol_name,
+            "max_tokens": self.max_tokens
+        });
+
+        minimal_edit_context::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+impl CallGraph {
+    pub fn call_tool(&self, cancel: &CancellationToken) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "file_path": self.file_path,
+            "symbol_name": self.symbol_name,
+            "direction": self.direction,
+            "depth": self.depth,
+            "max_tokens": self.max_tokens
+        });
+
+        call_graph::execute_with_cancel(&args, cancel).map_err(CallToolError::new)
+    }
+}
+
+impl SymbolAtLine {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "file_path": self.file_path,
+            "line": self.line,
+            "column": self.column,
+            "scope": self.scope,
+            "max_lines": self.max_lines
+        });
+
+        symbol_at_line::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+impl ParseDiff {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "file_path": self.file_path,
+            "compare_to": self.compare_to,
+            "compare_file": self.compare_file
+        });
+
+        diff::execute_parse_diff(&args).map_err(CallToolError::new)
+    }
+}
+
+impl BatchDiff {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "file_paths": self.file_paths,
+            "compare_to": self.compare_to
+        });
+
+        diff::execute_batch_diff(&args).map_err(CallToolError::new)
+    }
+}
+
+impl AffectedByDiff {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "file_path": self.file_path,
+            "compare_to": self.compare_to,
+            "scope": self.scope
+        });
+
+        diff::execute_affected_by_diff(&args).map_err(CallToolError::new)
+    }
+}
+
+impl PreviewImpact {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "file_path": self.file_path,
+            "symbol_name": self.symbol_name,
+            "new_signature": self.new_signature,
+            "scope": self.scope
+        });
+
+        diff::execute_preview_impact(&args).map_err(CallToolError::new)
+    }
+}
+
+impl QueryPattern {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path,
+            "query": self.query,
+            "language": self.language,
+            "capture": self.capture,
+            "context_lines": self.context_lines
+        });
+
+        query_pattern::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+impl RelevantTests {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "file_path": self.file_path,
+            "symbol_name": self.symbol_name
+        });
+
+        relevant_tests::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+impl VerifyEdit {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "file_path": self.file_path,
+            "compare_to": self.compare_to,
+            "target_symbol": self.target_symbol
+        });
+
+        verify_edit::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+impl ReviewContext {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "file_path": self.file_path,
+            "compare_to": self.compare_to,
+            "scope": self.scope,
+            "max_tokens": self.max_tokens
+        });
+
+        review_context::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+/// Find Rust structs that provide context for an Askama or Tera template.
+///
+/// The engine is detected from the project's Cargo.toml. Tera contexts are
+/// resolved from `tera.render("name.html", &context)` call sites.
+///
+/// USE WHEN:
+/// ✅ Editing Askama/Tera HTML templates and need to know available variables
+/// ✅ Understanding what data is passed to a template
+/// ✅ Debugging template rendering issues
+///
+/// DON'T USE:
+/// ❌ Not using Askama or Tera templates
+/// ❌ Working with non-template files
+///
+/// RETURNS:
+/// - Struct names associated with the template
+/// - All fields with their types (resolved up to 3 levels deep)
+/// - Nested struct field expansions
+/// - Variables the template (and its parents/includes) reads
+///
+/// TOKEN COST: LOW-MEDIUM
+/// WORKFLOW: template_context → edit template with known variables
+#[mcp_tool(
+    name = "template_context",
+    description = "Find Askama, Tera or Liquid template context in compact schema (BREAKING). Liquid is detected from `_layouts/` or `_includes/` in the site root (Jekyll, Shopify) and has no context structs; otherwise the engine is detected from Cargo.toml, and for Tera, context structs come from `render(\"name.html\", &context)` calls. Output keys: `tpl` (relative template path), `h` (header), `ctx` (rows: struct|field|type), `sh` (header), `s` (rows: struct|file|line), `vh` (header), `vars` (rows: name|path|kind|tpl — variables read by the template and the templates it extends/includes/imports, or for Liquid its front matter layout and includes; kind is scalar, iterator or conditional). Rows are newline-delimited; fields are pipe-delimited and escaped: `\\` -> `\\\\`, `\n` -> `\\n`, `\r` -> `\\r`, `|` -> `\\|`."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct TemplateContext {
+    /// Path to the template file (relative or absolute)
+    pub template_path: String,
+}
+
+impl TemplateContext {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "template_path": self.template_path
+        });
+
+        crate::analysis::askama::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+/// Generate a usage-sorted map of all project types. Returns structs, classes, enums, interfaces, traits, protocols, and type aliases prioritized by usage frequency.
+#[mcp_tool(
+    name = "type_map",
+    description = "Generate a usage-sorted map of project types in compact schema (BREAKING). Output keys: `h` (header) and `types` (rows: name|kind|file|line|usage_count|col|end_line|end_col; `col` is 1-based and `end_line`/`end_col` mark the position just past the definition). Optional meta under `@` (e.g. `@.t=true` when truncated). Rows are newline-delimited; fields are pipe-delimited and escaped: `\\` -> `\\\\`, `\n` -> `\\n`, `\r` -> `\\r`, `|` -> `\\|`. PERFORMANCE: Set count_usages=false to skip usage counting for faster results when you only need type locations. DIAGRAMS: Set output_format='mermaid' to get `mermaid` (a Mermaid classDiagram with fields, enum variants as <<enumeration>>, members, and inheritance arrows) instead of rows. TABLES: Set output_format='csv' or 'tsv' to get an embedded text/csv or text/tab-separated-values resource instead of JSON: a name,kind,file,line,usage_count section followed by type,field,field_type / type,variant,variant_type / type,member,member_type sections separated by blank lines; trailing types are dropped to fit max_tokens. STREAMING: Over the WebSocket transport, stream=true with a `_meta.progressToken` sends each type as its own NDJSON line, in the `message` of a `notifications/progress`, as soon as its file is parsed, then a `{\"total_count\": N}` line; without a token the lines come back as the result text instead; usage counting, sorting and max_tokens do not apply. Other transports reject stream=true."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct TypeMap {
+    /// Directory path to scan for types
+    pub path: String,
+    /// Maximum tokens in output (counted via tiktoken, default: 2000)
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    /// Optional glob pattern to filter files (e.g., '*.rs', 'src/**/*.ts')
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Whether to count usages across the project (default: true).
+    /// Set to false for faster results when you only need type locations.
+    #[serde(default)]
+    pub count_usages: Option<bool>,
+    /// Output format: "compact" (default), "mermaid" for a class diagram, or "csv"/"tsv" for tables
+    #[serde(default)]
+    pub output_format: Option<String>,
+    /// Stream types as NDJSON progress notifications while scanning (WebSocket transport only; rejected elsewhere, default: false)
+    #[serde(default)]
+    pub stream: Option<bool>,
+}
+
+impl TypeMap {
+    pub fn call_tool(&self, cancel: &CancellationToken) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path,
+            "max_tokens": self.max_tokens.unwrap_or(2000),
+            "pattern": self.pattern,
+            "count_usages": self.count_usages.unwrap_or(true),
+            "output_format": self.output_format
+        });
+
+        crate::analysis::type_map::execute_with_cancel(&args, cancel)
+            .map_err(|e| CallToolError::new(std::io::Error::other(e.to_string())))
+    }
+}
+
+/// List every function, type, constant and import in a project with visibility
+#[mcp_tool(
+    name = "project_inventory",
+    description = "Whole-project symbol inventory in compact schema. Lists functions, types, constants, and imports with file, line, language, and visibility (public/private/crate/protected/internal/package). Output keys: `by` (grouping), `h` (header), `g` (object mapping group name to newline-delimited rows). group_by='file' (DEFAULT, rows: kind|name|line|lang|vis), 'kind' (groups functions/types/constants/imports, rows: name|file|line|lang|vis), or 'language' (rows: kind|name|file|line|vis). Optional meta under `@` (`@.t=true` when truncated). USE WHEN: ✅ Need a bird's-eye list of all named symbols ✅ Looking for the public surface of a project. DON'T USE: ❌ Need signatures or code → use code_map or view_code. TOKEN COST: MEDIUM (budgeted via max_tokens, default: 2000)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct ProjectInventory {
+    /// Path to file or directory
+    pub path: String,
+    /// Grouping: "file", "kind", or "language" (default: "file")
+    #[serde(default)]
+    pub group_by: Option<String>,
+    /// Maximum tokens for output (counted via tiktoken, default: 2000)
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+}
+
+impl ProjectInventory {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path,
+            "group_by": self.group_by,
+            "max_tokens": self.max_tokens.unwrap_or(2000)
+        });
+
+        symbol_inventory::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+/// Find imports whose names are never used in their file
+#[mcp_tool(
+    name = "stale_imports",
+    description = "Find imports that are never used. For each file, imported names are checked against syntax-aware identifier references outside the import itself. Output keys: `h` (header), `stale` (rows: file|line|import|unused, where unused is a comma-separated list of unreferenced names). Rust `pub use` re-exports are never stale, Python names listed in `__all__` count as used, glob imports are skipped. USE WHEN: ✅ Cleaning up after a refactor ✅ Trimming noisy imports before reading a file. LIMITATION: heuristic; Rust trait imports used only through method calls are reported. TOKEN COST: LOW."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct StaleImports {
+    /// Path to file or directory
+    pub path: String,
+}
+
+impl StaleImports {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path
+        });
+
+        stale_imports::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+/// Cyclomatic complexity of every function in a file or directory
+#[mcp_tool(
+    name = "complexity",
+    description = "Cyclomatic complexity per function: 1 + decision points (if/else-if, loops, extra match/switch arms, &&/||, ternaries, catch clauses, Rust `?`). Output keys: `threshold`, `h` (header), `fns` (rows: symbol|file|line|complexity|over, most complex first; `over` is 1 above the threshold). USE WHEN: ✅ Code review: find functions that need splitting ✅ Choosing refactoring targets. TOKEN COST: LOW (one row per function)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct Complexity {
+    /// Path to file or directory
+    pub path: String,
+    /// Flag functions with complexity above this value (default: 10)
+    #[serde(default)]
+    pub threshold: Option<u32>,
+}
+
+impl Complexity {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path,
+            "threshold": self.threshold
+        });
+
+        metrics::execute_complexity(&args).map_err(CallToolError::new)
+    }
+}
+
+/// Classify every import as used, unused or unknown
+#[mcp_tool(
+    name = "find_unused_imports",
+    description = "List every import with whether its names are referenced in the rest of the file (syntax-aware identifier matches, handling aliases and `{a, b}` groups). Output keys: `h` (header), `imports` (rows: file|line|import|status|unused). `status` is `used`, `unused` (no bound name referenced) or `unknown` (globs such as `use a::*` / `from a import *`, Go dot imports, C# namespace usings, Swift modules: not resolvable without type info). Re-exports (`pub use`, Python `__all__`) and side-effect imports are `used`. `unused` lists unreferenced names, also for partly used imports. USE WHEN: ✅ Auditing a file's imports including globs ✅ Cleanup after a refactor. DON'T USE: ❌ Only want imports to delete → use stale_imports (smaller output). TOKEN COST: LOW."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct FindUnusedImports {
+    /// Path to file or directory
+    pub path: String,
+}
+
+impl FindUnusedImports {
+    pub fn call_tool(&self, cancel: &CancellationToken) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path
+        });
+
+        unused::execute_find_unused_imports_with_cancel(&args, cancel).map_err(CallToolError::new)
+    }
+}
+
+/// Suggest return type annotations for unannotated Python and TypeScript functions
+#[mcp_tool(
+    name = "infer_types",
+    description = "Heuristically infer missing return type annotations for Python and TypeScript functions from their return statements (literals, comparisons, constructor calls, bare returns). Output keys: `h` (header), `hints` (rows: file|line|fn|type|conf) where conf is high, medium, or low. Mixed returns become unions (e.g. `str | None`); async TypeScript functions are wrapped in `Promise<...>`; generators and unrecognized return values are skipped. USE WHEN: ✅ Adding type annotations to an untyped codebase ✅ Checking what an unannotated function returns without reading it. TOKEN COST: LOW."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct InferTypes {
+    /// Path to file or directory
+    pub path: String,
+}
+
+impl InferTypes {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path
+        });
+
+        annotation_inference::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+/// List exact dependency versions pinned by Cargo.lock and go.sum
+#[mcp_tool(
+    name = "list_locked_dependencies",
+    description = "List exact dependency versions pinned in a project's Cargo.lock and go.sum. Output keys: `h` (header), `deps` (rows: name|version|checksum|src). With check_cve=true and a cve_url pointing to a JSON array of {id, package, versions} advisories, also returns `vh` (header) and `vulns` (rows: name|version|id) for locked versions listed in the advisories. USE WHEN: ✅ Security auditing ✅ Checking which exact version of a dependency is in use. TOKEN COST: MEDIUM (one row per locked package)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct ListLockedDependencies {
+    /// Project root containing Cargo.lock and/or go.sum
+    pub project_root: String,
+    /// Cross-reference locked versions against the advisories at cve_url (default: false)
+    #[serde(default)]
+    pub check_cve: Option<bool>,
+    /// URL of a JSON advisory list, required when check_cve is true
+    #[serde(default)]
+    pub cve_url: Option<String>,
+}
+
+impl ListLockedDependencies {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "project_root": self.project_root,
+            "check_cve": self.check_cve.unwrap_or(false),
+            "cve_url": self.cve_url
+        });
+
+        lockfile::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+/// List dependencies declared in Gradle and Maven build files
+#[mcp_tool(
+    name = "list_build_dependencies",
+    description = "List dependencies declared in Gradle (build.gradle, build.gradle.kts) and Maven (pom.xml) build files under a project root. Maven ${property} versions are resolved and missing versions are filled from dependencyManagement. Output keys: `h` (header), `deps` (rows: group|artifact|version|scope|src). When a gradle.lockfile sits next to a Gradle build file, also returns `dh` (header) and `drift` (rows: group|artifact|declared|locked|src) for declared versions that differ from the locked ones. USE WHEN: ✅ Auditing Java/Kotlin dependencies ✅ Finding declared versions that no longer match the lockfile. TOKEN COST: MEDIUM (one row per declared dependency)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct ListBuildDependencies {
+    /// Project root to scan for build files
+    pub project_root: String,
+}
+
+impl ListBuildDependencies {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "project_root": self.project_root
+        });
+
+        build_files::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+/// Inspect a package.json manifest
+#[mcp_tool(
+    name = "inspect_package_json",
+    description = "List the dependencies, devDependencies, peerDependencies (with version ranges) and npm scripts of a package.json. Output keys: `name`, `version`, `h` (header), `deps` (rows: name|range|kind), `sh` (header), `scripts` (rows: name|command). With check_outdated=true, runs `npm outdated --json` in the package directory and adds a `latest` column to `deps` for packages with a newer version available. USE WHEN: ✅ Understanding a Node.js project's dependencies ✅ Finding the npm run targets ✅ Spotting outdated packages. TOKEN COST: LOW (one row per dependency and script)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct InspectPackageJson {
+    /// Path to package.json or the directory containing it
+    pub path: String,
+    /// Run `npm outdated` and report the latest available versions (default: false)
+    #[serde(default)]
+    pub check_outdated: Option<bool>,
+}
+
+impl InspectPackageJson {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path,
+            "check_outdated": self.check_outdated.unwrap_or(false)
+        });
+
+        build_files::execute_inspect_package_json(&args).map_err(CallToolError::new)
+    }
+}
+
+/// Diff the types of two snapshots of a file or directory
+#[mcp_tool(
+    name = "diff_types",
+    description = "Compare the type definitions (structs, classes, interfaces, enums, ...) of two snapshots: `path` against `other_path`, or `path` against its content at git revision `compare_to`. Types are matched by name. Output keys: `before`, `after`, `h` (header), `types` (rows: change|kind|name|file|line for added `+` and removed `-` types), `fh` (header), `fields` (rows: type|change|field|before|after for fields, members and variants added `+`, removed `-` or retyped `~`). USE WHEN: ✅ Reviewing data model changes across a branch ✅ Comparing two versions of a schema file ✅ Checking what a refactor did to public types. DON'T USE: ❌ Need function-level changes → use parse_diff. TOKEN COST: LOW (only changed types and fields are listed)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct DiffTypes {
+    /// File or directory holding the new version
+    pub path: String,
+    /// File or directory holding the old version
+    #[serde(default)]
+    pub other_path: Option<String>,
+    /// Git revision holding the old version of `path`, e.g. "HEAD", "main"
+    #[serde(default)]
+    pub compare_to: Option<String>,
+}
+
+impl DiffTypes {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path,
+            "other_path": self.other_path,
+            "compare_to": self.compare_to
+        });
+
+        type_diff::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+/// Outline the JSX element tree rendered by React components
+#[mcp_tool(
+    name = "extract_jsx_tree",
+    description = "Outline the JSX element tree of a React .tsx/.jsx/.js file: which elements and components each component renders, nested as in the source, with prop names only (no values). Output keys: `file`, `h` (header), `tree` (rows: component|depth|tag|props|line in document order; depth 0 is the root of each JSX expression, fragments are `<>`, spread props `...name`). Pass component to keep only what that component renders. USE WHEN: ✅ Understanding a component's structure without reading its markup ✅ Finding where a child component is used and with which props. DON'T USE: ❌ Need the component's logic → use view_code. TOKEN COST: LOW (one short row per element)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct ExtractJsxTree {
+    /// Path to the .tsx, .jsx or .js file
+    pub file_path: String,
+    /// Only include JSX rendered by this component
+    #[serde(default)]
+    pub component: Option<String>,
+}
+
+impl ExtractJsxTree {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "file_path": self.file_path,
+            "component": self.component
+        });
+
+        jsx::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+/// Link test functions to the production symbols they probably test
+#[mcp_tool(
+    name = "map_test_coverage",
+    description = "Map test functions to the production functions and types they probably test, by name: test_add → add, test_Calculator_reset → Calculator::reset, TestParseConfig → ParseConfig, add_item_test → add_item. Names are compared word by word (snake_case and camelCase), longest match wins. Output keys: `h` (header), `tests` (rows: test_file|test_fn|line|targets, targets comma-separated; empty when nothing matches). USE WHEN: ✅ Finding which tests cover a function before changing it ✅ Spotting tests whose target was renamed or removed. DON'T USE: ❌ Need tests that actually call a symbol → use relevant_tests. TOKEN COST: LOW-MEDIUM (one row per test function)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct MapTestCoverage {
+    /// Project directory (or file) containing tests and production code
+    pub path: String,
+}
+
+impl MapTestCoverage {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path
+        });
+
+        coverage_map::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+/// Classify tokens the way an editor's semantic highlighting does
+#[mcp_tool(
+    name = "semantic_tokens",
+    description = "Classify the tokens of a source file like an editor's semantic highlighting, using LSP token types: function (declared or called), type (declared or used as a type), variable, keyword, comment, string. Comments and strings are single tokens. Output keys: `file`, `h` (header), `tokens` (rows: line|col|len|type|mods|text in document order; line/col 1-based, len in bytes, mods comma-separated from declaration,documentation; text empty for comments and strings). Punctuation, operators and numbers are omitted. USE WHEN: ✅ Telling calls apart from variables or types in unfamiliar syntax ✅ Finding every declaration name in a file with its exact position. DON'T USE: ❌ Need a file outline → use view_code ❌ Need where a symbol is used → use find_usages. TOKEN COST: HIGH (one row per identifier and keyword)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct SemanticTokens {
+    /// Path to the source file
+    pub file_path: String,
+}
+
+impl SemanticTokens {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "file_path": self.file_path
+        });
+
+        semantic_tokens::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+/// List Next.js App Router routes
+#[mcp_tool(
+    name = "list_routes",
+    description = "List the routes of a Next.js App Router project, derived from the `app/` directory layout. Route groups `(group)` and slots `@slot` are dropped from URLs, `_private` folders are skipped, dynamic segments like `[id]` are kept. Output keys: `app` (app directory), `h` (header), `routes` (rows: route|file|loading|error|layouts|exports). loading/error are 1 when a loading.*/error.* boundary covers the route; layouts are outermost first; exports lists exported functions (`default` for the default export). USE WHEN: ✅ Mapping URLs to source files ✅ Finding which layouts wrap a page. TOKEN COST: LOW (one row per route)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct ListRoutes {
+    /// Project root (containing app/ or src/app/) or the app directory itself
+    pub path: String,
+}
+
+impl ListRoutes {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path
+        });
+
+        nextjs::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+/// List FastAPI path operations
+#[mcp_tool(
+    name = "list_api_routes",
+    description = "List FastAPI path operations registered with decorators like `@app.get(\"/items/{id}\")` or `@router.post(\"/users\")` in a Python file or directory. Output keys: `h` (header), `routes` (rows: method|path|function|file|line|params). params lists the handler's parameters with their annotations, e.g. `item_id: int, q: str | None`. USE WHEN: ✅ Mapping endpoints to handlers ✅ Reviewing a service's API surface. TOKEN COST: LOW (one row per route)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct ListApiRoutes {
+    /// Python file or directory to scan
+    pub path: String,
+}
+
+impl ListApiRoutes {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path
+        });
+
+        fastapi::execute(&args).map_err(CallToolError::new)
+    }
+}
+
+/// List NestJS modules
+#[mcp_tool(
+    name = "list_nest_modules",
+    description = "List NestJS modules declared with `@Module({ imports, controllers, providers, exports })` in a TypeScript file or directory. Output keys: `h` (header), `modules` (rows: module|file|line|imports|controllers|providers|exports). List columns are comma-separated source text; `{ provide: X, ... }` providers are listed as X. USE WHEN: ✅ Understanding a NestJS app's dependency wiring ✅ Finding which module provides a service. TOKEN COST: LOW (one row per module)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct ListNestModules {
+    /// TypeScript file or directory to scan
+    pub path: String,
+}
+
+impl ListNestModules {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path
+        });
+
+        nestjs::execute_list_nest_modules(&args).map_err(CallToolError::new)
+    }
+}
+
+/// List NestJS controller routes
+#[mcp_tool(
+    name = "list_nest_routes",
+    description = "List NestJS routes from `@Controller('prefix')` classes and their `@Get`/`@Post`/`@Put`/`@Patch`/`@Delete`/`@Options`/`@Head`/`@All` handlers in a TypeScript file or directory. Each route is linked to the module whose `controllers` array lists its controller. Output keys: `h` (header), `routes` (rows: method|path|controller|handler|module|file|line). USE WHEN: ✅ Mapping endpoints to handlers ✅ Reviewing a NestJS API surface. TOKEN COST: LOW (one row per route)."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct ListNestRoutes {
+    /// TypeScript file or directory to scan
+    pub path: String,
+}
+
+impl ListNestRoutes {
+    pub fn call_tool(&self) -> Result<CallToolResult, CallToolError> {
+        let args = serde_json::json!({
+            "path": self.path
+        });
+
+        nestjs::execute_list_nest_routes(&args).map_err(CallToolError::new)
     }
 }
 
@@ -665,6 +1557,7 @@ tool_box!(
         CallGraph,
         SymbolAtLine,
         ParseDiff,
+        BatchDiff,
         AffectedByDiff,
         PreviewImpact,
         QueryPattern,
@@ -672,6 +1565,39 @@ tool_box!(
         VerifyEdit,
         ReviewContext,
         TemplateContext,
-        TypeMap
+        TypeMap,
+        ProjectInventory,
+        StaleImports,
+        Complexity,
+        FindUnusedImports,
+        InferTypes,
+        ListLockedDependencies,
+        ListRoutes,
+        ListApiRoutes,
+        ListNestModules,
+        ListNestRoutes,
+        ListBuildDependencies,
+        InspectPackageJson,
+        DiffTypes,
+        ExtractJsxTree,
+        MapTestCoverage,
+        SemanticTokens,
+        FindTraitImplementations,
+        FindMacros,
+        RenameSymbol,
+        FindComments,
+        FindRoutes,
+        FindPanics,
+        GetSymbol,
+        FoldingRanges,
+        SvelteShape,
+        VueShape,
+        CssVarUsages,
+        HtmlForms,
+        ModuleTree,
+        PythonImportGraph,
+        PublicApi,
+        FindTests,
+        AssembleContext
     ]
 );
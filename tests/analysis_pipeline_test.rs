@@ -0,0 +1,97 @@
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::tempdir;
+use treesitter_mcp::analysis::pipeline::AnalysisPipeline;
+use treesitter_mcp::parser::Language;
+
+mod common;
+
+fn function_rows(result: &treesitter_mcp::mcp_types::CallToolResult) -> Vec<Vec<String>> {
+    let text = common::get_result_text(result);
+    let view: Value = serde_json::from_str(&text).unwrap();
+    common::helpers::parse_compact_rows(view["f"].as_str().unwrap_or(""))
+}
+
+#[test]
+fn test_pipeline_matches_view_code_arguments() {
+    let file_path = common::fixture_path("rust", "src/calculator.rs");
+    let arguments = json!({
+        "file_path": file_path.to_str().unwrap(),
+        "focus_symbol": "add",
+        "include_deps": true,
+        "max_tokens": 4000
+    });
+    let expected = treesitter_mcp::analysis::view_code::execute(&arguments).unwrap();
+
+    let actual = AnalysisPipeline::new(&file_path)
+        .language(Language::Rust)
+        .max_tokens(4000)
+        .include_deps(true)
+        .focus("add")
+        .execute()
+        .unwrap();
+
+    assert_eq!(
+        common::get_result_text(&actual),
+        common::get_result_text(&expected)
+    );
+}
+
+#[test]
+fn test_pipeline_signatures_only() {
+    let file_path = common::fixture_path("rust", "src/calculator.rs");
+    let result = AnalysisPipeline::new(&file_path)
+        .include_code(false)
+        .include_deps(false)
+        .max_tokens(10_000)
+        .execute()
+        .unwrap();
+
+    let text = common::get_result_text(&result);
+    let view: Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(view["h"], "name|line|sig");
+    assert!(function_rows(&result).iter().any(|row| row[0] == "add"));
+}
+
+#[test]
+fn test_pipeline_language_override_parses_unknown_extension() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("snippet.txt");
+    fs::write(&path, "pub fn answer() -> u32 {\n    42\n}\n").unwrap();
+
+    let pipeline = AnalysisPipeline::new(&path);
+    assert!(pipeline.execute().is_err());
+
+    let pipeline = pipeline.language(Language::Rust).include_deps(false);
+    assert_eq!(pipeline.resolved_language().unwrap(), Language::Rust);
+    let rows = function_rows(&pipeline.execute().unwrap());
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0][0], "answer");
+}
+
+#[test]
+fn test_pipeline_reuses_parsed_file_across_executions() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("lib.rs");
+    fs::write(&path, "pub fn first() {}\n").unwrap();
+
+    let pipeline = AnalysisPipeline::new(&path).include_deps(false);
+    assert_eq!(pipeline.shape().unwrap().functions.len(), 1);
+
+    // The cached parse is used even after the file changes on disk.
+    fs::write(&path, "pub fn first() {}\npub fn second() {}\n").unwrap();
+    let pipeline = pipeline.max_tokens(500).focus("first");
+    assert_eq!(function_rows(&pipeline.execute().unwrap()).len(), 1);
+
+    // Switching detail level re-extracts the shape from the cached parse.
+    let pipeline = pipeline.include_code(false);
+    assert_eq!(function_rows(&pipeline.execute().unwrap()).len(), 1);
+}
+
+#[test]
+fn test_pipeline_missing_file_errors() {
+    let err = AnalysisPipeline::new("/nonexistent/file.rs")
+        .execute()
+        .unwrap_err();
+    assert!(err.to_string().contains("Failed to read file"));
+}
@@ -399,3 +399,111 @@ fn test_template_context_execute_compact_schema() {
         "Should include CalculatorTemplate location"
     );
 }
+
+// ============================================================================
+// Template Dependency Cycles
+// ============================================================================
+
+fn write_askama_project(templates: &[(&str, &str)]) -> tempfile::TempDir {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir_all(dir.path().join("templates")).unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"site\"\n\n[dependencies]\naskama = \"0.12\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "#[derive(Template)]\n#[template(path = \"base.html\")]\npub struct BaseTemplate {\n    pub title: String,\n}\n",
+    )
+    .unwrap();
+    for (name, source) in templates {
+        std::fs::write(dir.path().join("templates").join(name), source).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn test_circular_template_dependency_is_reported() {
+    let project = write_askama_project(&[
+        (
+            "base.html",
+            "{% extends \"layout.html\" %}{% block body %}{{ title }}{% endblock %}",
+        ),
+        (
+            "layout.html",
+            "{% extends \"base.html\" %}{% block body %}{% endblock %}",
+        ),
+    ]);
+
+    let err = treesitter_mcp::analysis::askama::find_askama_structs_for_template(
+        &project.path().join("templates/base.html"),
+        project.path(),
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Circular template dependency: base.html -> layout.html -> base.html"),
+        "unexpected error: {err}"
+    );
+
+    let args = json!({"template_path": project.path().join("templates/layout.html")});
+    let err = treesitter_mcp::analysis::askama::execute(&args).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("layout.html -> base.html -> layout.html"));
+}
+
+#[test]
+fn test_shared_partial_is_not_a_cycle() {
+    let project = write_askama_project(&[
+        (
+            "base.html",
+            "{% include \"nav.html\" %}{% include \"footer.html\" %}{{ title }}",
+        ),
+        ("nav.html", "{% include \"link.html\" %}"),
+        ("footer.html", "{% include \"link.html\" %}"),
+        ("link.html", "<a href=\"/\">home</a>"),
+    ]);
+
+    let structs = treesitter_mcp::analysis::askama::find_askama_structs_for_template(
+        &project.path().join("templates/base.html"),
+        project.path(),
+    )
+    .unwrap();
+    assert_eq!(structs.len(), 1);
+    assert_eq!(structs[0].struct_name, "BaseTemplate");
+}
+
+#[test]
+fn test_deep_template_chain_is_not_an_error() {
+    let mut templates: Vec<(String, String)> = (0..60)
+        .map(|i| {
+            (
+                format!("level{i}.html"),
+                format!("{{% extends \"level{}.html\" %}}", i + 1),
+            )
+        })
+        .collect();
+    templates.push(("base.html".into(), "{% extends \"level0.html\" %}".into()));
+    let borrowed: Vec<(&str, &str)> = templates
+        .iter()
+        .map(|(name, source)| (name.as_str(), source.as_str()))
+        .collect();
+    let project = write_askama_project(&borrowed);
+
+    let mut visited = std::collections::HashSet::new();
+    treesitter_mcp::analysis::file_shape::resolve_template_dependencies(
+        &project.path().join("templates/base.html"),
+        &project.path().join("templates"),
+        &mut visited,
+        &mut Vec::new(),
+    )
+    .expect("depth cap is a warning, not an error");
+    assert_eq!(
+        visited.len(),
+        51,
+        "traversal stops at MAX_TEMPLATE_DEPTH (50)"
+    );
+}
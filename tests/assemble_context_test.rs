@@ -0,0 +1,197 @@
+mod common;
+
+use std::fs;
+
+use serde_json::json;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::context_assembler::{assemble_context, ContextChunk};
+
+const PRICING_RS: &str = r#"pub struct Order {
+    pub total: u32,
+}
+
+pub struct Discount {
+    pub percent: u32,
+}
+
+pub fn apply_discount(order: &Order, discount: Discount) -> u32 {
+    order.total * (100 - discount.percent) / 100
+}
+"#;
+
+const CHECKOUT_RS: &str = r#"use crate::pricing::{apply_discount, Discount, Order};
+
+pub fn checkout(order: Order) -> u32 {
+    let discount = Discount { percent: 10 };
+    let total = apply_discount(&order, discount);
+    println!("charging {total}");
+    total
+}
+
+pub fn preview(order: &Order) -> String {
+    let price = apply_discount(order, Discount { percent: 0 });
+    format!("Your order comes to {price} before shipping and taxes")
+}
+"#;
+
+fn shop() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("pricing.rs"), PRICING_RS).unwrap();
+    fs::write(src.join("checkout.rs"), CHECKOUT_RS).unwrap();
+    dir
+}
+
+fn spans(chunks: &[ContextChunk]) -> Vec<(&str, usize, usize, f64)> {
+    chunks
+        .iter()
+        .map(|chunk| {
+            let file = chunk.file.rsplit('/').next().unwrap();
+            (
+                file,
+                chunk.start_line,
+                chunk.end_line,
+                chunk.relevance_score,
+            )
+        })
+        .collect()
+}
+
+fn focal(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn test_unlimited_budget_orders_definition_callers_then_types() {
+    let dir = shop();
+    let chunks = assemble_context(&focal(&["apply_discount"]), dir.path(), 10_000).unwrap();
+
+    assert_eq!(
+        spans(&chunks),
+        vec![
+            ("pricing.rs", 9, 11, 1.0),
+            ("checkout.rs", 3, 8, 0.7),
+            ("checkout.rs", 10, 13, 0.7),
+            ("pricing.rs", 1, 3, 0.5),
+            ("pricing.rs", 5, 7, 0.5),
+        ]
+    );
+    assert!(chunks[0].code.starts_with("pub fn apply_discount("));
+    assert!(chunks.iter().all(|chunk| chunk.tokens > 0));
+}
+
+#[test]
+fn test_tiny_budget_returns_only_the_focal_definition() {
+    let dir = shop();
+    let all = assemble_context(&focal(&["apply_discount"]), dir.path(), 10_000).unwrap();
+
+    let chunks = assemble_context(&focal(&["apply_discount"]), dir.path(), all[0].tokens).unwrap();
+
+    assert_eq!(spans(&chunks), vec![("pricing.rs", 9, 11, 1.0)]);
+}
+
+#[test]
+fn test_growing_budget_adds_callers_progressively() {
+    let dir = shop();
+    let all = assemble_context(&focal(&["apply_discount"]), dir.path(), 10_000).unwrap();
+    let definition = all[0].tokens;
+    let first_caller = all[1].tokens;
+    let second_caller = all[2].tokens;
+
+    let one_caller = assemble_context(
+        &focal(&["apply_discount"]),
+        dir.path(),
+        definition + first_caller,
+    )
+    .unwrap();
+    assert_eq!(
+        spans(&one_caller),
+        vec![("pricing.rs", 9, 11, 1.0), ("checkout.rs", 3, 8, 0.7)]
+    );
+
+    let two_callers = assemble_context(
+        &focal(&["apply_discount"]),
+        dir.path(),
+        definition + first_caller + second_caller,
+    )
+    .unwrap();
+    assert_eq!(
+        spans(&two_callers),
+        vec![
+            ("pricing.rs", 9, 11, 1.0),
+            ("checkout.rs", 3, 8, 0.7),
+            ("checkout.rs", 10, 13, 0.7),
+        ]
+    );
+}
+
+#[test]
+fn test_overlapping_chunks_are_not_repeated() {
+    let dir = shop();
+    let chunks =
+        assemble_context(&focal(&["apply_discount", "checkout"]), dir.path(), 10_000).unwrap();
+
+    let checkout_chunks: Vec<_> = spans(&chunks)
+        .into_iter()
+        .filter(|(file, start, _, _)| *file == "checkout.rs" && *start == 3)
+        .collect();
+    assert_eq!(checkout_chunks, vec![("checkout.rs", 3, 8, 1.0)]);
+    assert_eq!(chunks.len(), 5);
+}
+
+#[test]
+fn test_python_callers_and_annotation_types() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("billing.py"),
+        "class Invoice:\n    total: int\n\n\ndef settle(invoice: Invoice) -> int:\n    return invoice.total\n\n\ndef close_month(invoices):\n    return sum(settle(invoice) for invoice in invoices)\n\n\nsettle(Invoice())\n",
+    )
+    .unwrap();
+
+    let chunks = assemble_context(&focal(&["settle"]), dir.path(), 10_000).unwrap();
+
+    assert_eq!(
+        spans(&chunks),
+        vec![
+            ("billing.py", 5, 6, 1.0),
+            ("billing.py", 9, 10, 0.7),
+            ("billing.py", 13, 13, 0.7),
+            ("billing.py", 1, 2, 0.5),
+        ]
+    );
+}
+
+#[test]
+fn test_assemble_context_tool_output() {
+    let dir = shop();
+    let result = treesitter_mcp::analysis::context_assembler::execute_assemble_context(&json!({
+        "focal_symbols": ["apply_discount"],
+        "path": dir.path().to_str().unwrap(),
+        "token_budget": 10_000
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["h"], "file|start|end|score|tokens|code");
+    let rows = common::helpers::parse_compact_rows(output["chunks"].as_str().unwrap());
+    assert_eq!(rows.len(), 5);
+    assert!(rows[0][0].ends_with("pricing.rs"));
+    assert_eq!(rows[0][1], "9");
+    assert_eq!(rows[0][3], "1");
+    assert!(rows[0][5].contains("discount.percent"));
+    let used: u64 = rows.iter().map(|row| row[4].parse::<u64>().unwrap()).sum();
+    assert_eq!(output["used"], used);
+}
+
+#[test]
+fn test_empty_focal_symbols_is_rejected() {
+    let dir = shop();
+    let result = treesitter_mcp::analysis::context_assembler::execute_assemble_context(&json!({
+        "focal_symbols": [],
+        "path": dir.path().to_str().unwrap()
+    }));
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,343 @@
+mod common;
+
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::build_files::{
+    self, parse_gradle, parse_gradle_lockfile, parse_maven_pom, parse_npm_outdated,
+    parse_package_json, BuildDep,
+};
+
+const BUILD_GRADLE: &str = r#"plugins {
+    id 'java'
+}
+
+def jacksonVersion = '2.17.0'
+
+dependencies {
+    implementation 'com.google.guava:guava:31.1-jre'
+    implementation platform('org.springframework.boot:spring-boot-dependencies:3.2.0')
+    implementation 'org.springframework.boot:spring-boot-starter-web'
+    implementation "com.fasterxml.jackson.core:jackson-databind:$jacksonVersion"
+    runtimeOnly group: 'org.postgresql', name: 'postgresql', version: '42.7.1'
+    testImplementation 'org.junit.jupiter:junit-jupiter:5.10.0'
+}
+"#;
+
+const BUILD_GRADLE_KTS: &str = r#"dependencies {
+    implementation("io.ktor:ktor-server-core:2.3.7")
+    compileOnly(group = "org.projectlombok", name = "lombok", version = "1.18.30")
+    testImplementation(kotlin("test"))
+}
+"#;
+
+const GRADLE_LOCKFILE: &str = "# This is a Gradle generated file for dependency locking.\n# Manual edits can break the build and are not advised.\n# This file is expected to be part of source control.\ncom.google.guava:guava:32.0.0-jre=compileClasspath,runtimeClasspath\norg.junit.jupiter:junit-jupiter:5.10.0=testCompileClasspath\nempty=annotationProcessor\n";
+
+const POM_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project>
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.example</groupId>
+  <artifactId>demo</artifactId>
+  <version>1.0.0</version>
+
+  <properties>
+    <slf4j.version>2.0.9</slf4j.version>
+  </properties>
+
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <groupId>org.apache.commons</groupId>
+        <artifactId>commons-lang3</artifactId>
+        <version>3.14.0</version>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+
+  <dependencies>
+    <dependency>
+      <groupId>org.slf4j</groupId>
+      <artifactId>slf4j-api</artifactId>
+      <version>${slf4j.version}</version>
+      <exclusions>
+        <exclusion>
+          <groupId>org.example</groupId>
+          <artifactId>excluded</artifactId>
+        </exclusion>
+      </exclusions>
+    </dependency>
+    <dependency>
+      <groupId>org.apache.commons</groupId>
+      <artifactId>commons-lang3</artifactId>
+    </dependency>
+    <!-- <dependency><groupId>commented</groupId><artifactId>out</artifactId></dependency> -->
+    <dependency>
+      <groupId>junit</groupId>
+      <artifactId>junit</artifactId>
+      <version>4.13.2</version>
+      <scope>test</scope>
+    </dependency>
+  </dependencies>
+
+  <build>
+    <plugins>
+      <plugin>
+        <groupId>org.apache.maven.plugins</groupId>
+        <artifactId>maven-surefire-plugin</artifactId>
+        <dependencies>
+          <dependency>
+            <groupId>org.plugin</groupId>
+            <artifactId>plugin-only</artifactId>
+            <version>1.0</version>
+          </dependency>
+        </dependencies>
+      </plugin>
+    </plugins>
+  </build>
+</project>
+"#;
+
+fn dep(group: &str, artifact: &str, version: Option<&str>, scope: &str) -> BuildDep {
+    BuildDep {
+        group: group.to_string(),
+        artifact: artifact.to_string(),
+        version: version.map(str::to_string),
+        scope: scope.to_string(),
+    }
+}
+
+#[test]
+fn test_parse_gradle_groovy_and_kotlin_dsl() {
+    let dir = TempDir::new().unwrap();
+    let groovy = dir.path().join("build.gradle");
+    let kotlin = dir.path().join("build.gradle.kts");
+    fs::write(&groovy, BUILD_GRADLE).unwrap();
+    fs::write(&kotlin, BUILD_GRADLE_KTS).unwrap();
+
+    assert_eq!(
+        parse_gradle(&groovy),
+        [
+            dep(
+                "com.google.guava",
+                "guava",
+                Some("31.1-jre"),
+                "implementation"
+            ),
+            dep(
+                "org.springframework.boot",
+                "spring-boot-dependencies",
+                Some("3.2.0"),
+                "implementation"
+            ),
+            dep(
+                "org.springframework.boot",
+                "spring-boot-starter-web",
+                None,
+                "implementation"
+            ),
+            dep(
+                "com.fasterxml.jackson.core",
+                "jackson-databind",
+                Some("$jacksonVersion"),
+                "implementation"
+            ),
+            dep(
+                "org.postgresql",
+                "postgresql",
+                Some("42.7.1"),
+                "runtimeOnly"
+            ),
+            dep(
+                "org.junit.jupiter",
+                "junit-jupiter",
+                Some("5.10.0"),
+                "testImplementation"
+            ),
+        ]
+    );
+    assert_eq!(
+        parse_gradle(&kotlin),
+        [
+            dep(
+                "io.ktor",
+                "ktor-server-core",
+                Some("2.3.7"),
+                "implementation"
+            ),
+            dep(
+                "org.projectlombok",
+                "lombok",
+                Some("1.18.30"),
+                "compileOnly"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_maven_pom_resolves_properties_and_managed_versions() {
+    let dir = TempDir::new().unwrap();
+    let pom = dir.path().join("pom.xml");
+    fs::write(&pom, POM_XML).unwrap();
+
+    assert_eq!(
+        parse_maven_pom(&pom),
+        [
+            dep("org.slf4j", "slf4j-api", Some("2.0.9"), "compile"),
+            dep(
+                "org.apache.commons",
+                "commons-lang3",
+                Some("3.14.0"),
+                "compile"
+            ),
+            dep("junit", "junit", Some("4.13.2"), "test"),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_gradle_lockfile() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = dir.path().join("gradle.lockfile");
+    fs::write(&lockfile, GRADLE_LOCKFILE).unwrap();
+
+    let locked = parse_gradle_lockfile(&lockfile);
+    assert_eq!(locked.len(), 2);
+    assert_eq!(locked[0].name, "com.google.guava:guava");
+    assert_eq!(locked[0].version, "32.0.0-jre");
+}
+
+#[test]
+fn test_list_build_dependencies_reports_version_drift() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("build.gradle"), BUILD_GRADLE).unwrap();
+    fs::write(dir.path().join("gradle.lockfile"), GRADLE_LOCKFILE).unwrap();
+    fs::create_dir(dir.path().join("legacy")).unwrap();
+    fs::write(dir.path().join("legacy").join("pom.xml"), POM_XML).unwrap();
+
+    let result =
+        build_files::execute(&json!({ "project_root": dir.path().to_str().unwrap() })).unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["h"], "group|artifact|version|scope|src");
+    let deps = common::helpers::parse_compact_rows(output["deps"].as_str().unwrap());
+    assert_eq!(deps.len(), 9);
+    assert!(deps
+        .iter()
+        .any(|row| row[1] == "junit" && row[3] == "test" && row[4].ends_with("pom.xml")));
+
+    // Only guava differs; junit matches and variables are not compared.
+    assert_eq!(output["dh"], "group|artifact|declared|locked|src");
+    let drift = common::helpers::parse_compact_rows(output["drift"].as_str().unwrap());
+    assert_eq!(drift.len(), 1);
+    assert_eq!(
+        drift[0][..4],
+        ["com.google.guava", "guava", "31.1-jre", "32.0.0-jre"]
+    );
+}
+
+#[test]
+fn test_list_build_dependencies_omits_drift_without_lockfile() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("pom.xml"), POM_XML).unwrap();
+
+    let result =
+        build_files::execute(&json!({ "project_root": dir.path().to_str().unwrap() })).unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert!(output.get("drift").is_none());
+}
+
+const PACKAGE_JSON: &str = r#"{
+  "name": "web",
+  "version": "1.0.0",
+  "scripts": {
+    "dev": "vite",
+    "build": "tsc && vite build"
+  },
+  "dependencies": {
+    "react": "^18.2.0",
+    "react-dom": "^18.2.0"
+  },
+  "devDependencies": {
+    "typescript": "~5.3.0"
+  },
+  "peerDependencies": {
+    "vite": ">=5"
+  }
+}
+"#;
+
+#[test]
+fn test_parse_package_json_dependencies_and_scripts() {
+    let dir = TempDir::new().unwrap();
+    let manifest = dir.path().join("package.json");
+    fs::write(&manifest, PACKAGE_JSON).unwrap();
+
+    let info = parse_package_json(&manifest);
+    assert_eq!(info.name.as_deref(), Some("web"));
+    let deps: Vec<(&str, &str, &str)> = info
+        .dependencies
+        .iter()
+        .map(|dep| (dep.name.as_str(), dep.range.as_str(), dep.kind.as_str()))
+        .collect();
+    assert_eq!(
+        deps,
+        [
+            ("react", "^18.2.0", "dependencies"),
+            ("react-dom", "^18.2.0", "dependencies"),
+            ("typescript", "~5.3.0", "devDependencies"),
+            ("vite", ">=5", "peerDependencies"),
+        ]
+    );
+    assert_eq!(
+        info.scripts,
+        [
+            ("build".to_string(), "tsc && vite build".to_string()),
+            ("dev".to_string(), "vite".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_npm_outdated_output() {
+    let outdated = parse_npm_outdated(
+        r#"{
+  "react": {"current": "18.2.0", "wanted": "18.3.1", "latest": "19.0.0", "location": "node_modules/react"},
+  "typescript": [{"current": "5.3.3", "wanted": "5.3.3", "latest": "5.7.2"}]
+}"#,
+    );
+
+    assert_eq!(outdated["react"].latest.as_deref(), Some("19.0.0"));
+    assert_eq!(outdated["react"].wanted.as_deref(), Some("18.3.1"));
+    assert_eq!(outdated["typescript"].current.as_deref(), Some("5.3.3"));
+    assert!(parse_npm_outdated("").is_empty());
+}
+
+#[test]
+fn test_inspect_package_json_tool() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("package.json"), PACKAGE_JSON).unwrap();
+
+    let result =
+        build_files::execute_inspect_package_json(&json!({ "path": dir.path().to_str().unwrap() }))
+            .unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["name"], "web");
+    assert_eq!(output["version"], "1.0.0");
+    assert_eq!(output["h"], "name|range|kind");
+    let deps = common::helpers::parse_compact_rows(output["deps"].as_str().unwrap());
+    assert_eq!(deps.len(), 4);
+    assert_eq!(deps[3], ["vite", ">=5", "peerDependencies"]);
+    let scripts = common::helpers::parse_compact_rows(output["scripts"].as_str().unwrap());
+    assert_eq!(scripts[0], ["build", "tsc && vite build"]);
+}
+
+#[test]
+fn test_inspect_package_json_requires_manifest() {
+    let dir = TempDir::new().unwrap();
+    let result =
+        build_files::execute_inspect_package_json(&json!({ "path": dir.path().to_str().unwrap() }));
+    assert!(result.is_err());
+}
@@ -0,0 +1,131 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::json;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::{call_graph, unused};
+use treesitter_mcp::common::cancel::{CancellationToken, CANCELLED};
+use treesitter_mcp::extraction::types::{TypeExtractionOptions, TypeExtractor};
+
+fn write_rust_files(dir: &TempDir, count: usize) {
+    for i in 0..count {
+        fs::write(
+            dir.path().join(format!("module_{i:04}.rs")),
+            format!("use std::fmt;\n\npub struct Type{i};\n\npub fn caller_{i}() {{\n    helper();\n}}\n"),
+        )
+        .unwrap();
+    }
+    fs::write(dir.path().join("helper.rs"), "pub fn helper() {}\n").unwrap();
+}
+
+fn cancelled(token: CancellationToken) -> CancellationToken {
+    token.cancel();
+    token
+}
+
+#[test]
+fn test_token_is_checked_every_interval() {
+    let token = cancelled(CancellationToken::with_check_interval(3));
+
+    assert!(token.check(1).is_ok());
+    assert!(token.check(2).is_ok());
+    let err = token.check(3).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Interrupted);
+    assert_eq!(err.to_string(), CANCELLED);
+
+    // Clones share the flag.
+    let token = CancellationToken::new();
+    let clone = token.clone();
+    clone.cancel();
+    assert!(token.is_cancelled());
+}
+
+#[test]
+fn test_streaming_type_extraction_stops_mid_walk() {
+    let dir = TempDir::new().unwrap();
+    write_rust_files(&dir, 10);
+
+    let cancel = CancellationToken::with_check_interval(1);
+    let options = TypeExtractionOptions {
+        cancel: cancel.clone(),
+        ..Default::default()
+    };
+    let mut extractor = TypeExtractor::new(dir.path(), options);
+    assert!(extractor.next().unwrap().is_ok());
+    assert!(extractor.next().unwrap().is_ok());
+
+    cancel.cancel();
+    let err = extractor.next().unwrap().unwrap_err();
+    assert_eq!(err.to_string(), CANCELLED);
+    assert!(extractor.next().is_none());
+}
+
+#[test]
+fn test_parallel_type_extraction_cancelled_from_another_thread() {
+    let dir = TempDir::new().unwrap();
+    write_rust_files(&dir, 400);
+
+    let cancel = CancellationToken::new();
+    let canceller = {
+        let cancel = cancel.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(5));
+            cancel.cancel();
+        })
+    };
+    let options = TypeExtractionOptions {
+        cancel: cancel.clone(),
+        ..Default::default()
+    };
+    let result = TypeExtractor::new(dir.path(), options).collect_result();
+    canceller.join().unwrap();
+
+    // Either the scan finished first or it stopped without a partial result.
+    match result {
+        Ok(result) => assert_eq!(result.types.len(), 400),
+        Err(err) => assert_eq!(err.to_string(), CANCELLED),
+    }
+
+    let options = TypeExtractionOptions {
+        cancel,
+        ..Default::default()
+    };
+    let err = TypeExtractor::new(dir.path(), options)
+        .collect_result()
+        .unwrap_err();
+    assert_eq!(err.to_string(), CANCELLED);
+}
+
+#[test]
+fn test_cancelled_unused_imports_and_call_graph_return_errors() {
+    let dir = TempDir::new().unwrap();
+    write_rust_files(&dir, 5);
+    let cancel = cancelled(CancellationToken::new());
+
+    let err = unused::execute_find_unused_imports_with_cancel(
+        &json!({"path": dir.path().to_str().unwrap()}),
+        &cancel,
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Interrupted);
+    assert_eq!(err.to_string(), CANCELLED);
+
+    let err = call_graph::execute_with_cancel(
+        &json!({
+            "file_path": dir.path().join("helper.rs").to_str().unwrap(),
+            "symbol_name": "helper",
+            "direction": "callers",
+        }),
+        &cancel,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), CANCELLED);
+
+    // Without cancellation the same calls succeed.
+    assert!(unused::execute_find_unused_imports(&json!({
+        "path": dir.path().to_str().unwrap()
+    }))
+    .is_ok());
+}
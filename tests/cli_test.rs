@@ -0,0 +1,147 @@
+mod common;
+
+use std::fs;
+use std::process::{Command, Output};
+
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+const LIB_FILE: &str = "tests/fixtures/rust_project/src/lib.rs";
+
+fn cli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_treesitter-mcp"))
+        .arg("--cli")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn cli_json(args: &[&str]) -> Value {
+    let output = cli(args);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn tool_json(result: std::io::Result<treesitter_mcp::mcp_types::CallToolResult>) -> Value {
+    serde_json::from_str(&common::get_result_text(&result.unwrap())).unwrap()
+}
+
+#[test]
+fn test_cli_shape_matches_view_code_signatures() {
+    let output = cli_json(&["shape", LIB_FILE]);
+    let expected = tool_json(treesitter_mcp::analysis::view_code::execute(&json!({
+        "file_path": LIB_FILE,
+        "detail": "signatures",
+    })));
+
+    assert_eq!(output, expected);
+    assert!(output["h"].is_string());
+    assert!(output["f"].as_str().unwrap().contains("format_result"));
+}
+
+#[test]
+fn test_cli_pretty_prints_the_same_json() {
+    let compact = cli(&["complexity", LIB_FILE]);
+    let pretty = cli(&["--pretty", "complexity", LIB_FILE, "--threshold", "2"]);
+    assert!(compact.status.success() && pretty.status.success());
+
+    let compact_text = String::from_utf8(compact.stdout).unwrap();
+    let pretty_text = String::from_utf8(pretty.stdout).unwrap();
+    assert_eq!(compact_text.trim_end().lines().count(), 1);
+    assert!(pretty_text.lines().count() > 1);
+
+    let pretty_json: Value = serde_json::from_str(&pretty_text).unwrap();
+    assert_eq!(
+        pretty_json,
+        tool_json(treesitter_mcp::analysis::metrics::execute_complexity(
+            &json!({"path": LIB_FILE, "threshold": 2})
+        ))
+    );
+}
+
+#[test]
+fn test_cli_usages_and_diff_match_tools() {
+    let usages = cli_json(&["usages", "Calculator", "tests/fixtures/rust_project/src"]);
+    let expected = tool_json(treesitter_mcp::analysis::find_usages::execute(&json!({
+        "symbol": "Calculator",
+        "path": "tests/fixtures/rust_project/src",
+    })));
+    assert_eq!(usages, expected);
+
+    let dir = TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(dir.path())
+            .output()
+            .unwrap()
+    };
+    git(&["init"]);
+    git(&["config", "user.email", "test@test.com"]);
+    git(&["config", "user.name", "Test"]);
+    let file = dir.path().join("lib.rs");
+    fs::write(&file, "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-m", "initial"]);
+    fs::write(&file, "pub fn add(a: i64, b: i64) -> i64 {\n    a + b\n}\n").unwrap();
+
+    let file = file.to_str().unwrap();
+    let diff = cli_json(&["diff", file, "--compare", "HEAD"]);
+    let expected = tool_json(treesitter_mcp::analysis::diff::execute_parse_diff(
+        &json!({"file_path": file, "compare_to": "HEAD"}),
+    ));
+    assert_eq!(diff, expected);
+    assert!(diff.to_string().contains("add"));
+}
+
+#[test]
+fn test_cli_rejects_invalid_arguments() {
+    let unknown = cli(&["bogus"]);
+    assert_eq!(unknown.status.code(), Some(2));
+
+    let missing = cli(&["usages", "Calculator"]);
+    assert_eq!(missing.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("<path>"));
+
+    let failed = cli(&["shape", "/nonexistent/lib.rs"]);
+    assert_eq!(failed.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("/nonexistent/lib.rs"));
+}
+
+#[test]
+fn test_cli_uses_config_on_either_side_of_cli_flag() {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir_all(dir.path().join("generated")).unwrap();
+    fs::write(dir.path().join("lib.rs"), "pub struct Kept {}\n").unwrap();
+    fs::write(
+        dir.path().join("generated").join("out.rs"),
+        "pub struct Skipped {}\n",
+    )
+    .unwrap();
+    let config = dir.path().join("cli.toml");
+    fs::write(&config, "extra_ignored_dirs = [\"generated\"]\n").unwrap();
+    let (config, path) = (config.to_str().unwrap(), dir.path().to_str().unwrap());
+
+    for args in [
+        vec!["--config", config, "--cli", "types", path],
+        vec!["--cli", "types", path, "--config", config],
+    ] {
+        let output = Command::new(env!("CARGO_BIN_EXE_treesitter-mcp"))
+            .args(&args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let types: Value = serde_json::from_slice(&output.stdout).unwrap();
+        let rows = types["types"].as_str().unwrap();
+        assert!(rows.contains("Kept"));
+        assert!(!rows.contains("Skipped"), "{args:?}: {rows}");
+    }
+}
@@ -446,3 +446,80 @@ const config: Config = { enabled: true };
         "Unreferenced Config should stay at zero"
     );
 }
+
+fn write_workspace(dir: &TempDir) {
+    let root = dir.path();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\", \"tools/cli\"]\nexclude = [\"crates/skipped\"]\n",
+    )
+    .unwrap();
+    for (member, name, source) in [
+        (
+            "crates/core",
+            "core",
+            "pub struct Engine;\n\npub fn start() {}\n",
+        ),
+        ("crates/skipped", "skipped", "pub fn hidden() {}\n"),
+        ("tools/cli", "my-cli", "fn main() {}\n"),
+    ] {
+        let member_dir = root.join(member);
+        fs::create_dir_all(member_dir.join("src")).unwrap();
+        fs::write(
+            member_dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+        )
+        .unwrap();
+        fs::write(member_dir.join("src/lib.rs"), source).unwrap();
+    }
+}
+
+#[test]
+fn test_list_workspace_members_expands_globs_and_excludes() {
+    let dir = TempDir::new().unwrap();
+    write_workspace(&dir);
+
+    let members = treesitter_mcp::analysis::workspace::list_workspace_members(dir.path()).unwrap();
+    let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, ["core", "my-cli"]);
+    assert_eq!(members[0].path, dir.path().join("crates/core"));
+}
+
+#[test]
+fn test_code_map_workspace_mode_groups_by_member() {
+    let dir = setup_git_repo();
+    write_workspace(&dir);
+
+    let arguments = json!({
+        "path": dir.path().to_str().unwrap(),
+        "workspace_mode": true,
+        "max_tokens": 4000
+    });
+    let result = treesitter_mcp::analysis::code_map::execute(&arguments).unwrap();
+    let map: serde_json::Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    let members: Vec<&String> = map.as_object().unwrap().keys().collect();
+    assert_eq!(members, ["core", "my-cli"]);
+
+    let (path, file) = map["core"].as_object().unwrap().iter().next().unwrap();
+    assert!(path.ends_with("crates/core/src/lib.rs"));
+    let functions = common::helpers::compact_table_get_rows(file, "f");
+    assert_eq!(functions[0][0], "start");
+}
+
+#[test]
+fn test_code_map_workspace_mode_requires_workspace() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"solo\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    let arguments = json!({
+        "path": dir.path().to_str().unwrap(),
+        "workspace_mode": true
+    });
+    let err = treesitter_mcp::analysis::code_map::execute(&arguments).unwrap_err();
+    assert!(err.to_string().contains("No Cargo workspace members"));
+}
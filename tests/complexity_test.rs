@@ -0,0 +1,119 @@
+mod common;
+
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+
+const SOURCE: &str = r#"fn simple(a: i32) -> i32 {
+    a + 1
+}
+
+fn nested(a: i32, b: bool) -> i32 {
+    if a > 0 {
+        if b && a > 10 {
+            1
+        } else {
+            2
+        }
+    } else {
+        for i in 0..a {
+            if i == 3 {
+                return i;
+            }
+        }
+        0
+    }
+}
+
+fn five_arms(n: u8) -> &'static str {
+    match n {
+        0 => "zero",
+        1 => "one",
+        2 => "two",
+        3 => "three",
+        _ => "many",
+    }
+}
+"#;
+
+fn complexity(dir: &TempDir, threshold: Option<u32>) -> Value {
+    let file = dir.path().join("lib.rs");
+    fs::write(&file, SOURCE).unwrap();
+
+    let mut args = json!({"path": dir.path().to_str().unwrap()});
+    if let Some(threshold) = threshold {
+        args["threshold"] = json!(threshold);
+    }
+
+    let result = treesitter_mcp::analysis::metrics::execute_complexity(&args).unwrap();
+    serde_json::from_str(&common::get_result_text(&result)).unwrap()
+}
+
+/// `(symbol, line, complexity, over)` per function
+fn summarize(output: &Value) -> Vec<(String, String, String, String)> {
+    common::helpers::parse_compact_rows(output["fns"].as_str().unwrap_or(""))
+        .into_iter()
+        .map(|row| {
+            (
+                row[0].clone(),
+                row[2].clone(),
+                row[3].clone(),
+                row[4].clone(),
+            )
+        })
+        .collect()
+}
+
+fn function(
+    symbol: &str,
+    line: &str,
+    complexity: &str,
+    over: &str,
+) -> (String, String, String, String) {
+    (
+        symbol.to_string(),
+        line.to_string(),
+        complexity.to_string(),
+        over.to_string(),
+    )
+}
+
+#[test]
+fn reports_functions_most_complex_first() {
+    let dir = TempDir::new().unwrap();
+    let output = complexity(&dir, None);
+
+    assert_eq!(output["threshold"], 10);
+    assert_eq!(output["h"], "symbol|file|line|complexity|over");
+    // nested: three ifs, one for and one && on top of the base path.
+    // five_arms: each arm beyond the first adds one.
+    assert_eq!(
+        summarize(&output),
+        [
+            function("nested", "5", "6", ""),
+            function("five_arms", "22", "5", ""),
+            function("simple", "1", "1", ""),
+        ]
+    );
+}
+
+#[test]
+fn flags_functions_above_threshold() {
+    let dir = TempDir::new().unwrap();
+    let output = complexity(&dir, Some(5));
+
+    assert_eq!(
+        summarize(&output),
+        [
+            function("nested", "5", "6", "1"),
+            function("five_arms", "22", "5", ""),
+            function("simple", "1", "1", ""),
+        ]
+    );
+}
+
+#[test]
+fn rejects_missing_path() {
+    let result = treesitter_mcp::analysis::metrics::execute_complexity(&json!({}));
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
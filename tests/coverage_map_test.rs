@@ -0,0 +1,107 @@
+mod common;
+
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::coverage_map::{self, map_tests_to_production};
+
+const CALC_RS: &str = r#"pub struct Calculator {
+    value: i64,
+}
+
+impl Calculator {
+    pub fn reset(&mut self) {
+        self.value = 0;
+    }
+}
+
+pub fn add(a: i64, b: i64) -> i64 {
+    a + b
+}
+
+pub fn add_item(items: &mut Vec<i64>, item: i64) {
+    items.push(item);
+}
+
+pub fn parse_http_request(raw: &str) -> &str {
+    raw
+}
+"#;
+
+const CALC_TEST_RS: &str = r#"use calc::*;
+
+#[test]
+fn test_add() {}
+
+#[test]
+fn test_add_item_twice() {}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_Calculator_reset() {}
+
+#[test]
+fn test_unrelated_behaviour() {}
+
+fn helper() {}
+"#;
+
+const PARSER_TEST_GO: &str = r#"package calc
+
+import "testing"
+
+func TestParseHTTPRequest(t *testing.T) {}
+"#;
+
+fn project() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir_all(dir.path().join("src")).unwrap();
+    fs::create_dir_all(dir.path().join("tests")).unwrap();
+    fs::write(dir.path().join("src").join("calc.rs"), CALC_RS).unwrap();
+    fs::write(dir.path().join("tests").join("calc_test.rs"), CALC_TEST_RS).unwrap();
+    fs::write(dir.path().join("parser_test.go"), PARSER_TEST_GO).unwrap();
+    dir
+}
+
+#[test]
+fn test_maps_tests_by_name() {
+    let dir = project();
+    let mappings = map_tests_to_production(dir.path());
+
+    let targets = |test: &str| {
+        mappings
+            .iter()
+            .find(|mapping| mapping.test_function == test)
+            .unwrap_or_else(|| panic!("missing {test}"))
+            .probable_targets
+            .clone()
+    };
+
+    assert_eq!(targets("test_add"), ["add"]);
+    // `add` lies inside the longer `add_item` match.
+    assert_eq!(targets("test_add_item_twice"), ["add_item"]);
+    assert_eq!(targets("test_Calculator_reset"), ["Calculator::reset"]);
+    assert!(targets("test_unrelated_behaviour").is_empty());
+    // camelCase Go test against a snake_case Rust function
+    assert_eq!(targets("TestParseHTTPRequest"), ["parse_http_request"]);
+
+    // Helpers are not tests.
+    assert!(mappings
+        .iter()
+        .all(|mapping| mapping.test_function != "helper"));
+}
+
+#[test]
+fn test_map_test_coverage_tool() {
+    let dir = project();
+    let result = coverage_map::execute(&json!({ "path": dir.path().to_str().unwrap() })).unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["h"], "test_file|test_fn|line|targets");
+    let rows = common::helpers::parse_compact_rows(output["tests"].as_str().unwrap());
+    assert_eq!(rows.len(), 5);
+    let add = rows.iter().find(|row| row[1] == "test_add").unwrap();
+    assert!(add[0].ends_with("calc_test.rs"));
+    assert_eq!(add[2], "4");
+    assert_eq!(add[3], "add");
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use serde_json::json;
+use treesitter_mcp::analysis::css_analysis::{css_var_usages, extract_css_vars};
+
+#[test]
+fn test_theme_variables_are_marked_as_tailwind() {
+    let theme = common::fixture_path("css_vars", "styles/theme.css");
+    let report = css_var_usages(&theme).unwrap();
+
+    let definitions: Vec<(&str, &str, usize, bool)> = report
+        .definitions
+        .iter()
+        .map(|d| (d.name.as_str(), d.value.as_str(), d.line, d.theme))
+        .collect();
+    assert_eq!(
+        definitions,
+        vec![
+            ("--color-primary", "#3b82f6", 4, true),
+            ("--color-surface", "#ffffff", 5, true),
+            ("--radius-lg", "0.75rem", 6, true),
+            ("--spacing-card", "1.5rem", 10, false),
+        ]
+    );
+}
+
+#[test]
+fn test_usages_across_component_files() {
+    let dir = common::fixture_dir("css_vars");
+    let report = css_var_usages(&dir).unwrap();
+
+    let usages: Vec<(&str, &str, usize)> = report
+        .usages
+        .iter()
+        .map(|usage| {
+            let file = usage.file.rsplit('/').next().unwrap();
+            (usage.name.as_str(), file, usage.line)
+        })
+        .collect();
+    assert_eq!(
+        usages,
+        vec![
+            ("--color-primary", "button.css", 2),
+            ("--color-surface", "button.css", 3),
+            ("--color-accent", "button.css", 4),
+            ("--color-primary", "button.css", 4),
+            ("--spacing-card", "card.html", 5),
+            ("--color-surface", "card.html", 9),
+        ]
+    );
+}
+
+#[test]
+fn test_undefined_and_dead_variables_are_flagged() {
+    let dir = common::fixture_dir("css_vars");
+    let report = css_var_usages(&dir).unwrap();
+
+    assert_eq!(report.undefined, ["--color-accent"]);
+    assert_eq!(report.dead, ["--card-shadow", "--radius-lg"]);
+}
+
+#[test]
+fn test_comments_and_var_arguments_are_not_definitions() {
+    let source = "/* --old: 1px; */\n.a { --gap: 4px; margin: var(--gap); }\n";
+    let (definitions, usages) = extract_css_vars(source, "a.css").unwrap();
+
+    let names: Vec<&str> = definitions.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, ["--gap"]);
+    assert_eq!(definitions[0].line, 2);
+    assert_eq!(usages.len(), 1);
+    assert_eq!(usages[0].name, "--gap");
+}
+
+#[test]
+fn test_css_var_usages_tool_output() {
+    let dir = common::fixture_dir("css_vars");
+    let result = treesitter_mcp::analysis::css_analysis::execute_css_var_usages(&json!({
+        "path": dir.to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["dh"], "name|value|file|line|theme");
+    assert_eq!(output["uh"], "name|file|line");
+    let defs = common::helpers::parse_compact_rows(output["defs"].as_str().unwrap());
+    let primary = defs.iter().find(|row| row[0] == "--color-primary").unwrap();
+    assert_eq!(primary[1], "#3b82f6");
+    assert!(primary[2].ends_with("styles/theme.css"));
+    common::helpers::assert_path_is_relative(&primary[2]);
+    assert_eq!(primary[4], "y");
+    assert_eq!(output["undefined"], "--color-accent");
+    assert_eq!(output["dead"], "--card-shadow\n--radius-lg");
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use serde_json::{json, Value};
+use treesitter_mcp::analysis::{
+    annotation_inference, call_graph, code_map, find_usages, symbol_at_line, symbol_inventory,
+    type_map, view_code,
+};
+use treesitter_mcp::mcp_types::CallToolResult;
+
+type Tool = fn(&Value) -> Result<CallToolResult, String>;
+
+fn run_twice(name: &str, tool: Tool, arguments: Value) {
+    let first = common::get_result_text(&tool(&arguments).unwrap());
+    let second = common::get_result_text(&tool(&arguments).unwrap());
+    assert_eq!(first, second, "{name} output should be byte-identical");
+}
+
+#[test]
+fn test_analysis_tools_produce_identical_output_across_runs() {
+    let rust_dir = common::fixture_dir("rust");
+    let rust_file = common::fixture_path("rust", "src/calculator.rs");
+    let python_dir = common::fixture_dir("python");
+
+    let cases: Vec<(&str, Tool, Value)> = vec![
+        (
+            "code_map",
+            |args| code_map::execute(args).map_err(|e| e.to_string()),
+            json!({"path": rust_dir.to_str().unwrap(), "with_types": true, "count_usages": true}),
+        ),
+        (
+            "type_map",
+            |args| type_map::execute(args).map_err(|e| e.to_string()),
+            json!({"path": rust_dir.to_str().unwrap(), "max_tokens": 4000}),
+        ),
+        (
+            "find_usages",
+            |args| find_usages::execute(args).map_err(|e| e.to_string()),
+            json!({"symbol": "add", "path": rust_dir.to_str().unwrap(), "context_lines": 2}),
+        ),
+        (
+            "view_code",
+            |args| view_code::execute(args).map_err(|e| e.to_string()),
+            json!({"file_path": rust_file.to_str().unwrap()}),
+        ),
+        (
+            "call_graph",
+            |args| call_graph::execute(args).map_err(|e| e.to_string()),
+            json!({"file_path": rust_file.to_str().unwrap(), "symbol_name": "apply_operation", "direction": "both"}),
+        ),
+        (
+            "symbol_at_line",
+            |args| symbol_at_line::execute(args).map_err(|e| e.to_string()),
+            json!({"file_path": rust_file.to_str().unwrap(), "line": 14, "scope": "enclosing_function"}),
+        ),
+        (
+            "project_inventory",
+            |args| symbol_inventory::execute(args).map_err(|e| e.to_string()),
+            json!({"path": python_dir.to_str().unwrap(), "group_by": "kind"}),
+        ),
+        (
+            "infer_types",
+            |args| annotation_inference::execute(args).map_err(|e| e.to_string()),
+            json!({"path": python_dir.to_str().unwrap()}),
+        ),
+    ];
+
+    for (name, tool, arguments) in cases {
+        run_twice(name, tool, arguments);
+    }
+}
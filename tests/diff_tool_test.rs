@@ -443,6 +443,309 @@ fn main() {
         .any(|row| row[2] == "calc.rs" && row[4] == "low"));
 }
 
+// ============================================================================
+// parse_diff compare_file Tests (no git)
+// ============================================================================
+
+fn parse_diff_files(
+    dir: &TempDir,
+    (old_name, old): (&str, &str),
+    (new_name, new): (&str, &str),
+) -> Result<serde_json::Value, std::io::Error> {
+    let old_path = dir.path().join(old_name);
+    let new_path = dir.path().join(new_name);
+    fs::write(&old_path, old).unwrap();
+    fs::write(&new_path, new).unwrap();
+
+    let arguments = json!({
+        "file_path": new_path.to_str().unwrap(),
+        "compare_file": old_path.to_str().unwrap(),
+    });
+
+    let result = treesitter_mcp::analysis::diff::execute_parse_diff(&arguments)?;
+    Ok(serde_json::from_str(&common::get_result_text(&result)).unwrap())
+}
+
+#[test]
+fn test_parse_diff_compare_file_signature_changed() {
+    let dir = TempDir::new().unwrap();
+    let analysis = parse_diff_files(
+        &dir,
+        ("old.rs", "fn add(a: i32, b: i32) -> i32 { a + b }\n"),
+        (
+            "new.rs",
+            "fn add(a: i64, b: i64) -> i64 { a + b }\nfn sub(a: i64, b: i64) -> i64 { a - b }\n",
+        ),
+    )
+    .unwrap();
+
+    assert!(analysis["cmp"].as_str().unwrap().ends_with("old.rs"));
+    let changes = rows(&analysis, "changes");
+    assert!(changes
+        .iter()
+        .any(|row| row[1] == "add" && row[3].starts_with("sig_changed")));
+    assert!(changes
+        .iter()
+        .any(|row| row[1] == "sub" && row[3] == "added"));
+}
+
+#[test]
+fn test_parse_diff_compare_file_empty_old_file() {
+    let dir = TempDir::new().unwrap();
+    let analysis = parse_diff_files(
+        &dir,
+        ("old.py", ""),
+        ("new.py", "def greet(name):\n    return name\n"),
+    )
+    .unwrap();
+
+    let changes = rows(&analysis, "changes");
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0][1], "greet");
+    assert_eq!(changes[0][3], "added");
+}
+
+#[test]
+fn test_parse_diff_compare_file_identical_content() {
+    let dir = TempDir::new().unwrap();
+    let source = "fn add(a: i32, b: i32) -> i32 { a + b }\n";
+    let analysis = parse_diff_files(&dir, ("old.rs", source), ("new.rs", source)).unwrap();
+
+    assert_eq!(analysis["changes"].as_str().unwrap_or(""), "");
+}
+
+#[test]
+fn test_parse_diff_compare_file_different_languages() {
+    let dir = TempDir::new().unwrap();
+    let result = parse_diff_files(
+        &dir,
+        ("old.py", "def add(a, b):\n    return a + b\n"),
+        ("new.rs", "fn add(a: i32, b: i32) -> i32 { a + b }\n"),
+    );
+
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_parse_diff_rejects_compare_to_with_compare_file() {
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("lib.rs");
+    fs::write(&file_path, "fn test() {}").unwrap();
+
+    let arguments = json!({
+        "file_path": file_path.to_str().unwrap(),
+        "compare_to": "HEAD",
+        "compare_file": file_path.to_str().unwrap(),
+    });
+
+    let result = treesitter_mcp::analysis::diff::execute_parse_diff(&arguments);
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
+
+// ============================================================================
+// Struct field change Tests
+// ============================================================================
+
+const CONFIG_V1: &str = "pub struct Config {
+    pub host: String,
+    pub timeout: u64,
+}
+";
+
+#[test]
+fn test_parse_diff_struct_optional_field_added() {
+    let dir = TempDir::new().unwrap();
+    let analysis = parse_diff_files(
+        &dir,
+        ("old.rs", CONFIG_V1),
+        (
+            "new.rs",
+            "pub struct Config {\n    pub host: String,\n    pub timeout: u64,\n    pub retries: Option<u32>,\n}\n",
+        ),
+    )
+    .unwrap();
+
+    let changes = rows(&analysis, "changes");
+    assert!(changes.contains(&vec![
+        "s".to_string(),
+        "Config.retries".to_string(),
+        "4".to_string(),
+        "added".to_string(),
+    ]));
+    assert!(!changes.iter().any(|row| row[3] == "removed"));
+}
+
+#[test]
+fn test_parse_diff_struct_required_field_removed() {
+    let dir = TempDir::new().unwrap();
+    let analysis = parse_diff_files(
+        &dir,
+        ("old.rs", CONFIG_V1),
+        ("new.rs", "pub struct Config {\n    pub host: String,\n}\n"),
+    )
+    .unwrap();
+
+    let changes = rows(&analysis, "changes");
+    assert!(changes.contains(&vec![
+        "s".to_string(),
+        "Config.timeout".to_string(),
+        "3".to_string(),
+        "removed".to_string(),
+    ]));
+    // The struct itself still exists.
+    assert!(!changes
+        .iter()
+        .any(|row| row[1] == "Config" && row[3] == "removed"));
+}
+
+#[test]
+fn test_parse_diff_struct_fields_reordered() {
+    let dir = TempDir::new().unwrap();
+    let analysis = parse_diff_files(
+        &dir,
+        (
+            "old.go",
+            "package cfg\n\ntype Config struct {\n\tHost string\n\tTimeout int\n}\n",
+        ),
+        (
+            "new.go",
+            "package cfg\n\ntype Config struct {\n\tTimeout int\n\tHost string\n}\n",
+        ),
+    )
+    .unwrap();
+
+    let changes = rows(&analysis, "changes");
+    assert!(!changes.iter().any(|row| row[1].starts_with("Config.")));
+}
+
+#[test]
+fn test_affected_by_diff_public_field_removal_is_high_risk() {
+    let dir = setup_git_repo();
+    let main_content = "mod config;
+fn main() {
+    let cfg = config::load();
+    println!(\"{}\", cfg.timeout);
+}
+";
+    fs::write(dir.path().join("main.rs"), main_content).unwrap();
+    commit_file(&dir, "config.rs", CONFIG_V1);
+    commit_file(&dir, "main.rs", main_content);
+
+    fs::write(
+        dir.path().join("config.rs"),
+        "pub struct Config {\n    pub host: String,\n}\n",
+    )
+    .unwrap();
+
+    let arguments = json!({
+        "file_path": dir.path().join("config.rs").to_str().unwrap(),
+        "compare_to": "HEAD",
+        "scope": dir.path().to_str().unwrap()
+    });
+
+    let result = treesitter_mcp::analysis::diff::execute_affected_by_diff(&arguments).unwrap();
+    let affected: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    let rows = rows(&affected, "affected");
+    assert!(rows.iter().any(|row| row[0] == "Config.timeout"
+        && row[1] == "removed"
+        && row[2].contains("main.rs")
+        && row[4] == "high"));
+}
+
+// ============================================================================
+// batch_diff Tests
+// ============================================================================
+
+#[test]
+fn test_batch_diff_mixed_outcomes() {
+    let dir = setup_git_repo();
+    commit_file(
+        &dir,
+        "math.rs",
+        "pub fn add(a: i32, b: i32) -> i32 { a + b }\n",
+    );
+    commit_file(&dir, "util.py", "def greet(name):\n    return name\n");
+
+    // Signature change, body-only change, and a file git has never seen.
+    fs::write(
+        dir.path().join("math.rs"),
+        "pub fn add(a: i64, b: i64) -> i64 { a + b }\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("util.py"),
+        "def greet(name):\n    return name.upper()\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("new.rs"), "fn fresh() {}\n").unwrap();
+
+    let paths: Vec<String> = ["math.rs", "util.py", "new.rs"]
+        .iter()
+        .map(|name| dir.path().join(name).to_string_lossy().to_string())
+        .collect();
+    let arguments = json!({"file_paths": paths, "compare_to": "HEAD"});
+
+    let result = treesitter_mcp::analysis::diff::execute_batch_diff(&arguments).unwrap();
+    let batch: serde_json::Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(batch["h"], "file|type|name|line|change");
+    let changes = rows(&batch, "changes");
+    assert!(changes.iter().any(|row| row[0].ends_with("math.rs")
+        && row[2] == "add"
+        && row[4].starts_with("sig_changed")));
+    assert!(changes
+        .iter()
+        .any(|row| row[0].ends_with("util.py") && row[2] == "greet" && row[4] == "body_changed"));
+
+    assert_eq!(batch["summary"]["modified"], 2);
+    assert_eq!(batch["summary"]["added"], 0);
+    assert_eq!(batch["summary"]["has_breaking_changes"], true);
+
+    assert_eq!(batch["eh"], "file|error");
+    let errors = rows(&batch, "errors");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0][0].ends_with("new.rs"));
+}
+
+#[test]
+fn test_batch_diff_without_breaking_changes() {
+    let dir = setup_git_repo();
+    commit_file(&dir, "a.rs", "fn one() -> i32 { 1 }\n");
+    commit_file(&dir, "b.rs", "fn two() -> i32 { 2 }\n");
+    commit_file(&dir, "c.rs", "fn three() -> i32 { 3 }\n");
+
+    fs::write(
+        dir.path().join("a.rs"),
+        "fn one() -> i32 { 1 }\nfn extra() {}\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("b.rs"), "fn two() -> i32 { 1 + 1 }\n").unwrap();
+
+    let paths: Vec<String> = ["a.rs", "b.rs", "c.rs"]
+        .iter()
+        .map(|name| dir.path().join(name).to_string_lossy().to_string())
+        .collect();
+    let arguments = json!({"file_paths": paths});
+
+    let result = treesitter_mcp::analysis::diff::execute_batch_diff(&arguments).unwrap();
+    let batch: serde_json::Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(batch["cmp"], "HEAD");
+    assert_eq!(rows(&batch, "changes").len(), 2);
+    assert_eq!(batch["summary"]["added"], 1);
+    assert_eq!(batch["summary"]["modified"], 1);
+    assert_eq!(batch["summary"]["has_breaking_changes"], false);
+    assert!(batch.get("errors").is_none());
+}
+
+#[test]
+fn test_batch_diff_requires_file_paths() {
+    let result = treesitter_mcp::analysis::diff::execute_batch_diff(&json!({"file_paths": "a.rs"}));
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
+
 // ============================================================================
 // Error Handling Tests
 // ============================================================================
@@ -492,3 +795,68 @@ fn test_parse_diff_invalid_revision() {
     let result = treesitter_mcp::analysis::diff::execute_parse_diff(&arguments);
     assert!(result.is_err());
 }
+
+// ============================================================================
+// view_code compare_to Tests
+// ============================================================================
+
+#[test]
+fn test_view_code_compare_to_marks_changed_functions() {
+    let dir = setup_git_repo();
+    commit_file(
+        &dir,
+        "lib.rs",
+        "fn same() {}\nfn body() -> i32 { 1 }\nfn sig(a: i32) {}\nfn gone() {}\n",
+    );
+    fs::write(
+        dir.path().join("lib.rs"),
+        "fn same() {}\nfn body() -> i32 { 2 }\nfn sig(a: i64) {}\nfn fresh() {}\n",
+    )
+    .unwrap();
+
+    let file_path = dir.path().join("lib.rs");
+    let arguments = json!({
+        "file_path": file_path.to_str().unwrap(),
+        "detail": "signatures",
+        "include_deps": false,
+        "compare_to": "HEAD"
+    });
+    let result = treesitter_mcp::analysis::view_code::execute(&arguments).unwrap();
+    let view: serde_json::Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(view["cmp"], "HEAD");
+    assert_eq!(view["fh"], "name|line|sig|chg");
+    let markers: Vec<(String, String)> = rows(&view, "f")
+        .into_iter()
+        .map(|row| (row[0].clone(), row[3].clone()))
+        .collect();
+    assert_eq!(
+        markers,
+        [
+            ("same".to_string(), String::new()),
+            ("body".to_string(), "!".to_string()),
+            ("sig".to_string(), "~".to_string()),
+            ("fresh".to_string(), "+".to_string()),
+            ("gone".to_string(), "-".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_view_code_without_compare_to_has_no_markers() {
+    let dir = setup_git_repo();
+    commit_file(&dir, "lib.rs", "fn add() {}\n");
+
+    let file_path = dir.path().join("lib.rs");
+    let arguments = json!({
+        "file_path": file_path.to_str().unwrap(),
+        "detail": "signatures",
+        "include_deps": false
+    });
+    let result = treesitter_mcp::analysis::view_code::execute(&arguments).unwrap();
+    let view: serde_json::Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert!(view.get("fh").is_none());
+    assert!(view.get("cmp").is_none());
+    assert_eq!(rows(&view, "f")[0].len(), 3);
+}
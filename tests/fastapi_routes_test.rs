@@ -0,0 +1,117 @@
+mod common;
+
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+use treesitter_mcp::analysis::fastapi::{self, extract_fastapi_routes, RouteParameter};
+
+const SERVICE: &str = r#"from typing import Optional
+
+from fastapi import APIRouter, FastAPI
+
+app = FastAPI()
+router = APIRouter(prefix="/users")
+
+
+@app.get("/items/{item_id}")
+async def read_item(item_id: int, q: Optional[str] = None):
+    return {"item_id": item_id, "q": q}
+
+
+@router.post("/", status_code=201)
+def create_user(user: UserIn, db=Depends(get_db)):
+    return user
+
+
+@router.delete(path="/{user_id}")
+def delete_user(user_id: int):
+    return None
+
+
+@app.api_route("/legacy")
+@functools.lru_cache
+def not_a_route():
+    pass
+
+
+@app.put("/items/{item_id}")
+@app.patch("/items/{item_id}")
+def update_item(item_id: int, item: Item):
+    return item
+"#;
+
+fn param(name: &str, type_annotation: Option<&str>) -> RouteParameter {
+    RouteParameter {
+        name: name.to_string(),
+        type_annotation: type_annotation.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_extracts_decorated_path_operations() {
+    let routes = extract_fastapi_routes(SERVICE, Path::new("main.py"));
+    let summary: Vec<(&str, &str, &str, &str)> = routes
+        .iter()
+        .map(|route| {
+            (
+                route.method.as_str(),
+                route.path.as_str(),
+                route.function.as_str(),
+                route.router.as_str(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        [
+            ("GET", "/items/{item_id}", "read_item", "app"),
+            ("POST", "/", "create_user", "router"),
+            ("DELETE", "/{user_id}", "delete_user", "router"),
+            ("PUT", "/items/{item_id}", "update_item", "app"),
+            ("PATCH", "/items/{item_id}", "update_item", "app"),
+        ]
+    );
+    assert_eq!(routes[0].line, 9);
+}
+
+#[test]
+fn test_extracts_handler_parameter_types() {
+    let routes = extract_fastapi_routes(SERVICE, Path::new("main.py"));
+
+    assert_eq!(
+        routes[0].parameters,
+        [
+            param("item_id", Some("int")),
+            param("q", Some("Optional[str]"))
+        ]
+    );
+    assert_eq!(
+        routes[1].parameters,
+        [param("user", Some("UserIn")), param("db", None)]
+    );
+}
+
+#[test]
+fn test_list_api_routes_scans_python_files() {
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join("app")).unwrap();
+    fs::write(dir.path().join("app").join("main.py"), SERVICE).unwrap();
+    fs::write(
+        dir.path().join("app").join("models.py"),
+        "class Item:\n    pass\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("notes.txt"), "@app.get(\"/nope\")\n").unwrap();
+
+    let result = fastapi::execute(&json!({ "path": dir.path().to_str().unwrap() })).unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["h"], "method|path|function|file|line|params");
+    let rows = common::helpers::parse_compact_rows(output["routes"].as_str().unwrap());
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[0][0], "GET");
+    assert!(rows[0][3].ends_with("main.py"));
+    assert_eq!(rows[0][5], "item_id: int, q: Optional[str]");
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use serde_json::json;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::comments::{extract_comment_markers, CommentMarker};
+use treesitter_mcp::parser::Language;
+
+fn summary(markers: &[CommentMarker]) -> Vec<(&str, usize, Option<&str>, &str)> {
+    markers
+        .iter()
+        .map(|m| {
+            (
+                m.kind.as_str(),
+                m.line,
+                m.author.as_deref(),
+                m.text.as_str(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_rust_inline_todo() {
+    let source = r#"fn parse(input: &str) -> usize {
+    let label = "TODO: not a comment";
+    input.len() // TODO: fix this
+}
+
+/// FIXME(alice): document the error cases
+fn todo_list() {}
+"#;
+    let markers = extract_comment_markers(source, Language::Rust, "src/lib.rs").unwrap();
+
+    assert_eq!(
+        summary(&markers),
+        vec![
+            ("TODO", 3, None, "fix this"),
+            ("FIXME", 6, Some("alice"), "document the error cases"),
+        ]
+    );
+    assert_eq!(markers[0].file, "src/lib.rs");
+}
+
+#[test]
+fn test_python_fixme() {
+    let source = "def total(items):\n    # FIXME off by one when items is empty (bob)\n    return sum(items) + 1\n";
+    let markers = extract_comment_markers(source, Language::Python, "app.py").unwrap();
+
+    assert_eq!(
+        summary(&markers),
+        vec![("FIXME", 2, Some("bob"), "off by one when items is empty")]
+    );
+}
+
+#[test]
+fn test_javascript_block_hack() {
+    let source = "const delay = 10; /* HACK */\nconst TODO_COUNT = 0;\nrun(task(x)); // XXX: retry run(task)\n";
+    let markers = extract_comment_markers(source, Language::JavaScript, "index.js").unwrap();
+
+    assert_eq!(
+        summary(&markers),
+        vec![("HACK", 1, None, ""), ("XXX", 3, None, "retry run(task)")]
+    );
+}
+
+#[test]
+fn test_java_block_comment_with_multiple_markers() {
+    let source = r#"public class Cache {
+    /*
+     * TODO(carol): evict by size
+     * OPTIMIZE: avoid copying keys
+     * BUG - stale reads after reload
+     */
+    private int size;
+}
+"#;
+    let markers = extract_comment_markers(source, Language::Java, "Cache.java").unwrap();
+
+    assert_eq!(
+        summary(&markers),
+        vec![
+            ("TODO", 3, Some("carol"), "evict by size"),
+            ("OPTIMIZE", 4, None, "avoid copying keys"),
+            ("BUG", 5, None, "stale reads after reload"),
+        ]
+    );
+}
+
+#[test]
+fn test_find_comments_tool_sorts_by_file_and_line() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("b.py"),
+        "x = 1  # TODO: rename x\n# HACK: global state\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("a.rs"),
+        "// FIXME(dave): unsafe cast\nfn f() {}\n",
+    )
+    .unwrap();
+
+    let result = treesitter_mcp::analysis::comments::execute_find_comments(&json!({
+        "path": dir.path().to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["h"], "kind|file|line|author|text");
+    let rows = common::helpers::parse_compact_rows(output["markers"].as_str().unwrap());
+    let rows: Vec<(String, String, String, String)> = rows
+        .into_iter()
+        .map(|row| {
+            let file = row[1].rsplit('/').next().unwrap().to_string();
+            (row[0].clone(), file, row[2].clone(), row[3].clone())
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            ("FIXME".into(), "a.rs".into(), "1".into(), "dave".into()),
+            ("TODO".into(), "b.py".into(), "1".into(), "".into()),
+            ("HACK".into(), "b.py".into(), "2".into(), "".into()),
+        ]
+    );
+}
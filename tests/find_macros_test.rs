@@ -0,0 +1,143 @@
+mod common;
+
+use serde_json::json;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::macros::{extract_rust_macros, MacroCallSite};
+
+const SOURCE: &str = r#"#[macro_export]
+macro_rules! square {
+    ($x:expr) => {
+        $x * $x
+    };
+    ($x:expr, $y:expr) => {
+        ($x * $x, $y * $y)
+    };
+}
+
+macro_rules! noop {
+    () => {};
+}
+
+#[derive(Debug, Clone,
+         PartialEq)]
+pub struct Point {
+    x: i32,
+}
+
+fn main() {
+    let values = vec![1, 2, 3];
+    println!("{} {}", square!(2), values.len());
+    std::println!("done");
+}
+"#;
+
+fn call<'a>(calls: &'a [MacroCallSite], name: &str) -> &'a MacroCallSite {
+    calls
+        .iter()
+        .find(|call| call.name == name)
+        .unwrap_or_else(|| panic!("no call of {name} in {calls:?}"))
+}
+
+#[test]
+fn test_macro_rules_definitions_count_arms() {
+    let macros = extract_rust_macros(SOURCE, "src/lib.rs").unwrap();
+
+    let definitions: Vec<(&str, usize, usize)> = macros
+        .definitions
+        .iter()
+        .map(|d| (d.name.as_str(), d.line, d.pattern_count))
+        .collect();
+    assert_eq!(definitions, vec![("square", 2, 2), ("noop", 11, 1)]);
+}
+
+#[test]
+fn test_derive_attributes_are_call_sites() {
+    let macros = extract_rust_macros(SOURCE, "src/lib.rs").unwrap();
+
+    let derive = call(&macros.call_sites, "derive");
+    assert_eq!(derive.line, 15);
+    assert_eq!(derive.args_text, "Debug, Clone, PartialEq");
+    // Plain attributes such as #[macro_export] are not macro calls
+    assert!(macros.call_sites.iter().all(|c| c.name != "macro_export"));
+}
+
+#[test]
+fn test_println_and_vec_invocations_have_lines_and_args() {
+    let macros = extract_rust_macros(SOURCE, "src/lib.rs").unwrap();
+
+    let vec_call = call(&macros.call_sites, "vec");
+    assert_eq!(vec_call.line, 22);
+    assert_eq!(vec_call.args_text, "1, 2, 3");
+
+    let println_call = call(&macros.call_sites, "println");
+    assert_eq!(println_call.line, 23);
+    assert_eq!(
+        println_call.args_text,
+        "\"{} {}\", square!(2), values.len()"
+    );
+
+    let scoped = call(&macros.call_sites, "std::println");
+    assert_eq!(scoped.line, 24);
+
+    // square! inside println!'s arguments is an unparsed token tree
+    assert!(macros.call_sites.iter().all(|c| c.name != "square"));
+    assert_eq!(macros.call_sites.len(), 4);
+}
+
+#[test]
+fn test_long_arguments_are_shortened() {
+    let source = format!("fn f() {{ let v = vec![{}]; }}\n", "1, ".repeat(100));
+    let macros = extract_rust_macros(&source, "src/lib.rs").unwrap();
+
+    let args = &call(&macros.call_sites, "vec").args_text;
+    assert_eq!(args.chars().count(), 123);
+    assert!(args.ends_with("..."));
+}
+
+#[test]
+fn test_find_macros_tool_scans_directory() {
+    let dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/lib.rs"), SOURCE).unwrap();
+    std::fs::write(
+        dir.path().join("src/other.rs"),
+        "fn g() { assert_eq!(1, 1); }\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("notes.py"), "print('not rust')\n").unwrap();
+
+    let result = treesitter_mcp::analysis::macros::execute(&json!({
+        "path": dir.path().to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["dh"], "name|file|line|arms");
+    assert_eq!(output["ch"], "name|file|line|args");
+
+    let defs = common::helpers::parse_compact_rows(output["defs"].as_str().unwrap());
+    assert_eq!(defs.len(), 2);
+    assert_eq!(defs[0][0], "square");
+    assert!(defs[0][1].ends_with("src/lib.rs"));
+    assert_eq!(defs[0][3], "2");
+
+    let calls = common::helpers::parse_compact_rows(output["calls"].as_str().unwrap());
+    let assert_eq_row = calls
+        .iter()
+        .find(|row| row[0] == "assert_eq")
+        .expect("assert_eq! call in other.rs");
+    assert!(assert_eq_row[1].ends_with("src/other.rs"));
+    assert_eq!(assert_eq_row[2], "1");
+    assert_eq!(assert_eq_row[3], "1, 1");
+}
+
+#[test]
+fn test_find_macros_rejects_non_rust_file() {
+    let file = common::fixture_path("python", "calculator.py");
+    let err = treesitter_mcp::analysis::macros::execute(&json!({
+        "path": file.to_str().unwrap()
+    }))
+    .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
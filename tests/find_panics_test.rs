@@ -0,0 +1,134 @@
+mod common;
+
+use serde_json::json;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::safety::{extract_panic_sites, PanicSite};
+
+const SOURCE: &str = r#"use std::collections::HashMap;
+
+fn load(path: &str, env: &HashMap<String, String>) -> u16 {
+    let text = std::fs::read_to_string(path).unwrap();
+    let port = env.get("PORT").unwrap().parse::<u16>().unwrap();
+    let fallback = env.get("FALLBACK").unwrap_or(&text);
+    let config = parse(&text)
+        .expect("config must be valid");
+    port + config.len() as u16 + fallback.len() as u16
+}
+
+fn pending() -> u32 {
+    todo!("implement this")
+}
+
+fn check(kind: u8) {
+    match kind {
+        0 => std::unreachable!(),
+        1 => panic!("bad kind {}", kind),
+        _ => unimplemented!(),
+    }
+}
+"#;
+
+fn summary(sites: &[PanicSite]) -> Vec<(&str, usize, &str)> {
+    sites
+        .iter()
+        .map(|site| (site.kind.as_str(), site.line, site.expression.as_str()))
+        .collect()
+}
+
+#[test]
+fn test_chained_unwraps_on_option_and_result() {
+    let sites = extract_panic_sites(SOURCE, "src/config.rs").unwrap();
+    let unwraps: Vec<_> = summary(&sites)
+        .into_iter()
+        .filter(|(kind, _, _)| *kind == "unwrap")
+        .collect();
+
+    assert_eq!(
+        unwraps,
+        vec![
+            ("unwrap", 4, "std::fs::read_to_string(path)"),
+            ("unwrap", 5, "env.get(\"PORT\")"),
+            ("unwrap", 5, "env.get(\"PORT\").unwrap().parse::<u16>()"),
+        ]
+    );
+    // unwrap_or cannot panic
+    assert!(sites.iter().all(|site| site.line != 6));
+    assert_eq!(sites[0].file, "src/config.rs");
+}
+
+#[test]
+fn test_expect_reports_its_own_line() {
+    let sites = extract_panic_sites(SOURCE, "src/config.rs").unwrap();
+    let expect = sites.iter().find(|site| site.kind == "expect").unwrap();
+
+    assert_eq!(expect.line, 8);
+    assert_eq!(expect.expression, "parse(&text)");
+}
+
+#[test]
+fn test_panicking_macros_report_their_arguments() {
+    let sites = extract_panic_sites(SOURCE, "src/config.rs").unwrap();
+    let macros: Vec<_> = summary(&sites)
+        .into_iter()
+        .filter(|(kind, _, _)| !matches!(*kind, "unwrap" | "expect"))
+        .collect();
+
+    assert_eq!(
+        macros,
+        vec![
+            ("todo", 13, "\"implement this\""),
+            ("unreachable", 18, ""),
+            ("panic", 19, "\"bad kind {}\", kind"),
+            ("unimplemented", 20, ""),
+        ]
+    );
+}
+
+#[test]
+fn test_find_panics_tool_skips_whitelisted_files() {
+    let dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    std::fs::create_dir_all(dir.path().join("tests")).unwrap();
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "pub fn first(v: &[u8]) -> u8 {\n    *v.first().unwrap()\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("tests/it.rs"),
+        "#[test]\nfn t() { Some(1).unwrap(); }\n",
+    )
+    .unwrap();
+
+    let run = |whitelist: Option<&str>| {
+        let result = treesitter_mcp::analysis::safety::execute_find_panics(&json!({
+            "path": dir.path().to_str().unwrap(),
+            "whitelist_pattern": whitelist
+        }))
+        .unwrap();
+        let output: serde_json::Value =
+            serde_json::from_str(&common::get_result_text(&result)).unwrap();
+        assert_eq!(output["h"], "kind|file|line|expression");
+        common::helpers::parse_compact_rows(output["panics"].as_str().unwrap())
+    };
+
+    assert_eq!(run(None).len(), 2);
+
+    let rows = run(Some("tests/**"));
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0][0], "unwrap");
+    assert!(rows[0][1].ends_with("src/lib.rs"));
+    assert_eq!(rows[0][2], "2");
+    assert_eq!(rows[0][3], "v.first()");
+}
+
+#[test]
+fn test_invalid_whitelist_pattern_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    let err = treesitter_mcp::analysis::safety::execute_find_panics(&json!({
+        "path": dir.path().to_str().unwrap(),
+        "whitelist_pattern": "src/[unclosed"
+    }))
+    .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
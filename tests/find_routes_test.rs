@@ -0,0 +1,145 @@
+mod common;
+
+use std::path::Path;
+
+use serde_json::json;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::routes::{extract_routes, RouteInfo};
+use treesitter_mcp::parser::Language;
+
+fn summary(routes: &[RouteInfo]) -> Vec<(&str, &str, &str, usize)> {
+    routes
+        .iter()
+        .map(|r| {
+            (
+                r.method.as_str(),
+                r.path.as_str(),
+                r.handler_name.as_str(),
+                r.line,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_flask_blueprint_routes() {
+    let source = r#"from flask import Blueprint
+
+bp = Blueprint("users", __name__)
+
+
+@bp.route("/users")
+def list_users():
+    return []
+
+
+@bp.route("/users/<int:user_id>", methods=["GET", "DELETE"])
+@login_required
+def user_detail(user_id):
+    return {}
+
+
+@bp.get("/health")
+def health():
+    return "ok"
+"#;
+    let routes = extract_routes(source, Language::Python, Path::new("app/users.py"));
+
+    assert_eq!(
+        summary(&routes),
+        vec![
+            ("GET", "/users", "list_users", 6),
+            ("GET", "/users/<int:user_id>", "user_detail", 11),
+            ("DELETE", "/users/<int:user_id>", "user_detail", 11),
+            ("GET", "/health", "health", 17),
+        ]
+    );
+    assert_eq!(routes[0].file, "app/users.py");
+}
+
+#[test]
+fn test_fastapi_path_parameters() {
+    let source = r#"from fastapi import APIRouter
+
+router = APIRouter()
+
+
+@router.get("/items/{id}")
+async def read_item(id: int):
+    return {"id": id}
+
+
+@router.post("/items")
+def create_item(item: Item):
+    return item
+"#;
+    let routes = extract_routes(source, Language::Python, Path::new("main.py"));
+
+    assert_eq!(
+        summary(&routes),
+        vec![
+            ("GET", "/items/{id}", "read_item", 6),
+            ("POST", "/items", "create_item", 11),
+        ]
+    );
+}
+
+#[test]
+fn test_express_middleware_chains() {
+    let source = r#"const express = require("express");
+const router = express.Router();
+const cache = new Map();
+
+router.get("/users/:id", authenticate, validate(schema), getUser);
+router.post(`/users`, authenticate, async (req, res) => res.json(req.body));
+app.delete("/users/:id", function removeUser(req, res) {});
+router
+  .route("/orders")
+  .get(orders.list)
+  .post(authenticate, orders.create);
+cache.get("key");
+app.use("/api", router);
+"#;
+    let routes = extract_routes(source, Language::JavaScript, Path::new("src/app.js"));
+
+    assert_eq!(
+        summary(&routes),
+        vec![
+            ("GET", "/users/:id", "getUser", 5),
+            ("POST", "/users", "<anonymous>", 6),
+            ("DELETE", "/users/:id", "removeUser", 7),
+            ("GET", "/orders", "orders.list", 10),
+            ("POST", "/orders", "orders.create", 11),
+        ]
+    );
+}
+
+#[test]
+fn test_find_routes_tool_covers_python_and_typescript() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("app.py"),
+        "@app.route(\"/\")\ndef index():\n    pass\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("server.ts"),
+        "app.put('/items/:id', updateItem);\n",
+    )
+    .unwrap();
+
+    let result = treesitter_mcp::analysis::routes::execute_find_routes(&json!({
+        "path": dir.path().to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["h"], "method|path|handler|file|line");
+    let rows = common::helpers::parse_compact_rows(output["routes"].as_str().unwrap());
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0][..3], ["GET", "/", "index"]);
+    assert!(rows[0][3].ends_with("app.py"));
+    assert_eq!(rows[1][..3], ["PUT", "/items/:id", "updateItem"]);
+    assert!(rows[1][3].ends_with("server.ts"));
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use serde_json::json;
+use treesitter_mcp::analysis::tests_finder::{extract_tests, find_tests, TestInfo};
+use treesitter_mcp::parser::Language;
+
+fn summary(tests: &[TestInfo]) -> Vec<(&str, &str, &str, usize)> {
+    tests
+        .iter()
+        .map(|t| {
+            (
+                t.name.as_str(),
+                t.kind.as_str(),
+                t.test_framework.as_str(),
+                t.line,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_rust_unit_integration_and_benchmark() {
+    let tests = find_tests(&common::fixture_dir("rust_project")).unwrap();
+
+    assert_eq!(
+        summary(&tests),
+        vec![
+            ("bench_multiply", "benchmark", "criterion", 4),
+            ("test_format_result", "unit", "libtest", 24),
+            ("test_add_then_divide", "integration", "libtest", 4),
+            ("test_divide_by_zero_async", "integration", "tokio", 9),
+        ]
+    );
+    assert!(tests[0].file.ends_with("benches/calculator_bench.rs"));
+    assert!(tests[2].file.ends_with("calculator_flow.rs"));
+}
+
+#[test]
+fn test_python_unittest_and_pytest() {
+    let tests = find_tests(&common::fixture_dir("python_project")).unwrap();
+
+    assert_eq!(
+        summary(&tests),
+        vec![
+            ("CalculatorTestCase.test_add", "unit", "unittest", 14),
+            ("TestDivide.test_divide_by_zero", "unit", "pytest", 19),
+            ("test_add_is_commutative", "unit", "pytest", 24),
+        ]
+    );
+}
+
+#[test]
+fn test_javascript_describe_it_and_test() {
+    let tests = find_tests(&common::fixture_dir("javascript_project")).unwrap();
+
+    assert_eq!(
+        summary(&tests),
+        vec![
+            ("calculator > adds two numbers", "unit", "jest", 4),
+            (
+                "calculator > divide > returns null when dividing by zero",
+                "unit",
+                "jest",
+                9
+            ),
+            ("handles overflow", "unit", "jest", 15),
+        ]
+    );
+}
+
+#[test]
+fn test_java_annotated_methods() {
+    let tests = find_tests(&common::fixture_dir("java_project")).unwrap();
+
+    assert_eq!(
+        summary(&tests),
+        vec![
+            ("CalculatorTest.addsTwoNumbers", "unit", "junit5", 17),
+            ("CalculatorTest.subtractsItself", "unit", "junit5", 23),
+        ]
+    );
+}
+
+#[test]
+fn test_mocha_and_go_benchmarks() {
+    let mocha = "const { expect } = require('chai');\n\ndescribe('api', function () {\n  context('when empty', function () {\n    it('returns []', function () {});\n  });\n});\n";
+    let tests = extract_tests(mocha, Language::JavaScript, "test/api.spec.js").unwrap();
+    assert_eq!(
+        summary(&tests),
+        vec![("api > when empty > returns []", "unit", "mocha", 5)]
+    );
+
+    let go = "package calc\n\nimport \"testing\"\n\nfunc TestAdd(t *testing.T) {}\n\nfunc BenchmarkAdd(b *testing.B) {}\n\nfunc TestMain(m *testing.M) {}\n";
+    let tests = extract_tests(go, Language::Go, "calc_test.go").unwrap();
+    assert_eq!(
+        summary(&tests),
+        vec![
+            ("TestAdd", "unit", "go-test", 5),
+            ("BenchmarkAdd", "benchmark", "go-test", 7),
+        ]
+    );
+}
+
+#[test]
+fn test_find_tests_tool_output() {
+    let dir = common::fixture_dir("java_project");
+    let result = treesitter_mcp::analysis::tests_finder::execute_find_tests(&json!({
+        "path": dir.to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["h"], "name|kind|framework|file|line");
+    let rows = common::helpers::parse_compact_rows(output["tests"].as_str().unwrap());
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0][0], "CalculatorTest.addsTwoNumbers");
+    assert_eq!(rows[0][2], "junit5");
+    assert!(rows[0][3].ends_with("CalculatorTest.java"));
+    common::helpers::assert_path_is_relative(&rows[0][3]);
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::find_usages::{
+    execute_find_trait_implementations, find_trait_implementations,
+};
+
+fn write_project() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir_all(dir.path().join("src")).unwrap();
+    fs::write(
+        dir.path().join("src/shapes.rs"),
+        r#"use std::fmt;
+
+pub trait Describe {
+    fn describe(&self) -> String;
+}
+
+pub struct Point;
+pub struct Wrapper<T>(T);
+
+impl Describe for Point {
+    fn describe(&self) -> String {
+        "point".into()
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "point")
+    }
+}
+
+impl<T: Clone> Describe for Vec<T>
+where
+    T: fmt::Debug,
+{
+    fn describe(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl<T> std::fmt::Display for Wrapper<T> where T: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Point {
+    fn new() -> Self {
+        Point
+    }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("src/blanket.rs"),
+        r#"use crate::shapes::Describe;
+
+impl<T: std::fmt::Display> Describe for &T {
+    fn describe(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl From<u32> for crate::shapes::Point {
+    fn from(_: u32) -> Self {
+        crate::shapes::Point
+    }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("notes.md"),
+        "impl Describe for Markdown {}\n",
+    )
+    .unwrap();
+    dir
+}
+
+#[test]
+fn finds_plain_conditional_and_blanket_impls() {
+    let dir = write_project();
+
+    let impls = find_trait_implementations("Describe", dir.path()).unwrap();
+    let summary: Vec<(&str, usize, Vec<&str>, bool)> = impls
+        .iter()
+        .map(|i| {
+            (
+                i.impl_type.as_str(),
+                i.line,
+                i.bounds.iter().map(String::as_str).collect(),
+                i.blanket,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("&T", 3, vec!["T: std::fmt::Display"], true),
+            ("Point", 10, vec![], false),
+            ("Vec<T>", 22, vec!["T: Clone", "T: fmt::Debug"], false),
+        ]
+    );
+    assert!(impls[0].file.ends_with("blanket.rs"));
+    assert!(impls.iter().all(|i| i.trait_name == "Describe"));
+}
+
+#[test]
+fn matches_foreign_traits_by_last_path_segment() {
+    let dir = write_project();
+
+    for query in ["Display", "fmt::Display", "std::fmt::Display"] {
+        let impls = find_trait_implementations(query, dir.path()).unwrap();
+        let found: Vec<(&str, &str)> = impls
+            .iter()
+            .map(|i| (i.impl_type.as_str(), i.trait_name.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("Point", "fmt::Display"),
+                ("Wrapper<T>", "std::fmt::Display")
+            ],
+            "query {query}"
+        );
+    }
+
+    let from = find_trait_implementations("From", dir.path()).unwrap();
+    assert_eq!(from.len(), 1);
+    assert_eq!(from[0].impl_type, "crate::shapes::Point");
+    assert_eq!(from[0].trait_name, "From<u32>");
+}
+
+#[test]
+fn tool_reports_rows_with_relative_paths_and_kinds() {
+    let dir = write_project();
+    let file = dir.path().join("src/shapes.rs");
+
+    let result = execute_find_trait_implementations(&json!({
+        "trait_name": "Display",
+        "path": file.to_str().unwrap(),
+    }))
+    .unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["trait"], "Display");
+    assert_eq!(output["h"], "impl_type|trait|file|line|bounds|kind");
+    let rows = common::helpers::parse_compact_rows(output["impls"].as_str().unwrap());
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0][0], "Point");
+    assert_eq!(rows[0][5], "plain");
+    assert_eq!(rows[1][0], "Wrapper<T>");
+    assert_eq!(rows[1][4], "T: fmt::Display");
+    assert_eq!(rows[1][5], "conditional");
+    assert!(!rows[0][2].starts_with('/'), "path should be relative");
+}
+
+#[test]
+fn tool_rejects_missing_trait_name() {
+    let err = execute_find_trait_implementations(&json!({"path": "."})).unwrap_err();
+    assert!(err.to_string().contains("trait_name"));
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+
+const SOURCE: &str = r#"fn total(values: &[i32]) -> i32 {
+    values.iter().sum()
+}
+
+fn pick() -> fn(&[i32]) -> i32 {
+    total
+}
+
+fn main() {
+    let values = vec![1, 2, 3];
+    let sum = total(&values);
+    assert_eq!(pick()(&values), sum);
+}
+"#;
+
+fn find_total(dir: &TempDir, max_context_lines: Option<u32>) -> Vec<Vec<String>> {
+    let file = dir.path().join("lib.rs");
+    fs::write(&file, SOURCE).unwrap();
+
+    let mut args = json!({
+        "symbol": "total",
+        "path": file.to_str().unwrap(),
+        "context_lines": 1,
+    });
+    if let Some(max) = max_context_lines {
+        args["max_context_lines"] = json!(max);
+    }
+
+    let result = treesitter_mcp::analysis::find_usages::execute(&args).unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+    common::helpers::find_usages_rows(&output)
+}
+
+/// `(line, type, context line count)` per usage
+fn summarize(rows: &[Vec<String>]) -> Vec<(String, String, usize)> {
+    rows.iter()
+        .map(|row| (row[1].clone(), row[3].clone(), row[4].lines().count()))
+        .collect()
+}
+
+#[test]
+fn omitted_budget_keeps_all_context() {
+    let dir = TempDir::new().unwrap();
+    let rows = find_total(&dir, None);
+
+    assert_eq!(
+        summarize(&rows),
+        [
+            ("1".to_string(), "definition".to_string(), 2),
+            ("6".to_string(), "reference".to_string(), 3),
+            ("11".to_string(), "call".to_string(), 3),
+        ]
+    );
+}
+
+#[test]
+fn budget_goes_to_definitions_then_calls() {
+    let dir = TempDir::new().unwrap();
+
+    // Room for the definition (2 lines) and the call (3 lines) only.
+    let rows = find_total(&dir, Some(5));
+    assert_eq!(
+        summarize(&rows),
+        [
+            ("1".to_string(), "definition".to_string(), 2),
+            ("6".to_string(), "reference".to_string(), 0),
+            ("11".to_string(), "call".to_string(), 3),
+        ]
+    );
+
+    // Not enough for the call: it keeps its location but loses its code.
+    let rows = find_total(&dir, Some(4));
+    assert_eq!(
+        summarize(&rows),
+        [
+            ("1".to_string(), "definition".to_string(), 2),
+            ("6".to_string(), "reference".to_string(), 0),
+            ("11".to_string(), "call".to_string(), 0),
+        ]
+    );
+    assert_eq!(rows[2][2], "15", "column is preserved");
+}
+
+#[test]
+fn zero_budget_returns_every_usage_without_code() {
+    let dir = TempDir::new().unwrap();
+    let rows = find_total(&dir, Some(0));
+
+    assert_eq!(rows.len(), 3);
+    assert!(rows.iter().all(|row| row[4].is_empty()));
+}
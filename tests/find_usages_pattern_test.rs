@@ -0,0 +1,112 @@
+mod common;
+
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+
+const SOURCE: &str = r#"fn get_user() -> u32 {
+    1
+}
+
+fn get_order() -> u32 {
+    2
+}
+
+fn GetLegacy() -> u32 {
+    3
+}
+
+fn forget() -> u32 {
+    get_user() + get_order()
+}
+"#;
+
+fn find_pattern(dir: &TempDir, pattern: &str) -> Value {
+    let file = dir.path().join("api.rs");
+    fs::write(&file, SOURCE).unwrap();
+
+    let args = json!({
+        "pattern": pattern,
+        "path": file.to_str().unwrap(),
+        "context_lines": 0,
+    });
+
+    let result = treesitter_mcp::analysis::find_usages::execute(&args).unwrap();
+    serde_json::from_str(&common::get_result_text(&result)).unwrap()
+}
+
+/// `(line, type, matched symbol)` per usage
+fn summarize(output: &Value) -> Vec<(String, String, String)> {
+    common::helpers::find_usages_rows(output)
+        .iter()
+        .map(|row| (row[1].clone(), row[3].clone(), row[8].clone()))
+        .collect()
+}
+
+fn usage(line: &str, usage_type: &str, symbol: &str) -> (String, String, String) {
+    (line.to_string(), usage_type.to_string(), symbol.to_string())
+}
+
+#[test]
+fn pattern_matches_whole_names_by_default() {
+    let dir = TempDir::new().unwrap();
+    let output = find_pattern(&dir, "get_.*");
+
+    assert_eq!(output["sym"], "get_.*");
+    assert_eq!(
+        output["h"],
+        "file|line|col|type|context|scope|conf|owner|sym"
+    );
+    // `forget` contains `get` but is not a whole-name match.
+    assert_eq!(
+        summarize(&output),
+        [
+            usage("1", "definition", "get_user"),
+            usage("5", "definition", "get_order"),
+            usage("14", "call", "get_user"),
+            usage("14", "call", "get_order"),
+        ]
+    );
+}
+
+#[test]
+fn explicit_anchor_allows_partial_matches() {
+    let dir = TempDir::new().unwrap();
+    let output = find_pattern(&dir, "get$");
+
+    assert_eq!(summarize(&output), [usage("13", "definition", "forget")]);
+}
+
+#[test]
+fn inline_flag_makes_pattern_case_insensitive() {
+    let dir = TempDir::new().unwrap();
+
+    let exact = find_pattern(&dir, "get.*");
+    assert!(summarize(&exact)
+        .iter()
+        .all(|(_, _, symbol)| symbol != "GetLegacy"));
+
+    let insensitive = find_pattern(&dir, "(?i)get.*");
+    assert!(summarize(&insensitive).contains(&usage("9", "definition", "GetLegacy")));
+    assert!(summarize(&insensitive).contains(&usage("1", "definition", "get_user")));
+}
+
+#[test]
+fn pattern_matching_nothing_returns_no_rows() {
+    let dir = TempDir::new().unwrap();
+    let output = find_pattern(&dir, "set_.*");
+
+    assert_eq!(output["u"], "");
+    assert!(summarize(&output).is_empty());
+}
+
+#[test]
+fn invalid_pattern_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("api.rs");
+    fs::write(&file, SOURCE).unwrap();
+
+    let args = json!({"pattern": "get_(", "path": file.to_str().unwrap()});
+    let err = treesitter_mcp::analysis::find_usages::execute(&args).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
@@ -301,3 +301,87 @@ fn test_find_usages_respects_gitignore() {
 
     assert!(rows.iter().all(|row| row[0] == "visible.rs"));
 }
+
+// ============================================================================
+// CSS Class Name Tests
+// ============================================================================
+
+#[test]
+fn test_find_usages_css_class_in_html_and_stylesheets() {
+    let dir = TempDir::new().unwrap();
+
+    fs::write(
+        dir.path().join("styles.css"),
+        ".btn { padding: 0; }\n.btn-primary { color: blue; }\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("theme.scss"),
+        ".card .btn-primary { color: red; }\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("index.html"),
+        "<div>\n  <button class=\"btn btn-primary\">Go</button>\n  <a class=\"btn\">No</a>\n</div>\n",
+    )
+    .unwrap();
+
+    let arguments = json!({
+        "symbol": "btn-primary",
+        "path": dir.path().to_str().unwrap(),
+        "context_lines": 0
+    });
+
+    let result = treesitter_mcp::analysis::find_usages::execute(&arguments).unwrap();
+    let text = common::get_result_text(&result);
+    let usages: serde_json::Value = serde_json::from_str(&text).unwrap();
+    let rows = common::helpers::find_usages_rows(&usages);
+
+    let html: Vec<_> = rows.iter().filter(|r| r[3] == "html_usage").collect();
+    assert_eq!(html.len(), 1);
+    assert!(html[0][0].ends_with("index.html"));
+    assert_eq!(html[0][1], "2");
+    assert_eq!(html[0][2], "22");
+
+    let css: Vec<_> = rows.iter().filter(|r| r[3] == "css_definition").collect();
+    assert_eq!(css.len(), 2);
+    assert!(css
+        .iter()
+        .any(|r| r[0].ends_with("styles.css") && r[1] == "2"));
+    assert!(css
+        .iter()
+        .any(|r| r[0].ends_with("styles.css") && r[6] == "high"));
+    // SCSS is searched as plain CSS, so its definitions are best-effort.
+    assert!(css
+        .iter()
+        .any(|r| r[0].ends_with("theme.scss") && r[6] == "medium"));
+}
+
+#[test]
+fn test_find_usages_context_window_clamped_at_file_edges() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("edges.py"),
+        "def first():\r\n    return 1\r\n\r\nvalue = first()\r\n",
+    )
+    .unwrap();
+
+    let arguments = json!({
+        "symbol": "first",
+        "path": dir.path().to_str().unwrap(),
+        "context_lines": 20
+    });
+
+    let result = treesitter_mcp::analysis::find_usages::execute(&arguments).unwrap();
+    let text = common::get_result_text(&result);
+    let usages: serde_json::Value = serde_json::from_str(&text).unwrap();
+    let rows = common::helpers::find_usages_rows(&usages);
+
+    assert_eq!(rows.len(), 2);
+    for row in &rows {
+        assert_eq!(
+            row[4], "def first():\n    return 1\n\nvalue = first()",
+            "context should span the whole file without trailing blank lines"
+        );
+    }
+}
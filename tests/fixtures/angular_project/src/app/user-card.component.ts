@@ -0,0 +1,12 @@
+import { Component, Input } from '@angular/core';
+import { User } from './user.service';
+
+@Component({
+  selector: 'app-user-card',
+  templateUrl: './user-card.component.html',
+  styleUrls: ['./user-card.component.css'],
+})
+export class UserCardComponent {
+  @Input() user: User;
+  expanded: boolean = false;
+}
@@ -0,0 +1,15 @@
+import { Injectable } from '@angular/core';
+
+export interface User {
+  id: number;
+  name: string;
+}
+
+@Injectable({ providedIn: 'root' })
+export class UserService {
+  private users: User[] = [];
+
+  find(id: number): User | undefined {
+    return this.users.find((user) => user.id === id);
+  }
+}
@@ -0,0 +1,26 @@
+package com.example.calculator;
+
+import static org.junit.jupiter.api.Assertions.assertEquals;
+
+import org.junit.jupiter.api.BeforeEach;
+import org.junit.jupiter.api.Test;
+import org.junit.jupiter.params.ParameterizedTest;
+import org.junit.jupiter.params.provider.ValueSource;
+
+class CalculatorTest {
+
+    @BeforeEach
+    void setUp() {
+    }
+
+    @Test
+    void addsTwoNumbers() {
+        assertEquals(5, Calculator.add(2, 3));
+    }
+
+    @ParameterizedTest
+    @ValueSource(ints = {1, 2, 3})
+    void subtractsItself(int value) {
+        assertEquals(0, Calculator.subtract(value, value));
+    }
+}
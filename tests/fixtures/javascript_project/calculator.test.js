@@ -0,0 +1,15 @@
+const { add, divide } = require('./calculator');
+
+describe('calculator', () => {
+    it('adds two numbers', () => {
+        expect(add(2, 3)).toBe(5);
+    });
+
+    describe('divide', () => {
+        test('returns null when dividing by zero', () => {
+            expect(divide(1, 0)).toBeNull();
+        });
+    });
+});
+
+test.skip('handles overflow', () => {});
@@ -0,0 +1,9 @@
+import { Controller, Get } from '@nestjs/common';
+
+@Controller({ path: '/health' })
+export class HealthController {
+  @Get('/live')
+  live(): string {
+    return 'ok';
+  }
+}
@@ -0,0 +1,25 @@
+import { Body, Controller, Get, HttpCode, Param, Post } from '@nestjs/common';
+import { UsersService } from './users.service';
+
+@Controller('users')
+export class UsersController {
+  constructor(private readonly usersService: UsersService) {}
+
+  @Get()
+  findAll(): string[] {
+    return this.usersService.findAll();
+  }
+
+  @Get(':id')
+  findOne(@Param('id') id: string): string | undefined {
+    return this.usersService.findOne(id);
+  }
+
+  @Post()
+  @HttpCode(201)
+  create(@Body('name') name: string): string {
+    return this.usersService.create(name);
+  }
+
+  private audit(): void {}
+}
@@ -0,0 +1,15 @@
+import { Module } from '@nestjs/common';
+import { UsersController } from './users.controller';
+import { UsersService } from './users.service';
+
+export const USERS_REPOSITORY = 'USERS_REPOSITORY';
+
+@Module({
+  controllers: [UsersController],
+  providers: [
+    UsersService,
+    { provide: USERS_REPOSITORY, useValue: new Map() },
+  ],
+  exports: [UsersService],
+})
+export class UsersModule {}
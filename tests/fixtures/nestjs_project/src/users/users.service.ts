@@ -0,0 +1,19 @@
+import { Injectable } from '@nestjs/common';
+
+@Injectable()
+export class UsersService {
+  private readonly users = new Map<string, string>();
+
+  findAll(): string[] {
+    return [...this.users.values()];
+  }
+
+  findOne(id: string): string | undefined {
+    return this.users.get(id);
+  }
+
+  create(name: string): string {
+    this.users.set(name, name);
+    return name;
+  }
+}
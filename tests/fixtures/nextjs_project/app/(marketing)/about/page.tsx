@@ -0,0 +1,5 @@
+export const metadata = { title: "About" };
+
+export default function AboutPage() {
+  return <p>About us</p>;
+}
@@ -0,0 +1,3 @@
+export default function LoginModal() {
+  return <dialog open>Login</dialog>;
+}
@@ -0,0 +1,8 @@
+export async function GET(request: Request) {
+  return Response.json([]);
+}
+
+export async function POST(request: Request) {
+  const body = await request.json();
+  return Response.json(body, { status: 201 });
+}
@@ -0,0 +1,11 @@
+export async function generateMetadata({ params }: { params: { id: string } }) {
+  return { title: `Item ${params.id}` };
+}
+
+function formatId(id: string): string {
+  return id.toUpperCase();
+}
+
+export default function ItemPage({ params }: { params: { id: string } }) {
+  return <h1>{formatId(params.id)}</h1>;
+}
@@ -0,0 +1,3 @@
+export default function DashboardLoading() {
+  return <p>Loading...</p>;
+}
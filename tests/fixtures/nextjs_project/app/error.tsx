@@ -0,0 +1,5 @@
+"use client";
+
+export default function RootError({ reset }: { reset: () => void }) {
+  return <button onClick={reset}>Try again</button>;
+}
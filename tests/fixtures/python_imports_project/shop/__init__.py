@@ -0,0 +1 @@
+"""Shop package."""
@@ -0,0 +1,5 @@
+from . import orders
+
+
+def find(customer_id: int) -> "orders.Order":
+    return orders.Order(customer_id)
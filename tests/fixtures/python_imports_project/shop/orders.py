@@ -0,0 +1,15 @@
+from __future__ import annotations
+
+import logging
+
+from .billing import create_invoice
+
+logger = logging.getLogger(__name__)
+
+
+class Order:
+    def __init__(self, customer_id: int) -> None:
+        self.customer_id = customer_id
+
+    def checkout(self) -> None:
+        create_invoice(self)
@@ -0,0 +1,8 @@
+import json as _json
+from ..customers import find
+
+
+def charge(customer, amount):
+    from shop.orders import Order  # deferred: no cycle at import time
+
+    return _json.dumps({"customer": str(customer), "amount": str(amount), "find": find.__name__})
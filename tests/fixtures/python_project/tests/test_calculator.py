@@ -0,0 +1,29 @@
+"""Tests for the calculator module"""
+
+import unittest
+
+import pytest
+
+from calculator import add, divide
+
+
+class CalculatorTestCase(unittest.TestCase):
+    def setUp(self):
+        self.a = 6
+
+    def test_add(self):
+        self.assertEqual(add(self.a, 1), 7)
+
+
+class TestDivide:
+    def test_divide_by_zero(self):
+        assert divide(1, 0) is None
+
+
+@pytest.mark.parametrize("a,b", [(1, 2), (3, 4)])
+def test_add_is_commutative(a, b):
+    assert add(a, b) == add(b, a)
+
+
+def helper():
+    return 1
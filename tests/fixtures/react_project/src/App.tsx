@@ -0,0 +1,28 @@
+import { useState } from 'react';
+import { Button } from './Button';
+import { Icon } from './Icon';
+
+interface Item {
+  id: number;
+  label: string;
+}
+
+export function App({ items }: { items: Item[] }) {
+  const [open, setOpen] = useState(false);
+  const rows = items.map((item) => <li key={item.id}>{item.label}</li>);
+
+  return (
+    <div className="app">
+      <Button onClick={() => setOpen(!open)} icon={<Icon name="menu" />} disabled>
+        Menu
+      </Button>
+      {open && <ul>{rows}</ul>}
+    </div>
+  );
+}
+
+export const Footer = (props: { year: number }) => (
+  <>
+    <small {...props}>{props.year}</small>
+  </>
+);
@@ -0,0 +1,9 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rust_project::calculator::multiply;
+
+fn bench_multiply(c: &mut Criterion) {
+    c.bench_function("multiply", |b| b.iter(|| multiply(6, 7)));
+}
+
+criterion_group!(benches, bench_multiply);
+criterion_main!(benches);
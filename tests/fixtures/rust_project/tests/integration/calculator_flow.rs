@@ -0,0 +1,11 @@
+use rust_project::calculator::{add, divide};
+
+#[test]
+fn test_add_then_divide() {
+    assert_eq!(divide(add(4, 6), 2), Some(5));
+}
+
+#[tokio::test]
+async fn test_divide_by_zero_async() {
+    assert_eq!(divide(1, 0), None);
+}
@@ -0,0 +1,36 @@
+use serde::Serialize;
+use tera::{Context, Tera};
+
+mod models;
+
+use models::{Author, Post};
+
+#[derive(Serialize)]
+struct IndexPage {
+    title: String,
+    posts: Vec<Post>,
+}
+
+fn render_index(tera: &Tera, posts: Vec<Post>) -> tera::Result<String> {
+    let page = IndexPage {
+        title: "Blog".to_string(),
+        posts,
+    };
+    tera.render("index.html", &Context::from_serialize(&page)?)
+}
+
+fn render_post(tera: &Tera, post: &Post) -> tera::Result<String> {
+    let context = Context::from_serialize(post)?;
+    tera.render("post.html", &context)
+}
+
+fn render_author(tera: &Tera, author: Author) -> tera::Result<String> {
+    let mut context = Context::new();
+    context.insert("author", &author);
+    tera.render("author.html", &context)
+}
+
+fn main() {
+    let tera = Tera::new("templates/**/*").unwrap();
+    let _ = render_index(&tera, Vec::new());
+}
@@ -0,0 +1,13 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct Post {
+    pub title: String,
+    pub body: String,
+    pub author: Author,
+}
+
+#[derive(Serialize)]
+pub struct Author {
+    pub name: String,
+}
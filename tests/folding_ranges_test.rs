@@ -0,0 +1,135 @@
+use serde_json::json;
+use treesitter_mcp::analysis::lsp::{folding_ranges, FoldKind, FoldingRange};
+use treesitter_mcp::analysis::shape::extract_enhanced_shape;
+use treesitter_mcp::parser::{parse_code, Language};
+
+mod common;
+
+fn models_source() -> String {
+    let path = common::fixture_dir("complex_rust_service").join("src/domain/models.rs");
+    std::fs::read_to_string(path).unwrap()
+}
+
+fn folds(source: &str, language: Language) -> Vec<FoldingRange> {
+    let tree = parse_code(source, language).unwrap();
+    folding_ranges(tree.root_node(), source, language)
+}
+
+fn spans(folds: &[FoldingRange], kind: FoldKind) -> Vec<(usize, usize)> {
+    folds
+        .iter()
+        .filter(|fold| fold.kind == kind)
+        .map(|fold| (fold.start_line, fold.end_line))
+        .collect()
+}
+
+#[test]
+fn test_rust_use_declarations_fold_per_blank_line_separated_group() {
+    let folds = folds(&models_source(), Language::Rust);
+
+    // `use serde..; use std::fmt;` and the two `use super::..` lines
+    assert_eq!(spans(&folds, FoldKind::ImportGroup), [(3, 4), (6, 7)]);
+}
+
+#[test]
+fn test_rust_structs_impls_and_functions_fold_at_their_boundaries() {
+    let source = models_source();
+    let folds = folds(&source, Language::Rust);
+    let classes = spans(&folds, FoldKind::Class);
+    let functions = spans(&folds, FoldKind::Function);
+
+    assert!(classes.contains(&(11, 18)), "struct User: {classes:?}");
+    assert!(classes.contains(&(20, 68)), "impl User: {classes:?}");
+    assert!(functions.contains(&(22, 37)), "User::new: {functions:?}");
+    assert!(functions.contains(&(61, 63)), "record_event: {functions:?}");
+
+    let tree = parse_code(&source, Language::Rust).unwrap();
+    let shape = extract_enhanced_shape(&tree, &source, Language::Rust, None, false).unwrap();
+    assert!(!shape.structs.is_empty());
+    for structure in &shape.structs {
+        if structure.end_line > structure.line {
+            assert!(
+                classes.contains(&(structure.line, structure.end_line)),
+                "struct {} ({}-{}) has no fold",
+                structure.name,
+                structure.line,
+                structure.end_line
+            );
+        }
+    }
+    for function in &shape.functions {
+        if function.end_line > function.line {
+            assert!(
+                functions.contains(&(function.line, function.end_line)),
+                "fn {} ({}-{}) has no fold",
+                function.name,
+                function.line,
+                function.end_line
+            );
+        }
+    }
+}
+
+#[test]
+fn test_folds_are_sorted_with_enclosing_ranges_first() {
+    let folds = folds(&models_source(), Language::Rust);
+
+    assert!(folds.windows(2).all(
+        |pair| (pair[0].start_line, std::cmp::Reverse(pair[0].end_line))
+            <= (pair[1].start_line, std::cmp::Reverse(pair[1].end_line))
+    ));
+    // Single-line regions such as the `use` on line 3 alone never appear
+    assert!(folds.iter().all(|fold| fold.end_line > fold.start_line));
+}
+
+#[test]
+fn test_python_functions_classes_docstrings_and_comment_blocks() {
+    let source = r#"import os
+import sys
+
+# Helpers for the
+# command line entry point
+x = 1  # trailing
+# not joined to the trailing comment
+
+
+class Runner:
+    """Runs things.
+
+    More detail.
+    """
+
+    def run(self):
+        return os.getcwd()
+
+
+def main():
+    Runner().run()
+"#;
+    let folds = folds(source, Language::Python);
+
+    assert_eq!(spans(&folds, FoldKind::ImportGroup), [(1, 2)]);
+    assert_eq!(spans(&folds, FoldKind::Class), [(10, 17)]);
+    assert_eq!(spans(&folds, FoldKind::Function), [(16, 17), (20, 21)]);
+    assert_eq!(spans(&folds, FoldKind::Comment), [(4, 5), (11, 14)]);
+}
+
+#[test]
+fn test_folding_ranges_tool_rows() {
+    let path = common::fixture_dir("complex_rust_service").join("src/domain/models.rs");
+    let result = treesitter_mcp::analysis::lsp::execute_folding_ranges(&json!({
+        "file_path": path.to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["h"], "start_line|end_line|kind");
+    let rows = common::helpers::parse_compact_rows(output["folds"].as_str().unwrap());
+    assert_eq!(rows[0], ["3", "4", "import_group"]);
+    assert!(rows.contains(&vec![
+        "11".to_string(),
+        "18".to_string(),
+        "class".to_string()
+    ]));
+}
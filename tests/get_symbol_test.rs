@@ -0,0 +1,123 @@
+use std::fs;
+use std::process::Command;
+
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use treesitter_mcp::analysis::get_symbol::{get_symbol, SymbolKind};
+
+mod common;
+
+fn run(arguments: Value) -> Vec<Value> {
+    let result = treesitter_mcp::analysis::get_symbol::execute_get_symbol(&arguments).unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+    output["symbols"].as_array().unwrap().clone()
+}
+
+#[test]
+fn test_get_symbol_returns_rust_function_that_compiles_standalone() {
+    let path = common::fixture_path("rust", "src/calculator.rs");
+    let symbols = run(json!({
+        "file_path": path.to_str().unwrap(),
+        "symbol_name": "add"
+    }));
+
+    assert_eq!(symbols.len(), 1);
+    let add = &symbols[0];
+    assert_eq!(add["name"], "add");
+    assert_eq!(add["kind"], "function");
+    assert!(add["file"].as_str().unwrap().ends_with("calculator.rs"));
+    assert_eq!(add["start_line"], 13);
+    assert_eq!(add["end_line"], 15);
+    let code = add["code"].as_str().unwrap();
+    assert!(code.starts_with("pub fn add(a: i32, b: i32) -> i32 {"));
+    assert!(code.ends_with('}'));
+
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("add.rs");
+    fs::write(&file, code).unwrap();
+    let status = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()))
+        .args([
+            "--crate-type",
+            "lib",
+            "--emit",
+            "metadata",
+            "--edition",
+            "2021",
+        ])
+        .arg("--out-dir")
+        .arg(dir.path())
+        .arg(&file)
+        .status()
+        .unwrap();
+    assert!(status.success(), "extracted code did not compile:\n{code}");
+}
+
+#[test]
+fn test_unknown_symbol_returns_empty_list() {
+    let path = common::fixture_path("rust", "src/calculator.rs");
+    let symbols = run(json!({
+        "file_path": path.to_str().unwrap(),
+        "symbol_name": "does_not_exist"
+    }));
+    assert!(symbols.is_empty());
+}
+
+#[test]
+fn test_same_name_returns_all_definitions_and_kind_filters() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("shapes.py");
+    fs::write(
+        &path,
+        "class Shape:\n    def area(self):\n        return 0\n\n\ndef Shape():\n    return None\n\n\nclass Square(Shape):\n    def area(self):\n        return 4\n",
+    )
+    .unwrap();
+    let path = path.to_str().unwrap();
+
+    let areas = get_symbol(path, "area", None).unwrap();
+    assert_eq!(
+        areas.iter().map(|s| s.start_line).collect::<Vec<_>>(),
+        [2, 11]
+    );
+    assert_eq!(areas[1].code, "def area(self):\n        return 4");
+
+    let shapes = get_symbol(path, "Shape", None).unwrap();
+    assert_eq!(shapes.len(), 2);
+
+    let classes = get_symbol(path, "Shape", Some(SymbolKind::Class)).unwrap();
+    assert_eq!(classes.len(), 1);
+    assert_eq!((classes[0].start_line, classes[0].end_line), (1, 3));
+
+    let functions = get_symbol(path, "Shape", Some(SymbolKind::Function)).unwrap();
+    assert_eq!(functions.len(), 1);
+    assert_eq!(functions[0].start_line, 6);
+}
+
+#[test]
+fn test_arrow_function_is_returned_with_its_declaration() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("math.ts");
+    fs::write(
+        &path,
+        "export const double = (n: number): number => n * 2;\n",
+    )
+    .unwrap();
+
+    let symbols = get_symbol(path.to_str().unwrap(), "double", None).unwrap();
+    assert_eq!(symbols.len(), 1);
+    assert_eq!(
+        symbols[0].code,
+        "const double = (n: number): number => n * 2;"
+    );
+}
+
+#[test]
+fn test_invalid_kind_is_rejected() {
+    let path = common::fixture_path("rust", "src/calculator.rs");
+    let err = treesitter_mcp::analysis::get_symbol::execute_get_symbol(&json!({
+        "file_path": path.to_str().unwrap(),
+        "symbol_name": "add",
+        "kind": "enum"
+    }))
+    .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
@@ -88,6 +88,63 @@ func (c *Calculator) GetResult() float64 {
     );
 }
 
+/// Test Go methods are reported with their receiver type
+#[test]
+fn test_extract_go_methods_with_receivers() {
+    let source = r#"
+package server
+
+type Server struct{ addr string }
+
+type Point struct{ X, Y int }
+
+type List[T any] struct{ items []T }
+
+// Start runs the server
+func (s *Server) Start() error {
+    return nil
+}
+
+func (p Point) Norm() int {
+    return p.X*p.X + p.Y*p.Y
+}
+
+func NewServer(addr string) *Server {
+    return &Server{addr: addr}
+}
+
+func (s *Server) Stop() {}
+
+func (l *List[T]) Push(item T) {
+    l.items = append(l.items, item)
+}
+"#;
+    let tree = parse_code(source, Language::Go).expect("Failed to parse Go");
+    let shape = extract_enhanced_shape(&tree, source, Language::Go, None, false)
+        .expect("Failed to extract shape");
+
+    let functions: Vec<(&str, Option<&str>)> = shape
+        .functions
+        .iter()
+        .map(|f| (f.name.as_str(), f.receiver.as_deref()))
+        .collect();
+    assert_eq!(
+        functions,
+        [
+            ("Start", Some("Server")),
+            ("Norm", Some("Point")),
+            ("NewServer", None),
+            ("Stop", Some("Server")),
+            ("Push", Some("List")),
+        ]
+    );
+
+    let start = &shape.functions[0];
+    assert_eq!(start.signature, "func (s *Server) Start() error");
+    assert_eq!(start.doc.as_deref(), Some("Start runs the server"));
+    assert_eq!(start.line, 10, "Start line includes its doc comment");
+}
+
 /// Test extraction of Go package imports
 #[test]
 fn test_extract_go_imports() {
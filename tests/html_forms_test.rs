@@ -0,0 +1,151 @@
+mod common;
+
+use serde_json::json;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::html_analysis::{extract_html_forms, FormInfo, InputInfo};
+use treesitter_mcp::parser::{parse_code, Language};
+
+const ACCOUNT_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<body>
+  <form id="login" action="/login" method="POST">
+    <input type="email" name="email" placeholder="you@example.com" required>
+    <input type="hidden" name="csrf" value="token">
+    <input type="password" name="password" required />
+    <button type="submit">Sign in</button>
+  </form>
+
+  <section>
+    <form id="register" action="/register" method="post">
+      <input name="username" placeholder="Username" required>
+      <select name="interests" multiple>
+        <option>Rust</option>
+        <option>Web</option>
+      </select>
+      <select name="country"><option>DE</option></select>
+      <textarea name="bio" placeholder="About you"></textarea>
+    </form>
+  </section>
+</body>
+</html>
+"#;
+
+fn forms(source: &str) -> Vec<FormInfo> {
+    let tree = parse_code(source, Language::Html).unwrap();
+    extract_html_forms(tree.root_node(), source)
+}
+
+fn controls(form: &FormInfo) -> Vec<(Option<&str>, &str, bool, usize)> {
+    form.inputs
+        .iter()
+        .map(|input: &InputInfo| {
+            (
+                input.name.as_deref(),
+                input.type_attr.as_str(),
+                input.required,
+                input.line,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_login_and_registration_forms() {
+    let forms = forms(ACCOUNT_PAGE);
+
+    assert_eq!(forms.len(), 2);
+    assert_eq!(forms[0].id.as_deref(), Some("login"));
+    assert_eq!(forms[0].action.as_deref(), Some("/login"));
+    assert_eq!(forms[0].method.as_deref(), Some("post"));
+    assert_eq!(forms[0].line, 4);
+    assert_eq!(forms[1].id.as_deref(), Some("register"));
+    assert_eq!(forms[1].line, 12);
+}
+
+#[test]
+fn test_hidden_and_required_inputs() {
+    let forms = forms(ACCOUNT_PAGE);
+
+    assert_eq!(
+        controls(&forms[0]),
+        vec![
+            (Some("email"), "email", true, 5),
+            (Some("csrf"), "hidden", false, 6),
+            (Some("password"), "password", true, 7),
+        ]
+    );
+    assert_eq!(
+        forms[0].inputs[0].placeholder.as_deref(),
+        Some("you@example.com")
+    );
+}
+
+#[test]
+fn test_select_multiple_and_textarea() {
+    let forms = forms(ACCOUNT_PAGE);
+
+    assert_eq!(
+        controls(&forms[1]),
+        vec![
+            (Some("username"), "text", true, 13),
+            (Some("interests"), "select-multiple", false, 14),
+            (Some("country"), "select-one", false, 18),
+            (Some("bio"), "textarea", false, 19),
+        ]
+    );
+}
+
+#[test]
+fn test_nested_form_controls_belong_to_outer_form() {
+    let source = r#"<form action="/outer">
+  <input name="a">
+  <form action="/inner">
+    <input name="b" required>
+  </form>
+</form>
+<input name="outside">
+"#;
+    let forms = forms(source);
+
+    assert_eq!(forms.len(), 1);
+    assert_eq!(forms[0].action.as_deref(), Some("/outer"));
+    let names: Vec<Option<&str>> = forms[0]
+        .inputs
+        .iter()
+        .map(|input| input.name.as_deref())
+        .collect();
+    assert_eq!(names, [Some("a"), Some("b")]);
+}
+
+#[test]
+fn test_html_forms_tool_output() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("account.html");
+    std::fs::write(&path, ACCOUNT_PAGE).unwrap();
+
+    let result = treesitter_mcp::analysis::html_analysis::execute_html_forms(&json!({
+        "file_path": path.to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert!(output["file"].as_str().unwrap().ends_with("account.html"));
+    assert_eq!(output["fh"], "form|action|method|id|line");
+    assert_eq!(
+        output["forms"],
+        "1|/login|post|login|4\n2|/register|post|register|12"
+    );
+    assert_eq!(output["ih"], "form|name|type|required|placeholder|line");
+    let inputs = common::helpers::parse_compact_rows(output["inputs"].as_str().unwrap());
+    assert_eq!(inputs.len(), 7);
+    assert_eq!(
+        inputs[0],
+        ["1", "email", "email", "y", "you@example.com", "5"]
+    );
+    assert_eq!(inputs[1], ["1", "csrf", "hidden", "", "", "6"]);
+    assert_eq!(
+        inputs[4],
+        ["2", "interests", "select-multiple", "", "", "14"]
+    );
+}
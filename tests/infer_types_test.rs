@@ -0,0 +1,159 @@
+mod common;
+
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+fn hint_rows(path: &std::path::Path) -> Vec<Vec<String>> {
+    let result = treesitter_mcp::analysis::annotation_inference::execute(&json!({
+        "path": path.to_str().unwrap()
+    }))
+    .unwrap();
+    let text = common::get_result_text(&result);
+    let output: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(output["h"], "file|line|fn|type|conf");
+    common::helpers::parse_compact_rows(output["hints"].as_str().unwrap())
+}
+
+fn hint<'a>(rows: &'a [Vec<String>], name: &str) -> Option<&'a Vec<String>> {
+    rows.iter().find(|row| row[2] == name)
+}
+
+#[test]
+fn test_infer_types_python_literals_and_optionals() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("service.py"),
+        r#"
+def is_ready(x):
+    return x > 3
+
+def label():
+    return "ready"
+
+def find(items, key):
+    for item in items:
+        if item == key:
+            return item.name()
+    return None
+
+def lookup(flag):
+    if flag:
+        return "yes"
+    return None
+
+def make():
+    return Service()
+
+def log(msg):
+    print(msg)
+
+def annotated() -> int:
+    return 1
+
+def stub():
+    raise NotImplementedError
+
+def numbers():
+    yield 1
+"#,
+    )
+    .unwrap();
+
+    let rows = hint_rows(dir.path());
+
+    assert_eq!(hint(&rows, "is_ready").unwrap()[3..], ["bool", "high"]);
+    assert_eq!(hint(&rows, "label").unwrap()[3..], ["str", "high"]);
+    assert_eq!(
+        hint(&rows, "lookup").unwrap()[3..],
+        ["str | None", "medium"]
+    );
+    assert_eq!(hint(&rows, "make").unwrap()[3..], ["Service", "medium"]);
+    assert_eq!(hint(&rows, "log").unwrap()[3..], ["None", "high"]);
+    assert_eq!(hint(&rows, "log").unwrap()[1], "22");
+    assert!(
+        hint(&rows, "find").is_none(),
+        "unknown call result is skipped"
+    );
+    assert!(hint(&rows, "annotated").is_none());
+    assert!(hint(&rows, "stub").is_none());
+    assert!(hint(&rows, "numbers").is_none());
+}
+
+#[test]
+fn test_infer_types_typescript_functions_and_arrows() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("app.ts"),
+        r#"
+export function isEmpty(value) {
+  return value.length === 0;
+}
+
+const greet = (name) => `hello ${name}`;
+
+async function load() {
+  return new Map();
+}
+
+function reset() {
+  counter = 0;
+}
+
+function typed(): number {
+  return 1;
+}
+
+class Store {
+  size() {
+    return 42;
+  }
+}
+"#,
+    )
+    .unwrap();
+
+    let rows = hint_rows(dir.path());
+
+    assert_eq!(hint(&rows, "isEmpty").unwrap()[3..], ["boolean", "high"]);
+    assert_eq!(hint(&rows, "greet").unwrap()[3..], ["string", "high"]);
+    assert_eq!(hint(&rows, "load").unwrap()[3..], ["Promise<Map>", "high"]);
+    assert_eq!(hint(&rows, "reset").unwrap()[3..], ["void", "high"]);
+    assert_eq!(hint(&rows, "size").unwrap()[3..], ["number", "high"]);
+    assert!(hint(&rows, "typed").is_none());
+}
+
+#[test]
+fn test_infer_types_skips_typescript_constructors_and_setters() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("box.ts"),
+        r#"class Box {
+  constructor(v) {
+    this.v = v;
+  }
+
+  set value(x) {
+    this.v = x;
+  }
+
+  get value() {
+    return `${this.v}`;
+  }
+
+  clear() {
+    this.v = null;
+  }
+}
+"#,
+    )
+    .unwrap();
+
+    let rows = hint_rows(dir.path());
+
+    assert!(hint(&rows, "constructor").is_none());
+    let value: Vec<_> = rows.iter().filter(|row| row[2] == "value").collect();
+    assert_eq!(value.len(), 1);
+    assert_eq!(value[0][1], "10");
+    assert_eq!(hint(&rows, "clear").unwrap()[3..], ["void", "high"]);
+}
@@ -0,0 +1,164 @@
+use treesitter_mcp::analysis::shape::{extract_enhanced_shape, extract_jsdoc, JsDocParam};
+use treesitter_mcp::parser::{parse_code, Language};
+
+fn param(name: &str, ty: Option<&str>, description: Option<&str>) -> JsDocParam {
+    JsDocParam {
+        name: name.to_string(),
+        type_annotation: ty.map(str::to_string),
+        description: description.map(str::to_string),
+        optional: false,
+    }
+}
+
+#[test]
+fn test_extract_jsdoc_with_all_supported_tags() {
+    let jsdoc = extract_jsdoc(
+        r#"/**
+     * Greets a user by name.
+     * Falls back to a generic greeting.
+     *
+     * @param {string} name - The user name
+     * @param {number} times - How often to
+     *   repeat the greeting
+     * @returns {string} The greeting
+     * @throws {TypeError} When name is not a string
+     * @throws {RangeError} When times is negative
+     * @example
+     * greet("Ada", 2);
+     * // => "Hello, Ada! Hello, Ada!"
+     * @see other
+     */"#,
+    );
+
+    assert_eq!(
+        jsdoc.description.as_deref(),
+        Some("Greets a user by name. Falls back to a generic greeting.")
+    );
+    assert_eq!(
+        jsdoc.params,
+        [
+            param("name", Some("string"), Some("The user name")),
+            param(
+                "times",
+                Some("number"),
+                Some("How often to repeat the greeting")
+            ),
+        ]
+    );
+    assert_eq!(jsdoc.returns.as_deref(), Some("{string} The greeting"));
+    assert_eq!(
+        jsdoc.throws,
+        [
+            "{TypeError} When name is not a string",
+            "{RangeError} When times is negative"
+        ]
+    );
+    assert_eq!(
+        jsdoc.examples,
+        ["greet(\"Ada\", 2);\n// => \"Hello, Ada! Hello, Ada!\""]
+    );
+}
+
+#[test]
+fn test_jsdoc_params_with_optional_nested_and_untyped_forms() {
+    let jsdoc = extract_jsdoc(
+        "/**\n * @param {{ id: number, tags: string[] }} record The record\n * @param {string} [label=\"none\"] - Optional label\n * @arg callback\n */",
+    );
+
+    assert_eq!(jsdoc.description, None);
+    assert_eq!(jsdoc.params.len(), 3);
+    assert_eq!(
+        jsdoc.params[0],
+        param(
+            "record",
+            Some("{ id: number, tags: string[] }"),
+            Some("The record")
+        )
+    );
+    assert_eq!(jsdoc.params[1].name, "label");
+    assert!(jsdoc.params[1].optional);
+    assert_eq!(
+        jsdoc.params[1].description.as_deref(),
+        Some("Optional label")
+    );
+    assert_eq!(jsdoc.params[2], param("callback", None, None));
+}
+
+#[test]
+fn test_js_functions_carry_structured_jsdoc() {
+    let source = r#"
+/**
+ * Adds two numbers together
+ * @param {number} a - First number
+ * @param {number} b - Second number
+ * @returns {number} The sum
+ */
+function add(a, b) {
+    return a + b;
+}
+
+/**
+ * Doubles a value
+ * @param {number} n - Value to double
+ */
+export const double = (n) => n * 2;
+
+// Plain comment, not JSDoc
+function plain() {}
+"#;
+    let tree = parse_code(source, Language::JavaScript).unwrap();
+    let shape = extract_enhanced_shape(&tree, source, Language::JavaScript, None, false).unwrap();
+    let function = |name: &str| shape.functions.iter().find(|f| f.name == name).unwrap();
+
+    let add = function("add");
+    assert_eq!(add.doc.as_deref(), Some("Adds two numbers together"));
+    let jsdoc = add.jsdoc.as_ref().unwrap();
+    assert_eq!(
+        jsdoc.params,
+        [
+            param("a", Some("number"), Some("First number")),
+            param("b", Some("number"), Some("Second number")),
+        ]
+    );
+    assert_eq!(jsdoc.returns.as_deref(), Some("{number} The sum"));
+
+    let double = function("double");
+    assert_eq!(double.doc.as_deref(), Some("Doubles a value"));
+    assert_eq!(double.jsdoc.as_ref().unwrap().params[0].name, "n");
+
+    let plain = function("plain");
+    assert!(plain.jsdoc.is_none());
+    assert_eq!(plain.doc.as_deref(), Some("Plain comment, not JSDoc"));
+}
+
+#[test]
+fn test_exported_typescript_function_jsdoc() {
+    let source = r#"
+/**
+ * Finds a user.
+ * @param {string} id - The user id
+ * @throws {NotFoundError} When no user matches
+ */
+export function findUser(id: string): User {
+    return users[id];
+}
+"#;
+    let tree = parse_code(source, Language::TypeScript).unwrap();
+    let shape = extract_enhanced_shape(&tree, source, Language::TypeScript, None, false).unwrap();
+    let find_user = shape
+        .functions
+        .iter()
+        .find(|f| f.name == "findUser")
+        .unwrap();
+
+    let jsdoc = find_user.jsdoc.as_ref().unwrap();
+    assert_eq!(jsdoc.description.as_deref(), Some("Finds a user."));
+    assert_eq!(
+        jsdoc.params,
+        [param("id", Some("string"), Some("The user id"))]
+    );
+    assert_eq!(jsdoc.throws, ["{NotFoundError} When no user matches"]);
+
+    let json = serde_json::to_value(find_user).unwrap();
+    assert_eq!(json["jsdoc"]["params"][0]["type"], "string");
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use serde_json::{json, Value};
+use std::fs;
+use treesitter_mcp::analysis::jsx::{self, extract_jsx_trees};
+
+#[test]
+fn test_extracts_nested_tsx_tree() {
+    let path = common::fixture_dir("react").join("src/App.tsx");
+    let source = fs::read_to_string(path).unwrap();
+    let trees = extract_jsx_trees(&source, true).unwrap();
+
+    // The mapped `<li>` helper and the returned `<div>` both belong to App.
+    let summary: Vec<(&str, &str, usize)> = trees
+        .iter()
+        .map(|tree| {
+            (
+                tree.component.as_str(),
+                tree.root_element.as_str(),
+                tree.line,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [("App", "li", 12), ("App", "div", 15), ("Footer", "<>", 25)]
+    );
+
+    let div = &trees[1];
+    assert_eq!(div.props, ["className"]);
+    let button = &div.children[0];
+    assert_eq!(button.tag, "Button");
+    assert_eq!(button.props, ["onClick", "icon", "disabled"]);
+    // Elements passed as props are children of the element receiving them.
+    assert_eq!(button.children[0].tag, "Icon");
+    assert_eq!(button.children[0].props, ["name"]);
+    // Conditionally rendered elements stay in the tree.
+    assert_eq!(div.children[1].tag, "ul");
+
+    let footer = &trees[2];
+    assert_eq!(footer.children[0].tag, "small");
+    assert_eq!(footer.children[0].props, ["...props"]);
+}
+
+#[test]
+fn test_extract_jsx_tree_tool_rows() {
+    let path = common::fixture_dir("react").join("src/Card.jsx");
+    let result = jsx::execute(&json!({ "file_path": path.to_str().unwrap() })).unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["h"], "component|depth|tag|props|line");
+    let rows = common::helpers::parse_compact_rows(output["tree"].as_str().unwrap());
+    assert_eq!(
+        rows,
+        [
+            ["Card", "0", "section", "className", "3"],
+            ["Card", "1", "h2", "", "4"],
+        ]
+    );
+}
+
+#[test]
+fn test_extract_jsx_tree_scoped_to_component() {
+    let path = common::fixture_dir("react").join("src/App.tsx");
+    let result = jsx::execute(&json!({
+        "file_path": path.to_str().unwrap(),
+        "component": "Footer",
+    }))
+    .unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    let rows = common::helpers::parse_compact_rows(output["tree"].as_str().unwrap());
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|row| row[0] == "Footer"));
+
+    let missing = jsx::execute(&json!({
+        "file_path": path.to_str().unwrap(),
+        "component": "Header",
+    }));
+    assert!(missing.is_err());
+}
@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+
+mod common;
+
+use serde_json::json;
+use treesitter_mcp::analysis::askama::{detect_template_engine, TemplateEngine};
+use treesitter_mcp::analysis::liquid::{
+    extract_liquid_variables, find_site_root, site_template_variables, LiquidVar,
+};
+
+fn fixture() -> PathBuf {
+    PathBuf::from("tests/fixtures/liquid_project")
+}
+
+fn var(name: &str, access_path: &str, usage_kind: &str) -> LiquidVar {
+    LiquidVar {
+        name: name.to_string(),
+        access_path: access_path.to_string(),
+        usage_kind: usage_kind.to_string(),
+    }
+}
+
+#[test]
+fn test_detect_liquid_from_site_layout() {
+    assert_eq!(detect_template_engine(&fixture()), TemplateEngine::Liquid);
+    assert_eq!(
+        find_site_root(&fixture().join("_includes/header.html")),
+        Some(fixture())
+    );
+}
+
+#[test]
+fn test_extract_liquid_variables_skips_bound_names() {
+    let source = r#"
+{% assign recent = site.posts | slice: 0, 5 %}
+{% for post in recent %}{{ post.title }} {{ forloop.index }}{% endfor %}
+{%- unless page.hidden -%}{{ page.title | upcase }}{%- endunless -%}
+{% capture greeting %}Hi {{ customer.first_name }}{% endcapture %}{{ greeting }}
+{% if product.available == true %}{{ content }}{% endif %}
+"#;
+
+    let vars = extract_liquid_variables(source);
+
+    assert_eq!(
+        vars,
+        vec![
+            var("site", "site.posts", "scalar"),
+            var("page", "page.hidden", "conditional"),
+            var("page", "page.title", "scalar"),
+            var("customer", "customer.first_name", "scalar"),
+            var("product", "product.available", "conditional"),
+        ]
+    );
+}
+
+#[test]
+fn test_site_template_variables_follow_layout_and_includes() {
+    let vars = site_template_variables(&fixture().join("index.html"), &fixture());
+
+    let found: Vec<(&str, &str, &str)> = vars
+        .iter()
+        .map(|(tpl, v)| (tpl.as_str(), v.access_path.as_str(), v.usage_kind.as_str()))
+        .collect();
+    assert!(found.contains(&("index.html", "paginator.next_page", "conditional")));
+    assert!(found.contains(&("_layouts/default.html", "site.title", "scalar")));
+    assert!(found.contains(&("_includes/header.html", "site.navigation", "iterator")));
+    assert!(!found.iter().any(|(_, path, _)| path.starts_with("post.")));
+}
+
+#[test]
+fn test_template_context_tool_uses_liquid_for_jekyll_sites() {
+    let template = fixture().join("index.html");
+    let result = treesitter_mcp::analysis::askama::execute(&json!({
+        "template_path": template.to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["ctx"], "");
+    assert_eq!(output["vh"], "name|path|kind|tpl");
+    let vars = common::helpers::parse_compact_rows(output["vars"].as_str().unwrap());
+    assert!(vars
+        .iter()
+        .any(|r| r[1] == "site.posts" && r[2] == "scalar" && r[3] == "index.html"));
+    assert!(vars
+        .iter()
+        .any(|r| r[1] == "page.title" && r[3] == "_layouts/default.html"));
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use serde_json::json;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::lockfile::{parse_cargo_lock, parse_go_sum, LockedDep};
+
+const CARGO_LOCK: &str = r#"# This file is automatically @generated by Cargo.
+version = 4
+
+[[package]]
+name = "demo"
+version = "0.1.0"
+dependencies = [
+ "serde",
+]
+
+[[package]]
+name = "serde"
+version = "1.0.210"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "c8e3592472072e6e22e0a54d5904d9febf8508f65fb8552499a1abc7d1078c3a"
+"#;
+
+const GO_SUM: &str = "github.com/pkg/errors v0.9.1 h1:FEBLx1zS214owpjy7qsBeixbURkuhQAwrK5UwLGTwt4=\ngithub.com/pkg/errors v0.9.1/go.mod h1:bwawxfHBFNV+L2hUp1rHADufV3IMtnDRdf1r5NINEl0=\ngolang.org/x/text v0.3.0/go.mod h1:NqM8EUOU14njkJ3fqMW+pc6Ldnwhi/IjpwHt7yyuwOQ=\n";
+
+fn serve_once(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buffer = [0u8; 1024];
+        let _ = stream.read(&mut buffer);
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).unwrap();
+    });
+    format!("http://{address}/advisories.json")
+}
+
+#[test]
+fn test_parse_cargo_lock_reads_packages() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("Cargo.lock");
+    fs::write(&path, CARGO_LOCK).unwrap();
+
+    let deps = parse_cargo_lock(&path);
+
+    assert_eq!(deps.len(), 2);
+    assert_eq!(
+        deps[0],
+        LockedDep {
+            name: "demo".to_string(),
+            version: "0.1.0".to_string(),
+            checksum: None,
+        }
+    );
+    assert_eq!(deps[1].name, "serde");
+    assert_eq!(deps[1].version, "1.0.210");
+    assert!(deps[1].checksum.as_deref().unwrap().starts_with("c8e359"));
+}
+
+#[test]
+fn test_parse_go_sum_merges_go_mod_entries() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("go.sum");
+    fs::write(&path, GO_SUM).unwrap();
+
+    let deps = parse_go_sum(&path);
+
+    assert_eq!(deps.len(), 2);
+    assert_eq!(deps[0].name, "github.com/pkg/errors");
+    assert_eq!(deps[0].version, "v0.9.1");
+    assert!(deps[0].checksum.as_deref().unwrap().starts_with("h1:FEBL"));
+    assert_eq!(deps[1].name, "golang.org/x/text");
+    assert_eq!(deps[1].checksum, None);
+}
+
+#[test]
+fn test_parse_go_sum_prefers_module_hash_listed_after_go_mod() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("go.sum");
+    fs::write(
+        &path,
+        "golang.org/x/text v0.3.0/go.mod h1:GOMOD=\ngolang.org/x/text v0.3.0 h1:MODULE=\n",
+    )
+    .unwrap();
+
+    let deps = parse_go_sum(&path);
+
+    assert_eq!(deps.len(), 1);
+    assert_eq!(deps[0].checksum.as_deref(), Some("h1:MODULE="));
+}
+
+#[test]
+fn test_list_locked_dependencies_with_cve_check() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("Cargo.lock"), CARGO_LOCK).unwrap();
+    fs::write(dir.path().join("go.sum"), GO_SUM).unwrap();
+
+    let url = serve_once(
+        r#"[{"id":"CVE-2099-0001","package":"serde","versions":["1.0.210"]},{"id":"CVE-2099-0002","package":"serde","versions":["0.9.0"]}]"#,
+    );
+
+    let result = treesitter_mcp::analysis::lockfile::execute(&json!({
+        "project_root": dir.path().to_str().unwrap(),
+        "check_cve": true,
+        "cve_url": url
+    }))
+    .unwrap();
+    let text = common::get_result_text(&result);
+    let output: serde_json::Value = serde_json::from_str(&text).unwrap();
+
+    assert_eq!(output["h"], "name|version|checksum|src");
+    let rows = common::helpers::parse_compact_rows(output["deps"].as_str().unwrap());
+    assert_eq!(rows.len(), 4);
+    assert!(rows.iter().any(|r| r[0] == "serde" && r[3] == "Cargo.lock"));
+    assert!(rows
+        .iter()
+        .any(|r| r[0] == "github.com/pkg/errors" && r[3] == "go.sum"));
+
+    assert_eq!(output["vh"], "name|version|id");
+    assert_eq!(output["vulns"], "serde|1.0.210|CVE-2099-0001");
+}
+
+#[test]
+fn test_list_locked_dependencies_requires_cve_url() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("Cargo.lock"), CARGO_LOCK).unwrap();
+
+    let result = treesitter_mcp::analysis::lockfile::execute(&json!({
+        "project_root": dir.path().to_str().unwrap(),
+        "check_cve": true
+    }));
+
+    assert!(result.is_err());
+}
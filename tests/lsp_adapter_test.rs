@@ -0,0 +1,166 @@
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use treesitter_mcp::lsp::adapter::{read_message, serve_listener, write_message};
+use url::Url;
+
+const SOURCE: &str = r#"pub struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    pub fn increment(&mut self) -> u32 {
+        self.count += 1;
+        self.count
+    }
+}
+
+pub fn helper() {}
+"#;
+
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    next_id: u64,
+}
+
+impl Client {
+    fn connect() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve_listener(listener));
+
+        let writer = TcpStream::connect(address).unwrap();
+        let reader = BufReader::new(writer.try_clone().unwrap());
+        Client {
+            reader,
+            writer,
+            next_id: 1,
+        }
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        write_message(
+            &mut self.writer,
+            &json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}),
+        )
+        .unwrap();
+        let response = read_message(&mut self.reader).unwrap().unwrap();
+        assert_eq!(response["id"], id);
+        response
+    }
+
+    fn notify(&mut self, method: &str) {
+        write_message(
+            &mut self.writer,
+            &json!({"jsonrpc": "2.0", "method": method, "params": {}}),
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn test_lsp_adapter_serves_read_only_requests_over_tcp() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    std::fs::write(&file, SOURCE).unwrap();
+    let root_uri = Url::from_directory_path(dir.path()).unwrap().to_string();
+    let file_uri = Url::from_file_path(&file).unwrap().to_string();
+
+    let mut client = Client::connect();
+
+    let init = client.request("initialize", json!({"rootUri": root_uri}));
+    let capabilities = &init["result"]["capabilities"];
+    assert_eq!(capabilities["documentSymbolProvider"], true);
+    assert_eq!(capabilities["workspaceSymbolProvider"], true);
+    assert_eq!(capabilities["hoverProvider"], true);
+    assert_eq!(capabilities["foldingRangeProvider"], true);
+    assert!(capabilities.get("renameProvider").is_none());
+    client.notify("initialized");
+
+    let symbols = client.request(
+        "textDocument/documentSymbol",
+        json!({"textDocument": {"uri": file_uri}}),
+    );
+    let symbols = symbols["result"].as_array().unwrap();
+    let names: Vec<&str> = symbols
+        .iter()
+        .map(|s| s["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Counter", "impl Counter", "helper"]);
+    let method = &symbols[1]["children"][0];
+    assert_eq!(method["name"], "increment");
+    assert_eq!(method["kind"], 6);
+    assert_eq!(method["range"]["start"]["line"], 5);
+    assert_eq!(method["range"]["end"]["line"], 8);
+
+    // Files that are not UTF-8 are decoded like everywhere else.
+    let latin1 = dir.path().join("legacy.rs");
+    std::fs::write(&latin1, b"/// Prix en \xe9cus.\npub fn prix() {}\n").unwrap();
+    let latin1_symbols = client.request(
+        "textDocument/documentSymbol",
+        json!({"textDocument": {"uri": Url::from_file_path(&latin1).unwrap().to_string()}}),
+    );
+    assert_eq!(latin1_symbols["result"][0]["name"], "prix");
+    let latin1_folds = client.request(
+        "textDocument/foldingRange",
+        json!({"textDocument": {"uri": Url::from_file_path(&latin1).unwrap().to_string()}}),
+    );
+    assert!(latin1_folds["result"].is_array());
+
+    let hover = client.request(
+        "textDocument/hover",
+        json!({
+            "textDocument": {"uri": file_uri},
+            "position": {"line": 6, "character": 8}
+        }),
+    );
+    let hover_text = hover["result"]["contents"]["value"].as_str().unwrap();
+    assert!(hover_text.contains("increment(&mut self)"));
+    assert!(hover_text.contains("`Counter::increment`"));
+
+    let folds = client.request(
+        "textDocument/foldingRange",
+        json!({"textDocument": {"uri": file_uri}}),
+    );
+    let folds: Vec<(u64, u64, &str)> = folds["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|fold| {
+            (
+                fold["startLine"].as_u64().unwrap(),
+                fold["endLine"].as_u64().unwrap(),
+                fold["kind"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        folds,
+        vec![(0, 2, "region"), (4, 9, "region"), (5, 8, "region")]
+    );
+
+    let workspace = client.request("workspace/symbol", json!({"query": "help"}));
+    let found = workspace["result"].as_array().unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0]["name"], "helper");
+    assert_eq!(found[0]["kind"], 12);
+    assert_eq!(found[0]["location"]["range"]["start"]["line"], 11);
+    assert!(found[0]["location"]["uri"]
+        .as_str()
+        .unwrap()
+        .ends_with("/lib.rs"));
+
+    let rename = client.request("textDocument/rename", json!({}));
+    assert_eq!(rename["error"]["code"], -32601);
+
+    let shutdown = client.request("shutdown", Value::Null);
+    assert_eq!(shutdown["result"], Value::Null);
+    client.notify("exit");
+    assert!(read_message(&mut client.reader).unwrap().is_none());
+}
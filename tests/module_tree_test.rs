@@ -0,0 +1,209 @@
+mod common;
+
+use std::fs;
+
+use serde_json::json;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::module_tree::{
+    build_module_tree, crate_root, expand_module, module_tree, ModuleNode,
+};
+
+fn names(node: &ModuleNode) -> Vec<&str> {
+    node.children
+        .iter()
+        .map(|child| child.name.as_str())
+        .collect()
+}
+
+fn child<'a>(node: &'a ModuleNode, name: &str) -> &'a ModuleNode {
+    node.children
+        .iter()
+        .find(|child| child.name == name)
+        .unwrap_or_else(|| panic!("no module {name} in {:?}", names(node)))
+}
+
+#[test]
+fn test_complex_service_top_level_modules() {
+    let root = crate_root(&common::fixture_dir("complex_rust_service")).unwrap();
+    let tree = module_tree(&root).unwrap();
+
+    assert_eq!(tree.name, "crate");
+    assert!(tree.path.as_deref().unwrap().ends_with("src/lib.rs"));
+    assert_eq!(
+        names(&tree),
+        ["domain", "infrastructure", "application", "api"]
+    );
+    assert!(tree.children.iter().all(|module| module.is_public));
+}
+
+#[test]
+fn test_complex_service_nested_files_are_resolved() {
+    let root = crate_root(&common::fixture_dir("complex_rust_service")).unwrap();
+    let tree = module_tree(&root).unwrap();
+
+    let domain = child(&tree, "domain");
+    assert!(domain
+        .path
+        .as_deref()
+        .unwrap()
+        .ends_with("src/domain/mod.rs"));
+    let models = child(domain, "models");
+    assert!(models
+        .path
+        .as_deref()
+        .unwrap()
+        .ends_with("src/domain/models.rs"));
+    let api = child(&tree, "api");
+    assert!(names(api).contains(&"handlers"));
+}
+
+#[test]
+fn test_inline_and_non_mod_rs_modules() {
+    let dir = TempDir::new().unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir_all(src.join("net")).unwrap();
+    fs::write(
+        src.join("main.rs"),
+        "mod net;\npub(crate) mod util {\n    pub mod strings {}\n}\n#[cfg(test)]\nmod missing;\nfn main() {}\n",
+    )
+    .unwrap();
+    fs::write(src.join("net.rs"), "pub mod tcp;\n").unwrap();
+    fs::write(src.join("net").join("tcp.rs"), "pub fn connect() {}\n").unwrap();
+
+    let tree = module_tree(&crate_root(dir.path()).unwrap()).unwrap();
+
+    assert_eq!(names(&tree), ["net", "util"]);
+    let net = child(&tree, "net");
+    assert!(!net.is_public);
+    assert!(net.path.as_deref().unwrap().ends_with("src/net.rs"));
+    let tcp = child(net, "tcp");
+    assert!(tcp.path.as_deref().unwrap().ends_with("src/net/tcp.rs"));
+    assert!(tcp.is_public);
+
+    let util = child(&tree, "util");
+    assert_eq!(util.path, None);
+    assert!(!util.is_public);
+    let strings = child(util, "strings");
+    assert_eq!(strings.path, None);
+    assert!(strings.is_public);
+}
+
+#[test]
+fn test_missing_crate_root_is_an_error() {
+    let dir = TempDir::new().unwrap();
+    let err = crate_root(dir.path()).unwrap_err();
+    assert!(err.to_string().contains("No src/lib.rs or src/main.rs"));
+}
+
+#[test]
+fn test_module_tree_tool_output() {
+    let dir = common::fixture_dir("complex_rust_service");
+    let result = treesitter_mcp::analysis::module_tree::execute_module_tree(&json!({
+        "path": dir.to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["name"], "crate");
+    common::helpers::assert_path_is_relative(output["path"].as_str().unwrap());
+    let children = output["children"].as_array().unwrap();
+    let top: Vec<&str> = children
+        .iter()
+        .map(|child| child["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(top, ["domain", "infrastructure", "application", "api"]);
+    assert_eq!(children[0]["is_public"], true);
+    assert!(children[0]["children"].as_array().unwrap().len() >= 2);
+}
+
+/// `src/lib.rs` with `a` (file, declaring `b` with `c`) and inline `d::e`
+fn nested_crate() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir_all(src.join("a/b")).unwrap();
+    fs::write(
+        src.join("lib.rs"),
+        "pub mod a;\nmod d {\n    pub mod e {}\n}\n",
+    )
+    .unwrap();
+    fs::write(src.join("a.rs"), "pub mod b;\n").unwrap();
+    fs::write(src.join("a/b.rs"), "mod c {}\nmod x {}\n").unwrap();
+    dir
+}
+
+#[test]
+fn test_max_depth_truncates_with_child_counts() {
+    let dir = nested_crate();
+    let root = crate_root(dir.path()).unwrap();
+
+    let tree = build_module_tree(&root, Some(1)).unwrap();
+    let a = child(&tree, "a");
+    assert!(a.truncated);
+    assert_eq!(a.child_count, Some(1));
+    assert!(a.children.is_empty());
+    let d = child(&tree, "d");
+    assert!(d.truncated);
+    assert_eq!(d.child_count, Some(1));
+
+    let tree = build_module_tree(&root, Some(2)).unwrap();
+    let b = child(child(&tree, "a"), "b");
+    assert!(b.truncated);
+    assert_eq!(b.child_count, Some(2));
+    let e = child(child(&tree, "d"), "e");
+    assert!(!e.truncated);
+    assert_eq!(e.child_count, None);
+
+    let root_only = build_module_tree(&root, Some(0)).unwrap();
+    assert!(root_only.truncated);
+    assert_eq!(root_only.child_count, Some(2));
+    assert_eq!(
+        build_module_tree(&root, None).unwrap(),
+        module_tree(&root).unwrap()
+    );
+}
+
+#[test]
+fn test_expand_module_returns_direct_children() {
+    let dir = nested_crate();
+
+    let a = expand_module("crate::a", dir.path()).unwrap();
+    assert_eq!(a.name, "a");
+    assert!(a.path.as_deref().unwrap().ends_with("src/a.rs"));
+    assert_eq!(names(&a), ["b"]);
+    assert_eq!(child(&a, "b").child_count, Some(2));
+
+    let b = expand_module("a::b", dir.path()).unwrap();
+    assert_eq!(names(&b), ["c", "x"]);
+    assert!(!child(&b, "c").truncated);
+
+    let d = expand_module("d", dir.path()).unwrap();
+    assert_eq!(d.path, None);
+    assert!(!d.is_public);
+    assert_eq!(names(&d), ["e"]);
+
+    let err = expand_module("a::missing", dir.path()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn test_module_tree_tool_max_depth_and_module() {
+    let dir = nested_crate();
+    let run = |arguments: serde_json::Value| -> serde_json::Value {
+        let result =
+            treesitter_mcp::analysis::module_tree::execute_module_tree(&arguments).unwrap();
+        serde_json::from_str(&common::get_result_text(&result)).unwrap()
+    };
+
+    let output = run(json!({"path": dir.path().to_str().unwrap(), "max_depth": 1}));
+    let a = &output["children"][0];
+    assert_eq!(a["name"], "a");
+    assert_eq!(a["truncated"], true);
+    assert_eq!(a["child_count"], 1);
+    assert!(a.get("children").is_none());
+
+    let output = run(json!({"path": dir.path().to_str().unwrap(), "module": "a"}));
+    assert_eq!(output["name"], "a");
+    assert_eq!(output["children"][0]["name"], "b");
+    assert_eq!(output["children"][0]["truncated"], true);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use serde_json::{json, Value};
+use std::fs;
+use treesitter_mcp::analysis::nestjs::{self, extract_nest_modules, extract_nest_routes};
+
+fn fixture_source(relative: &str) -> (std::path::PathBuf, String) {
+    let path = common::fixture_dir("nestjs").join(relative);
+    let source = fs::read_to_string(&path).unwrap();
+    (path, source)
+}
+
+#[test]
+fn test_extracts_module_metadata() {
+    let (path, source) = fixture_source("src/users/users.module.ts");
+    let modules = extract_nest_modules(&source, &path);
+
+    assert_eq!(modules.len(), 1);
+    let module = &modules[0];
+    assert_eq!(module.name, "UsersModule");
+    assert_eq!(module.line, 15);
+    assert!(module.imports.is_empty());
+    assert_eq!(module.controllers, ["UsersController"]);
+    assert_eq!(module.providers, ["UsersService", "USERS_REPOSITORY"]);
+    assert_eq!(module.exports, ["UsersService"]);
+}
+
+#[test]
+fn test_extracts_controller_routes() {
+    let (path, source) = fixture_source("src/users/users.controller.ts");
+    let routes = extract_nest_routes(&source, &path);
+    let summary: Vec<(&str, &str, &str)> = routes
+        .iter()
+        .map(|route| {
+            (
+                route.method.as_str(),
+                route.path.as_str(),
+                route.handler.as_str(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        [
+            ("GET", "/users", "findAll"),
+            ("GET", "/users/:id", "findOne"),
+            ("POST", "/users", "create"),
+        ]
+    );
+    assert!(routes
+        .iter()
+        .all(|route| route.controller == "UsersController"));
+    assert!(routes.iter().all(|route| route.module.is_none()));
+}
+
+#[test]
+fn test_list_nest_routes_links_controllers_to_modules() {
+    let root = common::fixture_dir("nestjs");
+    let result =
+        nestjs::execute_list_nest_routes(&json!({ "path": root.to_str().unwrap() })).unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(
+        output["h"],
+        "method|path|controller|handler|module|file|line"
+    );
+    let rows = common::helpers::parse_compact_rows(output["routes"].as_str().unwrap());
+    assert_eq!(rows.len(), 4);
+
+    let live = rows.iter().find(|row| row[3] == "live").unwrap();
+    assert_eq!(live[1], "/health/live");
+    assert_eq!(live[4], "AppModule");
+
+    let find_one = rows.iter().find(|row| row[3] == "findOne").unwrap();
+    assert_eq!(find_one[4], "UsersModule");
+    assert!(find_one[5].ends_with("users.controller.ts"));
+}
+
+#[test]
+fn test_list_nest_modules_lists_every_module() {
+    let root = common::fixture_dir("nestjs");
+    let result =
+        nestjs::execute_list_nest_modules(&json!({ "path": root.to_str().unwrap() })).unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    let rows = common::helpers::parse_compact_rows(output["modules"].as_str().unwrap());
+    let names: Vec<&str> = rows.iter().map(|row| row[0].as_str()).collect();
+    assert_eq!(names, ["AppModule", "UsersModule"]);
+    assert_eq!(rows[0][3], "UsersModule");
+    assert_eq!(rows[0][4], "HealthController");
+}
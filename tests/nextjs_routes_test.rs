@@ -0,0 +1,94 @@
+mod common;
+
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use treesitter_mcp::analysis::nextjs::{self, extract_nextjs_routes, NextjsRoute};
+
+fn app_dir() -> PathBuf {
+    common::fixture_dir("nextjs").join("app")
+}
+
+fn route<'a>(routes: &'a [NextjsRoute], path: &str) -> &'a NextjsRoute {
+    routes
+        .iter()
+        .find(|route| route.path == path)
+        .unwrap_or_else(|| panic!("missing route {path}"))
+}
+
+#[test]
+fn test_routes_follow_app_directory_layout() {
+    let routes = extract_nextjs_routes(&app_dir());
+    let paths: Vec<&str> = routes.iter().map(|route| route.path.as_str()).collect();
+
+    // (marketing) and @modal add no URL segment; _components is private.
+    assert_eq!(
+        paths,
+        [
+            "/",
+            "/about",
+            "/api/users",
+            "/dashboard",
+            "/dashboard/[id]",
+            "/login"
+        ]
+    );
+    assert!(route(&routes, "/api/users").file.ends_with("route.ts"));
+}
+
+#[test]
+fn test_routes_inherit_layouts_and_boundaries() {
+    let app = app_dir();
+    let routes = extract_nextjs_routes(&app);
+
+    let item = route(&routes, "/dashboard/[id]");
+    assert!(item.has_loading);
+    assert!(item.has_error);
+    assert_eq!(
+        item.layout_files,
+        [
+            app.join("layout.tsx"),
+            app.join("dashboard").join("layout.tsx")
+        ]
+    );
+
+    let home = route(&routes, "/");
+    assert!(!home.has_loading);
+    assert!(home.has_error);
+    assert_eq!(home.layout_files, [app.join("layout.tsx")]);
+}
+
+#[test]
+fn test_routes_list_exported_functions() {
+    let routes = extract_nextjs_routes(&app_dir());
+
+    assert_eq!(
+        route(&routes, "/dashboard/[id]").exported_functions,
+        ["generateMetadata", "default"]
+    );
+    assert_eq!(
+        route(&routes, "/api/users").exported_functions,
+        ["GET", "POST"]
+    );
+}
+
+#[test]
+fn test_list_routes_tool_accepts_project_root() {
+    let root = common::fixture_dir("nextjs");
+    let result = nextjs::execute(&json!({ "path": root.to_str().unwrap() })).unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["h"], "route|file|loading|error|layouts|exports");
+    let rows = common::helpers::parse_compact_rows(output["routes"].as_str().unwrap());
+    let dashboard = rows.iter().find(|row| row[0] == "/dashboard").unwrap();
+    assert!(dashboard[1].ends_with("app/dashboard/page.tsx"));
+    assert_eq!(dashboard[2], "1");
+    assert_eq!(dashboard[3], "1");
+    assert_eq!(dashboard[5], "default");
+}
+
+#[test]
+fn test_list_routes_without_app_directory_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let result = nextjs::execute(&json!({ "path": dir.path().to_str().unwrap() }));
+    assert!(result.is_err());
+}
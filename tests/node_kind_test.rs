@@ -0,0 +1,102 @@
+mod common;
+
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::node_kind::{classify_node, NodeKind};
+use treesitter_mcp::parser::Language;
+
+#[test]
+fn test_classify_node_maps_equivalent_kinds_across_languages() {
+    assert_eq!(
+        classify_node("function_item", Language::Rust),
+        NodeKind::FunctionDefinition
+    );
+    assert_eq!(
+        classify_node("function_definition", Language::Python),
+        NodeKind::FunctionDefinition
+    );
+    assert_eq!(
+        classify_node("call_expression", Language::TypeScript),
+        NodeKind::FunctionCall
+    );
+    assert_eq!(
+        classify_node("call", Language::Python),
+        NodeKind::FunctionCall
+    );
+    assert_eq!(
+        classify_node("use_declaration", Language::Rust),
+        NodeKind::Import
+    );
+    assert_eq!(
+        classify_node("import_from_statement", Language::Python),
+        NodeKind::Import
+    );
+    assert_eq!(
+        classify_node("type_declaration", Language::Go),
+        NodeKind::TypeDefinition
+    );
+    assert_eq!(
+        classify_node("line_comment", Language::Rust),
+        NodeKind::Comment
+    );
+    assert_eq!(
+        classify_node("not_a_real_kind", Language::Java),
+        NodeKind::Other
+    );
+}
+
+#[test]
+fn test_classify_node_resolves_grammar_specific_names() {
+    // `attribute` is `obj.attr` in Python but the inside of `#[...]` in Rust.
+    assert_eq!(
+        classify_node("attribute", Language::Python),
+        NodeKind::FieldAccess
+    );
+    assert_eq!(
+        classify_node("attribute", Language::Rust),
+        NodeKind::Decorator
+    );
+    assert_eq!(
+        classify_node("import_statement", Language::Css),
+        NodeKind::Import
+    );
+    assert_eq!(classify_node("identifier", Language::Html), NodeKind::Other);
+}
+
+#[test]
+fn test_symbol_at_line_reports_raw_and_semantic_kind() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    fs::write(
+        &file,
+        "fn total(items: &[u32]) -> u32 {\n    items.iter().sum()\n}\n",
+    )
+    .unwrap();
+
+    let result = treesitter_mcp::analysis::symbol_at_line::execute(&json!({
+        "file_path": file.to_str().unwrap(),
+        "line": 2,
+        "column": 5
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["sym"], "total");
+    assert_eq!(output["node"], "identifier");
+    assert_eq!(output["nk"], "identifier");
+
+    let result = treesitter_mcp::analysis::symbol_at_line::execute(&json!({
+        "file_path": file.to_str().unwrap(),
+        "line": 2,
+        "column": 5,
+        "scope": "enclosing_function"
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["node"], "function_item");
+    assert_eq!(output["nk"], "function_definition");
+}
@@ -421,3 +421,62 @@ fn test_parse_file_python_code_matches_fixture() {
     // the full `code` block should still contain the docstring.
     assert!(code.contains("Adds two numbers together"));
 }
+
+#[test]
+fn test_view_code_group_by_trait_inserts_separator_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    let file_path = dir.path().join("point.rs");
+    std::fs::write(
+        &file_path,
+        r#"
+pub struct Point { x: i32 }
+
+impl Point {
+    pub fn new(x: i32) -> Self { Self { x } }
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { write!(f, "{}", self.x) }
+}
+
+impl Point {
+    pub fn x(&self) -> i32 { self.x }
+}
+"#,
+    )
+    .unwrap();
+
+    let result = treesitter_mcp::analysis::view_code::execute(&json!({
+        "file_path": file_path.to_str().unwrap(),
+        "detail": "signatures",
+        "include_deps": false,
+        "group_by_trait": true
+    }))
+    .unwrap();
+    let shape: serde_json::Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(shape["bh"], "impl|trait|name|line|sig");
+    let lines: Vec<&str> = shape["bm"].as_str().unwrap().lines().collect();
+    assert_eq!(lines[0], "--- trait: - ---");
+    assert!(lines[1].contains("|new|"));
+    assert!(lines[2].contains("|x|"));
+    assert_eq!(lines[3], "--- trait: Display ---");
+    assert!(lines[4].contains("|fmt|"));
+    assert_eq!(lines.len(), 5);
+}
+
+#[test]
+fn test_view_code_without_group_by_trait_has_no_separators() {
+    let file_path = common::fixture_path("rust", "src/models/mod.rs");
+    let result = treesitter_mcp::analysis::view_code::execute(&json!({
+        "file_path": file_path.to_str().unwrap(),
+        "detail": "signatures",
+        "include_deps": false
+    }))
+    .unwrap();
+    let shape: serde_json::Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    let rows = shape["bm"].as_str().unwrap_or("");
+    assert!(!rows.is_empty());
+    assert!(!rows.contains("--- trait:"));
+}
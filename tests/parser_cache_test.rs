@@ -0,0 +1,125 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use tempfile::TempDir;
+use treesitter_mcp::parser::cache::{CachedParser, CACHE_SIZE_VAR};
+use treesitter_mcp::parser::Language;
+
+fn parse(cache: &CachedParser, path: &Path) -> Arc<(tree_sitter::Tree, String)> {
+    let source = fs::read_to_string(path).unwrap();
+    cache.parse(path, &source, Language::Rust).unwrap()
+}
+
+#[test]
+fn cache_hit_returns_the_same_tree() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    fs::write(&file, "fn main() {}\n").unwrap();
+
+    let cache = CachedParser::new(4);
+    let first = parse(&cache, &file);
+    let second = parse(&cache, &file);
+
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(first.0.root_node().id(), second.0.root_node().id());
+    assert_eq!(cache.tree_count(), 1);
+}
+
+#[test]
+fn modifying_the_file_invalidates_the_entry() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    fs::write(&file, "fn main() {}\n").unwrap();
+
+    let cache = CachedParser::new(4);
+    let before = parse(&cache, &file);
+
+    // Make sure the modification time moves even on coarse filesystems.
+    thread::sleep(Duration::from_millis(20));
+    fs::write(&file, "fn main() {}\nfn helper() {}\n").unwrap();
+    let after = parse(&cache, &file);
+
+    assert!(!Arc::ptr_eq(&before, &after));
+    assert_eq!(after.1, "fn main() {}\nfn helper() {}\n");
+    assert_eq!(after.0.root_node().named_child_count(), 2);
+    // The stale tree is dropped rather than kept alongside the new one.
+    assert_eq!(cache.tree_count(), 1);
+}
+
+#[test]
+fn least_recently_used_tree_is_evicted() {
+    let dir = TempDir::new().unwrap();
+    let files: Vec<_> = ["a.rs", "b.rs", "c.rs"]
+        .iter()
+        .map(|name| {
+            let file = dir.path().join(name);
+            fs::write(&file, format!("// {name}\nfn f() {{}}\n")).unwrap();
+            file
+        })
+        .collect();
+
+    let cache = CachedParser::new(2);
+    let a = parse(&cache, &files[0]);
+    let b = parse(&cache, &files[1]);
+    // Touch `a` so `b` becomes the least recently used entry.
+    assert!(Arc::ptr_eq(&a, &parse(&cache, &files[0])));
+    parse(&cache, &files[2]);
+
+    assert_eq!(cache.tree_count(), 2);
+    assert!(Arc::ptr_eq(&a, &parse(&cache, &files[0])));
+    assert!(!Arc::ptr_eq(&b, &parse(&cache, &files[1])));
+}
+
+#[test]
+fn zero_capacity_disables_caching() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    fs::write(&file, "fn main() {}\n").unwrap();
+
+    let cache = CachedParser::new(0);
+    let first = parse(&cache, &file);
+    let second = parse(&cache, &file);
+
+    assert!(!Arc::ptr_eq(&first, &second));
+    assert_eq!(cache.tree_count(), 0);
+}
+
+#[test]
+fn cache_size_comes_from_environment() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    fs::write(&file, "fn main() {}\n").unwrap();
+
+    std::env::set_var(CACHE_SIZE_VAR, "0");
+    let cache = CachedParser::from_env();
+    std::env::remove_var(CACHE_SIZE_VAR);
+
+    parse(&cache, &file);
+    assert_eq!(cache.tree_count(), 0);
+}
+
+#[test]
+fn code_map_and_find_usages_share_the_process_cache() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    fs::write(&file, "pub fn shared_tree() {}\n").unwrap();
+    let path = file.to_str().unwrap();
+    // Sized explicitly so the environment test above cannot disable it.
+    let cache = treesitter_mcp::parser::cache::init_shared(16);
+
+    treesitter_mcp::analysis::code_map::execute(&serde_json::json!({ "path": path })).unwrap();
+    let after_code_map = cache.tree_count();
+    let cached = parse(&cache, &file);
+
+    // A hit adds nothing, and find_usages gets the very same tree back.
+    assert_eq!(cache.tree_count(), after_code_map);
+    treesitter_mcp::analysis::find_usages::execute(&serde_json::json!({
+        "symbol": "shared_tree",
+        "path": path,
+    }))
+    .unwrap();
+    assert!(Arc::ptr_eq(&cached, &parse(&cache, &file)));
+}
@@ -5,8 +5,11 @@
 //! - Detection of all supported languages (Rust, Python, JavaScript, TypeScript, HTML, CSS, Swift, C#, Java)
 //! - Case-insensitive extension matching
 //! - Error handling for unsupported and missing extensions
+//! - Shebang fallback for extensionless scripts
 
-use treesitter_mcp::parser::{detect_language, parse_code, Language};
+mod common;
+
+use treesitter_mcp::parser::{detect_language, detect_language_from_content, parse_code, Language};
 
 /// Test that Rust files (.rs) are correctly detected
 ///
@@ -48,15 +51,22 @@ fn test_detect_language_from_typescript_file() {
     assert_eq!(lang, Language::TypeScript);
 }
 
-/// Test that TypeScript React files (.tsx) are correctly detected as TypeScript
+/// Test that TypeScript React files (.tsx) are detected as TSX
 ///
 /// Verifies that the `detect_language` function properly identifies TypeScript React component files
-/// by their .tsx extension. TSX files are TypeScript files with JSX syntax and should be parsed
-/// using the TypeScript grammar.
+/// by their .tsx extension. TSX files are TypeScript files with JSX syntax and are parsed
+/// using the TSX grammar, so they get their own language.
 #[test]
 fn test_detect_language_from_tsx_file() {
     let lang = detect_language("component.tsx").unwrap();
-    assert_eq!(lang, Language::TypeScript);
+    assert_eq!(lang, Language::Tsx);
+}
+
+/// Test that JavaScript React files (.jsx) are detected as JSX
+#[test]
+fn test_detect_language_from_jsx_file() {
+    let lang = detect_language("component.jsx").unwrap();
+    assert_eq!(lang, Language::Jsx);
 }
 
 /// Test that HTML files (.html) are correctly detected
@@ -79,6 +89,15 @@ fn test_detect_language_from_css_file() {
     assert_eq!(lang, Language::Css);
 }
 
+/// Test that SCSS files (.scss) are not treated as CSS by default
+///
+/// SCSS has no grammar of its own; only `find_usages` reads it as CSS, on a
+/// best-effort basis, so general language detection must not claim it.
+#[test]
+fn test_detect_language_rejects_scss_file() {
+    assert!(detect_language("theme.scss").is_err());
+}
+
 /// Test that unsupported file extensions are rejected
 ///
 /// Verifies that the `detect_language` function returns an error when given a file
@@ -227,3 +246,63 @@ fn test_tree_sitter_language_java_parses_methods() {
     assert!(!root.has_error());
     assert!(root.to_sexp().contains("method"));
 }
+
+/// Test that extensionless scripts are detected from their shebang line
+///
+/// Verifies that `detect_language_from_content` follows `#!/usr/bin/env` to the
+/// interpreter it names, strips version suffixes such as `python3.12`, and keeps
+/// the extension-first behaviour for files that have one.
+#[test]
+fn test_detect_language_from_shebang() {
+    let cases = [
+        ("#!/usr/bin/env python3", Language::Python),
+        ("#!/usr/bin/python3.12 -u", Language::Python),
+        ("#!/usr/bin/node", Language::JavaScript),
+        ("#!/usr/bin/env -S deno run", Language::JavaScript),
+        ("#!/usr/bin/env ts-node", Language::TypeScript),
+    ];
+    for (shebang, expected) in cases {
+        let source = format!("{shebang}\nprint('hi')\n");
+        let lang = detect_language_from_content("bin/tool", &source)
+            .unwrap_or_else(|e| panic!("{shebang}: {e}"));
+        assert_eq!(lang, expected, "{shebang}");
+    }
+
+    // The extension wins over the shebang.
+    let lang = detect_language_from_content("tool.rs", "#!/usr/bin/env python3\n").unwrap();
+    assert_eq!(lang, Language::Rust);
+
+    assert!(detect_language_from_content("bin/tool", "#!/bin/sh\n").is_err());
+    assert!(detect_language_from_content("bin/tool", "echo hi\n").is_err());
+}
+
+/// Test that `detect_language` reads the shebang of extensionless files on disk
+#[test]
+fn test_detect_language_reads_shebang_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("deploy");
+    std::fs::write(&script, "#!/usr/bin/env python3\nimport sys\n").unwrap();
+
+    assert_eq!(detect_language(&script).unwrap(), Language::Python);
+}
+
+/// Test that `view_code` works on extensionless scripts without extra arguments
+#[test]
+fn test_view_code_on_extensionless_script() {
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("deploy");
+    std::fs::write(
+        &script,
+        "#!/usr/bin/env python3\n\ndef greet(name):\n    return name\n",
+    )
+    .unwrap();
+
+    let result = treesitter_mcp::analysis::view_code::execute(&serde_json::json!({
+        "file_path": script.to_str().unwrap(),
+        "include_deps": false
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+    assert!(output["f"].as_str().unwrap().contains("greet"));
+}
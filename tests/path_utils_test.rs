@@ -68,3 +68,198 @@ fn test_find_project_root_nested_deep() {
     assert!(root.is_some());
     assert!(root.unwrap().join("Cargo.toml").exists());
 }
+
+// ============================================================================
+// Binary File Detection
+// ============================================================================
+
+#[test]
+fn test_is_binary_file_detects_null_bytes_and_invalid_utf8() {
+    use treesitter_mcp::analysis::path_utils::is_binary_file;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let text = dir.path().join("lib.rs");
+    let nulls = dir.path().join("data.bin");
+    let latin1 = dir.path().join("notes.txt");
+    std::fs::write(&text, "fn main() { println!(\"héllo\"); }\n").unwrap();
+    std::fs::write(&nulls, [0x7f, b'E', b'L', b'F', 0, 0, 1, 2]).unwrap();
+    std::fs::write(&latin1, [b'c', b'a', b'f', 0xe9, b'\n'].repeat(20)).unwrap();
+
+    assert!(!is_binary_file(&text));
+    assert!(is_binary_file(&nulls));
+    assert!(is_binary_file(&latin1));
+    assert!(!is_binary_file(&dir.path().join("missing.rs")));
+}
+
+#[test]
+fn test_directory_walk_skips_binary_files() {
+    use treesitter_mcp::common::project_files::collect_project_files;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("lib.rs"), "pub struct Point { x: i32 }\n").unwrap();
+    std::fs::write(dir.path().join("blob.bin"), [0u8, 159, 146, 150, 0, 255]).unwrap();
+    std::fs::write(dir.path().join("module.wasm"), b"\0asm\x01\0\0\0").unwrap();
+    std::fs::write(dir.path().join("stale.rs"), [0u8, 1, 2, 3, 0xff]).unwrap();
+
+    let files = collect_project_files(dir.path()).unwrap();
+    let names: Vec<_> = files
+        .iter()
+        .map(|file| file.file_name().unwrap().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(names, vec!["lib.rs"]);
+
+    let result = treesitter_mcp::analysis::type_map::execute(&serde_json::json!({
+        "path": dir.path().to_str().unwrap()
+    }));
+    assert!(result.is_ok(), "binary files must not fail the scan");
+    assert!(common::get_result_text(&result.unwrap()).contains("Point"));
+}
+
+// ============================================================================
+// Source Decoding
+// ============================================================================
+
+#[test]
+fn test_read_source_file_decodes_utf16_and_windows_1252() {
+    use treesitter_mcp::analysis::path_utils::{read_source_file_with_encoding, SourceEncoding};
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let text = "class Café: pass\n";
+
+    let utf8 = dir.path().join("utf8.py");
+    std::fs::write(&utf8, text).unwrap();
+
+    let utf16_le = dir.path().join("le.py");
+    let mut bytes = vec![0xff, 0xfe];
+    bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+    std::fs::write(&utf16_le, bytes).unwrap();
+
+    let utf16_be = dir.path().join("be.py");
+    let mut bytes = vec![0xfe, 0xff];
+    bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+    std::fs::write(&utf16_be, bytes).unwrap();
+
+    let cp1252 = dir.path().join("cp1252.py");
+    std::fs::write(&cp1252, b"s = \x93quoted\x94 caf\xe9\n").unwrap();
+
+    let read = |path: &Path| read_source_file_with_encoding(path).unwrap();
+    assert_eq!(read(&utf8), (text.to_string(), SourceEncoding::Utf8));
+    assert_eq!(read(&utf16_le), (text.to_string(), SourceEncoding::Utf16Le));
+    assert_eq!(read(&utf16_be), (text.to_string(), SourceEncoding::Utf16Be));
+    assert_eq!(
+        read(&cp1252),
+        (
+            "s = \u{201c}quoted\u{201d} café\n".to_string(),
+            SourceEncoding::Windows1252
+        )
+    );
+
+    // UTF-16 files contain null bytes but are text
+    assert!(!treesitter_mcp::analysis::path_utils::is_binary_file(
+        &utf16_le
+    ));
+}
+
+// ============================================================================
+// Cargo Workspaces
+// ============================================================================
+
+#[test]
+fn test_find_workspace_root_prefers_workspace_over_member_manifest() {
+    use treesitter_mcp::analysis::path_utils::find_workspace_root;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let member = dir.path().join("crates/core");
+    std::fs::create_dir_all(member.join("src")).unwrap();
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    std::fs::write(member.join("Cargo.toml"), "[package]\nname = \"core\"\n").unwrap();
+    std::fs::write(member.join("src/lib.rs"), "pub struct Config;\n").unwrap();
+
+    let file = member.join("src/lib.rs");
+    assert_eq!(find_workspace_root(&file).as_deref(), Some(dir.path()));
+    assert_eq!(find_project_root(&file).as_deref(), Some(member.as_path()));
+}
+
+#[test]
+fn test_find_workspace_root_outside_workspace() {
+    use treesitter_mcp::analysis::path_utils::find_workspace_root;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"solo\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(find_workspace_root(dir.path()), None);
+}
+
+#[test]
+fn test_find_workspace_root_ignores_non_cargo_projects_inside_workspace() {
+    use treesitter_mcp::analysis::path_utils::find_workspace_root;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let web = dir.path().join("web");
+    std::fs::create_dir_all(&web).unwrap();
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    std::fs::write(web.join("package.json"), "{}\n").unwrap();
+    std::fs::write(web.join("index.ts"), "export const x = 1;\n").unwrap();
+
+    assert_eq!(find_workspace_root(&web.join("index.ts")), None);
+    assert_eq!(
+        find_project_root(&web.join("index.ts")).as_deref(),
+        Some(web.as_path())
+    );
+}
+
+#[test]
+fn test_find_workspace_root_requires_membership() {
+    use treesitter_mcp::analysis::path_utils::find_workspace_root;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/legacy\"]\n",
+    )
+    .unwrap();
+    for package in ["crates/legacy", "tools/gen"] {
+        let package_dir = dir.path().join(package);
+        std::fs::create_dir_all(&package_dir).unwrap();
+        std::fs::write(package_dir.join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+    }
+
+    assert_eq!(find_workspace_root(&dir.path().join("crates/legacy")), None);
+    assert_eq!(find_workspace_root(&dir.path().join("tools/gen")), None);
+}
+
+#[test]
+fn test_find_workspace_root_stops_at_non_member_manifest() {
+    use treesitter_mcp::analysis::path_utils::find_workspace_root;
+
+    // outer lists `inner/*`, but `inner` is a standalone package that does
+    // not declare `inner/member` as a member.
+    let dir = tempfile::TempDir::new().unwrap();
+    let member = dir.path().join("inner/member");
+    std::fs::create_dir_all(&member).unwrap();
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"inner/*\"]\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("inner/Cargo.toml"),
+        "[package]\nname = \"inner\"\n",
+    )
+    .unwrap();
+    std::fs::write(member.join("Cargo.toml"), "[package]\nname = \"member\"\n").unwrap();
+
+    assert_eq!(find_workspace_root(&member), None);
+}
@@ -68,3 +68,93 @@ fn main() {
         .iter()
         .any(|row| row[2].contains("main.rs") && row[4] == "high"));
 }
+
+#[test]
+fn test_preview_impact_reports_argument_type_mismatch_hints() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname='fixture'\nversion='0.1.0'\n",
+    )
+    .unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir(&src).unwrap();
+
+    let lib_path = src.join("lib.rs");
+    fs::write(
+        &lib_path,
+        r#"
+pub fn greet(name: String, times: u32) -> String {
+    name.repeat(times as usize)
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        src.join("main.rs"),
+        r#"
+mod lib;
+
+fn main() {
+    let a = lib::greet(String::from("ann"), 2);
+    let b = lib::greet(name_for(), 3);
+    let d = lib::greet("x".to_string(), 3);
+    let c = lib::greet("bob", 1);
+}
+"#,
+    )
+    .unwrap();
+
+    let result = treesitter_mcp::analysis::diff::execute_preview_impact(&json!({
+        "file_path": lib_path.to_str().unwrap(),
+        "symbol_name": "greet",
+        "new_signature": "pub fn greet(name: &str, times: u32) -> String",
+        "scope": dir.path().to_str().unwrap()
+    }))
+    .unwrap();
+
+    let text = common::get_result_text(&result);
+    let output: serde_json::Value = serde_json::from_str(&text).unwrap();
+
+    assert_eq!(output["mh"], "file|line|hint");
+    let hints = rows(&output, "mismatch");
+    assert_eq!(hints.len(), 3, "hints: {hints:?}");
+    assert_eq!(hints[0][1], "5");
+    assert_eq!(
+        hints[0][2],
+        "call at line 5 passes `String` but signature now expects `&str`"
+    );
+    assert_eq!(
+        hints[1][2],
+        "call at line 6 passes `name_for()` (written for `String`) but signature now expects `&str`"
+    );
+    assert_eq!(hints[2][1], "7");
+    assert!(
+        !hints.iter().any(|row| row[1] == "8"),
+        "`\"bob\"` already is a &str"
+    );
+}
+
+#[test]
+fn test_preview_impact_omits_mismatch_rows_without_parameter_type_changes() {
+    let dir = tempdir().unwrap();
+    let lib_path = dir.path().join("lib.rs");
+    fs::write(
+        &lib_path,
+        "pub fn calculate(x: i32) -> i32 {\n    x * 2\n}\n\nfn main() {\n    calculate(5);\n}\n",
+    )
+    .unwrap();
+
+    let result = treesitter_mcp::analysis::diff::execute_preview_impact(&json!({
+        "file_path": lib_path.to_str().unwrap(),
+        "symbol_name": "calculate",
+        "new_signature": "pub fn calculate(x: i32) -> i64",
+        "scope": dir.path().to_str().unwrap()
+    }))
+    .unwrap();
+
+    let text = common::get_result_text(&result);
+    let output: serde_json::Value = serde_json::from_str(&text).unwrap();
+
+    assert!(output.get("mismatch").is_none());
+}
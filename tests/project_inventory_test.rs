@@ -0,0 +1,135 @@
+mod common;
+
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+fn run_inventory(arguments: serde_json::Value) -> serde_json::Value {
+    let result = treesitter_mcp::analysis::symbol_inventory::execute(&arguments)
+        .unwrap_or_else(|e| panic!("project_inventory failed: {e}"));
+    let text = common::get_result_text(&result);
+    serde_json::from_str(&text).unwrap()
+}
+
+fn group_rows(output: &serde_json::Value, group: &str) -> Vec<Vec<String>> {
+    output["g"][group]
+        .as_str()
+        .unwrap_or("")
+        .lines()
+        .map(|line| line.split('|').map(ToString::to_string).collect())
+        .collect()
+}
+
+#[test]
+fn test_project_inventory_groups_by_file_with_visibility() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("lib.rs"),
+        "use std::fmt;\npub use std::io;\n\npub const MAX: usize = 3;\nstatic COUNT: u32 = 0;\n\npub struct Point { x: i32 }\n\nenum Mode { A }\n\npub fn run() {}\n\npub(crate) fn helper() {}\n\nfn private() {}\n",
+    )
+    .unwrap();
+
+    let output = run_inventory(json!({ "path": dir.path().to_str().unwrap() }));
+
+    assert_eq!(output["by"], "file");
+    assert_eq!(output["h"], "kind|name|line|lang|vis");
+    let (file, _) = output["g"].as_object().unwrap().iter().next().unwrap();
+    assert!(file.ends_with("lib.rs"));
+
+    let rows = group_rows(&output, file);
+    let find = |kind: &str, name: &str| {
+        rows.iter()
+            .find(|r| r[0] == kind && r[1] == name)
+            .unwrap_or_else(|| panic!("missing {kind} {name} in {rows:?}"))
+            .clone()
+    };
+
+    assert_eq!(find("fn", "run")[4], "public");
+    assert_eq!(find("fn", "helper")[4], "crate");
+    assert_eq!(find("fn", "private")[4], "private");
+    assert_eq!(find("type", "Point")[4], "public");
+    assert_eq!(find("type", "Mode")[4], "private");
+    assert_eq!(find("const", "MAX")[2], "4");
+    assert_eq!(find("const", "COUNT")[4], "private");
+    assert_eq!(find("import", "pub use std::io;")[4], "public");
+    assert_eq!(find("import", "use std::fmt;")[3], "Rust");
+}
+
+#[test]
+fn test_project_inventory_groups_by_kind_and_language() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("app.py"),
+        "import os\n\nLIMIT = 10\n\nclass Service:\n    def _hidden(self):\n        pass\n\ndef serve():\n    pass\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("util.go"),
+        "package util\n\nconst Version = \"1\"\n\nfunc Exported() {}\n\nfunc local() {}\n",
+    )
+    .unwrap();
+
+    let by_kind = run_inventory(json!({
+        "path": dir.path().to_str().unwrap(),
+        "group_by": "kind"
+    }));
+    assert_eq!(by_kind["h"], "name|file|line|lang|vis");
+
+    let functions = group_rows(&by_kind, "functions");
+    assert!(functions
+        .iter()
+        .any(|r| r[0] == "_hidden" && r[4] == "private"));
+    assert!(functions
+        .iter()
+        .any(|r| r[0] == "serve" && r[4] == "public"));
+    assert!(functions
+        .iter()
+        .any(|r| r[0] == "Exported" && r[4] == "public"));
+    assert!(functions
+        .iter()
+        .any(|r| r[0] == "local" && r[4] == "private"));
+
+    let constants = group_rows(&by_kind, "constants");
+    assert!(constants.iter().any(|r| r[0] == "LIMIT"));
+    assert!(constants.iter().any(|r| r[0] == "Version" && r[3] == "Go"));
+
+    assert!(group_rows(&by_kind, "types")
+        .iter()
+        .any(|r| r[0] == "Service"));
+    assert!(group_rows(&by_kind, "imports")
+        .iter()
+        .any(|r| r[0] == "import os"));
+
+    let by_language = run_inventory(json!({
+        "path": dir.path().to_str().unwrap(),
+        "group_by": "language"
+    }));
+    let groups = by_language["g"].as_object().unwrap();
+    assert!(groups.contains_key("Python"));
+    assert!(groups.contains_key("Go"));
+}
+
+#[test]
+fn test_project_inventory_respects_token_budget() {
+    let dir = common::fixture_dir("rust");
+
+    let output = run_inventory(json!({
+        "path": dir.to_str().unwrap(),
+        "max_tokens": 120
+    }));
+
+    assert_eq!(output["@"]["t"], true);
+    let text = serde_json::to_string(&output).unwrap();
+    let bpe = tiktoken_rs::cl100k_base().unwrap();
+    assert!(bpe.encode_with_special_tokens(&text).len() <= 120);
+}
+
+#[test]
+fn test_project_inventory_rejects_unknown_grouping() {
+    let dir = TempDir::new().unwrap();
+    let result = treesitter_mcp::analysis::symbol_inventory::execute(&json!({
+        "path": dir.path().to_str().unwrap(),
+        "group_by": "size"
+    }));
+    assert!(result.is_err());
+}
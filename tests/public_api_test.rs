@@ -0,0 +1,241 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::json;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::public_api::{public_api, ApiItem};
+
+const LIB_RS: &str = r#"pub mod config;
+
+/// Engine settings
+pub struct Engine {
+    pub name: String,
+    workers: usize,
+    pub(crate) cache: Vec<u8>,
+}
+
+pub enum Mode {
+    Fast,
+    Safe { retries: u32 },
+}
+
+pub trait Runner {
+    fn run(&self);
+}
+
+pub const MAX_WORKERS: usize = 8;
+pub static VERSION: &str = "1.0";
+
+impl Engine {
+    pub fn new(name: &str) -> Self {
+        todo!()
+    }
+
+    fn reset(&mut self) {}
+}
+
+impl Runner for Engine {
+    fn run(&self) {}
+}
+
+pub(crate) fn internal_helper() {}
+
+fn private_helper() {}
+
+pub mod util {
+    pub fn slugify(input: &str) -> String {
+        input.to_lowercase()
+    }
+}
+
+#[cfg(test)]
+pub fn test_fixture() -> Engine {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests;
+"#;
+
+const CONFIG_RS: &str = "pub type Port = u16;\n\npub(super) fn load() {}\n";
+
+const TESTS_RS: &str = "pub fn helper_only_for_tests() {}\n";
+
+fn rust_crate() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("lib.rs"), LIB_RS).unwrap();
+    fs::write(src.join("config.rs"), CONFIG_RS).unwrap();
+    fs::write(src.join("tests.rs"), TESTS_RS).unwrap();
+    fs::create_dir_all(dir.path().join("tests")).unwrap();
+    fs::write(
+        dir.path().join("tests").join("integration.rs"),
+        "pub fn shared_setup() {}\n",
+    )
+    .unwrap();
+    dir
+}
+
+fn names(items: &[ApiItem]) -> Vec<(&str, &str)> {
+    items
+        .iter()
+        .map(|item| (item.kind.as_str(), item.name.as_str()))
+        .collect()
+}
+
+fn find<'a>(items: &'a [ApiItem], name: &str) -> &'a ApiItem {
+    items
+        .iter()
+        .find(|item| item.name == name)
+        .unwrap_or_else(|| panic!("{name} not in {:?}", names(items)))
+}
+
+#[test]
+fn test_rust_public_items_across_module_tree() {
+    let dir = rust_crate();
+    let items = public_api(dir.path(), false).unwrap();
+
+    assert_eq!(
+        names(&items),
+        vec![
+            ("type", "Port"),
+            ("struct", "Engine"),
+            ("field", "Engine::name"),
+            ("enum", "Mode"),
+            ("variant", "Mode::Fast"),
+            ("variant", "Mode::Safe"),
+            ("trait", "Runner"),
+            ("const", "MAX_WORKERS"),
+            ("static", "VERSION"),
+            ("fn", "Engine::new"),
+            ("fn", "slugify"),
+        ]
+    );
+    assert!(items[0].file.ends_with("config.rs"));
+    assert!(items[1].file.ends_with("lib.rs"));
+}
+
+#[test]
+fn test_rust_signatures_and_lines() {
+    let dir = rust_crate();
+    let items = public_api(dir.path(), false).unwrap();
+
+    let engine = find(&items, "Engine");
+    assert_eq!(engine.signature, "pub struct Engine");
+    assert_eq!(engine.line, 4);
+    assert_eq!(
+        find(&items, "Engine::new").signature,
+        "pub fn new(name: &str) -> Self"
+    );
+    assert_eq!(find(&items, "Engine::name").signature, "pub name: String");
+    assert_eq!(
+        find(&items, "MAX_WORKERS").signature,
+        "pub const MAX_WORKERS: usize"
+    );
+    assert_eq!(find(&items, "Port").signature, "pub type Port = u16");
+}
+
+#[test]
+fn test_crate_visible_items_need_include_crate_pub() {
+    let dir = rust_crate();
+
+    let public = public_api(dir.path(), false).unwrap();
+    assert!(public
+        .iter()
+        .all(|item| !["internal_helper", "load", "Engine::cache"].contains(&item.name.as_str())));
+
+    let with_crate = public_api(dir.path(), true).unwrap();
+    let names = names(&with_crate);
+    assert!(names.contains(&("fn", "internal_helper")));
+    assert!(names.contains(&("fn", "load")));
+    assert!(names.contains(&("field", "Engine::cache")));
+    assert!(!names.contains(&("fn", "private_helper")));
+    assert!(!names.contains(&("fn", "Engine::reset")));
+}
+
+#[test]
+fn test_cfg_test_items_and_modules_are_excluded() {
+    let dir = rust_crate();
+    let items = public_api(dir.path(), true).unwrap();
+
+    let names = names(&items);
+    assert!(!names.contains(&("fn", "test_fixture")));
+    assert!(!names.contains(&("fn", "helper_only_for_tests")));
+    assert!(!names.contains(&("fn", "shared_setup")));
+}
+
+#[test]
+fn test_typescript_exports() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("api.ts");
+    fs::write(
+        &path,
+        r#"export interface User {
+  id: number;
+}
+
+export type UserId = User["id"];
+
+export class UserService {
+  find(id: UserId): User | undefined {
+    return undefined;
+  }
+}
+
+export function createUser(name: string): User {
+  return { id: 1 };
+}
+
+export const formatUser = (user: User): string => `${user.id}`;
+
+export const DEFAULT_ID = 0;
+
+function internal() {}
+"#,
+    )
+    .unwrap();
+
+    let items = public_api(&path, false).unwrap();
+
+    assert_eq!(
+        names(&items),
+        vec![
+            ("interface", "User"),
+            ("type", "UserId"),
+            ("class", "UserService"),
+            ("function", "createUser"),
+            ("function", "formatUser"),
+        ]
+    );
+    assert_eq!(
+        find(&items, "createUser").signature,
+        "function createUser(name: string): User"
+    );
+    assert_eq!(
+        find(&items, "formatUser").signature,
+        "const formatUser = (user: User): string =>"
+    );
+    assert_eq!(find(&items, "formatUser").line, 17);
+}
+
+#[test]
+fn test_public_api_tool_output() {
+    let dir = rust_crate();
+    let result = treesitter_mcp::analysis::public_api::execute_public_api(&json!({
+        "path": dir.path().to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["h"], "kind|name|signature|file|line");
+    let rows = common::helpers::parse_compact_rows(output["api"].as_str().unwrap());
+    assert_eq!(rows.len(), 11);
+    assert_eq!(rows[1][0], "struct");
+    assert_eq!(rows[1][1], "Engine");
+    assert!(Path::new(&rows[1][3]).ends_with("src/lib.rs"));
+    assert_eq!(rows[1][4], "4");
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use std::fs;
+
+use serde_json::json;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::import_graph::{module_name, python_import_graph};
+
+#[test]
+fn test_module_names_follow_packages() {
+    assert_eq!(
+        module_name(&common::fixture_path("python_imports", "shop/orders.py")),
+        ("shop.orders".to_string(), false)
+    );
+    assert_eq!(
+        module_name(&common::fixture_path(
+            "python_imports",
+            "shop/payments/__init__.py"
+        )),
+        ("shop.payments".to_string(), true)
+    );
+}
+
+#[test]
+fn test_relative_and_absolute_imports_are_resolved() {
+    let graph = python_import_graph(&common::fixture_dir("python_imports"))
+        .unwrap()
+        .graph;
+
+    assert_eq!(graph["shop.orders"], ["logging", "shop.billing"]);
+    assert_eq!(
+        graph["shop.billing"],
+        [
+            "decimal",
+            "typing",
+            "shop.customers",
+            "shop.payments.gateway"
+        ]
+    );
+    assert_eq!(graph["shop.customers"], ["shop.orders"]);
+    assert_eq!(graph["shop.payments.gateway"], ["json", "shop.customers"]);
+    assert!(graph["shop"].is_empty());
+}
+
+#[test]
+fn test_future_import_is_not_a_dependency() {
+    let graph = python_import_graph(&common::fixture_dir("python_imports"))
+        .unwrap()
+        .graph;
+
+    assert!(graph
+        .values()
+        .flatten()
+        .all(|module| module != "__future__"));
+}
+
+#[test]
+fn test_indirect_cycle_is_detected() {
+    let cycles = python_import_graph(&common::fixture_dir("python_imports"))
+        .unwrap()
+        .cycles;
+
+    assert_eq!(
+        cycles,
+        [[
+            "shop.billing",
+            "shop.customers",
+            "shop.orders",
+            "shop.billing"
+        ]]
+    );
+}
+
+#[test]
+fn test_acyclic_project_has_no_cycles() {
+    let dir = TempDir::new().unwrap();
+    let app = dir.path().join("app");
+    fs::create_dir_all(&app).unwrap();
+    fs::write(app.join("__init__.py"), "").unwrap();
+    fs::write(app.join("models.py"), "import dataclasses\n").unwrap();
+    fs::write(
+        app.join("views.py"),
+        "from app import models\n\ndef render():\n    from . import views\n",
+    )
+    .unwrap();
+
+    let import_graph = python_import_graph(dir.path()).unwrap();
+
+    assert_eq!(import_graph.graph["app.views"], ["app.models"]);
+    assert!(import_graph.cycles.is_empty());
+}
+
+#[test]
+fn test_python_import_graph_tool_output() {
+    let dir = common::fixture_dir("python_imports");
+    let result = treesitter_mcp::analysis::import_graph::execute_python_import_graph(&json!({
+        "path": dir.to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["graph"]["shop.customers"], json!(["shop.orders"]));
+    assert_eq!(
+        output["cycles"],
+        json!(["shop.billing -> shop.customers -> shop.orders -> shop.billing"])
+    );
+}
@@ -211,3 +211,144 @@ fn test_query_pattern_multiple_captures_is_supported() {
     let query_result: serde_json::Value = serde_json::from_str(&text).unwrap();
     assert!(query_result["m"].is_string());
 }
+
+// ============================================================================
+// Directory, Language and Capture Tests
+// ============================================================================
+
+fn run_query(arguments: serde_json::Value) -> serde_json::Value {
+    let result = treesitter_mcp::analysis::query_pattern::execute(&arguments).unwrap();
+    serde_json::from_str(&common::get_result_text(&result)).unwrap()
+}
+
+#[test]
+fn test_query_pattern_use_declarations_match_manual_extraction() {
+    let dir = common::fixture_dir("rust");
+    let result = run_query(json!({
+        "path": dir.to_str().unwrap(),
+        "language": "rust",
+        "query": "(use_declaration) @use"
+    }));
+
+    let mut found: Vec<(String, String)> = rows(&result)
+        .into_iter()
+        .map(|row| {
+            let file = std::path::Path::new(&row[0])
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string();
+            (file, row[3].clone())
+        })
+        .collect();
+    found.sort();
+
+    let mut expected = Vec::new();
+    for file in treesitter_mcp::common::project_files::collect_project_files(&dir).unwrap() {
+        if file.extension().and_then(|ext| ext.to_str()) != Some("rs") {
+            continue;
+        }
+        let name = file.file_name().unwrap().to_string_lossy().to_string();
+        for line in std::fs::read_to_string(&file).unwrap().lines() {
+            let line = line.trim();
+            if (line.starts_with("use ") || line.starts_with("pub use ")) && line.ends_with(';') {
+                expected.push((name.clone(), line.to_string()));
+            }
+        }
+    }
+    expected.sort();
+
+    assert!(
+        !expected.is_empty(),
+        "fixture should contain use declarations"
+    );
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn test_query_pattern_invalid_query_reports_error() {
+    let file_path = common::fixture_path("rust", "src/calculator.rs");
+    let err = treesitter_mcp::analysis::query_pattern::execute(&json!({
+        "path": file_path.to_str().unwrap(),
+        "language": "rust",
+        "query": "(function_item name: (identifier) @name"
+    }))
+    .unwrap_err();
+
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("Failed to parse query"));
+}
+
+#[test]
+fn test_query_pattern_capture_selects_nodes() {
+    let file_path = common::fixture_path("rust", "src/calculator.rs");
+    let result = run_query(json!({
+        "path": file_path.to_str().unwrap(),
+        "query": "(function_item name: (identifier) @name parameters: (parameters) @params)",
+        "capture": "params"
+    }));
+
+    let matches = rows(&result);
+    assert!(!matches.is_empty());
+    assert!(matches.iter().all(|row| row[3].starts_with('(')));
+}
+
+#[test]
+fn test_query_pattern_unknown_capture_is_error() {
+    let file_path = common::fixture_path("rust", "src/calculator.rs");
+    let err = treesitter_mcp::analysis::query_pattern::execute(&json!({
+        "path": file_path.to_str().unwrap(),
+        "query": "(function_item name: (identifier) @name)",
+        "capture": "missing"
+    }))
+    .unwrap_err();
+
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("name"));
+}
+
+#[test]
+fn test_query_pattern_directory_requires_language() {
+    let dir = common::fixture_dir("rust");
+    let err = treesitter_mcp::analysis::query_pattern::execute(&json!({
+        "path": dir.to_str().unwrap(),
+        "query": "(function_item)"
+    }))
+    .unwrap_err();
+
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_query_pattern_directory_skips_other_languages() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("lib.rs"), "fn one() {}\nfn two() {}\n").unwrap();
+    std::fs::write(dir.path().join("app.py"), "def three():\n    pass\n").unwrap();
+
+    let result = run_query(json!({
+        "path": dir.path().to_str().unwrap(),
+        "language": "Rust",
+        "query": "(function_item name: (identifier) @name)"
+    }));
+
+    let names: Vec<String> = rows(&result)
+        .into_iter()
+        .map(|row| row[3].clone())
+        .collect();
+    assert_eq!(names, vec!["one", "two"]);
+}
+
+#[test]
+fn test_query_pattern_caps_matches() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let source: String = (0..1200).map(|i| format!("fn f{i}() {{}}\n")).collect();
+    std::fs::write(dir.path().join("many.rs"), source).unwrap();
+
+    let result = run_query(json!({
+        "path": dir.path().join("many.rs").to_str().unwrap(),
+        "query": "(function_item name: (identifier) @name)"
+    }));
+
+    assert_eq!(rows(&result).len(), 1000);
+    assert_eq!(result["@"]["t"], true);
+}
@@ -0,0 +1,266 @@
+mod common;
+
+use std::fs;
+
+use serde_json::json;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::refactor::{execute_rename_symbol, rename_symbol};
+
+const LIB_RS: &str = r#"/// Calls compute on the input
+pub fn compute(value: i32) -> i32 {
+    value * 2
+}
+
+pub fn compute_all(values: &[i32]) -> Vec<i32> {
+    values.iter().map(|v| compute(*v)).collect()
+}
+"#;
+
+const MAIN_RS: &str = r#"use crate::compute;
+
+fn main() {
+    // compute is called twice
+    let a = compute(1);
+    let b = compute(a);
+    println!("compute: {}", b);
+}
+"#;
+
+fn project() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir_all(dir.path().join("src")).unwrap();
+    fs::write(dir.path().join("src/lib.rs"), LIB_RS).unwrap();
+    fs::write(dir.path().join("src/main.rs"), MAIN_RS).unwrap();
+    dir
+}
+
+#[test]
+fn test_rename_function_across_two_files() {
+    let dir = project();
+
+    let result = rename_symbol("compute", "calculate", dir.path(), false, false).unwrap();
+
+    assert_eq!(result.files_changed, 2);
+    assert_eq!(result.occurrences_renamed, 5);
+    assert!(result.skipped.is_empty());
+    assert_eq!(result.diff, None);
+
+    // Doc comments, line comments, strings and `compute_all` are untouched
+    assert_eq!(
+        fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(),
+        r#"/// Calls compute on the input
+pub fn calculate(value: i32) -> i32 {
+    value * 2
+}
+
+pub fn compute_all(values: &[i32]) -> Vec<i32> {
+    values.iter().map(|v| calculate(*v)).collect()
+}
+"#
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("src/main.rs")).unwrap(),
+        r#"use crate::calculate;
+
+fn main() {
+    // compute is called twice
+    let a = calculate(1);
+    let b = calculate(a);
+    println!("compute: {}", b);
+}
+"#
+    );
+}
+
+#[test]
+fn test_dry_run_leaves_files_unchanged() {
+    let dir = project();
+
+    let result = rename_symbol("compute", "calculate", dir.path(), true, false).unwrap();
+
+    assert_eq!(result.files_changed, 2);
+    assert_eq!(result.occurrences_renamed, 5);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(),
+        LIB_RS
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("src/main.rs")).unwrap(),
+        MAIN_RS
+    );
+}
+
+#[test]
+fn test_return_diff_lists_changed_lines() {
+    let dir = project();
+    let file = dir.path().join("src/main.rs");
+
+    let result = rename_symbol("compute", "calculate", &file, true, true).unwrap();
+    let diff = result.diff.unwrap();
+
+    assert!(diff.starts_with("--- a/"));
+    assert!(diff.contains("src/main.rs\n+++ b/"));
+    assert!(diff.ends_with(
+        "@@ -1 +1 @@\n\
+         -use crate::compute;\n\
+         +use crate::calculate;\n\
+         @@ -5,2 +5,2 @@\n\
+         -    let a = compute(1);\n\
+         -    let b = compute(a);\n\
+         +    let a = calculate(1);\n\
+         +    let b = calculate(a);\n"
+    ));
+}
+
+#[test]
+fn test_files_with_syntax_errors_are_skipped() {
+    let dir = project();
+    let broken = "fn broken( { compute(1) }\n";
+    fs::write(dir.path().join("src/broken.rs"), broken).unwrap();
+
+    let result = rename_symbol("compute", "calculate", dir.path(), false, false).unwrap();
+
+    assert_eq!(result.files_changed, 2);
+    assert_eq!(result.skipped.len(), 1);
+    assert!(result.skipped[0].ends_with("broken.rs: syntax errors"));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("src/broken.rs")).unwrap(),
+        broken
+    );
+}
+
+#[test]
+fn test_invalid_new_name_is_rejected() {
+    let dir = project();
+
+    for new_name in ["", "two words", "1st", "compute", "compute-all"] {
+        let err = rename_symbol("compute", new_name, dir.path(), true, false).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{new_name:?}");
+    }
+}
+
+#[test]
+fn test_name_invalid_for_one_language_writes_nothing() {
+    let dir = project();
+    let script = "import { compute } from './wasm';\nexport const twice = compute(21);\n";
+    fs::write(dir.path().join("app.ts"), script).unwrap();
+
+    let err = rename_symbol("compute", "$compute", dir.path(), false, false).unwrap_err();
+
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("Rust identifier"), "{err}");
+    assert_eq!(
+        fs::read_to_string(dir.path().join("app.ts")).unwrap(),
+        script
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(),
+        LIB_RS
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("src/main.rs")).unwrap(),
+        MAIN_RS
+    );
+}
+
+#[test]
+fn test_dollar_is_allowed_in_typescript() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("app.ts"),
+        "export function compute(x: number) { return x; }\ncompute(1);\n",
+    )
+    .unwrap();
+
+    let result = rename_symbol("compute", "$compute", dir.path(), false, false).unwrap();
+
+    assert_eq!(result.occurrences_renamed, 2);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("app.ts")).unwrap(),
+        "export function $compute(x: number) { return x; }\n$compute(1);\n"
+    );
+}
+
+#[test]
+fn test_rename_tool_defaults_to_dry_run() {
+    let dir = project();
+
+    let result = execute_rename_symbol(&json!({
+        "old_name": "compute",
+        "new_name": "calculate",
+        "path": dir.path().to_str().unwrap(),
+        "return_diff": true
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["dry_run"], true);
+    assert_eq!(output["files"], 2);
+    assert_eq!(output["renamed"], 5);
+    assert_eq!(output["skipped"], "");
+    assert!(output["diff"]
+        .as_str()
+        .unwrap()
+        .contains("+pub fn calculate("));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(),
+        LIB_RS
+    );
+}
+
+#[test]
+fn test_reserved_words_are_rejected_per_language() {
+    let dir = project();
+    fs::write(dir.path().join("tool.py"), "def compute():\n    pass\n").unwrap();
+
+    for new_name in ["fn", "match", "Self", "_"] {
+        let err = rename_symbol("compute", new_name, dir.path(), true, false).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{new_name:?}");
+    }
+    let err = rename_symbol("compute", "def", dir.path(), true, false).unwrap_err();
+    assert!(err.to_string().contains("reserved word in Python"), "{err}");
+
+    // Reserved elsewhere, but a valid name in both Rust and Python
+    rename_symbol("compute", "function", dir.path(), true, false).unwrap();
+}
+
+#[test]
+fn test_non_utf8_files_keep_their_encoding() {
+    let dir = TempDir::new().unwrap();
+    // Windows-1252: "é" is 0xE9. Enough ASCII that the file does not look
+    // binary to the directory walk.
+    let padding = "# plain ascii comment line\n".repeat(20);
+    let latin1_source = |name: &str| {
+        let mut bytes = format!("{padding}# r").into_bytes();
+        bytes.extend(b"\xe9sum\xe9\n");
+        bytes.extend(format!("def {name}():\n    pass\n").into_bytes());
+        bytes
+    };
+    let latin1 = dir.path().join("latin1.py");
+    fs::write(&latin1, latin1_source("compute")).unwrap();
+    let utf16 = dir.path().join("utf16.py");
+    let mut bytes = vec![0xff, 0xfe];
+    bytes.extend(
+        "# résumé\ncompute()\n"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes),
+    );
+    fs::write(&utf16, bytes).unwrap();
+
+    rename_symbol("compute", "calculate", dir.path(), false, false).unwrap();
+
+    assert_eq!(fs::read(&latin1).unwrap(), latin1_source("calculate"));
+    let mut expected = vec![0xff, 0xfe];
+    expected.extend(
+        "# résumé\ncalculate()\n"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes),
+    );
+    assert_eq!(fs::read(&utf16).unwrap(), expected);
+
+    // "ł" has no Windows-1252 byte, so nothing is written.
+    let err = rename_symbol("calculate", "całculate", dir.path(), false, false).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(fs::read(&latin1).unwrap(), latin1_source("calculate"));
+}
@@ -0,0 +1,114 @@
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::rust_edition::{
+    cached_manifest_count, detect_rust_edition, RustEdition, MANIFEST_CACHE_CAPACITY,
+};
+
+mod common;
+
+fn write_package(dir: &Path, manifest: &str) {
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+    fs::write(
+        dir.join("src/lib.rs"),
+        "extern crate serde;\nuse std::fmt;\n\npub fn run() {}\n",
+    )
+    .unwrap();
+}
+
+fn import_rows(lib: &Path) -> Vec<String> {
+    let arguments = json!({
+        "file_path": lib.to_str().unwrap(),
+        "detail": "signatures",
+        "include_deps": false
+    });
+    let result = treesitter_mcp::analysis::view_code::execute(&arguments).unwrap();
+    let view: serde_json::Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+    common::helpers::parse_compact_rows(view["im"].as_str().unwrap_or(""))
+        .into_iter()
+        .map(|row| row[1].clone())
+        .collect()
+}
+
+#[test]
+fn test_detect_rust_edition_reads_package_edition() {
+    let dir = TempDir::new().unwrap();
+    let manifest = dir.path().join("Cargo.toml");
+
+    fs::write(&manifest, "[package]\nname = \"a\"\nedition = \"2018\"\n").unwrap();
+    assert_eq!(detect_rust_edition(&manifest), RustEdition::E2018);
+
+    // Cargo treats a missing edition as 2015.
+    fs::write(&manifest, "[package]\nname = \"a\"\n").unwrap();
+    assert_eq!(detect_rust_edition(&manifest), RustEdition::E2015);
+
+    assert_eq!(
+        detect_rust_edition(&dir.path().join("missing/Cargo.toml")),
+        RustEdition::E2021
+    );
+}
+
+#[test]
+fn test_detect_rust_edition_follows_workspace_inheritance() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"member\"]\n\n[workspace.package]\nedition = \"2024\"\n",
+    )
+    .unwrap();
+    let member = dir.path().join("member");
+    write_package(
+        &member,
+        "[package]\nname = \"member\"\nedition.workspace = true\n",
+    );
+
+    assert_eq!(
+        detect_rust_edition(&member.join("Cargo.toml")),
+        RustEdition::E2024
+    );
+}
+
+#[test]
+fn test_extern_crate_is_import_only_in_2015() {
+    let dir = TempDir::new().unwrap();
+
+    let old = dir.path().join("old");
+    write_package(&old, "[package]\nname = \"old\"\nedition = \"2015\"\n");
+    let imports = import_rows(&old.join("src/lib.rs"));
+    assert_eq!(imports, ["extern crate serde;", "use std::fmt;"]);
+
+    let new = dir.path().join("new");
+    write_package(&new, "[package]\nname = \"new\"\nedition = \"2021\"\n");
+    let imports = import_rows(&new.join("src/lib.rs"));
+    assert_eq!(imports, ["use std::fmt;"]);
+}
+
+#[test]
+fn test_manifest_edits_are_seen_without_a_new_modification_time() {
+    let dir = TempDir::new().unwrap();
+    let manifest = dir.path().join("Cargo.toml");
+    fs::write(&manifest, "[package]\nname = \"a\"\nedition = \"2018\"\n").unwrap();
+    let modified = fs::metadata(&manifest).unwrap().modified().unwrap();
+    assert_eq!(detect_rust_edition(&manifest), RustEdition::E2018);
+
+    // Same size and modification time, different edition.
+    fs::write(&manifest, "[package]\nname = \"a\"\nedition = \"2021\"\n").unwrap();
+    let file = fs::File::options().write(true).open(&manifest).unwrap();
+    file.set_modified(modified).unwrap();
+    assert_eq!(detect_rust_edition(&manifest), RustEdition::E2021);
+}
+
+#[test]
+fn test_manifest_cache_is_bounded() {
+    let dir = TempDir::new().unwrap();
+    for i in 0..MANIFEST_CACHE_CAPACITY + 10 {
+        let manifest = dir.path().join(format!("p{i}/Cargo.toml"));
+        fs::create_dir_all(manifest.parent().unwrap()).unwrap();
+        fs::write(&manifest, "[package]\nname = \"p\"\nedition = \"2024\"\n").unwrap();
+        assert_eq!(detect_rust_edition(&manifest), RustEdition::E2024);
+    }
+
+    assert!(cached_manifest_count() <= MANIFEST_CACHE_CAPACITY);
+}
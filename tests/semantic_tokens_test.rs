@@ -0,0 +1,122 @@
+mod common;
+
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::semantic_tokens::{
+    self, classify_tokens, SemanticTokenType, MODIFIER_DECLARATION, MODIFIER_DOCUMENTATION,
+};
+use treesitter_mcp::parser::{parse_code, Language};
+
+/// `(text, type, modifiers)` for every token of `source`
+fn classify(source: &str, language: Language) -> Vec<(String, SemanticTokenType, u32)> {
+    let tree = parse_code(source, language).unwrap();
+    classify_tokens(&tree, source, language)
+        .into_iter()
+        .map(|token| {
+            let text = &source[token.start_byte..token.start_byte + token.length];
+            (text.to_string(), token.token_type, token.modifiers)
+        })
+        .collect()
+}
+
+fn token_type(tokens: &[(String, SemanticTokenType, u32)], text: &str) -> SemanticTokenType {
+    tokens
+        .iter()
+        .find(|(token, _, _)| token == text)
+        .unwrap_or_else(|| panic!("no token {text:?} in {tokens:?}"))
+        .1
+}
+
+const RUST_SOURCE: &str = r#"/// Adds numbers
+pub fn add(a: i32, b: i32) -> i32 {
+    // plain comment
+    let total = helper(a) + self::ops::run(b);
+    println!("{}", total);
+    total
+}
+"#;
+
+#[test]
+fn test_classifies_rust_tokens() {
+    let tokens = classify(RUST_SOURCE, Language::Rust);
+
+    assert_eq!(
+        tokens[0],
+        (
+            "/// Adds numbers\n".to_string(),
+            SemanticTokenType::Comment,
+            MODIFIER_DOCUMENTATION
+        )
+    );
+    assert_eq!(token_type(&tokens, "pub"), SemanticTokenType::Keyword);
+    assert_eq!(token_type(&tokens, "let"), SemanticTokenType::Keyword);
+    assert_eq!(token_type(&tokens, "i32"), SemanticTokenType::Type);
+    assert_eq!(token_type(&tokens, "helper"), SemanticTokenType::Function);
+    assert_eq!(token_type(&tokens, "println"), SemanticTokenType::Function);
+    // Only the last segment of a path callee is the function.
+    assert_eq!(token_type(&tokens, "ops"), SemanticTokenType::Variable);
+    assert_eq!(token_type(&tokens, "run"), SemanticTokenType::Function);
+    assert_eq!(token_type(&tokens, "\"{}\""), SemanticTokenType::String);
+
+    let comment = tokens.iter().find(|t| t.0 == "// plain comment").unwrap();
+    assert_eq!((comment.1, comment.2), (SemanticTokenType::Comment, 0));
+
+    let declared: Vec<&str> = tokens
+        .iter()
+        .filter(|t| t.2 & MODIFIER_DECLARATION != 0)
+        .map(|t| t.0.as_str())
+        .collect();
+    assert_eq!(declared, ["add", "a", "b", "total"]);
+
+    // Punctuation and operators are not tokens.
+    assert!(tokens.iter().all(|t| t.0 != "(" && t.0 != "+"));
+}
+
+#[test]
+fn test_classifies_python_and_go_tokens() {
+    let python = classify(
+        "class Foo:\n    def bar(self, x):\n        return len(x) + self.baz()\n",
+        Language::Python,
+    );
+    assert_eq!(token_type(&python, "Foo"), SemanticTokenType::Type);
+    assert_eq!(token_type(&python, "class"), SemanticTokenType::Keyword);
+    assert_eq!(token_type(&python, "bar"), SemanticTokenType::Function);
+    assert_eq!(token_type(&python, "len"), SemanticTokenType::Function);
+    assert_eq!(token_type(&python, "baz"), SemanticTokenType::Function);
+    assert_eq!(token_type(&python, "x"), SemanticTokenType::Variable);
+
+    let go = classify(
+        "package main\n\nfunc (s *Server) Run() { fmt.Println(`hi`) }\n",
+        Language::Go,
+    );
+    assert_eq!(token_type(&go, "func"), SemanticTokenType::Keyword);
+    assert_eq!(token_type(&go, "Server"), SemanticTokenType::Type);
+    assert_eq!(token_type(&go, "Run"), SemanticTokenType::Function);
+    assert_eq!(token_type(&go, "fmt"), SemanticTokenType::Variable);
+    assert_eq!(token_type(&go, "Println"), SemanticTokenType::Function);
+    assert_eq!(token_type(&go, "`hi`"), SemanticTokenType::String);
+}
+
+#[test]
+fn test_semantic_tokens_tool_rows() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    fs::write(&file, "// note\nfn main() {\n    run(\"x\");\n}\n").unwrap();
+
+    let result = semantic_tokens::execute(&json!({ "file_path": file.to_str().unwrap() })).unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["h"], "line|col|len|type|mods|text");
+    let rows = common::helpers::parse_compact_rows(output["tokens"].as_str().unwrap());
+    assert_eq!(
+        rows,
+        [
+            ["1", "1", "7", "comment", "", ""],
+            ["2", "1", "2", "keyword", "", "fn"],
+            ["2", "4", "4", "function", "declaration", "main"],
+            ["3", "5", "3", "function", "", "run"],
+            ["3", "9", "3", "string", "", ""],
+        ]
+    );
+}
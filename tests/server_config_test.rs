@@ -0,0 +1,131 @@
+use std::fs;
+use std::io::ErrorKind;
+
+use tempfile::TempDir;
+use treesitter_mcp::common::project_files::collect_project_files;
+use treesitter_mcp::common::tokenizer::TokenizerKind;
+use treesitter_mcp::config::{ServerConfig, TransportKind};
+use treesitter_mcp::handler::TreesitterServerHandler;
+
+#[test]
+fn test_loads_every_setting_from_toml() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("treesitter-mcp.toml");
+    fs::write(
+        &path,
+        r#"
+cache_size = 8
+extra_ignored_dirs = ["generated", "snapshots"]
+max_file_bytes = 4096
+transport = "ws"
+port = 7001
+allowed_origins = ["chrome-extension://trusted"]
+log_level = "debug"
+tokenizer = "o200k"
+"#,
+    )
+    .unwrap();
+
+    let config = ServerConfig::load(Some(&path)).unwrap();
+    assert_eq!(
+        config,
+        ServerConfig {
+            cache_size: 8,
+            extra_ignored_dirs: vec!["generated".to_string(), "snapshots".to_string()],
+            max_file_bytes: 4096,
+            transport: TransportKind::Ws,
+            port: 7001,
+            allowed_origins: vec!["chrome-extension://trusted".to_string()],
+            log_level: "debug".to_string(),
+            tokenizer: TokenizerKind::O200k,
+        }
+    );
+}
+
+#[test]
+fn test_missing_keys_use_defaults() {
+    let config = ServerConfig::from_toml_str("max_file_bytes = 10\n").unwrap();
+    assert_eq!(config.max_file_bytes, 10);
+    assert_eq!(config.transport, TransportKind::Stdio);
+    assert_eq!(config.port, 9229);
+    assert!(config.extra_ignored_dirs.is_empty());
+    assert!(config.allowed_origins.is_empty());
+    assert_eq!(config.tokenizer, TokenizerKind::Cl100k);
+}
+
+#[test]
+fn test_invalid_config_is_rejected() {
+    let unknown = ServerConfig::from_toml_str("cache_sise = 8\n").unwrap_err();
+    assert_eq!(unknown.kind(), ErrorKind::InvalidData);
+    assert!(unknown.to_string().contains("cache_sise"));
+
+    let bad_transport = ServerConfig::from_toml_str("transport = \"http\"\n").unwrap_err();
+    assert_eq!(bad_transport.kind(), ErrorKind::InvalidData);
+
+    let bad_tokenizer = ServerConfig::from_toml_str("tokenizer = \"claude\"\n").unwrap_err();
+    assert_eq!(bad_tokenizer.kind(), ErrorKind::InvalidData);
+
+    let missing =
+        ServerConfig::load(Some("/nonexistent/treesitter-mcp.toml".as_ref())).unwrap_err();
+    assert_eq!(missing.kind(), ErrorKind::NotFound);
+}
+
+#[test]
+fn test_configured_ignored_dirs_and_file_size_limit_apply_to_walks() {
+    let dir = TempDir::new().unwrap();
+    let project = dir.path().join("project");
+    fs::create_dir_all(project.join("generated")).unwrap();
+    fs::write(project.join("lib.rs"), "pub fn kept() {}\n").unwrap();
+    fs::write(
+        project.join("generated/bindings.rs"),
+        "pub fn skipped() {}\n",
+    )
+    .unwrap();
+    fs::write(project.join("huge.rs"), format!("// {}\n", "x".repeat(200))).unwrap();
+
+    let before = collect_project_files(&project).unwrap();
+    assert_eq!(before.len(), 3);
+
+    let config_path = dir.path().join("treesitter-mcp.toml");
+    fs::write(
+        &config_path,
+        "extra_ignored_dirs = [\"generated\"]\nmax_file_bytes = 100\n",
+    )
+    .unwrap();
+    let config = ServerConfig::load(Some(&config_path)).unwrap();
+    let _handler = TreesitterServerHandler::with_config(&config);
+
+    let after = collect_project_files(&project).unwrap();
+    assert_eq!(after, vec![project.join("lib.rs")]);
+}
+
+#[test]
+fn test_server_exits_nonzero_on_bad_config_or_flags() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("bad.toml");
+    fs::write(&path, "colour = \"blue\"\n").unwrap();
+    let server = |args: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_treesitter-mcp"))
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let unknown_key = server(&["--config", path.to_str().unwrap()]);
+    assert_eq!(unknown_key.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&unknown_key.stderr).contains("colour"));
+
+    let missing = server(&["--config", "/nonexistent/treesitter-mcp.toml"]);
+    assert_eq!(missing.status.code(), Some(2));
+
+    assert_eq!(
+        server(&["--transport", "carrier-pigeon"]).status.code(),
+        Some(2)
+    );
+    assert_eq!(
+        server(&["--transport", "ws", "--port", "http"])
+            .status
+            .code(),
+        Some(2)
+    );
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+fn stale_rows(path: &std::path::Path) -> Vec<Vec<String>> {
+    let result = treesitter_mcp::analysis::stale_imports::execute(&json!({
+        "path": path.to_str().unwrap()
+    }))
+    .unwrap();
+    let text = common::get_result_text(&result);
+    let output: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(output["h"], "file|line|import|unused");
+    common::helpers::parse_compact_rows(output["stale"].as_str().unwrap())
+}
+
+#[test]
+fn test_stale_imports_rust_skips_reexports_and_globs() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    fs::write(
+        &file,
+        "use std::collections::{HashMap, HashSet};\nuse std::fmt::Display as Show;\nuse std::io::*;\npub use std::path::PathBuf;\nuse std::fs;\n\nfn build() -> HashMap<String, u32> {\n    fs::read_to_string(\"x\").ok();\n    HashMap::new()\n}\n",
+    )
+    .unwrap();
+
+    let rows = stale_rows(&file);
+
+    assert_eq!(rows.len(), 2, "rows: {rows:?}");
+    assert_eq!(rows[0][1], "1");
+    assert_eq!(rows[0][3], "HashSet");
+    assert_eq!(rows[1][1], "2");
+    assert_eq!(rows[1][3], "Show");
+}
+
+#[test]
+fn test_stale_imports_python_respects_dunder_all() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("pkg.py"),
+        "import os\nimport json as j\nfrom typing import List, Dict\nfrom .models import User\n\n__all__ = [\"User\"]\n\ndef names() -> List[str]:\n    return os.listdir(\".\")\n",
+    )
+    .unwrap();
+
+    let rows = stale_rows(dir.path());
+    let unused: Vec<&str> = rows.iter().map(|row| row[3].as_str()).collect();
+
+    assert_eq!(unused, vec!["j", "Dict"]);
+}
+
+#[test]
+fn test_stale_imports_typescript_named_default_and_side_effect() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("app.ts"),
+        "import React from 'react';\nimport { useState, useEffect as onEffect } from 'react';\nimport * as path from 'path';\nimport './styles.css';\n\nexport function App() {\n  const [v] = useState(0);\n  return path.join(String(v));\n}\n",
+    )
+    .unwrap();
+
+    let rows = stale_rows(dir.path());
+    let unused: Vec<&str> = rows.iter().map(|row| row[3].as_str()).collect();
+
+    assert_eq!(unused, vec!["React", "onEffect"]);
+}
+
+#[test]
+fn test_stale_imports_go_aliases_and_blank_imports() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("main.go"),
+        "package main\n\nimport (\n\t\"fmt\"\n\tstr \"strings\"\n\t_ \"embed\"\n\t\"os\"\n)\n\nfunc main() {\n\tfmt.Println(str.ToUpper(\"x\"))\n}\n",
+    )
+    .unwrap();
+
+    let rows = stale_rows(dir.path());
+    let unused: Vec<&str> = rows.iter().map(|row| row[3].as_str()).collect();
+
+    assert_eq!(unused, vec!["os"]);
+}
@@ -0,0 +1,199 @@
+use std::fs;
+use std::io;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+use rust_mcp_sdk::schema::CallToolRequestParams;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use treesitter_mcp::common::cancel::CancellationToken;
+use treesitter_mcp::handler::TreesitterServerHandler;
+use treesitter_mcp::mcp::streaming::StreamingWriter;
+use treesitter_mcp::mcp::ws_transport::process_message;
+
+fn tools_call(arguments: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "tools/call",
+        "params": {"name": "type_map", "arguments": arguments},
+    })
+}
+
+/// The NDJSON line carried by a progress notification
+fn streamed_line(notification: &Value) -> Value {
+    assert_eq!(notification["method"], "notifications/progress");
+    serde_json::from_str(notification["params"]["message"].as_str().unwrap()).unwrap()
+}
+
+#[test]
+fn test_ndjson_lines_end_with_total_count() {
+    let mut writer = StreamingWriter::new(Vec::new());
+    writer.write_ndjson_line(&json!({"name": "A"})).unwrap();
+    writer.write_ndjson_line(&json!({"name": "B"})).unwrap();
+    writer.finish().unwrap();
+
+    assert_eq!(writer.count(), 2);
+    assert_eq!(
+        String::from_utf8(writer.into_inner()).unwrap(),
+        "{\"name\":\"A\"}\n{\"name\":\"B\"}\n{\"total_count\":2}\n"
+    );
+}
+
+#[test]
+fn test_streamed_types_arrive_before_the_walk_completes() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("a.rs"), "pub struct Alpha {}\n").unwrap();
+    fs::write(dir.path().join("b.rs"), "pub struct Beta {}\n").unwrap();
+
+    let (notification_tx, notification_rx) = mpsc::sync_channel(0);
+    let (resume_tx, resume_rx) = mpsc::channel();
+    let mut message = tools_call(json!({"path": dir.path().to_str().unwrap(), "stream": true}));
+    message["params"]["_meta"] = json!({"progressToken": 7});
+    let server = Arc::new(TreesitterServerHandler::new());
+    let walk = thread::spawn(move || {
+        // Every notification waits until the test lets the walk go on.
+        let mut notify = |notification: Value| {
+            notification_tx
+                .send(notification)
+                .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+            resume_rx
+                .recv()
+                .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))
+        };
+        process_message(
+            &server,
+            &message,
+            &CancellationToken::default(),
+            &mut notify,
+        )
+    });
+
+    let first = notification_rx.recv().unwrap();
+    assert_eq!(first["params"]["progressToken"], 7);
+    assert_eq!(first["params"]["progress"], 1);
+    assert_eq!(streamed_line(&first)["name"], "Alpha");
+    assert!(!walk.is_finished());
+
+    // b.rs has not been read yet, so the stream must see this version.
+    fs::write(dir.path().join("b.rs"), "pub struct Gamma {}\n").unwrap();
+    resume_tx.send(()).unwrap();
+
+    let mut notifications = vec![first];
+    while let Ok(notification) = notification_rx.recv() {
+        notifications.push(notification);
+        resume_tx.send(()).unwrap();
+    }
+
+    assert_eq!(notifications.len(), 3);
+    assert_eq!(streamed_line(&notifications[1])["name"], "Gamma");
+    assert_eq!(streamed_line(&notifications[2]), json!({"total_count": 2}));
+    assert_eq!(notifications[2]["params"]["progress"], 3);
+
+    let response = walk.join().unwrap().unwrap();
+    assert_eq!(response["id"], 7);
+    assert_eq!(
+        response["result"]["content"][0]["text"],
+        json!({"total_count": 2}).to_string()
+    );
+}
+
+#[test]
+fn test_notifications_use_the_client_progress_token() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("a.rs"), "pub struct Alpha {}\n").unwrap();
+    let mut message = tools_call(json!({"path": dir.path().to_str().unwrap(), "stream": true}));
+    message["params"]["_meta"] = json!({"progressToken": "types-1"});
+
+    let mut notifications = Vec::new();
+    process_message(
+        &TreesitterServerHandler::new(),
+        &message,
+        &CancellationToken::default(),
+        &mut |notification| {
+            notifications.push(notification);
+            Ok(())
+        },
+    )
+    .unwrap();
+
+    assert_eq!(notifications.len(), 2);
+    assert!(notifications
+        .iter()
+        .all(|notification| notification["params"]["progressToken"] == "types-1"));
+}
+
+#[test]
+fn test_streams_without_a_progress_token_return_every_line_in_the_result() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("a.rs"), "pub struct Alpha {}\n").unwrap();
+    fs::write(dir.path().join("b.rs"), "pub struct Beta {}\n").unwrap();
+    let message = tools_call(json!({"path": dir.path().to_str().unwrap(), "stream": true}));
+
+    let mut notifications = 0;
+    let response = process_message(
+        &TreesitterServerHandler::new(),
+        &message,
+        &CancellationToken::default(),
+        &mut |_| {
+            notifications += 1;
+            Ok(())
+        },
+    )
+    .unwrap();
+
+    assert_eq!(notifications, 0);
+    let text = response["result"]["content"][0]["text"].as_str().unwrap();
+    let lines: Vec<Value> = text
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["name"], "Alpha");
+    assert_eq!(lines[1]["name"], "Beta");
+    assert_eq!(lines[2], json!({"total_count": 2}));
+}
+
+#[test]
+fn test_calls_without_stream_send_no_notifications() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("a.rs"), "pub struct Alpha {}\n").unwrap();
+    let server = TreesitterServerHandler::new();
+
+    let mut notifications = 0;
+    let response = process_message(
+        &server,
+        &tools_call(json!({"path": dir.path().to_str().unwrap()})),
+        &CancellationToken::default(),
+        &mut |_| {
+            notifications += 1;
+            Ok(())
+        },
+    )
+    .unwrap();
+
+    assert_eq!(notifications, 0);
+    let text = response["result"]["content"][0]["text"].as_str().unwrap();
+    let output: Value = serde_json::from_str(text).unwrap();
+    assert!(output["types"].as_str().unwrap().starts_with("Alpha|"));
+}
+
+#[test]
+fn test_stream_is_rejected_without_a_streaming_transport() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("a.rs"), "pub struct Alpha {}\n").unwrap();
+    let arguments = json!({"path": dir.path().to_str().unwrap(), "stream": true});
+    let request = CallToolRequestParams {
+        name: "type_map".to_string(),
+        arguments: arguments.as_object().cloned(),
+        meta: None,
+        task: None,
+    };
+
+    let err = TreesitterServerHandler::new()
+        .call_tool(request, &CancellationToken::default())
+        .unwrap_err();
+
+    assert!(err.to_string().contains("cannot stream"), "{err}");
+}
@@ -0,0 +1,145 @@
+use serde_json::json;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::svelte::{extract_svelte_shape, PropInfo};
+use treesitter_mcp::parser::{detect_language, parse_code, Language};
+
+mod common;
+
+const COUNTER: &str = r#"<script lang="ts">
+  import Button from './Button.svelte';
+  import { format } from '../lib/format';
+
+  export let count: number = 0;
+  export let label: string;
+  let internal = 1;
+
+  $: doubled = count * 2;
+  $: quadrupled = doubled * 2;
+  $: console.log(count);
+
+  function increment(): void {
+    count += 1;
+  }
+</script>
+
+<div class="counter">
+  <slot name="header" />
+  <Button on:click={increment}>{label}: {format(count)}</Button>
+  <button on:click={() => (count = 0)}>Reset</button>
+  <form on:submit|preventDefault={increment}>
+    <slot />
+  </form>
+</div>
+
+<style>
+  .counter { display: flex; }
+  .counter :global(.label) { color: red; }
+</style>
+"#;
+
+fn prop(name: &str, ty: Option<&str>, default: Option<&str>, line: usize) -> PropInfo {
+    PropInfo {
+        name: name.to_string(),
+        type_annotation: ty.map(str::to_string),
+        default: default.map(str::to_string),
+        line,
+    }
+}
+
+#[test]
+fn test_svelte_files_are_detected() {
+    assert_eq!(
+        detect_language("src/Counter.svelte").unwrap(),
+        Language::Svelte
+    );
+    assert_eq!(Language::from_name("svelte"), Some(Language::Svelte));
+}
+
+#[test]
+fn test_typescript_script_props_and_reactive_statements() {
+    let tree = parse_code(COUNTER, Language::Svelte).unwrap();
+    let shape = extract_svelte_shape(tree.root_node(), COUNTER).unwrap();
+
+    assert_eq!(
+        shape.props,
+        [
+            prop("count", Some("number"), Some("0"), 5),
+            prop("label", Some("string"), None, 6),
+        ]
+    );
+    assert_eq!(shape.reactive_vars, ["doubled", "quadrupled"]);
+    assert_eq!(
+        shape
+            .imports
+            .iter()
+            .map(|import| (import.line, import.text.as_str()))
+            .collect::<Vec<_>>(),
+        [
+            (2, "import Button from './Button.svelte';"),
+            (3, "import { format } from '../lib/format';"),
+        ]
+    );
+}
+
+#[test]
+fn test_template_slots_events_and_style_classes() {
+    let tree = parse_code(COUNTER, Language::Svelte).unwrap();
+    let shape = extract_svelte_shape(tree.root_node(), COUNTER).unwrap();
+
+    assert_eq!(shape.slots, ["header", "default"]);
+    assert_eq!(
+        shape.event_handlers,
+        [
+            "on:click={increment}",
+            "on:click={() => (count = 0)}",
+            "on:submit|preventDefault={increment}",
+        ]
+    );
+    assert_eq!(shape.style_classes, ["counter", "label"]);
+}
+
+#[test]
+fn test_svelte5_props_rune_in_javascript_script() {
+    let source = r#"<script>
+  let { title, size = 'md', onClose: close } = $props();
+  let open = $state(false);
+</script>
+
+<h1>{title}</h1>
+"#;
+    let tree = parse_code(source, Language::Svelte).unwrap();
+    let shape = extract_svelte_shape(tree.root_node(), source).unwrap();
+
+    assert_eq!(
+        shape.props,
+        [
+            prop("title", None, None, 2),
+            prop("size", None, Some("'md'"), 2),
+            prop("onClose", None, None, 2),
+        ]
+    );
+    assert!(shape.slots.is_empty());
+}
+
+#[test]
+fn test_svelte_shape_tool_output() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("Counter.svelte");
+    std::fs::write(&path, COUNTER).unwrap();
+
+    let result = treesitter_mcp::analysis::svelte::execute_svelte_shape(&json!({
+        "file_path": path.to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert!(output["file"].as_str().unwrap().ends_with("Counter.svelte"));
+    assert_eq!(output["ph"], "name|type|default|line");
+    let props = common::helpers::parse_compact_rows(output["props"].as_str().unwrap());
+    assert_eq!(props[0], ["count", "number", "0", "5"]);
+    assert_eq!(props[1], ["label", "string", "", "6"]);
+    assert_eq!(output["reactive"], "doubled\nquadrupled");
+    assert_eq!(output["slots"], "header\ndefault");
+    assert_eq!(output["ih"], "line|text");
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+fn symbol_at(path: &std::path::Path, line: u32, extra: serde_json::Value) -> serde_json::Value {
+    let mut arguments = json!({
+        "file_path": path.to_str().unwrap(),
+        "line": line,
+        "column": 9
+    });
+    for (key, value) in extra.as_object().unwrap() {
+        arguments[key] = value.clone();
+    }
+    let result = treesitter_mcp::analysis::symbol_at_line::execute(&arguments).unwrap();
+    serde_json::from_str(&common::get_result_text(&result)).unwrap()
+}
+
+const PYTHON_SOURCE: &str = r#"class Cart:
+    def total(self, items):
+        subtotal = 0
+        for item in items:
+            subtotal += item.price
+        return subtotal
+"#;
+
+#[test]
+fn test_symbol_at_line_leaf_scope_has_no_code() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("cart.py");
+    fs::write(&file, PYTHON_SOURCE).unwrap();
+
+    let output = symbol_at(&file, 5, json!({}));
+
+    assert_eq!(output["sym"], "total");
+    assert!(output.get("code").is_none());
+}
+
+#[test]
+fn test_symbol_at_line_enclosing_function_returns_full_body() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("cart.py");
+    fs::write(&file, PYTHON_SOURCE).unwrap();
+
+    let output = symbol_at(&file, 5, json!({"scope": "enclosing_function"}));
+
+    assert_eq!(output["sym"], "total");
+    assert_eq!(output["kind"], "fn");
+    assert_eq!(output["range"], "2-6");
+    let code = output["code"].as_str().unwrap();
+    assert!(code.starts_with("def total(self, items):"));
+    assert!(code.ends_with("return subtotal"));
+    assert!(output.get("@").is_none());
+}
+
+#[test]
+fn test_symbol_at_line_enclosing_class_respects_max_lines() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("cart.py");
+    fs::write(&file, PYTHON_SOURCE).unwrap();
+
+    let output = symbol_at(
+        &file,
+        5,
+        json!({"scope": "enclosing_class", "max_lines": 2}),
+    );
+
+    assert_eq!(output["sym"], "Cart");
+    assert_eq!(output["range"], "1-6");
+    assert_eq!(output["code"], "class Cart:\n    def total(self, items):");
+    assert_eq!(output["@"]["t"], true);
+}
+
+#[test]
+fn test_symbol_at_line_enclosing_function_from_rust_fixture() {
+    let file = common::fixture_path("rust", "src/calculator.rs");
+
+    let output = symbol_at(&file, 38, json!({"scope": "enclosing_function"}));
+
+    assert_eq!(output["sym"], "divide");
+    let code = output["code"].as_str().unwrap();
+    assert!(code.starts_with("pub fn divide(a: i32, b: i32) -> Option<i32> {"));
+    assert!(code.contains("Some(a / b)"));
+}
+
+#[test]
+fn test_symbol_at_line_enclosing_function_outside_function_errors() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("consts.py");
+    fs::write(&file, "LIMIT = 10\n").unwrap();
+
+    let result = treesitter_mcp::analysis::symbol_at_line::execute(&json!({
+        "file_path": file.to_str().unwrap(),
+        "line": 1,
+        "scope": "enclosing_function"
+    }));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_symbol_at_line_rejects_unknown_scope() {
+    let file = common::fixture_path("rust", "src/calculator.rs");
+
+    let result = treesitter_mcp::analysis::symbol_at_line::execute(&json!({
+        "file_path": file.to_str().unwrap(),
+        "line": 14,
+        "scope": "module"
+    }));
+
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
@@ -83,3 +83,60 @@ fn test_find_template_dependencies_nonexistent() {
     // Should not include nonexistent files
     assert_eq!(deps.len(), 0);
 }
+
+#[test]
+fn test_extract_template_variables_kinds_and_bound_names() {
+    use treesitter_mcp::analysis::askama::extract_template_variables;
+
+    let source = r#"
+<h1>{{ title | upper }}</h1>
+{%- for item in order.items %}
+  {{ item.name }} {{ loop.index }} {{ currency }}
+{%- endfor %}
+{% set total = order.total %}{{ total }}
+{% if user.is_admin %}{{ user.name }}{% elif not guest %}{% endif %}
+{{ macros::byline(author=post.author) }}
+"#;
+
+    let vars: Vec<(String, String, String)> = extract_template_variables(source)
+        .into_iter()
+        .map(|v| (v.name, v.access_path, v.usage_kind))
+        .collect();
+
+    let expected = [
+        ("title", "title", "scalar"),
+        ("order", "order.items", "iterator"),
+        ("currency", "currency", "scalar"),
+        ("user", "user.is_admin", "conditional"),
+        ("user", "user.name", "scalar"),
+        ("guest", "guest", "conditional"),
+    ];
+    let expected: Vec<(String, String, String)> = expected
+        .iter()
+        .map(|(a, b, c)| (a.to_string(), b.to_string(), c.to_string()))
+        .collect();
+    assert_eq!(vars, expected);
+}
+
+#[test]
+fn test_template_variables_follow_extends_hierarchy() {
+    use treesitter_mcp::analysis::askama::template_hierarchy_variables;
+
+    let template = PathBuf::from("tests/fixtures/tera_project/templates/index.html");
+    let vars = template_hierarchy_variables(&template);
+
+    let rows: Vec<(&str, &str, &str)> = vars
+        .iter()
+        .map(|(tpl, v)| (tpl.as_str(), v.access_path.as_str(), v.usage_kind.as_str()))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            ("index.html", "title", "scalar"),
+            ("index.html", "posts", "iterator"),
+            ("base.html", "site_name", "scalar"),
+            ("base.html", "user", "conditional"),
+            ("base.html", "user.name", "scalar"),
+        ]
+    );
+}
@@ -0,0 +1,108 @@
+use std::path::PathBuf;
+
+mod common;
+
+use serde_json::json;
+use treesitter_mcp::analysis::askama::{
+    detect_template_engine, find_tera_structs_for_template, TemplateEngine,
+};
+use treesitter_mcp::analysis::file_shape::find_template_dependencies;
+
+fn fixture() -> PathBuf {
+    PathBuf::from("tests/fixtures/tera_project")
+}
+
+#[test]
+fn test_detect_template_engine_from_cargo_toml() {
+    assert_eq!(detect_template_engine(&fixture()), TemplateEngine::Tera);
+    // No manifest: keep the Askama behaviour.
+    assert_eq!(
+        detect_template_engine(&PathBuf::from("tests/fixtures/askama_project")),
+        TemplateEngine::Askama
+    );
+}
+
+#[test]
+fn test_tera_render_with_from_serialize_of_local() {
+    let template = fixture().join("templates/index.html");
+    let structs = find_tera_structs_for_template(&template, &fixture()).unwrap();
+
+    assert_eq!(structs.len(), 1);
+    assert_eq!(structs[0].struct_name, "IndexPage");
+    assert!(structs[0].file_path.ends_with("src/main.rs"));
+    assert_eq!(structs[0].line, 9);
+
+    let posts = structs[0]
+        .fields
+        .iter()
+        .find(|f| f.name == "posts")
+        .unwrap();
+    assert_eq!(posts.field_type, "Vec<Post>");
+}
+
+#[test]
+fn test_tera_render_with_context_bound_to_parameter() {
+    let template = fixture().join("templates/post.html");
+    let structs = find_tera_structs_for_template(&template, &fixture()).unwrap();
+
+    assert_eq!(structs.len(), 1);
+    assert_eq!(structs[0].struct_name, "Post");
+    assert!(structs[0].file_path.ends_with("src/models.rs"));
+
+    let author = structs[0]
+        .fields
+        .iter()
+        .find(|f| f.name == "author")
+        .unwrap();
+    let nested = author.nested_definition.as_ref().unwrap();
+    assert_eq!(nested.type_name, "Author");
+}
+
+#[test]
+fn test_tera_manual_context_has_no_struct() {
+    let template = fixture().join("templates/author.html");
+    let structs = find_tera_structs_for_template(&template, &fixture()).unwrap();
+
+    assert!(structs.is_empty());
+}
+
+#[test]
+fn test_template_context_tool_uses_tera_for_tera_projects() {
+    let template = fixture().join("templates/post.html");
+    let result = treesitter_mcp::analysis::askama::execute(&json!({
+        "template_path": template.to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    let rows = common::helpers::parse_compact_rows(output["ctx"].as_str().unwrap());
+    assert!(rows.iter().any(|r| r[0] == "Post" && r[1] == "title"));
+    assert!(rows.iter().any(|r| r[0] == "Author" && r[1] == "name"));
+
+    assert_eq!(output["vh"], "name|path|kind|tpl");
+    let vars = common::helpers::parse_compact_rows(output["vars"].as_str().unwrap());
+    assert!(vars
+        .iter()
+        .any(|r| r[1] == "body" && r[2] == "scalar" && r[3] == "post.html"));
+    assert!(vars
+        .iter()
+        .any(|r| r[1] == "site_name" && r[3] == "base.html"));
+}
+
+#[test]
+fn test_find_template_dependencies_includes_imports() {
+    let templates_dir = fixture().join("templates");
+    let source = std::fs::read_to_string(templates_dir.join("index.html")).unwrap();
+
+    let dependencies = find_template_dependencies(&source, &templates_dir).unwrap();
+
+    let kinds: Vec<(&str, &str)> = dependencies
+        .iter()
+        .map(|d| (d.dependency_type.as_str(), d.name.as_str()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![("extends", "base.html"), ("import", "macros.html")]
+    );
+}
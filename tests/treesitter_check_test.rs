@@ -0,0 +1,142 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use tempfile::TempDir;
+
+const LIB_RS: &str = r#"/// Adds two numbers.
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+pub fn undocumented() {}
+
+fn private_helper() {}
+
+/// Classifies a value.
+pub fn classify(x: i32, y: i32) -> &'static str {
+    if x > 0 && y > 0 {
+        "both"
+    } else if x > 0 || y > 0 {
+        "one"
+    } else {
+        match x {
+            -1 => "minus one",
+            -2 => "minus two",
+            _ => "other",
+        }
+    }
+}
+"#;
+
+fn check(dir: &Path, args: &[&str], envs: &[(&str, &str)]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_treesitter_check"))
+        .args(args)
+        .current_dir(dir)
+        .env_remove("GITHUB_ACTIONS")
+        .envs(envs.iter().copied())
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn test_check_reports_missing_docs_and_complexity() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("lib.rs"), LIB_RS).unwrap();
+
+    let output = check(dir.path(), &["--max-complexity", "5", "lib.rs"], &[]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "lib.rs:6: Public function `undocumented` has no doc comment\n\
+         lib.rs:11: Function `classify` has cyclomatic complexity 7 (max 5)\n"
+    );
+}
+
+#[test]
+fn test_check_emits_github_annotations() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("lib.rs"), LIB_RS).unwrap();
+
+    let output = check(
+        dir.path(),
+        &["--no-doc-check", "--max-complexity", "5", "lib.rs"],
+        &[("GITHUB_ACTIONS", "true")],
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "::error file=lib.rs,line=11::Function `classify` has cyclomatic complexity 7 (max 5)\n"
+    );
+}
+
+#[test]
+fn test_check_reads_staged_files_and_passes_clean_ones() {
+    let dir = TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir.path())
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?} failed");
+    };
+    git(&["init", "-q"]);
+    fs::write(dir.path().join("lib.rs"), LIB_RS).unwrap();
+    fs::write(
+        dir.path().join("clean.rs"),
+        "/// Documented.\npub fn ok() {}\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("README.md"), "# notes\n").unwrap();
+
+    git(&["add", "clean.rs", "README.md"]);
+    let output = check(dir.path(), &[], &[]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "");
+
+    git(&["add", "lib.rs"]);
+    let output = check(dir.path(), &[], &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("lib.rs:6: Public function `undocumented`"));
+}
+
+#[test]
+fn test_check_internal_errors_exit_with_two() {
+    let dir = TempDir::new().unwrap();
+
+    let missing = check(dir.path(), &["missing.rs"], &[]);
+    assert_eq!(missing.status.code(), Some(2));
+
+    let bad_option = check(dir.path(), &["--max-complexity", "lots"], &[]);
+    assert_eq!(bad_option.status.code(), Some(2));
+
+    // Not a git repository and no files given.
+    let no_repo = check(dir.path(), &[], &[]);
+    assert_eq!(no_repo.status.code(), Some(2));
+}
+
+#[test]
+fn test_check_reads_latin1_files() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("legacy.rs"),
+        b"/// Prix en \xe9cus.\npub fn prix() {}\n\npub fn caf\xe9_free() {}\n",
+    )
+    .unwrap();
+
+    let output = check(dir.path(), &["legacy.rs"], &[]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "legacy.rs:4: Public function `caf\u{e9}_free` has no doc comment\n"
+    );
+}
@@ -0,0 +1,199 @@
+mod common;
+
+use serde_json::{json, Value};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::type_diff;
+use treesitter_mcp::extraction::types::{
+    diff_type_results, extract_types_with_options, FieldChange, TypeExtractionResult,
+};
+
+const BEFORE: &str = r#"
+pub struct User {
+    pub id: u32,
+    pub name: String,
+    pub nickname: String,
+}
+
+pub struct Session {
+    pub token: String,
+}
+
+pub enum Status {
+    Active,
+    Banned(String),
+}
+"#;
+
+const AFTER: &str = r#"
+pub struct User {
+    pub id: u64,
+    pub name: String,
+    pub email: String,
+}
+
+pub struct Invoice {
+    pub total: f64,
+}
+
+pub enum Status {
+    Active,
+    Banned(String),
+}
+"#;
+
+fn extract_source(dir: &TempDir, name: &str, source: &str) -> TypeExtractionResult {
+    let path = dir.path().join(name);
+    fs::write(&path, source).unwrap();
+    extract_types_with_options(&path, None, 0, false, false).unwrap()
+}
+
+fn git(dir: &TempDir, args: &[&str]) {
+    Command::new("git")
+        .args(args)
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+}
+
+#[test]
+fn test_diff_type_results_reports_added_removed_and_modified() {
+    let dir = TempDir::new().unwrap();
+    let before = extract_source(&dir, "before.rs", BEFORE);
+    let after = extract_source(&dir, "after.rs", AFTER);
+
+    let diff = diff_type_results(&before, &after);
+
+    let added: Vec<&str> = diff.added.iter().map(|ty| ty.name.as_str()).collect();
+    let removed: Vec<&str> = diff.removed.iter().map(|ty| ty.name.as_str()).collect();
+    assert_eq!(added, ["Invoice"]);
+    assert_eq!(removed, ["Session"]);
+
+    // Unchanged `Status` is not reported.
+    assert_eq!(diff.modified.len(), 1);
+    let user = &diff.modified[0];
+    assert_eq!(user.name, "User");
+    assert_eq!(user.added_fields[0].name, "email");
+    assert_eq!(user.removed_fields[0].name, "nickname");
+    assert_eq!(
+        user.changed_fields,
+        [FieldChange {
+            name: "id".to_string(),
+            before: "u32".to_string(),
+            after: "u64".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_diff_type_results_identical_snapshots_are_empty() {
+    let dir = TempDir::new().unwrap();
+    let snapshot = extract_source(&dir, "lib.rs", BEFORE);
+
+    let diff = diff_type_results(&snapshot, &snapshot);
+    assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.modified.is_empty());
+}
+
+#[test]
+fn test_diff_types_tool_compares_two_paths() {
+    let dir = TempDir::new().unwrap();
+    let old = dir.path().join("old.rs");
+    let new = dir.path().join("new.rs");
+    fs::write(&old, BEFORE).unwrap();
+    fs::write(&new, AFTER).unwrap();
+
+    let result = type_diff::execute(&json!({
+        "path": new.to_str().unwrap(),
+        "other_path": old.to_str().unwrap(),
+    }))
+    .unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert_eq!(output["h"], "change|kind|name|file|line");
+    let types = common::helpers::parse_compact_rows(output["types"].as_str().unwrap());
+    assert_eq!(types[0][..3], ["+", "struct", "Invoice"]);
+    assert_eq!(types[1][..3], ["-", "struct", "Session"]);
+
+    assert_eq!(output["fh"], "type|change|field|before|after");
+    let fields = common::helpers::parse_compact_rows(output["fields"].as_str().unwrap());
+    assert_eq!(
+        fields,
+        [
+            ["User", "+", "email", "", "String"],
+            ["User", "~", "id", "u32", "u64"],
+            ["User", "-", "nickname", "String", ""],
+        ]
+    );
+}
+
+#[test]
+fn test_diff_types_tool_compares_against_git_revision() {
+    let dir = TempDir::new().unwrap();
+    git(&dir, &["init"]);
+    git(&dir, &["config", "user.email", "test@test.com"]);
+    git(&dir, &["config", "user.name", "Test"]);
+    fs::create_dir(dir.path().join("src")).unwrap();
+    fs::write(dir.path().join("src").join("models.rs"), BEFORE).unwrap();
+    fs::write(
+        dir.path().join("src").join("legacy.rs"),
+        "pub struct Legacy;\n",
+    )
+    .unwrap();
+    git(&dir, &["add", "."]);
+    git(&dir, &["commit", "-m", "init"]);
+
+    fs::write(dir.path().join("src").join("models.rs"), AFTER).unwrap();
+    fs::remove_file(dir.path().join("src").join("legacy.rs")).unwrap();
+
+    // Single file
+    let file = dir.path().join("src").join("models.rs");
+    let result = type_diff::execute(&json!({
+        "path": file.to_str().unwrap(),
+        "compare_to": "HEAD",
+    }))
+    .unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+    assert_eq!(output["before"], "HEAD");
+    let fields = common::helpers::parse_compact_rows(output["fields"].as_str().unwrap());
+    assert_eq!(fields.len(), 3);
+
+    // Directory, including a file deleted since the revision
+    let src = dir.path().join("src");
+    let result = type_diff::execute(&json!({
+        "path": src.to_str().unwrap(),
+        "compare_to": "HEAD",
+    }))
+    .unwrap();
+    let output: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+    let types = common::helpers::parse_compact_rows(output["types"].as_str().unwrap());
+    let changes: Vec<(&str, &str, &str)> = types
+        .iter()
+        .map(|row| (row[0].as_str(), row[2].as_str(), row[3].as_str()))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            ("+", "Invoice", "models.rs"),
+            ("-", "Legacy", "legacy.rs"),
+            ("-", "Session", "models.rs"),
+        ]
+    );
+}
+
+#[test]
+fn test_diff_types_tool_requires_one_baseline() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("lib.rs");
+    fs::write(&path, BEFORE).unwrap();
+
+    let neither = type_diff::execute(&json!({ "path": path.to_str().unwrap() }));
+    assert!(neither.is_err());
+
+    let both = type_diff::execute(&json!({
+        "path": path.to_str().unwrap(),
+        "other_path": path.to_str().unwrap(),
+        "compare_to": "HEAD",
+    }));
+    assert!(both.is_err());
+}
@@ -3,7 +3,10 @@ mod common;
 use std::fs;
 use std::process::Command;
 use tempfile::TempDir;
-use treesitter_mcp::extraction::types::{extract_types_with_options, LimitHit, TypeKind};
+use treesitter_mcp::analysis::path_utils::SourceEncoding;
+use treesitter_mcp::extraction::types::{
+    extract_types_with_options, LimitHit, TypeExtractionOptions, TypeExtractor, TypeKind,
+};
 
 fn setup_git_repo() -> TempDir {
     let dir = TempDir::new().unwrap();
@@ -32,7 +35,7 @@ fn setup_git_repo() -> TempDir {
 #[test]
 fn extracts_typescript_interfaces_and_aliases() {
     let file_path = common::fixture_path("typescript", "types/models.ts");
-    let result = extract_types_with_options(&file_path, None, 1000, false)
+    let result = extract_types_with_options(&file_path, None, 1000, false, false)
         .expect("type extraction should succeed");
 
     let point = result
@@ -59,7 +62,7 @@ fn extracts_typescript_interfaces_and_aliases() {
 #[test]
 fn extracts_rust_struct_with_fields_and_methods() {
     let file_path = common::fixture_path("rust", "src/models/mod.rs");
-    let result = extract_types_with_options(&file_path, None, 1000, false)
+    let result = extract_types_with_options(&file_path, None, 1000, false, false)
         .expect("type extraction should succeed");
 
     let point = result
@@ -85,7 +88,7 @@ fn extracts_rust_struct_with_fields_and_methods() {
 #[test]
 fn extracts_go_structs_and_interfaces() {
     let file_path = common::fixture_path("go", "types/models.go");
-    let result = extract_types_with_options(&file_path, None, 1000, false)
+    let result = extract_types_with_options(&file_path, None, 1000, false, false)
         .expect("type extraction should succeed");
 
     let point = result
@@ -118,16 +121,165 @@ fn extracts_go_structs_and_interfaces() {
     assert!(members.iter().any(|member| member.name == "Subtract"));
 }
 
+#[test]
+fn extracts_go_defined_types_and_aliases() {
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("units.go");
+    fs::write(
+        &file_path,
+        r#"package units
+
+import "net/http"
+
+type Celsius float64
+
+type Handler func(w http.ResponseWriter,
+    r *http.Request) error
+
+type Temperature = Celsius
+
+type (
+    Point struct{ X, Y int }
+    Labels map[string]string
+)
+"#,
+    )
+    .unwrap();
+
+    let result = extract_types_with_options(&file_path, None, 1000, false, false).unwrap();
+    let find = |name: &str| result.types.iter().find(|t| t.name == name).unwrap();
+
+    let celsius = find("Celsius");
+    assert_eq!(celsius.kind, TypeKind::TypeAlias);
+    assert_eq!(celsius.signature, "Celsius float64");
+
+    let handler = find("Handler");
+    assert_eq!(handler.kind, TypeKind::TypeAlias);
+    assert_eq!(
+        handler.signature,
+        "Handler func(w http.ResponseWriter, r *http.Request) error"
+    );
+
+    let temperature = find("Temperature");
+    assert_eq!(temperature.kind, TypeKind::TypeAlias);
+    assert_eq!(temperature.signature, "Temperature = Celsius");
+
+    assert_eq!(find("Labels").signature, "Labels map[string]string");
+    // Struct specs are reported once, as structs.
+    let points: Vec<_> = result.types.iter().filter(|t| t.name == "Point").collect();
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].kind, TypeKind::Struct);
+}
+
 #[test]
 fn directory_scan_respects_pattern_and_limit() {
     let dir_path = common::fixture_dir("typescript");
-    let result = extract_types_with_options(&dir_path, Some("**/*.ts"), 1, false)
+    let result = extract_types_with_options(&dir_path, Some("**/*.ts"), 1, false, false)
         .expect("type extraction should succeed");
 
     assert!(result.types.len() <= 1);
     assert_eq!(result.limit_hit, Some(LimitHit::TypeLimit));
 }
 
+#[test]
+fn type_extractor_streams_until_limit() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("a.rs"), "pub struct A;\npub struct B;\n").unwrap();
+    fs::write(dir.path().join("b.rs"), "pub struct C;\n").unwrap();
+
+    let options = TypeExtractionOptions {
+        max_types: 2,
+        ..Default::default()
+    };
+    let mut extractor = TypeExtractor::new(dir.path(), options);
+    let first = extractor.next().unwrap().expect("first type");
+    assert!(["A", "B", "C"].contains(&first.name.as_str()));
+    assert_eq!(extractor.limit_hit(), None);
+
+    assert!(extractor.next().unwrap().is_ok());
+    assert!(extractor.next().is_none());
+    assert_eq!(extractor.limit_hit(), Some(LimitHit::TypeLimit));
+}
+
+#[test]
+fn type_extractor_collect_result_matches_wrapper() {
+    let dir_path = common::fixture_dir("typescript");
+    let options = TypeExtractionOptions {
+        pattern: Some("**/*.ts".to_string()),
+        max_types: 1,
+        ..Default::default()
+    };
+    let streamed = TypeExtractor::new(&dir_path, options)
+        .collect_result()
+        .expect("type extraction should succeed");
+    let wrapped = extract_types_with_options(&dir_path, Some("**/*.ts"), 1, false, false)
+        .expect("type extraction should succeed");
+
+    assert_eq!(streamed, wrapped);
+    assert_eq!(streamed.total_types, 2);
+    assert!(streamed.truncated);
+}
+
+fn write_many_type_files(dir: &TempDir) {
+    for i in 0..20 {
+        fs::write(
+            dir.path().join(format!("module_{i:02}.rs")),
+            format!(
+                "pub struct First{i};\npub enum Second{i} {{ A, B }}\npub trait Third{i} {{}}\n"
+            ),
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn parallel_collect_matches_sequential_iteration() {
+    let dir = TempDir::new().unwrap();
+    write_many_type_files(&dir);
+
+    let sequential = TypeExtractor::new(dir.path(), TypeExtractionOptions::default())
+        .collect::<eyre::Result<Vec<_>>>()
+        .expect("type extraction should succeed");
+    let parallel = extract_types_with_options(dir.path(), None, 0, false, false)
+        .expect("type extraction should succeed");
+
+    assert_eq!(sequential.len(), 60);
+    assert_eq!(parallel.types, sequential);
+    assert_eq!(parallel.limit_hit, None);
+}
+
+#[test]
+fn parallel_collect_respects_type_limit() {
+    let dir = TempDir::new().unwrap();
+    write_many_type_files(&dir);
+
+    let sequential: Vec<_> = TypeExtractor::new(
+        dir.path(),
+        TypeExtractionOptions {
+            max_types: 7,
+            ..Default::default()
+        },
+    )
+    .collect::<eyre::Result<_>>()
+    .expect("type extraction should succeed");
+    let parallel = extract_types_with_options(dir.path(), None, 7, false, false)
+        .expect("type extraction should succeed");
+
+    assert_eq!(parallel.types.len(), 7);
+    assert_eq!(parallel.types, sequential);
+    assert_eq!(parallel.total_types, 8);
+    assert!(parallel.truncated);
+    assert_eq!(parallel.limit_hit, Some(LimitHit::TypeLimit));
+}
+
+#[test]
+fn type_extractor_yields_error_for_missing_path() {
+    let mut extractor = TypeExtractor::new("/nonexistent/types", TypeExtractionOptions::default());
+    let err = extractor.next().unwrap().unwrap_err();
+    assert!(err.to_string().contains("Path does not exist"));
+    assert!(extractor.next().is_none());
+}
+
 #[test]
 fn directory_scan_respects_gitignore() {
     let dir = setup_git_repo();
@@ -136,9 +288,712 @@ fn directory_scan_respects_gitignore() {
     fs::write(dir.path().join("visible.rs"), "pub struct Visible;\n").unwrap();
     fs::write(dir.path().join("ignored.rs"), "pub struct Hidden;\n").unwrap();
 
-    let result = extract_types_with_options(dir.path(), Some("**/*.rs"), 100, false)
+    let result = extract_types_with_options(dir.path(), Some("**/*.rs"), 100, false, false)
         .expect("type extraction should succeed");
 
     assert!(result.types.iter().any(|ty| ty.name == "Visible"));
     assert!(result.types.iter().all(|ty| ty.name != "Hidden"));
 }
+
+#[test]
+fn resolve_field_types_points_fields_at_defining_file() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("order.rs"),
+        "pub struct Order {\n    pub items: Vec<OrderItem>,\n    pub note: String,\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("item.rs"),
+        "pub struct OrderItem {\n    pub sku: Sku,\n}\n\npub struct Sku(String);\n",
+    )
+    .unwrap();
+
+    let result = extract_types_with_options(dir.path(), None, 100, false, true)
+        .expect("type extraction should succeed");
+
+    let order = result.types.iter().find(|ty| ty.name == "Order").unwrap();
+    let fields = order.fields.as_ref().unwrap();
+    let items = fields.iter().find(|f| f.name == "items").unwrap();
+    assert_eq!(
+        items.resolved_to.as_deref(),
+        Some(std::path::Path::new("item.rs"))
+    );
+    let note = fields.iter().find(|f| f.name == "note").unwrap();
+    assert_eq!(note.resolved_to, None);
+
+    let unresolved = extract_types_with_options(dir.path(), None, 100, false, false)
+        .expect("type extraction should succeed");
+    let order = unresolved
+        .types
+        .iter()
+        .find(|ty| ty.name == "Order")
+        .unwrap();
+    assert!(order
+        .fields
+        .as_ref()
+        .unwrap()
+        .iter()
+        .all(|f| f.resolved_to.is_none()));
+}
+
+#[test]
+fn multi_line_signatures_keep_generics_and_where_clauses() {
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("cache.rs");
+    fs::write(
+        &file_path,
+        r#"pub struct Cache<K, V>
+where
+    K: Eq + std::hash::Hash,
+    V: Clone,
+{
+    entries: Vec<(K, V)>,
+}
+
+pub trait Store<
+    Key,
+    Value,
+>: Send
+{
+    fn get(&self, key: &Key) -> Option<Value>;
+}
+"#,
+    )
+    .unwrap();
+
+    let result = extract_types_with_options(&file_path, None, 1000, false, false)
+        .expect("type extraction should succeed");
+
+    let cache = result.types.iter().find(|ty| ty.name == "Cache").unwrap();
+    assert_eq!(
+        cache.signature,
+        "pub struct Cache<K, V> where K: Eq + std::hash::Hash, V: Clone,"
+    );
+    let store = result.types.iter().find(|ty| ty.name == "Store").unwrap();
+    assert_eq!(store.signature, "pub trait Store<Key, Value>: Send");
+}
+
+#[test]
+fn angular_decorators_tag_kind_and_metadata() {
+    let dir_path = common::fixture_dir("angular");
+    let result = extract_types_with_options(&dir_path, None, 100, false, false)
+        .expect("type extraction should succeed");
+    let find = |name: &str| {
+        result
+            .types
+            .iter()
+            .find(|ty| ty.name == name)
+            .unwrap_or_else(|| panic!("{name} should be extracted"))
+    };
+
+    let component = find("UserCardComponent");
+    assert_eq!(component.kind, TypeKind::AngularComponent);
+    let metadata = component.framework_metadata.as_ref().unwrap();
+    assert_eq!(metadata["selector"], "app-user-card");
+    assert_eq!(metadata["templateUrl"], "./user-card.component.html");
+    assert_eq!(metadata["styleUrls"], "['./user-card.component.css']");
+    assert!(component
+        .fields
+        .as_ref()
+        .unwrap()
+        .iter()
+        .any(|field| field.name == "expanded"));
+
+    let service = find("UserService");
+    assert_eq!(service.kind, TypeKind::AngularInjectable);
+    assert_eq!(
+        service.framework_metadata.as_ref().unwrap()["providedIn"],
+        "root"
+    );
+
+    let directive = find("HighlightDirective");
+    assert_eq!(directive.kind, TypeKind::AngularDirective);
+    assert_eq!(
+        directive.framework_metadata.as_ref().unwrap()["standalone"],
+        "true"
+    );
+
+    assert_eq!(find("TruncatePipe").kind, TypeKind::AngularPipe);
+
+    let user = find("User");
+    assert_eq!(user.kind, TypeKind::Interface);
+    assert!(user.framework_metadata.is_none());
+}
+
+#[test]
+fn django_models_report_field_types_and_key_options() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("models.py"),
+        r#"from django.db import models
+
+
+class Author(models.Model):
+    name = models.CharField(max_length=100)
+    email = models.EmailField(unique=True)
+    objects = models.Manager()
+
+
+class Book(models.Model):
+    title = models.CharField("Title", max_length=200)
+    author = models.ForeignKey(Author, on_delete=models.CASCADE, related_name="books")
+    tags = models.ManyToManyField("Tag")
+
+    class Meta:
+        ordering = ["title"]
+
+
+class Ebook(Book):
+    url = models.URLField(null=True)
+
+
+class Plain:
+    name = "not a model"
+"#,
+    )
+    .unwrap();
+
+    let result = extract_types_with_options(dir.path(), None, 100, false, false)
+        .expect("type extraction should succeed");
+    let find = |name: &str| {
+        result
+            .types
+            .iter()
+            .find(|ty| ty.name == name)
+            .unwrap_or_else(|| panic!("{name} should be extracted"))
+    };
+    let fields = |name: &str| -> Vec<(String, String)> {
+        find(name)
+            .fields
+            .iter()
+            .flatten()
+            .map(|field| (field.name.clone(), field.type_annotation.clone()))
+            .collect()
+    };
+
+    let author = find("Author");
+    assert_eq!(author.kind, TypeKind::Class);
+    assert_eq!(
+        author.framework_metadata.as_ref().unwrap()["framework"],
+        "django"
+    );
+    assert_eq!(
+        fields("Author"),
+        [
+            ("name".to_string(), "CharField(max_length=100)".to_string()),
+            ("email".to_string(), "EmailField".to_string()),
+        ]
+    );
+    assert_eq!(
+        fields("Book"),
+        [
+            ("title".to_string(), "CharField(max_length=200)".to_string()),
+            (
+                "author".to_string(),
+                "ForeignKey(to=Author, on_delete=models.CASCADE, related_name=books)".to_string()
+            ),
+            ("tags".to_string(), "ManyToManyField(to=Tag)".to_string()),
+        ]
+    );
+
+    // Subclasses of a model in the same file are models too.
+    assert_eq!(
+        fields("Ebook"),
+        [("url".to_string(), "URLField(null=True)".to_string())]
+    );
+    assert!(find("Plain").framework_metadata.is_none());
+    assert!(find("Meta").framework_metadata.is_none());
+}
+
+#[test]
+fn sqlalchemy_models_report_columns_for_both_declarative_apis() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("legacy.py"),
+        r#"from sqlalchemy import Column, ForeignKey, Integer, String
+from sqlalchemy.orm import declarative_base, relationship
+
+Base = declarative_base()
+
+
+class User(Base):
+    __tablename__ = "users"
+
+    id = Column(Integer, primary_key=True)
+    name = Column("user_name", String(100), nullable=False, unique=True)
+    addresses = relationship("Address")
+
+
+class Address(Base):
+    __tablename__ = "addresses"
+
+    id = Column(Integer, primary_key=True)
+    user_id = Column(Integer, ForeignKey("users.id"))
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("modern.py"),
+        r#"from typing import Optional
+
+from sqlalchemy import String
+from sqlalchemy.orm import DeclarativeBase, Mapped, mapped_column
+
+
+class Model(DeclarativeBase):
+    pass
+
+
+class Account(Model):
+    __tablename__ = "accounts"
+
+    id: Mapped[int] = mapped_column(primary_key=True)
+    email: Mapped[str] = mapped_column(String(255), index=True)
+    nickname: Mapped[Optional[str]]
+
+
+class Helper:
+    name = "not a model"
+"#,
+    )
+    .unwrap();
+
+    let result = extract_types_with_options(dir.path(), None, 100, false, false)
+        .expect("type extraction should succeed");
+    let find = |name: &str| {
+        result
+            .types
+            .iter()
+            .find(|ty| ty.name == name)
+            .unwrap_or_else(|| panic!("{name} should be extracted"))
+    };
+    let fields = |name: &str| -> Vec<(String, String)> {
+        find(name)
+            .fields
+            .iter()
+            .flatten()
+            .map(|field| (field.name.clone(), field.type_annotation.clone()))
+            .collect()
+    };
+
+    let user = find("User");
+    assert_eq!(user.kind, TypeKind::Class);
+    let metadata = user.framework_metadata.as_ref().unwrap();
+    assert_eq!(metadata["framework"], "sqlalchemy");
+    assert_eq!(metadata["table"], "users");
+    assert_eq!(
+        fields("User"),
+        [
+            ("id".to_string(), "Integer [primary_key]".to_string()),
+            (
+                "name".to_string(),
+                "String(100) [nullable=False, unique]".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        fields("Address")[1],
+        ("user_id".to_string(), "Integer [fk=users.id]".to_string())
+    );
+
+    assert_eq!(
+        fields("Account"),
+        [
+            ("id".to_string(), "int [primary_key]".to_string()),
+            ("email".to_string(), "String(255) [index]".to_string()),
+            ("nickname".to_string(), "Optional[str]".to_string()),
+        ]
+    );
+    assert_eq!(
+        find("Account").framework_metadata.as_ref().unwrap()["table"],
+        "accounts"
+    );
+
+    // The declarative base itself is not a model.
+    assert!(find("Model").framework_metadata.is_none());
+    assert!(find("Helper").framework_metadata.is_none());
+}
+
+#[test]
+fn records_one_based_start_and_end_positions() {
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("shapes.rs");
+    fs::write(
+        &file_path,
+        "mod geometry {\n    pub struct Point {\n        pub x: i32,\n    }\n}\n",
+    )
+    .unwrap();
+
+    let result = extract_types_with_options(&file_path, None, 1000, false, false).unwrap();
+    let point = result.types.iter().find(|t| t.name == "Point").unwrap();
+
+    assert_eq!((point.line, point.column), (2, 5));
+    // The end position sits just past the closing brace.
+    assert_eq!((point.end_line, point.end_column), (4, 6));
+}
+
+#[test]
+fn extracts_rust_generics_and_where_bounds() {
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("generic.rs");
+    fs::write(
+        &file_path,
+        r#"
+pub struct Cache<'a, K: Eq + std::hash::Hash, V = String, const N: usize = 8>
+where
+    V: Clone
+        + Send,
+    &'a K: Copy,
+{
+    entries: Vec<(&'a K, V)>,
+}
+
+pub enum Either<L, R: Default> where L: std::fmt::Debug {
+    Left(L),
+    Right(R),
+}
+
+pub trait Store<T>: Send where T: Clone + 'static, Self: Sized {
+    fn put(&mut self, item: T);
+}
+
+impl<'a, K, V, const N: usize> Cache<'a, K, V, N>
+where
+    K: Eq + std::hash::Hash,
+    V: Clone + Send,
+{
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+pub struct Plain;
+"#,
+    )
+    .unwrap();
+
+    let result = extract_types_with_options(&file_path, None, 1000, false, false).unwrap();
+    let find = |name: &str| result.types.iter().find(|t| t.name == name).unwrap();
+
+    let cache = find("Cache");
+    assert_eq!(
+        cache.generics.as_deref().unwrap(),
+        [
+            "'a",
+            "K: Eq + std::hash::Hash",
+            "V = String",
+            "const N: usize = 8"
+        ]
+    );
+    assert_eq!(
+        cache.where_bounds.as_deref().unwrap(),
+        ["V: Clone + Send", "&'a K: Copy"]
+    );
+    // Methods of a generic impl block are merged; its bounds stay with the impl.
+    let members: Vec<&str> = cache
+        .members
+        .iter()
+        .flatten()
+        .map(|m| m.name.as_str())
+        .collect();
+    assert_eq!(members, ["len"]);
+
+    let either = find("Either");
+    assert_eq!(either.generics.as_deref().unwrap(), ["L", "R: Default"]);
+    assert_eq!(
+        either.where_bounds.as_deref().unwrap(),
+        ["L: std::fmt::Debug"]
+    );
+
+    let store = find("Store");
+    assert_eq!(store.generics.as_deref().unwrap(), ["T"]);
+    assert_eq!(
+        store.where_bounds.as_deref().unwrap(),
+        ["T: Clone + 'static", "Self: Sized"]
+    );
+
+    let plain = find("Plain");
+    assert!(plain.generics.is_none());
+    assert!(plain.where_bounds.is_none());
+}
+
+#[test]
+fn extracts_swift_types_with_associated_values() {
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("Models.swift");
+    fs::write(
+        &file_path,
+        r#"
+actor Counter {
+    var count: Int = 0
+}
+
+struct Stack<Element: Equatable> where Element: Hashable {
+    var items: [Element]
+    let limit = 10
+}
+
+enum Outcome {
+    case pending
+    case result(value: Int, error: String?)
+    case retry(Int), cancelled
+}
+
+final class Store {
+    private var cache: [String: Int]
+}
+
+protocol Repository {
+    var name: String { get }
+    func load(id: Int) -> String?
+}
+
+typealias Handler = (Outcome) -> Void
+
+extension Store {
+    var size: Int { cache.count }
+}
+"#,
+    )
+    .unwrap();
+
+    let result = extract_types_with_options(&file_path, None, 1000, false, false).unwrap();
+    let find = |name: &str| result.types.iter().find(|t| t.name == name).unwrap();
+
+    let counter = find("Counter");
+    assert_eq!(counter.kind, TypeKind::Class);
+    assert_eq!(counter.fields.as_ref().unwrap()[0].type_annotation, "Int");
+
+    let stack = find("Stack");
+    assert_eq!(stack.kind, TypeKind::Struct);
+    assert_eq!(stack.generics.as_deref().unwrap(), ["Element: Equatable"]);
+    assert_eq!(
+        stack.where_bounds.as_deref().unwrap(),
+        ["Element: Hashable"]
+    );
+    let fields: Vec<(&str, &str)> = stack
+        .fields
+        .iter()
+        .flatten()
+        .map(|f| (f.name.as_str(), f.type_annotation.as_str()))
+        .collect();
+    assert_eq!(fields, [("items", "[Element]"), ("limit", "")]);
+
+    let outcome = find("Outcome");
+    assert_eq!(outcome.kind, TypeKind::Enum);
+    let variants: Vec<(&str, Option<&str>)> = outcome
+        .variants
+        .iter()
+        .flatten()
+        .map(|v| (v.name.as_str(), v.type_annotation.as_deref()))
+        .collect();
+    assert_eq!(
+        variants,
+        [
+            ("pending", None),
+            ("result", Some("(value: Int, error: String?)")),
+            ("retry", Some("(Int)")),
+            ("cancelled", None),
+        ]
+    );
+
+    assert_eq!(find("Store").kind, TypeKind::Class);
+
+    let repository = find("Repository");
+    assert_eq!(repository.kind, TypeKind::Protocol);
+    let members: Vec<&str> = repository
+        .members
+        .iter()
+        .flatten()
+        .map(|m| m.name.as_str())
+        .collect();
+    assert_eq!(members, ["name", "load"]);
+
+    assert_eq!(find("Handler").kind, TypeKind::TypeAlias);
+    // Extensions add to an existing type rather than declaring one.
+    assert_eq!(result.types.iter().filter(|t| t.name == "Store").count(), 1);
+}
+
+#[test]
+fn latin1_python_class_is_decoded_and_extracted() {
+    let dir = TempDir::new().unwrap();
+    let mut source = b"# -*- coding: latin-1 -*-\n\nclass Caf".to_vec();
+    source.push(0xe9); // 'é' in Latin-1
+    source.extend_from_slice(
+        b"Order:\n    \"\"\"An order placed at the counter.\"\"\"\n\n    def total(self) -> int:\n        return 0\n\n\nclass Receipt:\n    pass\n",
+    );
+    fs::write(dir.path().join("orders.py"), &source).unwrap();
+    fs::write(dir.path().join("plain.py"), "class Plain:\n    pass\n").unwrap();
+
+    let result = extract_types_with_options(dir.path(), None, 0, false, false).unwrap();
+
+    let names: Vec<&str> = result.types.iter().map(|t| t.name.as_str()).collect();
+    assert!(names.contains(&"CaféOrder"), "got {names:?}");
+    assert!(names.contains(&"Receipt"));
+    assert!(names.contains(&"Plain"));
+
+    assert_eq!(result.encoding_detected.len(), 1);
+    assert_eq!(
+        result.encoding_detected[0].file,
+        std::path::PathBuf::from("orders.py")
+    );
+    assert_eq!(
+        result.encoding_detected[0].encoding,
+        SourceEncoding::Windows1252
+    );
+}
+
+#[test]
+fn utf8_sources_report_no_encoding() {
+    let file = common::fixture_path("python", "calculator.py");
+    let result = extract_types_with_options(&file, None, 0, false, false).unwrap();
+    assert!(result.encoding_detected.is_empty());
+}
+
+#[test]
+fn workspace_root_extracts_types_from_every_member() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    for (member, source) in [
+        ("core", "pub struct Config {\n    pub name: String,\n}\n"),
+        (
+            "server",
+            "use core::Config;\n\npub struct Server {\n    pub config: Config,\n}\n",
+        ),
+    ] {
+        let member_dir = dir.path().join("crates").join(member);
+        fs::create_dir_all(member_dir.join("src")).unwrap();
+        fs::write(
+            member_dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{member}\"\n"),
+        )
+        .unwrap();
+        fs::write(member_dir.join("src/lib.rs"), source).unwrap();
+    }
+
+    let member_file = dir.path().join("crates/server/src/lib.rs");
+    let root = treesitter_mcp::analysis::path_utils::find_workspace_root(&member_file).unwrap();
+    let result = extract_types_with_options(&root, None, 0, true, true).unwrap();
+
+    let config = result.types.iter().find(|t| t.name == "Config").unwrap();
+    assert_eq!(
+        config.file,
+        std::path::PathBuf::from("crates/core/src/lib.rs")
+    );
+    let server = result.types.iter().find(|t| t.name == "Server").unwrap();
+    assert_eq!(
+        server.file,
+        std::path::PathBuf::from("crates/server/src/lib.rs")
+    );
+    let field = &server.fields.as_ref().unwrap()[0];
+    assert_eq!(
+        field.resolved_to.as_deref(),
+        Some(std::path::Path::new("crates/core/src/lib.rs"))
+    );
+}
+
+// ============================================================================
+// Python Function Annotations
+// ============================================================================
+
+const ANNOTATED_PYTHON: &str = r#"from typing import Any, Dict, Optional
+
+
+def foo(x: int, y: str = "default") -> Optional[bool]:
+    return None
+
+
+def collect(*args: Any, **kwargs: Dict[str, Any]) -> None:
+    pass
+
+
+def untyped(a, b=1):
+    return a
+
+
+class Client:
+    async def fetch(self, url: str, *, timeout: float = 2.5) -> bytes:
+        return b""
+
+    def close(self):
+        pass
+"#;
+
+#[test]
+fn python_function_annotations_capture_params_and_return_types() {
+    use treesitter_mcp::extraction::types::{extract_python_function_annotations, TypedParam};
+
+    let param = |name: &str, ty: Option<&str>, default: Option<&str>| TypedParam {
+        name: name.to_string(),
+        type_annotation: ty.map(str::to_string),
+        default: default.map(str::to_string),
+    };
+
+    let signatures =
+        extract_python_function_annotations(ANNOTATED_PYTHON, std::path::Path::new("api.py"))
+            .unwrap();
+    let names: Vec<&str> = signatures.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["foo", "collect", "Client.fetch"]);
+
+    let foo = &signatures[0];
+    assert_eq!(foo.line, 4);
+    assert!(!foo.is_async);
+    assert_eq!(
+        foo.params,
+        [
+            param("x", Some("int"), None),
+            param("y", Some("str"), Some("\"default\"")),
+        ]
+    );
+    assert_eq!(foo.return_type.as_deref(), Some("Optional[bool]"));
+
+    let collect = &signatures[1];
+    assert_eq!(
+        collect.params,
+        [
+            param("*args", Some("Any"), None),
+            param("**kwargs", Some("Dict[str, Any]"), None),
+        ]
+    );
+    assert_eq!(collect.return_type.as_deref(), Some("None"));
+
+    let fetch = &signatures[2];
+    assert!(fetch.is_async);
+    assert_eq!(fetch.line, 17);
+    assert_eq!(
+        fetch.params,
+        [
+            param("self", None, None),
+            param("url", Some("str"), None),
+            param("timeout", Some("float"), Some("2.5")),
+        ]
+    );
+    assert_eq!(fetch.return_type.as_deref(), Some("bytes"));
+    assert_eq!(fetch.file, std::path::PathBuf::from("api.py"));
+}
+
+#[test]
+fn type_extraction_result_lists_function_signatures() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("api.py"), ANNOTATED_PYTHON).unwrap();
+    fs::write(
+        dir.path().join("lib.rs"),
+        "pub fn typed(x: i32) -> i32 { x }\n",
+    )
+    .unwrap();
+
+    let result = extract_types_with_options(dir.path(), None, 0, false, false).unwrap();
+
+    assert!(result.types.iter().any(|t| t.name == "Client"));
+    let names: Vec<&str> = result
+        .function_signatures
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect();
+    assert_eq!(names, ["foo", "collect", "Client.fetch"]);
+}
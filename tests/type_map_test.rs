@@ -46,7 +46,10 @@ fn test_type_map_compact_header_present() {
     let text = common::get_result_text(&result);
     let (header, rows, _) = parse_type_map(&text);
 
-    assert_eq!(header, "name|kind|file|line|usage_count");
+    assert_eq!(
+        header,
+        "name|kind|file|line|usage_count|col|end_line|end_col"
+    );
     assert!(!rows.is_empty());
 }
 
@@ -276,3 +279,143 @@ const config: Config = { enabled: true };
         "Unreferenced Config should stay at zero"
     );
 }
+
+#[test]
+fn test_type_map_mermaid_class_diagram() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("shapes.ts"),
+        r#"
+export interface Shape {
+  area(): number;
+  name: string;
+}
+
+export class Circle implements Shape {
+  radius: number;
+}
+
+export class Ring extends Circle {}
+
+export enum Color {
+  Red,
+  Green,
+}
+
+export type Id = string;
+"#,
+    )
+    .unwrap();
+
+    let result = treesitter_mcp::analysis::type_map::execute(&json!({
+        "path": dir.path().to_str().unwrap(),
+        "count_usages": false,
+        "output_format": "mermaid"
+    }))
+    .unwrap();
+    let text = common::get_result_text(&result);
+    let out: serde_json::Value = serde_json::from_str(&text).unwrap();
+    let diagram = out["mermaid"].as_str().unwrap();
+
+    assert!(diagram.starts_with("classDiagram\n"));
+    assert!(diagram.contains("class Shape {\n  <<interface>>\n  area()\n  name: string\n}"));
+    assert!(diagram.contains("class Color {\n  <<enumeration>>\n  Red\n  Green\n}"));
+    assert!(diagram.contains("Shape <|-- Circle"));
+    assert!(diagram.contains("Circle <|-- Ring"));
+
+    let bpe = tiktoken_rs::cl100k_base().unwrap();
+    assert!(bpe.encode_with_special_tokens(diagram).len() < 100);
+}
+
+#[test]
+fn test_type_map_rejects_unknown_output_format() {
+    let file_path = common::fixture_path("typescript", "types/models.ts");
+    let result = treesitter_mcp::analysis::type_map::execute(&json!({
+        "path": file_path.to_str().unwrap(),
+        "output_format": "svg"
+    }));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_type_map_csv_output_is_a_text_csv_resource() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("models.rs"),
+        r#"
+pub struct Point {
+    pub x: i32,
+    pub label: String,
+}
+
+pub enum Shape {
+    Circle(f64),
+    Square,
+}
+"#,
+    )
+    .unwrap();
+
+    let result = treesitter_mcp::analysis::type_map::execute(&json!({
+        "path": dir.path().to_str().unwrap(),
+        "count_usages": false,
+        "output_format": "csv"
+    }))
+    .unwrap();
+    let content = serde_json::to_value(&result.content[0]).unwrap();
+    assert_eq!(content["type"], "resource");
+    assert_eq!(content["resource"]["mimeType"], "text/csv");
+
+    let csv = content["resource"]["text"].as_str().unwrap();
+    let sections: Vec<&str> = csv.split("\n\n").collect();
+    assert_eq!(sections.len(), 3);
+    assert_eq!(
+        sections[0],
+        "name,kind,file,line,usage_count\n\
+         Point,struct,models.rs,2,0\n\
+         Shape,enum,models.rs,7,0"
+    );
+    assert!(sections[1].starts_with("type,field,field_type\nPoint,x,i32\n"));
+    assert_eq!(
+        sections[2],
+        "type,variant,variant_type\nShape,Circle,\nShape,Square,"
+    );
+}
+
+#[test]
+fn test_type_map_tsv_output_uses_tab_separated_mime_type() {
+    let file_path = common::fixture_path("typescript", "types/models.ts");
+    let result = treesitter_mcp::analysis::type_map::execute(&json!({
+        "path": file_path.to_str().unwrap(),
+        "count_usages": false,
+        "output_format": "tsv"
+    }))
+    .unwrap();
+    let content = serde_json::to_value(&result.content[0]).unwrap();
+    assert_eq!(content["resource"]["mimeType"], "text/tab-separated-values");
+    assert!(content["resource"]["text"]
+        .as_str()
+        .unwrap()
+        .starts_with("name\tkind\tfile\tline\tusage_count\n"));
+}
+
+#[test]
+fn test_type_map_rows_include_columns_and_end_positions() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("models.rs"),
+        "pub struct Point {\n    pub x: i32,\n}\n\n  pub enum Shape { Circle }\n",
+    )
+    .unwrap();
+
+    let result = treesitter_mcp::analysis::type_map::execute(&json!({
+        "path": dir.path().to_str().unwrap(),
+        "count_usages": false,
+    }))
+    .unwrap();
+    let (_header, rows, _) = parse_type_map(&common::get_result_text(&result));
+
+    let row = |name: &str| rows.iter().find(|row| row[0] == name).unwrap().clone();
+    assert_eq!(row("Point")[3..], ["1", "0", "1", "3", "2"]);
+    assert_eq!(row("Shape")[3..], ["5", "0", "3", "5", "28"]);
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+/// `(line, status, unused names)` per import
+fn import_rows(path: &std::path::Path) -> Vec<(String, String, String)> {
+    let result = treesitter_mcp::analysis::unused::execute_find_unused_imports(&json!({
+        "path": path.to_str().unwrap()
+    }))
+    .unwrap();
+    let text = common::get_result_text(&result);
+    let output: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(output["h"], "file|line|import|status|unused");
+    common::helpers::parse_compact_rows(output["imports"].as_str().unwrap())
+        .into_iter()
+        .map(|row| (row[1].clone(), row[3].clone(), row[4].clone()))
+        .collect()
+}
+
+fn import(line: &str, status: &str, unused: &str) -> (String, String, String) {
+    (line.to_string(), status.to_string(), unused.to_string())
+}
+
+#[test]
+fn test_unused_imports_rust_aliases_groups_and_reexports() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    fs::write(
+        &file,
+        "use std::collections::{HashMap, HashSet};\nuse std::fmt::Display as Show;\nuse std::io::Read as _Read;\nuse std::fs::File as Handle;\npub use std::path::PathBuf;\n\nfn open() -> Option<HashMap<String, Handle>> {\n    None\n}\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        import_rows(&file),
+        [
+            import("1", "used", "HashSet"),
+            import("2", "unused", "Show"),
+            import("3", "unused", "_Read"),
+            import("4", "used", ""),
+            import("5", "used", ""),
+        ]
+    );
+}
+
+#[test]
+fn test_unused_imports_wildcards_are_unknown() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("lib.rs"),
+        "use std::io::*;\npub use std::fmt::*;\n\nfn main() {}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("app.py"),
+        "from os.path import *\nimport json\n\nprint(join(\"a\", \"b\"))\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        import_rows(&dir.path().join("lib.rs")),
+        [import("1", "unknown", ""), import("2", "used", "")]
+    );
+    assert_eq!(
+        import_rows(&dir.path().join("app.py")),
+        [import("1", "unknown", ""), import("2", "unused", "json")]
+    );
+}
+
+#[test]
+fn test_unused_imports_python_reexports_via_dunder_all() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("__init__.py");
+    fs::write(
+        &file,
+        "from .models import User, Group\nimport os as system\n\n__all__ = [\"User\", \"Group\"]\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        import_rows(&file),
+        [import("1", "used", ""), import("2", "unused", "system")]
+    );
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::usage_counter::{
+    count_all_usages, count_words_in_content, language_for_path, CountLanguage,
+};
+use treesitter_mcp::extraction::types::extract_types_with_options;
+
+fn word_counts(content: &str, file_name: &str) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    count_words_in_content(
+        content,
+        language_for_path(Path::new(file_name)),
+        &mut counts,
+    );
+    counts
+}
+
+#[test]
+fn test_typescript_module_extensions_use_javascript_rules() {
+    for file in ["a.ts", "a.tsx", "a.mts", "a.cts"] {
+        assert!(
+            matches!(
+                language_for_path(Path::new(file)),
+                CountLanguage::JavaScript
+            ),
+            "{file}"
+        );
+    }
+}
+
+#[test]
+fn test_jvm_and_swift_sources_skip_comments_and_text_blocks() {
+    let kotlin = r#"
+// Order in a comment
+val order: Order = load()
+val doc = """
+    Order in a text block
+"""
+"#;
+    assert_eq!(word_counts(kotlin, "Main.kt")["Order"], 1);
+
+    let swift = "/* Order */ let order: Order = Order()\nlet s = \"\"\"\nOrder\n\"\"\"\n";
+    assert_eq!(word_counts(swift, "main.swift")["Order"], 2);
+
+    let java = "Order order = new Order(); // Order\nString s = \"Order\";\n";
+    assert_eq!(word_counts(java, "Main.java")["Order"], 2);
+
+    let csharp = "var order = new Order(); /* Order */\n";
+    assert_eq!(word_counts(csharp, "Program.cs")["Order"], 1);
+}
+
+#[test]
+fn test_go_raw_strings_are_skipped() {
+    // A trailing backslash does not escape the closing backtick of a raw string.
+    let go = "var path = `C:\\`\nvar o Order // Order\nvar q = `Order`\n";
+    assert_eq!(word_counts(go, "main.go")["Order"], 1);
+}
+
+#[test]
+fn test_usage_counts_across_languages_ignore_comments() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("models.ts"),
+        "export interface Order { id: string }\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("use.mts"),
+        "import { Order } from './models';\nconst o: Order = { id: '1' };\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("Client.kt"),
+        "// Order Order Order\nclass Client(val order: Order)\n",
+    )
+    .unwrap();
+
+    let mut result = extract_types_with_options(dir.path(), None, 0, false, false).unwrap();
+    count_all_usages(&mut result.types, dir.path()).unwrap();
+
+    let order = result.types.iter().find(|ty| ty.name == "Order").unwrap();
+    // Two references in use.mts and one in Client.kt; the declaration is not counted.
+    assert_eq!(order.usage_count, 3);
+}
@@ -0,0 +1,146 @@
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::tempdir;
+use treesitter_mcp::common::tokenizer::{tokenizer, TokenizerKind};
+
+mod common;
+
+fn rust_source() -> String {
+    let mut lines = vec![
+        "use std::collections::HashMap;".to_string(),
+        "use std::fmt;".to_string(),
+        String::new(),
+    ];
+    for i in 0..4 {
+        lines.push(format!("pub struct Record{i} {{ pub id: u32 }}"));
+    }
+    for i in 0..30 {
+        lines.push(format!(
+            "pub fn handler_{i}(input: &str) -> usize {{ input.len() }}"
+        ));
+    }
+    lines.join("\n")
+}
+
+fn view(max_tokens: usize) -> Value {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("lib.rs");
+    fs::write(&path, rust_source()).unwrap();
+
+    let arguments = json!({
+        "file_path": path.to_str().unwrap(),
+        "detail": "signatures",
+        "include_deps": false,
+        "max_tokens": max_tokens
+    });
+    let result = treesitter_mcp::analysis::view_code::execute(&arguments).unwrap();
+    serde_json::from_str(&common::get_result_text(&result)).unwrap()
+}
+
+fn row_count(view: &Value, key: &str) -> usize {
+    common::helpers::parse_compact_rows(view[key].as_str().unwrap_or("")).len()
+}
+
+#[test]
+fn test_budget_trims_local_types_before_functions_and_imports() {
+    let full = view(100_000);
+    assert_eq!(row_count(&full, "f"), 30);
+    assert_eq!(row_count(&full, "s"), 4);
+    assert!(full.get("@").is_none());
+
+    let trimmed = view(400);
+    assert_eq!(trimmed["@"]["t"], true);
+    // Functions outnumber structs but structs are trimmed first.
+    assert!(trimmed.get("s").is_none());
+    let functions = common::helpers::parse_compact_rows(trimmed["f"].as_str().unwrap());
+    assert!(!functions.is_empty() && functions.len() < 30);
+    assert_eq!(functions[0][0], "handler_0");
+    assert_eq!(row_count(&trimmed, "im"), 2);
+}
+
+#[test]
+fn test_budget_drops_most_expensive_function_first() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("lib.rs");
+    let body: Vec<String> = (0..80)
+        .map(|i| format!("    let value_{i} = input.len() * {i};"))
+        .collect();
+    let source = format!(
+        "pub fn large(input: &str) -> usize {{\n{}\n    0\n}}\n\npub fn tiny() -> u8 {{ 1 }}\n",
+        body.join("\n")
+    );
+    fs::write(&path, source).unwrap();
+
+    let view = |max_tokens: usize| -> Value {
+        let result = treesitter_mcp::analysis::view_code::execute(&json!({
+            "file_path": path.to_str().unwrap(),
+            "detail": "full",
+            "include_deps": false,
+            "max_tokens": max_tokens
+        }))
+        .unwrap();
+        serde_json::from_str(&common::get_result_text(&result)).unwrap()
+    };
+
+    assert_eq!(row_count(&view(100_000), "f"), 2);
+
+    // `large` is declared first but costs far more tokens, so it goes first
+    let trimmed = view(150);
+    assert_eq!(trimmed["@"]["t"], true);
+    let functions = common::helpers::parse_compact_rows(trimmed["f"].as_str().unwrap());
+    assert_eq!(functions.len(), 1);
+    assert_eq!(functions[0][0], "tiny");
+}
+
+#[test]
+fn test_tokenizers_count_the_same_source_differently() {
+    let source = rust_source();
+    let counts: Vec<usize> = TokenizerKind::ALL
+        .iter()
+        .map(|kind| tokenizer(*kind).unwrap().count(&source))
+        .collect();
+
+    // r50k's smaller vocabulary splits code into more tokens
+    let r50k = tokenizer(TokenizerKind::R50k).unwrap().count(&source);
+    let cl100k = tokenizer(TokenizerKind::Cl100k).unwrap().count(&source);
+    assert!(r50k > cl100k, "r50k {r50k} vs cl100k {cl100k}");
+    assert!(counts.iter().any(|count| *count != counts[0]));
+}
+
+#[test]
+fn test_budget_is_enforced_with_each_tokenizer() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("lib.rs");
+    fs::write(&path, rust_source()).unwrap();
+
+    for kind in TokenizerKind::ALL {
+        let result = treesitter_mcp::analysis::view_code::execute(&json!({
+            "file_path": path.to_str().unwrap(),
+            "detail": "full",
+            "include_deps": false,
+            "max_tokens": 300,
+            "tokenizer": kind.name()
+        }))
+        .unwrap();
+        let text = common::get_result_text(&result);
+        let view: Value = serde_json::from_str(&text).unwrap();
+
+        assert_eq!(view["@"]["t"], true, "{kind:?}");
+        let used = tokenizer(kind).unwrap().count(&text);
+        assert!(used <= 300, "{kind:?} used {used} tokens");
+    }
+}
+
+#[test]
+fn test_unknown_tokenizer_is_rejected() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("lib.rs");
+    fs::write(&path, rust_source()).unwrap();
+
+    let err = treesitter_mcp::analysis::view_code::execute(&json!({
+        "file_path": path.to_str().unwrap(),
+        "tokenizer": "claude"
+    }))
+    .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,89 @@
+use serde_json::{json, Value};
+
+mod common;
+
+fn view_lines(start_line: u64, end_line: u64) -> Value {
+    let path = common::fixture_path("rust", "src/calculator.rs");
+    let result = treesitter_mcp::analysis::view_code::execute(&json!({
+        "file_path": path.to_str().unwrap(),
+        "detail": "signatures",
+        "include_deps": true,
+        "max_tokens": 10_000,
+        "start_line": start_line,
+        "end_line": end_line
+    }))
+    .unwrap();
+    serde_json::from_str(&common::get_result_text(&result)).unwrap()
+}
+
+fn function_names(view: &Value) -> Vec<String> {
+    common::helpers::parse_compact_rows(view["f"].as_str().unwrap_or(""))
+        .into_iter()
+        .map(|row| row[0].clone())
+        .collect()
+}
+
+fn dep_type_names(view: &Value) -> Vec<String> {
+    let Some(deps) = view["deps"].as_object() else {
+        return Vec::new();
+    };
+    deps.values()
+        .filter_map(Value::as_str)
+        .flat_map(common::helpers::parse_compact_rows)
+        .map(|row| row[0].clone())
+        .collect()
+}
+
+#[test]
+fn test_line_range_keeps_only_overlapping_functions() {
+    let view = view_lines(10, 30);
+
+    assert_eq!(function_names(&view), ["add", "subtract", "multiply"]);
+    // The arithmetic helpers only use i32, and the imports on line 3 are
+    // outside the range, so no model types are pulled in.
+    let deps = dep_type_names(&view);
+    assert!(!deps.contains(&"Calculator".to_string()), "{deps:?}");
+    assert!(!deps.contains(&"Point".to_string()), "{deps:?}");
+}
+
+#[test]
+fn test_line_range_deps_follow_signatures_in_range() {
+    let view = view_lines(54, 62);
+
+    assert_eq!(
+        function_names(&view),
+        ["create_calculator", "create_calculator_with_value"]
+    );
+    let deps = dep_type_names(&view);
+    assert!(deps.contains(&"Calculator".to_string()), "{deps:?}");
+    assert!(!deps.contains(&"Point".to_string()), "{deps:?}");
+}
+
+#[test]
+fn test_without_line_range_all_referenced_types_are_deps() {
+    let path = common::fixture_path("rust", "src/calculator.rs");
+    let result = treesitter_mcp::analysis::view_code::execute(&json!({
+        "file_path": path.to_str().unwrap(),
+        "detail": "signatures",
+        "include_deps": true,
+        "max_tokens": 10_000
+    }))
+    .unwrap();
+    let view: Value = serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    let deps = dep_type_names(&view);
+    assert!(deps.contains(&"Calculator".to_string()), "{deps:?}");
+    assert!(deps.contains(&"Point".to_string()), "{deps:?}");
+}
+
+#[test]
+fn test_inverted_line_range_is_rejected() {
+    let path = common::fixture_path("rust", "src/calculator.rs");
+    let err = treesitter_mcp::analysis::view_code::execute(&json!({
+        "file_path": path.to_str().unwrap(),
+        "start_line": 30,
+        "end_line": 10
+    }))
+    .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
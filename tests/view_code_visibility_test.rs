@@ -0,0 +1,125 @@
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::tempdir;
+
+mod common;
+
+fn view(path: &std::path::Path, min_visibility: Option<&str>) -> Value {
+    let arguments = json!({
+        "file_path": path.to_str().unwrap(),
+        "detail": "signatures",
+        "include_deps": false,
+        "max_tokens": 10_000,
+        "min_visibility": min_visibility
+    });
+    let result = treesitter_mcp::analysis::view_code::execute(&arguments).unwrap();
+    serde_json::from_str(&common::get_result_text(&result)).unwrap()
+}
+
+fn names(view: &Value, key: &str, column: usize) -> Vec<String> {
+    common::helpers::parse_compact_rows(view[key].as_str().unwrap_or(""))
+        .into_iter()
+        .map(|row| row[column].clone())
+        .collect()
+}
+
+const RUST_SOURCE: &str = r#"
+pub struct Public;
+pub(crate) struct Internal;
+struct Hidden;
+
+pub fn exported() {}
+pub(crate) fn crate_only() {}
+fn helper() {}
+
+impl Public {
+    pub fn new() -> Self { Public }
+    fn secret(&self) {}
+}
+
+impl Default for Public {
+    fn default() -> Self { Public }
+}
+"#;
+
+#[test]
+fn test_min_visibility_defaults_to_everything() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("lib.rs");
+    fs::write(&path, RUST_SOURCE).unwrap();
+
+    let all = view(&path, None);
+    assert_eq!(
+        names(&all, "f", 0),
+        [
+            "exported",
+            "crate_only",
+            "helper",
+            "new",
+            "secret",
+            "default"
+        ]
+    );
+    assert_eq!(view(&path, Some("private")), all);
+}
+
+#[test]
+fn test_min_visibility_pub_keeps_public_api_only() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("lib.rs");
+    fs::write(&path, RUST_SOURCE).unwrap();
+
+    let public = view(&path, Some("pub"));
+    assert_eq!(names(&public, "f", 0), ["exported", "new", "default"]);
+    assert_eq!(names(&public, "s", 0), ["Public"]);
+
+    // Trait impl methods stay; private inherent methods go.
+    let methods = names(&public, "bm", 2);
+    assert!(methods.contains(&"new".to_string()));
+    assert!(methods.contains(&"default".to_string()));
+    assert!(!methods.contains(&"secret".to_string()));
+}
+
+#[test]
+fn test_min_visibility_pub_crate_drops_private_only() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("lib.rs");
+    fs::write(&path, RUST_SOURCE).unwrap();
+
+    let crate_view = view(&path, Some("pub_crate"));
+    assert_eq!(
+        names(&crate_view, "f", 0),
+        ["exported", "crate_only", "new", "default"]
+    );
+    assert_eq!(names(&crate_view, "s", 0), ["Public", "Internal"]);
+}
+
+#[test]
+fn test_min_visibility_python_underscore_is_private() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("module.py");
+    fs::write(
+        &path,
+        "def public():\n    pass\n\ndef _private():\n    pass\n\nclass _Hidden:\n    pass\n\nclass Shown:\n    def __init__(self):\n        pass\n\n    def _helper(self):\n        pass\n",
+    )
+    .unwrap();
+
+    let public = view(&path, Some("pub"));
+    assert_eq!(names(&public, "f", 0), ["public"]);
+    assert_eq!(names(&public, "c", 0), ["Shown"]);
+    assert_eq!(names(&public, "cm", 1), ["__init__"]);
+}
+
+#[test]
+fn test_min_visibility_typescript_exports_and_underscore() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("api.ts");
+    fs::write(
+        &path,
+        "export function shown(): void {}\nfunction local(): void {}\nexport function _internal(): void {}\n",
+    )
+    .unwrap();
+
+    let public = view(&path, Some("pub"));
+    assert_eq!(names(&public, "f", 0), ["shown"]);
+}
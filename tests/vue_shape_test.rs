@@ -0,0 +1,193 @@
+use serde_json::json;
+use tempfile::TempDir;
+use treesitter_mcp::analysis::svelte::PropInfo;
+use treesitter_mcp::analysis::vue::extract_vue_shape;
+use treesitter_mcp::parser::{parse_code, Language};
+
+mod common;
+
+const COMPOSITION: &str = r#"<template>
+  <div class="counter">
+    <span class="label">{{ title }}: {{ doubled }}</span>
+    <button @click="reset">Reset</button>
+  </div>
+</template>
+
+<script setup lang="ts">
+import { computed, ref } from 'vue';
+import { useMouse } from '@vueuse/core';
+
+interface Props {
+  title: string;
+  count?: number;
+}
+
+const props = withDefaults(defineProps<Props>(), { count: 0 });
+const emit = defineEmits<{
+  (e: 'change', value: number): void;
+  (e: 'reset'): void;
+}>();
+
+const total = ref(props.count);
+const doubled = computed(() => total.value * 2);
+const { x, y } = useMouse();
+const step = 1;
+
+function reset() {
+  total.value = 0;
+  emit('reset');
+}
+
+defineExpose({ reset, total });
+</script>
+
+<style scoped>
+.counter { display: flex; }
+.counter .label { font-weight: bold; }
+</style>
+
+<style>
+.global-only { color: red; }
+</style>
+"#;
+
+const OPTIONS: &str = r#"<template>
+  <ul class="todo-list"><li v-for="item in items">{{ item }}</li></ul>
+</template>
+
+<script>
+export default {
+  name: 'TodoList',
+  props: {
+    title: String,
+    limit: { type: Number, default: 10 },
+  },
+  emits: ['add', 'remove'],
+  data() {
+    return {
+      items: [],
+      draft: '',
+    };
+  },
+  computed: {
+    remaining() {
+      return this.limit - this.items.length;
+    },
+  },
+  methods: {
+    add() {
+      this.$emit('add', this.draft);
+    },
+    clear() {
+      this.items = [];
+    },
+  },
+};
+</script>
+"#;
+
+fn prop(name: &str, ty: Option<&str>, default: Option<&str>, line: usize) -> PropInfo {
+    PropInfo {
+        name: name.to_string(),
+        type_annotation: ty.map(str::to_string),
+        default: default.map(str::to_string),
+        line,
+    }
+}
+
+#[test]
+fn test_composition_api_macros_and_composables() {
+    let tree = parse_code(COMPOSITION, Language::Html).unwrap();
+    let shape = extract_vue_shape(tree.root_node(), COMPOSITION).unwrap();
+
+    assert_eq!(
+        shape.props,
+        [
+            prop("title", Some("string"), None, 13),
+            prop("count", Some("number?"), Some("0"), 14),
+        ]
+    );
+    assert_eq!(shape.emits, ["change", "reset"]);
+    assert_eq!(shape.exposed, ["reset", "total"]);
+    assert_eq!(
+        shape.composables,
+        ["total: ref", "doubled: computed", "{ x, y }: useMouse"]
+    );
+    assert!(shape.data.is_empty() && shape.methods.is_empty() && shape.computed.is_empty());
+}
+
+#[test]
+fn test_only_scoped_style_classes_are_listed() {
+    let tree = parse_code(COMPOSITION, Language::Html).unwrap();
+    let shape = extract_vue_shape(tree.root_node(), COMPOSITION).unwrap();
+
+    assert_eq!(shape.scoped_classes, ["counter", "label"]);
+}
+
+#[test]
+fn test_options_api_props_data_methods_and_computed() {
+    let tree = parse_code(OPTIONS, Language::Html).unwrap();
+    let shape = extract_vue_shape(tree.root_node(), OPTIONS).unwrap();
+
+    assert_eq!(
+        shape.props,
+        [
+            prop("title", Some("String"), None, 9),
+            prop("limit", Some("Number"), Some("10"), 10),
+        ]
+    );
+    assert_eq!(shape.emits, ["add", "remove"]);
+    assert_eq!(shape.data, ["items", "draft"]);
+    assert_eq!(shape.computed, ["remaining"]);
+    assert_eq!(shape.methods, ["add", "clear"]);
+    assert!(shape.composables.is_empty() && shape.exposed.is_empty());
+    assert!(shape.scoped_classes.is_empty());
+}
+
+#[test]
+fn test_runtime_declarations_in_javascript_setup() {
+    let source = r#"<script setup>
+import { reactive } from 'vue';
+
+const props = defineProps(['modelValue', 'disabled']);
+defineEmits({ 'update:modelValue': (value) => typeof value === 'string' });
+const state = reactive({ focused: false });
+</script>
+"#;
+    let tree = parse_code(source, Language::Html).unwrap();
+    let shape = extract_vue_shape(tree.root_node(), source).unwrap();
+
+    assert_eq!(
+        shape.props,
+        [
+            prop("modelValue", None, None, 4),
+            prop("disabled", None, None, 4),
+        ]
+    );
+    assert_eq!(shape.emits, ["update:modelValue"]);
+    assert_eq!(shape.composables, ["state: reactive"]);
+}
+
+#[test]
+fn test_vue_shape_tool_output() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("Counter.vue");
+    std::fs::write(&path, COMPOSITION).unwrap();
+
+    let result = treesitter_mcp::analysis::vue::execute_vue_shape(&json!({
+        "file_path": path.to_str().unwrap()
+    }))
+    .unwrap();
+    let output: serde_json::Value =
+        serde_json::from_str(&common::get_result_text(&result)).unwrap();
+
+    assert!(output["file"].as_str().unwrap().ends_with("Counter.vue"));
+    assert_eq!(output["ph"], "name|type|default|line");
+    let props = common::helpers::parse_compact_rows(output["props"].as_str().unwrap());
+    assert_eq!(props[0], ["title", "string", "", "13"]);
+    assert_eq!(props[1], ["count", "number?", "0", "14"]);
+    assert_eq!(output["emits"], "change\nreset");
+    assert_eq!(output["exposed"], "reset\ntotal");
+    assert_eq!(output["classes"], "counter\nlabel");
+    assert_eq!(output["methods"], "");
+}
@@ -0,0 +1,358 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use treesitter_mcp::handler::TreesitterServerHandler;
+use treesitter_mcp::mcp::ws_transport::{accept_key, serve_listener, WebSocketOptions};
+
+/// Key from the RFC 6455 handshake example
+const CLIENT_KEY: &str = "dGhlIHNhbXBsZSBub25jZQ==";
+
+fn start_server() -> std::net::SocketAddr {
+    start_server_with(WebSocketOptions::default())
+}
+
+fn start_server_with(options: WebSocketOptions) -> std::net::SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = Arc::new(TreesitterServerHandler::new());
+    thread::spawn(move || serve_listener(listener, server, options));
+    address
+}
+
+/// Send a raw upgrade request and return the response's status line
+fn upgrade_status(address: std::net::SocketAddr, request: &str) -> String {
+    let mut stream = TcpStream::connect(address).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut status = String::new();
+    BufReader::new(stream).read_line(&mut status).unwrap();
+    status.trim_end().to_string()
+}
+
+fn upgrade_request(method: &str, extra_headers: &str) -> String {
+    format!(
+        "{method} / HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: keep-alive, Upgrade\r\nSec-WebSocket-Key: {CLIENT_KEY}\r\nSec-WebSocket-Version: 13\r\n{extra_headers}\r\n"
+    )
+}
+
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    next_id: u64,
+}
+
+impl Client {
+    fn connect(address: std::net::SocketAddr) -> Self {
+        let mut writer = TcpStream::connect(address).unwrap();
+        write!(
+            writer,
+            "GET / HTTP/1.1\r\nHost: {address}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: {CLIENT_KEY}\r\nSec-WebSocket-Version: 13\r\n\r\n"
+        )
+        .unwrap();
+
+        let mut reader = BufReader::new(writer.try_clone().unwrap());
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim_end().is_empty() {
+                break;
+            }
+            headers.push(line.trim_end().to_string());
+        }
+        assert_eq!(headers[0], "HTTP/1.1 101 Switching Protocols");
+        assert!(headers
+            .iter()
+            .any(|h| h == "Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+
+        Client {
+            reader,
+            writer,
+            next_id: 1,
+        }
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        let message = json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
+        self.send(0x1, message.to_string().as_bytes());
+
+        let (opcode, payload) = self.receive();
+        assert_eq!(opcode, 0x1);
+        let response: Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(response["id"], id);
+        response
+    }
+
+    /// Send a masked frame, as clients must.
+    fn send(&mut self, opcode: u8, payload: &[u8]) {
+        let mask = [0x12, 0x34, 0x56, 0x78];
+        let mut frame = vec![0x80 | opcode];
+        if payload.len() < 126 {
+            frame.push(0x80 | payload.len() as u8);
+        } else {
+            frame.push(0x80 | 126);
+            frame.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+        }
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        self.writer.write_all(&frame).unwrap();
+    }
+
+    fn receive(&mut self) -> (u8, Vec<u8>) {
+        let mut header = [0u8; 2];
+        self.reader.read_exact(&mut header).unwrap();
+        let length = match header[1] & 0x7F {
+            126 => {
+                let mut bytes = [0u8; 2];
+                self.reader.read_exact(&mut bytes).unwrap();
+                u16::from_be_bytes(bytes) as usize
+            }
+            127 => {
+                let mut bytes = [0u8; 8];
+                self.reader.read_exact(&mut bytes).unwrap();
+                u64::from_be_bytes(bytes) as usize
+            }
+            length => length as usize,
+        };
+        let mut payload = vec![0; length];
+        self.reader.read_exact(&mut payload).unwrap();
+        (header[0] & 0x0F, payload)
+    }
+}
+
+#[test]
+fn test_accept_key_matches_rfc_example() {
+    assert_eq!(accept_key(CLIENT_KEY), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+}
+
+#[test]
+fn test_initialize_over_websocket_returns_server_details() {
+    let mut client = Client::connect(start_server());
+
+    let response = client.request(
+        "initialize",
+        json!({
+            "protocolVersion": "2025-11-25",
+            "capabilities": {},
+            "clientInfo": {"name": "test", "version": "0.0.0"},
+        }),
+    );
+    let result = &response["result"];
+    assert_eq!(result["serverInfo"]["name"], "treesitter-mcp");
+    assert_eq!(result["protocolVersion"], "2025-11-25");
+    assert!(result["capabilities"]["tools"].is_object());
+
+    // Pings are answered with pongs carrying the same payload.
+    client.send(0x9, b"alive");
+    assert_eq!(client.receive(), (0xA, b"alive".to_vec()));
+
+    let unknown = client.request("resources/list", json!({}));
+    assert_eq!(unknown["error"]["code"], -32601);
+}
+
+#[test]
+fn test_concurrent_clients_call_tools() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("lib.rs"),
+        "pub struct Counter {\n    count: u32,\n}\n\npub fn helper() {}\n",
+    )
+    .unwrap();
+    let path = dir.path().to_str().unwrap().to_string();
+    let address = start_server();
+
+    let clients: Vec<_> = (0..4)
+        .map(|_| {
+            let path = path.clone();
+            thread::spawn(move || {
+                let mut client = Client::connect(address);
+                let tools = client.request("tools/list", json!({}));
+                let names: Vec<&str> = tools["result"]["tools"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .filter_map(|tool| tool["name"].as_str())
+                    .collect();
+                assert!(names.contains(&"code_map"));
+
+                let response = client.request(
+                    "tools/call",
+                    json!({"name": "code_map", "arguments": {"path": path}}),
+                );
+                response["result"]["content"][0]["text"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+        })
+        .collect();
+
+    for client in clients {
+        let text = client.join().unwrap();
+        assert!(text.contains("Counter"));
+        assert!(text.contains("helper"));
+    }
+}
+
+#[test]
+fn test_cancelled_notification_stops_in_flight_tool_call() {
+    let dir = TempDir::new().unwrap();
+    for i in 0..2000 {
+        std::fs::write(
+            dir.path().join(format!("module_{i:04}.rs")),
+            format!("pub struct Type{i} {{\n    next: Option<Box<Type{i}>>,\n}}\n"),
+        )
+        .unwrap();
+    }
+    let mut client = Client::connect(start_server());
+
+    let call = json!({
+        "jsonrpc": "2.0",
+        "id": "scan",
+        "method": "tools/call",
+        "params": {"name": "type_map", "arguments": {"path": dir.path().to_str().unwrap()}},
+    });
+    client.send(0x1, call.to_string().as_bytes());
+    let cancel = json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/cancelled",
+        "params": {"requestId": "scan", "reason": "no longer needed"},
+    });
+    client.send(0x1, cancel.to_string().as_bytes());
+
+    let (_, payload) = client.receive();
+    let response: Value = serde_json::from_slice(&payload).unwrap();
+    assert_eq!(response["id"], "scan");
+    assert_eq!(response["result"]["isError"], true);
+    assert_eq!(response["result"]["content"][0]["text"], "cancelled");
+
+    // The connection keeps serving requests afterwards.
+    let tools = client.request("tools/list", json!({}));
+    assert!(tools["result"]["tools"].is_array());
+}
+
+#[test]
+fn test_streamed_tool_call_sends_a_progress_notification_per_line() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("a.rs"), "pub struct Alpha {}\n").unwrap();
+    std::fs::write(dir.path().join("b.rs"), "pub enum Beta {}\n").unwrap();
+    let mut client = Client::connect(start_server());
+
+    let message = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "type_map",
+            "arguments": {"path": dir.path().to_str().unwrap(), "stream": true},
+            "_meta": {"progressToken": "types-1"},
+        },
+    });
+    client.send(0x1, message.to_string().as_bytes());
+
+    let mut frames = Vec::new();
+    for _ in 0..4 {
+        let (opcode, payload) = client.receive();
+        assert_eq!(opcode, 0x1);
+        frames.push(serde_json::from_slice::<Value>(&payload).unwrap());
+    }
+
+    let lines: Vec<Value> = frames[..3]
+        .iter()
+        .map(|frame| {
+            assert_eq!(frame["method"], "notifications/progress");
+            assert_eq!(frame["params"]["progressToken"], "types-1");
+            serde_json::from_str(frame["params"]["message"].as_str().unwrap()).unwrap()
+        })
+        .collect();
+    assert_eq!(lines[0]["name"], "Alpha");
+    assert_eq!(lines[1]["name"], "Beta");
+    assert_eq!(lines[2], json!({"total_count": 2}));
+    assert_eq!(frames[3]["id"], 1);
+    assert_eq!(
+        frames[3]["result"]["content"][0]["text"],
+        "{\"total_count\":2}"
+    );
+}
+
+#[test]
+fn test_upgrade_from_unlisted_origin_is_refused() {
+    let address = start_server_with(WebSocketOptions {
+        allowed_origins: vec!["chrome-extension://trusted".to_string()],
+        ..Default::default()
+    });
+
+    assert_eq!(
+        upgrade_status(
+            address,
+            &upgrade_request("GET", "Origin: https://evil.example\r\n")
+        ),
+        "HTTP/1.1 403 Forbidden"
+    );
+    assert_eq!(
+        upgrade_status(
+            address,
+            &upgrade_request("GET", "Origin: chrome-extension://trusted\r\n")
+        ),
+        "HTTP/1.1 101 Switching Protocols"
+    );
+    // Clients outside a browser send no Origin at all.
+    assert_eq!(
+        upgrade_status(address, &upgrade_request("GET", "")),
+        "HTTP/1.1 101 Switching Protocols"
+    );
+}
+
+#[test]
+fn test_malformed_upgrade_requests_are_refused() {
+    let address = start_server();
+
+    assert_eq!(
+        upgrade_status(address, &upgrade_request("POST", "")),
+        "HTTP/1.1 405 Method Not Allowed"
+    );
+    assert_eq!(
+        upgrade_status(
+            address,
+            &format!("GET / HTTP/1.1\r\nSec-WebSocket-Key: {CLIENT_KEY}\r\n\r\n")
+        ),
+        "HTTP/1.1 400 Bad Request"
+    );
+
+    let many_headers = "X-Filler: 1\r\n".repeat(200);
+    assert_eq!(
+        upgrade_status(address, &upgrade_request("GET", &many_headers)),
+        "HTTP/1.1 431 Request Header Fields Too Large"
+    );
+    let long_header = format!("X-Filler: {}\r\n", "a".repeat(32 * 1024));
+    assert_eq!(
+        upgrade_status(address, &upgrade_request("GET", &long_header)),
+        "HTTP/1.1 431 Request Header Fields Too Large"
+    );
+}
+
+#[test]
+fn test_connections_and_calls_over_the_limit_are_refused() {
+    let address = start_server_with(WebSocketOptions {
+        max_connections: 1,
+        max_calls_per_connection: 0,
+        ..Default::default()
+    });
+    let mut client = Client::connect(address);
+
+    assert_eq!(
+        upgrade_status(address, &upgrade_request("GET", "")),
+        "HTTP/1.1 503 Service Unavailable"
+    );
+
+    let response = client.request(
+        "tools/call",
+        json!({"name": "code_map", "arguments": {"path": "."}}),
+    );
+    assert_eq!(response["error"]["code"], -32000);
+}